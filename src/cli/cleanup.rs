@@ -16,7 +16,7 @@ pub const LEINDEX_MARKER_FILE: &str = ".leindex-artifact-marker";
 pub const DEFAULT_MAX_AGE_DAYS: u64 = 7;
 
 /// Summary of a garbage-collection pass.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, serde::Serialize)]
 pub struct GcReport {
     /// Number of artifact directories scanned.
     pub scanned: usize,
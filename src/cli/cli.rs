@@ -2,16 +2,22 @@
 //
 // This module provides the command-line interface for LeIndex.
 
+use crate::cli::errors::{
+    cli_exit_code, render_cli_error, render_cli_error_json, CommandFailure, ErrorContext,
+    LeIndexError,
+};
 use crate::cli::leindex::LeIndex;
 use crate::cli::mcp::handlers::{all_tool_handlers, ToolHandler};
 use crate::cli::mcp::output::render_tool_output;
 use crate::cli::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
 use crate::cli::mcp::McpServer;
 use crate::cli::registry::{ProjectRegistry, DEFAULT_MAX_PROJECTS};
-use crate::phase::{run_phase_analysis, DocsMode, FormatMode, PhaseOptions, PhaseSelection};
+use crate::phase::{
+    run_phase_analysis, CancellationToken, DocsMode, FormatMode, PhaseOptions, PhaseSelection,
+};
 use anyhow::Context;
 use anyhow::Result as AnyhowResult;
-use clap::{error::ErrorKind, Parser, Subcommand};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use serde_json::{Map, Value};
 use std::fs;
 use std::net::SocketAddr;
@@ -54,11 +60,30 @@ pub struct Cli {
     #[arg(global = true, long = "memory-report", value_name = "PATH")]
     pub memory_report: Option<PathBuf>,
 
+    /// Output format for command results and errors
+    ///
+    /// With `json`, each invocation prints one JSON object to stdout:
+    /// `{"command", "ok": true, "result"}` on success and
+    /// `{"command", "ok": false, "error"}` on failure. Server, setup and
+    /// completion commands always print text.
+    #[arg(global = true, long = "output", value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// Output format selected with the global `--output` flag
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A single JSON object per invocation
+    Json,
+}
+
 /// Available CLI commands
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -85,18 +110,51 @@ pub enum Commands {
         /// OOM kills. No-op on non-Linux platforms if monitoring is unavailable.
         #[arg(long = "max-memory", value_name = "MB")]
         max_memory: Option<u64>,
+
+        /// Only index the listed languages (comma-separated, e.g. rust,python)
+        #[arg(long = "languages", value_name = "LANGS", value_delimiter = ',')]
+        languages: Vec<String>,
+
+        /// Number of parser threads (defaults to all cores)
+        #[arg(long = "jobs", short = 'j', value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Report which files would be parsed, kept and removed without indexing
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Index a git ref (branch, tag or commit) from the object database
+        /// instead of the working tree; stored as `<project>@<ref>`
+        #[arg(long = "git-ref", value_name = "REF", conflicts_with = "dry_run")]
+        git_ref: Option<String>,
     },
 
     /// Search indexed code
     #[command(visible_alias = "leindex_search")]
     Search {
         /// Search query
-        #[arg(value_name = "QUERY")]
-        query: String,
+        #[arg(value_name = "QUERY", required_unless_present = "saved")]
+        query: Option<String>,
 
         /// Maximum number of results to return
         #[arg(long = "top-k", default_value = "10")]
         top_k: usize,
+
+        /// Drop results scoring below this relevance (default 0.1)
+        #[arg(long, value_name = "SCORE")]
+        threshold: Option<f32>,
+
+        /// Keep every scored result, however weak
+        #[arg(long, conflicts_with = "threshold")]
+        no_threshold: bool,
+
+        /// Also save the query under this name
+        #[arg(long, value_name = "NAME")]
+        save: Option<String>,
+
+        /// Re-run the query saved under this name
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["query", "save"])]
+        saved: Option<String>,
     },
 
     /// Perform deep analysis with context expansion
@@ -109,6 +167,14 @@ pub enum Commands {
         /// Maximum tokens for context expansion
         #[arg(long = "tokens", default_value = "2000")]
         token_budget: usize,
+
+        /// Drop entry points scoring below this relevance (default 0.05)
+        #[arg(long, value_name = "SCORE")]
+        threshold: Option<f32>,
+
+        /// Keep every scored entry point, however weak
+        #[arg(long, conflicts_with = "threshold")]
+        no_threshold: bool,
     },
 
     /// Expand context around a symbol or node
@@ -158,6 +224,10 @@ pub enum Commands {
         #[arg(long = "max-chars", default_value = "12000")]
         max_output_chars: usize,
 
+        /// Maximum output tokens, counted by the project's configured tokenizer
+        #[arg(long = "max-tokens")]
+        max_output_tokens: Option<usize>,
+
         /// Explicitly opt in to Markdown/Text analysis
         #[arg(long = "include-docs", default_value_t = false)]
         include_docs: bool,
@@ -169,12 +239,58 @@ pub enum Commands {
         /// Disable incremental freshness checks (forces full refresh)
         #[arg(long = "no-incremental-refresh", default_value_t = false)]
         no_incremental_refresh: bool,
+
+        /// Fail when phase 1 average parser completeness is below this score (0..1)
+        #[arg(long = "fail-under")]
+        fail_under: Option<f32>,
+
+        /// Fail when phase 1 parse failure ratio exceeds this value (0..1)
+        #[arg(long = "max-parse-failure-ratio")]
+        max_parse_failure_ratio: Option<f32>,
     },
 
     /// Show system diagnostics
     #[command(visible_alias = "leindex_diagnostics")]
     Diagnostics,
 
+    /// Show parse errors recorded during indexing
+    ///
+    /// Files that failed to parse are always listed; syntax errors in files
+    /// that did parse require `record_parse_errors = true` under `[indexing]`
+    /// in `.leindex/config.toml` when the project was indexed.
+    Errors,
+
+    /// List recent and saved searches
+    ///
+    /// Searches are saved with `leindex search QUERY --save NAME` and re-run
+    /// with `leindex search --saved NAME`.
+    History {
+        /// Number of recent searches to list
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Delete the saved search with this name instead of listing
+        #[arg(long, value_name = "NAME")]
+        forget: Option<String>,
+    },
+
+    /// Check the environment and index health of the project
+    ///
+    /// Verifies that storage opens and is consistent, that the grammars of
+    /// the project's languages load, and reports disk usage. Exits nonzero
+    /// if any check fails.
+    Doctor,
+
+    /// Generate a shell completion script
+    ///
+    /// Prints the script to stdout, e.g.
+    /// `leindex completions bash > ~/.local/share/bash-completion/completions/leindex`.
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
     /// List, inspect, or run the MCP tool surface directly from the CLI
     #[command(disable_help_subcommand = true)]
     Tools {
@@ -228,6 +344,11 @@ pub enum Commands {
         /// Show what would be removed without actually removing
         #[arg(long = "dry-run")]
         dry_run: bool,
+
+        /// Also remove orphaned nodes, dangling edges and stale global
+        /// symbols from the project database (see --project)
+        #[arg(long = "storage")]
+        storage: bool,
     },
 
     /// Configure neural search: install ORT, set up models, and write config
@@ -291,7 +412,39 @@ pub enum ToolCommands {
     },
 }
 
+impl Commands {
+    /// Subcommand name, as reported under `--output json`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Index { .. } => "index",
+            Commands::Search { .. } => "search",
+            Commands::Analyze { .. } => "analyze",
+            Commands::Context { .. } => "context",
+            Commands::Phase { .. } => "phase",
+            Commands::Diagnostics => "diagnostics",
+            Commands::Errors => "errors",
+            Commands::History { .. } => "history",
+            Commands::Doctor => "doctor",
+            Commands::Completions { .. } => "completions",
+            Commands::Tools { .. } => "tools",
+            Commands::Serve { .. } => "serve",
+            Commands::Mcp { .. } => "mcp",
+            Commands::Dashboard { .. } => "dashboard",
+            Commands::Cleanup { .. } => "cleanup",
+            Commands::Setup { .. } => "setup",
+        }
+    }
+}
+
 impl Cli {
+    /// Name of the command this invocation runs (`mcp` when none is given)
+    pub fn command_name(&self) -> &'static str {
+        match &self.command {
+            Some(command) if !self.stdio => command.name(),
+            _ => "mcp",
+        }
+    }
+
     /// Run the CLI
     pub async fn run(self) -> AnyhowResult<()> {
         // Initialize logging
@@ -311,6 +464,7 @@ impl Cli {
 
         // Get global project path
         let global_project = self.project_path;
+        let output = self.output;
 
         // Execute the appropriate command
         // Default to Mcp if no command is provided or if --stdio is set
@@ -334,18 +488,58 @@ impl Cli {
                 force,
                 progress,
                 max_memory,
-            } => cmd_index_impl(path, force, progress, max_memory).await,
-            Commands::Search { query, top_k } => {
-                cmd_search_impl(query, top_k, global_project).await
+                languages,
+                jobs,
+                dry_run,
+                git_ref,
+            } => match git_ref {
+                Some(git_ref) => cmd_index_git_ref_impl(path, git_ref, output).await,
+                None => {
+                    cmd_index_impl(
+                        path, force, progress, max_memory, languages, jobs, dry_run, output,
+                    )
+                    .await
+                }
+            },
+            Commands::Search {
+                query,
+                top_k,
+                threshold,
+                no_threshold,
+                save,
+                saved,
+            } => {
+                let threshold = resolve_threshold(
+                    threshold,
+                    no_threshold,
+                    crate::cli::leindex::DEFAULT_SEARCH_THRESHOLD,
+                );
+                let query = match saved {
+                    Some(name) => SearchInput::Saved(name),
+                    None => SearchInput::Text {
+                        query: query.unwrap_or_default(),
+                        save,
+                    },
+                };
+                cmd_search_impl(query, top_k, threshold, global_project, output).await
             }
             Commands::Analyze {
                 query,
                 token_budget,
-            } => cmd_analyze_impl(query, token_budget, global_project).await,
+                threshold,
+                no_threshold,
+            } => {
+                let threshold = resolve_threshold(
+                    threshold,
+                    no_threshold,
+                    crate::cli::leindex::DEFAULT_ANALYSIS_THRESHOLD,
+                );
+                cmd_analyze_impl(query, token_budget, threshold, global_project, output).await
+            }
             Commands::Context {
                 node_id,
                 token_budget,
-            } => cmd_context_impl(node_id, token_budget, global_project).await,
+            } => cmd_context_impl(node_id, token_budget, global_project, output).await,
             Commands::Phase {
                 phase,
                 all,
@@ -355,9 +549,12 @@ impl Cli {
                 max_focus_files,
                 top_n,
                 max_output_chars,
+                max_output_tokens,
                 include_docs,
                 docs_mode,
                 no_incremental_refresh,
+                fail_under,
+                max_parse_failure_ratio,
             } => {
                 cmd_phase_impl(
                     phase,
@@ -369,14 +566,27 @@ impl Cli {
                     max_focus_files,
                     top_n,
                     max_output_chars,
+                    max_output_tokens,
                     include_docs,
                     docs_mode,
                     no_incremental_refresh,
+                    fail_under,
+                    max_parse_failure_ratio,
+                    output,
                 )
                 .await
             }
-            Commands::Diagnostics => cmd_diagnostics_impl(global_project).await,
-            Commands::Tools { command } => cmd_tools_impl(command, global_project).await,
+            Commands::Diagnostics => cmd_diagnostics_impl(global_project, output).await,
+            Commands::Errors => cmd_errors_impl(global_project, output).await,
+            Commands::History { limit, forget } => {
+                cmd_history_impl(limit, forget, global_project, output).await
+            }
+            Commands::Doctor => cmd_doctor_impl(global_project, output).await,
+            Commands::Completions { shell } => {
+                write_completions(shell, &mut std::io::stdout());
+                Ok(())
+            }
+            Commands::Tools { command } => cmd_tools_impl(command, global_project, output).await,
             Commands::Serve { host, port } => cmd_serve_impl(host, port).await,
             Commands::Mcp { socket, .. } => {
                 if let Some(ref socket_path) = socket {
@@ -389,7 +599,16 @@ impl Cli {
             Commands::Cleanup {
                 max_age_days,
                 dry_run,
-            } => cmd_cleanup_impl(max_age_days, dry_run).await,
+                storage,
+            } => {
+                cmd_cleanup_impl(
+                    max_age_days,
+                    dry_run,
+                    storage.then_some(global_project),
+                    output,
+                )
+                .await
+            }
             Commands::Setup {
                 neural,
                 no_neural,
@@ -433,7 +652,7 @@ fn init_logging_impl(verbose: bool) {
 
 fn maybe_complete_post_install_actions(command: &Commands) {
     if std::env::var_os(POST_INSTALL_SKIP_ENV).is_some()
-        || matches!(command, Commands::Mcp { .. })
+        || matches!(command, Commands::Mcp { .. } | Commands::Completions { .. })
         || !running_from_cargo_bin()
     {
         return;
@@ -659,15 +878,26 @@ fn get_project_path(explicit: Option<PathBuf>) -> PathBuf {
 }
 
 /// Index command implementation
+#[allow(clippy::too_many_arguments)]
 async fn cmd_index_impl(
     path: PathBuf,
     force: bool,
     _progress: bool,
     max_memory: Option<u64>,
+    languages: Vec<String>,
+    jobs: Option<usize>,
+    dry_run: bool,
+    output: OutputFormat,
 ) -> AnyhowResult<()> {
-    let canonical_path = path
-        .canonicalize()
-        .context("Failed to canonicalize project path")?;
+    let canonical_path = path.canonicalize().map_err(|source| {
+        ErrorContext::new("index")
+            .with_project_path(&path)
+            .with_error(LeIndexError::Io {
+                context: "Failed to canonicalize project path".to_string(),
+                path: Some(path.clone()),
+                source,
+            })
+    })?;
 
     info!("Indexing project at: {}", canonical_path.display());
 
@@ -679,9 +909,46 @@ async fn cmd_index_impl(
     // Create a single LeIndex instance and reuse it for both the staleness
     // check and the indexing operation (VAL-QUALITY-015).
     let mut leindex = LeIndex::new(&canonical_path).context("Failed to create LeIndex instance")?;
+    leindex.set_language_filter(&languages).map_err(|err| {
+        ErrorContext::new("index")
+            .with_project_path(&canonical_path)
+            .with_error(LeIndexError::config_error(
+                err.to_string(),
+                Some("Pass a comma-separated list such as --languages rust,python.".to_string()),
+            ))
+    })?;
+    if let Some(jobs) = jobs {
+        leindex.set_parse_threads(jobs).map_err(|err| {
+            ErrorContext::new("index")
+                .with_project_path(&canonical_path)
+                .with_error(LeIndexError::config_error(
+                    format!("{:#}", err),
+                    Some("Pass a smaller --jobs value.".to_string()),
+                ))
+        })?;
+    }
+
+    if dry_run {
+        let plan = leindex.plan_index(force).map_err(|err| {
+            ErrorContext::new("index")
+                .with_project_path(&canonical_path)
+                .with_error(LeIndexError::index_error(format!("{:#}", err), true))
+        })?;
+        if output == OutputFormat::Json {
+            return print_json_result(
+                "index",
+                serde_json::json!({ "status": "dry_run", "plan": plan }),
+            );
+        }
+        print_index_plan(&plan, &canonical_path);
+        return Ok(());
+    }
 
     // Check if already indexed (unless force)
     if !force && leindex.is_indexed() && !leindex.is_stale_fast() {
+        if output == OutputFormat::Json {
+            return print_json_result("index", serde_json::json!({ "status": "up_to_date" }));
+        }
         println!("Project already indexed and up-to-date. Use --force to re-index.");
         return Ok(());
     }
@@ -689,15 +956,103 @@ async fn cmd_index_impl(
     // (VAL-INDEX-005). If not indexed at all, fall through to full index.
 
     let max_memory_bytes = max_memory.map(|mb| mb * 1024 * 1024);
+    let cancel = CancellationToken::new();
+    let ctrl_c = cancel_on_ctrl_c(&cancel);
     let stats = tokio::task::spawn_blocking(move || {
-        leindex.index_project_with_memory_cap(force, max_memory_bytes)
+        leindex.index_project_with_cancellation(force, max_memory_bytes, &cancel)
+    })
+    .await;
+    ctrl_c.abort();
+    let stats = stats.context("Indexing task failed")?.map_err(|err| {
+        ErrorContext::new("index")
+            .with_project_path(&canonical_path)
+            .with_error(LeIndexError::index_error(format!("{:#}", err), true))
+    })?;
+
+    if output == OutputFormat::Json {
+        let status = if stats.cancelled_files > 0 {
+            "cancelled"
+        } else {
+            "indexed"
+        };
+        return print_json_result(
+            "index",
+            serde_json::json!({ "status": status, "stats": stats }),
+        );
+    }
+    print_index_stats(&stats);
+
+    Ok(())
+}
+
+/// Index command implementation for `--git-ref`
+async fn cmd_index_git_ref_impl(
+    path: PathBuf,
+    git_ref: String,
+    output: OutputFormat,
+) -> AnyhowResult<()> {
+    info!("Indexing git ref '{}' of {}", git_ref, path.display());
+    let leindex = tokio::task::spawn_blocking({
+        let path = path.clone();
+        let git_ref = git_ref.clone();
+        move || LeIndex::index_git_ref(&path, &git_ref)
     })
     .await
     .context("Indexing task failed")?
-    .context("Indexing failed")?;
+    .map_err(|err| {
+        ErrorContext::new("index")
+            .with_project_path(&path)
+            .with_error(LeIndexError::index_error(format!("{:#}", err), true))
+    })?;
+
+    if output == OutputFormat::Json {
+        return print_json_result(
+            "index",
+            serde_json::json!({
+                "status": "indexed",
+                "stats": leindex.get_stats(),
+                "project_id": leindex.project_id(),
+            }),
+        );
+    }
+    print_index_stats(leindex.get_stats());
+    println!("  Stored as: {}", leindex.project_id());
+    Ok(())
+}
+
+/// Cancel `cancel` on the first Ctrl-C so long operations can stop cleanly
+///
+/// A second Ctrl-C exits immediately. Abort the returned handle once the
+/// operation is done so a later Ctrl-C no longer cancels `cancel`. Tokio
+/// keeps its signal handler installed for the rest of the process, so after
+/// that Ctrl-C is ignored rather than terminating; only call this from
+/// commands that exit when the operation finishes.
+fn cancel_on_ctrl_c(cancel: &CancellationToken) -> tokio::task::JoinHandle<()> {
+    let cancel = cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        eprintln!(
+            "Cancelling: finishing files in progress and saving (press Ctrl-C again to abort)..."
+        );
+        cancel.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    })
+}
 
-    // Print results
-    println!("\n✓ Indexing complete!");
+/// Print the summary of a completed indexing run
+fn print_index_stats(stats: &crate::cli::leindex::IndexStats) {
+    if stats.cancelled_files > 0 {
+        println!(
+            "\nIndexing cancelled: {} file(s) not parsed yet; run `leindex index` again to finish.",
+            stats.cancelled_files
+        );
+    } else {
+        println!("\n✓ Indexing complete!");
+    }
     println!("  Files parsed: {}", stats.files_parsed);
     println!("  Successful: {}", stats.successful_parses);
     println!("  Failed: {}", stats.failed_parses);
@@ -706,16 +1061,149 @@ async fn cmd_index_impl(
     println!("  PDG edges: {}", stats.pdg_edges);
     println!("  Indexed nodes: {}", stats.indexed_nodes);
     println!("  Time: {}ms", stats.indexing_time_ms);
+    if !stats.slowest_files.is_empty() {
+        println!("  Slowest files:");
+        for timing in stats.slowest_files.iter().take(5) {
+            println!(
+                "    {:>8.1}ms  {}",
+                timing.parse_duration_ms, timing.file_path
+            );
+        }
+    }
+}
 
-    Ok(())
+/// Print an index plan with paths shown relative to the project root
+fn print_index_plan(plan: &crate::cli::leindex::IndexPlan, project_path: &std::path::Path) {
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(project_path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+
+    println!(
+        "Index plan{} (dry run, nothing written):",
+        if plan.force { " (forced)" } else { "" }
+    );
+    println!("  To parse: {}", plan.to_parse.len());
+    println!("  Unchanged: {}", plan.unchanged.len());
+    println!("  Deleted: {}", plan.deleted.len());
+    println!("  Renamed: {}", plan.renamed.len());
+    if !plan.to_parse.is_empty() {
+        println!("\nFiles to parse:");
+        for path in &plan.to_parse {
+            println!("  + {}", relative(path));
+        }
+    }
+    if !plan.deleted.is_empty() {
+        println!("\nFiles to remove from the index:");
+        for path in &plan.deleted {
+            println!("  - {}", relative(std::path::Path::new(path)));
+        }
+    }
+    if !plan.renamed.is_empty() {
+        println!("\nFiles renamed (stored nodes kept):");
+        for (old_path, new_path) in &plan.renamed {
+            println!(
+                "  ~ {} -> {}",
+                relative(std::path::Path::new(old_path)),
+                relative(new_path)
+            );
+        }
+    }
+    if plan.is_noop() {
+        println!("\nNo source changes; the index is up to date.");
+    }
+}
+
+/// Combine `--threshold`/`--no-threshold` into the relevance cutoff to apply
+fn resolve_threshold(threshold: Option<f32>, no_threshold: bool, default: f32) -> Option<f32> {
+    if no_threshold {
+        None
+    } else {
+        Some(threshold.unwrap_or(default))
+    }
+}
+
+/// What `leindex search` runs
+enum SearchInput {
+    /// A query given on the command line, optionally saved under a name
+    Text { query: String, save: Option<String> },
+    /// The query saved under a name
+    Saved(String),
 }
 
 /// Search command implementation
 async fn cmd_search_impl(
-    query: String,
+    query: SearchInput,
     top_k: usize,
+    threshold: Option<f32>,
     project: Option<PathBuf>,
+    output: OutputFormat,
 ) -> AnyhowResult<()> {
+    let project_path = get_project_path(project);
+    let query = match query {
+        SearchInput::Text { query, save } => {
+            if let Some(name) = save {
+                let mut leindex =
+                    LeIndex::new(&project_path).context("Failed to create LeIndex instance")?;
+                leindex.save_query(&name, &query)?;
+            }
+            query
+        }
+        SearchInput::Saved(name) => {
+            let leindex =
+                LeIndex::new(&project_path).context("Failed to create LeIndex instance")?;
+            match leindex.saved_query(&name)? {
+                Some(saved) => saved.query,
+                None => {
+                    return Err(ErrorContext::new("search")
+                        .with_project_path(&project_path)
+                        .with_error(LeIndexError::config_error(
+                            format!("No saved search named '{}'", name),
+                            Some("Run `leindex history` to list saved searches.".to_string()),
+                        ))
+                        .into());
+                }
+            }
+        }
+    };
+    let results_json = search_results_json(&query, top_k, threshold, Some(project_path)).await?;
+
+    if output == OutputFormat::Json {
+        return print_json_result(
+            "search",
+            serde_json::json!({ "query": &query, "results": results_json }),
+        );
+    }
+
+    if results_json.is_empty() {
+        println!("No results found for: {}", query);
+        return Ok(());
+    }
+
+    // Use the unified CLI renderer so the search output matches the
+    // shape of `leindex tools run leindex.search`.
+    println!(
+        "{}",
+        render_tool_output(
+            "leindex.search",
+            &serde_json::json!(results_json),
+            &serde_json::json!({ "query": &query }),
+        )
+    );
+
+    Ok(())
+}
+
+/// Run a search and convert the results to the JSON shape shared by the
+/// text renderer and `--output json`
+async fn search_results_json(
+    query: &str,
+    top_k: usize,
+    threshold: Option<f32>,
+    project: Option<PathBuf>,
+) -> AnyhowResult<Vec<Value>> {
     let project_path = get_project_path(project);
     let canonical_path = project_path
         .canonicalize()
@@ -734,16 +1222,14 @@ async fn cmd_search_impl(
 
     // Perform search
     let results = leindex
-        .search(&query, top_k, None)
+        .search_with_threshold(query, top_k, None, threshold)
         .context("Search failed")?;
-
-    if results.is_empty() {
-        println!("No results found for: {}", query);
-        return Ok(());
+    if let Err(e) = leindex.record_query(query) {
+        warn!("{:#}", e);
     }
 
     // Convert results to JSON value for formatter
-    let results_json: Vec<Value> = results
+    Ok(results
         .iter()
         .map(|r| {
             serde_json::json!({
@@ -760,27 +1246,16 @@ async fn cmd_search_impl(
                 "language": r.language,
             })
         })
-        .collect();
-
-    // Use the unified CLI renderer so the search output matches the
-    // shape of `leindex tools run leindex.search`.
-    println!(
-        "{}",
-        render_tool_output(
-            "leindex.search",
-            &serde_json::json!(results_json),
-            &serde_json::json!({ "query": &query }),
-        )
-    );
-
-    Ok(())
+        .collect())
 }
 
 /// Analyze command implementation
 async fn cmd_analyze_impl(
     query: String,
     token_budget: usize,
+    threshold: Option<f32>,
     project: Option<PathBuf>,
+    output: OutputFormat,
 ) -> AnyhowResult<()> {
     let project_path = get_project_path(project);
     let canonical_path = project_path
@@ -792,20 +1267,33 @@ async fn cmd_analyze_impl(
     // Create LeIndex and try to load from storage
     let mut leindex = LeIndex::new(&canonical_path).context("Failed to create LeIndex instance")?;
 
-    // Load from storage if available
-    if let Err(e) = leindex.load_from_storage() {
-        warn!("Failed to load from storage: {}", e);
-        warn!("Project may not be indexed. Run 'leindex index' first.");
-    }
+    let cancel = CancellationToken::new();
+    let ctrl_c = cancel_on_ctrl_c(&cancel);
+    let analysis = tokio::task::spawn_blocking({
+        let query = query.clone();
+        move || {
+            // Load from storage if available
+            if let Err(e) = leindex.load_from_storage() {
+                warn!("Failed to load from storage: {}", e);
+                warn!("Project may not be indexed. Run 'leindex index' first.");
+            }
+
+            // Perform analysis
+            leindex
+                .analyze_with_cancellation(&query, token_budget, threshold, &cancel)
+                .context("Analysis failed")
+        }
+    })
+    .await;
+    ctrl_c.abort();
+    let result = analysis.context("Analysis task failed")??;
 
-    // Perform analysis
-    let result = leindex
-        .analyze(&query, token_budget)
-        .context("Analysis failed")?;
+    if output == OutputFormat::Json {
+        return print_json_result("analyze", serde_json::to_value(&result)?);
+    }
 
     // Print results with nice formatting
-    let output = format_analysis_output(&query, &result);
-    println!("{}", output);
+    println!("{}", format_analysis_output(&query, &result));
 
     Ok(())
 }
@@ -818,6 +1306,9 @@ fn format_analysis_output(query: &str, result: &crate::cli::leindex::AnalysisRes
         "{}┌─ Analysis: {} ─┐{}\n",
         LIGHT_CYAN, query, RESET
     ));
+    if result.cancelled {
+        out.push_str("  Analysis cancelled: showing the entry points found so far.\n");
+    }
     out.push_str(&format!(
         "  {}Found:{} {} entry point(s)\n",
         BOLD,
@@ -849,6 +1340,7 @@ async fn cmd_context_impl(
     node_id: String,
     token_budget: usize,
     project: Option<PathBuf>,
+    output: OutputFormat,
 ) -> AnyhowResult<()> {
     let args = merge_tool_args(
         serde_json::json!({
@@ -861,6 +1353,10 @@ async fn cmd_context_impl(
 
     let value = execute_tool_handler("leindex_context", args, project).await?;
 
+    if output == OutputFormat::Json {
+        return print_json_result("context", value);
+    }
+
     println!(
         "{}",
         render_tool_output(
@@ -885,9 +1381,13 @@ async fn cmd_phase_impl(
     max_focus_files: usize,
     top_n: usize,
     max_output_chars: usize,
+    max_output_tokens: Option<usize>,
     include_docs: bool,
     docs_mode: String,
     no_incremental_refresh: bool,
+    fail_under: Option<f32>,
+    max_parse_failure_ratio: Option<f32>,
+    output: OutputFormat,
 ) -> AnyhowResult<()> {
     if !all && phase.is_none() {
         anyhow::bail!("Specify either --phase <1..5> or --all");
@@ -932,6 +1432,17 @@ async fn cmd_phase_impl(
             .ok_or_else(|| anyhow::anyhow!("Invalid phase '{}'. Use 1..5", p))?
     };
 
+    if (fail_under.is_some() || max_parse_failure_ratio.is_some()) && !selection.includes(1) {
+        anyhow::bail!(
+            "--fail-under and --max-parse-failure-ratio check phase 1; use --phase 1 or --all"
+        );
+    }
+
+    let tokenizer = crate::cli::config::ProjectConfig::load(&root)
+        .unwrap_or_default()
+        .tokens
+        .tokenizer;
+
     let options = PhaseOptions {
         root,
         focus_files,
@@ -940,22 +1451,60 @@ async fn cmd_phase_impl(
         max_focus_files,
         top_n,
         max_output_chars,
+        max_output_tokens,
+        tokenizer,
         use_incremental_refresh: !no_incremental_refresh,
         include_docs,
         docs_mode: parsed_docs_mode,
         hotspot_keywords: PhaseOptions::default().hotspot_keywords,
+        min_parser_completeness: fail_under,
+        max_parse_failure_ratio,
     };
 
     let report = tokio::task::spawn_blocking(move || run_phase_analysis(options, selection))
         .await
         .context("Phase task failed")??;
 
-    println!("{}", report.formatted_output);
+    let threshold_failure = report.phase1.as_ref().filter(|p1| !p1.meets_threshold);
+    match output {
+        // A failed threshold is reported as the error document instead
+        OutputFormat::Json if threshold_failure.is_none() => {
+            print_json_result("phase", serde_json::to_value(&report)?)?
+        }
+        OutputFormat::Json => {}
+        OutputFormat::Text => println!("{}", report.formatted_output),
+    }
+
+    if let Some(p1) = threshold_failure {
+        anyhow::bail!(
+            "Phase 1 thresholds not met: parser_completeness_avg={:.2} parse_failure_ratio={:.2}",
+            p1.average_completeness(),
+            p1.parse_failure_ratio()
+        );
+    }
     Ok(())
 }
 
 /// Diagnostics command implementation
-async fn cmd_diagnostics_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
+async fn cmd_diagnostics_impl(project: Option<PathBuf>, output: OutputFormat) -> AnyhowResult<()> {
+    let diag_json = diagnostics_json(project).await?;
+
+    if output == OutputFormat::Json {
+        return print_json_result("diagnostics", diag_json);
+    }
+
+    // Use the unified CLI renderer so `leindex diagnostics` and
+    // `leindex tools run leindex.diagnostics` produce identical output.
+    println!(
+        "{}",
+        render_tool_output("leindex.diagnostics", &diag_json, &serde_json::json!({}))
+    );
+
+    Ok(())
+}
+
+/// Collect the diagnostics shared by the text renderer and `--output json`
+async fn diagnostics_json(project: Option<PathBuf>) -> AnyhowResult<Value> {
     let project_path = get_project_path(project);
     let canonical_path = project_path
         .canonicalize()
@@ -1068,7 +1617,7 @@ async fn cmd_diagnostics_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
     let (ort_path, ort_version, execution_provider) = collect_ort_diagnostics();
 
     // Convert to JSON for formatter
-    let diag_json = serde_json::json!({
+    Ok(serde_json::json!({
         "project_path": diag.project_path,
         "indexed_files": indexed_ct,
         "index_size_mb": diag.memory_usage_bytes as f64 / 1024.0 / 1024.0,
@@ -1080,15 +1629,156 @@ async fn cmd_diagnostics_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
         "ort_version": ort_version,
         "execution_provider": execution_provider,
         "issues": issues,
-    });
+    }))
+}
 
-    // Use the unified CLI renderer so `leindex diagnostics` and
-    // `leindex tools run leindex.diagnostics` produce identical output.
+/// Write the completion script for `shell` covering every command and flag
+fn write_completions(shell: clap_complete::Shell, out: &mut dyn std::io::Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Doctor command implementation
+async fn cmd_doctor_impl(project: Option<PathBuf>, output: OutputFormat) -> AnyhowResult<()> {
+    let project_path = get_project_path(project);
+    let report = crate::cli::doctor::run_doctor(&project_path);
+
+    let failed: Vec<String> = report
+        .failures()
+        .map(|check| format!("{}: {}", check.name, check.detail))
+        .collect();
+    if !failed.is_empty() {
+        if output == OutputFormat::Text {
+            print!("{}", report);
+        }
+        return Err(CommandFailure {
+            error: LeIndexError::validation_error(
+                format!(
+                    "{} of {} doctor check(s) failed ({})",
+                    failed.len(),
+                    report.checks.len(),
+                    failed.join("; ")
+                ),
+                None,
+            ),
+            result: serde_json::to_value(&report)?,
+        }
+        .into());
+    }
+
+    match output {
+        OutputFormat::Json => print_json_result("doctor", serde_json::to_value(&report)?)?,
+        OutputFormat::Text => {
+            print!("{}", report);
+            println!("All {} checks passed.", report.checks.len());
+        }
+    }
+    Ok(())
+}
+
+/// History command implementation
+async fn cmd_history_impl(
+    limit: usize,
+    forget: Option<String>,
+    project: Option<PathBuf>,
+    output: OutputFormat,
+) -> AnyhowResult<()> {
+    let project_path = get_project_path(project);
+    let mut leindex = LeIndex::new(&project_path).context("Failed to create LeIndex instance")?;
+
+    if let Some(name) = forget {
+        let forgotten = leindex.forget_saved_query(&name)?;
+        if output == OutputFormat::Json {
+            return print_json_result(
+                "history",
+                serde_json::json!({ "forgotten": name, "existed": forgotten }),
+            );
+        }
+        if forgotten {
+            println!("Forgot saved search '{}'.", name);
+        } else {
+            println!("No saved search named '{}'.", name);
+        }
+        return Ok(());
+    }
+
+    let recent = leindex.recent_queries(limit)?;
+    let saved = leindex.saved_queries()?;
+    if output == OutputFormat::Json {
+        return print_json_result(
+            "history",
+            serde_json::json!({ "recent": recent, "saved": saved }),
+        );
+    }
+
+    if recent.is_empty() {
+        println!("No recent searches.");
+    } else {
+        println!("Recent searches:");
+        for entry in &recent {
+            println!("  {}", entry.query);
+        }
+    }
+    if !saved.is_empty() {
+        println!("Saved searches (run with `leindex search --saved NAME`):");
+        let width = saved.iter().map(|q| q.name.len()).max().unwrap_or(0);
+        for query in &saved {
+            println!("  {:<width$}  {}", query.name, query.query, width = width);
+        }
+    }
+    Ok(())
+}
+
+/// Errors command implementation
+async fn cmd_errors_impl(project: Option<PathBuf>, output: OutputFormat) -> AnyhowResult<()> {
+    let project_path = get_project_path(project);
+    let leindex = LeIndex::new(&project_path).context("Failed to create LeIndex instance")?;
+    let errors = leindex.parse_errors()?;
+
+    if output == OutputFormat::Json {
+        return print_json_result("errors", serde_json::json!({ "errors": errors }));
+    }
+
+    if errors.is_empty() {
+        let recording = crate::cli::config::ProjectConfig::load(leindex.project_path())
+            .unwrap_or_default()
+            .indexing
+            .record_parse_errors;
+        if recording {
+            println!("No parse errors recorded.");
+        } else {
+            println!(
+                "No parse errors recorded. Set `record_parse_errors = true` under [indexing] \
+                 in .leindex/config.toml and reindex to also record syntax errors."
+            );
+        }
+        return Ok(());
+    }
+
+    let files: std::collections::BTreeSet<&str> =
+        errors.iter().map(|e| e.file_path.as_str()).collect();
     println!(
-        "{}",
-        render_tool_output("leindex.diagnostics", &diag_json, &serde_json::json!({}))
+        "{} parse error(s) in {} file(s):",
+        errors.len(),
+        files.len()
     );
-
+    for error in &errors {
+        let path = std::path::Path::new(&error.file_path);
+        let path = path.strip_prefix(leindex.project_path()).unwrap_or(path);
+        match (error.line, error.column) {
+            (Some(line), Some(column)) => {
+                println!(
+                    "  {}:{}:{}: {}",
+                    path.display(),
+                    line,
+                    column,
+                    error.message
+                )
+            }
+            _ => println!("  {}: {}", path.display(), error.message),
+        }
+    }
     Ok(())
 }
 
@@ -1146,7 +1836,11 @@ pub(crate) fn collect_ort_diagnostics() -> (Option<String>, Option<String>, Stri
     (ort_path, ort_version, execution_provider)
 }
 
-async fn cmd_tools_impl(command: ToolCommands, project: Option<PathBuf>) -> AnyhowResult<()> {
+async fn cmd_tools_impl(
+    command: ToolCommands,
+    project: Option<PathBuf>,
+    output: OutputFormat,
+) -> AnyhowResult<()> {
     match command {
         ToolCommands::List => {
             // Display as `LeIndex [Tool Name]  description` so the user sees the
@@ -1154,6 +1848,19 @@ async fn cmd_tools_impl(command: ToolCommands, project: Option<PathBuf>) -> Anyh
             // the canonical dotted name available via `leindex tools help`.
             let mut handlers = all_tool_handlers();
             handlers.sort_by(|a, b| a.title().cmp(b.title()));
+            if output == OutputFormat::Json {
+                let tools: Vec<Value> = handlers
+                    .iter()
+                    .map(|handler| {
+                        serde_json::json!({
+                            "name": handler.name(),
+                            "title": handler.title(),
+                            "description": handler.description(),
+                        })
+                    })
+                    .collect();
+                return print_json_result("tools", serde_json::json!({ "tools": tools }));
+            }
             for handler in handlers {
                 println!("{}\t{}", handler.title(), handler.description());
             }
@@ -1162,14 +1869,27 @@ async fn cmd_tools_impl(command: ToolCommands, project: Option<PathBuf>) -> Anyh
         ToolCommands::Help { name } => {
             let handler = find_tool_handler(&name)
                 .ok_or_else(|| anyhow::anyhow!("Unknown tool '{}'", name))?;
+            if output == OutputFormat::Json {
+                return print_json_result(
+                    "tools",
+                    serde_json::json!({
+                        "name": handler.name(),
+                        "title": handler.title(),
+                        "description": handler.description(),
+                        "schema": handler.argument_schema(),
+                    }),
+                );
+            }
             print_tool_help(&handler);
             Ok(())
         }
         ToolCommands::Schema { name } => {
             let handler = find_tool_handler(&name)
                 .ok_or_else(|| anyhow::anyhow!("Unknown tool '{}'", name))?;
-            print_json_value(&handler.argument_schema())?;
-            Ok(())
+            match output {
+                OutputFormat::Json => print_json_result("tools", handler.argument_schema()),
+                OutputFormat::Text => print_json_value(&handler.argument_schema()),
+            }
         }
         ToolCommands::Run {
             name,
@@ -1179,6 +1899,9 @@ async fn cmd_tools_impl(command: ToolCommands, project: Option<PathBuf>) -> Anyh
             let parsed_args = parse_tool_args_json(&args_json)?;
             let args = merge_tool_args(parsed_args.clone(), &set, project.as_ref())?;
             let value = execute_tool_handler(&name, args, project).await?;
+            if output == OutputFormat::Json {
+                return print_json_result("tools", value);
+            }
 
             // Use the unified renderer — same path used by the MCP transport
             // so CLI and LLM-visible payloads stay in lock-step.
@@ -1258,8 +1981,10 @@ async fn cmd_serve_impl(host: String, port: u16) -> AnyhowResult<()> {
 /// (no SQLite open, no PDG load, no TF-IDF rebuild, no file watcher at startup).
 /// Projects are loaded lazily on first tool call via `ProjectRegistry::get_or_load()`.
 async fn cmd_mcp_stdio_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
-    use crate::cli::mcp::protocol::{JsonRpcError, JsonRpcMessage, JsonRpcResponse};
+    use crate::cli::mcp::protocol::{JsonRpcError, JsonRpcMessage, JsonRpcResponse, RequestLimits};
+    use crate::cli::mcp::server::{generate_request_id, request_span};
     use std::io::{self, BufRead, Read, Write};
+    use tracing::Instrument;
 
     info!("Starting LeIndex MCP stdio server (lazy project loading)");
 
@@ -1316,6 +2041,7 @@ async fn cmd_mcp_stdio_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
         info!("Default project path set to: {}", resolved_path.display());
     }
 
+    let limits = RequestLimits::default();
     let stdin = io::stdin();
     let mut reader = io::BufReader::new(stdin.lock());
     let mut stdout = io::stdout().lock();
@@ -1400,11 +2126,14 @@ async fn cmd_mcp_stdio_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
 
         use_content_length = use_content_length || framed;
 
+        let trace_id = generate_request_id();
+
         // Parse JSON-RPC message (request or notification)
-        let message = match JsonRpcMessage::from_json(&json_payload) {
+        let message = match JsonRpcMessage::from_json_with_limits(&json_payload, &limits) {
             Ok(m) => m,
             Err(e) => {
-                let error_response = JsonRpcResponse::error(serde_json::Value::Null, e);
+                let error_response =
+                    JsonRpcResponse::error(serde_json::Value::Null, e).with_request_id(&trace_id);
                 let response = serde_json::to_string(&error_response).unwrap_or_default();
                 if use_content_length {
                     let _ = writeln!(
@@ -1427,7 +2156,12 @@ async fn cmd_mcp_stdio_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
             JsonRpcMessage::Request(request) => {
                 let request_id = request.id.clone().unwrap_or(serde_json::Value::Null);
 
-                let response = match handle_mcp_request(request, PathBuf::new()).await {
+                let span = request_span(&trace_id);
+                span.record("method", request.method.as_str());
+                let response = match handle_mcp_request(request, PathBuf::new())
+                    .instrument(span)
+                    .await
+                {
                     Ok(r) => r,
                     Err(e) => Some(JsonRpcResponse::error(
                         request_id,
@@ -1440,6 +2174,7 @@ async fn cmd_mcp_stdio_impl(project: Option<PathBuf>) -> AnyhowResult<()> {
                 let Some(response) = response else {
                     continue;
                 };
+                let response = response.with_request_id(&trace_id);
 
                 let response_json = match serde_json::to_string(&response) {
                     Ok(j) => j,
@@ -1567,6 +2302,16 @@ fn merge_tool_args(
     Ok(Value::Object(object))
 }
 
+/// The `--output json` document of a successful command
+fn json_result(command: &str, result: Value) -> Value {
+    serde_json::json!({ "command": command, "ok": true, "result": result })
+}
+
+/// Print the `--output json` document of a successful command
+fn print_json_result(command: &str, result: Value) -> AnyhowResult<()> {
+    print_json_value(&json_result(command, result))
+}
+
 fn print_json_value(value: &Value) -> AnyhowResult<()> {
     println!(
         "{}",
@@ -1965,30 +2710,77 @@ fn check_neutral_conflicts(
 }
 
 /// Cleanup command implementation — remove stale LeIndex temp artifacts.
-async fn cmd_cleanup_impl(max_age_days: u64, dry_run: bool) -> AnyhowResult<()> {
+///
+/// With `storage` set, also garbage-collects that project's database.
+async fn cmd_cleanup_impl(
+    max_age_days: u64,
+    dry_run: bool,
+    storage: Option<Option<PathBuf>>,
+    output: OutputFormat,
+) -> AnyhowResult<()> {
     use crate::cli::cleanup::run_gc;
     use std::time::Duration;
 
     let max_age = Duration::from_secs(max_age_days * 24 * 3600);
+    let text = output == OutputFormat::Text;
 
-    if dry_run {
+    let report = if dry_run {
         // In dry-run mode we scan but do not remove
-        println!("LeIndex Cleanup (dry run)\n");
-        println!(
-            "Scanning for artifacts older than {} day(s)...\n",
-            max_age_days
-        );
-
-        let report = run_gc_dry_run(max_age);
-        println!("{}", report);
+        if text {
+            println!("LeIndex Cleanup (dry run)\n");
+            println!(
+                "Scanning for artifacts older than {} day(s)...\n",
+                max_age_days
+            );
+        }
+        run_gc_dry_run(max_age)
     } else {
-        println!("LeIndex Cleanup\n");
-        println!("Removing artifacts older than {} day(s)...\n", max_age_days);
-
-        let report = run_gc(max_age);
+        if text {
+            println!("LeIndex Cleanup\n");
+            println!("Removing artifacts older than {} day(s)...\n", max_age_days);
+        }
+        run_gc(max_age)
+    };
+    if text {
         println!("{}", report);
     }
 
+    let mut storage_summary = None;
+    if let Some(project) = storage {
+        let project_path = get_project_path(project);
+        let mut leindex =
+            LeIndex::new(&project_path).context("Failed to create LeIndex instance")?;
+        let summary = leindex.gc(dry_run)?;
+        if text {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            println!("Storage ({}):", leindex.project_path().display());
+            println!("  {} {} orphan node(s)", verb, summary.orphan_nodes_removed);
+            println!(
+                "  {} {} dangling edge(s)",
+                verb, summary.dangling_edges_removed
+            );
+            println!(
+                "  {} {} stale global symbol(s)",
+                verb, summary.stale_global_symbols_removed
+            );
+            println!(
+                "  {} {} dangling external reference(s)",
+                verb, summary.dangling_external_refs_removed
+            );
+        }
+        storage_summary = Some(summary);
+    }
+
+    if output == OutputFormat::Json {
+        return print_json_result(
+            "cleanup",
+            serde_json::json!({
+                "dry_run": dry_run,
+                "artifacts": report,
+                "storage": storage_summary,
+            }),
+        );
+    }
     Ok(())
 }
 
@@ -2225,7 +3017,18 @@ async fn handle_mcp_request(
 /// Main entry point for the CLI
 pub async fn main() -> AnyhowResult<()> {
     match Cli::try_parse() {
-        Ok(cli) => cli.run().await,
+        Ok(cli) => {
+            let output = cli.output;
+            let command = cli.command_name();
+            if let Err(err) = cli.run().await {
+                match output {
+                    OutputFormat::Json => println!("{}", render_cli_error_json(command, &err)),
+                    OutputFormat::Text => eprintln!("{}", render_cli_error(&err)),
+                }
+                std::process::exit(cli_exit_code(&err));
+            }
+            Ok(())
+        }
         Err(err) => {
             if matches!(
                 err.kind(),
@@ -2248,6 +3051,83 @@ mod tests {
         assert!(matches!(cli.command, Some(Commands::Index { .. })));
     }
 
+    #[test]
+    fn test_errors_command_parsing() {
+        let cli = Cli::try_parse_from(["leindex", "errors"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Errors)));
+    }
+
+    #[test]
+    fn test_doctor_command_parsing() {
+        let cli = Cli::try_parse_from(["leindex", "doctor"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Doctor)));
+    }
+
+    #[test]
+    fn test_bash_completions_cover_subcommands() {
+        let cli = Cli::try_parse_from(["leindex", "completions", "bash"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions {
+                shell: clap_complete::Shell::Bash
+            })
+        ));
+
+        let mut out = Vec::new();
+        write_completions(clap_complete::Shell::Bash, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("_leindex()"));
+        for subcommand in [
+            "index",
+            "search",
+            "diagnostics",
+            "doctor",
+            "completions",
+            "serve",
+        ] {
+            assert!(script.contains(subcommand), "missing {}", subcommand);
+        }
+    }
+
+    #[test]
+    fn test_index_languages_flag_parsing() {
+        let cli =
+            Cli::try_parse_from(["leindex", "index", ".", "--languages", "rust,python"]).unwrap();
+        match cli.command {
+            Some(Commands::Index { languages, .. }) => {
+                assert_eq!(languages, vec!["rust".to_string(), "python".to_string()]);
+            }
+            _ => panic!("Expected Index command"),
+        }
+    }
+
+    #[test]
+    fn test_index_jobs_flag_parsing() {
+        let cli = Cli::try_parse_from(["leindex", "index", ".", "--jobs", "2"]).unwrap();
+        match cli.command {
+            Some(Commands::Index { jobs, .. }) => assert_eq!(jobs, Some(2)),
+            _ => panic!("Expected Index command"),
+        }
+    }
+
+    #[test]
+    fn test_index_git_ref_flag_parsing() {
+        let cli = Cli::try_parse_from(["leindex", "index", ".", "--git-ref", "HEAD~1"]).unwrap();
+        match cli.command {
+            Some(Commands::Index { git_ref, .. }) => assert_eq!(git_ref.as_deref(), Some("HEAD~1")),
+            _ => panic!("Expected Index command"),
+        }
+    }
+
+    #[test]
+    fn test_index_dry_run_flag_parsing() {
+        let cli = Cli::try_parse_from(["leindex", "index", ".", "--dry-run"]).unwrap();
+        match cli.command {
+            Some(Commands::Index { dry_run, .. }) => assert!(dry_run),
+            _ => panic!("Expected Index command"),
+        }
+    }
+
     #[test]
     fn test_mcp_command_parsing() {
         let cli = Cli::try_parse_from(["leindex", "mcp"]).unwrap();
@@ -2265,13 +3145,214 @@ mod tests {
         let cli = Cli::try_parse_from(["leindex", "search", "test query"]).unwrap();
         match cli.command {
             Some(Commands::Search { query, top_k, .. }) => {
-                assert_eq!(query, "test query");
+                assert_eq!(query.as_deref(), Some("test query"));
                 assert_eq!(top_k, 10);
             }
             _ => panic!("Expected Search command"),
         }
     }
 
+    #[test]
+    fn test_search_threshold_flags() {
+        let parse = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Some(Commands::Search {
+                threshold,
+                no_threshold,
+                ..
+            }) => resolve_threshold(threshold, no_threshold, 0.1),
+            _ => panic!("Expected Search command"),
+        };
+        assert_eq!(parse(&["leindex", "search", "q"]), Some(0.1));
+        assert_eq!(
+            parse(&["leindex", "search", "q", "--threshold", "0.02"]),
+            Some(0.02)
+        );
+        assert_eq!(parse(&["leindex", "search", "q", "--no-threshold"]), None);
+        assert!(Cli::try_parse_from([
+            "leindex",
+            "search",
+            "q",
+            "--threshold",
+            "0.2",
+            "--no-threshold"
+        ])
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_index_missing_path_renders_recovery_suggestion() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("does-not-exist");
+
+        let err = cmd_index_impl(
+            missing.clone(),
+            false,
+            false,
+            None,
+            Vec::new(),
+            None,
+            false,
+            OutputFormat::Text,
+        )
+        .await
+        .unwrap_err();
+        let rendered = render_cli_error(&err);
+
+        assert!(rendered.contains("Operation: index"));
+        assert!(rendered.contains(&missing.display().to_string()));
+        assert!(rendered.contains("Suggestion: Check that the path exists and is readable."));
+        assert_eq!(cli_exit_code(&err), 8);
+
+        let document = render_cli_error_json("index", &err);
+        assert_eq!(document["command"], "index");
+        assert_eq!(document["ok"], false);
+        assert_eq!(document["error"]["exit_code"], 8);
+        assert_eq!(
+            document["error"]["suggestion"],
+            "Check that the path exists and is readable."
+        );
+    }
+
+    /// Index a one-file project so search and diagnostics have data
+    fn indexed_project(dir: &tempfile::TempDir) -> PathBuf {
+        let project_path = dir.path().join("project");
+        std::fs::create_dir_all(&project_path).unwrap();
+        std::fs::write(
+            project_path.join("config.rs"),
+            "pub fn parse_config(input: &str) -> usize {\n    input.len()\n}\n",
+        )
+        .unwrap();
+        LeIndex::new(&project_path)
+            .unwrap()
+            .index_project(false)
+            .unwrap();
+        project_path
+    }
+
+    #[test]
+    fn test_output_flag_is_global() {
+        let cli = Cli::try_parse_from(["leindex", "search", "q", "--output", "json"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert_eq!(cli.command_name(), "search");
+        let cli = Cli::try_parse_from(["leindex", "diagnostics"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Text);
+    }
+
+    #[tokio::test]
+    async fn test_search_output_json_is_structured() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = indexed_project(&dir);
+
+        let results = search_results_json("parse_config", 10, None, Some(project_path))
+            .await
+            .unwrap();
+        let printed = serde_json::to_string_pretty(&json_result(
+            "search",
+            serde_json::json!({ "query": "parse_config", "results": results }),
+        ))
+        .unwrap();
+
+        let document: Value = serde_json::from_str(&printed).unwrap();
+        assert_eq!(document["command"], "search");
+        assert_eq!(document["ok"], true);
+        assert_eq!(document["result"]["query"], "parse_config");
+        let results = document["result"]["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        assert!(results
+            .iter()
+            .any(|r| r["symbol"].as_str().unwrap().contains("parse_config")));
+        assert!(results[0]["score"].is_number());
+        assert!(results[0]["file_path"]
+            .as_str()
+            .unwrap()
+            .ends_with("config.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_is_listed_and_rerun_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = indexed_project(&dir);
+
+        let save = SearchInput::Text {
+            query: "parse_config".to_string(),
+            save: Some("config".to_string()),
+        };
+        cmd_search_impl(
+            save,
+            10,
+            None,
+            Some(project_path.clone()),
+            OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+        let leindex = LeIndex::new(&project_path).unwrap();
+        let saved = leindex.saved_queries().unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(
+            (saved[0].name.as_str(), saved[0].query.as_str()),
+            ("config", "parse_config")
+        );
+        drop(leindex);
+
+        let rerun = SearchInput::Saved("config".to_string());
+        cmd_search_impl(
+            rerun,
+            10,
+            None,
+            Some(project_path.clone()),
+            OutputFormat::Json,
+        )
+        .await
+        .unwrap();
+        let recent = LeIndex::new(&project_path)
+            .unwrap()
+            .recent_queries(10)
+            .unwrap();
+        let queries: Vec<&str> = recent.iter().map(|e| e.query.as_str()).collect();
+        assert_eq!(queries, ["parse_config", "parse_config"]);
+
+        let missing = SearchInput::Saved("nope".to_string());
+        let err = cmd_search_impl(missing, 10, None, Some(project_path), OutputFormat::Json)
+            .await
+            .unwrap_err();
+        assert_eq!(cli_exit_code(&err), 7);
+        assert!(format!("{:#}", err).contains("No saved search named 'nope'"));
+    }
+
+    #[test]
+    fn test_search_saved_flag_replaces_query() {
+        let cli = Cli::try_parse_from(["leindex", "search", "--saved", "config"]).unwrap();
+        match cli.command {
+            Some(Commands::Search { query, saved, .. }) => {
+                assert_eq!(query, None);
+                assert_eq!(saved.as_deref(), Some("config"));
+            }
+            _ => panic!("Expected Search command"),
+        }
+        assert!(Cli::try_parse_from(["leindex", "search"]).is_err());
+        assert!(Cli::try_parse_from(["leindex", "search", "q", "--saved", "config"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_output_json_is_structured() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_path = indexed_project(&dir);
+
+        let diagnostics = diagnostics_json(Some(project_path)).await.unwrap();
+        let printed =
+            serde_json::to_string_pretty(&json_result("diagnostics", diagnostics)).unwrap();
+
+        let document: Value = serde_json::from_str(&printed).unwrap();
+        assert_eq!(document["command"], "diagnostics");
+        assert_eq!(document["ok"], true);
+        let result = &document["result"];
+        assert_eq!(result["indexed_files"], 1);
+        assert!(result["symbol_count"].as_u64().unwrap() > 0);
+        assert!(result["stale"].is_boolean());
+        assert!(result["issues"].is_array());
+    }
+
     #[test]
     fn test_phase_command_parsing() {
         let cli =
@@ -2380,9 +3461,11 @@ mod tests {
             Some(Commands::Cleanup {
                 max_age_days,
                 dry_run,
+                storage,
             }) => {
                 assert_eq!(max_age_days, 7);
                 assert!(!dry_run);
+                assert!(!storage);
             }
             _ => panic!("Expected Cleanup command"),
         }
@@ -2390,15 +3473,24 @@ mod tests {
 
     #[test]
     fn test_cleanup_command_with_flags() {
-        let cli = Cli::try_parse_from(["leindex", "cleanup", "--max-age-days", "14", "--dry-run"])
-            .unwrap();
+        let cli = Cli::try_parse_from([
+            "leindex",
+            "cleanup",
+            "--max-age-days",
+            "14",
+            "--dry-run",
+            "--storage",
+        ])
+        .unwrap();
         match cli.command {
             Some(Commands::Cleanup {
                 max_age_days,
                 dry_run,
+                storage,
             }) => {
                 assert_eq!(max_age_days, 14);
                 assert!(dry_run);
+                assert!(storage);
             }
             _ => panic!("Expected Cleanup command"),
         }
@@ -7,8 +7,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::search::tokenizer::{Tokenizer, TokenizerKind};
 use crate::storage::schema::{PROJECT_STORE_MMAP_SIZE, PROJECT_WRITER_CACHE_SIZE_KIB};
+use crate::storage::IdentityStrategy;
 
 /// Default configuration file name
 pub const DEFAULT_CONFIG_FILE: &str = ".leindex/config.toml";
@@ -146,6 +149,26 @@ impl ProjectConfig {
     }
 }
 
+/// Language names accepted by `LanguageConfig::extensions_for_languages`.
+pub const SUPPORTED_LANGUAGE_NAMES: &[&str] = &[
+    "rust",
+    "python",
+    "javascript",
+    "typescript",
+    "go",
+    "java",
+    "cpp",
+    "c",
+    "objc",
+    "csharp",
+    "ruby",
+    "php",
+    "lua",
+    "scala",
+    "bash",
+    "json",
+];
+
 /// Language filtering configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageConfig {
@@ -180,8 +203,8 @@ impl LanguageConfig {
         let all_extensions = [
             "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts",
             "cts", // Main languages
-            "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", // Systems languages
-            "cs",  // C#
+            "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", "m",  // Systems languages
+            "cs", // C#
             "rb", "php", "lua", "scala", "sc", // Scripting languages
             "sh", "bash", // Shell
             "json", // Data
@@ -213,6 +236,25 @@ impl LanguageConfig {
         extensions
     }
 
+    /// Resolve language names (e.g. `rust`, `python`) to their file extensions.
+    ///
+    /// Fails on the first name that is not a supported language.
+    pub fn extensions_for_languages(names: &[String]) -> Result<HashSet<String>> {
+        let mut extensions = HashSet::new();
+        for name in names {
+            let exts = Self::language_to_extensions(name.trim());
+            if exts.is_empty() {
+                anyhow::bail!(
+                    "Unsupported language '{}'. Supported languages: {}",
+                    name,
+                    SUPPORTED_LANGUAGE_NAMES.join(", ")
+                );
+            }
+            extensions.extend(exts.into_iter().map(str::to_string));
+        }
+        Ok(extensions)
+    }
+
     /// Map language name to file extensions
     fn language_to_extensions(lang: &str) -> Vec<&'static str> {
         match lang.to_lowercase().as_str() {
@@ -224,6 +266,7 @@ impl LanguageConfig {
             "java" => vec!["java"],
             "cpp" | "c++" => vec!["cpp", "cc", "cxx", "c", "h", "hpp"],
             "c" => vec!["c", "h"],
+            "objc" | "objective-c" => vec!["m", "h"],
             "csharp" | "c#" => vec!["cs"],
             "ruby" => vec!["rb"],
             "php" => vec!["php"],
@@ -421,6 +464,10 @@ pub struct TokenConfig {
 
     /// Maximum number of results to return
     pub max_results: usize,
+
+    /// Tokenizer used to count tokens against these budgets
+    #[serde(default)]
+    pub tokenizer: TokenizerKind,
 }
 
 impl Default for TokenConfig {
@@ -430,10 +477,18 @@ impl Default for TokenConfig {
             max_context: 5000,
             min_results: 5,
             max_results: 20,
+            tokenizer: TokenizerKind::default(),
         }
     }
 }
 
+impl TokenConfig {
+    /// Build the configured tokenizer
+    pub fn build_tokenizer(&self) -> Arc<dyn Tokenizer> {
+        self.tokenizer.build()
+    }
+}
+
 /// Storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -446,8 +501,10 @@ pub struct StorageConfig {
     /// Whether to enable WAL mode
     pub wal_enabled: bool,
 
-    /// Cache size in KiB (negative = KiB units per SQLite convention).
-    /// Defaults to the writer budget.
+    /// SQLite page cache size: negative = KiB, positive = pages, per
+    /// SQLite convention. Defaults to the writer budget. Out-of-range values
+    /// (see `storage::schema::MAX_CACHE_SIZE_KIB`) are rejected when the
+    /// project is opened.
     pub cache_size_kib: Option<i64>,
 
     /// mmap_size cap in bytes. Defaults to 64 MiB.
@@ -455,6 +512,25 @@ pub struct StorageConfig {
 
     /// Connection timeout in seconds
     pub connection_timeout_secs: Option<u64>,
+
+    /// Milliseconds SQLite waits on a locked database before reporting it
+    /// busy. Defaults to 5 seconds.
+    #[serde(default)]
+    pub busy_timeout_ms: Option<u64>,
+
+    /// Name of the environment variable holding the database encryption
+    /// key. When set, the database is encrypted at rest (requires the
+    /// `encryption` feature). The key itself never goes in this file.
+    #[serde(default)]
+    pub encryption_key_env: Option<String>,
+
+    /// How the project's unique ID is derived: `path-hash` (default),
+    /// `file-marker` or `git-root`. The latter two keep the ID when the
+    /// project is moved; `git-root` also gives clones and worktrees of the
+    /// same repository the same ID. Storage rows, and storage directories
+    /// outside the project, are keyed by this ID.
+    #[serde(default)]
+    pub identity: IdentityStrategy,
 }
 
 impl Default for StorageConfig {
@@ -466,6 +542,9 @@ impl Default for StorageConfig {
             cache_size_kib: Some(PROJECT_WRITER_CACHE_SIZE_KIB),
             mmap_size: Some(PROJECT_STORE_MMAP_SIZE),
             connection_timeout_secs: Some(30),
+            busy_timeout_ms: None,
+            encryption_key_env: None,
+            identity: IdentityStrategy::PathHash,
         }
     }
 }
@@ -520,6 +599,13 @@ pub struct EmbeddingConfig {
     /// Local ONNX model path (if using local neural embeddings)
     #[serde(default)]
     pub onnx_model_path: Option<String>,
+
+    /// Dimension of the node embeddings held by the search engine
+    ///
+    /// Embeddings stored under another dimension are skipped with a warning
+    /// until the project is re-indexed.
+    #[serde(default = "default_embedding_dim")]
+    pub embedding_dim: usize,
 }
 
 const fn default_enable_hybrid() -> bool {
@@ -530,6 +616,10 @@ const fn default_neural_weight() -> f32 {
     0.4 // Default neural weight from HybridScoringWeights
 }
 
+const fn default_embedding_dim() -> usize {
+    crate::search::search::DEFAULT_EMBEDDING_DIMENSION
+}
+
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
@@ -538,6 +628,7 @@ impl Default for EmbeddingConfig {
             remote_endpoint: None,
             remote_api_key: None,
             onnx_model_path: None,
+            embedding_dim: default_embedding_dim(),
         }
     }
 }
@@ -553,9 +644,10 @@ pub struct IndexingConfig {
     #[serde(default = "default_max_files")]
     pub max_files: usize,
 
-    /// Maximum individual file size in bytes. Files exceeding this limit are
-    /// skipped with a warning (0 = unlimited).
-    #[serde(default = "default_max_file_size")]
+    /// Maximum individual file size in bytes. Larger files (typically
+    /// minified bundles or generated code) are skipped with a warning and
+    /// listed in `IndexStats::skipped_oversized_files` (0 = unlimited).
+    #[serde(default = "default_max_file_size", alias = "max_file_bytes")]
     pub max_file_size: u64,
 
     /// Maximum total size of all indexed source files in bytes. Scanning stops
@@ -563,6 +655,19 @@ pub struct IndexingConfig {
     #[serde(default = "default_max_total_size")]
     pub max_total_size: u64,
 
+    /// Also store the recovered syntax errors of files that did parse, for
+    /// review with `leindex errors` (files that fail to parse are always
+    /// recorded). Locating syntax errors costs a second parse of every file,
+    /// so this is off by default.
+    #[serde(default)]
+    pub record_parse_errors: bool,
+
+    /// Record the symbol and edge sets of each indexing run so generations
+    /// can be diffed. Every run stores a row per symbol and edge, for up to
+    /// `GENERATIONS_KEPT` runs, so this is off by default.
+    #[serde(default)]
+    pub record_generations: bool,
+
     /// Embedding configuration for hybrid system
     #[serde(default)]
     pub embeddings: EmbeddingConfig,
@@ -572,7 +677,7 @@ const fn default_max_files() -> usize {
     100_000
 }
 const fn default_max_file_size() -> u64 {
-    10_485_760 // 10 MB
+    1_048_576 // 1 MB
 }
 const fn default_max_total_size() -> u64 {
     5_368_709_120 // 5 GB
@@ -585,6 +690,8 @@ impl Default for IndexingConfig {
             max_files: default_max_files(),
             max_file_size: default_max_file_size(),
             max_total_size: default_max_total_size(),
+            record_parse_errors: false,
+            record_generations: false,
             embeddings: EmbeddingConfig::default(),
         }
     }
@@ -709,8 +816,8 @@ mod tests {
         // All extensions supported by leparse
         for ext in &[
             "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", "go", "java", "cpp",
-            "cc", "cxx", "c", "h", "hpp", "cs", "rb", "php", "lua", "scala", "sc", "sh", "bash",
-            "json",
+            "cc", "cxx", "c", "h", "hpp", "m", "cs", "rb", "php", "lua", "scala", "sc", "sh",
+            "bash", "json",
         ] {
             assert!(exts.contains(*ext), "Extension '{}' should be enabled", ext);
         }
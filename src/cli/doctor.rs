@@ -0,0 +1,343 @@
+// Environment and index health checks behind `leindex doctor`
+//
+// Each check is independent, so one failure (a corrupt database, say) does
+// not hide the others. Failed checks carry a `RecoveryStrategy` and a
+// concrete command or step that fixes them.
+
+use crate::cli::config::{ProjectConfig, DEFAULT_CONFIG_FILE};
+use crate::cli::errors::RecoveryStrategy;
+use crate::cli::leindex::LeIndex;
+use crate::parse::grammar::LanguageId;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Outcome of a single doctor check
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    /// Short name of what was checked (e.g. `storage`)
+    pub name: &'static str,
+    /// Whether the check passed
+    pub passed: bool,
+    /// What was found
+    pub detail: String,
+    /// How to recover when the check failed
+    pub recovery: Option<RecoveryStrategy>,
+    /// Concrete fix for a failed check
+    pub suggestion: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            detail: detail.into(),
+            recovery: None,
+            suggestion: None,
+        }
+    }
+
+    fn fail(
+        name: &'static str,
+        detail: impl Into<String>,
+        recovery: RecoveryStrategy,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            passed: false,
+            detail: detail.into(),
+            recovery: Some(recovery),
+            suggestion: Some(suggestion.into()),
+        }
+    }
+}
+
+/// Results of [`run_doctor`], in the order the checks ran
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    /// All checks that ran
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// True when every check passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Checks that failed
+    pub fn failures(&self) -> impl Iterator<Item = &DoctorCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+}
+
+impl std::fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "ok" } else { "FAIL" };
+            writeln!(f, "[{:>4}] {}: {}", status, check.name, check.detail)?;
+            if let Some(recovery) = check.recovery {
+                writeln!(f, "       recovery: {}", recovery)?;
+            }
+            if let Some(suggestion) = &check.suggestion {
+                writeln!(f, "       fix: {}", suggestion)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check the environment and index of the project at `project_path`
+///
+/// Covers the project config, whether storage opens and passes
+/// [`LeIndex::verify_storage`], whether the grammars of the languages found
+/// in the project load, and how much disk the index and cache use.
+pub fn run_doctor(project_path: &Path) -> DoctorReport {
+    let canonical = project_path.canonicalize();
+    let project_path = canonical.as_deref().unwrap_or(project_path);
+    let mut report = DoctorReport::default();
+    report.checks.push(check_config(project_path));
+    report.checks.push(check_storage(project_path));
+    report.checks.push(check_grammars(project_path));
+    report.checks.push(check_disk_usage(project_path));
+    report
+}
+
+fn check_config(project_path: &Path) -> DoctorCheck {
+    match ProjectConfig::load(project_path) {
+        Ok(_) => DoctorCheck::pass("config", "project config is valid"),
+        Err(e) => DoctorCheck::fail(
+            "config",
+            format!("{:#}", e),
+            RecoveryStrategy::Abort,
+            format!(
+                "Fix or remove {} (defaults are used when it is missing)",
+                project_path.join(DEFAULT_CONFIG_FILE).display()
+            ),
+        ),
+    }
+}
+
+fn check_storage(project_path: &Path) -> DoctorCheck {
+    let leindex = match LeIndex::new(project_path) {
+        Ok(leindex) => leindex,
+        Err(e) => {
+            let storage_path = LeIndex::resolve_storage_path(project_path)
+                .unwrap_or_else(|_| project_path.join(".leindex"));
+            let db_path = storage_path.join("leindex.db");
+            if is_corrupt_database(&e) {
+                return DoctorCheck::fail(
+                    "storage",
+                    format!(
+                        "Storage database {} is corrupt or not a database",
+                        db_path.display()
+                    ),
+                    RecoveryStrategy::Fallback,
+                    format!(
+                        "Delete {} and run `leindex index --force`; if storage encryption is \
+                         enabled, check that the key is correct",
+                        storage_path.display()
+                    ),
+                );
+            }
+            return DoctorCheck::fail(
+                "storage",
+                format!("Failed to open storage at {}: {:#}", db_path.display(), e),
+                RecoveryStrategy::Retry,
+                "Check that the storage directory is writable and not locked by another \
+                 process, then run `leindex doctor` again",
+            );
+        }
+    };
+
+    let report = match leindex.verify_storage() {
+        Ok(report) => report,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "storage",
+                format!("{:#}", e),
+                RecoveryStrategy::Fallback,
+                format!(
+                    "Delete {} and run `leindex index --force`",
+                    leindex.storage_path().display()
+                ),
+            );
+        }
+    };
+    if !report.dangling_edges.is_empty() || !report.orphan_nodes.is_empty() {
+        return DoctorCheck::fail(
+            "storage",
+            format!(
+                "stored PDG is inconsistent: {} dangling edge(s), {} orphan node(s)",
+                report.dangling_edges.len(),
+                report.orphan_nodes.len()
+            ),
+            RecoveryStrategy::Retry,
+            "Run `leindex index --force` to rebuild the stored PDG",
+        );
+    }
+
+    // Changed files only mean the index is stale, not damaged
+    let mut detail = format!(
+        "{} opens and passes verify",
+        leindex.storage_path().display()
+    );
+    if !report.hash_mismatches.is_empty() {
+        detail.push_str(&format!(
+            " ({} file(s) changed since the last index)",
+            report.hash_mismatches.len()
+        ));
+    }
+    DoctorCheck::pass("storage", detail)
+}
+
+/// True when opening storage failed because the file is not a usable database
+fn is_corrupt_database(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause
+                .downcast_ref::<rusqlite::Error>()
+                .and_then(rusqlite::Error::sqlite_error_code),
+            Some(rusqlite::ErrorCode::NotADatabase | rusqlite::ErrorCode::DatabaseCorrupt)
+        )
+    })
+}
+
+fn check_grammars(project_path: &Path) -> DoctorCheck {
+    let scan = match crate::cli::index_builder::scan_project_files(project_path) {
+        Ok(scan) => scan,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "grammars",
+                format!("Failed to scan project files: {:#}", e),
+                RecoveryStrategy::Abort,
+                "Check the [indexing] limits and exclusions in the project config",
+            );
+        }
+    };
+
+    let mut languages: BTreeMap<&str, LanguageId> = BTreeMap::new();
+    for path in &scan.source_paths {
+        if let Some(language) = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(LanguageId::from_extension)
+        {
+            languages.insert(language.config().name.as_str(), language);
+        }
+    }
+    if languages.is_empty() {
+        return DoctorCheck::pass("grammars", "no supported source files found");
+    }
+
+    let mut failed = Vec::new();
+    for (name, language) in &languages {
+        let loaded = language
+            .from_cache()
+            .map_err(|e| e.to_string())
+            .and_then(|grammar| {
+                tree_sitter::Parser::new()
+                    .set_language(&grammar)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = loaded {
+            failed.push(format!("{} ({})", name, e));
+        }
+    }
+    if !failed.is_empty() {
+        return DoctorCheck::fail(
+            "grammars",
+            format!("failed to load grammar(s): {}", failed.join(", ")),
+            RecoveryStrategy::Skip,
+            "Files in these languages are skipped; reinstall leindex to restore the grammars, \
+             or exclude the languages with `leindex index --languages`",
+        );
+    }
+
+    let names: Vec<&str> = languages.keys().copied().collect();
+    DoctorCheck::pass("grammars", format!("loaded {}", names.join(", ")))
+}
+
+fn check_disk_usage(project_path: &Path) -> DoctorCheck {
+    let storage_path = match LeIndex::resolve_storage_path(project_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "disk",
+                format!("{:#}", e),
+                RecoveryStrategy::Abort,
+                "Set LEINDEX_HOME to a writable directory",
+            );
+        }
+    };
+    DoctorCheck::pass(
+        "disk",
+        format!(
+            "{} in {} (cache {})",
+            format_size(dir_size(&storage_path)),
+            storage_path.display(),
+            format_size(dir_size(&storage_path.join("cache")))
+        ),
+    )
+}
+
+fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn format_size(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doctor_passes_on_a_healthy_project() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project_path).unwrap();
+        std::fs::write(project_path.join("lib.rs"), "fn healthy() {}\n").unwrap();
+        LeIndex::new(&project_path)
+            .unwrap()
+            .index_project(false)
+            .unwrap();
+
+        let report = run_doctor(&project_path);
+        assert!(report.passed(), "{}", report);
+        let grammars = report.checks.iter().find(|c| c.name == "grammars").unwrap();
+        assert!(grammars.detail.contains("Rust"), "{}", grammars.detail);
+    }
+
+    #[test]
+    fn test_doctor_fails_on_a_corrupt_database() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_path.join(".leindex")).unwrap();
+        std::fs::write(project_path.join("lib.rs"), "fn healthy() {}\n").unwrap();
+        std::fs::write(project_path.join(".leindex/leindex.db"), vec![0xAB; 8192]).unwrap();
+
+        let report = run_doctor(&project_path);
+        assert!(!report.passed());
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1, "{}", report);
+        assert_eq!(failures[0].name, "storage");
+        assert!(
+            failures[0]
+                .detail
+                .ends_with("leindex.db is corrupt or not a database"),
+            "{}",
+            failures[0].detail
+        );
+        assert_eq!(failures[0].recovery, Some(RecoveryStrategy::Fallback));
+    }
+}
@@ -154,15 +154,45 @@ impl LeIndexError {
             LeIndexError::Validation { suggestion, .. } => suggestion.clone(),
             LeIndexError::Index {
                 recoverable: true, ..
-            } => {
-                Some("Try re-indexing with a smaller token budget or fewer languages.".to_string())
-            }
+            } => Some(
+                "Re-run with --force to rebuild the index, or index fewer languages.".to_string(),
+            ),
             LeIndexError::Storage {
                 recoverable: true, ..
             } => Some("Try deleting .leindex directory and re-indexing.".to_string()),
+            LeIndexError::Io { path: Some(_), .. } => {
+                Some("Check that the path exists and is readable.".to_string())
+            }
             _ => None,
         }
     }
+
+    /// Process exit code for this error category.
+    ///
+    /// | Code | Category   |
+    /// |------|------------|
+    /// | 3    | Parse      |
+    /// | 4    | Index      |
+    /// | 5    | Storage    |
+    /// | 6    | Search     |
+    /// | 7    | Config     |
+    /// | 8    | I/O        |
+    /// | 9    | Memory     |
+    /// | 10   | Validation |
+    ///
+    /// Code 1 is reserved for untyped failures and 2 for usage errors (clap).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            LeIndexError::Parse { .. } => 3,
+            LeIndexError::Index { .. } => 4,
+            LeIndexError::Storage { .. } => 5,
+            LeIndexError::Search { .. } => 6,
+            LeIndexError::Config { .. } => 7,
+            LeIndexError::Io { .. } => 8,
+            LeIndexError::Memory { .. } => 9,
+            LeIndexError::Validation { .. } => 10,
+        }
+    }
 }
 
 impl std::fmt::Display for LeIndexError {
@@ -180,7 +210,14 @@ impl std::fmt::Display for LeIndexError {
     }
 }
 
-impl std::error::Error for LeIndexError {}
+impl std::error::Error for LeIndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LeIndexError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl From<std::io::Error> for LeIndexError {
     fn from(err: std::io::Error) -> Self {
@@ -193,7 +230,8 @@ impl From<std::io::Error> for LeIndexError {
 }
 
 /// Error recovery strategy
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RecoveryStrategy {
     /// Skip the problematic item and continue
     Skip,
@@ -208,6 +246,17 @@ pub enum RecoveryStrategy {
     Abort,
 }
 
+impl std::fmt::Display for RecoveryStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecoveryStrategy::Skip => write!(f, "skip the affected item and continue"),
+            RecoveryStrategy::Retry => write!(f, "retry the operation"),
+            RecoveryStrategy::Fallback => write!(f, "fall back to a simpler approach"),
+            RecoveryStrategy::Abort => write!(f, "abort and fix the cause manually"),
+        }
+    }
+}
+
 /// Error recovery context
 #[derive(Debug)]
 pub struct ErrorContext {
@@ -217,6 +266,9 @@ pub struct ErrorContext {
     /// File being processed (if applicable)
     pub file_path: Option<PathBuf>,
 
+    /// Project being processed (if applicable)
+    pub project_path: Option<PathBuf>,
+
     /// Error that occurred
     pub error: LeIndexError,
 
@@ -233,6 +285,7 @@ impl ErrorContext {
         Self {
             operation: operation.into(),
             file_path: None,
+            project_path: None,
             error: LeIndexError::validation_error("Unknown error", None),
             error_count: 0,
             max_errors: 100,
@@ -245,6 +298,12 @@ impl ErrorContext {
         self
     }
 
+    /// Set the project path
+    pub fn with_project_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.project_path = Some(path.into());
+        self
+    }
+
     /// Set the error
     pub fn with_error(mut self, error: LeIndexError) -> Self {
         self.error = error;
@@ -289,6 +348,43 @@ impl ErrorContext {
     }
 }
 
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.operation, self.error)
+    }
+}
+
+impl std::error::Error for ErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A failed command that still has a result worth reporting
+///
+/// Renders like `error`; with `--output json` the error document also
+/// carries `result`.
+#[derive(Debug)]
+pub struct CommandFailure {
+    /// Error that failed the command
+    pub error: LeIndexError,
+
+    /// What the command produced before failing
+    pub result: serde_json::Value,
+}
+
+impl std::fmt::Display for CommandFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for CommandFailure {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
 /// Partial indexing result
 #[derive(Debug, Clone)]
 pub struct PartialIndexResult {
@@ -439,18 +535,27 @@ pub fn detect_corruption<P: AsRef<Path>>(project_path: P) -> Result<CorruptionSt
         return Ok(CorruptionStatus::Minor { missing_files: 1 });
     }
 
-    // Try to open and validate the database
+    // Try to open and validate the database, with the project's storage
+    // settings so an encrypted database is opened with its key
     // This is a simplified check - actual implementation would run queries
-    match crate::storage::schema::Storage::open(&db_path) {
+    let project_config = crate::cli::config::ProjectConfig::load(project_path).unwrap_or_default();
+    let opened = crate::cli::leindex::LeIndex::storage_config(&project_config, &db_path).and_then(
+        |config| {
+            Ok(crate::storage::schema::Storage::open_with_config(
+                &db_path, config,
+            )?)
+        },
+    );
+    match opened {
         Ok(_) => Ok(CorruptionStatus::Healthy),
         Err(e) => {
             if e.to_string().contains("corrupted") {
                 Ok(CorruptionStatus::Major {
-                    description: format!("Database corruption detected: {}", e),
+                    description: format!("Database corruption detected: {:#}", e),
                 })
             } else {
                 Ok(CorruptionStatus::Severe {
-                    description: format!("Cannot access database: {}", e),
+                    description: format!("Cannot access database: {:#}", e),
                 })
             }
         }
@@ -512,6 +617,91 @@ pub fn format_error(error: &LeIndexError) -> String {
     message
 }
 
+/// Format an error context for user display
+///
+/// Renders the failed operation, the affected project/file, the recovery
+/// strategy, and the error's suggestion in a fixed layout.
+pub fn format_error_context(context: &ErrorContext) -> String {
+    let mut message = format!("Error: {}", context.error);
+    message.push_str(&format!("\n  Operation: {}", context.operation));
+
+    if let Some(project) = &context.project_path {
+        message.push_str(&format!("\n  Project: {}", project.display()));
+    }
+
+    let file = context.file_path.as_ref().or(match &context.error {
+        LeIndexError::Parse { file_path, .. } => file_path.as_ref(),
+        LeIndexError::Io { path, .. } => path.as_ref(),
+        _ => None,
+    });
+    if let Some(file) = file {
+        message.push_str(&format!("\n  File: {}", file.display()));
+    }
+
+    message.push_str(&format!("\n  Recovery: {}", context.recovery_strategy()));
+
+    if let Some(suggestion) = context.error.suggestion() {
+        message.push_str(&format!("\n\nSuggestion: {}", suggestion));
+    }
+
+    message
+}
+
+/// Render a CLI failure, preferring structured context when the chain carries one
+pub fn render_cli_error(error: &anyhow::Error) -> String {
+    if let Some(context) = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ErrorContext>())
+    {
+        return format_error_context(context);
+    }
+
+    if let Some(error) = typed_error(error) {
+        return format_error(error);
+    }
+
+    format!("Error: {:#}", error)
+}
+
+/// Render a CLI failure as the `--output json` error document
+///
+/// Carries the same message, suggestion and exit code as the text rendering,
+/// plus the command's `result` when it failed with a [`CommandFailure`].
+pub fn render_cli_error_json(command: &str, error: &anyhow::Error) -> serde_json::Value {
+    let mut document = serde_json::json!({
+        "command": command,
+        "ok": false,
+        "error": {
+            "message": format!("{:#}", error),
+            "suggestion": typed_error(error).and_then(LeIndexError::suggestion),
+            "exit_code": cli_exit_code(error),
+        },
+    });
+    if let Some(failure) = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<CommandFailure>())
+    {
+        document["result"] = failure.result.clone();
+    }
+    document
+}
+
+/// Exit code for a CLI failure (see [`LeIndexError::exit_code`]); 1 when untyped
+pub fn cli_exit_code(error: &anyhow::Error) -> i32 {
+    typed_error(error).map(LeIndexError::exit_code).unwrap_or(1)
+}
+
+/// The first typed error in a failure's chain, looking inside wrappers
+fn typed_error(error: &anyhow::Error) -> Option<&LeIndexError> {
+    error.chain().find_map(|cause| {
+        cause
+            .downcast_ref::<ErrorContext>()
+            .map(|context| &context.error)
+            .or_else(|| cause.downcast_ref::<CommandFailure>().map(|f| &f.error))
+            .or_else(|| cause.downcast_ref::<LeIndexError>())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -540,6 +730,53 @@ mod tests {
         assert_eq!(strategy, RecoveryStrategy::Skip);
     }
 
+    #[test]
+    fn test_error_context_rendering_includes_recovery() {
+        let err: anyhow::Error = ErrorContext::new("index")
+            .with_project_path("/projects/demo")
+            .with_error(LeIndexError::storage_error("database is locked", true))
+            .into();
+
+        let rendered = render_cli_error(&err);
+        assert!(rendered.contains("Storage error: database is locked"));
+        assert!(rendered.contains("Operation: index"));
+        assert!(rendered.contains("Project: /projects/demo"));
+        assert!(rendered.contains("Recovery: retry the operation"));
+        assert!(rendered.contains("Try deleting .leindex directory and re-indexing."));
+        assert_eq!(cli_exit_code(&err), 5);
+    }
+
+    #[test]
+    fn test_untyped_errors_use_generic_exit_code() {
+        let err = anyhow::anyhow!("boom").context("outer");
+        assert_eq!(render_cli_error(&err), "Error: outer: boom");
+        assert_eq!(cli_exit_code(&err), 1);
+    }
+
+    #[test]
+    fn test_command_failure_keeps_its_result() {
+        let err: anyhow::Error = CommandFailure {
+            error: LeIndexError::validation_error("1 of 2 doctor check(s) failed", None),
+            result: serde_json::json!({ "checks": [] }),
+        }
+        .into();
+
+        assert_eq!(
+            render_cli_error(&err),
+            format_error(&LeIndexError::validation_error(
+                "1 of 2 doctor check(s) failed",
+                None
+            ))
+        );
+        let document = render_cli_error_json("doctor", &err);
+        assert_eq!(document["ok"], false);
+        assert_eq!(document["result"], serde_json::json!({ "checks": [] }));
+        assert_eq!(
+            document["error"]["exit_code"],
+            LeIndexError::validation_error("", None).exit_code()
+        );
+    }
+
     #[test]
     fn test_partial_result() {
         let mut result = PartialIndexResult::new();
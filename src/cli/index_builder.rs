@@ -886,7 +886,8 @@ impl FileReadCache {
         self.order.push_back(path.to_path_buf());
     }
 
-    fn insert(&mut self, path: PathBuf, bytes: std::sync::Arc<Vec<u8>>) {
+    /// Cache contents that did not come from disk (e.g. git blobs)
+    pub(crate) fn insert(&mut self, path: PathBuf, bytes: std::sync::Arc<Vec<u8>>) {
         if self.entries.contains_key(&path) {
             self.entries.insert(path.clone(), bytes);
             self.touch(&path);
@@ -923,7 +924,7 @@ pub(crate) fn scan_project_files(project_path: &Path) -> Result<ProjectFileScan>
     let mut source_paths = Vec::new();
     let mut manifest_paths = Vec::new();
     let mut total_source_size: u64 = 0;
-    let mut oversized_count: usize = 0;
+    let mut oversized_paths = Vec::new();
     let mut walker = walkdir::WalkDir::new(project_path).into_iter();
 
     while let Some(entry) = walker.next() {
@@ -974,8 +975,8 @@ pub(crate) fn scan_project_files(project_path: &Path) -> Result<ProjectFileScan>
                 // Enforce individual file size limit
                 let file_size = entry.metadata().map(|m| m.len()).unwrap_or(0);
                 if limits.max_file_size > 0 && file_size > limits.max_file_size {
-                    oversized_count += 1;
-                    if oversized_count <= 5 {
+                    oversized_paths.push(path.to_path_buf());
+                    if oversized_paths.len() <= 5 {
                         tracing::warn!(
                             file = %path.display(),
                             size_bytes = file_size,
@@ -1014,9 +1015,9 @@ pub(crate) fn scan_project_files(project_path: &Path) -> Result<ProjectFileScan>
         }
     }
 
-    if oversized_count > 5 {
+    if oversized_paths.len() > 5 {
         tracing::warn!(
-            total_oversized = oversized_count,
+            total_oversized = oversized_paths.len(),
             "Additional oversized files skipped (showing first 5 warnings)"
         );
     }
@@ -1059,6 +1060,7 @@ pub(crate) fn scan_project_files(project_path: &Path) -> Result<ProjectFileScan>
         manifest_paths_canonical,
         source_directories,
         manifest_hashes,
+        oversized_paths,
     })
 }
 
@@ -1155,6 +1157,26 @@ pub(crate) fn save_to_storage(
     Ok(())
 }
 
+/// Save only the rows of `files` to storage, leaving other files untouched.
+pub(crate) fn save_delta_to_storage(
+    storage: &mut Storage,
+    project_id: &str,
+    pdg: &ProgramDependenceGraph,
+    files: &[String],
+) -> Result<()> {
+    let summary = pdg_store::save_file_delta(storage, project_id, pdg, files)
+        .context("Failed to save PDG delta to storage")?;
+    info!(
+        "Saved PDG delta for project {}: {} files, {} nodes written, {} nodes removed, {} edges written",
+        project_id,
+        files.len(),
+        summary.nodes_written,
+        summary.nodes_removed,
+        summary.edges_written
+    );
+    Ok(())
+}
+
 /// Index nodes from PDG for search.
 ///
 /// Builds a TF-IDF embedder from the full corpus of node content, then uses
@@ -1302,12 +1324,14 @@ pub(crate) fn index_nodes_with_embedder(
                 .then_with(|| a.0.cmp(&b.0))
         });
 
-        let final_scores = if idf_scores.len() <= crate::search::search::DEFAULT_EMBEDDING_DIMENSION
-        {
+        // Size the vocabulary to the engine's configured dimension so every
+        // embedding produced here fits its vector index.
+        let target_dim = search_engine.embedding_dim();
+        let final_scores = if idf_scores.len() <= target_dim {
             idf_scores
         } else {
             let total = idf_scores.len();
-            let target = crate::search::search::DEFAULT_EMBEDDING_DIMENSION;
+            let target = target_dim;
             let stride = total as f64 / target as f64;
             (0..target)
                 .map(|i| {
@@ -1320,7 +1344,7 @@ pub(crate) fn index_nodes_with_embedder(
         let tfidf_embedder = TfIdfEmbedder {
             vocab: final_scores.iter().map(|(t, _)| t.clone()).collect(),
             idf: final_scores.iter().map(|(_, s)| *s).collect(),
-            dimension: crate::search::search::DEFAULT_EMBEDDING_DIMENSION,
+            dimension: target_dim,
             pdg_nodes: pdg.node_count(),
             pdg_edges: pdg.edge_count(),
         };
@@ -1341,7 +1365,7 @@ pub(crate) fn index_nodes_with_embedder(
                     HybridEmbedder::tfidf_only(TfIdfEmbedder {
                         vocab: final_scores.iter().map(|(t, _)| t.clone()).collect(),
                         idf: final_scores.iter().map(|(_, s)| *s).collect(),
-                        dimension: crate::search::search::DEFAULT_EMBEDDING_DIMENSION,
+                        dimension: target_dim,
                         pdg_nodes: pdg.node_count(),
                         pdg_edges: pdg.edge_count(),
                     })
@@ -1457,6 +1481,7 @@ pub(crate) fn index_nodes_with_embedder(
                     complexity: node.complexity,
                     signature,
                     pre_tokenized: Some(search_tokens),
+                    node_type: Some(node.node_type.clone()),
                 });
             }
         }
@@ -1668,15 +1693,17 @@ pub(crate) fn search_cache_key_for(
     query: &str,
     top_k: usize,
     query_type: Option<&crate::search::ranking::QueryType>,
+    threshold: Option<f32>,
     neural_available: bool,
 ) -> String {
     search_cache_key(&format!(
-        "query:{}:{}:{}:{}:{:?}:neural={}",
+        "query:{}:{}:{}:{}:{:?}:threshold={:?}:neural={}",
         stable_project_cache_id(project_id, project_path),
         index_fingerprint(stats),
         top_k,
         query.trim().to_lowercase(),
         query_type,
+        threshold,
         neural_available,
     ))
 }
@@ -1687,12 +1714,14 @@ pub(crate) fn analysis_cache_key_for(
     stats: &IndexStats,
     query: &str,
     token_budget: usize,
+    threshold: Option<f32>,
 ) -> String {
     analysis_cache_key(&format!(
-        "analyze:{}:{}:{}:{}",
+        "analyze:{}:{}:{}:threshold={:?}:{}",
         stable_project_cache_id(project_id, project_path),
         index_fingerprint(stats),
         token_budget,
+        threshold,
         query.trim().to_lowercase()
     ))
 }
@@ -2261,6 +2290,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![PathBuf::from("/project/src")],
             manifest_hashes: old_hashes,
+            oversized_paths: Vec::new(),
         };
 
         // Serialize and store in cache
@@ -2295,6 +2325,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![],
             manifest_hashes: current_hashes,
+            oversized_paths: Vec::new(),
         };
 
         // Without the fix, this would return the manifest as changed (false positive)
@@ -2391,6 +2422,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![PathBuf::from("/project/src")],
             manifest_hashes: old_hashes,
+            oversized_paths: Vec::new(),
         };
 
         let cache_key = crate::cli::memory::project_scan_cache_key("test_project2");
@@ -2416,6 +2448,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![PathBuf::from("/project/src")],
             manifest_hashes: current_hashes,
+            oversized_paths: Vec::new(),
         };
 
         let changed = detect_changed_manifests(&current_scan, "test_project2", &spiller);
@@ -2460,6 +2493,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![],
             manifest_hashes: disk_hashes,
+            oversized_paths: Vec::new(),
         };
         let cache_key = crate::cli::memory::project_scan_cache_key("test_project3");
         let serialized = bincode::serialize(&disk_scan).unwrap();
@@ -2485,6 +2519,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![],
             manifest_hashes: mem_hashes,
+            oversized_paths: Vec::new(),
         };
         let mem_serialized = bincode::serialize(&mem_scan).unwrap();
         let mem_entry = crate::cli::memory::CacheEntry::Binary {
@@ -2508,6 +2543,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![PathBuf::from("/project/src")],
             manifest_hashes: current_hashes,
+            oversized_paths: Vec::new(),
         };
 
         let changed = detect_changed_manifests(&current_scan, "test_project3", &spiller);
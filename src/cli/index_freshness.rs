@@ -928,6 +928,7 @@ mod tests {
             manifest_paths_canonical: Vec::new(),
             source_directories: vec![],
             manifest_hashes: std::collections::HashMap::new(),
+            oversized_paths: Vec::new(),
         };
         assert!(
             scan.manifest_paths_canonical.is_empty(),
@@ -1,7 +1,7 @@
 // Diagnostics and coverage reporting methods for LeIndex.
 
 use super::LeIndex;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use std::collections::HashSet;
 
 impl LeIndex {
@@ -152,4 +152,99 @@ impl LeIndex {
             },
         })
     }
+
+    /// Parse errors recorded by the last indexing runs, ordered by file
+    ///
+    /// Files that failed to parse are always recorded; syntax errors in files
+    /// that parsed are only recorded when `indexing.record_parse_errors` is
+    /// enabled in the project config.
+    pub fn parse_errors(&self) -> Result<Vec<crate::storage::ParseErrorRecord>> {
+        crate::storage::load_parse_errors(&self.storage, &self.project_id)
+            .context("Failed to load parse errors from storage")
+    }
+
+    /// Check the project database for corruption and PDG inconsistencies
+    ///
+    /// Runs SQLite's `quick_check` first and fails with the problems it
+    /// reports; otherwise returns the
+    /// [`verify`](crate::storage::pdg_store::verify) report of the stored PDG.
+    pub fn verify_storage(&self) -> Result<crate::storage::pdg_store::ConsistencyReport> {
+        let problems: Vec<String> = self
+            .storage
+            .conn()
+            .prepare("PRAGMA quick_check")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()
+            })
+            .context("Failed to run the storage integrity check")?;
+        if problems.iter().any(|problem| problem != "ok") {
+            bail!("Storage integrity check failed: {}", problems.join("; "));
+        }
+        crate::storage::pdg_store::verify(&self.storage, &self.project_id)
+            .context("Failed to verify the stored PDG")
+    }
+
+    /// Remove orphaned and dangling rows from the project database
+    ///
+    /// Deletes nodes of files that are no longer indexed (with their
+    /// embeddings), edges to missing nodes, and global symbols of projects
+    /// with no data left. With `dry_run`, only counts what would be removed.
+    /// The in-memory PDG is not affected.
+    pub fn gc(&mut self, dry_run: bool) -> Result<crate::storage::GcSummary> {
+        let summary = if dry_run {
+            crate::storage::gc_dry_run(&mut self.storage)
+        } else {
+            crate::storage::gc(&mut self.storage)
+        };
+        summary.context("Failed to garbage-collect storage")
+    }
+
+    /// Indexing generations recorded for this project, oldest first
+    ///
+    /// With `indexing.record_generations` enabled, every indexing run records
+    /// its symbol and edge sets under the generation hash of the indexed
+    /// files; the most recent
+    /// [`GENERATIONS_KEPT`](crate::storage::generations::GENERATIONS_KEPT)
+    /// are kept.
+    pub fn generations(&self) -> Result<Vec<crate::storage::GenerationInfo>> {
+        crate::storage::list_generations(&self.storage, &self.project_id)
+            .context("Failed to load index generations from storage")
+    }
+
+    /// Diff the symbols and edges of two recorded generations
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_a` - Generation to diff from (usually the older one)
+    /// * `gen_b` - Generation to diff to
+    ///
+    /// # Returns
+    ///
+    /// `Result<GenerationDiff>` - Symbols added, removed and modified going
+    /// from `gen_a` to `gen_b`, plus added and removed edges. Fails if
+    /// either generation is not recorded.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let gens = leindex.generations()?;
+    /// let [.., before, after] = gens.as_slice() else { return Ok(()) };
+    /// let diff = leindex.diff_generations(&before.generation, &after.generation)?;
+    /// ```
+    pub fn diff_generations(
+        &self,
+        gen_a: &str,
+        gen_b: &str,
+    ) -> Result<crate::storage::GenerationDiff> {
+        for generation in [gen_a, gen_b] {
+            if !crate::storage::has_generation(&self.storage, &self.project_id, generation)
+                .context("Failed to look up index generation")?
+            {
+                bail!("Unknown index generation '{}'", generation);
+            }
+        }
+        crate::storage::diff_generations(&self.storage, &self.project_id, gen_a, gen_b)
+            .context("Failed to diff index generations")
+    }
 }
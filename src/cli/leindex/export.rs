@@ -0,0 +1,66 @@
+// Streaming export of indexed nodes for external consumers.
+
+use super::{ExportedNode, LeIndex, EXPORT_PAGE_SIZE};
+use anyhow::{Context, Result};
+use std::io::Write;
+
+impl LeIndex {
+    /// Stream every stored node to `writer` as JSON lines
+    ///
+    /// Nodes are read from storage `EXPORT_PAGE_SIZE` at a time, so memory
+    /// stays bounded regardless of project size. Each line is one
+    /// [`ExportedNode`]; the embedding is included when the node is present
+    /// in the loaded search index.
+    ///
+    /// # Returns
+    ///
+    /// The number of nodes written
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let file = std::fs::File::create("nodes.jsonl")?;
+    /// let written = leindex.export_nodes_jsonl(std::io::BufWriter::new(file))?;
+    /// ```
+    pub fn export_nodes_jsonl<W: Write>(&self, mut writer: W) -> Result<usize> {
+        let mut offset = 0;
+        loop {
+            let page = crate::storage::pdg_store::load_nodes_page(
+                &self.storage,
+                &self.project_id,
+                offset,
+                EXPORT_PAGE_SIZE,
+            )
+            .context("Failed to load nodes page from storage")?;
+            let page_len = page.len();
+
+            for node in page {
+                let embedding = self
+                    .search_engine
+                    .node_embedding(&node.id)
+                    .map(|e| e.to_vec());
+                let exported = ExportedNode {
+                    id: node.id,
+                    symbol: node.name,
+                    file: node.file_path.to_string(),
+                    language: node.language,
+                    byte_range: node.byte_range,
+                    complexity: node.complexity,
+                    embedding,
+                };
+                serde_json::to_writer(&mut writer, &exported)
+                    .context("Failed to serialize exported node")?;
+                writer
+                    .write_all(b"\n")
+                    .context("Failed to write exported node")?;
+            }
+
+            offset += page_len;
+            if page_len < EXPORT_PAGE_SIZE {
+                break;
+            }
+        }
+        writer.flush().context("Failed to flush node export")?;
+        Ok(offset)
+    }
+}
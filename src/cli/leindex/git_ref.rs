@@ -0,0 +1,321 @@
+// Indexing a git ref straight from the object database, without a checkout.
+
+use super::{LeIndex, SKIP_DIRS, SOURCE_FILE_EXTENSIONS};
+use crate::cli::index_builder;
+use anyhow::{bail, Context, Result};
+use git2::{ObjectType, Oid, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Git file mode of a symbolic link
+const GIT_FILEMODE_LINK: i32 = 0o120000;
+
+/// A source blob in the tree of a commit
+struct SourceBlob {
+    /// Path relative to the indexed directory, `/`-separated
+    path: String,
+    /// Blob object ID
+    oid: Oid,
+    /// Blob size in bytes
+    size: u64,
+}
+
+/// The tree of `commit` at `project_path`, which may be a subdirectory of
+/// the repository's working directory
+fn project_tree<'r>(
+    repo: &'r Repository,
+    commit: &git2::Commit<'r>,
+    project_path: &Path,
+) -> Result<Tree<'r>> {
+    let tree = commit.tree().context("Failed to read commit tree")?;
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?
+        .canonicalize()
+        .context("Failed to resolve repository working directory")?;
+    let prefix = project_path
+        .strip_prefix(&workdir)
+        .with_context(|| format!("{} is outside the repository", project_path.display()))?;
+    if prefix.as_os_str().is_empty() {
+        return Ok(tree);
+    }
+    let entry = tree
+        .get_path(prefix)
+        .with_context(|| format!("{} is not in the commit", prefix.display()))?;
+    entry
+        .to_object(repo)?
+        .peel_to_tree()
+        .with_context(|| format!("{} is not a directory in the commit", prefix.display()))
+}
+
+/// List the indexable source blobs of `tree`
+///
+/// Applies the same filters as a working-tree scan: hidden and skipped
+/// directories, source extensions and the project's exclusion patterns.
+/// Exclusions are checked against the path joined onto `project_root`, as
+/// in a working-tree scan, so patterns such as `*/generated/*` match top-level
+/// directories too. Blob sizes come from the object headers, so nothing is
+/// read yet.
+fn list_source_blobs(
+    repo: &Repository,
+    tree: &Tree<'_>,
+    project_root: &Path,
+    config: &crate::cli::config::ProjectConfig,
+) -> Result<Vec<SourceBlob>> {
+    let odb = repo.odb().context("Failed to open git object database")?;
+    let mut blobs = Vec::new();
+    let mut error = None;
+    let walked = tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        let Some(name) = entry.name() else {
+            return TreeWalkResult::Skip;
+        };
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                if name.starts_with('.') || SKIP_DIRS.contains(&name) {
+                    TreeWalkResult::Skip
+                } else {
+                    TreeWalkResult::Ok
+                }
+            }
+            // Symlinks are blobs too, but their contents are a target path
+            Some(ObjectType::Blob) if entry.filemode() != GIT_FILEMODE_LINK => {
+                let path = format!("{}{}", root, name);
+                let is_source = Path::new(name)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.to_ascii_lowercase())
+                    .is_some_and(|ext| SOURCE_FILE_EXTENSIONS.contains(&ext.as_str()));
+                if name.starts_with('.')
+                    || !is_source
+                    || config.should_exclude(project_root.join(&path))
+                {
+                    return TreeWalkResult::Ok;
+                }
+                match odb.read_header(entry.id()) {
+                    Ok((size, _)) => {
+                        blobs.push(SourceBlob {
+                            path,
+                            oid: entry.id(),
+                            size: size as u64,
+                        });
+                        TreeWalkResult::Ok
+                    }
+                    Err(e) => {
+                        error = Some(
+                            anyhow::Error::new(e)
+                                .context(format!("Failed to read git object header of {}", path)),
+                        );
+                        TreeWalkResult::Abort
+                    }
+                }
+            }
+            _ => TreeWalkResult::Ok,
+        }
+    });
+    if let Some(error) = error {
+        return Err(error);
+    }
+    walked.context("Failed to walk commit tree")?;
+    Ok(blobs)
+}
+
+impl LeIndex {
+    /// Index the tree of a git ref without checking it out
+    ///
+    /// `git_ref` is any revision git accepts: a branch, tag, commit or
+    /// expression such as `HEAD~1`. Source files are read from the git
+    /// object database, so the working tree is neither read nor modified.
+    ///
+    /// The index is stored under the project ID `<project>@<commit sha>`,
+    /// next to but separate from the working-tree index, so two refs can be
+    /// indexed and compared, and a ref that moved to a new commit gets a
+    /// fresh index instead of mixing snapshots. Node file paths are rooted at the repository as
+    /// usual. The search index is built in memory only; the working tree's
+    /// persisted embedder and stats files are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - Path to the repository; a subdirectory indexes only that
+    ///   part of the tree
+    /// * `git_ref` - Ref to index
+    ///
+    /// # Returns
+    ///
+    /// `Result<LeIndex>` - An instance holding the ref's index; see
+    /// [`get_stats`](Self::get_stats) for what was indexed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let old = LeIndex::index_git_ref("/path/to/repo", "v1.0")?;
+    /// let new = LeIndex::index_git_ref("/path/to/repo", "main")?;
+    /// ```
+    pub fn index_git_ref<P: AsRef<Path>>(repo: P, git_ref: &str) -> Result<Self> {
+        let start_time = std::time::Instant::now();
+        if git_ref.is_empty() || git_ref.starts_with('-') {
+            bail!("Invalid git ref '{}'", git_ref);
+        }
+
+        let mut index = Self::new(repo)?;
+        let repository = Repository::discover(&index.project_path).with_context(|| {
+            format!(
+                "{} is not in a git repository",
+                index.project_path.display()
+            )
+        })?;
+        let commit = repository
+            .revparse_single(git_ref)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Failed to resolve git ref '{}'", git_ref))?;
+        index.project_id = format!("{}@{}", index.project_id, commit.id());
+        info!(
+            "Indexing git ref '{}' ({}) of {}",
+            git_ref,
+            commit.id(),
+            index.project_path.display()
+        );
+
+        // Read source blobs, skipping the ones over the size limit
+        let tree = project_tree(&repository, &commit, &index.project_path)?;
+        let max_file_size = index.project_config.indexing.max_file_size;
+        let (blobs, oversized): (Vec<_>, Vec<_>) = list_source_blobs(
+            &repository,
+            &tree,
+            &index.project_path,
+            &index.project_config,
+        )?
+        .into_iter()
+        .partition(|blob| max_file_size == 0 || blob.size <= max_file_size);
+        let sources = blobs
+            .iter()
+            .map(|blob| {
+                let bytes = repository
+                    .find_blob(blob.oid)
+                    .with_context(|| format!("Failed to read git blob of {}", blob.path))?
+                    .content()
+                    .to_vec();
+                Ok((index.project_path.join(&blob.path), bytes))
+            })
+            .collect::<Result<Vec<(PathBuf, Vec<u8>)>>>()?;
+
+        // Search indexing reads node content through the file cache; seed it
+        // with the blobs so nothing is read from the working tree
+        let mut file_cache = index_builder::FileReadCache::new(sources.len());
+        for (path, bytes) in &sources {
+            file_cache.insert(path.clone(), Arc::new(bytes.clone()));
+        }
+        let source_files: Vec<(PathBuf, String)> = sources
+            .iter()
+            .map(|(path, _)| path.clone())
+            .zip(blobs.iter().map(|blob| blob.oid.to_string()))
+            .collect();
+
+        let parsing_results = index.parallel_parser().parse_sources(sources);
+        let successful = parsing_results.iter().filter(|r| r.is_success()).count();
+        let failed = parsing_results.iter().filter(|r| r.is_failure()).count();
+        let total_sigs: usize = parsing_results.iter().map(|r| r.signatures.len()).sum();
+        let signatures_by_language = super::indexing::signatures_by_language(&parsing_results);
+        let slowest_files =
+            super::FileParseTiming::slowest(&parsing_results, super::SLOWEST_FILES_REPORTED);
+        for result in parsing_results.iter().filter(|r| r.is_failure()) {
+            warn!(
+                "Parse failure for '{}' at git ref '{}': {}",
+                result.file_path.display(),
+                git_ref,
+                result.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        // Build the PDG
+        let all_signatures: Vec<(String, crate::parse::prelude::SignatureInfo)> = parsing_results
+            .iter()
+            .filter(|r| r.is_success())
+            .flat_map(|r| {
+                let file_path = r.file_path.display().to_string();
+                r.signatures
+                    .iter()
+                    .cloned()
+                    .map(move |sig| (file_path.clone(), sig))
+            })
+            .collect();
+        let mut pdg = crate::graph::pdg::ProgramDependenceGraph::new();
+        for result in parsing_results.into_iter().filter(|r| r.is_success()) {
+            let file_pdg = crate::graph::extract_pdg_from_signatures(
+                result.signatures,
+                result.source_bytes.as_deref().unwrap_or(&[]),
+                &result.file_path.display().to_string(),
+                result.language.as_deref().unwrap_or("unknown"),
+            );
+            index_builder::merge_pdgs(&mut pdg, file_pdg);
+        }
+        if !all_signatures.is_empty() {
+            crate::graph::resolve_cross_file_call_edges_for_files(&mut pdg, &all_signatures);
+        }
+
+        // Build the search index from the cached blobs
+        let batch_size = index.indexing_batch_size();
+        let embedder = index_builder::index_nodes_with_embedder(
+            &pdg,
+            &mut index.search_engine,
+            &mut index.cache.file_stats_cache,
+            batch_size,
+            None,
+            Some(file_cache),
+        )?;
+        index.embedder = Some(embedder);
+
+        // Replace whatever an earlier run stored for this commit
+        crate::storage::pdg_store::delete_pdg(&mut index.storage, &index.project_id)
+            .context("Failed to clear previous index of git ref")?;
+        index_builder::save_to_storage(&mut index.storage, &index.project_id, &pdg)?;
+        for (path, oid) in &source_files {
+            if let Err(e) = crate::storage::pdg_store::update_indexed_file(
+                &mut index.storage,
+                &index.project_id,
+                &path.display().to_string(),
+                oid,
+            ) {
+                warn!(
+                    "Failed to record indexed file '{}' for git ref '{}': {}",
+                    path.display(),
+                    git_ref,
+                    e
+                );
+            }
+        }
+
+        index.stats = super::IndexStats {
+            total_files: source_files.len(),
+            files_parsed: source_files.len(),
+            successful_parses: successful,
+            failed_parses: failed,
+            total_signatures: total_sigs,
+            pdg_nodes: pdg.node_count(),
+            pdg_edges: pdg.edge_count(),
+            indexed_nodes: index.search_engine.node_count(),
+            indexing_time_ms: start_time.elapsed().as_millis() as u64,
+            external_deps_in_lockfile: 0,
+            external_deps_resolved: 0,
+            external_deps_unresolved: 0,
+            external_deps_total: 0,
+            external_deps_builtin: 0,
+            slowest_files,
+            language_stats: super::indexing::collect_language_stats(
+                &source_files,
+                signatures_by_language,
+                &pdg,
+            ),
+            skipped_oversized_files: oversized
+                .iter()
+                .map(|blob| index.project_path.join(&blob.path).display().to_string())
+                .collect(),
+            cancelled_files: 0,
+        };
+
+        index_builder::normalize_external_nodes(&mut pdg);
+        index.pdg = Some(pdg);
+        index.build_file_stats_cache();
+        Ok(index)
+    }
+}
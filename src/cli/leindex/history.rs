@@ -0,0 +1,47 @@
+// Query history and saved-search methods for LeIndex.
+
+use super::LeIndex;
+use crate::storage::query_history;
+use crate::storage::{QueryHistoryEntry, SavedQuery};
+use anyhow::{bail, Context, Result};
+
+impl LeIndex {
+    /// Append `query` to this project's search history
+    pub fn record_query(&mut self, query: &str) -> Result<()> {
+        query_history::record_query(&mut self.storage, &self.project_id, query)
+            .context("Failed to record query history")
+    }
+
+    /// Up to `limit` of this project's most recent queries, newest first
+    pub fn recent_queries(&self, limit: usize) -> Result<Vec<QueryHistoryEntry>> {
+        query_history::recent_queries(&self.storage, &self.project_id, limit)
+            .context("Failed to load query history")
+    }
+
+    /// Save `query` under `name`, replacing any query already saved under it
+    pub fn save_query(&mut self, name: &str, query: &str) -> Result<()> {
+        if name.trim().is_empty() {
+            bail!("Saved query name must not be empty");
+        }
+        query_history::save_query(&mut self.storage, &self.project_id, name, query)
+            .context("Failed to save query")
+    }
+
+    /// The query saved under `name`, if any
+    pub fn saved_query(&self, name: &str) -> Result<Option<SavedQuery>> {
+        query_history::load_saved_query(&self.storage, &self.project_id, name)
+            .context("Failed to load saved query")
+    }
+
+    /// All of this project's saved queries, ordered by name
+    pub fn saved_queries(&self) -> Result<Vec<SavedQuery>> {
+        query_history::load_saved_queries(&self.storage, &self.project_id)
+            .context("Failed to load saved queries")
+    }
+
+    /// Delete the query saved under `name`, returning whether one existed
+    pub fn forget_saved_query(&mut self, name: &str) -> Result<bool> {
+        query_history::delete_saved_query(&mut self.storage, &self.project_id, name)
+            .context("Failed to delete saved query")
+    }
+}
@@ -0,0 +1,264 @@
+// Import of externally-produced nodes and edges into the index.
+
+use super::{ImportedEdge, ImportedNode, LeIndex, IMPORTED_LANGUAGE};
+use crate::cli::index_builder;
+use crate::graph::pdg::{Edge, EdgeMetadata, EdgeType, Node, NodeType, ProgramDependenceGraph};
+use anyhow::{bail, Context, Result};
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Parse a node kind as written in import files
+fn parse_node_kind(kind: &str) -> Option<NodeType> {
+    match kind {
+        "function" => Some(NodeType::Function),
+        "class" => Some(NodeType::Class),
+        "method" => Some(NodeType::Method),
+        "variable" => Some(NodeType::Variable),
+        "module" => Some(NodeType::Module),
+        _ => None,
+    }
+}
+
+/// Parse an edge kind as written in import files
+fn parse_edge_kind(kind: &str) -> Option<EdgeType> {
+    match kind {
+        "call" => Some(EdgeType::Call),
+        "data_dependency" => Some(EdgeType::DataDependency),
+        "inheritance" => Some(EdgeType::Inheritance),
+        "import" => Some(EdgeType::Import),
+        "containment" => Some(EdgeType::Containment),
+        _ => None,
+    }
+}
+
+/// Parse every non-blank line of `reader` as JSON, keeping 1-based line numbers
+fn read_jsonl<T: serde::de::DeserializeOwned, R: BufRead>(
+    reader: R,
+    what: &str,
+) -> Result<Vec<(usize, T)>> {
+    let mut items = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.with_context(|| format!("line {}: failed to read input", line_no))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let item = serde_json::from_str(&line)
+            .with_context(|| format!("line {}: malformed {}", line_no, what))?;
+        items.push((line_no, item));
+    }
+    Ok(items)
+}
+
+impl LeIndex {
+    /// Import nodes produced by an external system from JSON lines
+    ///
+    /// Each line is one [`ImportedNode`]. All lines are validated before
+    /// anything is written, so a malformed line (reported with its line
+    /// number) leaves the index untouched, as does a failed storage write.
+    /// Valid nodes are tagged with the [`IMPORTED_LANGUAGE`] language,
+    /// upserted into storage and the in-memory PDG, and added to the search
+    /// index.
+    ///
+    /// # Returns
+    ///
+    /// The number of nodes imported
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let file = std::fs::File::open("nodes.jsonl")?;
+    /// leindex.import_nodes_jsonl(std::io::BufReader::new(file))?;
+    /// ```
+    pub fn import_nodes_jsonl<R: BufRead>(&mut self, reader: R) -> Result<usize> {
+        let mut nodes = Vec::new();
+        let mut contents = Vec::new();
+        for (line_no, imported) in read_jsonl::<ImportedNode, _>(reader, "node")? {
+            if imported.id.is_empty() || imported.symbol.is_empty() || imported.file.is_empty() {
+                bail!(
+                    "line {}: node requires non-empty id, symbol and file",
+                    line_no
+                );
+            }
+            if imported.byte_range.0 > imported.byte_range.1 {
+                bail!(
+                    "line {}: byte range start {} is past end {}",
+                    line_no,
+                    imported.byte_range.0,
+                    imported.byte_range.1
+                );
+            }
+            let kind = imported.kind.as_deref().unwrap_or("function");
+            let Some(node_type) = parse_node_kind(kind) else {
+                bail!("line {}: unknown node kind '{}'", line_no, kind);
+            };
+            let language = match &imported.language {
+                Some(lang) => format!("{}:{}", IMPORTED_LANGUAGE, lang.to_lowercase()),
+                None => IMPORTED_LANGUAGE.to_string(),
+            };
+            contents.push(imported.content.unwrap_or_default());
+            nodes.push(Node {
+                id: imported.id,
+                node_type,
+                name: imported.symbol,
+                file_path: Arc::from(imported.file),
+                byte_range: imported.byte_range,
+                complexity: imported.complexity,
+                language,
+            });
+        }
+        if nodes.is_empty() {
+            return Ok(0);
+        }
+
+        // Storage is written first, in one transaction, so a failed save
+        // leaves the in-memory PDG and search index matching it
+        self.ensure_pdg_loaded()?;
+        crate::storage::pdg_store::save_incremental(
+            &mut self.storage,
+            &self.project_id,
+            &nodes,
+            &[],
+            &[],
+            &[],
+        )
+        .context("Failed to store imported nodes")?;
+        let pdg = self.pdg.get_or_insert_with(ProgramDependenceGraph::new);
+        for node in &nodes {
+            // Upsert: a replaced node loses its old edges, matching storage
+            if let Some(existing) = pdg.find_by_id(&node.id) {
+                pdg.remove_node(existing);
+            }
+            pdg.add_node(node.clone());
+        }
+
+        let tfidf = match &self.embedder {
+            Some(_) => None,
+            None => index_builder::TfIdfEmbedder::load_from_storage(&self.project_path)
+                .ok()
+                .flatten(),
+        };
+        let updated_nodes = nodes
+            .iter()
+            .zip(contents)
+            .map(|(node, content)| {
+                let node_content = format!("// {} in {}\n{}", node.name, node.file_path, content);
+                let tokens = index_builder::tokenize_code(&node_content);
+                let tfidf_embedding = match (&self.embedder, &tfidf) {
+                    (Some(embedder), _) => embedder.embed_tfidf(&tokens),
+                    (None, Some(tfidf)) => tfidf.embed_tokens(&tokens),
+                    (None, None) => vec![0.0; self.search_engine.embedding_dim()],
+                };
+                crate::search::search::NodeInfo {
+                    node_id: node.id.clone(),
+                    file_path: node.file_path.to_string(),
+                    symbol_name: node.name.clone(),
+                    language: node.language.clone(),
+                    signature: crate::search::search::SearchEngine::extract_signature_from_content(
+                        &node_content,
+                    ),
+                    content: node_content,
+                    byte_range: node.byte_range,
+                    tfidf_embedding,
+                    neural_embedding: None,
+                    complexity: node.complexity,
+                    pre_tokenized: Some(tokens),
+                    node_type: Some(node.node_type.clone()),
+                }
+            })
+            .collect();
+        self.search_engine
+            .incremental_reindex(crate::search::search::TextIndexDelta {
+                removed_node_ids: Vec::new(),
+                updated_nodes,
+            });
+
+        if let Some(pdg) = &self.pdg {
+            self.stats.pdg_nodes = pdg.node_count();
+            self.stats.pdg_edges = pdg.edge_count();
+        }
+        self.stats.indexed_nodes = self.search_engine.node_count();
+        self.build_file_stats_cache();
+        Ok(nodes.len())
+    }
+
+    /// Import edges produced by an external system from JSON lines
+    ///
+    /// Each line is one [`ImportedEdge`] connecting two node IDs that must
+    /// already be indexed (e.g. by [`LeIndex::import_nodes_jsonl`]). As with
+    /// nodes, every line is validated before anything is written, and the
+    /// in-memory PDG only changes once storage has the edges.
+    ///
+    /// # Returns
+    ///
+    /// The number of edges imported
+    pub fn import_edges_jsonl<R: BufRead>(&mut self, reader: R) -> Result<usize> {
+        self.ensure_pdg_loaded()?;
+        let Some(pdg) = self.pdg.as_ref() else {
+            bail!("No nodes are indexed; import nodes before edges");
+        };
+
+        let mut resolved = Vec::new();
+        let mut stored = Vec::new();
+        for (line_no, imported) in read_jsonl::<ImportedEdge, _>(reader, "edge")? {
+            let kind = imported.edge_type.as_deref().unwrap_or("call");
+            let Some(edge_type) = parse_edge_kind(kind) else {
+                bail!("line {}: unknown edge type '{}'", line_no, kind);
+            };
+            let Some(caller) = pdg.find_by_id(&imported.caller) else {
+                bail!(
+                    "line {}: unknown caller node '{}'",
+                    line_no,
+                    imported.caller
+                );
+            };
+            let Some(callee) = pdg.find_by_id(&imported.callee) else {
+                bail!(
+                    "line {}: unknown callee node '{}'",
+                    line_no,
+                    imported.callee
+                );
+            };
+            let metadata = match imported.confidence {
+                Some(confidence) if (0.0..=1.0).contains(&confidence) => {
+                    EdgeMetadata::with_confidence(confidence)
+                }
+                Some(confidence) => {
+                    bail!(
+                        "line {}: confidence {} is outside 0.0..=1.0",
+                        line_no,
+                        confidence
+                    );
+                }
+                None => EdgeMetadata::empty(),
+            };
+            let edge = Edge {
+                edge_type,
+                metadata,
+            };
+            stored.push((imported.caller, imported.callee, edge.clone()));
+            resolved.push((caller, callee, edge));
+        }
+        if resolved.is_empty() {
+            return Ok(0);
+        }
+
+        // As with nodes, the PDG only changes once storage has the edges
+        crate::storage::pdg_store::save_incremental(
+            &mut self.storage,
+            &self.project_id,
+            &[],
+            &[],
+            &stored,
+            &[],
+        )
+        .context("Failed to store imported edges")?;
+        if let Some(pdg) = self.pdg.as_mut() {
+            for (caller, callee, edge) in resolved {
+                pdg.add_edge(caller, callee, edge);
+            }
+            self.stats.pdg_edges = pdg.edge_count();
+        }
+        Ok(stored.len())
+    }
+}
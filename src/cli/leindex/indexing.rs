@@ -3,8 +3,10 @@
 use super::LeIndex;
 use crate::cli::index_builder;
 use crate::cli::memory_cap::MemoryCapGuard;
+use crate::phase::CancellationToken;
 use anyhow::{bail, Context, Result};
 use std::collections::HashSet;
+use std::path::PathBuf;
 use tracing::{info, warn};
 
 /// Write a progress line to stderr if stderr is a terminal.
@@ -32,7 +34,159 @@ fn progress_clear() {
     }
 }
 
+/// Classify discovered source files against the stored file hashes
+fn plan_from_hashes(
+    force: bool,
+    source_files_with_hashes: &[(PathBuf, String)],
+    indexed_files: &std::collections::HashMap<String, String>,
+) -> super::IndexPlan {
+    let mut plan = super::IndexPlan {
+        force,
+        ..Default::default()
+    };
+    let mut current_file_paths = HashSet::new();
+    for (path, hash) in source_files_with_hashes {
+        let path_str = path.display().to_string();
+        if force || indexed_files.get(&path_str) != Some(hash) {
+            plan.to_parse.push(path.clone());
+        } else {
+            plan.unchanged.push(path.clone());
+        }
+        current_file_paths.insert(path_str);
+    }
+
+    plan.deleted = indexed_files
+        .keys()
+        .filter(|p| !current_file_paths.contains(*p))
+        .cloned()
+        .collect();
+    plan.deleted.sort();
+    if !force {
+        pair_renamed_files(&mut plan, source_files_with_hashes, indexed_files);
+    }
+    plan
+}
+
+/// Move (deleted, new) file pairs with identical content into `plan.renamed`
+///
+/// A new file matches a deleted one when both share a content hash and an
+/// extension (so the language, and therefore the parse, is the same). Each
+/// deleted file is paired at most once, in sorted order.
+fn pair_renamed_files(
+    plan: &mut super::IndexPlan,
+    source_files_with_hashes: &[(PathBuf, String)],
+    indexed_files: &std::collections::HashMap<String, String>,
+) {
+    if plan.deleted.is_empty() || plan.to_parse.is_empty() {
+        return;
+    }
+    let new_hashes: std::collections::HashMap<&PathBuf, &String> = source_files_with_hashes
+        .iter()
+        .map(|(path, hash)| (path, hash))
+        .collect();
+
+    let mut claimed = HashSet::new();
+    plan.to_parse.retain(|path| {
+        let path_str = path.display().to_string();
+        if indexed_files.contains_key(&path_str) {
+            return true;
+        }
+        let Some(hash) = new_hashes.get(path) else {
+            return true;
+        };
+        let candidate = plan.deleted.iter().find(|old| {
+            !claimed.contains(*old)
+                && indexed_files.get(*old) == Some(*hash)
+                && std::path::Path::new(old.as_str()).extension() == path.extension()
+        });
+        match candidate {
+            Some(old) => {
+                claimed.insert(old.clone());
+                plan.renamed.push((old.clone(), path.clone()));
+                false
+            }
+            None => true,
+        }
+    });
+    plan.deleted.retain(|old| !claimed.contains(old));
+}
+
+/// Count parsed signatures per lowercase language name
+pub(super) fn signatures_by_language(
+    results: &[crate::parse::parallel::ParsingResult],
+) -> std::collections::HashMap<String, usize> {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for result in results {
+        let language = result
+            .language
+            .as_deref()
+            .unwrap_or("unknown")
+            .to_ascii_lowercase();
+        *counts.entry(language).or_default() += result.signatures.len();
+    }
+    counts
+}
+
+/// Break file, signature and node counts down by lowercase language name
+///
+/// Files are attributed by extension, signatures come from the files parsed
+/// in this run (matching `IndexStats::total_signatures`), and nodes use each
+/// PDG node's own language, so external modules are counted as `external`.
+pub(super) fn collect_language_stats(
+    source_files: &[(PathBuf, String)],
+    signatures_by_language: std::collections::HashMap<String, usize>,
+    pdg: &crate::graph::pdg::ProgramDependenceGraph,
+) -> std::collections::HashMap<String, super::LanguageStat> {
+    let mut stats: std::collections::HashMap<String, super::LanguageStat> =
+        std::collections::HashMap::new();
+    for (path, _) in source_files {
+        let language = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(crate::parse::grammar::LanguageId::from_extension)
+            .map(|id| id.config().name.to_ascii_lowercase())
+            .unwrap_or_else(|| "unknown".to_string());
+        stats.entry(language).or_default().files += 1;
+    }
+    for (language, count) in signatures_by_language {
+        stats.entry(language).or_default().signatures += count;
+    }
+    for node in pdg.node_indices().filter_map(|id| pdg.get_node(id)) {
+        stats
+            .entry(node.language.to_ascii_lowercase())
+            .or_default()
+            .nodes += 1;
+    }
+    stats
+}
+
 impl LeIndex {
+    /// Report what [`index_project`](Self::index_project) would change
+    ///
+    /// Runs the discovery, hashing and diff steps of indexing only: nothing
+    /// is parsed and neither storage nor the cached project scan is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `force` - Plan a forced re-index, listing every source file to parse
+    ///
+    /// # Returns
+    ///
+    /// `Result<IndexPlan>` - Files to parse, unchanged, deleted, and renamed
+    pub fn plan_index(&self, force: bool) -> Result<super::IndexPlan> {
+        let indexed_files =
+            crate::storage::pdg_store::get_indexed_files(&self.storage, &self.project_id)
+                .context("Failed to load indexed files from storage")?;
+        let scan = self.scan_project_files()?;
+        let source_files_with_hashes =
+            index_builder::collect_source_files_with_hashes(&scan, None)?;
+        Ok(plan_from_hashes(
+            force,
+            &source_files_with_hashes,
+            &indexed_files,
+        ))
+    }
+
     pub(crate) fn incremental_reindex_from_watcher(&mut self) -> Result<super::IndexStats> {
         let start_time = std::time::Instant::now();
         let indexed_files =
@@ -75,12 +229,269 @@ impl LeIndex {
             return Ok(self.stats.clone());
         }
 
-        let parser = crate::parse::parallel::ParallelParser::new();
+        self.apply_file_changes(
+            changed_files,
+            deleted_files,
+            &source_file_hashes,
+            shared_file_cache,
+            start_time,
+        )?;
+        info!(
+            "Watcher incremental reindex completed in {}ms",
+            self.stats.indexing_time_ms
+        );
+        Ok(self.stats.clone())
+    }
+
+    /// Reindex an explicit list of changed files, skipping tree discovery
+    ///
+    /// Intended for CI and other callers that already know which files a
+    /// commit touched. Relative paths are resolved against the project root.
+    /// Existing supported source files are reparsed; missing files that were
+    /// indexed are removed from the PDG, storage and search index, as are
+    /// files grown past `indexing.max_file_size`. Paths that are neither
+    /// (outside the project, unsupported, oversized, or never indexed) are
+    /// skipped with a warning.
+    ///
+    /// # Returns
+    ///
+    /// `Result<IndexStats>` - Statistics where `files_parsed` counts only
+    /// the reparsed files
+    pub fn index_changed(&mut self, paths: &[PathBuf]) -> Result<super::IndexStats> {
+        let start_time = std::time::Instant::now();
+        self.ensure_pdg_loaded()?;
+        let indexed_files =
+            crate::storage::pdg_store::get_indexed_files(&self.storage, &self.project_id)
+                .context("Failed to load indexed files from storage")?;
+
+        let mut file_cache = index_builder::FileReadCache::new(100);
+        let mut source_file_hashes = std::collections::HashMap::new();
+        let mut changed_files = Vec::new();
+        let mut deleted_files = Vec::new();
+        for path in paths {
+            let path = if path.is_relative() {
+                self.project_path.join(path)
+            } else {
+                path.clone()
+            };
+            let path_str = path.display().to_string();
+            if !path.starts_with(&self.project_path) {
+                warn!("Skipping '{}': outside project root", path_str);
+                continue;
+            }
+            if !path.exists() {
+                if indexed_files.contains_key(&path_str) {
+                    deleted_files.push(path_str);
+                } else {
+                    warn!(
+                        "Skipping '{}': file does not exist and is not indexed",
+                        path_str
+                    );
+                }
+                continue;
+            }
+            let supported = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(crate::parse::grammar::LanguageId::from_extension)
+                .is_some();
+            if !path.is_file() || !supported {
+                warn!("Skipping '{}': not a supported source file", path_str);
+                continue;
+            }
+            let max_file_size = self.project_config.indexing.max_file_size;
+            let file_size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if max_file_size > 0 && file_size > max_file_size {
+                warn!(
+                    "Skipping '{}': {} bytes exceeds max_file_size ({} bytes)",
+                    path_str, file_size, max_file_size
+                );
+                // A full scan would no longer list it either
+                if indexed_files.contains_key(&path_str) {
+                    deleted_files.push(path_str);
+                }
+                continue;
+            }
+            let bytes = file_cache
+                .get_or_read(&path)
+                .with_context(|| format!("Failed to read changed file '{}'", path_str))?;
+            source_file_hashes.insert(
+                path_str,
+                blake3::hash(bytes.as_slice()).to_hex().to_string(),
+            );
+            changed_files.push(path);
+        }
+
+        if changed_files.is_empty() && deleted_files.is_empty() {
+            return Ok(self.stats.clone());
+        }
+
+        self.apply_file_changes(
+            changed_files,
+            deleted_files,
+            &source_file_hashes,
+            file_cache,
+            start_time,
+        )?;
+        info!(
+            "Changed-file reindex completed in {}ms",
+            self.stats.indexing_time_ms
+        );
+        Ok(self.stats.clone())
+    }
+
+    /// Store the parse failures and syntax errors of `results`, replacing
+    /// those recorded for the same files
+    ///
+    /// Failures are always recorded; syntax errors are only present when
+    /// `indexing.record_parse_errors` enabled the parser's second pass.
+    fn record_parse_errors(&self, results: &[crate::parse::parallel::ParsingResult]) {
+        if results.is_empty() {
+            return;
+        }
+        let files: Vec<String> = results
+            .iter()
+            .map(|result| result.file_path.display().to_string())
+            .collect();
+        let mut errors = Vec::new();
+        for (result, file_path) in results.iter().zip(&files) {
+            if let Some(message) = &result.error {
+                errors.push(crate::storage::ParseErrorRecord {
+                    file_path: file_path.clone(),
+                    byte_range: None,
+                    line: None,
+                    column: None,
+                    message: message.clone(),
+                });
+            }
+            errors.extend(result.syntax_errors.iter().map(|span| {
+                crate::storage::ParseErrorRecord {
+                    file_path: file_path.clone(),
+                    byte_range: Some(span.byte_range),
+                    line: Some(span.line),
+                    column: Some(span.column),
+                    message: span.message.clone(),
+                }
+            }));
+        }
+        if let Err(e) =
+            crate::storage::replace_parse_errors(&self.storage, &self.project_id, &files, &errors)
+        {
+            warn!("Failed to record parse errors: {}", e);
+        }
+    }
+
+    /// Record the symbol and edge sets of the current index under its
+    /// generation hash so later generations can be diffed against it
+    ///
+    /// The generation hash is computed from the indexed file hashes as in
+    /// [`crate::phase::freshness`]. Node fingerprints hash the indexed
+    /// content, so a symbol whose body changed shows up as modified. Does
+    /// nothing unless `indexing.record_generations` is enabled.
+    fn record_generation(&self) {
+        if !self.project_config.indexing.record_generations {
+            return;
+        }
+        let Some(pdg) = &self.pdg else {
+            return;
+        };
+        let indexed_files =
+            match crate::storage::pdg_store::get_indexed_files(&self.storage, &self.project_id) {
+                Ok(files) => files,
+                Err(e) => {
+                    warn!("Failed to load indexed files for generation: {}", e);
+                    return;
+                }
+            };
+        let mut inventory: Vec<(PathBuf, String)> = indexed_files
+            .iter()
+            .map(|(path, hash)| (PathBuf::from(path), hash.clone()))
+            .collect();
+        inventory.sort();
+        let generation = match crate::phase::freshness::compute_freshness(
+            &self.project_path,
+            inventory,
+            &indexed_files,
+        ) {
+            Ok(state) => state.generation_hash,
+            Err(e) => {
+                warn!("Failed to compute index generation: {e:#}");
+                return;
+            }
+        };
+
+        let nodes: Vec<crate::storage::GenerationNode> = pdg
+            .node_indices()
+            .filter_map(|idx| pdg.get_node(idx))
+            .map(|node| {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(format!("{:?}\0{}\0", node.node_type, node.complexity).as_bytes());
+                match self.search_engine.node_info(&node.id) {
+                    Some(info) => hasher.update(info.content.as_bytes()),
+                    None => hasher.update(&(node.byte_range.1 - node.byte_range.0).to_le_bytes()),
+                };
+                crate::storage::GenerationNode {
+                    node_id: node.id.clone(),
+                    symbol_name: node.name.clone(),
+                    file_path: node.file_path.to_string(),
+                    node_type: format!("{:?}", node.node_type).to_lowercase(),
+                    fingerprint: hasher.finalize().to_hex().to_string(),
+                }
+            })
+            .collect();
+        let edges: Vec<crate::storage::GenerationEdge> = pdg
+            .edge_indices()
+            .filter_map(|idx| {
+                let (source, target) = pdg.edge_endpoints(idx)?;
+                Some(crate::storage::GenerationEdge {
+                    caller_id: pdg.get_node(source)?.id.clone(),
+                    callee_id: pdg.get_node(target)?.id.clone(),
+                    edge_type: format!("{:?}", pdg.get_edge(idx)?.edge_type).to_lowercase(),
+                })
+            })
+            .collect();
+
+        if let Err(e) = crate::storage::record_generation(
+            &self.storage,
+            &self.project_id,
+            &generation,
+            &nodes,
+            &edges,
+        ) {
+            warn!("Failed to record index generation: {}", e);
+        }
+    }
+
+    /// Reparse `changed_files`, drop `deleted_files`, and update the PDG,
+    /// storage and search index to match
+    ///
+    /// `source_file_hashes` holds the current content hash of each changed
+    /// file, keyed by its display path; `shared_file_cache` should already
+    /// hold their bytes.
+    fn apply_file_changes(
+        &mut self,
+        changed_files: Vec<PathBuf>,
+        deleted_files: Vec<String>,
+        source_file_hashes: &std::collections::HashMap<String, String>,
+        shared_file_cache: index_builder::FileReadCache,
+        start_time: std::time::Instant,
+    ) -> Result<()> {
+        // Only nodes from these files go into the incremental search delta
+        let changed_file_set: HashSet<String> = changed_files
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+
+        let parser = self.parallel_parser();
         let parsing_results = if changed_files.is_empty() {
             Vec::new()
         } else {
             parser.parse_files(changed_files)
         };
+        self.stats.files_parsed = parsing_results.len();
+        self.stats.successful_parses = parsing_results.iter().filter(|r| r.is_success()).count();
+        self.stats.failed_parses = parsing_results.len() - self.stats.successful_parses;
+        self.record_parse_errors(&parsing_results);
 
         let mut pdg = self.pdg.take().unwrap_or_default();
         let mut removed_node_ids = Vec::new();
@@ -135,17 +546,6 @@ impl LeIndex {
             }
         }
 
-        // Build the set of changed file paths so we only include nodes from
-        // those files in the incremental delta.
-        let changed_file_set: HashSet<String> = source_file_hashes
-            .keys()
-            .filter(|p| {
-                indexed_files.get(*p).map(|s| s.as_str())
-                    != source_file_hashes.get(*p).map(|s| s.as_str())
-            })
-            .cloned()
-            .collect();
-
         // Load the persisted embedder (built during the last full index) so we
         // can embed changed-file nodes with the same TF-IDF vocabulary.  Do NOT
         // call index_nodes_with_embedder() here — that processes ALL nodes and
@@ -287,6 +687,7 @@ impl LeIndex {
                 complexity: node.complexity,
                 signature,
                 pre_tokenized: Some(tokens),
+                node_type: Some(node.node_type.clone()),
             });
         }
 
@@ -329,7 +730,11 @@ impl LeIndex {
             }
         }
         self.build_file_stats_cache();
+        self.record_generation();
         self.stats.indexing_time_ms = start_time.elapsed().as_millis() as u64;
+        if let Err(err) = self.record_index_run() {
+            warn!("Failed to record indexing run: {err:#}");
+        }
 
         // R10: Persist embeddings to mmap file after watcher incremental reindex
         if let Err(err) =
@@ -351,12 +756,7 @@ impl LeIndex {
         // Clear search query and analysis caches so stale results are not
         // served after an incremental reindex (VAL-INDEX-005).
         index_builder::clear_query_caches(&mut self.cache.cache_spiller, &self.project_id);
-
-        info!(
-            "Watcher incremental reindex completed in {}ms",
-            self.stats.indexing_time_ms
-        );
-        Ok(self.stats.clone())
+        Ok(())
     }
 
     /// Index the project with an optional memory cap.
@@ -373,6 +773,23 @@ impl LeIndex {
         &mut self,
         force: bool,
         max_memory_bytes: Option<u64>,
+    ) -> Result<super::IndexStats> {
+        self.index_project_with_cancellation(force, max_memory_bytes, &CancellationToken::new())
+    }
+
+    /// Index the project like
+    /// [`index_project_with_memory_cap`](Self::index_project_with_memory_cap),
+    /// stopping early once `cancel` fires
+    ///
+    /// Cancellation is checked between files: files already being parsed
+    /// finish, the remaining ones are left for the next run, and everything
+    /// parsed so far is saved so storage stays consistent. The returned stats
+    /// count the files left over in `cancelled_files`.
+    pub fn index_project_with_cancellation(
+        &mut self,
+        force: bool,
+        max_memory_bytes: Option<u64>,
+        cancel: &CancellationToken,
     ) -> Result<super::IndexStats> {
         let mut cap_guard = match max_memory_bytes {
             Some(bytes) => {
@@ -386,7 +803,7 @@ impl LeIndex {
             None => None,
         };
 
-        self.index_project_inner(force, cap_guard.as_mut())
+        self.index_project_inner(force, cap_guard.as_mut(), &mut |_| {}, cancel)
     }
 
     /// Index the project
@@ -405,15 +822,61 @@ impl LeIndex {
     ///
     /// `Result<IndexStats>` - Statistics from the indexing operation
     pub fn index_project(&mut self, force: bool) -> Result<super::IndexStats> {
-        self.index_project_inner(force, None)
+        self.index_project_with_progress(force, &mut |_| {})
+    }
+
+    /// Index the project, reporting progress through `progress`
+    ///
+    /// Events follow the pipeline phases: [`IndexProgress::Scanned`] once
+    /// discovery finishes, one [`IndexProgress::Parsing`] per parsed file,
+    /// [`IndexProgress::PdgBuildStarted`]/[`IndexProgress::PdgBuildFinished`]
+    /// around PDG construction, and [`IndexProgress::Done`] at the end. The
+    /// callback runs on the calling thread.
+    ///
+    /// [`IndexProgress::Scanned`]: super::IndexProgress::Scanned
+    /// [`IndexProgress::Parsing`]: super::IndexProgress::Parsing
+    /// [`IndexProgress::PdgBuildStarted`]: super::IndexProgress::PdgBuildStarted
+    /// [`IndexProgress::PdgBuildFinished`]: super::IndexProgress::PdgBuildFinished
+    /// [`IndexProgress::Done`]: super::IndexProgress::Done
+    pub fn index_project_with_progress(
+        &mut self,
+        force: bool,
+        progress: &mut dyn FnMut(super::IndexProgress),
+    ) -> Result<super::IndexStats> {
+        self.index_project_inner(force, None, progress, &CancellationToken::new())
+    }
+
+    /// Shared indexing implementation used by `index_project_with_progress`
+    /// and `index_project_with_memory_cap`.
+    /// Drop an index an older version stored under the directory name
+    ///
+    /// Rows used to be keyed by the directory name rather than the unique ID.
+    /// Runs before indexing, which rebuilds them under the unique ID; it is a
+    /// no-op once they are gone, and leaves them alone if another project in
+    /// the database may own that key.
+    fn migrate_legacy_project_key(&mut self) -> Result<()> {
+        let legacy_key = self.unique_id.base_name.clone();
+        let migrated = crate::storage::pdg_store::migrate_legacy_project_key(
+            &mut self.storage,
+            &legacy_key,
+            &self.project_path,
+        )
+        .context("Failed to remove the index stored under the legacy project key")?;
+        if migrated {
+            info!(
+                "Removed the index stored under the legacy project key '{}'",
+                legacy_key
+            );
+        }
+        Ok(())
     }
 
-    /// Shared indexing implementation used by both `index_project` and
-    /// `index_project_with_memory_cap`.
     fn index_project_inner(
         &mut self,
         force: bool,
         mut cap_guard: Option<&mut MemoryCapGuard>,
+        progress: &mut dyn FnMut(super::IndexProgress),
+        cancel: &CancellationToken,
     ) -> Result<super::IndexStats> {
         let start_time = std::time::Instant::now();
 
@@ -421,6 +884,7 @@ impl LeIndex {
             "Starting project indexing for: {} (force={})",
             self.project_id, force
         );
+        self.migrate_legacy_project_key()?;
 
         // Step 1: Get currently indexed files from storage
         progress_stderr("Indexing: scanning files...");
@@ -436,6 +900,17 @@ impl LeIndex {
         let source_files_with_hashes =
             self.collect_source_files_with_hashes(true, Some(&mut shared_file_cache))?;
         info!("Found {} source files", source_files_with_hashes.len());
+        let skipped_oversized_files: Vec<String> = self
+            .cache
+            .project_scan
+            .as_ref()
+            .map(|scan| {
+                scan.oversized_paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
 
         // Memory cap checkpoint: after file scanning (file cache populated)
         if let Some(ref mut guard) = cap_guard {
@@ -443,42 +918,61 @@ impl LeIndex {
         }
 
         // Step 3: Identify changed/new/deleted files
-        let mut files_to_parse = Vec::new();
-        let mut unchanged_files = std::collections::HashSet::new();
+        let plan = plan_from_hashes(force, &source_files_with_hashes, &indexed_files);
         let source_file_hashes: std::collections::HashMap<String, String> =
             source_files_with_hashes
                 .iter()
                 .map(|(path, hash)| (path.display().to_string(), hash.clone()))
                 .collect();
-
-        let current_file_paths: std::collections::HashSet<String> = source_files_with_hashes
+        let mut files_to_parse = plan.to_parse;
+        let unchanged_files: std::collections::HashSet<String> = plan
+            .unchanged
             .iter()
-            .map(|(p, _)| p.display().to_string())
+            .map(|path| path.display().to_string())
             .collect();
-
-        for (path, hash) in &source_files_with_hashes {
-            let path_str = path.display().to_string();
-            if force
-                || !indexed_files.contains_key(&path_str)
-                || indexed_files.get(&path_str) != Some(hash)
-            {
-                files_to_parse.push(path.clone());
-            } else {
-                unchanged_files.insert(path_str);
-            }
+        let deleted_files = plan.deleted;
+        let renamed_files = plan.renamed;
+
+        // Moved files keep their stored nodes under the new path, preserving
+        // node IDs (and the history keyed on them). They are still re-parsed,
+        // since module paths and import resolution depend on the file's
+        // location; if that parse fails the moved rows stay in place. The
+        // in-memory PDG still uses the old paths, so it is reloaded below.
+        for (old_path, new_path) in &renamed_files {
+            let new_path = new_path.display().to_string();
+            let moved = crate::storage::pdg_store::rename_file_data(
+                &mut self.storage,
+                &self.project_id,
+                old_path,
+                &new_path,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to move stored data from '{}' to '{}'",
+                    old_path, new_path
+                )
+            })?;
+            info!(
+                "Detected rename '{}' -> '{}' ({} nodes kept)",
+                old_path, new_path, moved
+            );
+        }
+        if !renamed_files.is_empty() {
+            self.pdg = None;
+            files_to_parse.extend(renamed_files.iter().map(|(_, new_path)| new_path.clone()));
         }
 
-        let deleted_files: Vec<String> = indexed_files
-            .keys()
-            .filter(|p| !current_file_paths.contains(*p))
-            .cloned()
-            .collect();
+        progress(super::IndexProgress::Scanned {
+            total_files: source_files_with_hashes.len(),
+            to_parse: files_to_parse.len(),
+        });
 
         info!(
-            "Incremental analysis: {} to parse, {} unchanged, {} deleted",
+            "Incremental analysis: {} to parse, {} unchanged, {} deleted, {} renamed",
             files_to_parse.len(),
             unchanged_files.len(),
-            deleted_files.len()
+            deleted_files.len(),
+            renamed_files.len()
         );
 
         if files_to_parse.is_empty() && deleted_files.is_empty() && self.is_indexed() {
@@ -516,6 +1010,8 @@ impl LeIndex {
                 };
                 if changed_manifests.is_empty() {
                     info!("No changes detected, skipping indexing");
+                    progress(super::IndexProgress::Done { files_parsed: 0 });
+                    self.stats.skipped_oversized_files = skipped_oversized_files;
                     return Ok(self.stats.clone());
                 }
                 info!(
@@ -532,31 +1028,107 @@ impl LeIndex {
             "Indexing: parsing {} files...",
             files_to_parse.len()
         ));
+        let parse_start = std::time::Instant::now();
+        let pending_files = files_to_parse.clone();
         let parsing_results = if !files_to_parse.is_empty() {
-            let parser = crate::parse::parallel::ParallelParser::new();
-            parser.parse_files(files_to_parse)
+            let parser = self.parallel_parser();
+            let total = files_to_parse.len();
+            // Workers signal each finished file; the callback runs here so it
+            // needs neither `Send` nor `Sync`
+            let (tx, rx) = std::sync::mpsc::channel::<()>();
+            std::thread::scope(|scope| {
+                let worker = scope.spawn(move || {
+                    parser.parse_files_until(
+                        files_to_parse,
+                        &|_| {
+                            let _ = tx.send(());
+                        },
+                        &|| cancel.is_cancelled(),
+                    )
+                });
+                for (done, ()) in rx.iter().enumerate() {
+                    progress(super::IndexProgress::Parsing {
+                        parsed: done + 1,
+                        total,
+                    });
+                }
+                worker.join().expect("parser thread panicked")
+            })
         } else {
             Vec::new()
         };
+        info!(
+            phase = "parse",
+            files = parsing_results.len(),
+            elapsed_ms = parse_start.elapsed().as_millis() as u64,
+            "Parsed source files"
+        );
 
         // Memory cap checkpoint: after parallel parsing (ASTs in memory)
         if let Some(ref mut guard) = cap_guard {
             guard.check_now()?;
         }
 
+        let parsed_paths: HashSet<&std::path::Path> = parsing_results
+            .iter()
+            .map(|result| result.file_path.as_path())
+            .collect();
+        let cancelled_files: Vec<String> = pending_files
+            .iter()
+            .filter(|path| !parsed_paths.contains(path.as_path()))
+            .map(|path| path.display().to_string())
+            .collect();
+        if !cancelled_files.is_empty() {
+            warn!(
+                "Indexing cancelled: {} file(s) left for the next run",
+                cancelled_files.len()
+            );
+        }
+
+        // On an incremental run the unchanged files' rows are still valid, so
+        // only the rewritten files are saved. A changed manifest can re-annotate
+        // external nodes anywhere in the graph, so it forces a full save.
+        // A cancelled run always saves a delta, even under `--force`: the files
+        // it never reached keep their stored rows and hashes for the next run.
+        let manifests_unchanged = match (&old_scan, self.cache.project_scan.as_ref()) {
+            (Some(old), Some(new)) => old.manifest_hashes == new.manifest_hashes,
+            _ => false,
+        };
+        let cancelled = !cancelled_files.is_empty();
+        let delta_save =
+            cancelled || (!force && !unchanged_files.is_empty() && manifests_unchanged);
+
         // Step 5: Update PDG
         progress_stderr("Indexing: building PDG...");
-        if !unchanged_files.is_empty() && self.pdg.is_none() {
-            self.load_pdg_from_storage()
-                .context("Failed to load existing PDG for incremental reindex. Please reindex with --force if corruption persists.")?;
+        let keeps_stored_files =
+            !unchanged_files.is_empty() || (cancelled && !indexed_files.is_empty());
+        if keeps_stored_files && self.pdg.is_none() {
+            if let Err(e) = self.load_pdg_from_storage() {
+                // A stored PDG that fails its checksum cannot be patched
+                // incrementally; drop it and rebuild from scratch
+                if let Some(corrupted @ crate::storage::PdgStoreError::Corrupted { .. }) =
+                    e.downcast_ref::<crate::storage::PdgStoreError>()
+                {
+                    warn!("{}; rebuilding the index", corrupted);
+                    crate::storage::pdg_store::delete_pdg(&mut self.storage, &self.project_id)
+                        .context("Failed to clear corrupted PDG")?;
+                    return self.index_project_inner(true, cap_guard, progress, cancel);
+                }
+                return Err(e).context("Failed to load existing PDG for incremental reindex. Please reindex with --force if corruption persists.");
+            }
         }
 
+        progress(super::IndexProgress::PdgBuildStarted);
+        let pdg_start = std::time::Instant::now();
         let mut pdg = self.pdg.take().unwrap_or_default();
         let files_parsed = parsing_results.len();
 
         let successful = parsing_results.iter().filter(|r| r.is_success()).count();
         let failed = parsing_results.iter().filter(|r| r.is_failure()).count();
         let total_sigs: usize = parsing_results.iter().map(|r| r.signatures.len()).sum();
+        let signatures_by_language = signatures_by_language(&parsing_results);
+        let slowest_files =
+            super::FileParseTiming::slowest(&parsing_results, super::SLOWEST_FILES_REPORTED);
 
         // Log individual parse failures with file path context
         for result in parsing_results.iter().filter(|r| r.is_failure()) {
@@ -578,6 +1150,7 @@ impl LeIndex {
                 successful + failed
             );
         }
+        self.record_parse_errors(&parsing_results);
 
         for path in &deleted_files {
             index_builder::remove_file_from_pdg(&mut pdg, path)?;
@@ -608,6 +1181,10 @@ impl LeIndex {
             })
             .collect();
 
+        // Files whose rows must be rewritten in storage: every successfully
+        // reparsed file plus every deleted one.
+        let mut rewritten_files = deleted_files.clone();
+
         // Iterate over parsing_results directly, avoiding intermediate HashMap construction
         // and the associated cloning of source_bytes, language, and signatures.
         for result in parsing_results.into_iter() {
@@ -630,6 +1207,7 @@ impl LeIndex {
                 language,
             );
             index_builder::merge_pdgs(&mut pdg, file_pdg);
+            rewritten_files.push(file_path.clone());
 
             if let Some(hash) = source_file_hashes.get(&file_path) {
                 if let Err(e) = crate::storage::pdg_store::update_indexed_file(
@@ -651,6 +1229,18 @@ impl LeIndex {
         // This pass uses all signatures to resolve cross-file call relationships.
         if !all_signatures.is_empty() {
             crate::graph::resolve_cross_file_call_edges_for_files(&mut pdg, &all_signatures);
+
+            // Calls into symbols indexed from other files (e.g. FFI) that only
+            // the global symbol table knows about
+            let symbol_table = crate::storage::GlobalSymbolTable::new(&self.storage);
+            if let Err(e) = crate::storage::resolve_call_edges_with_symbol_table(
+                &mut pdg,
+                &self.project_id,
+                &all_signatures,
+                &symbol_table,
+            ) {
+                warn!("Global symbol call resolution failed: {}", e);
+            }
         }
 
         // Step 5b: Resolve external dependencies via lock files
@@ -692,9 +1282,16 @@ impl LeIndex {
         let pdg_edge_count = pdg.edge_count();
 
         info!(
+            phase = "pdg_build",
+            elapsed_ms = pdg_start.elapsed().as_millis() as u64,
             "Updated PDG has {} nodes and {} edges",
-            pdg_node_count, pdg_edge_count
+            pdg_node_count,
+            pdg_edge_count
         );
+        progress(super::IndexProgress::PdgBuildFinished {
+            nodes: pdg_node_count,
+            edges: pdg_edge_count,
+        });
 
         // Memory cap checkpoint: after PDG construction (peak PDG memory usage)
         if let Some(ref mut guard) = cap_guard {
@@ -709,7 +1306,11 @@ impl LeIndex {
                 .ok()
                 .flatten();
         let embedder = if let Some(embedder) = persisted_embedder {
-            if embedder.is_fresh(pdg_node_count, pdg_edge_count) {
+            // An embedder persisted under a different embedding_dim would
+            // produce vectors the search engine rejects.
+            if embedder.is_fresh(pdg_node_count, pdg_edge_count)
+                && embedder.dimension() == self.search_engine.embedding_dim()
+            {
                 info!("Loaded persisted embedder from storage");
                 // VAL-ONNX-001: Wrap as hybrid_local when onnx feature is enabled
                 #[cfg(feature = "onnx")]
@@ -766,9 +1367,20 @@ impl LeIndex {
 
         // Step 7: Persist to storage
         progress_stderr("Indexing: saving to storage...");
-        index_builder::save_to_storage(&mut self.storage, &self.project_id, &pdg)?;
+        if delta_save {
+            index_builder::save_delta_to_storage(
+                &mut self.storage,
+                &self.project_id,
+                &pdg,
+                &rewritten_files,
+            )?;
+        } else {
+            index_builder::save_to_storage(&mut self.storage, &self.project_id, &pdg)?;
+        }
 
         // Update statistics
+        let language_stats =
+            collect_language_stats(&source_files_with_hashes, signatures_by_language, &pdg);
         self.stats = super::IndexStats {
             total_files: source_files_with_hashes.len(),
             files_parsed,
@@ -784,6 +1396,10 @@ impl LeIndex {
             external_deps_unresolved: ext_unresolved,
             external_deps_total: ext_total,
             external_deps_builtin: ext_builtin,
+            slowest_files,
+            language_stats,
+            skipped_oversized_files,
+            cancelled_files: cancelled_files.len(),
         };
 
         // Normalize external nodes (legacy compat)
@@ -816,12 +1432,17 @@ impl LeIndex {
         if let Err(err) = self.update_last_indexed_timestamp() {
             warn!("Failed to update last_indexed timestamp: {err:#}");
         }
+        if let Err(err) = self.record_index_run() {
+            warn!("Failed to record indexing run: {err:#}");
+        }
         // Persist IndexStats so diagnostics can report accurate totals
         // after loading from storage (without a full re-index).
         if let Err(err) = self.save_stats_to_storage() {
             warn!("Failed to persist index stats: {err:#}");
         }
 
+        self.record_generation();
+
         // Clear search query and analysis caches so stale results are not
         // served after a reindex (VAL-INDEX-005). The cache key fingerprint
         // now includes more stats fields, but we also proactively remove
@@ -841,6 +1462,7 @@ impl LeIndex {
 
         // Clear the progress line so the final output is clean.
         progress_clear();
+        progress(super::IndexProgress::Done { files_parsed });
 
         Ok(self.stats.clone())
     }
@@ -856,6 +1478,17 @@ impl LeIndex {
         Ok(())
     }
 
+    /// Append this run's duration to the `index_runs` history shared with leserve
+    fn record_index_run(&mut self) -> Result<()> {
+        crate::storage::index_runs::record_index_run(
+            &mut self.storage,
+            &self.project_id,
+            std::time::Duration::from_millis(self.stats.indexing_time_ms),
+        )
+        .context("Failed to record indexing run")?;
+        Ok(())
+    }
+
     /// Load a previously indexed project from storage
     ///
     /// # Returns
@@ -905,7 +1538,9 @@ impl LeIndex {
                 .ok()
                 .flatten();
         let embedder = if let Some(embedder) = persisted_embedder {
-            if embedder.is_fresh(pdg_node_count, pdg_edge_count) {
+            if embedder.is_fresh(pdg_node_count, pdg_edge_count)
+                && embedder.dimension() == self.search_engine.embedding_dim()
+            {
                 info!("Loaded persisted embedder from storage");
                 // Use tfidf_only during load_from_storage to avoid expensive
                 // batch neural embedding of all nodes. Neural embeddings are
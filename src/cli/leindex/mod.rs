@@ -3,6 +3,10 @@
 // *L'Index* (The Index) - Unified API that brings together all LeIndex crates
 
 mod diagnostics;
+mod export;
+mod git_ref;
+mod history;
+mod import;
 mod indexing;
 pub(crate) mod model_download;
 mod query;
@@ -13,7 +17,11 @@ mod types;
 mod tests;
 
 // Re-export public types for external callers
-pub use types::{AnalysisResult, CoverageReport, Diagnostics, FileStats, IndexStats};
+pub use types::{
+    AnalysisResult, CoverageReport, Diagnostics, ExportedNode, FileParseTiming, FileStats,
+    ImportedEdge, ImportedNode, IndexPlan, IndexProgress, IndexStats, DEFAULT_ANALYSIS_THRESHOLD,
+    DEFAULT_SEARCH_THRESHOLD, EXPORT_PAGE_SIZE, IMPORTED_LANGUAGE, SLOWEST_FILES_REPORTED,
+};
 // Re-export crate-internal types for sibling modules (index_builder, index_cache, etc.)
 pub(crate) use types::{
     ProjectFileScan, DEPENDENCY_MANIFEST_NAMES, SKIP_DIRS, SOURCE_FILE_EXTENSIONS,
@@ -24,7 +32,7 @@ use crate::cli::memory::WarmStrategy;
 use crate::graph::pdg::ProgramDependenceGraph;
 use crate::search::search::SearchEngine;
 use crate::storage::{schema::Storage, UniqueProjectId};
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
@@ -43,7 +51,8 @@ pub struct LeIndex {
     /// Resolved storage root for index artifacts (may be outside project)
     storage_path: PathBuf,
 
-    /// Project identifier (legacy, for backward compatibility)
+    /// Key of this project's rows in storage: the unique ID, suffixed with
+    /// `@<commit>` for an index of a git ref
     project_id: String,
 
     /// Unique project identifier with BLAKE3-based path hashing
@@ -69,6 +78,12 @@ pub struct LeIndex {
 
     /// TF-IDF embedder (None until index_nodes() runs).
     embedder: Option<index_builder::HybridEmbedder>,
+
+    /// Optional extension allow-list restricting which sources are scanned.
+    language_filter: Option<std::collections::HashSet<String>>,
+
+    /// Thread pool used for parsing (None = all cores).
+    parse_pool: Option<std::sync::Arc<rayon::ThreadPool>>,
 }
 
 impl LeIndex {
@@ -80,13 +95,67 @@ impl LeIndex {
                 .unwrap_or(false)
     }
 
+    /// Expand a leading `~` to the home directory and resolve relative paths
+    /// against the current directory, without touching the filesystem.
+    fn absolute_project_path(project_path: &Path) -> Result<PathBuf> {
+        let expanded = match project_path.strip_prefix("~") {
+            Ok(rest) => {
+                let home = std::env::var("HOME")
+                    .or_else(|_| std::env::var("USERPROFILE"))
+                    .with_context(|| {
+                        format!(
+                            "Cannot expand '~' in project path '{}': home directory is not set",
+                            project_path.display()
+                        )
+                    })?;
+                PathBuf::from(home).join(rest)
+            }
+            Err(_) => project_path.to_path_buf(),
+        };
+        if expanded.is_relative() {
+            Ok(std::env::current_dir()
+                .context("Failed to read current directory")?
+                .join(expanded))
+        } else {
+            Ok(expanded)
+        }
+    }
+
+    /// Resolve a user-supplied project path to its canonical form.
+    ///
+    /// Symlinks resolve to their target, so every spelling of a project
+    /// (relative, `~`-prefixed or linked) maps to the same path and project
+    /// ID. Errors name the offending path.
+    fn resolve_project_path(project_path: &Path) -> Result<PathBuf> {
+        let absolute = Self::absolute_project_path(project_path)?;
+        if !absolute.exists() {
+            bail!(
+                "Project path '{}' does not exist; create it first or use LeIndex::create",
+                absolute.display()
+            );
+        }
+        let canonical = absolute.canonicalize().with_context(|| {
+            format!(
+                "Failed to canonicalize project path '{}'",
+                absolute.display()
+            )
+        })?;
+        if !canonical.is_dir() {
+            bail!("Project path '{}' is not a directory", canonical.display());
+        }
+        Ok(canonical)
+    }
+
     /// Resolve the storage directory (in-project → LEINDEX_HOME → XDG → tmp).
-    fn resolve_storage_path(project_path: &Path) -> Result<PathBuf> {
-        let path_hash = &blake3::hash(project_path.to_string_lossy().as_bytes()).to_hex()[..12];
-        let dir_name = project_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
+    ///
+    /// Directories outside the project are named after its `identity`, so
+    /// they follow the configured [`IdentityStrategy`](crate::storage::IdentityStrategy).
+    pub(crate) fn resolve_storage_path(
+        project_path: &Path,
+        identity: &UniqueProjectId,
+    ) -> Result<PathBuf> {
+        let path_hash = &identity.path_hash;
+        let dir_name = &identity.base_name;
 
         // 1. Prefer in-project .leindex
         let in_project = project_path.join(".leindex");
@@ -144,12 +213,58 @@ impl LeIndex {
         Ok(tmp_path)
     }
 
+    /// Storage settings for the project database at `db_path`.
+    ///
+    /// Built from the `[storage]` section of `project_config`, with the
+    /// encryption key read from `encryption_key_env`. Every open of a
+    /// project's database goes through this, so an encrypted database is
+    /// never opened without its key.
+    pub(crate) fn storage_config(
+        project_config: &crate::cli::config::ProjectConfig,
+        db_path: &Path,
+    ) -> Result<crate::storage::StorageConfig> {
+        let encryption_key = match &project_config.storage.encryption_key_env {
+            Some(var) => Some(std::env::var(var).with_context(|| {
+                format!(
+                    "Storage encryption is enabled but ${} is not set to the key",
+                    var
+                )
+            })?),
+            None => None,
+        };
+        Ok(crate::storage::StorageConfig {
+            db_path: db_path.display().to_string(),
+            wal_enabled: project_config.storage.wal_enabled,
+            cache_size_kib: project_config.storage.cache_size_kib,
+            mmap_size: project_config.storage.mmap_size,
+            busy_timeout_ms: project_config
+                .storage
+                .busy_timeout_ms
+                .unwrap_or(crate::storage::schema::DEFAULT_BUSY_TIMEOUT_MS),
+            encryption_key,
+        })
+    }
+
     /// Open storage with retry and exponential backoff.
-    fn open_storage_with_retry(db_path: &Path, max_retries: u32) -> Result<Storage> {
+    ///
+    /// An invalid config is reported immediately rather than retried.
+    fn open_storage_with_retry(
+        db_path: &Path,
+        config: &crate::storage::StorageConfig,
+        max_retries: u32,
+    ) -> Result<Storage> {
         let mut attempt = 0;
         loop {
-            match Storage::open(db_path) {
+            match Storage::open_with_config(db_path, config.clone()) {
                 Ok(s) => return Ok(s),
+                // Rejected by StorageConfig::validate before touching the database
+                Err(e) if e.sqlite_error_code() == Some(rusqlite::ErrorCode::ApiMisuse) => {
+                    return Err(e).context("Invalid [storage] settings in project config");
+                }
+                // Retrying cannot fix a wrong encryption key
+                Err(e) if e.sqlite_error_code() == Some(rusqlite::ErrorCode::NotADatabase) => {
+                    return Err(e).context("Failed to open storage");
+                }
                 Err(e) if attempt < max_retries => {
                     attempt += 1;
                     let delay = std::time::Duration::from_millis(100 * 2u64.pow(attempt));
@@ -176,23 +291,22 @@ impl LeIndex {
 
     /// Create a new LeIndex instance for a project.
     ///
+    /// The path may be relative, start with `~`, or go through symlinks; it
+    /// is resolved to its canonical form. The directory must already exist
+    /// (see [`LeIndex::create`]).
+    ///
     /// ```ignore
     /// let leindex = LeIndex::new("/path/to/project")?;
     /// ```
     pub fn new<P: AsRef<Path>>(project_path: P) -> Result<Self> {
-        let project_path = project_path
-            .as_ref()
-            .canonicalize()
-            .context("Failed to canonicalize project path")?;
+        let project_path = Self::resolve_project_path(project_path.as_ref())?;
 
-        let project_id = project_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        let project_config =
+            crate::cli::config::ProjectConfig::load(&project_path).unwrap_or_default();
+        let identity = UniqueProjectId::identity(&project_path, project_config.storage.identity);
 
         // Initialize storage with multi-location fallback and retry
-        let storage_path = Self::resolve_storage_path(&project_path)?;
+        let storage_path = Self::resolve_storage_path(&project_path, &identity)?;
 
         // Write artifact ownership marker for GC (only for non-in-project storage)
         crate::cli::cleanup::write_artifact_marker(&storage_path);
@@ -201,14 +315,16 @@ impl LeIndex {
         crate::cli::cleanup::register_at_exit_cleanup(storage_path.clone());
 
         let db_path = storage_path.join("leindex.db");
-        let storage = Self::open_storage_with_retry(&db_path, 3)?;
+        let storage_config = Self::storage_config(&project_config, &db_path)?;
+        let storage = Self::open_storage_with_retry(&db_path, &storage_config, 3)?;
 
-        // Generate unique project ID with conflict resolution
-        // Load existing projects with same base name
+        // Resolve conflicts with the projects already stored under the
+        // same base name
         let existing_ids = storage
-            .load_existing_ids(&project_id)
+            .load_existing_ids(&identity.base_name)
             .context("Failed to load existing project IDs from storage")?;
-        let unique_id = UniqueProjectId::generate(&project_path, &existing_ids);
+        let unique_id = identity.resolve_instance(&existing_ids);
+        let project_id = unique_id.to_string();
 
         // Store the project metadata
         storage
@@ -217,19 +333,18 @@ impl LeIndex {
 
         info!(
             "Creating LeIndex for project: {} (unique ID: {}) at {:?}",
+            unique_id.display(),
             project_id,
-            unique_id.to_string(),
             project_path
         );
 
         // Initialize search engine
-        let search_engine = SearchEngine::new();
+        let mut search_engine = SearchEngine::new();
+        search_engine.set_tokenizer(project_config.tokens.build_tokenizer());
 
         // Initialize cache subsystem
         let cache_dir = storage_path.join("cache");
         let cache = crate::cli::index_cache::IndexCache::new(cache_dir)?;
-        let project_config =
-            crate::cli::config::ProjectConfig::load(&project_path).unwrap_or_default();
 
         let instance = Self {
             project_path,
@@ -256,13 +371,23 @@ impl LeIndex {
                 external_deps_unresolved: 0,
                 external_deps_total: 0,
                 external_deps_builtin: 0,
+                slowest_files: Vec::new(),
+                language_stats: HashMap::new(),
+                skipped_oversized_files: Vec::new(),
+                cancelled_files: 0,
             },
             embedder: None,
+            language_filter: None,
+            parse_pool: None,
         };
 
+        let mut instance = instance;
+        instance
+            .set_embedding_dim(instance.project_config.indexing.embeddings.embedding_dim)
+            .context("Invalid [indexing.embeddings] settings in project config")?;
+
         // Restore persisted index stats (if any) so diagnostics can report
         // accurate totals without requiring a full re-index.
-        let mut instance = instance;
         if let Err(err) = instance.load_stats_from_storage() {
             warn!("Failed to load persisted index stats: {err:#}");
         }
@@ -270,6 +395,19 @@ impl LeIndex {
         Ok(instance)
     }
 
+    /// Create the project directory (and any missing parents), then open it
+    /// like [`LeIndex::new`].
+    pub fn create<P: AsRef<Path>>(project_path: P) -> Result<Self> {
+        let project_path = Self::absolute_project_path(project_path.as_ref())?;
+        std::fs::create_dir_all(&project_path).with_context(|| {
+            format!(
+                "Failed to create project directory '{}'",
+                project_path.display()
+            )
+        })?;
+        Self::new(project_path)
+    }
+
     // ---- Internal helpers ----
 
     fn collect_source_files_with_hashes(
@@ -292,7 +430,9 @@ impl LeIndex {
             }
         }
         let project_id = self.project_id.clone();
-        if !refresh {
+        // The persistent scan cache always holds unfiltered scans, so it is
+        // neither read nor written while a language filter is active.
+        if !refresh && self.language_filter.is_none() {
             if let result @ Ok(_) = self
                 .cache
                 .get_project_scan(&project_id, false, || Err(anyhow::anyhow!("cache miss")))
@@ -301,13 +441,102 @@ impl LeIndex {
             }
         }
         let scan = self.scan_project_files()?;
-        self.cache.cache_project_scan(&project_id, &scan);
+        if self.language_filter.is_none() {
+            self.cache.cache_project_scan(&project_id, &scan);
+        }
         self.cache.project_scan = Some(scan.clone());
         Ok(scan)
     }
 
     fn scan_project_files(&self) -> Result<ProjectFileScan> {
-        index_builder::scan_project_files(&self.project_path)
+        let mut scan = index_builder::scan_project_files(&self.project_path)?;
+        if let Some(extensions) = &self.language_filter {
+            let keep = |path: &PathBuf| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| extensions.contains(&ext.to_ascii_lowercase()))
+                    .unwrap_or(false)
+            };
+            scan.source_paths.retain(keep);
+            scan.oversized_paths.retain(keep);
+        }
+        Ok(scan)
+    }
+
+    /// Restrict scanning and parsing to the named languages (e.g. `rust`, `python`).
+    ///
+    /// Unknown names are rejected. An empty list clears the filter. Project
+    /// exclusions still apply on top of the filter.
+    pub fn set_language_filter(&mut self, languages: &[String]) -> Result<()> {
+        self.language_filter = if languages.is_empty() {
+            None
+        } else {
+            Some(crate::cli::config::LanguageConfig::extensions_for_languages(languages)?)
+        };
+        self.cache.project_scan = None;
+        Ok(())
+    }
+
+    /// Bound parsing to `threads` worker threads.
+    ///
+    /// `0` restores the default of using every core.
+    pub fn set_parse_threads(&mut self, threads: usize) -> Result<()> {
+        self.parse_pool = if threads == 0 {
+            None
+        } else {
+            Some(std::sync::Arc::new(
+                crate::parse::parallel::build_parse_pool(threads)
+                    .context("Failed to build parse thread pool")?,
+            ))
+        };
+        Ok(())
+    }
+
+    /// Set the embedding dimension used by the search engine.
+    ///
+    /// [`LeIndex::new`] applies `indexing.embeddings.embedding_dim` from the
+    /// project config. Must be called before indexing or loading: it replaces
+    /// the search engine, and embeddings persisted under another dimension
+    /// are skipped (with a warning) until the project is re-indexed.
+    pub fn set_embedding_dim(&mut self, dim: usize) -> Result<()> {
+        if !(crate::search::query::MIN_EMBEDDING_DIMENSION
+            ..=crate::search::query::MAX_EMBEDDING_DIMENSION)
+            .contains(&dim)
+        {
+            anyhow::bail!(
+                "Invalid embedding dimension: {} (must be between {} and {})",
+                dim,
+                crate::search::query::MIN_EMBEDDING_DIMENSION,
+                crate::search::query::MAX_EMBEDDING_DIMENSION
+            );
+        }
+        if dim != self.search_engine.embedding_dim() {
+            let tokenizer = self.search_engine.tokenizer();
+            self.search_engine = SearchEngine::with_dimension(dim);
+            self.search_engine.set_tokenizer(tokenizer);
+            self.embedder = None;
+        }
+        Ok(())
+    }
+
+    /// Embedding dimension used by the search engine.
+    pub fn embedding_dim(&self) -> usize {
+        self.search_engine.embedding_dim()
+    }
+
+    /// Parse on an existing rayon pool shared with other components.
+    pub fn set_parse_pool(&mut self, pool: std::sync::Arc<rayon::ThreadPool>) {
+        self.parse_pool = Some(pool);
+    }
+
+    /// Parallel parser honoring the configured thread pool.
+    fn parallel_parser(&self) -> crate::parse::parallel::ParallelParser {
+        let parser = crate::parse::parallel::ParallelParser::new()
+            .with_syntax_errors(self.project_config.indexing.record_parse_errors);
+        match &self.parse_pool {
+            Some(pool) => parser.with_thread_pool(pool.clone()),
+            None => parser,
+        }
     }
 
     /// Build a FreshnessContext for delegation to index_freshness module.
@@ -331,6 +560,7 @@ impl LeIndex {
         query: &str,
         top_k: usize,
         query_type: Option<&crate::search::ranking::QueryType>,
+        threshold: Option<f32>,
         neural_available: bool,
     ) -> String {
         index_builder::search_cache_key_for(
@@ -340,17 +570,24 @@ impl LeIndex {
             query,
             top_k,
             query_type,
+            threshold,
             neural_available,
         )
     }
 
-    fn analysis_cache_key_for(&self, query: &str, token_budget: usize) -> String {
+    fn analysis_cache_key_for(
+        &self,
+        query: &str,
+        token_budget: usize,
+        threshold: Option<f32>,
+    ) -> String {
         index_builder::analysis_cache_key_for(
             &self.project_id,
             &self.project_path,
             &self.stats,
             query,
             token_budget,
+            threshold,
         )
     }
 
@@ -405,7 +642,7 @@ impl LeIndex {
         &self.storage_path
     }
 
-    /// Get the project ID (legacy, for backward compatibility).
+    /// Get the key this project's rows are stored under.
     #[inline]
     pub fn project_id(&self) -> &str {
         &self.project_id
@@ -448,9 +685,20 @@ impl LeIndex {
 
         // Open a separate Storage connection for the validator.
         // Storage wraps rusqlite::Connection which is not Clone, so we
-        // create a new read-only handle to the same database.
+        // create a new handle to the same database, with the same key.
         let db_path = self.storage_path.join("leindex.db");
-        let storage = crate::storage::schema::Storage::open(&db_path).ok()?;
+        let storage = Self::storage_config(&self.project_config, &db_path).and_then(|config| {
+            Ok(crate::storage::schema::Storage::open_with_config(
+                &db_path, config,
+            )?)
+        });
+        let storage = match storage {
+            Ok(storage) => storage,
+            Err(e) => {
+                warn!("Failed to open storage for the logic validator: {:#}", e);
+                return None;
+            }
+        };
 
         Some(crate::validation::LogicValidator::new(
             std::sync::Arc::new(pdg.clone()),
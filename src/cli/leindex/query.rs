@@ -5,11 +5,17 @@ use crate::cli::index_builder;
 use crate::cli::memory::CacheEntry;
 use crate::graph::{
     pdg::ProgramDependenceGraph,
-    traversal::{GravityTraversal, TraversalConfig},
+    traversal::{GravityTraversal, TokenCounter, TraversalConfig},
 };
+use crate::phase::CancellationToken;
+use crate::search::query::expand_query_terms;
+use crate::search::ranking::Score;
 use crate::search::search::{SearchQuery, SearchResult};
-use anyhow::{Context, Result};
+use crate::search::tokenizer::Tokenizer;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tracing::{debug, warn};
 
 /// Maximum wall-clock time allowed for generating a single query neural
@@ -26,16 +32,15 @@ const QUERY_EMBED_TIMEOUT_SECS: u64 = 15;
 
 impl LeIndex {
     fn resolve_indexed_file_path(&self, file_path: &str) -> PathBuf {
-        let path = Path::new(file_path);
-        if path.is_absolute() {
-            path.to_path_buf()
-        } else {
-            self.project_path.join(path)
-        }
+        resolve_indexed_file_path(&self.project_path, file_path)
     }
 
     /// Search the indexed code
     ///
+    /// Results scoring below [`DEFAULT_SEARCH_THRESHOLD`](super::DEFAULT_SEARCH_THRESHOLD)
+    /// are dropped; use [`search_with_threshold`](Self::search_with_threshold)
+    /// to tune that per query.
+    ///
     /// # Arguments
     ///
     /// * `query` - Search query string
@@ -58,6 +63,26 @@ impl LeIndex {
         query: &str,
         top_k: usize,
         query_type: Option<crate::search::ranking::QueryType>,
+    ) -> Result<Vec<SearchResult>> {
+        self.search_with_threshold(
+            query,
+            top_k,
+            query_type,
+            Some(super::DEFAULT_SEARCH_THRESHOLD),
+        )
+    }
+
+    /// Search the indexed code, dropping results that score below `threshold`
+    ///
+    /// A lower threshold trades precision for recall; `None` keeps every
+    /// scored match. [`search`](Self::search) uses
+    /// [`DEFAULT_SEARCH_THRESHOLD`](super::DEFAULT_SEARCH_THRESHOLD).
+    pub fn search_with_threshold(
+        &mut self,
+        query: &str,
+        top_k: usize,
+        query_type: Option<crate::search::ranking::QueryType>,
+        threshold: Option<f32>,
     ) -> Result<Vec<SearchResult>> {
         if self.search_engine.is_empty() {
             warn!("Search attempted on empty index");
@@ -65,41 +90,129 @@ impl LeIndex {
         }
 
         if let Some(cached_results) =
-            self.cached_search_results(query, top_k, query_type.as_ref())?
+            self.cached_search_results(query, top_k, query_type.as_ref(), threshold)?
         {
             return Ok(cached_results);
         }
 
-        let query_neural_embedding = self.generate_query_neural_embedding(query);
-        let neural_available = query_neural_embedding.is_some();
-        let search_cache_key =
-            self.search_cache_key_for(query, top_k, query_type.as_ref(), neural_available);
-
-        let search_query = SearchQuery {
-            query: query.to_string(),
+        let (results, neural_available) =
+            self.search_with_neural_flag(query, top_k, query_type, threshold)?;
+        let search_cache_key = self.search_cache_key_for(
+            query,
             top_k,
-            token_budget: None,
-            semantic: true,
-            expand_context: false,
-            query_embedding: Some(self.generate_query_embedding(query)),
-            query_neural_embedding,
-            threshold: Some(0.1), // Added default threshold for better quality
-            query_type,
-        };
+            query_type.as_ref(),
+            threshold,
+            neural_available,
+        );
 
-        let mut results = self
+        if let Ok(serialized) = bincode::serialize(&results) {
+            let entry = CacheEntry::Binary {
+                metadata: std::collections::HashMap::from([
+                    ("type".to_string(), "search_results".to_string()),
+                    ("query".to_string(), query.to_string()),
+                ]),
+                serialized_data: serialized,
+            };
+            if self
+                .cache
+                .cache_spiller
+                .store_mut()
+                .insert(search_cache_key.clone(), entry)
+                .is_ok()
+            {
+                let _ = self
+                    .cache
+                    .cache_spiller
+                    .store_mut()
+                    .persist_key(&search_cache_key);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Find the nodes nearest to a raw embedding, bypassing text entirely
+    ///
+    /// Useful for "find code similar to this snippet" when the caller
+    /// already holds an embedding in the index's vector space. Results carry
+    /// the same file, symbol and PDG metadata as [`LeIndex::search`].
+    ///
+    /// # Arguments
+    ///
+    /// * `embedding` - Query vector; must match the engine's embedding dimension
+    /// * `top_k` - Maximum number of results to return
+    ///
+    /// # Returns
+    ///
+    /// `Result<Vec<SearchResult>>` - Results sorted by cosine similarity
+    pub fn search_by_embedding(
+        &self,
+        embedding: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let expected = self.search_engine.embedding_dim();
+        if embedding.len() != expected {
+            bail!(
+                "Embedding dimension mismatch: expected {}, got {}",
+                expected,
+                embedding.len()
+            );
+        }
+
+        let entries = self
             .search_engine
-            .search(search_query)
-            .context("Search operation failed")?;
+            .semantic_search(embedding, top_k)
+            .context("Embedding search failed")?;
 
-        // Enrich results with PDG metadata: symbol_type, caller_count, dependency_count, line_number.
-        // These require the in-memory PDG which is available here but not in lerecherche.
+        let mut results: Vec<SearchResult> = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let info = self.search_engine.node_info(&entry.node_id)?;
+                Some(SearchResult {
+                    rank: 0,
+                    node_id: entry.node_id,
+                    file_path: info.file_path.clone(),
+                    symbol_name: info.symbol_name.clone(),
+                    symbol_type: None,
+                    signature: info.signature.clone(),
+                    complexity: info.complexity,
+                    caller_count: None,
+                    dependency_count: None,
+                    language: info.language.clone(),
+                    score: Score {
+                        overall: entry.relevance,
+                        tfidf: entry.relevance,
+                        ..Score::default()
+                    },
+                    context: None,
+                    byte_range: info.byte_range,
+                    line_number: None,
+                })
+            })
+            .collect();
+        for (idx, result) in results.iter_mut().enumerate() {
+            result.rank = idx + 1;
+        }
+
+        self.enrich_results_from_pdg(&mut results);
+        Ok(results)
+    }
+
+    /// Fill PDG-derived fields of search results: symbol_type, caller_count,
+    /// dependency_count and line_number.
+    ///
+    /// These require the in-memory PDG, which is available here but not in
+    /// the search engine. Results whose node is not in the PDG are left as is,
+    /// apart from empty file/symbol/language fields, which are first filled
+    /// from storage.
+    fn enrich_results_from_pdg(&self, results: &mut [SearchResult]) {
+        self.fill_missing_result_metadata(results);
         if let Some(pdg) = &self.pdg {
             // Cache file contents to avoid re-reading the same file for multiple results
             let mut file_cache: std::collections::HashMap<String, Option<Vec<u8>>> =
                 std::collections::HashMap::new();
 
-            for result in &mut results {
+            for result in results.iter_mut() {
                 // Look up the PDG node by its string ID
                 if let Some(node_idx) = pdg.find_by_id(&result.node_id) {
                     if let Some(node) = pdg.get_node(node_idx) {
@@ -149,33 +262,126 @@ impl LeIndex {
                 }
             }
         }
+    }
 
-        debug!("Search for '{}' returned {} results", query, results.len());
+    /// Fill result fields the search engine left empty from the PDG, then storage
+    ///
+    /// Nodes indexed with minimal `NodeInfo` (e.g. only an ID and content)
+    /// otherwise come back without a file path, symbol name or language. The
+    /// loaded PDG is consulted first; nodes it does not hold, or every node
+    /// when no PDG is loaded, are looked up in storage.
+    fn fill_missing_result_metadata(&self, results: &mut [SearchResult]) {
+        for result in results.iter_mut() {
+            if !result.file_path.is_empty()
+                && !result.symbol_name.is_empty()
+                && !result.language.is_empty()
+            {
+                continue;
+            }
 
-        if let Ok(serialized) = bincode::serialize(&results) {
-            let entry = CacheEntry::Binary {
-                metadata: std::collections::HashMap::from([
-                    ("type".to_string(), "search_results".to_string()),
-                    ("query".to_string(), query.to_string()),
-                ]),
-                serialized_data: serialized,
+            let in_pdg = self.pdg.as_ref().and_then(|pdg| {
+                pdg.find_by_id(&result.node_id)
+                    .and_then(|idx| pdg.get_node(idx))
+                    .cloned()
+            });
+            let node = match in_pdg {
+                Some(node) => Some(node),
+                None => crate::storage::pdg_store::load_node(
+                    &self.storage,
+                    &self.project_id,
+                    &result.node_id,
+                )
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to load metadata for search result '{}': {}",
+                        result.node_id, e
+                    );
+                    None
+                }),
             };
-            if self
-                .cache
-                .cache_spiller
-                .store_mut()
-                .insert(search_cache_key.clone(), entry)
-                .is_ok()
-            {
-                let _ = self
-                    .cache
-                    .cache_spiller
-                    .store_mut()
-                    .persist_key(&search_cache_key);
+            let Some(node) = node else {
+                continue;
+            };
+
+            if result.file_path.is_empty() {
+                result.file_path = node.file_path.to_string();
+            }
+            if result.symbol_name.is_empty() {
+                result.symbol_name = node.name;
+            }
+            if result.language.is_empty() {
+                result.language = node.language;
+            }
+            if result.byte_range == (0, 0) {
+                result.byte_range = node.byte_range;
             }
         }
+    }
 
-        Ok(results)
+    /// Search without consulting or filling the persistent query cache
+    ///
+    /// Takes `&self` so concurrent readers can share one instance (see
+    /// [`SharedLeIndex`](crate::cli::shared_index::SharedLeIndex)).
+    pub fn search_uncached(
+        &self,
+        query: &str,
+        top_k: usize,
+        query_type: Option<crate::search::ranking::QueryType>,
+    ) -> Result<Vec<SearchResult>> {
+        if self.search_engine.is_empty() {
+            warn!("Search attempted on empty index");
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .search_with_neural_flag(
+                query,
+                top_k,
+                query_type,
+                Some(super::DEFAULT_SEARCH_THRESHOLD),
+            )?
+            .0)
+    }
+
+    /// Run a search, also reporting whether a neural query embedding was used
+    fn search_with_neural_flag(
+        &self,
+        query: &str,
+        top_k: usize,
+        query_type: Option<crate::search::ranking::QueryType>,
+        threshold: Option<f32>,
+    ) -> Result<(Vec<SearchResult>, bool)> {
+        let query_neural_embedding = self.generate_query_neural_embedding(query);
+        let neural_available = query_neural_embedding.is_some();
+
+        let search_query = SearchQuery {
+            query: query.to_string(),
+            top_k,
+            semantic: true,
+            query_embedding: Some(self.generate_query_embedding(query)),
+            query_neural_embedding,
+            threshold,
+            query_type,
+            expansions: expand_query_terms(query, true),
+            ..Default::default()
+        };
+
+        let search_start = std::time::Instant::now();
+        let mut results = self
+            .search_engine
+            .search(search_query)
+            .context("Search operation failed")?;
+
+        self.enrich_results_from_pdg(&mut results);
+
+        debug!(
+            phase = "search",
+            elapsed_ms = search_start.elapsed().as_millis() as u64,
+            "Search for '{}' returned {} results",
+            query,
+            results.len()
+        );
+
+        Ok((results, neural_available))
     }
 
     fn cached_search_results(
@@ -183,6 +389,7 @@ impl LeIndex {
         query: &str,
         top_k: usize,
         query_type: Option<&crate::search::ranking::QueryType>,
+        threshold: Option<f32>,
     ) -> Result<Option<Vec<SearchResult>>> {
         // Neural availability is part of the persisted cache key. Probe both
         // variants before generating the query neural embedding so repeated
@@ -190,7 +397,7 @@ impl LeIndex {
         // the 15s query-embedding timeout.
         for neural_available in [true, false] {
             let search_cache_key =
-                self.search_cache_key_for(query, top_k, query_type, neural_available);
+                self.search_cache_key_for(query, top_k, query_type, threshold, neural_available);
             if let Some(CacheEntry::Binary {
                 serialized_data, ..
             }) = self
@@ -237,9 +444,38 @@ impl LeIndex {
     /// println!("Context: {}", analysis.context.unwrap_or_default());
     /// ```
     pub fn analyze(&mut self, query: &str, token_budget: usize) -> Result<super::AnalysisResult> {
+        self.analyze_with_threshold(query, token_budget, Some(super::DEFAULT_ANALYSIS_THRESHOLD))
+    }
+
+    /// Analyze a query, keeping only entry points that score at least `threshold`
+    ///
+    /// `None` keeps every scored match. [`analyze`](Self::analyze) uses
+    /// [`DEFAULT_ANALYSIS_THRESHOLD`](super::DEFAULT_ANALYSIS_THRESHOLD).
+    pub fn analyze_with_threshold(
+        &mut self,
+        query: &str,
+        token_budget: usize,
+        threshold: Option<f32>,
+    ) -> Result<super::AnalysisResult> {
+        self.analyze_with_cancellation(query, token_budget, threshold, &CancellationToken::new())
+    }
+
+    /// Analyze like [`analyze_with_threshold`](Self::analyze_with_threshold),
+    /// stopping early once `cancel` fires
+    ///
+    /// Cancellation is checked before the entry-point search and before
+    /// context expansion. A cancelled analysis returns the entry points found
+    /// so far with `cancelled` set and no context, and is not cached.
+    pub fn analyze_with_cancellation(
+        &mut self,
+        query: &str,
+        token_budget: usize,
+        threshold: Option<f32>,
+        cancel: &CancellationToken,
+    ) -> Result<super::AnalysisResult> {
         let start_time = std::time::Instant::now();
 
-        let analysis_cache_key = self.analysis_cache_key_for(query, token_budget);
+        let analysis_cache_key = self.analysis_cache_key_for(query, token_budget, threshold);
         if let Some(CacheEntry::Analysis {
             serialized_data, ..
         }) = self
@@ -256,29 +492,11 @@ impl LeIndex {
             }
         }
 
-        // Step 1: Semantic search for entry points
-        // For natural language queries like "How does search scoring work?",
-        // we perform multiple searches with different query formulations
-        // and merge the results to get better coverage of relevant code.
-        let results = self.analyze_search(query)?;
-
-        // Step 2: Expand context using PDG traversal
-        let context = if let Some(ref pdg) = self.pdg {
-            self.expand_context(pdg, &results, token_budget)?
-        } else {
-            warn!("No PDG available for context expansion");
-            String::from("/* No PDG available for context expansion */")
-        };
-
-        // Estimate tokens used (rough approximation: 4 chars per token)
-        let tokens_used = context.len() / 4;
-        let analysis = super::AnalysisResult {
-            query: query.to_string(),
-            results,
-            context: Some(context),
-            tokens_used,
-            processing_time_ms: start_time.elapsed().as_millis() as u64,
-        };
+        let mut analysis = self.run_analysis(query, token_budget, threshold, cancel)?;
+        analysis.processing_time_ms = start_time.elapsed().as_millis() as u64;
+        if analysis.cancelled {
+            return Ok(analysis);
+        }
 
         if let Ok(serialized) = bincode::serialize(&analysis) {
             let entry = CacheEntry::Analysis {
@@ -307,6 +525,79 @@ impl LeIndex {
         Ok(analysis)
     }
 
+    /// Analyze without consulting or filling the persistent analysis cache
+    ///
+    /// Takes `&self` so concurrent readers can share one instance (see
+    /// [`SharedLeIndex`](crate::cli::shared_index::SharedLeIndex)).
+    pub fn analyze_uncached(
+        &self,
+        query: &str,
+        token_budget: usize,
+    ) -> Result<super::AnalysisResult> {
+        self.run_analysis(
+            query,
+            token_budget,
+            Some(super::DEFAULT_ANALYSIS_THRESHOLD),
+            &CancellationToken::new(),
+        )
+    }
+
+    /// Uncached analysis shared by the public `analyze*` entry points
+    fn run_analysis(
+        &self,
+        query: &str,
+        token_budget: usize,
+        threshold: Option<f32>,
+        cancel: &CancellationToken,
+    ) -> Result<super::AnalysisResult> {
+        let start_time = std::time::Instant::now();
+        let cancelled = |results: Vec<SearchResult>| super::AnalysisResult {
+            query: query.to_string(),
+            results,
+            context: None,
+            tokens_used: 0,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+            cancelled: true,
+        };
+        if cancel.is_cancelled() {
+            return Ok(cancelled(Vec::new()));
+        }
+
+        // Step 1: Semantic search for entry points
+        // For natural language queries like "How does search scoring work?",
+        // we perform multiple searches with different query formulations
+        // and merge the results to get better coverage of relevant code.
+        let results = self.analyze_search(query, threshold)?;
+        if cancel.is_cancelled() {
+            return Ok(cancelled(results));
+        }
+
+        // Step 2: Expand context using PDG traversal
+        let traverse_start = std::time::Instant::now();
+        let context = if let Some(ref pdg) = self.pdg {
+            self.expand_context(pdg, &results, token_budget)?
+        } else {
+            warn!("No PDG available for context expansion");
+            String::from("/* No PDG available for context expansion */")
+        };
+        debug!(
+            phase = "traverse",
+            elapsed_ms = traverse_start.elapsed().as_millis() as u64,
+            "Expanded context for {} entry points",
+            results.len()
+        );
+
+        let tokens_used = self.tokenizer().count_tokens(&context);
+        Ok(super::AnalysisResult {
+            query: query.to_string(),
+            results,
+            context: Some(context),
+            tokens_used,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+            cancelled: false,
+        })
+    }
+
     /// Expand context around a specific node.
     ///
     /// Accepts flexible node identification:
@@ -410,7 +701,7 @@ impl LeIndex {
         }];
 
         let context = self.expand_context(pdg, &results, token_budget)?;
-        let tokens_used = context.len() / 4;
+        let tokens_used = self.tokenizer().count_tokens(&context);
 
         Ok(super::AnalysisResult {
             query: format!("Context for node {}", node_id),
@@ -418,6 +709,7 @@ impl LeIndex {
             context: Some(context),
             tokens_used,
             processing_time_ms: start_time.elapsed().as_millis() as u64,
+            cancelled: false,
         })
     }
 
@@ -506,7 +798,7 @@ impl LeIndex {
     /// 1. Searches with the original query (semantic mode)
     /// 2. Extracts key technical terms and searches with those
     /// 3. Merges and deduplicates results, prioritizing source code files
-    fn analyze_search(&mut self, query: &str) -> Result<Vec<SearchResult>> {
+    fn analyze_search(&self, query: &str, threshold: Option<f32>) -> Result<Vec<SearchResult>> {
         // Primary search with the full query
         let primary_neural_embedding = self.generate_query_neural_embedding(query);
         let try_additional_neural = primary_neural_embedding.is_some();
@@ -514,13 +806,13 @@ impl LeIndex {
         let primary_query = SearchQuery {
             query: query.to_string(),
             top_k: 15,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(self.generate_query_embedding(query)),
             query_neural_embedding: primary_neural_embedding,
-            threshold: Some(0.05),
+            threshold,
             query_type: Some(crate::search::ranking::QueryType::Semantic),
+            expansions: expand_query_terms(query, true),
+            ..Default::default()
         };
 
         let primary_results = self
@@ -538,17 +830,16 @@ impl LeIndex {
             let secondary_query = SearchQuery {
                 query: key_terms.clone(),
                 top_k: 15,
-                token_budget: None,
                 semantic: true,
-                expand_context: false,
                 query_embedding: Some(self.generate_query_embedding(&key_terms)),
                 query_neural_embedding: if try_additional_neural {
                     self.generate_query_neural_embedding(&key_terms)
                 } else {
                     None
                 },
-                threshold: Some(0.05),
+                threshold,
                 query_type: Some(crate::search::ranking::QueryType::Semantic),
+                ..Default::default()
             };
 
             self.search_engine
@@ -565,17 +856,16 @@ impl LeIndex {
             let stemmed_query = SearchQuery {
                 query: stemmed_terms.clone(),
                 top_k: 15,
-                token_budget: None,
                 semantic: true,
-                expand_context: false,
                 query_embedding: Some(self.generate_query_embedding(&stemmed_terms)),
                 query_neural_embedding: if try_additional_neural {
                     self.generate_query_neural_embedding(&stemmed_terms)
                 } else {
                     None
                 },
-                threshold: Some(0.05),
+                threshold,
                 query_type: Some(crate::search::ranking::QueryType::Semantic),
+                ..Default::default()
             };
 
             self.search_engine.search(stemmed_query).unwrap_or_default()
@@ -713,18 +1003,46 @@ impl LeIndex {
         Ok(final_results)
     }
 
+    /// Tokenizer used for token-budget accounting, per the project config
+    pub fn tokenizer(&self) -> Arc<dyn Tokenizer> {
+        self.project_config.tokens.build_tokenizer()
+    }
+
     /// Expand context using PDG traversal
+    ///
+    /// Each node is charged against `token_budget` by the configured
+    /// tokenizer's count of the block it contributes to the context.
     fn expand_context(
         &self,
         pdg: &ProgramDependenceGraph,
         results: &[SearchResult],
         token_budget: usize,
     ) -> Result<String> {
+        // Blocks rendered while costing nodes are reused when assembling, and
+        // each source file is read at most once per expansion
+        let blocks: Arc<Mutex<HashMap<String, String>>> = Arc::default();
+        let sources: Arc<Mutex<SourceCache>> = Arc::default();
+        let tokenizer = self.tokenizer();
+        let project_path = self.project_path.clone();
+        let counter_blocks = Arc::clone(&blocks);
+        let counter_sources = Arc::clone(&sources);
+        let counter: TokenCounter = Arc::new(move |node| {
+            let block = render_node_block(&project_path, &counter_sources, node);
+            let tokens = tokenizer.count_tokens(&block);
+            if let Ok(mut blocks) = counter_blocks.lock() {
+                blocks.insert(node.id.clone(), block);
+            }
+            tokens
+        });
+
+        // Depth and node caps keep dense graphs from dominating analyze latency
         let config = TraversalConfig {
             max_tokens: token_budget,
+            max_depth: Some(5),
+            max_nodes: Some(150),
             ..TraversalConfig::default()
         };
-        let traversal = GravityTraversal::with_config(config);
+        let traversal = GravityTraversal::with_config(config).with_token_counter(counter);
 
         // Map SearchResult entries to PDG node IDs for the traversal call.
         // Try exact ID match first, then fall back to name-based lookup,
@@ -754,50 +1072,26 @@ impl LeIndex {
             pdg.node_count()
         );
 
-        let expanded_node_ids = traversal.expand_context(pdg, entry_points);
+        let expansion = traversal.expand_context_bounded(pdg, entry_points);
+        let expanded_node_ids = expansion.nodes;
 
-        debug!("expand_context: {} expanded nodes", expanded_node_ids.len());
+        debug!(
+            "expand_context: {} expanded nodes (truncated={})",
+            expanded_node_ids.len(),
+            expansion.truncated
+        );
 
+        let mut blocks = blocks
+            .lock()
+            .map(|mut blocks| std::mem::take(&mut *blocks))
+            .unwrap_or_default();
         let mut context = String::from("/* Context Expansion via Gravity Traversal */\n");
-
         for node_id in expanded_node_ids {
             if let Some(node) = pdg.get_node(node_id) {
-                context.push_str(&format!("\n// Symbol: {}\n", node.name));
-                context.push_str(&format!("// File: {}\n", node.file_path));
-                context.push_str(&format!("// Type: {:?}\n", node.node_type));
-
-                // Compute line number from byte range.
-                // byte_range.0 == 0 is valid (file start, line 1) so we
-                // must not use `> 0` as the guard.
-                let abs_path = self.resolve_indexed_file_path(&node.file_path);
-                if let Ok(content) = std::fs::read(&abs_path) {
-                    let start = node.byte_range.0;
-                    let end = node.byte_range.1.min(content.len());
-
-                    // Compute starting line number
-                    let line_num = content[..start.min(content.len())]
-                        .iter()
-                        .filter(|&&b| b == b'\n')
-                        .count()
-                        + 1;
-                    context.push_str(&format!("// Line: {}\n", line_num));
-
-                    if end > start {
-                        if let Ok(code) = std::str::from_utf8(&content[start..end]) {
-                            context.push_str(code);
-                            context.push('\n');
-                        } else {
-                            context.push_str("// [Error: Source code is not valid UTF-8]\n");
-                        }
-                    } else {
-                        context.push_str("// [No source code range available for this node]\n");
-                    }
-                } else {
-                    context.push_str(&format!(
-                        "// [Error: Could not read file: {}]\n",
-                        node.file_path
-                    ));
-                }
+                let block = blocks
+                    .remove(&node.id)
+                    .unwrap_or_else(|| render_node_block(&self.project_path, &sources, node));
+                context.push_str(&block);
             }
         }
 
@@ -805,6 +1099,134 @@ impl LeIndex {
     }
 }
 
+/// Lines of surrounding source included before and after each node's code
+const SNIPPET_CONTEXT_LINES: usize = 2;
+
+/// File contents read during one context expansion, keyed by absolute path
+///
+/// `None` records a file that could not be read so it is not retried.
+type SourceCache = HashMap<PathBuf, Option<Arc<Vec<u8>>>>;
+
+/// Resolve an indexed `file_path` against `project_path` unless it is absolute
+fn resolve_indexed_file_path(project_path: &Path, file_path: &str) -> PathBuf {
+    let path = Path::new(file_path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        project_path.join(path)
+    }
+}
+
+/// Read `file_path` (relative to `project_path` unless absolute) through `cache`
+fn read_source_cached(
+    cache: &Mutex<SourceCache>,
+    project_path: &Path,
+    file_path: &str,
+) -> Option<Arc<Vec<u8>>> {
+    let mut cache = cache.lock().unwrap_or_else(|p| p.into_inner());
+    cache
+        .entry(resolve_indexed_file_path(project_path, file_path))
+        .or_insert_with_key(|abs_path| std::fs::read(abs_path).ok().map(Arc::new))
+        .clone()
+}
+
+/// Widen `range` to whole lines plus `context_lines` lines on either side
+///
+/// Returns the widened byte range and the 1-based line it starts on.
+fn snippet_bounds(
+    content: &[u8],
+    range: (usize, usize),
+    context_lines: usize,
+) -> ((usize, usize), usize) {
+    let start = range.0.min(content.len());
+    let end = range.1.clamp(start, content.len());
+
+    let mut snippet_start = start;
+    let mut newlines_seen = 0;
+    while snippet_start > 0 {
+        if content[snippet_start - 1] == b'\n' {
+            if newlines_seen == context_lines {
+                break;
+            }
+            newlines_seen += 1;
+        }
+        snippet_start -= 1;
+    }
+
+    // A range ending right after a newline already ends on a line boundary
+    let mut snippet_end = end;
+    let mut lines_after = if end > start && content[end - 1] == b'\n' {
+        1
+    } else {
+        0
+    };
+    while lines_after <= context_lines && snippet_end < content.len() {
+        if content[snippet_end] == b'\n' {
+            lines_after += 1;
+        }
+        snippet_end += 1;
+    }
+
+    let first_line = content[..snippet_start]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1;
+    ((snippet_start, snippet_end), first_line)
+}
+
+/// Render the context block for one node: a header and its source slice
+///
+/// The slice is the node's byte range widened by [`SNIPPET_CONTEXT_LINES`]
+/// lines of surrounding code.
+fn render_node_block(
+    project_path: &Path,
+    sources: &Mutex<SourceCache>,
+    node: &crate::graph::pdg::Node,
+) -> String {
+    let mut block = format!(
+        "\n// Symbol: {}\n// File: {}\n// Type: {:?}\n",
+        node.name, node.file_path, node.node_type
+    );
+
+    let Some(content) = read_source_cached(sources, project_path, &node.file_path) else {
+        block.push_str(&format!(
+            "// [Error: Could not read file: {}]\n",
+            node.file_path
+        ));
+        return block;
+    };
+
+    // byte_range.0 == 0 is valid (file start, line 1) so we
+    // must not use `> 0` as the guard.
+    let start = node.byte_range.0.min(content.len());
+    let end = node.byte_range.1.min(content.len());
+    let line_num = || content[..start].iter().filter(|&&b| b == b'\n').count() + 1;
+
+    if end > start {
+        let ((from, to), first_line) =
+            snippet_bounds(&content, (start, end), SNIPPET_CONTEXT_LINES);
+        match std::str::from_utf8(&content[from..to]) {
+            Ok(code) => {
+                let last_line = first_line + code.trim_end_matches('\n').matches('\n').count();
+                block.push_str(&format!("// Lines: {}-{}\n", first_line, last_line));
+                block.push_str(code);
+                if !code.ends_with('\n') {
+                    block.push('\n');
+                }
+            }
+            Err(_) => {
+                block.push_str(&format!("// Line: {}\n", line_num()));
+                block.push_str("// [Error: Source code is not valid UTF-8]\n");
+            }
+        }
+    } else {
+        block.push_str(&format!("// Line: {}\n", line_num()));
+        block.push_str("// [No source code range available for this node]\n");
+    }
+    block
+}
+
 /// Generate a deterministic 768-dimensional embedding for a query string.
 /// Fallback when no TF-IDF embedder is available.
 fn generate_deterministic_embedding(symbol_name: &str) -> Vec<f32> {
@@ -1203,7 +1625,7 @@ mod tests {
         let results = vec![cached_result("fallback-cache-hit")];
         let serialized = bincode::serialize(&results).unwrap();
         assert!(!serialized.is_empty());
-        let fallback_key = leindex.search_cache_key_for("cached_symbol", 5, None, false);
+        let fallback_key = leindex.search_cache_key_for("cached_symbol", 5, None, Some(0.1), false);
         leindex
             .cache
             .cache_spiller
@@ -1235,12 +1657,93 @@ mod tests {
         assert_eq!(direct_results[0].node_id, "fallback-cache-hit");
 
         let cached = leindex
-            .cached_search_results("cached_symbol", 5, None)
+            .cached_search_results("cached_symbol", 5, None, Some(0.1))
             .unwrap()
             .expect("fallback cache entry should be returned without neural probing");
         assert_eq!(cached[0].node_id, "fallback-cache-hit");
     }
 
+    #[test]
+    fn expand_node_context_charges_nodes_by_tokenizer_count() {
+        use crate::search::tokenizer::{CharEstimateTokenizer, Tokenizer};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project_path).unwrap();
+        let mut source = String::new();
+        for i in 0..8 {
+            source.push_str(&format!(
+                "def f{i}(a, b):\n    return (a[0]+b[1])*(a[2]-b[3])/(a[4]%b[5])-[a[6],b[7]][0]\n\n"
+            ));
+        }
+        source.push_str("def run(a, b):\n");
+        for i in 0..8 {
+            source.push_str(&format!("    f{i}(a, b)\n"));
+        }
+        std::fs::write(project_path.join("dense.py"), source).unwrap();
+
+        let mut leindex = LeIndex::new(&project_path).unwrap();
+        leindex.index_project(false).unwrap();
+
+        let budget = 200;
+        let analysis = leindex.expand_node_context("run", budget).unwrap();
+        let context = analysis.context.unwrap();
+        let tokenizer = leindex.tokenizer();
+        let preamble = tokenizer.count_tokens("/* Context Expansion via Gravity Traversal */\n");
+
+        // Real token counts stay within budget even though the byte-range
+        // estimate (~4 bytes per token) would have admitted every function
+        assert!(tokenizer.count_tokens(&context) <= budget + preamble);
+        assert!(context.matches("// Symbol: ").count() < 9);
+        assert!(CharEstimateTokenizer::default().count_tokens(&context) < analysis.tokens_used);
+        assert_eq!(analysis.tokens_used, tokenizer.count_tokens(&context));
+    }
+
+    #[test]
+    fn snippet_bounds_adds_surrounding_lines() {
+        use super::snippet_bounds;
+
+        let content = b"one\ntwo\nthree\nfour\nfive\nsix\n";
+        let four = 14;
+        // "four\n" widened by one line each side: "three\nfour\nfive\n"
+        let ((from, to), first_line) = snippet_bounds(content, (four, four + 5), 1);
+        assert_eq!(&content[from..to], b"three\nfour\nfive\n");
+        assert_eq!(first_line, 3);
+        // A mid-line range still yields whole lines, clipped at the file edges
+        let ((from, to), first_line) = snippet_bounds(content, (1, 2), 2);
+        assert_eq!(&content[from..to], b"one\ntwo\nthree\n");
+        assert_eq!(first_line, 1);
+    }
+
+    #[test]
+    fn analyze_context_contains_entry_point_body_and_surroundings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let project_path = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project_path).unwrap();
+        std::fs::write(
+            project_path.join("billing.py"),
+            "RATE_TABLE = {'gold': 0.2}\n\n\
+             def compute_invoice_discount(customer, amount):\n    \
+             tier_rate = RATE_TABLE.get(customer.tier, 0.0)\n    \
+             return round(amount * tier_rate, 2)\n\n\
+             TRAILING_MARKER = True\n",
+        )
+        .unwrap();
+
+        let mut leindex = LeIndex::new(&project_path).unwrap();
+        leindex.index_project(false).unwrap();
+
+        let analysis = leindex.analyze("compute_invoice_discount", 2000).unwrap();
+        assert_eq!(analysis.results[0].symbol_name, "compute_invoice_discount");
+        let context = analysis.context.unwrap();
+        assert!(context.contains("tier_rate = RATE_TABLE.get(customer.tier, 0.0)"));
+        assert!(context.contains("return round(amount * tier_rate, 2)"));
+        // Surrounding lines come along with the function body
+        assert!(context.contains("TRAILING_MARKER = True"));
+        assert!(context.contains("// Lines: "));
+        assert!(!context.contains("// Line: "));
+    }
+
     #[test]
     fn simple_stem_handles_multibyte_double_consonant() {
         assert_eq!(simple_stem("ååing"), "å");
@@ -36,6 +36,171 @@ fn test_project_scan_excludes_lockfiles_from_source_but_keeps_manifests() {
     }));
 }
 
+#[test]
+fn test_relative_project_path_resolves_against_current_dir() {
+    let cwd = std::env::current_dir().unwrap();
+    let dir = tempfile::tempdir_in(&cwd).unwrap();
+    let relative = dir.path().strip_prefix(&cwd).unwrap();
+
+    let resolved = LeIndex::resolve_project_path(relative).unwrap();
+    assert_eq!(resolved, dir.path().canonicalize().unwrap());
+    let dotted = LeIndex::resolve_project_path(&Path::new(".").join(relative)).unwrap();
+    assert_eq!(dotted, resolved);
+}
+
+#[test]
+fn test_nonexistent_project_path_error_names_path() {
+    let dir = tempdir().unwrap();
+    let missing = dir.path().join("not-created-yet");
+
+    let err = LeIndex::new(&missing)
+        .err()
+        .expect("missing path must fail");
+    let message = format!("{:#}", err);
+    assert!(
+        message.contains(&missing.display().to_string()),
+        "{}",
+        message
+    );
+    assert!(message.contains("does not exist"), "{}", message);
+
+    let index = LeIndex::create(&missing).unwrap();
+    assert_eq!(index.project_path, missing.canonicalize().unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinked_project_path_resolves_to_target() {
+    let dir = tempdir().unwrap();
+    let target = dir.path().join("real_project");
+    std::fs::create_dir(&target).unwrap();
+    let link = dir.path().join("linked_project");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    let via_link = LeIndex::new(&link).unwrap();
+    assert_eq!(via_link.project_path, target.canonicalize().unwrap());
+    assert_eq!(via_link.unique_id.base_name, "real_project");
+    assert_eq!(via_link.project_id, via_link.unique_id.to_string());
+}
+
+#[test]
+fn test_rows_are_keyed_by_a_stable_unique_id() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("main.py"), "def main():\n    return 1\n").unwrap();
+
+    let mut first = LeIndex::new(dir.path()).unwrap();
+    let legacy_key = first.unique_id.base_name.clone();
+    first.index_project(false).unwrap();
+    assert!(crate::storage::pdg_store::has_indexed_files(
+        &first.storage,
+        &first.project_id
+    ));
+    // An index left under the directory name by an older version
+    let legacy_file = first.project_path.join("main.py").display().to_string();
+    crate::storage::pdg_store::update_indexed_file(
+        &mut first.storage,
+        &legacy_key,
+        &legacy_file,
+        "h",
+    )
+    .unwrap();
+    let key = first.project_id.clone();
+    drop(first);
+
+    // Opening the project leaves storage alone; indexing drops the old rows
+    let mut second = LeIndex::new(dir.path()).unwrap();
+    assert_eq!(second.project_id, key);
+    assert_eq!(second.unique_id.instance, 0);
+    assert!(crate::storage::pdg_store::has_indexed_files(
+        &second.storage,
+        &legacy_key
+    ));
+    second.index_project(false).unwrap();
+    assert!(crate::storage::pdg_store::has_indexed_files(
+        &second.storage,
+        &key
+    ));
+    assert!(!crate::storage::pdg_store::has_indexed_files(
+        &second.storage,
+        &legacy_key
+    ));
+    assert_eq!(
+        second
+            .storage
+            .stored_project_id(dir.path())
+            .unwrap()
+            .map(|id| id.to_string()),
+        Some(key)
+    );
+}
+
+#[test]
+fn test_legacy_key_migration_spares_projects_with_the_same_base_name() {
+    use crate::storage::pdg_store::{has_indexed_files, update_indexed_file};
+
+    let dir = tempdir().unwrap();
+    let ours = dir.path().join("a").join("shared");
+    let theirs = dir.path().join("b").join("shared");
+    for root in [&ours, &theirs] {
+        std::fs::create_dir_all(root).unwrap();
+        std::fs::write(root.join("main.py"), "def main():\n    return 1\n").unwrap();
+    }
+    let theirs = theirs.canonicalize().unwrap();
+
+    let mut index = LeIndex::new(&ours).unwrap();
+    let key = index.unique_id.base_name.clone();
+    assert_eq!(key, "shared");
+    let indexed_under_key = |index: &LeIndex| {
+        crate::storage::pdg_store::get_indexed_files(&index.storage, &key)
+            .unwrap()
+            .len()
+    };
+
+    // The other project keeps its rows under the bare directory name in the
+    // database this one uses
+    let their_file = theirs.join("main.py").display().to_string();
+    update_indexed_file(&mut index.storage, &key, &their_file, "h").unwrap();
+    index.index_project(false).unwrap();
+    assert_eq!(indexed_under_key(&index), 1);
+
+    // Rows inside this project are spared too while another project is
+    // recorded under the same base name
+    let our_file = index.project_path.join("main.py").display().to_string();
+    update_indexed_file(&mut index.storage, &key, &our_file, "h").unwrap();
+    let existing = index.storage.load_existing_ids(&key).unwrap();
+    let their_id = crate::storage::UniqueProjectId::identity(&theirs, Default::default())
+        .resolve_instance(&existing);
+    assert_ne!(their_id, index.unique_id);
+    index
+        .storage
+        .store_project_metadata(&their_id, &theirs)
+        .unwrap();
+    index.index_project(false).unwrap();
+    assert_eq!(indexed_under_key(&index), 2);
+
+    // Once nothing else can own the key, its rows are this project's legacy
+    // index and are dropped
+    index
+        .storage
+        .conn()
+        .execute(
+            "DELETE FROM project_metadata WHERE canonical_path = ?1",
+            rusqlite::params![theirs.to_string_lossy()],
+        )
+        .unwrap();
+    index
+        .storage
+        .conn()
+        .execute(
+            "DELETE FROM indexed_files WHERE project_id = ?1 AND file_path = ?2",
+            rusqlite::params![key, their_file],
+        )
+        .unwrap();
+    index.index_project(false).unwrap();
+    assert!(!has_indexed_files(&index.storage, &key));
+    assert!(has_indexed_files(&index.storage, &index.project_id));
+}
+
 #[test]
 fn test_project_scan_is_restored_from_cache_across_instances() {
     let dir = tempdir().unwrap();
@@ -57,6 +222,46 @@ fn test_project_scan_is_restored_from_cache_across_instances() {
     assert_eq!(first_scan.manifest_paths, second_scan.manifest_paths);
 }
 
+#[test]
+fn test_language_filter_restricts_indexed_nodes() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "pub fn rust_entry() -> i32 { 1 }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "def python_entry():\n    return 1\n",
+    )
+    .unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.set_language_filter(&["rust".to_string()]).unwrap();
+    index.index_project(true).unwrap();
+
+    let pdg = index.pdg().expect("pdg after indexing");
+    let languages: std::collections::HashSet<String> = pdg
+        .node_indices()
+        .filter_map(|idx| pdg.get_node(idx))
+        .filter(|node| node.language != "external")
+        .map(|node| node.language.clone())
+        .collect();
+    assert!(languages.contains("rust"));
+    assert!(!languages.contains("python"));
+}
+
+#[test]
+fn test_language_filter_rejects_unknown_language() {
+    let dir = tempdir().unwrap();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+
+    let err = index
+        .set_language_filter(&["cobol".to_string()])
+        .unwrap_err();
+    assert!(err.to_string().contains("Unsupported language 'cobol'"));
+}
+
 #[test]
 fn test_stats_serialization() {
     let stats = IndexStats {
@@ -74,6 +279,10 @@ fn test_stats_serialization() {
         external_deps_unresolved: 0,
         external_deps_total: 0,
         external_deps_builtin: 0,
+        slowest_files: Vec::new(),
+        language_stats: HashMap::new(),
+        skipped_oversized_files: Vec::new(),
+        cancelled_files: 0,
     };
 
     let json = serde_json::to_string(&stats).unwrap();
@@ -91,6 +300,7 @@ fn test_analysis_result_serialization() {
         context: Some("context".to_string()),
         tokens_used: 100,
         processing_time_ms: 50,
+        cancelled: false,
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -122,6 +332,10 @@ fn test_diagnostics_serialization() {
             external_deps_unresolved: 0,
             external_deps_total: 0,
             external_deps_builtin: 0,
+            slowest_files: Vec::new(),
+            language_stats: HashMap::new(),
+            skipped_oversized_files: Vec::new(),
+            cancelled_files: 0,
         },
         memory_usage_bytes: 1024,
         total_memory_bytes: 8192,
@@ -160,3 +374,810 @@ fn test_diagnostics_serialization() {
     assert_eq!(deserialized.cache_hits, 9);
     assert_eq!(deserialized.spilled_bytes, 30000);
 }
+
+#[test]
+fn test_export_nodes_jsonl_writes_one_line_per_node() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "pub fn first() -> i32 { second() }\npub fn second() -> i32 { 2 }\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("app.py"), "def helper():\n    return 1\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(true).unwrap();
+    let node_count = index.pdg().expect("pdg after indexing").node_count();
+    assert!(node_count > 0);
+
+    let mut out = Vec::new();
+    let written = index.export_nodes_jsonl(&mut out).unwrap();
+    assert_eq!(written, node_count);
+
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), node_count);
+    for line in lines {
+        let node: ExportedNode = serde_json::from_str(line).unwrap();
+        assert!(!node.id.is_empty());
+    }
+    assert!(text.contains("\"symbol\":\"first\""));
+}
+
+#[test]
+fn test_import_nodes_and_edges_jsonl_seed_pdg_and_search() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub fn native() -> i32 { 1 }\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(true).unwrap();
+
+    let nodes = concat!(
+        r#"{"id":"ledger.cbl:reconcile_ledger","symbol":"reconcile_ledger","file":"ledger.cbl","language":"COBOL","content":"PERFORM reconcile_ledger"}"#,
+        "\n",
+        r#"{"id":"ledger.cbl:post_entry","symbol":"post_entry","file":"ledger.cbl","language":"COBOL","byte_range":[10,40]}"#,
+        "\n",
+    );
+    let imported = index.import_nodes_jsonl(nodes.as_bytes()).unwrap();
+    assert_eq!(imported, 2);
+
+    let edges = r#"{"caller":"ledger.cbl:reconcile_ledger","callee":"ledger.cbl:post_entry","edge_type":"call"}"#;
+    assert_eq!(index.import_edges_jsonl(edges.as_bytes()).unwrap(), 1);
+
+    let pdg = index.pdg().expect("pdg after import");
+    let caller = pdg.find_by_id("ledger.cbl:reconcile_ledger").unwrap();
+    let callee = pdg.find_by_id("ledger.cbl:post_entry").unwrap();
+    assert_eq!(pdg.get_node(caller).unwrap().language, "imported:cobol");
+    assert!(pdg.neighbors(caller).contains(&callee));
+
+    let results = index.search("reconcile_ledger", 5, None).unwrap();
+    assert!(results
+        .iter()
+        .any(|r| r.node_id == "ledger.cbl:reconcile_ledger"));
+
+    // Malformed lines are rejected with their line number
+    let err = index
+        .import_nodes_jsonl("\n{\"id\":\"x\"}\n".as_bytes())
+        .unwrap_err();
+    assert!(err.to_string().contains("line 2"));
+    let err = index
+        .import_edges_jsonl(r#"{"caller":"missing","callee":"ledger.cbl:post_entry"}"#.as_bytes())
+        .unwrap_err();
+    assert!(err.to_string().contains("line 1"));
+}
+
+#[test]
+fn test_failed_import_save_leaves_index_unchanged() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub fn native() -> i32 { 1 }\n").unwrap();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(true).unwrap();
+    let nodes = concat!(
+        r#"{"id":"ledger.cbl:reconcile_ledger","symbol":"reconcile_ledger","file":"ledger.cbl"}"#,
+        "\n",
+        r#"{"id":"ledger.cbl:post_entry","symbol":"post_entry","file":"ledger.cbl"}"#,
+        "\n",
+    );
+    let edges = r#"{"caller":"ledger.cbl:reconcile_ledger","callee":"ledger.cbl:post_entry"}"#;
+    let fail_inserts_into = |index: &LeIndex, table: &str| {
+        index
+            .storage
+            .conn()
+            .execute_batch(&format!(
+                "CREATE TRIGGER fail_import BEFORE INSERT ON {} \
+                 BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+                table
+            ))
+            .unwrap();
+    };
+    let allow_inserts = |index: &LeIndex| {
+        index
+            .storage
+            .conn()
+            .execute_batch("DROP TRIGGER fail_import;")
+            .unwrap();
+    };
+
+    let node_count = index.pdg().unwrap().node_count();
+    let searchable = index.search_engine.node_count();
+    fail_inserts_into(&index, "intel_nodes");
+    assert!(index.import_nodes_jsonl(nodes.as_bytes()).is_err());
+    let pdg = index.pdg().unwrap();
+    assert_eq!(pdg.node_count(), node_count);
+    assert!(pdg.find_by_id("ledger.cbl:post_entry").is_none());
+    assert_eq!(index.search_engine.node_count(), searchable);
+    allow_inserts(&index);
+
+    assert_eq!(index.import_nodes_jsonl(nodes.as_bytes()).unwrap(), 2);
+    let edge_count = index.pdg().unwrap().edge_count();
+    fail_inserts_into(&index, "intel_edges");
+    assert!(index.import_edges_jsonl(edges.as_bytes()).is_err());
+    assert_eq!(index.pdg().unwrap().edge_count(), edge_count);
+    allow_inserts(&index);
+
+    assert_eq!(index.import_edges_jsonl(edges.as_bytes()).unwrap(), 1);
+    assert_eq!(index.pdg().unwrap().edge_count(), edge_count + 1);
+}
+
+#[test]
+fn test_index_changed_reparses_only_listed_files() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("a.py"), "def alpha():\n    return 1\n").unwrap();
+    std::fs::write(dir.path().join("b.py"), "def beta():\n    return 2\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(true).unwrap();
+
+    std::fs::write(
+        dir.path().join("a.py"),
+        "def alpha():\n    return 1\n\ndef alpha_two():\n    return 3\n",
+    )
+    .unwrap();
+    // b.py also changes on disk but is not listed, so it must not be reparsed
+    std::fs::write(dir.path().join("b.py"), "def gamma():\n    return 4\n").unwrap();
+
+    let stats = index
+        .index_changed(&[PathBuf::from("a.py"), PathBuf::from("untracked.py")])
+        .unwrap();
+    assert_eq!(stats.files_parsed, 1);
+
+    let pdg = index.pdg().expect("pdg after changed-file reindex");
+    assert!(pdg.find_by_name("alpha_two").is_some());
+    assert!(pdg.find_by_name("beta").is_some());
+    assert!(pdg.find_by_name("gamma").is_none());
+}
+
+#[test]
+fn test_plan_index_lists_only_changed_file_without_writing() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("a.py"), "def alpha():\n    return 1\n").unwrap();
+    std::fs::write(dir.path().join("b.py"), "def beta():\n    return 2\n").unwrap();
+    std::fs::write(dir.path().join("c.py"), "def gamma():\n    return 3\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    let initial = index.plan_index(false).unwrap();
+    assert_eq!(initial.to_parse.len(), 3);
+    index.index_project(false).unwrap();
+
+    std::fs::write(dir.path().join("a.py"), "def alpha():\n    return 10\n").unwrap();
+    std::fs::remove_file(dir.path().join("c.py")).unwrap();
+
+    let plan = index.plan_index(false).unwrap();
+    let names = |paths: &[PathBuf]| -> Vec<String> {
+        paths
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .collect()
+    };
+    assert_eq!(names(&plan.to_parse), vec!["a.py".to_string()]);
+    assert_eq!(names(&plan.unchanged), vec!["b.py".to_string()]);
+    assert_eq!(plan.deleted.len(), 1);
+    assert!(plan.deleted[0].ends_with("c.py"));
+    assert!(!plan.is_noop());
+
+    // Planning writes nothing: the stored hashes still describe the old tree
+    let again = index.plan_index(false).unwrap();
+    assert_eq!(again, plan);
+    assert_eq!(index.plan_index(true).unwrap().to_parse.len(), 2);
+}
+
+#[test]
+fn test_index_project_with_progress_reports_parse_counts() {
+    let dir = tempdir().unwrap();
+    for i in 0..6 {
+        std::fs::write(
+            dir.path().join(format!("m{}.py", i)),
+            format!("def f{}():\n    return {}\n", i, i),
+        )
+        .unwrap();
+    }
+
+    let mut events = Vec::new();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    let stats = index
+        .index_project_with_progress(true, &mut |event| events.push(event))
+        .unwrap();
+
+    assert_eq!(
+        events.first(),
+        Some(&IndexProgress::Scanned {
+            total_files: 6,
+            to_parse: 6
+        })
+    );
+    let parsed: Vec<usize> = events
+        .iter()
+        .filter_map(|event| match event {
+            IndexProgress::Parsing { parsed, total } => {
+                assert_eq!(*total, 6);
+                Some(*parsed)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(parsed, (1..=6).collect::<Vec<_>>());
+
+    let position = |wanted: &IndexProgress| events.iter().position(|e| e == wanted);
+    let started = position(&IndexProgress::PdgBuildStarted).expect("pdg start event");
+    assert_eq!(
+        events[started - 1],
+        IndexProgress::Parsing {
+            parsed: 6,
+            total: 6
+        }
+    );
+    assert!(matches!(
+        events[started + 1],
+        IndexProgress::PdgBuildFinished { nodes, .. } if nodes == stats.pdg_nodes
+    ));
+    assert_eq!(
+        events.last(),
+        Some(&IndexProgress::Done { files_parsed: 6 })
+    );
+}
+
+#[test]
+fn test_renamed_file_keeps_node_identity() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("old_name.py"),
+        "def helper():\n    return 1\n\ndef caller():\n    return helper()\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("other.py"), "def other():\n    return 2\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(false).unwrap();
+
+    let old_path = dir.path().join("old_name.py").display().to_string();
+    let new_path = dir.path().join("new_name.py").display().to_string();
+    let stored_nodes = |index: &LeIndex, path: &str| -> Vec<String> {
+        let mut stmt = index
+            .storage
+            .conn()
+            .prepare("SELECT node_id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2 ORDER BY node_id")
+            .unwrap();
+        stmt.query_map(rusqlite::params![index.project_id, path], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .unwrap()
+    };
+    let before = stored_nodes(&index, &old_path);
+    assert!(!before.is_empty());
+    let edges_before = index.pdg().unwrap().edge_count();
+
+    std::fs::rename(
+        dir.path().join("old_name.py"),
+        dir.path().join("new_name.py"),
+    )
+    .unwrap();
+    let plan = index.plan_index(false).unwrap();
+    assert!(plan.to_parse.is_empty() && plan.deleted.is_empty());
+    assert_eq!(plan.renamed.len(), 1);
+
+    // The moved file is re-parsed, since path-derived data may change, but
+    // its nodes keep their identity
+    let stats = index.index_project(false).unwrap();
+    assert_eq!(stats.files_parsed, 1);
+    assert!(stored_nodes(&index, &old_path).is_empty());
+    let mut expected: Vec<String> = before
+        .iter()
+        .map(|node_id| node_id.replacen(&old_path, &new_path, 1))
+        .collect();
+    expected.sort();
+    assert_eq!(stored_nodes(&index, &new_path), expected);
+
+    let pdg = index.pdg().unwrap();
+    assert_eq!(pdg.nodes_in_file(&new_path).len(), before.len());
+    assert!(pdg.nodes_in_file(&old_path).is_empty());
+    assert_eq!(pdg.edge_count(), edges_before);
+    assert!(index
+        .search("helper", 5, None)
+        .unwrap()
+        .iter()
+        .any(|r| r.file_path == new_path));
+}
+
+#[test]
+fn test_embedding_dim_is_taken_from_project_config() {
+    let dir = tempdir().unwrap();
+    let mut config = crate::cli::config::ProjectConfig::default();
+    config.indexing.embeddings.embedding_dim = 64;
+    config.save(dir.path()).unwrap();
+    std::fs::write(dir.path().join("lib.py"), "def kept():\n    return 1\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    assert_eq!(index.embedding_dim(), 64);
+    index.index_project(false).unwrap();
+    assert_eq!(index.embedding_dim(), 64);
+
+    config.indexing.embeddings.embedding_dim = 0;
+    config.save(dir.path()).unwrap();
+    let err = LeIndex::new(dir.path()).err().expect("zero dimension");
+    assert!(format!("{err:#}").contains("Invalid embedding dimension"));
+}
+
+#[test]
+fn test_oversized_files_are_skipped_and_reported() {
+    let dir = tempdir().unwrap();
+    crate::cli::config::ProjectConfig::default()
+        .save(dir.path())
+        .unwrap();
+    let config_path = dir.path().join(crate::cli::config::DEFAULT_CONFIG_FILE);
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    let config = config.replace(
+        &format!(
+            "max_file_size = {}",
+            crate::cli::config::IndexingConfig::default().max_file_size
+        ),
+        "max_file_bytes = 512",
+    );
+    assert!(config.contains("max_file_bytes = 512"));
+    std::fs::write(&config_path, config).unwrap();
+
+    std::fs::write(dir.path().join("small.py"), "def kept():\n    return 1\n").unwrap();
+    let bundle = format!(
+        "function generated() {{ return [{}]; }}\n",
+        ["1"; 400].join(",")
+    );
+    std::fs::write(dir.path().join("bundle.js"), bundle).unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    let stats = index.index_project(false).unwrap();
+
+    let bundle_path = dir.path().join("bundle.js").display().to_string();
+    assert_eq!(stats.skipped_oversized_files, vec![bundle_path.clone()]);
+    assert_eq!(stats.total_files, 1);
+    let pdg = index.pdg().unwrap();
+    let files: Vec<String> = pdg
+        .node_indices()
+        .filter_map(|id| pdg.get_node(id))
+        .map(|node| node.file_path.to_string())
+        .collect();
+    assert!(files.iter().any(|f| f.ends_with("small.py")));
+    assert!(!files.contains(&bundle_path));
+}
+
+#[test]
+fn test_parse_errors_are_recorded_and_queryable() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("good.py"), "def fine():\n    return 1\n").unwrap();
+    let broken = dir.path().join("broken.py");
+    std::fs::write(
+        &broken,
+        "def ok():\n    return 1\n\ndef broken(:\n    return\n",
+    )
+    .unwrap();
+
+    // Without the flag the recovered syntax errors are not looked for
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(false).unwrap();
+    assert!(index.parse_errors().unwrap().is_empty());
+
+    index.project_config.indexing.record_parse_errors = true;
+    index.index_project(true).unwrap();
+
+    // A fresh instance reads the errors back without re-indexing
+    let reopened = LeIndex::new(dir.path()).unwrap();
+    let errors = reopened.parse_errors().unwrap();
+    assert!(!errors.is_empty());
+    let broken_path = broken.display().to_string();
+    assert!(
+        errors.iter().all(|e| e.file_path == broken_path),
+        "{:?}",
+        errors
+    );
+    assert_eq!(errors[0].line, Some(4));
+    assert!(errors[0].byte_range.is_some());
+
+    // Fixing the file clears its errors on the next run
+    std::fs::write(&broken, "def ok():\n    return 1\n").unwrap();
+    index.index_project(false).unwrap();
+    assert!(index.parse_errors().unwrap().is_empty());
+}
+
+#[test]
+fn test_language_stats_sum_to_totals() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import os\n\ndef load():\n    return os.getcwd()\n\ndef save():\n    return load()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "pub fn parse() -> u32 {\n    1\n}\n\npub fn run() -> u32 {\n    parse()\n}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("util.rs"), "pub fn helper() {}\n").unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    let stats = index.index_project(false).unwrap();
+
+    let python = &stats.language_stats["python"];
+    let rust = &stats.language_stats["rust"];
+    assert_eq!(python.files, 1);
+    assert_eq!(rust.files, 2);
+    assert!(python.signatures >= 2 && rust.signatures >= 3);
+    assert!(python.nodes >= 2 && rust.nodes >= 3);
+
+    let sum = |field: fn(&LanguageStat) -> usize| -> usize {
+        stats.language_stats.values().map(field).sum()
+    };
+    assert_eq!(sum(|s| s.files), stats.total_files);
+    assert_eq!(sum(|s| s.signatures), stats.total_signatures);
+    assert_eq!(sum(|s| s.nodes), stats.pdg_nodes);
+}
+
+#[test]
+fn test_minimal_node_info_results_get_metadata_from_pdg_and_storage() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("auth.py"),
+        "def authenticate_user(name, password):\n    return len(password) > 8\n",
+    )
+    .unwrap();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(false).unwrap();
+
+    let file_path = dir.path().join("auth.py").display().to_string();
+    let node_id = {
+        let pdg = index.pdg().unwrap();
+        let idx = pdg
+            .nodes_in_file(&file_path)
+            .into_iter()
+            .find(|&idx| pdg.get_node(idx).unwrap().name == "authenticate_user")
+            .unwrap();
+        pdg.get_node(idx).unwrap().id.clone()
+    };
+
+    // Re-index the engine with only an ID and searchable content
+    index
+        .search_engine
+        .index_nodes(vec![crate::search::search::NodeInfo {
+            node_id: node_id.clone(),
+            file_path: String::new(),
+            symbol_name: String::new(),
+            language: String::new(),
+            signature: None,
+            content: "def authenticate_user(name, password):".to_string(),
+            byte_range: (0, 0),
+            tfidf_embedding: Vec::new(),
+            neural_embedding: None,
+            complexity: 1,
+            pre_tokenized: None,
+            node_type: None,
+        }]);
+
+    let check = |index: &LeIndex| {
+        let results = index.search_uncached("authenticate_user", 5, None).unwrap();
+        let hit = results.iter().find(|r| r.node_id == node_id).unwrap();
+        assert_eq!(hit.file_path, file_path);
+        assert_eq!(hit.symbol_name, "authenticate_user");
+        assert_eq!(hit.language, "python");
+    };
+    check(&index);
+
+    // Without a loaded PDG the metadata comes from storage
+    index.pdg = None;
+    check(&index);
+}
+
+#[test]
+fn test_lower_threshold_surfaces_borderline_result() {
+    let dir = tempdir().unwrap();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    // No node matches the query text and none has an embedding, so each
+    // scores on complexity alone: ~0.2 for `strong`, ~0.04 for `borderline`
+    let node = |name: &str, complexity: u32| crate::search::search::NodeInfo {
+        node_id: format!("src/lib.rs:{}", name),
+        file_path: "src/lib.rs".to_string(),
+        symbol_name: name.to_string(),
+        language: "rust".to_string(),
+        signature: None,
+        content: format!("fn {}() {{}}", name),
+        byte_range: (0, 0),
+        tfidf_embedding: Vec::new(),
+        neural_embedding: None,
+        complexity,
+        pre_tokenized: None,
+        node_type: None,
+    };
+    index
+        .search_engine
+        .index_nodes(vec![node("strong", 100), node("borderline", 20)]);
+
+    let ids = |results: Vec<crate::search::search::SearchResult>| -> Vec<String> {
+        results.into_iter().map(|r| r.symbol_name).collect()
+    };
+    let default = ids(index.search("zzqx", 10, None).unwrap());
+    assert_eq!(default, vec!["strong".to_string()]);
+
+    let lowered = ids(index
+        .search_with_threshold("zzqx", 10, None, Some(0.01))
+        .unwrap());
+    assert_eq!(
+        lowered,
+        vec!["strong".to_string(), "borderline".to_string()]
+    );
+    let unfiltered = ids(index.search_with_threshold("zzqx", 10, None, None).unwrap());
+    assert_eq!(unfiltered, lowered);
+}
+
+#[test]
+fn test_search_by_embedding_ranks_own_node_first() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("shapes.py"),
+        "def circle_area(radius):\n    return 3.14159 * radius * radius\n\n\
+         def parse_config(path):\n    with open(path) as handle:\n        return handle.read().split(',')\n",
+    )
+    .unwrap();
+
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(true).unwrap();
+
+    let pdg = index.pdg().expect("pdg after indexing");
+    let target = pdg
+        .find_by_name("parse_config")
+        .and_then(|idx| pdg.get_node(idx))
+        .map(|node| node.id.clone())
+        .expect("parse_config node");
+    let embedding = index
+        .search_engine()
+        .node_embedding(&target)
+        .expect("indexed embedding")
+        .to_vec();
+
+    let results = index.search_by_embedding(&embedding, 5).unwrap();
+    assert_eq!(results[0].node_id, target);
+    assert_eq!(results[0].rank, 1);
+    assert_eq!(results[0].symbol_name, "parse_config");
+    assert!(results[0].file_path.ends_with("shapes.py"));
+    assert_eq!(results[0].symbol_type.as_deref(), Some("function"));
+
+    let err = index
+        .search_by_embedding(&embedding[..embedding.len() - 1], 5)
+        .unwrap_err();
+    assert!(err.to_string().contains("dimension mismatch"));
+}
+
+#[test]
+fn test_index_git_ref_reads_older_commit() {
+    let dir = tempdir().unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .is_ok_and(|out| out.status.success())
+    };
+    if !git(&["init", "-q"]) {
+        eprintln!("git unavailable; skipping");
+        return;
+    }
+    assert!(git(&["config", "user.email", "test@example.com"]));
+    assert!(git(&["config", "user.name", "Test"]));
+
+    let source = dir.path().join("lib.py");
+    std::fs::write(&source, "def old_func(x):\n    return x + 1\n").unwrap();
+    assert!(git(&["add", "lib.py"]));
+    assert!(git(&["commit", "-q", "-m", "first"]));
+    std::fs::write(&source, "def new_func(x):\n    return x * 2\n").unwrap();
+    assert!(git(&["commit", "-q", "-am", "second"]));
+
+    let rev_parse = |rev: &str| {
+        let out = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(["rev-parse", rev])
+            .output()
+            .unwrap();
+        String::from_utf8(out.stdout).unwrap().trim().to_string()
+    };
+    let old = LeIndex::index_git_ref(dir.path(), "HEAD~1").unwrap();
+    assert!(old
+        .project_id()
+        .ends_with(&format!("@{}", rev_parse("HEAD~1"))));
+    let pdg = old.pdg().expect("pdg for git ref");
+    assert!(pdg.find_by_name("old_func").is_some());
+    assert!(pdg.find_by_name("new_func").is_none());
+    assert_eq!(old.get_stats().total_files, 1);
+
+    // The working tree is untouched
+    assert!(std::fs::read_to_string(&source)
+        .unwrap()
+        .contains("new_func"));
+    let head = LeIndex::index_git_ref(dir.path(), "HEAD").unwrap();
+    let pdg = head.pdg().expect("pdg for HEAD");
+    assert!(pdg.find_by_name("new_func").is_some());
+    assert!(pdg.find_by_name("old_func").is_none());
+
+    // The same ref name at a new commit is stored separately
+    std::fs::write(&source, "def newer_func(x):\n    return x\n").unwrap();
+    assert!(git(&["commit", "-q", "-am", "third"]));
+    let newer = LeIndex::index_git_ref(dir.path(), "HEAD").unwrap();
+    assert_ne!(newer.project_id(), head.project_id());
+    assert!(newer.pdg().unwrap().find_by_name("new_func").is_none());
+
+    assert!(LeIndex::index_git_ref(dir.path(), "no-such-branch").is_err());
+}
+
+#[test]
+fn test_index_git_ref_applies_path_exclusions() {
+    let dir = tempdir().unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .is_ok_and(|out| out.status.success())
+    };
+    if !git(&["init", "-q"]) {
+        eprintln!("git unavailable; skipping");
+        return;
+    }
+    assert!(git(&["config", "user.email", "test@example.com"]));
+    assert!(git(&["config", "user.name", "Test"]));
+
+    std::fs::create_dir(dir.path().join("generated")).unwrap();
+    std::fs::write(
+        dir.path().join("generated").join("stubs.py"),
+        "def generated_func(x):\n    return x\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("lib.py"),
+        "def kept_func(x):\n    return x + 1\n",
+    )
+    .unwrap();
+    assert!(git(&["add", "."]));
+    assert!(git(&["commit", "-q", "-m", "first"]));
+
+    let mut config = crate::cli::config::ProjectConfig::default();
+    config.exclusions.path_patterns.push("*/generated/*".into());
+    config.save(dir.path()).unwrap();
+
+    let index = LeIndex::index_git_ref(dir.path(), "HEAD").unwrap();
+    let pdg = index.pdg().expect("pdg for git ref");
+    assert!(pdg.find_by_name("kept_func").is_some());
+    assert!(pdg.find_by_name("generated_func").is_none());
+    assert_eq!(index.get_stats().total_files, 1);
+}
+
+#[test]
+fn test_diff_generations_reports_changed_symbol() {
+    let dir = tempdir().unwrap();
+    let source = dir.path().join("shapes.py");
+    std::fs::write(
+        &source,
+        "def area(r):\n    return 3.14 * r * r\n\ndef perimeter(r):\n    return 2 * 3.14 * r\n",
+    )
+    .unwrap();
+    // Generations are only recorded on request
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(false).unwrap();
+    assert!(index.generations().unwrap().is_empty());
+
+    index.project_config.indexing.record_generations = true;
+    index.index_project(true).unwrap();
+
+    std::fs::write(
+        &source,
+        "def area(r):\n    return 3.14159 * r * r * 1.0\n\ndef diameter(r):\n    return 2 * r\n",
+    )
+    .unwrap();
+    index.index_project(false).unwrap();
+
+    let generations = index.generations().unwrap();
+    assert_eq!(generations.len(), 2);
+    let diff = index
+        .diff_generations(&generations[0].generation, &generations[1].generation)
+        .unwrap();
+    let names = |nodes: &[crate::storage::GenerationNode]| -> Vec<String> {
+        nodes.iter().map(|n| n.symbol_name.clone()).collect()
+    };
+    assert!(names(&diff.added_nodes).contains(&"diameter".to_string()));
+    assert!(names(&diff.removed_nodes).contains(&"perimeter".to_string()));
+    assert!(!names(&diff.removed_nodes).contains(&"area".to_string()));
+    assert!(diff
+        .modified_nodes
+        .iter()
+        .any(|m| m.after.symbol_name == "area"));
+
+    assert!(index
+        .diff_generations(&generations[0].generation, "no-such-generation")
+        .is_err());
+}
+
+#[test]
+fn test_cancelled_index_leaves_storage_consistent() {
+    let dir = tempdir().unwrap();
+    for name in ["a", "b", "c"] {
+        std::fs::write(
+            dir.path().join(format!("{}.rs", name)),
+            format!("pub fn {}_entry() -> u32 {{\n    1\n}}\n", name),
+        )
+        .unwrap();
+    }
+    let cancelled = crate::phase::CancellationToken::new();
+    cancelled.cancel();
+
+    // Cancelled before the first file: nothing is stored
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    let stats = index
+        .index_project_with_cancellation(false, None, &cancelled)
+        .unwrap();
+    assert_eq!(stats.cancelled_files, 3);
+    assert_eq!(stats.files_parsed, 0);
+    assert!(index.verify_storage().unwrap().is_consistent());
+
+    let stats = index.index_project(false).unwrap();
+    assert_eq!((stats.files_parsed, stats.cancelled_files), (3, 0));
+    assert!(index.verify_storage().unwrap().is_consistent());
+
+    // Incremental run: the unparsed file keeps its old rows and hash, so it
+    // is stale but not broken
+    std::fs::write(
+        dir.path().join("b.rs"),
+        "pub fn b_entry() -> u32 {\n    2\n}\n\npub fn b_extra() {}\n",
+    )
+    .unwrap();
+    let stats = index
+        .index_project_with_cancellation(false, None, &cancelled)
+        .unwrap();
+    assert_eq!(stats.cancelled_files, 1);
+    let report = index.verify_storage().unwrap();
+    assert!(report.dangling_edges.is_empty() && report.orphan_nodes.is_empty());
+    assert_eq!(report.hash_mismatches.len(), 1);
+    assert!(report.hash_mismatches[0].file_path.ends_with("b.rs"));
+
+    let stats = index.index_project(false).unwrap();
+    assert_eq!(stats.files_parsed, 1);
+    assert!(index.verify_storage().unwrap().is_consistent());
+
+    // Forced run: only parsed files are saved, so the unparsed files keep
+    // their rows and hashes instead of being wiped
+    let stats = index
+        .index_project_with_cancellation(true, None, &cancelled)
+        .unwrap();
+    assert_eq!(stats.cancelled_files, 3);
+    assert!(index.verify_storage().unwrap().is_consistent());
+    let mut reopened = LeIndex::new(dir.path()).unwrap();
+    let indexed =
+        crate::storage::pdg_store::get_indexed_files(&reopened.storage, &reopened.project_id)
+            .unwrap();
+    assert_eq!(indexed.len(), 3);
+    let stats = reopened.index_project(false).unwrap();
+    assert_eq!((stats.files_parsed, stats.cancelled_files), (0, 0));
+    assert!(reopened.verify_storage().unwrap().is_consistent());
+}
+
+#[test]
+fn test_cancelled_analysis_returns_partial_result() {
+    let dir = tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "pub fn parse_config() -> u32 {\n    1\n}\n",
+    )
+    .unwrap();
+    let mut index = LeIndex::new(dir.path()).unwrap();
+    index.index_project(false).unwrap();
+
+    let cancelled = crate::phase::CancellationToken::new();
+    cancelled.cancel();
+    let result = index
+        .analyze_with_cancellation("parse_config", 2000, None, &cancelled)
+        .unwrap();
+    assert!(result.cancelled);
+    assert!(result.context.is_none());
+
+    // The cancelled result is not cached
+    let result = index
+        .analyze_with_threshold("parse_config", 2000, None)
+        .unwrap();
+    assert!(!result.cancelled);
+    assert!(result.context.is_some());
+}
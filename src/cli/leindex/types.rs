@@ -1,6 +1,7 @@
 // Data types and constants for the leindex module.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::search::search::SearchResult;
@@ -10,7 +11,7 @@ pub(crate) const SOURCE_FILE_EXTENSIONS: &[&str] = &[
     // Main languages
     "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts",
     // Systems languages
-    "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", // Scripting & other
+    "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", "m", // Scripting & other
     "cs", "rb", "php", "lua", "scala", "sc", "sh", "bash", "json",
 ];
 
@@ -71,6 +72,9 @@ pub(crate) struct ProjectFileScan {
     /// Format: manifest_path → blake3 hex hash
     #[serde(default)]
     pub(crate) manifest_hashes: std::collections::HashMap<String, String>,
+    /// Source files skipped for exceeding `indexing.max_file_size`
+    #[serde(default)]
+    pub(crate) oversized_paths: Vec<PathBuf>,
 }
 
 /// Per-file statistics cached from PDG
@@ -90,6 +94,77 @@ pub struct FileStats {
     pub incoming_deps: usize,
 }
 
+/// Files an `index_project` run would touch, from [`LeIndex::plan_index`]
+///
+/// [`LeIndex::plan_index`]: super::LeIndex::plan_index
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexPlan {
+    /// Whether the plan was computed for a forced re-index
+    pub force: bool,
+
+    /// Source files that are new or whose content changed
+    pub to_parse: Vec<PathBuf>,
+
+    /// Source files whose content matches the indexed hash
+    pub unchanged: Vec<PathBuf>,
+
+    /// Indexed files that no longer exist in the project
+    pub deleted: Vec<String>,
+
+    /// Indexed files that moved unchanged, as `(old path, new path)`
+    ///
+    /// Their stored nodes are re-keyed to the new path instead of being
+    /// deleted and re-parsed.
+    #[serde(default)]
+    pub renamed: Vec<(String, PathBuf)>,
+}
+
+impl IndexPlan {
+    /// True when no source file would be parsed, removed or renamed
+    ///
+    /// A no-op plan may still re-annotate external dependencies if a
+    /// manifest changed.
+    pub fn is_noop(&self) -> bool {
+        self.to_parse.is_empty() && self.deleted.is_empty() && self.renamed.is_empty()
+    }
+}
+
+/// Progress event reported by [`LeIndex::index_project_with_progress`]
+///
+/// [`LeIndex::index_project_with_progress`]: super::LeIndex::index_project_with_progress
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum IndexProgress {
+    /// Discovery finished; `to_parse` of `total_files` source files changed
+    Scanned {
+        /// Source files found in the project
+        total_files: usize,
+        /// Files that will be parsed
+        to_parse: usize,
+    },
+    /// `parsed` of `total` changed files have been parsed
+    Parsing {
+        /// Files parsed so far
+        parsed: usize,
+        /// Files to parse in this run
+        total: usize,
+    },
+    /// PDG construction started
+    PdgBuildStarted,
+    /// PDG construction finished
+    PdgBuildFinished {
+        /// Nodes in the updated PDG
+        nodes: usize,
+        /// Edges in the updated PDG
+        edges: usize,
+    },
+    /// Indexing finished (also sent when nothing changed)
+    Done {
+        /// Files parsed in this run
+        files_parsed: usize,
+    },
+}
+
 /// Statistics from indexing operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexStats {
@@ -145,6 +220,156 @@ pub struct IndexStats {
     /// Number of external nodes recognized as builtin/system modules
     #[serde(default)]
     pub external_deps_builtin: usize,
+
+    /// Files with the longest parse time in the last run, slowest first
+    #[serde(default)]
+    pub slowest_files: Vec<FileParseTiming>,
+
+    /// Per-language breakdown keyed by lowercase language name. Summed
+    /// over all languages, the fields match `total_files`,
+    /// `total_signatures` and `pdg_nodes`
+    #[serde(default)]
+    pub language_stats: HashMap<String, LanguageStat>,
+
+    /// Source files skipped for exceeding `indexing.max_file_size`
+    #[serde(default)]
+    pub skipped_oversized_files: Vec<String>,
+
+    /// Changed files left unparsed because the run was cancelled; the next
+    /// run picks them up
+    #[serde(default)]
+    pub cancelled_files: usize,
+}
+
+/// Per-language counts reported in `IndexStats::language_stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageStat {
+    /// Number of source files in the language
+    pub files: usize,
+
+    /// Number of signatures extracted from files parsed in the last run
+    pub signatures: usize,
+
+    /// Number of PDG nodes tagged with the language
+    pub nodes: usize,
+}
+
+/// Number of files reported in `IndexStats::slowest_files`
+pub const SLOWEST_FILES_REPORTED: usize = 10;
+
+/// Parse time of a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileParseTiming {
+    /// File path
+    pub file_path: String,
+
+    /// Time spent in the tree-sitter parse, excluding I/O (milliseconds)
+    pub parse_duration_ms: f64,
+}
+
+impl FileParseTiming {
+    /// Collect the slowest successfully parsed files from `results`
+    pub fn slowest(results: &[crate::parse::parallel::ParsingResult], n: usize) -> Vec<Self> {
+        crate::parse::parallel::ParallelParser::slowest_results(results, n)
+            .into_iter()
+            .map(|result| Self {
+                file_path: result.file_path.display().to_string(),
+                parse_duration_ms: result.parse_duration.as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
+}
+
+/// Minimum relevance score kept by `LeIndex::search` unless the caller overrides it
+pub const DEFAULT_SEARCH_THRESHOLD: f32 = 0.1;
+
+/// Minimum relevance score kept by the entry-point searches of `LeIndex::analyze`
+pub const DEFAULT_ANALYSIS_THRESHOLD: f32 = 0.05;
+
+/// Number of nodes pulled from storage per page by `LeIndex::export_nodes_jsonl`
+pub const EXPORT_PAGE_SIZE: usize = 1000;
+
+/// One node as written by `LeIndex::export_nodes_jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedNode {
+    /// Unique node ID
+    pub id: String,
+
+    /// Symbol name
+    pub symbol: String,
+
+    /// File containing the symbol
+    pub file: String,
+
+    /// Programming language
+    pub language: String,
+
+    /// Byte range in the source file
+    pub byte_range: (usize, usize),
+
+    /// Cyclomatic complexity
+    pub complexity: u32,
+
+    /// TF-IDF embedding, if the node is in the search index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// Language tag given to nodes brought in by `LeIndex::import_nodes_jsonl`
+///
+/// A node declaring its own language `lang` is tagged `imported:lang`.
+pub const IMPORTED_LANGUAGE: &str = "imported";
+
+/// One node line accepted by `LeIndex::import_nodes_jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedNode {
+    /// Unique node ID; an existing node with the same ID is replaced
+    pub id: String,
+
+    /// Symbol name
+    pub symbol: String,
+
+    /// File containing the symbol
+    pub file: String,
+
+    /// Source language as reported by the producing system
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Node kind: function, class, method, variable or module (default function)
+    #[serde(default)]
+    pub kind: Option<String>,
+
+    /// Byte range in the source file
+    #[serde(default)]
+    pub byte_range: (usize, usize),
+
+    /// Cyclomatic complexity
+    #[serde(default)]
+    pub complexity: u32,
+
+    /// Source text indexed for search, in addition to the symbol name
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// One edge line accepted by `LeIndex::import_edges_jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedEdge {
+    /// ID of the source node
+    pub caller: String,
+
+    /// ID of the target node
+    pub callee: String,
+
+    /// Edge kind: call, data_dependency, inheritance, import or containment
+    /// (default call)
+    #[serde(default)]
+    pub edge_type: Option<String>,
+
+    /// Confidence for inferred edges
+    #[serde(default)]
+    pub confidence: Option<f32>,
 }
 
 /// Result from a deep analysis operation
@@ -164,6 +389,11 @@ pub struct AnalysisResult {
 
     /// Total time taken for the analysis process in milliseconds
     pub processing_time_ms: u64,
+
+    /// Whether the analysis was cancelled before expanding context; the
+    /// results are then the entry points found so far
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 /// Diagnostics information about the indexed project
@@ -1,4 +1,4 @@
-use super::helpers::{extract_string, extract_usize, wrap_with_meta};
+use super::helpers::{extract_string, extract_threshold, extract_usize, wrap_with_meta};
 use super::protocol::JsonRpcError;
 use crate::cli::registry::ProjectRegistry;
 use serde_json::Value;
@@ -46,6 +46,12 @@ data flow, and impact radius. Use for broad codebase understanding queries."
                     "default": 2000,
                     "minimum": 100,
                     "maximum": 100000
+                },
+                "threshold": {
+                    "type": ["number", "null"],
+                    "description": "Minimum relevance score for entry points (default: 0.05). Lower it to surface weaker matches; null disables the cutoff.",
+                    "default": 0.05,
+                    "minimum": 0
                 }
             },
             "required": ["query"]
@@ -60,6 +66,11 @@ data flow, and impact radius. Use for broad codebase understanding queries."
     ) -> Result<Value, JsonRpcError> {
         let query = extract_string(&args, "query")?;
         let token_budget = extract_usize(&args, "token_budget", 2000)?;
+        let threshold = extract_threshold(
+            &args,
+            "threshold",
+            crate::cli::leindex::DEFAULT_ANALYSIS_THRESHOLD,
+        )?;
 
         let project_path = args.get("project_path").and_then(|v| v.as_str());
         let handle = registry.get_or_create(project_path).await?;
@@ -76,7 +87,7 @@ data flow, and impact radius. Use for broad codebase understanding queries."
         }
 
         let result = guard
-            .analyze(&query, token_budget)
+            .analyze_with_threshold(&query, token_budget, threshold)
             .map_err(|e| JsonRpcError::internal_error(format!("Analysis error: {}", e)))?;
 
         serde_json::to_value(result)
@@ -72,7 +72,7 @@ multiple or byte-offset edits. Supports dry_run=true for preview."
                 },
                 "preview_token": {
                     "type": "string",
-                    "description": "The token returned by a previous LeIndex [Edit Preview] (tool: leindex.edit-preview) call. Required if using cached preview."
+                    "description": "The token returned by a previous LeIndex [Edit Preview] (tool: leindex.edit-preview) call. Required if using cached preview; tokens expire after 10 minutes."
                 }
             },
             "required": ["file_path"]
@@ -118,7 +118,7 @@ multiple or byte-offset edits. Supports dry_run=true for preview."
             // Strict token enforcement: if token is provided, it MUST be valid and fresh
             let cache = cached_entry.ok_or_else(|| {
                 JsonRpcError::invalid_params(
-                    "No cached preview found for this file — request a new preview",
+                    "No pending preview for this file (never created or expired) — request a new preview",
                 )
             })?;
 
@@ -349,3 +349,104 @@ multiple or byte-offset edits. Supports dry_run=true for preview."
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::mcp::edit_cache::EDIT_PREVIEW_TTL;
+    use crate::cli::mcp::helpers::test_registry_for;
+    use tempfile::tempdir;
+
+    fn edit_args(file: &std::path::Path) -> Value {
+        serde_json::json!({
+            "file_path": file.to_string_lossy(),
+            "old_text": "return 1",
+            "new_text": "return 2",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_preview_then_apply_with_token_writes_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("calc.py");
+        std::fs::write(&file, "def total():\n    return 1\n").unwrap();
+        let file = file.canonicalize().unwrap();
+        let registry = test_registry_for(dir.path());
+
+        let preview = EditPreviewHandler
+            .execute(&registry, edit_args(&file))
+            .await
+            .unwrap();
+        let token = preview["preview_token"].as_str().unwrap().to_string();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "def total():\n    return 1\n",
+            "preview must not write"
+        );
+
+        let mut apply_args = edit_args(&file);
+        apply_args["preview_token"] = Value::String("not-the-token".to_string());
+        let rejected = EditApplyHandler.execute(&registry, apply_args).await;
+        assert!(rejected.is_err(), "unknown token must be rejected");
+
+        let mut apply_args = edit_args(&file);
+        apply_args["preview_token"] = Value::String(token.clone());
+        let applied = EditApplyHandler
+            .execute(&registry, apply_args)
+            .await
+            .unwrap();
+        assert_eq!(applied["success"], true);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "def total():\n    return 2\n"
+        );
+
+        // The token is single-use.
+        let mut replay = edit_args(&file);
+        replay["preview_token"] = Value::String(token);
+        assert!(EditApplyHandler.execute(&registry, replay).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_with_expired_token_is_rejected() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("calc.py");
+        std::fs::write(&file, "def total():\n    return 1\n").unwrap();
+        let file = file.canonicalize().unwrap();
+        let registry = test_registry_for(dir.path());
+
+        let preview = EditPreviewHandler
+            .execute(&registry, edit_args(&file))
+            .await
+            .unwrap();
+        let token = preview["preview_token"].as_str().unwrap().to_string();
+
+        // Age the pending preview past its TTL.
+        let storage_path = {
+            let handle = registry.get_or_load(None).await.unwrap();
+            let guard = handle.read().await;
+            guard.storage_path().to_path_buf()
+        };
+        let mut entry = GLOBAL_EDIT_CACHE.get(&storage_path, &file).await.unwrap();
+        entry.timestamp = chrono::Utc::now()
+            - chrono::Duration::from_std(EDIT_PREVIEW_TTL).unwrap()
+            - chrono::Duration::seconds(1);
+        GLOBAL_EDIT_CACHE
+            .set(&storage_path, entry)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut apply_args = edit_args(&file);
+        apply_args["preview_token"] = Value::String(token);
+        let err = EditApplyHandler
+            .execute(&registry, apply_args)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("request a new preview"));
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "def total():\n    return 1\n"
+        );
+    }
+}
@@ -19,6 +19,11 @@ pub const EDIT_CACHE_MAX_ENTRY_BYTES: usize = 256 * 1024; // 256 KiB
 /// Inserts that would exceed this cap trigger LRU eviction.
 pub const EDIT_CACHE_TOTAL_CAP_BYTES: usize = 8 * 1024 * 1024; // 8 MiB
 
+/// How long a preview token stays valid for LeIndex [Edit Apply].
+/// Older previews are discarded on lookup so a forgotten preview cannot be
+/// confirmed long after the reviewer saw its diff.
+pub const EDIT_PREVIEW_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
 /// Entry in the edit cache, representing a previewed but not yet applied edit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EditCacheEntry {
@@ -54,6 +59,14 @@ impl EditCacheEntry {
                 .sum::<usize>()
             + 64 // overhead estimate for timestamp, metadata
     }
+
+    /// Whether this preview is older than [`EDIT_PREVIEW_TTL`].
+    pub fn is_expired(&self) -> bool {
+        chrono::Utc::now()
+            .signed_duration_since(self.timestamp)
+            .to_std()
+            .map_or(false, |age| age > EDIT_PREVIEW_TTL)
+    }
 }
 
 /// Error returned when an edit-preview entry exceeds the per-entry size limit.
@@ -195,22 +208,33 @@ impl EditCache {
     }
 
     /// Retrieve an edit preview from the cache.
+    ///
+    /// Previews older than [`EDIT_PREVIEW_TTL`] are cleared and reported as absent.
     pub async fn get(&self, project_storage: &Path, file_path: &Path) -> Option<EditCacheEntry> {
         let (abs_path, cache_file) = self
             .get_abs_path_and_cache_file(project_storage, file_path)
             .await;
 
         // Try hot cache first
-        {
+        let hot = {
             let mut guard = self.entries.lock().await;
-            if let Some(entry) = guard.0.get_mut(&abs_path) {
-                return Some(entry.clone());
+            guard.0.get_mut(&abs_path).map(|entry| entry.clone())
+        };
+        if let Some(entry) = hot {
+            if entry.is_expired() {
+                self.clear(project_storage, &abs_path).await;
+                return None;
             }
+            return Some(entry);
         }
 
         // Try cold storage fallback
         if let Ok(json) = tokio::fs::read_to_string(&cache_file).await {
             if let Ok(entry) = serde_json::from_str::<EditCacheEntry>(&json) {
+                if entry.is_expired() {
+                    let _ = tokio::fs::remove_file(&cache_file).await;
+                    return None;
+                }
                 // Backfill hot cache only if within budget
                 let entry_size = entry.estimated_size();
                 if entry_size <= EDIT_CACHE_MAX_ENTRY_BYTES {
@@ -370,6 +394,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_edit_cache_drops_expired_preview() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = EditCache::new();
+
+        let mut entry = make_entry(16, 16);
+        entry.file_path = PathBuf::from("/test/expired.rs");
+        entry.timestamp = chrono::Utc::now()
+            - chrono::Duration::from_std(EDIT_PREVIEW_TTL).unwrap()
+            - chrono::Duration::seconds(1);
+        cache
+            .set(temp_dir.path(), entry.clone())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(cache.get(temp_dir.path(), &entry.file_path).await.is_none());
+        assert_eq!(cache.hot_cache_bytes().await, 0);
+        // The cold copy is gone too, so a fresh cache cannot resurrect it.
+        let reopened = EditCache::new();
+        assert!(reopened
+            .get(temp_dir.path(), &entry.file_path)
+            .await
+            .is_none());
+    }
+
     #[test]
     fn test_edit_cache_entry_estimated_size() {
         let entry = make_entry(100, 200);
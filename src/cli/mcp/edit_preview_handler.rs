@@ -1,4 +1,4 @@
-use super::edit_cache::{EditCacheEntry, GLOBAL_EDIT_CACHE};
+use super::edit_cache::{EditCacheEntry, EDIT_PREVIEW_TTL, GLOBAL_EDIT_CACHE};
 use super::helpers::{
     apply_changes_in_memory, extract_string, make_diff, parse_edit_changes,
     validate_file_within_project, wrap_with_meta,
@@ -291,6 +291,7 @@ LeIndex [Edit Apply] to understand the blast radius of your change."
 
         let mut response = serde_json::json!({
             "preview_token": preview_token,
+            "preview_expires_in_secs": EDIT_PREVIEW_TTL.as_secs(),
             "diff": diff_json,
             "diff_text": crate::cli::mcp::output::render_unified_diff(&diff, false),
             "affected_symbols": affected_nodes,
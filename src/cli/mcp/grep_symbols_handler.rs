@@ -205,7 +205,7 @@ impl GrepSymbolsHandler {
             .clamp(50, MAX_CANDIDATE_LIMIT);
         let mut candidate_results = index
             .search(&pattern, candidate_limit, None)
-            .map_err(|e| JsonRpcError::search_failed(format!("Search error: {}", e)))?;
+            .map_err(|e| JsonRpcError::from_search_error(&e))?;
 
         index
             .ensure_pdg_loaded()
@@ -296,10 +296,9 @@ impl GrepSymbolsHandler {
                     let expanded = (candidate_limit * 10).min(1000);
                     if expanded > candidate_limit {
                         candidate_limit = expanded;
-                        candidate_results =
-                            index.search(&pattern, candidate_limit, None).map_err(|e| {
-                                JsonRpcError::search_failed(format!("Search error: {}", e))
-                            })?;
+                        candidate_results = index
+                            .search(&pattern, candidate_limit, None)
+                            .map_err(|e| JsonRpcError::from_search_error(&e))?;
                         continue 'semantic_retry;
                     }
                 }
@@ -15,14 +15,17 @@ pub use super::git_status_handler::GitStatusHandler;
 pub use super::grep_symbols_handler::GrepSymbolsHandler;
 pub use super::impact_analysis_handler::ImpactAnalysisHandler;
 pub use super::index_handler::IndexHandler;
+pub use super::list_symbols_handler::ListSymbolsHandler;
 pub use super::phase_handler::{PhaseAnalysisAliasHandler, PhaseAnalysisHandler};
 pub use super::project_map_handler::ProjectMapHandler;
 pub use super::read_file_handler::ReadFileHandler;
 pub use super::read_symbol_handler::ReadSymbolHandler;
+pub use super::reload_handler::ReloadHandler;
 pub use super::rename_symbol_handler::RenameSymbolHandler;
 pub use super::search_handler::SearchHandler;
 pub use super::symbol_lookup_handler::SymbolLookupHandler;
 pub use super::text_search_handler::TextSearchHandler;
+pub use super::validate_edit_handler::ValidateEditHandler;
 pub use super::write_handler::WriteHandler;
 
 // ── Tool surface definition ──────────────────────────────────────────────
@@ -73,6 +76,12 @@ dispatch_handler! {
     ReadFile            => ReadFileHandler,
     /// Handler for git status
     GitStatus           => GitStatusHandler,
+    /// Handler for reloading the warm index from storage
+    Reload              => ReloadHandler,
+    /// Handler for paginated symbol listing
+    ListSymbols         => ListSymbolsHandler,
+    /// Handler for edit validation
+    ValidateEdit        => ValidateEditHandler,
 }
 
 #[cfg(test)]
@@ -100,6 +109,9 @@ mod tests {
         assert_eq!(EditApplyHandler.name(), "leindex.edit-apply");
         assert_eq!(RenameSymbolHandler.name(), "leindex.rename-symbol");
         assert_eq!(ImpactAnalysisHandler.name(), "leindex.impact-analysis");
+        assert_eq!(ReloadHandler.name(), "leindex.reload");
+        assert_eq!(ListSymbolsHandler.name(), "leindex.list-symbols");
+        assert_eq!(ValidateEditHandler.name(), "leindex.validate-edit");
     }
 
     #[test]
@@ -157,6 +169,8 @@ mod tests {
             EditApplyHandler.argument_schema(),
             RenameSymbolHandler.argument_schema(),
             ImpactAnalysisHandler.argument_schema(),
+            ListSymbolsHandler.argument_schema(),
+            ValidateEditHandler.argument_schema(),
         ];
         for schema in tools_with_project_path {
             let props = schema.get("properties").unwrap();
@@ -43,6 +43,31 @@ pub(crate) fn extract_bool(args: &Value, key: &str, default: bool) -> bool {
     }
 }
 
+/// Helper to extract a relevance threshold argument.
+///
+/// A missing argument yields `Some(default)`; an explicit `null` yields
+/// `None` (no threshold). Negative or non-numeric values are rejected.
+pub(crate) fn extract_threshold(
+    args: &Value,
+    key: &str,
+    default: f32,
+) -> Result<Option<f32>, JsonRpcError> {
+    let value = match args.get(key) {
+        None => return Ok(Some(default)),
+        Some(Value::Null) => return Ok(None),
+        Some(Value::Number(n)) => n.as_f64(),
+        Some(Value::String(s)) => s.trim().parse::<f64>().ok(),
+        Some(_) => None,
+    };
+    match value {
+        Some(v) if v.is_finite() && v >= 0.0 => Ok(Some(v as f32)),
+        _ => Err(JsonRpcError::invalid_params(format!(
+            "{} must be a non-negative number or null",
+            key
+        ))),
+    }
+}
+
 /// Validate that a file path resides within the project root.
 pub(crate) fn validate_file_within_project(
     file_path: &str,
@@ -572,6 +597,10 @@ pub(crate) fn phase_analysis_schema() -> Value {
                 "type": "integer",
                 "default": 12000
             },
+            "max_tokens": {
+                "type": "integer",
+                "description": "Optional output budget in tokens, counted by the project's configured tokenizer"
+            },
             "include_docs": {
                 "type": "boolean",
                 "description": "IMPORTANT: Enable to include prose/documentation files (README, docs/, *.md) \
@@ -622,6 +651,16 @@ mod tests {
         assert_eq!(extract_usize(&args, "missing", 10).unwrap(), 10);
     }
 
+    #[test]
+    fn test_extract_threshold() {
+        let args = serde_json::json!({"low": 0.02, "off": null, "text": "0.3", "neg": -1});
+        assert_eq!(extract_threshold(&args, "missing", 0.1).unwrap(), Some(0.1));
+        assert_eq!(extract_threshold(&args, "low", 0.1).unwrap(), Some(0.02));
+        assert_eq!(extract_threshold(&args, "off", 0.1).unwrap(), None);
+        assert_eq!(extract_threshold(&args, "text", 0.1).unwrap(), Some(0.3));
+        assert!(extract_threshold(&args, "neg", 0.1).is_err());
+    }
+
     #[test]
     fn test_extract_bool_native_bool() {
         let args = serde_json::json!({"flag": true, "off": false});
@@ -0,0 +1,244 @@
+use super::helpers::{extract_usize, node_type_str, wrap_with_meta};
+use super::protocol::JsonRpcError;
+use crate::cli::registry::ProjectRegistry;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Symbol kinds accepted by the `kind` argument.
+const SYMBOL_KINDS: &[&str] = &[
+    "function", "class", "method", "variable", "module", "external",
+];
+
+/// Default page size when `limit` is omitted.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Upper bound on `limit` so a single page stays small enough for a client.
+const MAX_LIMIT: usize = 500;
+
+/// Handler for LeIndex [List Symbols] — paginated symbol enumeration.
+#[derive(Clone)]
+pub struct ListSymbolsHandler;
+
+#[allow(missing_docs)]
+impl ListSymbolsHandler {
+    pub fn name(&self) -> &str {
+        "leindex.list-symbols"
+    }
+
+    pub fn title(&self) -> &str {
+        "LeIndex [List Symbols]"
+    }
+
+    pub fn description(&self) -> &str {
+        "List the project's symbols with their locations, optionally filtered by kind and \
+        file path prefix. Results are ordered by file and position and paginated with \
+        offset/limit; 'total' counts all matching symbols."
+    }
+
+    pub fn argument_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project directory (auto-indexes on first use; omit to use current project)"
+                },
+                "kind": {
+                    "type": "string",
+                    "enum": ["function", "class", "method", "variable", "module", "external", "all"],
+                    "description": "Filter by symbol kind (default: all, which excludes external symbols)",
+                    "default": "all"
+                },
+                "file": {
+                    "type": "string",
+                    "description": "Only list symbols whose file path starts with this prefix \
+                    (absolute or relative to the project root)"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Skip the first N symbols for pagination (default: 0)",
+                    "default": 0,
+                    "minimum": 0
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum symbols to return (default: 50, max: 500)",
+                    "default": 50,
+                    "minimum": 1,
+                    "maximum": 500
+                }
+            },
+            "required": []
+        })
+    }
+
+    pub async fn execute(
+        &self,
+        registry: &Arc<ProjectRegistry>,
+        args: Value,
+    ) -> Result<Value, JsonRpcError> {
+        let kind = args
+            .get("kind")
+            .and_then(|v| v.as_str())
+            .unwrap_or("all")
+            .to_owned();
+        if kind != "all" && !SYMBOL_KINDS.contains(&kind.as_str()) {
+            return Err(JsonRpcError::invalid_params(format!(
+                "Unknown symbol kind '{}'. Expected one of: {}, all",
+                kind,
+                SYMBOL_KINDS.join(", ")
+            )));
+        }
+        let offset = extract_usize(&args, "offset", 0)?;
+        let limit = extract_usize(&args, "limit", DEFAULT_LIMIT)?.clamp(1, MAX_LIMIT);
+        let project_path = args.get("project_path").and_then(|v| v.as_str());
+
+        let handle = registry.get_or_create(project_path).await?;
+        let mut index = handle.write().await;
+        index
+            .ensure_pdg_loaded()
+            .map_err(|e| JsonRpcError::indexing_failed(format!("Failed to load PDG: {}", e)))?;
+
+        let file_prefix = args
+            .get("file")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|raw| {
+                let path = Path::new(raw);
+                if path.is_relative() {
+                    index
+                        .project_path()
+                        .join(path)
+                        .to_string_lossy()
+                        .into_owned()
+                } else {
+                    raw.to_owned()
+                }
+            });
+
+        let Some(pdg) = index.pdg() else {
+            return Err(JsonRpcError::project_not_indexed(
+                index.project_path().display().to_string(),
+            ));
+        };
+
+        let mut matches: Vec<_> = pdg
+            .node_indices()
+            .filter_map(|nid| pdg.get_node(nid))
+            .filter(|node| {
+                let node_kind = node_type_str(&node.node_type);
+                if kind == "all" {
+                    node_kind != "external"
+                } else {
+                    node_kind == kind
+                }
+            })
+            .filter(|node| {
+                file_prefix
+                    .as_deref()
+                    .map_or(true, |prefix| node.file_path.starts_with(prefix))
+            })
+            .collect();
+        // Stable order so consecutive pages neither repeat nor skip symbols.
+        matches.sort_by(|a, b| {
+            (&a.file_path, a.byte_range.0, &a.name).cmp(&(&b.file_path, b.byte_range.0, &b.name))
+        });
+
+        let total = matches.len();
+        let symbols: Vec<Value> = matches
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|node| {
+                serde_json::json!({
+                    "name": node.name,
+                    "type": node_type_str(&node.node_type),
+                    "file": node.file_path,
+                    "byte_range": node.byte_range,
+                    "language": node.language,
+                })
+            })
+            .collect();
+        let shown = symbols.len();
+
+        let response = serde_json::json!({
+            "symbols": symbols,
+            "total": total,
+            "offset": offset,
+            "limit": limit,
+            "shown": shown,
+            "has_more": offset + shown < total,
+        });
+        Ok(wrap_with_meta(response, &index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::mcp::helpers::test_registry_for;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_list_symbols_paginates_and_filters_by_kind() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("shapes.py"),
+            "class Shape:\n    pass\n\n\
+             def area():\n    return 1\n\n\
+             def perimeter():\n    return 2\n\n\
+             def volume():\n    return 3\n",
+        )
+        .unwrap();
+        let registry = test_registry_for(dir.path());
+
+        let first = ListSymbolsHandler
+            .execute(
+                &registry,
+                serde_json::json!({ "kind": "function", "limit": 2 }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first["total"], 3);
+        assert_eq!(first["shown"], 2);
+        assert_eq!(first["has_more"], true);
+        let names: Vec<&str> = first["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["area", "perimeter"]);
+
+        let second = ListSymbolsHandler
+            .execute(
+                &registry,
+                serde_json::json!({ "kind": "function", "offset": 2, "limit": 2 }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(second["shown"], 1);
+        assert_eq!(second["symbols"][0]["name"], "volume");
+        assert_eq!(second["has_more"], false);
+
+        let classes = ListSymbolsHandler
+            .execute(&registry, serde_json::json!({ "kind": "class" }))
+            .await
+            .unwrap();
+        assert_eq!(classes["total"], 1);
+        assert_eq!(classes["symbols"][0]["name"], "Shape");
+        assert_eq!(classes["symbols"][0]["type"], "class");
+
+        let capped = ListSymbolsHandler
+            .execute(&registry, serde_json::json!({ "limit": 10_000 }))
+            .await
+            .unwrap();
+        assert_eq!(capped["limit"], MAX_LIMIT);
+
+        let bad_kind = ListSymbolsHandler
+            .execute(&registry, serde_json::json!({ "kind": "widget" }))
+            .await;
+        assert!(bad_kind.is_err());
+    }
+}
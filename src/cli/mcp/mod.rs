@@ -56,6 +56,8 @@ pub mod grep_symbols_handler;
 pub mod impact_analysis_handler;
 /// Handler for LeIndex [Index] — project indexing.
 pub mod index_handler;
+/// Handler for LeIndex [List Symbols] — paginated symbol enumeration.
+pub mod list_symbols_handler;
 /// Handler for LeIndex [Phase Analysis] — multi-phase analysis.
 pub mod phase_handler;
 /// Handler for LeIndex [Project Map] — annotated project tree.
@@ -64,6 +66,8 @@ pub mod project_map_handler;
 pub mod read_file_handler;
 /// Handler for LeIndex [Read Symbol] — targeted symbol source read.
 pub mod read_symbol_handler;
+/// Handler for LeIndex [Reload] — refresh the warm session cache from storage.
+pub mod reload_handler;
 /// Handler for LeIndex [Rename Symbol] — cross-file symbol rename.
 pub mod rename_symbol_handler;
 /// Handler for LeIndex [Search] — semantic code search.
@@ -72,6 +76,8 @@ pub mod search_handler;
 pub mod symbol_lookup_handler;
 /// Handler for LeIndex [Text Search] — raw text/regex search.
 pub mod text_search_handler;
+/// Handler for LeIndex [Validate Edit] — validate proposed changes without applying.
+pub mod validate_edit_handler;
 /// Handler for LeIndex [Write] — atomic file creation with PDG surfacing.
 pub mod write_handler;
 
@@ -82,7 +88,7 @@ pub mod sse;
 
 pub use protocol::{
     error_codes, JsonRpcError, JsonRpcMessage, JsonRpcNotification, JsonRpcRequest,
-    JsonRpcResponse, NotificationType,
+    JsonRpcResponse, NotificationType, RequestLimits,
 };
 pub use server::{McpServer, McpServerConfig};
 
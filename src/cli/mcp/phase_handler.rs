@@ -170,6 +170,10 @@ async fn execute_phase_analysis(
     let max_focus_files = extract_usize(&args, "max_focus_files", 20)?;
     let top_n = extract_usize(&args, "top_n", 10)?;
     let max_output_chars = extract_usize(&args, "max_chars", 12000)?;
+    let max_output_tokens = args
+        .get("max_tokens")
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize);
 
     // ── Single-file deep dive (Task C.7) ────────────────────────────────────
     // When `path` is a file, augment the phase report with per-symbol PDG data:
@@ -256,6 +260,11 @@ async fn execute_phase_analysis(
             None
         };
 
+    let tokenizer = crate::cli::config::ProjectConfig::load(&root)
+        .unwrap_or_default()
+        .tokens
+        .tokenizer;
+
     let options = PhaseOptions {
         root,
         focus_files,
@@ -264,10 +273,14 @@ async fn execute_phase_analysis(
         max_focus_files,
         top_n,
         max_output_chars,
+        max_output_tokens,
+        tokenizer,
         use_incremental_refresh: true,
         include_docs,
         docs_mode: parsed_docs_mode,
         hotspot_keywords: PhaseOptions::default().hotspot_keywords,
+        min_parser_completeness: None,
+        max_parse_failure_ratio: None,
     };
 
     let report = tokio::task::spawn_blocking(move || run_phase_analysis(options, selection))
@@ -8,6 +8,81 @@ use serde_json::Value;
 /// JSON-RPC 2.0 specification version
 pub const JSONRPC_VERSION: &str = "2.0";
 
+/// Default maximum size of a single JSON-RPC payload (10 MiB)
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 10 * 1024 * 1024;
+
+/// Default maximum nesting depth of arrays/objects in a JSON-RPC payload
+///
+/// Real MCP requests nest a handful of levels; the cap keeps a hostile
+/// `[[[[...` payload from driving the parser into deep recursion.
+pub const DEFAULT_MAX_JSON_DEPTH: usize = 64;
+
+/// Limits enforced on a raw JSON-RPC payload before it is deserialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Maximum payload size in bytes
+    pub max_bytes: usize,
+    /// Maximum nesting depth of arrays and objects
+    pub max_depth: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_REQUEST_BYTES,
+            max_depth: DEFAULT_MAX_JSON_DEPTH,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Check a raw payload against these limits without parsing it
+    ///
+    /// Oversized payloads are rejected as invalid requests; payloads nested
+    /// deeper than `max_depth` are rejected as parse errors. The depth scan
+    /// is a single pass that ignores brackets inside string literals.
+    pub fn check(&self, payload: &[u8]) -> Result<(), JsonRpcError> {
+        if payload.len() > self.max_bytes {
+            return Err(JsonRpcError::invalid_request(format!(
+                "Request too large: {} bytes (max: {} bytes)",
+                payload.len(),
+                self.max_bytes
+            )));
+        }
+
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for &byte in payload {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if byte == b'\\' {
+                    escaped = true;
+                } else if byte == b'"' {
+                    in_string = false;
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'{' | b'[' => {
+                    depth += 1;
+                    if depth > self.max_depth {
+                        return Err(JsonRpcError::parse_error(format!(
+                            "JSON nesting exceeds maximum depth of {}",
+                            self.max_depth
+                        )));
+                    }
+                }
+                b'}' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
 /// JSON-RPC 2.0 Request
 ///
 /// Per the JSON-RPC 2.0 spec, a request must have:
@@ -220,6 +295,12 @@ impl JsonRpcMessage {
         }
     }
 
+    /// Parse a JSON string into a JsonRpcMessage after enforcing `limits`
+    pub fn from_json_with_limits(json: &str, limits: &RequestLimits) -> Result<Self, JsonRpcError> {
+        limits.check(json.as_bytes())?;
+        Self::from_json(json)
+    }
+
     /// Check if this message is a notification
     pub fn is_notification(&self) -> bool {
         matches!(self, JsonRpcMessage::Notification(_))
@@ -307,6 +388,12 @@ impl JsonRpcResponse {
     pub fn from_result_opt(id: Option<Value>, result: Result<Value, JsonRpcError>) -> Self {
         Self::from_result(id.unwrap_or(Value::Null), result)
     }
+
+    /// Tag the error (if any) with the server-side request ID
+    pub fn with_request_id(mut self, request_id: &str) -> Self {
+        self.error = self.error.map(|e| e.with_request_id(request_id));
+        self
+    }
 }
 
 /// JSON-RPC 2.0 Error
@@ -340,6 +427,24 @@ impl JsonRpcError {
         }
     }
 
+    /// Record the server-side request ID in `data.request_id`
+    ///
+    /// Clients can quote it when reporting a failure so the matching
+    /// `mcp_request` log span can be found. Non-object `data` is preserved
+    /// under `data.detail`.
+    pub fn with_request_id(mut self, request_id: &str) -> Self {
+        let data = match self.data.take() {
+            Some(Value::Object(mut map)) => {
+                map.insert("request_id".to_string(), Value::from(request_id));
+                Value::Object(map)
+            }
+            Some(other) => serde_json::json!({ "request_id": request_id, "detail": other }),
+            None => serde_json::json!({ "request_id": request_id }),
+        };
+        self.data = Some(data);
+        self
+    }
+
     /// Create a parse error (code -32700)
     pub fn parse_error(msg: impl Into<String>) -> Self {
         Self::new(error_codes::PARSE_ERROR, msg)
@@ -464,6 +569,23 @@ impl JsonRpcError {
         )
     }
 
+    /// Map a search failure, reporting query mistakes as invalid params
+    ///
+    /// A typed [`crate::search::search::Error`] anywhere in the chain that
+    /// blames the query (see `is_invalid_query`) becomes -32602; anything
+    /// else is a [`search_failed`](Self::search_failed) error.
+    pub fn from_search_error(err: &anyhow::Error) -> Self {
+        let typed = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<crate::search::search::Error>());
+        match typed {
+            Some(search_err) if search_err.is_invalid_query() => {
+                Self::invalid_params(format!("Invalid search query: {}", search_err))
+            }
+            _ => Self::search_failed(format!("Search error: {}", err)),
+        }
+    }
+
     /// Create a context expansion failed error
     pub fn context_expansion_failed(msg: impl Into<String>) -> Self {
         let m = msg.into();
@@ -868,4 +990,65 @@ mod tests {
             panic!("Expected request");
         }
     }
+
+    #[test]
+    fn test_request_limits_reject_oversized_payload() {
+        let limits = RequestLimits {
+            max_bytes: 64,
+            ..Default::default()
+        };
+        let json = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{{"pad":"{}"}}}}"#,
+            "x".repeat(128)
+        );
+
+        let err = JsonRpcMessage::from_json_with_limits(&json, &limits).unwrap_err();
+        assert_eq!(err.code, error_codes::INVALID_REQUEST);
+        assert!(err.message.contains("too large"));
+    }
+
+    #[test]
+    fn test_request_limits_reject_deep_nesting() {
+        let limits = RequestLimits::default();
+        let depth = DEFAULT_MAX_JSON_DEPTH + 1;
+        let json = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{}{}}}"#,
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+
+        let err = JsonRpcMessage::from_json_with_limits(&json, &limits).unwrap_err();
+        assert_eq!(err.code, error_codes::PARSE_ERROR);
+        assert!(err.message.contains("depth"));
+    }
+
+    #[test]
+    fn test_request_limits_ignore_brackets_in_strings() {
+        let limits = RequestLimits {
+            max_depth: 3,
+            ..Default::default()
+        };
+        let json = r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":{"q":"[[[[{{{{ \" [[[["}}"#;
+
+        let msg = JsonRpcMessage::from_json_with_limits(json, &limits).unwrap();
+        assert!(msg.is_request());
+    }
+
+    #[test]
+    fn test_search_error_mapping_uses_typed_variant() {
+        use crate::search::search::Error as SearchError;
+
+        let mismatch = anyhow::Error::new(SearchError::DimensionMismatch {
+            expected: 768,
+            got: 384,
+        })
+        .context("Embedding search failed");
+        let err = JsonRpcError::from_search_error(&mismatch);
+        assert_eq!(err.code, error_codes::INVALID_PARAMS);
+        assert!(err.message.contains("expected 768, got 384"));
+
+        let failed = anyhow::Error::new(SearchError::TaskFailed("cancelled".into()));
+        let err = JsonRpcError::from_search_error(&failed);
+        assert_eq!(err.code, error_codes::SEARCH_FAILED);
+    }
 }
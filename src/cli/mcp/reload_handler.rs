@@ -0,0 +1,59 @@
+use super::helpers::wrap_with_meta;
+use super::protocol::JsonRpcError;
+use crate::cli::registry::ProjectRegistry;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Handler for LeIndex [Reload]
+///
+/// Re-reads a project's stored index into the server's warm session cache.
+#[derive(Clone)]
+pub struct ReloadHandler;
+
+impl ReloadHandler {
+    /// Returns the name of this MCP tool (MCP-compliant: ASCII letters, digits, underscore, hyphen, dot only)
+    pub fn name(&self) -> &str {
+        "leindex.reload"
+    }
+
+    /// Returns the human-readable display title for this tool
+    pub fn title(&self) -> &str {
+        "LeIndex [Reload]"
+    }
+
+    /// Returns the description of this RPC method
+    pub fn description(&self) -> &str {
+        "Reload the project's index from storage. The server keeps a loaded index warm across \
+calls; use this after the index was rebuilt outside the server (e.g. by the CLI)."
+    }
+
+    /// Returns the JSON schema for the arguments of this RPC method
+    pub fn argument_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project directory (omit to use current project)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    /// Executes the RPC method
+    pub async fn execute(
+        &self,
+        registry: &Arc<ProjectRegistry>,
+        args: Value,
+    ) -> Result<Value, JsonRpcError> {
+        let project_path = args.get("project_path").and_then(|v| v.as_str());
+        let stats = registry.reload_project(project_path).await?;
+
+        let handle = registry.get_or_load(project_path).await?;
+        let idx = handle.read().await;
+        serde_json::to_value(stats)
+            .map(|v| wrap_with_meta(v, &idx))
+            .map_err(|e| JsonRpcError::internal_error(format!("Serialization error: {}", e)))
+    }
+}
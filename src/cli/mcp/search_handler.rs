@@ -1,4 +1,6 @@
-use super::helpers::{extract_string, extract_usize, resolve_scope, wrap_with_meta};
+use super::helpers::{
+    extract_string, extract_threshold, extract_usize, resolve_scope, wrap_with_meta,
+};
 use super::protocol::JsonRpcError;
 use crate::cli::registry::ProjectRegistry;
 use serde_json::Value;
@@ -67,6 +69,12 @@ to auto-switch/auto-index projects."
         'exact' prioritizes exact symbol name matches (higher text/structural weights), \
         'semantic' prioritizes conceptual relevance (higher TF-IDF semantic weights).",
                     "default": "code"
+                },
+                "threshold": {
+                    "type": ["number", "null"],
+                    "description": "Minimum relevance score to keep (default: 0.1). Lower it to surface weaker matches; null disables the cutoff.",
+                    "default": 0.1,
+                    "minimum": 0
                 }
             },
             "required": ["query"]
@@ -82,6 +90,11 @@ to auto-switch/auto-index projects."
         let query = extract_string(&args, "query")?;
         let top_k = extract_usize(&args, "top_k", 10)?;
         let offset = extract_usize(&args, "offset", 0)?;
+        let threshold = extract_threshold(
+            &args,
+            "threshold",
+            crate::cli::leindex::DEFAULT_SEARCH_THRESHOLD,
+        )?;
         let search_mode = args
             .get("search_mode")
             .and_then(|v| v.as_str())
@@ -133,8 +146,8 @@ to auto-switch/auto-index projects."
         const MAX_FETCH_K: usize = 1000;
         let mut fetch_k = (top_k + offset).min(MAX_FETCH_K);
         let mut all_results = guard
-            .search(&query, fetch_k, query_type)
-            .map_err(|e| JsonRpcError::search_failed(format!("Search error: {}", e)))?;
+            .search_with_threshold(&query, fetch_k, query_type, threshold)
+            .map_err(|e| JsonRpcError::from_search_error(&e))?;
 
         let in_scope = |file_path: &str| match &scope {
             Some(s) => {
@@ -159,8 +172,8 @@ to auto-switch/auto-index projects."
             fetch_k = (fetch_k * 10).min(MAX_FETCH_K * 10);
             if fetch_k > top_k + offset {
                 all_results = guard
-                    .search(&query, fetch_k, query_type)
-                    .map_err(|e| JsonRpcError::search_failed(format!("Search error: {}", e)))?;
+                    .search_with_threshold(&query, fetch_k, query_type, threshold)
+                    .map_err(|e| JsonRpcError::from_search_error(&e))?;
                 filtered = all_results
                     .iter()
                     .filter(|r| in_scope(&r.file_path))
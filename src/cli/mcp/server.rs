@@ -4,11 +4,14 @@
 // using axum for HTTP handling.
 
 use super::handlers::{all_tool_handlers, ToolHandler};
-use super::protocol::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use super::protocol::{
+    JsonRpcError, JsonRpcRequest, JsonRpcResponse, RequestLimits, DEFAULT_MAX_JSON_DEPTH,
+};
 use crate::cli::registry::ProjectRegistry;
 use anyhow::Context;
 use axum::{
-    extract::Json,
+    body::Body,
+    extract::{DefaultBodyLimit, Json},
     http::{HeaderMap, HeaderValue, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
@@ -30,7 +33,7 @@ use std::sync::{
 use std::time::Instant;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 
 /// Global server state — multi-project registry.
 ///
@@ -54,6 +57,29 @@ fn generate_session_id() -> String {
     format!("leindex-{pid}-{seq}")
 }
 
+/// Monotonic counter for generating request IDs.
+static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Generate a unique request ID string: `"req-<pid>-<seq>"`.
+pub fn generate_request_id() -> String {
+    let pid = std::process::id();
+    let seq = REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("req-{pid}-{seq}")
+}
+
+/// Open the `mcp_request` span that scopes one JSON-RPC request.
+///
+/// The `method` field starts empty and is recorded once the body has been
+/// parsed. Work moved onto blocking threads must re-enter the span
+/// explicitly to keep its events correlated.
+pub fn request_span(request_id: &str) -> tracing::Span {
+    tracing::info_span!(
+        "mcp_request",
+        request_id = %request_id,
+        method = tracing::field::Empty
+    )
+}
+
 /// Default MCP server port.
 ///
 /// Chosen in IANA dynamic/private range (49152-65535) and well above the
@@ -223,6 +249,9 @@ pub struct McpServerConfig {
     /// Maximum request size in megabytes
     pub max_request_size_mb: usize,
 
+    /// Maximum nesting depth of arrays/objects in a request body
+    pub max_json_depth: usize,
+
     /// Request timeout in seconds (per tool call)
     pub request_timeout_secs: u64,
 }
@@ -236,11 +265,22 @@ impl Default for McpServerConfig {
             bind_address: SocketAddr::from(([127, 0, 0, 1], DEFAULT_MCP_PORT)),
             enable_cors: true,
             max_request_size_mb: 10,
+            max_json_depth: DEFAULT_MAX_JSON_DEPTH,
             request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
         }
     }
 }
 
+impl McpServerConfig {
+    /// Payload limits checked before a request body is deserialized.
+    pub fn request_limits(&self) -> RequestLimits {
+        RequestLimits {
+            max_bytes: self.max_request_size_mb.saturating_mul(1024 * 1024),
+            max_depth: self.max_json_depth,
+        }
+    }
+}
+
 /// MCP Server
 #[derive(Clone)]
 pub struct McpServer {
@@ -461,7 +501,11 @@ impl McpServer {
         // (which would itself be a startup error worth surfacing
         // via the log).
         let bind_address = listener.local_addr().unwrap_or(self.config.bind_address);
-        let router = Self::router();
+        // `/mcp` reads its own body and reports the size limit as a JSON-RPC
+        // error; this limit covers the routes using body extractors.
+        let router = Self::router().layer(DefaultBodyLimit::max(
+            self.config.request_limits().max_bytes,
+        ));
 
         // Spawn background task to clean up stale sessions periodically.
         // Uses 60-second interval and 5-minute idle threshold.
@@ -806,15 +850,53 @@ fn handle_ping() -> Value {
 }
 
 /// JSON-RPC request handler
-async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Response {
+///
+/// Every request runs inside an `mcp_request` span carrying a fresh request
+/// ID, so logs from parsing, PDG building and search can be correlated.
+///
+/// The handler reads the raw body itself so an oversized payload gets a
+/// JSON-RPC error rather than axum's plain-text 413.
+async fn json_rpc_handler(headers: HeaderMap, body: Body) -> Response {
+    let request_id = generate_request_id();
+    let span = request_span(&request_id);
+    json_rpc_dispatch(headers, body, &request_id)
+        .instrument(span)
+        .await
+}
+
+async fn json_rpc_dispatch(headers: HeaderMap, body: Body, request_id: &str) -> Response {
     // Extract Mcp-Session-Id header (if present)
     let incoming_session_id = headers
         .get("Mcp-Session-Id")
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
 
+    // Enforce size/depth limits before building any JSON value
+    let limits = SERVER_INSTANCE
+        .get()
+        .map(|s| s.config.request_limits())
+        .unwrap_or_default();
+    // Stops reading one byte past the limit, so the payload is never buffered
+    let body = match axum::body::to_bytes(body, limits.max_bytes).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Rejected JSON-RPC request body: {}", e);
+            let error = JsonRpcError::invalid_request(format!(
+                "Request too large or unreadable (max: {} bytes)",
+                limits.max_bytes
+            ));
+            let resp = JsonRpcResponse::error(Value::Null, error).with_request_id(request_id);
+            return Json(serde_json::to_value(&resp).unwrap()).into_response();
+        }
+    };
+    if let Err(e) = limits.check(&body) {
+        warn!("Rejected JSON-RPC request: {}", e);
+        let resp = JsonRpcResponse::error(Value::Null, e).with_request_id(request_id);
+        return Json(serde_json::to_value(&resp).unwrap()).into_response();
+    }
+
     // Parse JSON-RPC request
-    let json_req: JsonRpcRequest = match serde_json::from_value(body.clone()) {
+    let json_req: JsonRpcRequest = match serde_json::from_slice(&body) {
         Ok(r) => r,
         Err(e) => {
             warn!("Failed to parse JSON-RPC request: {}", e);
@@ -823,7 +905,8 @@ async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Respon
                 "id": null,
                 "error": {
                     "code": -32700,
-                    "message": "Invalid JSON"
+                    "message": "Invalid JSON",
+                    "data": { "request_id": request_id }
                 }
             }))
             .into_response();
@@ -839,7 +922,8 @@ async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Respon
                 "id": json_req.id,
                 "error": {
                     "code": -32603,
-                    "message": "Server instance not initialized"
+                    "message": "Server instance not initialized",
+                    "data": { "request_id": request_id }
                 }
             }))
             .into_response();
@@ -857,19 +941,21 @@ async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Respon
                 "id": json_req.id,
                 "error": {
                     "code": -32603,
-                    "message": "Handlers not initialized"
+                    "message": "Handlers not initialized",
+                    "data": { "request_id": request_id }
                 }
             }))
             .into_response();
         }
     };
 
+    tracing::Span::current().record("method", json_req.method.as_str());
     debug!("Received JSON-RPC request: method={}", json_req.method);
     let id = json_req.id.clone().unwrap_or(serde_json::Value::Null);
 
     if let Err(e) = json_req.validate() {
         warn!("Invalid JSON-RPC request: {}", e);
-        let resp = JsonRpcResponse::error(id, e);
+        let resp = JsonRpcResponse::error(id, e).with_request_id(request_id);
         return Json(serde_json::to_value(&resp).unwrap()).into_response();
     }
 
@@ -909,7 +995,8 @@ async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Respon
                 "id": json_req.id,
                 "error": {
                     "code": -32000,
-                    "message": "Server not initialized. Call 'initialize' first."
+                    "message": "Server not initialized. Call 'initialize' first.",
+                    "data": { "request_id": request_id }
                 }
             }))
             .into_response();
@@ -989,7 +1076,7 @@ async fn json_rpc_handler(headers: HeaderMap, Json(body): Json<Value>) -> Respon
         }
         Err(e) => {
             warn!("Request failed: {}", e);
-            JsonRpcResponse::error(id, e)
+            JsonRpcResponse::error(id, e).with_request_id(request_id)
         }
     };
 
@@ -1280,6 +1367,7 @@ impl McpServer {
             );
 
             let session_id_clone = session_id.clone();
+            let limits = self.config.request_limits();
             let session_handshakes = self.session_handshakes.clone();
             let handshake_complete = self.handshake_complete.clone();
 
@@ -1379,12 +1467,16 @@ impl McpServer {
                     };
 
                     // Parse and handle the JSON-RPC message
+                    let trace_id = generate_request_id();
                     let response_json = match handle_socket_message(
                         &json_payload,
+                        &limits,
+                        &trace_id,
                         &session_id_clone,
                         &session_handshakes,
                         &handshake_complete,
                     )
+                    .instrument(request_span(&trace_id))
                     .await
                     {
                         Some(json) => json,
@@ -1430,6 +1522,8 @@ impl McpServer {
 #[cfg(unix)]
 async fn handle_socket_message(
     json_payload: &str,
+    limits: &RequestLimits,
+    trace_id: &str,
     session_id: &str,
     session_handshakes: &Arc<DashMap<Arc<str>, (bool, Instant)>>,
     handshake_complete: &Arc<AtomicBool>,
@@ -1437,10 +1531,11 @@ async fn handle_socket_message(
     use super::protocol::{JsonRpcMessage, JsonRpcResponse};
     use crate::cli::mcp::server::{handle_tool_call, list_tools_json, HANDLERS, SERVER_STATE};
 
-    let message = match JsonRpcMessage::from_json(json_payload) {
+    let message = match JsonRpcMessage::from_json_with_limits(json_payload, limits) {
         Ok(m) => m,
         Err(e) => {
-            let error_response = JsonRpcResponse::error(serde_json::Value::Null, e);
+            let error_response =
+                JsonRpcResponse::error(serde_json::Value::Null, e).with_request_id(trace_id);
             return serde_json::to_string(&error_response).ok();
         }
     };
@@ -1453,6 +1548,7 @@ async fn handle_socket_message(
         JsonRpcMessage::Request(request) => {
             let request_id = request.id.clone().unwrap_or(serde_json::Value::Null);
             let method_name = request.method.clone();
+            tracing::Span::current().record("method", method_name.as_str());
 
             // Notifications with null id must not receive a response
             if request.id.is_none() {
@@ -1467,7 +1563,7 @@ async fn handle_socket_message(
                         request_id,
                         super::protocol::JsonRpcError::new(-32603, "Server state not initialized"),
                     );
-                    return serde_json::to_string(&resp).ok();
+                    return serde_json::to_string(&resp.with_request_id(trace_id)).ok();
                 }
             };
 
@@ -1478,7 +1574,7 @@ async fn handle_socket_message(
                         request_id,
                         super::protocol::JsonRpcError::new(-32603, "Handlers not initialized"),
                     );
-                    return serde_json::to_string(&resp).ok();
+                    return serde_json::to_string(&resp.with_request_id(trace_id)).ok();
                 }
             };
 
@@ -1499,7 +1595,7 @@ async fn handle_socket_message(
                             "Server not initialized. Call 'initialize' first.",
                         ),
                     );
-                    return serde_json::to_string(&resp).ok();
+                    return serde_json::to_string(&resp.with_request_id(trace_id)).ok();
                 }
             }
 
@@ -1554,7 +1650,7 @@ async fn handle_socket_message(
                 ),
             };
 
-            serde_json::to_string(&response).ok()
+            serde_json::to_string(&response.with_request_id(trace_id)).ok()
         }
     }
 }
@@ -1589,6 +1685,152 @@ mod tests {
         assert!(config.bind_address.port() >= 10000);
     }
 
+    /// Post `body` to `/mcp` through the full router and return the JSON-RPC
+    /// error code of the response
+    async fn json_rpc_error_code(body: Vec<u8>) -> i64 {
+        use tower::ServiceExt;
+
+        let request = axum::http::Request::post("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = McpServer::router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value: Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(value["error"]["data"]["request_id"]
+            .as_str()
+            .is_some_and(|id| id.starts_with("req-")));
+        value["error"]["code"].as_i64().unwrap()
+    }
+
+    /// `MakeWriter` collecting formatted tracing output in memory.
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CaptureWriter {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn tool_call_request(name: &str, arguments: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(serde_json::json!(1)),
+            method: "tools/call".to_string(),
+            params: Some(serde_json::json!({ "name": name, "arguments": arguments })),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_id_threads_through_nested_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("shapes.py"),
+            "def area(width, height):\n    return width * height\n",
+        )
+        .unwrap();
+        let registry = crate::cli::mcp::helpers::test_registry_for(dir.path());
+        let handlers = all_tool_handlers();
+        let project = dir.path().display().to_string();
+
+        let writer = CaptureWriter::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(writer.clone())
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .finish();
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let index_id = generate_request_id();
+        let index_req = tool_call_request(
+            "leindex.index",
+            serde_json::json!({ "project_path": project, "force_reindex": true }),
+        );
+        handle_tool_call(&registry, &handlers, &index_req)
+            .instrument(request_span(&index_id))
+            .await
+            .expect("index");
+
+        let search_id = generate_request_id();
+        let search_req = tool_call_request(
+            "leindex.search",
+            serde_json::json!({ "project_path": project, "query": "area" }),
+        );
+        handle_tool_call(&registry, &handlers, &search_req)
+            .instrument(request_span(&search_id))
+            .await
+            .expect("search");
+
+        let logs = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+        let phase_line = |phase: &str| {
+            logs.lines()
+                .find(|line| line.contains(&format!("phase=\"{}\"", phase)))
+                .unwrap_or_else(|| panic!("no {} event in:\n{}", phase, logs))
+                .to_string()
+        };
+        // Parse and PDG build run on a blocking thread but stay in the span
+        assert!(phase_line("parse").contains(&index_id));
+        assert!(phase_line("pdg_build").contains(&index_id));
+        assert!(phase_line("search").contains(&search_id));
+    }
+
+    #[test]
+    fn test_error_response_carries_request_id() {
+        let resp = JsonRpcResponse::error(
+            serde_json::json!(7),
+            JsonRpcError::with_data(-32602, "bad", serde_json::json!("detail text")),
+        )
+        .with_request_id("req-1-1");
+        let value = serde_json::to_value(&resp).unwrap();
+        assert_eq!(value["error"]["data"]["request_id"], "req-1-1");
+        assert_eq!(value["error"]["data"]["detail"], "detail text");
+    }
+
+    #[tokio::test]
+    async fn test_http_handler_rejects_oversized_request() {
+        let limits = McpServerConfig::default().request_limits();
+        let mut body = br#"{"jsonrpc":"2.0","id":1,"method":"ping","params":{"pad":""#.to_vec();
+        body.resize(limits.max_bytes + 1, b'x');
+        body.extend_from_slice(br#""}}"#);
+
+        assert_eq!(
+            json_rpc_error_code(body).await,
+            i64::from(super::super::protocol::error_codes::INVALID_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_handler_rejects_deeply_nested_request() {
+        let depth = McpServerConfig::default().max_json_depth + 1;
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{}{}}}"#,
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+
+        assert_eq!(
+            json_rpc_error_code(body.into_bytes()).await,
+            i64::from(super::super::protocol::error_codes::PARSE_ERROR)
+        );
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_socket_cleanup_guard_removes_file() {
@@ -0,0 +1,152 @@
+use super::helpers::{
+    apply_changes_in_memory, extract_string, parse_edit_changes, validate_file_within_project,
+    wrap_with_meta,
+};
+use super::protocol::JsonRpcError;
+use crate::cli::registry::ProjectRegistry;
+use crate::edit::ResolvedEditChange;
+use crate::validation::validation_to_json;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Handler for LeIndex [Validate Edit]
+///
+/// Checks proposed changes against the dependency graph without applying them.
+#[derive(Clone)]
+pub struct ValidateEditHandler;
+
+#[allow(missing_docs)]
+impl ValidateEditHandler {
+    pub fn name(&self) -> &str {
+        "leindex.validate-edit"
+    }
+
+    pub fn title(&self) -> &str {
+        "LeIndex [Validate Edit]"
+    }
+
+    pub fn description(&self) -> &str {
+        "Validate proposed changes to a file against the project's dependency graph: syntax \
+errors, broken or undefined references, semantic drift of existing symbols, and risk level. \
+Nothing is written or cached."
+    }
+
+    pub fn argument_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Absolute path to the file the changes apply to"
+                },
+                "project_path": {
+                    "type": "string",
+                    "description": "Project directory (auto-indexes on first use; omit to use current project)"
+                },
+                "changes": {
+                    "type": "array",
+                    "description": "List of changes to validate. Each has 'type' (replace_text/rename_symbol) and type-specific fields, as in LeIndex [Edit Preview].",
+                    "items": { "type": "object" }
+                }
+            },
+            "required": ["file_path", "changes"]
+        })
+    }
+
+    pub async fn execute(
+        &self,
+        registry: &Arc<ProjectRegistry>,
+        args: Value,
+    ) -> Result<Value, JsonRpcError> {
+        let file_path = extract_string(&args, "file_path")?;
+        let changes_val = args
+            .get("changes")
+            .cloned()
+            .ok_or_else(|| JsonRpcError::invalid_params("Missing required argument 'changes'"))?;
+
+        let project_path_arg = args.get("project_path").and_then(|v| v.as_str());
+        let handle = registry.get_or_create(project_path_arg).await?;
+
+        let abs_file_path = {
+            let mut guard = handle.write().await;
+            guard
+                .ensure_pdg_loaded()
+                .map_err(|e| JsonRpcError::indexing_failed(format!("Failed to load PDG: {}", e)))?;
+            validate_file_within_project(&file_path, guard.project_path())?
+        };
+
+        let original = tokio::fs::read_to_string(&abs_file_path)
+            .await
+            .map_err(|e| {
+                JsonRpcError::invalid_params(format!("Cannot read file '{}': {}", file_path, e))
+            })?;
+        let changes = parse_edit_changes(&changes_val, Some(&original))?;
+        let modified = apply_changes_in_memory(&original, &changes)?;
+
+        let guard = handle.read().await;
+        let validator = guard.create_validator().ok_or_else(|| {
+            JsonRpcError::project_not_indexed(guard.project_path().display().to_string())
+        })?;
+        let resolved = ResolvedEditChange::new(abs_file_path.clone(), original, modified);
+        let result = validator
+            .validate_changes(&[resolved])
+            .map_err(|e| JsonRpcError::internal_error(format!("Validation failed: {}", e)))?;
+
+        let mut response = validation_to_json(&result);
+        if let Some(obj) = response.as_object_mut() {
+            let risk_level = result
+                .impact_report
+                .as_ref()
+                .map(|r| format!("{:?}", r.risk_level).to_lowercase());
+            obj.insert("risk_level".to_string(), serde_json::json!(risk_level));
+            obj.insert(
+                "file".to_string(),
+                Value::String(abs_file_path.display().to_string()),
+            );
+            obj.insert("change_count".to_string(), serde_json::json!(changes.len()));
+        }
+        Ok(wrap_with_meta(response, &guard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::mcp::helpers::test_registry_for;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_validate_edit_reports_broken_import() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("calc.py");
+        std::fs::write(&file, "def total():\n    return 1\n").unwrap();
+        let registry = test_registry_for(dir.path());
+
+        let args = serde_json::json!({
+            "file_path": file.canonicalize().unwrap().to_string_lossy(),
+            "changes": [{
+                "type": "replace_text",
+                "old_text": "    return 1\n",
+                "new_text": "    from qqq_vanished import zzz\n    return zzz\n"
+            }]
+        });
+        let val = ValidateEditHandler.execute(&registry, args).await.unwrap();
+
+        assert_eq!(val["is_valid"], false);
+        let issues = val["reference_issues"].as_array().unwrap();
+        assert!(
+            issues.iter().any(|i| i["description"]
+                .as_str()
+                .is_some_and(|d| d.contains("qqq_vanished"))),
+            "expected a broken-import issue, got: {:?}",
+            issues
+        );
+        assert!(val["risk_level"].is_string());
+        assert_eq!(val["change_count"], 1);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "def total():\n    return 1\n",
+            "validation must not modify the file"
+        );
+    }
+}
@@ -144,19 +144,17 @@ context (symbols, types) immediately so the model knows how the new file fits in
 
         let signatures = if language != "unknown" {
             let abs_path_for_spawn = abs_path.clone();
-            // We just parse this one file for immediate surfacing.
+            let source = content.as_bytes().to_vec();
+            // Parse the content we just wrote for immediate surfacing,
+            // without re-reading it from disk.
             // Wrap in spawn_blocking to avoid blocking the async executor.
-            let results = tokio::task::spawn_blocking(move || {
-                GLOBAL_PARSER.parse_files(vec![abs_path_for_spawn])
+            let result = tokio::task::spawn_blocking(move || {
+                GLOBAL_PARSER.parse_source(abs_path_for_spawn, None, &source)
             })
             .await
             .map_err(|e| JsonRpcError::internal_error(format!("Parser task panicked: {}", e)))?;
 
-            if let Some(res) = results.first() {
-                res.signatures.clone()
-            } else {
-                vec![]
-            }
+            result.signatures
         } else {
             vec![]
         };
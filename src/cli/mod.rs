@@ -83,6 +83,8 @@ pub mod cleanup;
 pub mod cli;
 /// Configuration for projects, languages, and storage.
 pub mod config;
+/// Environment and index health checks (`leindex doctor`).
+pub mod doctor;
 /// Error types and error handling logic.
 pub mod errors;
 /// Indexing pipeline: project parsing, PDG building, search indexing.
@@ -104,6 +106,8 @@ pub mod neural_config;
 /// Multi-project registry with per-project concurrency.
 #[cfg(feature = "mcp-server")]
 pub mod registry;
+/// Concurrency-safe `LeIndex` wrapper splitting reads from index writes.
+pub mod shared_index;
 /// Shared directory exclusion constants.
 pub mod skip_dirs;
 /// File watcher for auto-reindex.
@@ -119,6 +123,7 @@ pub use config::{LanguageConfig, ProjectConfig, StorageConfig, TokenConfig};
 pub use errors::{ErrorContext, LeIndexError, RecoveryStrategy, Result as LeIndexResult};
 pub use leindex::{AnalysisResult as LeIndexAnalysisResult, Diagnostics, IndexStats, LeIndex};
 pub use memory::{MemoryConfig as MemoryManagementConfig, MemoryManager};
+pub use shared_index::SharedLeIndex;
 
 #[cfg(feature = "mcp-server")]
 pub use mcp::{
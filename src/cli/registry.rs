@@ -28,6 +28,12 @@
 //!     slot lock so only one rebuild runs at a time.
 //!   - Waiters re-check index status after acquiring the slot and return cached
 //!     stats when possible.
+//!
+//! * **Warm session cache**
+//!   - A project is loaded from storage once, on the first tool call that
+//!     names it, and stays resident across requests until evicted.
+//!   - `reload_project` re-reads storage in place when files changed outside
+//!     the server; `storage_load_count` exposes how often storage was read.
 
 use crate::cli::errors::detect_corruption;
 use crate::cli::leindex::{IndexStats, LeIndex};
@@ -36,6 +42,7 @@ use crate::cli::watcher::IndexWatcher;
 use dirs;
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, warn};
@@ -224,6 +231,9 @@ pub struct ProjectRegistry {
     /// of freshness checks that arrive at startup, short enough that a
     /// file edit becomes visible to subsequent reads within reasonable time.
     stale_cache: RwLock<HashMap<PathBuf, (std::time::Instant, bool)>>,
+
+    /// Number of times a project was loaded from storage.
+    storage_loads: AtomicUsize,
 }
 
 impl ProjectRegistry {
@@ -237,6 +247,7 @@ impl ProjectRegistry {
             max_projects,
             watchers: Mutex::new(HashMap::new()),
             stale_cache: RwLock::new(HashMap::new()),
+            storage_loads: AtomicUsize::new(0),
         }
     }
 
@@ -271,6 +282,7 @@ impl ProjectRegistry {
             max_projects,
             watchers: Mutex::new(watchers),
             stale_cache: RwLock::new(HashMap::new()),
+            storage_loads: AtomicUsize::new(0),
         }
     }
 
@@ -389,6 +401,47 @@ impl ProjectRegistry {
         self.index_handle(&handle, force_reindex).await
     }
 
+    /// Re-read a project's index from storage, replacing the warm copy.
+    ///
+    /// Loaded projects are kept resident across tool calls, so changes made
+    /// on disk by another process (e.g. a CLI `leindex index` run) are not
+    /// seen until the project is reloaded. The reload happens in place: the
+    /// existing handle, and any watcher holding it, sees the fresh state.
+    /// A project that is not loaded yet is simply loaded.
+    pub async fn reload_project(
+        &self,
+        project_path: Option<&str>,
+    ) -> Result<IndexStats, JsonRpcError> {
+        let canonical = self.resolve_path(project_path).await?;
+
+        let existing = self.projects.read().await.get(&canonical).cloned();
+        let handle = match existing {
+            Some(handle) => {
+                let slot = self.index_slot_for(&canonical).await;
+                let _slot_guard = slot.lock().await;
+                let fresh = self.load_from_storage(&canonical)?;
+                {
+                    let mut idx = handle.write().await;
+                    *idx = fresh;
+                }
+                self.stale_cache.write().await.remove(&canonical);
+                self.touch_lru(&canonical).await;
+                self.set_default(&canonical).await;
+                info!("Reloaded project from storage: {}", canonical.display());
+                handle
+            }
+            None => self.create_and_insert(canonical).await?,
+        };
+
+        let idx = handle.read().await;
+        Ok(idx.get_stats().clone())
+    }
+
+    /// Number of times a project has been loaded from storage by this registry.
+    pub fn storage_load_count(&self) -> usize {
+        self.storage_loads.load(Ordering::Relaxed)
+    }
+
     /// Number of projects currently in memory.
     pub async fn len(&self) -> usize {
         self.projects.read().await.len()
@@ -500,19 +553,7 @@ impl ProjectRegistry {
             }
         }
 
-        let mut leindex = LeIndex::new(&canonical).map_err(|e| {
-            JsonRpcError::init_failed(&canonical.display().to_string(), &e.to_string())
-        })?;
-        // Load from storage to populate search_engine (is_indexed() depends on it).
-        // PDG remains in memory; ensure_pdg_loaded() is a no-op after this.
-        if let Err(e) = leindex.load_from_storage() {
-            warn!(
-                "Failed to load project from storage for {}: {}. \
-                 The project will be auto-indexed on first tool call.",
-                canonical.display(),
-                e
-            );
-        }
+        let mut leindex = self.load_from_storage(&canonical)?;
 
         // Corruption detection and auto-repair
         let corruption =
@@ -590,6 +631,28 @@ impl ProjectRegistry {
         Ok(handle)
     }
 
+    /// Create a `LeIndex` for `canonical` and populate it from storage.
+    ///
+    /// Every storage read made by the registry goes through here so that
+    /// `storage_load_count` reflects how often a session cache was (re)filled.
+    fn load_from_storage(&self, canonical: &Path) -> Result<LeIndex, JsonRpcError> {
+        let mut leindex = LeIndex::new(canonical).map_err(|e| {
+            JsonRpcError::init_failed(&canonical.display().to_string(), &e.to_string())
+        })?;
+        self.storage_loads.fetch_add(1, Ordering::Relaxed);
+        // Load from storage to populate search_engine (is_indexed() depends on it).
+        // PDG remains in memory; ensure_pdg_loaded() is a no-op after this.
+        if let Err(e) = leindex.load_from_storage() {
+            warn!(
+                "Failed to load project from storage for {}: {}. \
+                 The project will be auto-indexed on first tool call.",
+                canonical.display(),
+                e
+            );
+        }
+        Ok(leindex)
+    }
+
     /// Build a fresh index for the project behind `handle`, then swap it in.
     ///
     /// Uses a per-project slot lock so concurrent index requests coalesce.
@@ -628,13 +691,25 @@ impl ProjectRegistry {
         );
 
         let path_for_blocking = project_path.clone();
+        // Blocking threads inherit neither the caller's span nor its
+        // subscriber; carry both over so parse/PDG timing events keep the
+        // request ID.
+        let span = tracing::Span::current();
+        let dispatch = tracing::dispatcher::get_default(|d| d.clone());
         let temp = tokio::task::spawn_blocking(move || {
-            let mut temp = LeIndex::new(&path_for_blocking).map_err(|e| {
-                JsonRpcError::init_failed(&path_for_blocking.display().to_string(), &e.to_string())
-            })?;
-            temp.index_project(force_reindex)
-                .map_err(|e| JsonRpcError::indexing_failed(format!("Indexing failed: {}", e)))?;
-            Ok::<LeIndex, JsonRpcError>(temp)
+            tracing::dispatcher::with_default(&dispatch, || {
+                let _entered = span.enter();
+                let mut temp = LeIndex::new(&path_for_blocking).map_err(|e| {
+                    JsonRpcError::init_failed(
+                        &path_for_blocking.display().to_string(),
+                        &e.to_string(),
+                    )
+                })?;
+                temp.index_project(force_reindex).map_err(|e| {
+                    JsonRpcError::indexing_failed(format!("Indexing failed: {}", e))
+                })?;
+                Ok::<LeIndex, JsonRpcError>(temp)
+            })
         })
         .await
         .map_err(|e| JsonRpcError::internal_error(format!("Task join error: {}", e)))??;
@@ -1116,4 +1191,43 @@ mod tests {
             "stale cache entry must be removed on invalidate with canonical input"
         );
     }
+
+    #[tokio::test]
+    async fn test_repeated_tool_calls_read_storage_once_until_reload() {
+        use crate::cli::mcp::handlers::{DiagnosticsHandler, ReloadHandler, ToolHandler};
+
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("main.rs"), "fn main() {}\n").unwrap();
+        {
+            let mut leindex = LeIndex::new(tmp.path()).unwrap();
+            leindex.index_project(false).unwrap();
+            leindex.close().unwrap();
+        }
+
+        let registry = Arc::new(ProjectRegistry::new(5));
+        let args = serde_json::json!({ "project_path": tmp.path().to_string_lossy() });
+        let diagnostics = ToolHandler::Diagnostics(DiagnosticsHandler);
+
+        diagnostics.execute(&registry, args.clone()).await.unwrap();
+        diagnostics.execute(&registry, args.clone()).await.unwrap();
+        assert_eq!(
+            registry.storage_load_count(),
+            1,
+            "second tool call must reuse the warm index"
+        );
+
+        let handle = registry.get_or_load(None).await.unwrap();
+        ToolHandler::Reload(ReloadHandler)
+            .execute(&registry, args.clone())
+            .await
+            .unwrap();
+        assert_eq!(registry.storage_load_count(), 2);
+        assert!(handle.read().await.is_indexed());
+
+        let after = registry.get_or_load(None).await.unwrap();
+        assert!(
+            Arc::ptr_eq(&handle, &after),
+            "reload must refresh the existing handle in place"
+        );
+    }
 }
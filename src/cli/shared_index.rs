@@ -0,0 +1,227 @@
+// Concurrency-safe sharing of one LeIndex between request handlers
+//
+// `LeIndex::search`/`analyze` take `&mut self` only to fill the persistent
+// query caches, so sharing an instance behind a plain lock serialises every
+// read. `SharedLeIndex` routes reads through the `&self` uncached paths under
+// a shared read lock and keeps its own in-memory result caches behind
+// mutexes, reserving the exclusive write lock for indexing.
+
+use crate::cli::leindex::{AnalysisResult, IndexStats, LeIndex};
+use crate::search::ranking::QueryType;
+use crate::search::search::SearchResult;
+use anyhow::Result;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+
+/// Result-cache entries kept per query kind
+const SHARED_CACHE_CAPACITY: usize = 256;
+
+/// A [`LeIndex`] shared by concurrent readers and an occasional writer
+///
+/// # Thread-safety contract
+///
+/// - [`search`](Self::search), [`analyze`](Self::analyze) and
+///   [`read`](Self::read) take a shared read lock: any number run in
+///   parallel and never block each other.
+/// - [`write`](Self::write) and [`index_project`](Self::index_project) take
+///   the exclusive write lock and wait for in-flight reads to finish. Whether
+///   later reads queue behind a waiting writer is up to the platform's
+///   [`RwLock`]. Nothing that runs under the read lock waits on the write
+///   lock, so reads and writes cannot deadlock each other.
+/// - Search and analysis results are cached in memory behind their own
+///   mutexes, held only for lookups and inserts. Both caches are cleared
+///   under the write lock, and entries are only inserted under the read
+///   lock, so a cached result never outlives the index state it came from.
+/// - A panic while holding a lock does not poison the wrapper; the next
+///   caller proceeds with whatever state the panicking call left behind.
+///
+/// Unlike [`LeIndex::search`], reads here do not touch the on-disk query
+/// cache.
+pub struct SharedLeIndex {
+    index: RwLock<LeIndex>,
+    search_cache: Mutex<LruCache<String, Vec<SearchResult>>>,
+    analysis_cache: Mutex<LruCache<String, AnalysisResult>>,
+}
+
+impl SharedLeIndex {
+    /// Wrap an index for shared use
+    pub fn new(index: LeIndex) -> Self {
+        let capacity = NonZeroUsize::new(SHARED_CACHE_CAPACITY).expect("non-zero capacity");
+        Self {
+            index: RwLock::new(index),
+            search_cache: Mutex::new(LruCache::new(capacity)),
+            analysis_cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Take a shared read lock on the underlying index
+    pub fn read(&self) -> RwLockReadGuard<'_, LeIndex> {
+        self.index.read().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Run `f` with exclusive access, then invalidate cached results
+    pub fn write<R>(&self, f: impl FnOnce(&mut LeIndex) -> R) -> R {
+        let mut index = self.index.write().unwrap_or_else(|p| p.into_inner());
+        let result = f(&mut index);
+        self.search_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clear();
+        self.analysis_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clear();
+        result
+    }
+
+    /// Index the project under the write lock
+    pub fn index_project(&self, force: bool) -> Result<IndexStats> {
+        self.write(|index| index.index_project(force))
+    }
+
+    /// Search the index; runs concurrently with other reads
+    pub fn search(
+        &self,
+        query: &str,
+        top_k: usize,
+        query_type: Option<QueryType>,
+    ) -> Result<Vec<SearchResult>> {
+        let index = self.read();
+        let key = format!("{}\0{}\0{:?}", query, top_k, query_type);
+        if let Some(hit) = self
+            .search_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(&key)
+        {
+            return Ok(hit.clone());
+        }
+
+        let results = index.search_uncached(query, top_k, query_type)?;
+        self.search_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .put(key, results.clone());
+        Ok(results)
+    }
+
+    /// Analyze a query with context expansion; runs concurrently with other reads
+    pub fn analyze(&self, query: &str, token_budget: usize) -> Result<AnalysisResult> {
+        let index = self.read();
+        let key = format!("{}\0{}", query, token_budget);
+        if let Some(hit) = self
+            .analysis_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(&key)
+        {
+            return Ok(hit.clone());
+        }
+
+        let analysis = index.analyze_uncached(query, token_budget)?;
+        self.analysis_cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .put(key, analysis.clone());
+        Ok(analysis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Barrier};
+    use std::time::Duration;
+
+    fn indexed_project(dir: &std::path::Path) -> SharedLeIndex {
+        std::fs::write(
+            dir.join("auth.py"),
+            "def authenticate_user(name, password):\n    return check_password(name, password)\n\n\
+             def check_password(name, password):\n    return len(password) > 8\n",
+        )
+        .unwrap();
+        let mut index = LeIndex::new(dir).unwrap();
+        index.index_project(false).unwrap();
+        SharedLeIndex::new(index)
+    }
+
+    #[test]
+    fn test_reads_do_not_block_each_other() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = Arc::new(indexed_project(dir.path()));
+
+        // Hold a read lock while another thread searches
+        let guard = shared.read();
+        let (tx, rx) = mpsc::channel();
+        let reader = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            let _ = tx.send(reader.search("authenticate_user", 5, None));
+        });
+        let results = rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("search blocked behind another reader")
+            .unwrap();
+        drop(guard);
+        assert!(results
+            .iter()
+            .any(|r| r.symbol_name.contains("authenticate_user")));
+    }
+
+    #[test]
+    fn test_concurrent_searches_with_pending_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = Arc::new(indexed_project(dir.path()));
+        let start = Arc::new(Barrier::new(5));
+        let (tx, rx) = mpsc::channel();
+
+        let writer = {
+            let shared = Arc::clone(&shared);
+            let start = Arc::clone(&start);
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                start.wait();
+                let stats = shared.write(|index| {
+                    std::thread::sleep(Duration::from_millis(50));
+                    index.index_project(true)
+                });
+                let _ = tx.send(("write", stats.is_ok()));
+            })
+        };
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                let start = Arc::clone(&start);
+                let tx = tx.clone();
+                std::thread::spawn(move || {
+                    start.wait();
+                    for _ in 0..3 {
+                        let found = shared.search("check_password", 5, None).is_ok_and(|r| {
+                            r.iter().any(|r| r.symbol_name.contains("check_password"))
+                        });
+                        let _ = tx.send(("search", found));
+                    }
+                })
+            })
+            .collect();
+        drop(tx);
+
+        let mut outcomes = Vec::new();
+        while outcomes.len() < 13 {
+            let outcome = rx
+                .recv_timeout(Duration::from_secs(60))
+                .expect("deadlock: reader or writer never finished");
+            outcomes.push(outcome);
+        }
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        assert!(outcomes.iter().all(|(_, ok)| *ok), "{:?}", outcomes);
+        assert_eq!(outcomes.iter().filter(|(op, _)| *op == "write").count(), 1);
+        // The write invalidated cached results; a fresh search still works
+        assert!(!shared.search("check_password", 5, None).unwrap().is_empty());
+    }
+}
@@ -63,6 +63,8 @@ mod tests {
                 wal_enabled: false,
                 cache_size_kib: None,
                 mmap_size: None,
+                busy_timeout_ms: crate::storage::schema::DEFAULT_BUSY_TIMEOUT_MS,
+                encryption_key: None,
             },
         )
         .unwrap()
@@ -5,6 +5,7 @@
 // from multiple projects.
 
 use crate::graph::pdg::{EdgeId, NodeId, NodeType, ProgramDependenceGraph};
+use petgraph::visit::EdgeRef;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
@@ -60,18 +61,63 @@ impl CrossProjectPDG {
     /// Merge external PDG into this one
     ///
     /// Adds all nodes and edges from the external PDG to the merged graph,
-    /// tracking their origin project.
+    /// tracking their origin project. Nodes whose symbol ID is already in the
+    /// merged graph are not duplicated: their edges attach to the existing
+    /// node. On error the graph is left unchanged.
     pub fn merge_external_pdg(
         &mut self,
         project_id: &str,
         external_pdg: &ProgramDependenceGraph,
     ) -> Result<(), MergeError> {
-        // Track current depth
-        let current_depth = self.external_refs.len();
-        if current_depth >= self.max_depth {
+        self.merge_all(&[(project_id, external_pdg)])
+    }
+
+    /// Merge several external PDGs as a single transaction
+    ///
+    /// Every project is checked before anything is written. If any project
+    /// would exceed the depth limit, the error is returned and the merged
+    /// graph is not modified. Symbols shared between projects are merged
+    /// into one node, owned by the first project that brought them in.
+    pub fn merge_all(
+        &mut self,
+        projects: &[(&str, &ProgramDependenceGraph)],
+    ) -> Result<(), MergeError> {
+        for staged in 0..projects.len() {
+            self.check_merge(staged)?;
+        }
+
+        for (project_id, external_pdg) in projects {
+            self.apply_merge(project_id, external_pdg);
+        }
+        Ok(())
+    }
+
+    /// Merge several external PDGs, skipping the ones that fail
+    ///
+    /// Each project is merged atomically on its own; failures are collected in
+    /// the returned report instead of aborting the remaining projects.
+    pub fn merge_partial(&mut self, projects: &[(&str, &ProgramDependenceGraph)]) -> MergeReport {
+        let mut report = MergeReport::default();
+        for (project_id, external_pdg) in projects {
+            match self.merge_external_pdg(project_id, external_pdg) {
+                Ok(()) => report.merged.push(project_id.to_string()),
+                Err(err) => report.failed.push((project_id.to_string(), err)),
+            }
+        }
+        report
+    }
+
+    /// Validate that a project can be merged once `staged` earlier projects
+    /// have been applied
+    fn check_merge(&self, staged: usize) -> Result<(), MergeError> {
+        if self.external_refs.len() + staged >= self.max_depth {
             return Err(MergeError::MaxDepthExceeded(self.max_depth));
         }
+        Ok(())
+    }
 
+    /// Copy nodes and edges of an already validated external PDG
+    fn apply_merge(&mut self, project_id: &str, external_pdg: &ProgramDependenceGraph) {
         // Track mapping from old node IDs to new node IDs
         let mut node_id_map: HashMap<NodeId, NodeId> = HashMap::new();
 
@@ -81,6 +127,12 @@ impl CrossProjectPDG {
         // Add all nodes from external PDG
         for old_node_id in external_pdg.node_indices() {
             if let Some(node) = external_pdg.get_node(old_node_id) {
+                // Reuse the node of a symbol that is already merged
+                if let Some(existing) = self.merged_pdg.find_by_id(&node.id) {
+                    node_id_map.insert(old_node_id, existing);
+                    continue;
+                }
+
                 // Add node to merged PDG, which returns the new NodeId
                 let new_node_id = self.merged_pdg.add_node(node.clone());
 
@@ -109,6 +161,16 @@ impl CrossProjectPDG {
                         None => old_target,
                     };
 
+                    // Skip edges already present between deduplicated nodes
+                    let duplicate = self
+                        .merged_pdg
+                        .graph
+                        .edges_connecting(new_source, new_target)
+                        .any(|existing| existing.weight().edge_type == edge.edge_type);
+                    if duplicate {
+                        continue;
+                    }
+
                     // Add the edge with remapped endpoints
                     self.merged_pdg
                         .add_edge(new_source, new_target, edge.clone());
@@ -119,8 +181,6 @@ impl CrossProjectPDG {
         // Record external reference
         self.external_refs
             .insert(project_id.to_string(), added_nodes);
-
-        Ok(())
     }
 
     /// Add external node reference (lazy load)
@@ -285,6 +345,22 @@ impl CrossProjectPDG {
     }
 }
 
+/// Outcome of a best-effort [`CrossProjectPDG::merge_partial`]
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// Projects merged successfully, in input order
+    pub merged: Vec<String>,
+    /// Projects that failed to merge, with the reason
+    pub failed: Vec<(String, MergeError)>,
+}
+
+impl MergeReport {
+    /// True when every project merged
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
 /// Error for PDG merging
 #[derive(Debug, Error)]
 pub enum MergeError {
@@ -433,6 +509,7 @@ mod tests {
                 call_count: Some(1),
                 variable_name: None,
                 confidence: None,
+                call_target: None,
             },
         };
         root_pdg.add_edge(id_a, id_b, edge);
@@ -452,6 +529,7 @@ mod tests {
             metadata: crate::graph::pdg::EdgeMetadata {
                 call_count: None,
                 confidence: None,
+                call_target: None,
                 variable_name: Some("data".to_string()),
             },
         };
@@ -467,6 +545,78 @@ mod tests {
         assert_eq!(cross_pdg.edge_count(), 2); // Both local and external edges
     }
 
+    #[test]
+    fn test_merge_deduplicates_shared_symbols() {
+        let root_pdg = create_test_pdg(vec!["root_func"]);
+        let mut cross_pdg = CrossProjectPDG::new("root_project".to_string(), root_pdg);
+
+        // Shares root_func with the root project and calls into it
+        let mut ext_pdg = create_test_pdg(vec!["ext_func", "root_func"]);
+        let call = crate::graph::pdg::Edge {
+            edge_type: crate::graph::pdg::EdgeType::Call,
+            metadata: crate::graph::pdg::EdgeMetadata {
+                call_count: Some(1),
+                variable_name: None,
+                confidence: None,
+                call_target: None,
+            },
+        };
+        let caller = ext_pdg.find_by_id("ext_func").unwrap();
+        let callee = ext_pdg.find_by_id("root_func").unwrap();
+        ext_pdg.add_edge(caller, callee, call);
+
+        cross_pdg.merge_external_pdg("a", &ext_pdg).unwrap();
+        // Merging the same project again adds no nodes or edges
+        cross_pdg.merge_external_pdg("b", &ext_pdg).unwrap();
+
+        assert_eq!(cross_pdg.node_count(), 2);
+        assert_eq!(cross_pdg.edge_count(), 1);
+        let root_func = cross_pdg.pdg().find_by_id("root_func").unwrap();
+        assert!(!cross_pdg.is_external_node(&root_func));
+        let ext_func = cross_pdg.pdg().find_by_id("ext_func").unwrap();
+        assert_eq!(cross_pdg.get_node_origin(&ext_func), Some(&"a".to_string()));
+        assert_eq!(cross_pdg.pdg().neighbors(ext_func), vec![root_func]);
+    }
+
+    #[test]
+    fn test_merge_all_is_transactional() {
+        let root_pdg = create_test_pdg(vec!["root_func"]);
+        let mut cross_pdg =
+            CrossProjectPDG::with_max_depth("root_project".to_string(), root_pdg, 2);
+
+        let ext_a = create_test_pdg(vec!["ext_a"]);
+        let ext_b = create_test_pdg(vec!["ext_b"]);
+        // Exceeds the depth limit of two external projects
+        let ext_c = create_test_pdg(vec!["ext_c"]);
+
+        let result = cross_pdg.merge_all(&[("a", &ext_a), ("b", &ext_b), ("c", &ext_c)]);
+        assert!(matches!(result, Err(MergeError::MaxDepthExceeded(2))));
+
+        // Nothing from "a" or "b" was committed
+        assert_eq!(cross_pdg.node_count(), 1);
+        assert_eq!(cross_pdg.edge_count(), 0);
+        assert!(cross_pdg.get_referenced_projects().is_empty());
+        assert!(cross_pdg.node_origins.is_empty());
+    }
+
+    #[test]
+    fn test_merge_partial_reports_failures() {
+        let root_pdg = create_test_pdg(vec!["root_func"]);
+        let mut cross_pdg =
+            CrossProjectPDG::with_max_depth("root_project".to_string(), root_pdg, 2);
+
+        let ext_a = create_test_pdg(vec!["ext_a"]);
+        let ext_b = create_test_pdg(vec!["ext_b"]);
+        let ext_c = create_test_pdg(vec!["ext_c"]);
+
+        let report = cross_pdg.merge_partial(&[("a", &ext_a), ("b", &ext_b), ("c", &ext_c)]);
+        assert!(!report.is_complete());
+        assert_eq!(report.merged, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "c");
+        assert_eq!(cross_pdg.node_count(), 3);
+    }
+
     #[test]
     fn test_serialization() {
         let root_pdg = create_test_pdg(vec!["root_func"]);
@@ -1,5 +1,6 @@
 // Node embedding generation and storage
 
+use crate::graph::pdg::{NodeId, NodeType, ProgramDependenceGraph};
 use serde::{Deserialize, Serialize};
 
 /// Node embedding (768-dimensional vector)
@@ -15,6 +16,13 @@ pub struct NodeEmbedding {
 
     /// Embedding version/model used
     pub model: String,
+
+    /// Whether `vector` has been L2-normalized
+    ///
+    /// Two normalized embeddings compare with a plain dot product; anything
+    /// else falls back to full cosine similarity.
+    #[serde(default)]
+    pub normalized: bool,
 }
 
 impl NodeEmbedding {
@@ -24,10 +32,36 @@ impl NodeEmbedding {
             vector,
             node_id,
             model: "CodeRankEmbed".to_string(),
+            normalized: false,
+        }
+    }
+
+    /// Scale the vector to unit length and mark the embedding as normalized
+    ///
+    /// A zero vector is left unchanged and stays unnormalized.
+    pub fn normalize(&mut self) {
+        if self.normalized {
+            return;
+        }
+        let norm: f32 = self.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return;
         }
+        for x in &mut self.vector {
+            *x /= norm;
+        }
+        self.normalized = true;
+    }
+
+    /// Return a normalized copy of this embedding
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
     }
 
     /// Calculate cosine similarity with another embedding
+    ///
+    /// Uses a dot product when both embeddings are normalized.
     pub fn similarity(&self, other: &NodeEmbedding) -> f32 {
         if self.vector.len() != other.vector.len() {
             return 0.0;
@@ -40,6 +74,10 @@ impl NodeEmbedding {
             .map(|(a, b)| a * b)
             .sum();
 
+        if self.normalized && other.normalized {
+            return dot_product;
+        }
+
         let norm_a: f32 = self.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
         let norm_b: f32 = other.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
 
@@ -64,6 +102,10 @@ pub struct EmbeddingCache {
 
     /// Maximum cache size
     max_size: usize,
+
+    /// Normalize embeddings on insert
+    #[serde(default)]
+    normalize: bool,
 }
 
 impl EmbeddingCache {
@@ -72,11 +114,26 @@ impl EmbeddingCache {
         Self {
             embeddings: Vec::with_capacity(max_size),
             max_size,
+            normalize: false,
+        }
+    }
+
+    /// Create a cache that stores L2-normalized embeddings
+    ///
+    /// Lookups against normalized entries use a dot product instead of
+    /// recomputing norms; queries should be normalized too to benefit.
+    pub fn normalized(max_size: usize) -> Self {
+        Self {
+            normalize: true,
+            ..Self::new(max_size)
         }
     }
 
     /// Insert an embedding
-    pub fn insert(&mut self, embedding: NodeEmbedding) {
+    pub fn insert(&mut self, mut embedding: NodeEmbedding) {
+        if self.normalize {
+            embedding.normalize();
+        }
         if self.embeddings.len() >= self.max_size {
             // Simple FIFO eviction - would use LRU in production
             self.embeddings.remove(0);
@@ -104,6 +161,198 @@ impl EmbeddingCache {
     }
 }
 
+/// Strategy for turning a PDG node into a [`NodeEmbedding`]
+///
+/// Implementations can look at the graph around the node, the node's source
+/// text, or both. Pass one to [`crate::graph::extraction::extract_pdg_with_embeddings`]
+/// (or [`embed_nodes`]) to choose how embeddings are produced.
+pub trait EmbeddingStrategy: Send + Sync {
+    /// Short name recorded as the embedding's `model`
+    fn name(&self) -> &str;
+
+    /// Embedding dimension produced by this strategy
+    fn dimension(&self) -> usize;
+
+    /// Embed one node
+    ///
+    /// `source` is the full source of the file the node belongs to; the
+    /// node's `byte_range` indexes into it.
+    fn embed(&self, pdg: &ProgramDependenceGraph, node: NodeId, source: &[u8]) -> Vec<f32>;
+}
+
+/// Structural embedding built from graph features (default strategy)
+///
+/// Features: node type one-hot, log-scaled complexity, in/out degree and
+/// body length. Nodes with the same shape get the same vector regardless of
+/// what their code says.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StructuralEmbedding;
+
+/// Number of features produced by [`StructuralEmbedding`]
+const STRUCTURAL_DIMENSION: usize = 10;
+
+impl EmbeddingStrategy for StructuralEmbedding {
+    fn name(&self) -> &str {
+        "structural"
+    }
+
+    fn dimension(&self) -> usize {
+        STRUCTURAL_DIMENSION
+    }
+
+    fn embed(&self, pdg: &ProgramDependenceGraph, node: NodeId, _source: &[u8]) -> Vec<f32> {
+        let mut vector = vec![0.0f32; STRUCTURAL_DIMENSION];
+        let Some(data) = pdg.get_node(node) else {
+            return vector;
+        };
+
+        let type_slot = match data.node_type {
+            NodeType::Function => 0,
+            NodeType::Method => 1,
+            NodeType::Class => 2,
+            NodeType::Module => 3,
+            NodeType::Variable => 4,
+            NodeType::External => 5,
+        };
+        vector[type_slot] = 1.0;
+        vector[6] = (1.0 + data.complexity as f32).ln();
+        vector[7] = (1.0 + pdg.predecessor_count(node) as f32).ln();
+        vector[8] = (1.0 + pdg.neighbors(node).len() as f32).ln();
+        let body_len = data.byte_range.1.saturating_sub(data.byte_range.0);
+        vector[9] = (1.0 + body_len as f32).ln();
+        vector
+    }
+}
+
+/// Content embedding built from the node's source text
+///
+/// Identifier tokens are hashed into a fixed number of buckets (feature
+/// hashing), so no vocabulary is needed.
+#[derive(Debug, Clone, Copy)]
+pub struct ContentEmbedding {
+    /// Number of hash buckets
+    pub dimension: usize,
+}
+
+impl Default for ContentEmbedding {
+    fn default() -> Self {
+        Self { dimension: 64 }
+    }
+}
+
+impl EmbeddingStrategy for ContentEmbedding {
+    fn name(&self) -> &str {
+        "content"
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn embed(&self, pdg: &ProgramDependenceGraph, node: NodeId, source: &[u8]) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimension];
+        let Some(data) = pdg.get_node(node) else {
+            return vector;
+        };
+        if self.dimension == 0 {
+            return vector;
+        }
+
+        let start = data.byte_range.0.min(source.len());
+        let end = data.byte_range.1.min(source.len());
+        let text = String::from_utf8_lossy(&source[start..end.max(start)]);
+        for token in text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|t| !t.is_empty())
+        {
+            let hash = blake3::hash(token.to_lowercase().as_bytes());
+            let bytes = hash.as_bytes();
+            let bucket = u64::from_le_bytes(bytes[..8].try_into().unwrap_or([0; 8]));
+            vector[(bucket % self.dimension as u64) as usize] += 1.0;
+        }
+        vector
+    }
+}
+
+/// Concatenation of two strategies' vectors
+#[derive(Debug, Clone)]
+pub struct CombinedEmbedding<A, B> {
+    /// First strategy; its features come first
+    pub first: A,
+    /// Second strategy
+    pub second: B,
+    name: String,
+}
+
+impl<A: EmbeddingStrategy, B: EmbeddingStrategy> CombinedEmbedding<A, B> {
+    /// Combine two strategies
+    pub fn new(first: A, second: B) -> Self {
+        let name = format!("{}+{}", first.name(), second.name());
+        Self {
+            first,
+            second,
+            name,
+        }
+    }
+}
+
+impl<A: EmbeddingStrategy, B: EmbeddingStrategy> EmbeddingStrategy for CombinedEmbedding<A, B> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dimension(&self) -> usize {
+        self.first.dimension() + self.second.dimension()
+    }
+
+    fn embed(&self, pdg: &ProgramDependenceGraph, node: NodeId, source: &[u8]) -> Vec<f32> {
+        let mut vector = self.first.embed(pdg, node, source);
+        vector.extend(self.second.embed(pdg, node, source));
+        vector
+    }
+}
+
+/// Embed every node of `file_path` with `strategy` and store the vectors in the PDG
+///
+/// Returns the number of nodes embedded.
+pub fn embed_nodes(
+    pdg: &mut ProgramDependenceGraph,
+    file_path: &str,
+    source: &[u8],
+    strategy: &dyn EmbeddingStrategy,
+) -> usize {
+    let embeddings: Vec<(String, Vec<f32>)> = pdg
+        .nodes_in_file(file_path)
+        .into_iter()
+        .filter_map(|node| {
+            let id = pdg.get_node(node)?.id.clone();
+            Some((id, strategy.embed(pdg, node, source)))
+        })
+        .collect();
+
+    let count = embeddings.len();
+    for (id, vector) in embeddings {
+        pdg.set_embedding(&id, vector);
+    }
+    count
+}
+
+/// Produce a [`NodeEmbedding`] for one node with `strategy`
+pub fn embed_node(
+    pdg: &ProgramDependenceGraph,
+    node: NodeId,
+    source: &[u8],
+    strategy: &dyn EmbeddingStrategy,
+) -> Option<NodeEmbedding> {
+    let id = pdg.get_node(node)?.id.clone();
+    Some(NodeEmbedding {
+        vector: strategy.embed(pdg, node, source),
+        node_id: id,
+        model: strategy.name().to_string(),
+        normalized: false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +381,41 @@ mod tests {
         assert!((emb1.similarity(&emb3) - 0.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_normalized_ranking_matches_cosine() {
+        let query = vec![0.3f32, 2.0, 0.5];
+        let candidates = [
+            ("a", vec![1.0f32, 0.2, 0.0]),
+            ("b", vec![0.1f32, 4.0, 1.5]),
+            ("c", vec![3.0f32, 3.0, 0.1]),
+        ];
+
+        let mut raw = EmbeddingCache::new(10);
+        let mut normalized = EmbeddingCache::normalized(10);
+        for (id, vector) in &candidates {
+            raw.insert(NodeEmbedding::new(vector.clone(), id.to_string()));
+            normalized.insert(NodeEmbedding::new(vector.clone(), id.to_string()));
+        }
+        assert!(normalized.get("a").unwrap().normalized);
+
+        let raw_query = NodeEmbedding::new(query.clone(), "q".to_string());
+        let norm_query = raw_query.clone().normalized();
+        let raw_ranking = raw.find_similar(&raw_query, 3);
+        let norm_ranking = normalized.find_similar(&norm_query, 3);
+
+        let ids = |ranking: &[(String, f32)]| -> Vec<String> {
+            ranking.iter().map(|(id, _)| id.clone()).collect()
+        };
+        assert_eq!(ids(&raw_ranking), ids(&norm_ranking));
+        for ((_, a), (_, b)) in raw_ranking.iter().zip(&norm_ranking) {
+            assert!((a - b).abs() < 1e-5);
+        }
+
+        // A raw query against normalized entries still gets cosine scores
+        let mixed = normalized.find_similar(&raw_query, 3);
+        assert_eq!(ids(&mixed), ids(&raw_ranking));
+    }
+
     #[test]
     fn test_cache_operations() {
         let mut cache = EmbeddingCache::new(10);
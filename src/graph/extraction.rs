@@ -9,6 +9,7 @@
 
 #![warn(missing_docs)]
 
+use crate::graph::embedding::{embed_nodes, EmbeddingStrategy};
 use crate::graph::pdg::{Node, NodeType, ProgramDependenceGraph};
 use crate::parse::prelude::{ImportInfo, SignatureInfo};
 use regex::Regex;
@@ -26,6 +27,26 @@ pub fn extract_pdg_from_signatures(
     source_code: &[u8],
     file_path: &str,
     language: &str,
+) -> ProgramDependenceGraph {
+    extract_pdg_with_data_flow_config(
+        signatures,
+        source_code,
+        file_path,
+        language,
+        &DataFlowConfig::default(),
+    )
+}
+
+/// Extract a PDG with custom data flow heuristics.
+///
+/// Same as [`extract_pdg_from_signatures`], but Phase 2 uses `data_flow`
+/// instead of [`DataFlowConfig::default`].
+pub fn extract_pdg_with_data_flow_config(
+    signatures: Vec<SignatureInfo>,
+    source_code: &[u8],
+    file_path: &str,
+    language: &str,
+    data_flow: &DataFlowConfig,
 ) -> ProgramDependenceGraph {
     let mut pdg = ProgramDependenceGraph::new();
     let mut node_ids: HashMap<String, crate::graph::pdg::NodeId> = HashMap::new();
@@ -49,7 +70,7 @@ pub fn extract_pdg_from_signatures(
     pdg.add_containment_edges(containment);
 
     // Phase 2: Type-based data flow edges (multi-signal, directional)
-    let data_edges = extract_data_flow_edges(&signatures, &node_ids);
+    let data_edges = extract_data_flow_edges_with_config(&signatures, &node_ids, data_flow);
     pdg.add_data_flow_edges(data_edges);
 
     // Phase 3: Inheritance edges (4-signal evidence model)
@@ -58,7 +79,7 @@ pub fn extract_pdg_from_signatures(
 
     // Phase 4: Explicit call edges from parser
     let call_edges = extract_call_edges(&signatures, &node_ids);
-    pdg.add_call_edges(call_edges);
+    pdg.add_call_edges_with_targets(call_edges);
 
     // Phase 5: Import edges with multi-line source fallback
     let import_edges = extract_import_edges(
@@ -74,6 +95,24 @@ pub fn extract_pdg_from_signatures(
     pdg
 }
 
+/// Extract a PDG and embed its nodes with the given strategy.
+///
+/// Same as [`extract_pdg_from_signatures`], followed by
+/// [`embed_nodes`](crate::graph::embedding::embed_nodes) over the file's nodes.
+/// Use [`StructuralEmbedding`](crate::graph::embedding::StructuralEmbedding)
+/// for the default behavior.
+pub fn extract_pdg_with_embeddings(
+    signatures: Vec<SignatureInfo>,
+    source_code: &[u8],
+    file_path: &str,
+    language: &str,
+    strategy: &dyn EmbeddingStrategy,
+) -> ProgramDependenceGraph {
+    let mut pdg = extract_pdg_from_signatures(signatures, source_code, file_path, language);
+    embed_nodes(&mut pdg, file_path, source_code, strategy);
+    pdg
+}
+
 // ---------------------------------------------------------------------------
 // Phase 1b: Class node inference + containment edges
 // ---------------------------------------------------------------------------
@@ -249,6 +288,59 @@ fn is_excluded_type(t: &str) -> bool {
     EXCLUDED_TYPES.contains(&base)
 }
 
+/// Default cap on how many signatures may share a type before it stops
+/// producing data flow edges.
+pub const DEFAULT_MAX_TYPE_FANOUT: usize = 50;
+
+/// Tuning for the data flow heuristics in [`extract_data_flow_edges_with_config`].
+#[derive(Debug, Clone)]
+pub struct DataFlowConfig {
+    /// Types never used as a data flow signal. Matching ignores generic
+    /// arguments, so `Vec<User>` is checked as `Vec`. Defaults to the
+    /// built-in list of primitive and container types.
+    pub ignored_types: HashSet<String>,
+
+    /// Skip a type entirely once more than this many signatures produce or
+    /// consume it. `None` disables the cap.
+    pub max_type_fanout: Option<usize>,
+
+    /// Only emit producer → consumer edges (Signal A), dropping the shared
+    /// return/parameter signals.
+    pub producer_consumer_only: bool,
+}
+
+impl Default for DataFlowConfig {
+    fn default() -> Self {
+        Self {
+            ignored_types: EXCLUDED_TYPES.iter().map(|t| t.to_string()).collect(),
+            max_type_fanout: Some(DEFAULT_MAX_TYPE_FANOUT),
+            producer_consumer_only: false,
+        }
+    }
+}
+
+impl DataFlowConfig {
+    /// Add types to the ignore-list
+    pub fn ignore_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ignored_types.extend(types.into_iter().map(Into::into));
+        self
+    }
+
+    fn is_ignored(&self, t: &str) -> bool {
+        // Strip generic brackets: "Vec<User>" → check "Vec" (excluded) and "User" (not excluded)
+        let base = t.split('<').next().unwrap_or(t).trim();
+        self.ignored_types.contains(base)
+    }
+
+    fn exceeds_fanout(&self, sharing: usize) -> bool {
+        self.max_type_fanout.is_some_and(|cap| sharing > cap)
+    }
+}
+
 /// Extracts data flow edges using a 3-signal directional model.
 ///
 /// This function implements a sophisticated data flow analysis that creates
@@ -268,7 +360,9 @@ fn is_excluded_type(t: &str) -> bool {
 ///   a lower-confidence edge is created.
 ///
 /// The function filters out ubiquitous types (String, i32, bool, etc.) to avoid
-/// creating meaningless O(n²) cliques that would dominate the graph.
+/// creating meaningless O(n²) cliques that would dominate the graph, and skips
+/// types shared by more than [`DEFAULT_MAX_TYPE_FANOUT`] signatures. Use
+/// [`extract_data_flow_edges_with_config`] to change either.
 ///
 /// # Arguments
 ///
@@ -287,6 +381,23 @@ pub fn extract_data_flow_edges(
     crate::graph::pdg::NodeId,
     String,
     f32,
+)> {
+    extract_data_flow_edges_with_config(signatures, node_ids, &DataFlowConfig::default())
+}
+
+/// Extracts data flow edges using the given ignore-list, fan-out cap and
+/// signal selection.
+///
+/// See [`extract_data_flow_edges`] for the signals and the returned tuples.
+pub fn extract_data_flow_edges_with_config(
+    signatures: &[SignatureInfo],
+    node_ids: &HashMap<String, crate::graph::pdg::NodeId>,
+    config: &DataFlowConfig,
+) -> Vec<(
+    crate::graph::pdg::NodeId,
+    crate::graph::pdg::NodeId,
+    String,
+    f32,
 )> {
     let mut edges = Vec::new();
     let mut seen: HashSet<(crate::graph::pdg::NodeId, crate::graph::pdg::NodeId)> = HashSet::new();
@@ -301,14 +412,14 @@ pub fn extract_data_flow_edges(
     for sig in signatures {
         if let Some(ret) = &sig.return_type {
             let norm = normalize_type_name(ret);
-            if !norm.is_empty() && !is_excluded_type(&norm) {
+            if !norm.is_empty() && !config.is_ignored(&norm) {
                 producers.entry(norm).or_default().push(sig);
             }
         }
         for param in &sig.parameters {
             if let Some(t) = &param.type_annotation {
                 let norm = normalize_type_name(t);
-                if !norm.is_empty() && !is_excluded_type(&norm) {
+                if !norm.is_empty() && !config.is_ignored(&norm) {
                     consumers.entry(norm).or_default().push(sig);
                 }
             }
@@ -320,6 +431,9 @@ pub fn extract_data_flow_edges(
     // Signal A: producer return type → consumer param type (confidence 0.85)
     for (type_name, producer_sigs) in &producers {
         if let Some(consumer_sigs) = consumers.get(type_name) {
+            if config.exceeds_fanout(producer_sigs.len() + consumer_sigs.len()) {
+                continue;
+            }
             for prod in producer_sigs {
                 for cons in consumer_sigs {
                     if prod.qualified_name == cons.qualified_name {
@@ -339,9 +453,13 @@ pub fn extract_data_flow_edges(
         }
     }
 
+    if config.producer_consumer_only {
+        return edges;
+    }
+
     // Signal B: shared return type + explicit call relationship (confidence 0.65)
     for (type_name, ret_sigs) in &producers {
-        if ret_sigs.len() < 2 {
+        if ret_sigs.len() < 2 || config.exceeds_fanout(ret_sigs.len()) {
             continue;
         }
         for i in 0..ret_sigs.len() {
@@ -393,20 +511,17 @@ pub fn extract_data_flow_edges(
             };
             for sig_b in callee_sigs {
                 // Find shared param types
-                let a_types: HashSet<String> = sig_a
-                    .parameters
-                    .iter()
-                    .filter_map(|p| p.type_annotation.as_ref())
-                    .map(|t| normalize_type_name(t))
-                    .filter(|t| !t.is_empty() && !is_excluded_type(t))
-                    .collect();
-                let b_types: HashSet<String> = sig_b
-                    .parameters
-                    .iter()
-                    .filter_map(|p| p.type_annotation.as_ref())
-                    .map(|t| normalize_type_name(t))
-                    .filter(|t| !t.is_empty() && !is_excluded_type(t))
-                    .collect();
+                let param_types = |sig: &SignatureInfo| -> HashSet<String> {
+                    sig.parameters
+                        .iter()
+                        .filter_map(|p| p.type_annotation.as_ref())
+                        .map(|t| normalize_type_name(t))
+                        .filter(|t| !t.is_empty() && !config.is_ignored(t))
+                        .filter(|t| !config.exceeds_fanout(consumers.get(t).map_or(0, Vec::len)))
+                        .collect()
+                };
+                let a_types = param_types(sig_a);
+                let b_types = param_types(sig_b);
                 let shared: Vec<&String> = a_types.intersection(&b_types).collect();
                 if shared.is_empty() {
                     continue;
@@ -502,10 +617,20 @@ fn normalize_type_name(raw: &str) -> String {
 //   Any signal reaching >= MIN_INHERITANCE_CONFIDENCE (0.45) produces an edge.
 //   This threshold is intentionally permissive; callers using TraversalConfig
 //   can filter edges by min_edge_confidence for tighter analysis.
+//
+// Explicit bases:
+//   Parsers that see base-class syntax (Python `class C(Base)`, Java
+//   `extends`/`implements`, Rust `impl Trait for Type`) record it in
+//   SignatureInfo::base_types. Those classes get edges straight from the
+//   declaration (confidence 1.0) and are left out of the signals above, which
+//   only run for classes without explicit data.
 // ---------------------------------------------------------------------------
 
 const MIN_INHERITANCE_CONFIDENCE: f32 = 0.45;
 
+/// Confidence for inheritance declared in source rather than inferred.
+const EXPLICIT_INHERITANCE_CONFIDENCE: f32 = 1.0;
+
 /// Method names so common they don't signal inheritance on their own.
 const COMMON_METHOD_NAMES: &[&str] = &[
     "new",
@@ -602,6 +727,10 @@ impl InheritanceEvidence {
 /// The minimum confidence threshold is set at 0.45 to ensure only meaningful
 /// inheritance relationships are captured.
 ///
+/// Classes whose signatures carry explicit `base_types` skip these signals and
+/// get one edge per declared base that resolves to a node in `node_ids`, with
+/// confidence 1.0.
+///
 /// # Arguments
 ///
 /// * `signatures` - A slice of function signature information containing class data
@@ -629,7 +758,73 @@ pub fn extract_inheritance_edges(
         }
     }
 
-    let class_names: Vec<&String> = class_methods.keys().collect();
+    // Explicit bases, keyed by the normalized name of the declaring class
+    let mut explicit: HashMap<String, Vec<&str>> = HashMap::new();
+    for sig in signatures.iter().filter(|sig| !sig.base_types.is_empty()) {
+        let normalized = normalize_symbol(&sig.qualified_name);
+        let owner = if sig.is_method {
+            match normalized.rfind('.') {
+                Some(dot_pos) => normalized[..dot_pos].to_string(),
+                None => continue,
+            }
+        } else {
+            normalized
+        };
+        let bases = explicit.entry(owner).or_default();
+        for base in &sig.base_types {
+            if !bases.contains(&base.as_str()) {
+                bases.push(base);
+            }
+        }
+    }
+
+    if !explicit.is_empty() {
+        // Names a declared base may refer to: inferred classes and type
+        // declarations, indexed by their last segment for unqualified bases
+        let mut type_names: HashMap<String, Vec<String>> = HashMap::new();
+        let declared = signatures
+            .iter()
+            .filter(|sig| !sig.is_method)
+            .map(|sig| normalize_symbol(&sig.qualified_name))
+            .chain(class_methods.keys().cloned());
+        for name in declared {
+            let last = name.rsplit('.').next().unwrap_or(&name).to_string();
+            let entry = type_names.entry(last).or_default();
+            if !entry.contains(&name) {
+                entry.push(name);
+            }
+        }
+
+        let mut children: Vec<&String> = explicit.keys().collect();
+        children.sort();
+        for child_cls in children {
+            let Some(child_id) = class_node_id(child_cls, node_ids, &class_methods) else {
+                continue;
+            };
+            for base in &explicit[child_cls] {
+                let base = normalize_symbol(base);
+                let last = base.rsplit('.').next().unwrap_or(&base);
+                let parent_cls = match type_names.get(last).map(Vec::as_slice) {
+                    Some([only]) => only.as_str(),
+                    Some(candidates) => match candidates.iter().find(|c| **c == base) {
+                        Some(exact) => exact.as_str(),
+                        None => continue,
+                    },
+                    None => continue,
+                };
+                if let Some(parent_id) = class_node_id(parent_cls, node_ids, &class_methods) {
+                    if parent_id != child_id {
+                        edges.push((child_id, parent_id, EXPLICIT_INHERITANCE_CONFIDENCE));
+                    }
+                }
+            }
+        }
+    }
+
+    let class_names: Vec<&String> = class_methods
+        .keys()
+        .filter(|cls| !explicit.contains_key(*cls))
+        .collect();
     if class_names.len() < 2 {
         return edges;
     }
@@ -796,6 +991,29 @@ pub fn extract_inheritance_edges(
     edges
 }
 
+/// Node for a class name: the class node itself, else its first method
+fn class_node_id(
+    class_name: &str,
+    node_ids: &HashMap<String, crate::graph::pdg::NodeId>,
+    class_methods: &HashMap<String, Vec<&SignatureInfo>>,
+) -> Option<crate::graph::pdg::NodeId> {
+    node_ids
+        .get(class_name)
+        .or_else(|| {
+            node_ids
+                .iter()
+                .find(|(key, _)| normalize_symbol(key) == class_name)
+                .map(|(_, id)| id)
+        })
+        .or_else(|| {
+            class_methods
+                .get(class_name)
+                .and_then(|m| m.first())
+                .and_then(|sig| node_ids.get(&sig.qualified_name))
+        })
+        .copied()
+}
+
 fn determine_inheritance_direction<'a>(
     cls_a: &'a str,
     cls_b: &'a str,
@@ -866,12 +1084,13 @@ fn determine_inheritance_direction<'a>(
 ///
 /// # Returns
 ///
-/// A vector of tuples containing (caller_node, callee_node) representing
-/// the extracted call graph edges.
+/// A vector of tuples containing (caller_node, callee_node, call_target)
+/// representing the extracted call graph edges. `call_target` is the call
+/// expression as written by the caller.
 pub fn extract_call_edges(
     signatures: &[SignatureInfo],
     node_ids: &HashMap<String, crate::graph::pdg::NodeId>,
-) -> Vec<(crate::graph::pdg::NodeId, crate::graph::pdg::NodeId)> {
+) -> Vec<(crate::graph::pdg::NodeId, crate::graph::pdg::NodeId, String)> {
     let mut edges = Vec::new();
     let mut seen: HashSet<(crate::graph::pdg::NodeId, crate::graph::pdg::NodeId)> = HashSet::new();
 
@@ -979,7 +1198,7 @@ pub fn extract_call_edges(
 
             for target_id in targets {
                 if caller_id != target_id && seen.insert((caller_id, target_id)) {
-                    edges.push((caller_id, target_id));
+                    edges.push((caller_id, target_id, call_target.clone()));
                 }
             }
 
@@ -1001,7 +1220,7 @@ pub fn extract_call_edges(
                         let pair = (caller_id, snid);
                         if !seen.contains(&pair) {
                             seen.insert(pair);
-                            edges.push(pair);
+                            edges.push((caller_id, snid, call_target.clone()));
                         }
                     }
                 }
@@ -1178,17 +1397,16 @@ fn resolve_cross_file_call_edges_inner(
                     .collect();
 
                 // Resolve via import aliases
+                let mut aliased: Option<String> = None;
                 if let Some(first) = call_segs.first() {
                     if let Some(import_path) = alias_map.get(first) {
-                        if call_segs.len() == 1 {
-                            candidates.push(import_path.clone());
+                        let resolved = if call_segs.len() == 1 {
+                            import_path.clone()
                         } else {
-                            candidates.push(format!(
-                                "{}.{}",
-                                import_path,
-                                call_segs[1..].join(".")
-                            ));
-                        }
+                            format!("{}.{}", import_path, call_segs[1..].join("."))
+                        };
+                        candidates.push(resolved.clone());
+                        aliased = Some(resolved);
                     }
                 }
 
@@ -1212,7 +1430,15 @@ fn resolve_cross_file_call_edges_inner(
 
                 // Try to resolve each candidate against the global maps
                 let mut targets: Vec<NodeId> = Vec::new();
-                let mut last_segment_fallback: Option<String> = None;
+                // An alias names the real symbol (`total` -> `utils.compute_total`),
+                // so its last segment is the better fallback
+                let mut last_segment_fallback: Option<String> = aliased.and_then(|path| {
+                    normalize_symbol(&path)
+                        .rsplit('.')
+                        .next()
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string)
+                });
                 for candidate in &candidates {
                     let norm = normalize_symbol(candidate);
                     let segs: Vec<String> = norm
@@ -1441,7 +1667,7 @@ fn resolve_cross_file_call_edges_inner(
                 for target_id in targets {
                     if caller_id != target_id && !existing_edges.contains(&(caller_id, target_id)) {
                         existing_edges.insert((caller_id, target_id));
-                        new_edges.push((caller_id, target_id));
+                        new_edges.push((caller_id, target_id, call_target.clone()));
                     }
                 }
                 let callee_name = normalize_symbol(call_target);
@@ -1459,7 +1685,7 @@ fn resolve_cross_file_call_edges_inner(
                             let pair = (caller_id, snid);
                             if !existing_edges.contains(&pair) {
                                 existing_edges.insert(pair);
-                                new_edges.push(pair);
+                                new_edges.push((caller_id, snid, call_target.clone()));
                             }
                         }
                     }
@@ -1473,7 +1699,7 @@ fn resolve_cross_file_call_edges_inner(
             "Cross-file call edge resolution: added {} new edges",
             new_edges.len()
         );
-        pdg.add_call_edges(new_edges);
+        pdg.add_call_edges_with_targets(new_edges);
     }
 }
 
@@ -1599,7 +1825,9 @@ pub fn extract_import_paths_from_source(source_code: &[u8], language: &str) -> H
         "php" => extract_php_imports(&source),
         "lua" => extract_lua_imports(&source),
         "scala" => extract_scala_imports(&source),
-        "c" | "cpp" | "c++" | "cxx" | "cc" | "h" | "hpp" => extract_c_imports(&source),
+        "c" | "cpp" | "c++" | "cxx" | "cc" | "h" | "hpp" | "objective-c" | "objc" | "m" => {
+            extract_c_imports(&source)
+        }
         _ => HashSet::new(),
     }
 }
@@ -1908,8 +2136,8 @@ fn extract_scala_imports(source: &str) -> HashSet<String> {
 
 fn extract_c_imports(source: &str) -> HashSet<String> {
     let mut imports = HashSet::new();
-    // #include <x> and #include "x"
-    let re = Regex::new(r#"#include\s*[<"']([^>"']+)[>"']"#).unwrap();
+    // #include <x>, #include "x" and Objective-C #import
+    let re = Regex::new(r#"#(?:include|import)\s*[<"']([^>"']+)[>"']"#).unwrap();
     for cap in re.captures_iter(source) {
         imports.insert(cap[1].trim().to_string());
     }
@@ -2122,6 +2350,7 @@ mod tests {
             imports: vec![],
             byte_range: (0, 100),
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         }
     }
 
@@ -2152,9 +2381,44 @@ mod tests {
             imports: vec![],
             byte_range: (0, 100),
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         }
     }
 
+    #[test]
+    fn extraction_uses_chosen_embedding_strategy() {
+        use crate::graph::embedding::{ContentEmbedding, StructuralEmbedding};
+
+        let source = b"def greet(name): return 'hello ' + name";
+        let mut greet = sig("greet", "greet", false);
+        greet.byte_range = (0, source.len());
+
+        let structural = StructuralEmbedding;
+        let content = ContentEmbedding { dimension: 16 };
+
+        let embedded_with = |strategy: &dyn EmbeddingStrategy| {
+            let pdg = extract_pdg_with_embeddings(
+                vec![greet.clone()],
+                source,
+                "greet.py",
+                "python",
+                strategy,
+            );
+            let node = pdg.find_by_name("greet").unwrap();
+            let stored = pdg.get_embedding(&pdg.get_node(node).unwrap().id).cloned();
+            (stored.unwrap(), strategy.embed(&pdg, node, source))
+        };
+
+        let (structural_stored, structural_expected) = embedded_with(&structural);
+        let (content_stored, content_expected) = embedded_with(&content);
+
+        assert_eq!(structural_stored, structural_expected);
+        assert_eq!(content_stored, content_expected);
+        assert_eq!(structural_stored.len(), structural.dimension());
+        assert_eq!(content_stored.len(), 16);
+        assert_ne!(structural_stored, content_stored);
+    }
+
     #[test]
     fn containment_edges_are_not_call_edges() {
         let sigs = vec![sig("speak", "Animal::speak", true)];
@@ -2197,6 +2461,39 @@ mod tests {
         assert!(*conf >= 0.8, "Signal A confidence should be >= 0.8");
     }
 
+    #[test]
+    fn explain_edges_names_shared_type_and_call_target() {
+        let producer = sig_with_types("make_user", "make_user", vec![], Some("Vec<User>"));
+        let mut consumer = sig_with_types("save_user", "save_user", vec![("u", "User")], None);
+        consumer.calls = vec!["make_user".to_string()];
+
+        let mut pdg = extract_pdg_from_signatures(vec![producer, consumer], b"", "f.rs", "rust");
+        let p = pdg.find_by_id("f.rs:make_user").unwrap();
+        let c = pdg.find_by_id("f.rs:save_user").unwrap();
+
+        let explanations = pdg.explain_edges(p, c);
+        let data = explanations
+            .iter()
+            .find(|e| e.edge_type == crate::graph::pdg::EdgeType::DataDependency)
+            .expect("producer and consumer share a type");
+        assert_eq!((data.from, data.to), (p, c));
+        assert_eq!(data.shared_type.as_deref(), Some("User"));
+
+        let call = explanations
+            .iter()
+            .find(|e| e.edge_type == crate::graph::pdg::EdgeType::Call)
+            .expect("consumer calls producer");
+        assert_eq!((call.from, call.to), (c, p));
+        assert_eq!(call.call_target.as_deref(), Some("make_user"));
+
+        let other = pdg.add_node(signature_to_node(
+            &sig("lonely", "lonely", false),
+            "g.rs",
+            "rust",
+        ));
+        assert!(pdg.explain_edges(p, other).is_empty());
+    }
+
     #[test]
     fn data_flow_clique_not_generated() {
         // 10 functions all taking String — old code would produce 45 edges
@@ -2227,6 +2524,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn data_flow_config_ignores_types_and_caps_fanout() {
+        // A chain of String → String functions, each calling the next. With
+        // String not ignored, all three signals fire between them.
+        let sigs: Vec<SignatureInfo> = (0..10)
+            .map(|i| {
+                let mut s = sig_with_types(
+                    &format!("f{i}"),
+                    &format!("f{i}"),
+                    vec![("s", "String")],
+                    Some("String"),
+                );
+                s.calls = vec![format!("f{}", i + 1)];
+                s
+            })
+            .collect();
+        let mut nids = HashMap::new();
+        let mut pdg = ProgramDependenceGraph::new();
+        for s in &sigs {
+            let nid = pdg.add_node(signature_to_node(s, "f.rs", "rust"));
+            nids.insert(s.qualified_name.clone(), nid);
+        }
+
+        let edges = extract_data_flow_edges_with_config(&sigs, &nids, &DataFlowConfig::default());
+        assert!(edges.is_empty(), "String is ignored by default");
+
+        let unfiltered = DataFlowConfig {
+            ignored_types: HashSet::new(),
+            max_type_fanout: None,
+            producer_consumer_only: false,
+        };
+        let edges = extract_data_flow_edges_with_config(&sigs, &nids, &unfiltered);
+        assert_eq!(
+            edges.len(),
+            90,
+            "Signal A links every producer/consumer pair"
+        );
+
+        let capped = DataFlowConfig {
+            max_type_fanout: Some(5),
+            ..unfiltered
+        };
+        assert!(extract_data_flow_edges_with_config(&sigs, &nids, &capped).is_empty());
+
+        let custom = DataFlowConfig::default().ignore_types(["Context"]);
+        let ctx_sigs = vec![
+            sig_with_types("make_ctx", "make_ctx", vec![], Some("Context")),
+            sig_with_types("use_ctx", "use_ctx", vec![("c", "Context")], None),
+        ];
+        let ctx_nids: HashMap<String, _> = ctx_sigs
+            .iter()
+            .map(|s| {
+                let nid = pdg.add_node(signature_to_node(s, "g.rs", "rust"));
+                (s.qualified_name.clone(), nid)
+            })
+            .collect();
+        assert!(extract_data_flow_edges_with_config(&ctx_sigs, &ctx_nids, &custom).is_empty());
+    }
+
+    #[test]
+    fn data_flow_producer_consumer_only_drops_call_signals() {
+        let mut caller = sig_with_types("caller", "caller", vec![("r", "Report")], None);
+        caller.calls = vec!["callee".to_string()];
+        let callee = sig_with_types("callee", "callee", vec![("r", "Report")], None);
+        let sigs = vec![caller, callee];
+        let mut nids = HashMap::new();
+        let mut pdg = ProgramDependenceGraph::new();
+        for s in &sigs {
+            let nid = pdg.add_node(signature_to_node(s, "f.rs", "rust"));
+            nids.insert(s.qualified_name.clone(), nid);
+        }
+
+        let edges = extract_data_flow_edges(&sigs, &nids);
+        assert_eq!(
+            edges.len(),
+            1,
+            "Signal C links caller and callee via Report"
+        );
+
+        let config = DataFlowConfig {
+            producer_consumer_only: true,
+            ..DataFlowConfig::default()
+        };
+        assert!(extract_data_flow_edges_with_config(&sigs, &nids, &config).is_empty());
+    }
+
     #[test]
     fn inheritance_super_call_signal() {
         let parent_speak = sig("speak", "Animal::speak", true);
@@ -2258,6 +2641,90 @@ mod tests {
         );
     }
 
+    fn inheritance_pairs(pdg: &ProgramDependenceGraph) -> Vec<(String, String, f32)> {
+        pdg.edge_indices()
+            .filter_map(|e| {
+                let edge = pdg.get_edge(e)?;
+                if edge.edge_type != crate::graph::pdg::EdgeType::Inheritance {
+                    return None;
+                }
+                let (child, parent) = pdg.edge_endpoints(e)?;
+                Some((
+                    pdg.get_node(child)?.name.clone(),
+                    pdg.get_node(parent)?.name.clone(),
+                    edge.metadata.confidence.unwrap_or(0.0),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn python_explicit_base_class_creates_parent_edge() {
+        use crate::parse::traits::CodeIntelligence;
+
+        let source = b"
+class Animal:
+    def speak(self):
+        pass
+
+    def eat(self):
+        pass
+
+class Dog(Animal):
+    def speak(self):
+        return 'woof'
+
+    def eat(self):
+        pass
+
+class Cat(Animal):
+    def speak(self):
+        return 'meow'
+
+    def eat(self):
+        pass
+";
+        let sigs = crate::parse::python::PythonParser::new()
+            .get_signatures(source)
+            .unwrap();
+        let pdg = extract_pdg_from_signatures(sigs, source, "zoo.py", "python");
+
+        let mut edges = inheritance_pairs(&pdg);
+        edges.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            edges,
+            vec![
+                ("Cat".to_string(), "Animal".to_string(), 1.0),
+                ("Dog".to_string(), "Animal".to_string(), 1.0),
+            ],
+            "Only declared bases should produce edges; Dog and Cat share methods but are siblings"
+        );
+    }
+
+    #[test]
+    fn java_extends_creates_parent_edge() {
+        use crate::parse::traits::CodeIntelligence;
+
+        let source = b"public class Animal {
+    public void speak() {}
+}
+
+public class Dog extends Animal implements Runnable {
+    public void speak() {}
+    public void run() {}
+}";
+        let sigs = crate::parse::java::JavaParser::new()
+            .get_signatures(source)
+            .unwrap();
+        let pdg = extract_pdg_from_signatures(sigs, source, "Zoo.java", "java");
+
+        assert_eq!(
+            inheritance_pairs(&pdg),
+            vec![("Dog".to_string(), "Animal".to_string(), 1.0)],
+            "Runnable is not declared in the file, so only Animal resolves"
+        );
+    }
+
     #[test]
     fn python_multiline_import_parsed() {
         let source = b"from os.path import (\n    join,\n    exists,\n    dirname\n)\n";
@@ -2299,6 +2766,7 @@ mod tests {
             imports: vec![],
             byte_range: (0, 10),
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         };
 
         let node = signature_to_node(&sig_simple, "test.rs", "rust");
@@ -2307,6 +2775,7 @@ mod tests {
         // Test 2: cyclomatic_complexity > 0 should use that value
         let sig_complex = SignatureInfo {
             cyclomatic_complexity: 5,
+            base_types: Vec::new(),
             ..sig_simple.clone()
         };
 
@@ -2330,6 +2799,7 @@ mod tests {
                 },
             ],
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
             ..sig_simple
         };
 
@@ -2339,6 +2809,7 @@ mod tests {
         // Test 4: cyclomatic should override parameter count
         let sig_both = SignatureInfo {
             cyclomatic_complexity: 10,
+            base_types: Vec::new(),
             ..sig_params
         };
 
@@ -2901,4 +3372,50 @@ mod tests {
 
         assert_eq!(qualified_name_from_node(&node), None);
     }
+
+    #[test]
+    fn python_aliased_import_call_resolves_to_real_target() {
+        use crate::parse::traits::CodeIntelligence;
+
+        let parser = crate::parse::python::PythonParser::new();
+        let utils_src = b"def compute_total(items):\n    return sum(items)\n";
+        let report_src = b"from .utils import compute_total as total\n\ndef report(items):\n    return total(items)\n";
+        let utils_sigs = parser.get_signatures(utils_src).unwrap();
+        let report_sigs = parser.get_signatures(report_src).unwrap();
+
+        let utils =
+            extract_pdg_from_signatures(utils_sigs.clone(), utils_src, "utils.py", "python");
+        let report =
+            extract_pdg_from_signatures(report_sigs.clone(), report_src, "report.py", "python");
+        let mut merged = ProgramDependenceGraph::new();
+        for source in [&utils, &report] {
+            for nid in source.node_indices() {
+                if let Some(node) = source.get_node(nid) {
+                    merged.add_node(node.clone());
+                }
+            }
+        }
+
+        let all: Vec<(String, SignatureInfo)> = utils_sigs
+            .into_iter()
+            .map(|sig| ("utils.py".to_string(), sig))
+            .chain(
+                report_sigs
+                    .into_iter()
+                    .map(|sig| ("report.py".to_string(), sig)),
+            )
+            .collect();
+        resolve_cross_file_call_edges_for_files(&mut merged, &all);
+
+        assert!(
+            has_call_edge_between_files(
+                &merged,
+                "report.py",
+                "report",
+                "utils.py",
+                "compute_total"
+            ),
+            "`total(...)` should resolve through the alias to compute_total"
+        );
+    }
 }
@@ -20,14 +20,23 @@ pub mod traversal;
 /// Trigram index for accelerated fuzzy node lookup.
 pub mod trigram;
 
-pub use cross_project::{CrossProjectPDG, ExternalNodeRef, MergeError};
-pub use embedding::NodeEmbedding;
+pub use cross_project::{CrossProjectPDG, ExternalNodeRef, MergeError, MergeReport};
+pub use embedding::{
+    embed_node, embed_nodes, CombinedEmbedding, ContentEmbedding, EmbeddingStrategy, NodeEmbedding,
+    StructuralEmbedding,
+};
 pub use external_deps::{annotate_external_nodes, ExternalDependencyRegistry};
 pub use extraction::extract_pdg_from_signatures;
+pub use extraction::extract_pdg_with_data_flow_config;
+pub use extraction::extract_pdg_with_embeddings;
 pub use extraction::resolve_cross_file_call_edges;
 pub use extraction::resolve_cross_file_call_edges_for_files;
-pub use pdg::{Edge, Node, ProgramDependenceGraph};
-pub use traversal::{GravityTraversal, TraversalConfig};
+pub use extraction::DataFlowConfig;
+pub use pdg::{
+    CycleError, DegreeStats, Edge, EdgeDirection, EdgeFilter, EdgeTypeCounts, Node,
+    ProgramDependenceGraph,
+};
+pub use traversal::{GravityTraversal, TokenCounter, TraversalConfig, TraversalResult};
 
 /// Graph library initialization
 pub fn init() {
@@ -9,7 +9,7 @@
 //   - All public traversal methods take `TraversalConfig` — callers must be explicit
 
 use petgraph::stable_graph::StableGraph;
-use petgraph::visit::EdgeRef;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
@@ -146,6 +146,7 @@ pub struct Edge {
 /// - `call_count`: Populated for Call edges
 /// - `variable_name`: Populated for DataDependency edges
 /// - `confidence`: Populated for inferred edges (Inheritance, DataDependency signals)
+/// - `call_target`: Populated for Call edges created during extraction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EdgeMetadata {
     /// Number of times this call relationship was observed in the codebase.
@@ -165,6 +166,13 @@ pub struct EdgeMetadata {
     /// relationship is inferred rather than explicitly declared. Higher
     /// values indicate stronger evidence for the relationship.
     pub confidence: Option<f32>,
+
+    /// The call expression, as written by the caller, that produced this edge.
+    ///
+    /// Only meaningful for Call edges. Absent on edges persisted before the
+    /// field existed.
+    #[serde(default)]
+    pub call_target: Option<String>,
 }
 
 impl EdgeMetadata {
@@ -177,6 +185,7 @@ impl EdgeMetadata {
             call_count: None,
             variable_name: None,
             confidence: None,
+            call_target: None,
         }
     }
 
@@ -195,6 +204,7 @@ impl EdgeMetadata {
             call_count: None,
             variable_name: None,
             confidence: Some(confidence),
+            call_target: None,
         }
     }
 
@@ -213,10 +223,45 @@ impl EdgeMetadata {
             call_count: None,
             variable_name: Some(name),
             confidence: None,
+            call_target: None,
+        }
+    }
+
+    /// Creates EdgeMetadata recording the call expression behind a Call edge.
+    pub fn with_call_target(target: String) -> Self {
+        Self {
+            call_count: None,
+            variable_name: None,
+            confidence: None,
+            call_target: Some(target),
         }
     }
 }
 
+/// Why two nodes are connected, as recorded on one PDG edge.
+///
+/// Returned by [`ProgramDependenceGraph::explain_edges`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeExplanation {
+    /// Source node of the edge.
+    pub from: NodeId,
+
+    /// Target node of the edge.
+    pub to: NodeId,
+
+    /// The type of relationship.
+    pub edge_type: EdgeType,
+
+    /// Type name shared by both ends (DataDependency edges).
+    pub shared_type: Option<String>,
+
+    /// Call expression that produced the edge (Call edges).
+    pub call_target: Option<String>,
+
+    /// Confidence for inferred edges.
+    pub confidence: Option<f32>,
+}
+
 // ---------------------------------------------------------------------------
 // Traversal configuration
 // ---------------------------------------------------------------------------
@@ -344,6 +389,124 @@ impl TraversalConfig {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Edge filter for subgraph extraction
+// ---------------------------------------------------------------------------
+
+/// Which way edges are followed from a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeDirection {
+    /// Follow outgoing edges (callees, dependencies).
+    Outgoing,
+    /// Follow incoming edges (callers, dependents).
+    Incoming,
+    /// Follow edges in both directions.
+    #[default]
+    Both,
+}
+
+/// Edge selection for subgraph queries such as
+/// [`ProgramDependenceGraph::k_hop_subgraph`] and
+/// [`ProgramDependenceGraph::topo_order`].
+#[derive(Debug, Clone, Default)]
+pub struct EdgeFilter {
+    /// Only follow edges of these types. `None` = all edge types.
+    pub edge_types: Option<Vec<EdgeType>>,
+    /// Direction in which edges are followed.
+    pub direction: EdgeDirection,
+}
+
+impl EdgeFilter {
+    /// Follow only edges of the given types, in both directions.
+    pub fn types(edge_types: &[EdgeType]) -> Self {
+        Self {
+            edge_types: Some(edge_types.to_vec()),
+            direction: EdgeDirection::Both,
+        }
+    }
+
+    /// Restrict this filter to one direction.
+    pub fn direction(mut self, direction: EdgeDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    fn allows(&self, edge: &Edge) -> bool {
+        self.edge_types
+            .as_ref()
+            .map(|types| types.contains(&edge.edge_type))
+            .unwrap_or(true)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Coupling metrics
+// ---------------------------------------------------------------------------
+
+/// Edge counts in one direction, broken down by edge type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeTypeCounts {
+    /// Call edges
+    pub call: usize,
+    /// Data dependency edges
+    pub data_dependency: usize,
+    /// Inheritance edges
+    pub inheritance: usize,
+    /// Import edges
+    pub import: usize,
+    /// Containment edges
+    pub containment: usize,
+}
+
+impl EdgeTypeCounts {
+    fn add(&mut self, edge_type: &EdgeType) {
+        match edge_type {
+            EdgeType::Call => self.call += 1,
+            EdgeType::DataDependency => self.data_dependency += 1,
+            EdgeType::Inheritance => self.inheritance += 1,
+            EdgeType::Import => self.import += 1,
+            EdgeType::Containment => self.containment += 1,
+        }
+    }
+
+    /// Count of dependency edges, i.e. every type except containment.
+    ///
+    /// Containment is structural (class → method), not coupling.
+    pub fn coupling(&self) -> usize {
+        self.call + self.data_dependency + self.inheritance + self.import
+    }
+}
+
+/// Fan-in and fan-out of a single node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegreeStats {
+    /// Incoming edges (callers, importers, data sources)
+    pub incoming: EdgeTypeCounts,
+    /// Outgoing edges (callees, imports, data sinks)
+    pub outgoing: EdgeTypeCounts,
+}
+
+impl DegreeStats {
+    /// Incoming dependency edges, excluding containment.
+    pub fn fan_in(&self) -> usize {
+        self.incoming.coupling()
+    }
+
+    /// Outgoing dependency edges, excluding containment.
+    pub fn fan_out(&self) -> usize {
+        self.outgoing.coupling()
+    }
+}
+
+/// Returned by [`ProgramDependenceGraph::topo_order`] when the filtered
+/// graph contains a cycle.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("graph is not acyclic: cycle through {}", cycle.join(" -> "))]
+pub struct CycleError {
+    /// IDs of the nodes forming one cycle (see [`ProgramDependenceGraph::find_cycles`]).
+    pub cycle: Vec<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Embedding store (externalized from Node)
 // ---------------------------------------------------------------------------
@@ -591,6 +754,94 @@ impl SerializablePDG {
     }
 }
 
+/// Header marking the stable serialization format. Bytes without it are
+/// decoded as the legacy index-keyed [`SerializablePDG`].
+const STABLE_FORMAT_MAGIC: &[u8; 5] = b"LPDG\x02";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StableEdge {
+    source: String,
+    target: String,
+    edge: Edge,
+}
+
+/// Serialized form keyed on `Node.id` instead of petgraph indices.
+///
+/// Nodes and edges are sorted and embeddings use an ordered map, so the same
+/// graph always produces the same bytes regardless of insertion order.
+/// Lookup indexes are rebuilt on load rather than stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StablePDG {
+    nodes: Vec<Node>,
+    edges: Vec<StableEdge>,
+    #[serde(default)]
+    embeddings: std::collections::BTreeMap<String, Vec<f32>>,
+}
+
+impl StablePDG {
+    fn from_pdg(pdg: &ProgramDependenceGraph) -> Self {
+        let mut nodes: Vec<Node> = pdg.graph.node_weights().cloned().collect();
+        nodes.sort_by(|a, b| {
+            a.id.cmp(&b.id)
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.byte_range.cmp(&b.byte_range))
+        });
+
+        let mut edges: Vec<(StableEdge, String)> = pdg
+            .graph
+            .edge_references()
+            .map(|e| {
+                let edge = StableEdge {
+                    source: pdg.graph[e.source()].id.clone(),
+                    target: pdg.graph[e.target()].id.clone(),
+                    edge: e.weight().clone(),
+                };
+                let tiebreak = format!("{:?}", edge.edge);
+                (edge, tiebreak)
+            })
+            .collect();
+        edges.sort_by(|(a, a_key), (b, b_key)| {
+            a.source
+                .cmp(&b.source)
+                .then_with(|| a.target.cmp(&b.target))
+                .then_with(|| a_key.cmp(b_key))
+        });
+
+        Self {
+            nodes,
+            edges: edges.into_iter().map(|(edge, _)| edge).collect(),
+            embeddings: pdg
+                .embedding_store
+                .embeddings
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    fn to_pdg(&self) -> Result<ProgramDependenceGraph, String> {
+        let mut pdg = ProgramDependenceGraph::new();
+        let mut ids: HashMap<&str, NodeId> = HashMap::new();
+        for node in &self.nodes {
+            let nid = pdg.add_node(node.clone());
+            ids.entry(node.id.as_str()).or_insert(nid);
+        }
+        for se in &self.edges {
+            let src = ids
+                .get(se.source.as_str())
+                .ok_or_else(|| format!("Missing source {}", se.source))?;
+            let tgt = ids
+                .get(se.target.as_str())
+                .ok_or_else(|| format!("Missing target {}", se.target))?;
+            pdg.add_edge(*src, *tgt, se.edge.clone());
+        }
+        for (node_id, embedding) in &self.embeddings {
+            pdg.embedding_store.insert(node_id, embedding.clone());
+        }
+        Ok(pdg)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ProgramDependenceGraph
 // ---------------------------------------------------------------------------
@@ -951,6 +1202,65 @@ impl ProgramDependenceGraph {
         self.graph.neighbors(node_id).collect()
     }
 
+    /// Returns outgoing neighbors reached through edges of the given types.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node to get neighbors for
+    /// * `allowed_edge_types` - Edge types to follow. `None` = all edge types,
+    ///   matching [`neighbors`](Self::neighbors).
+    pub fn neighbors_with_types(
+        &self,
+        node_id: NodeId,
+        allowed_edge_types: Option<&[EdgeType]>,
+    ) -> Vec<NodeId> {
+        match allowed_edge_types {
+            None => self.neighbors(node_id),
+            Some(types) => self
+                .graph
+                .edges(node_id)
+                .filter(|edge| types.contains(&edge.weight().edge_type))
+                .map(|edge| edge.target())
+                .collect(),
+        }
+    }
+
+    /// Explains why two nodes are connected.
+    ///
+    /// Returns one entry per edge between `a` and `b`, in either direction,
+    /// carrying the edge type and the evidence recorded at extraction time:
+    /// the shared type name for data dependencies and the call expression
+    /// for calls. Returns an empty vector when the nodes are not adjacent.
+    pub fn explain_edges(&self, a: NodeId, b: NodeId) -> Vec<EdgeExplanation> {
+        let forward = self.graph.edges_connecting(a, b);
+        let backward = self.graph.edges_connecting(b, a).filter(|_| a != b);
+        forward
+            .chain(backward)
+            .map(|edge| {
+                let weight = edge.weight();
+                let shared_type = match weight.edge_type {
+                    EdgeType::DataDependency => {
+                        weight.metadata.variable_name.as_deref().map(|name| {
+                            name.strip_prefix("ret:")
+                                .or_else(|| name.strip_prefix("param:"))
+                                .unwrap_or(name)
+                                .to_string()
+                        })
+                    }
+                    _ => None,
+                };
+                EdgeExplanation {
+                    from: edge.source(),
+                    to: edge.target(),
+                    edge_type: weight.edge_type.clone(),
+                    shared_type,
+                    call_target: weight.metadata.call_target.clone(),
+                    confidence: weight.metadata.confidence,
+                }
+            })
+            .collect()
+    }
+
     /// Returns all incoming predecessor nodes to the given node.
     ///
     /// # Arguments
@@ -983,6 +1293,58 @@ impl ProgramDependenceGraph {
             .count()
     }
 
+    /// In- and out-degree of `node_id`, broken down by edge type.
+    ///
+    /// Returns `None` if the node does not exist.
+    pub fn degree_stats(&self, node_id: NodeId) -> Option<DegreeStats> {
+        use petgraph::Direction;
+        self.graph.node_weight(node_id)?;
+
+        let mut stats = DegreeStats::default();
+        for edge in self.graph.edges_directed(node_id, Direction::Incoming) {
+            stats.incoming.add(&edge.weight().edge_type);
+        }
+        for edge in self.graph.edges_directed(node_id, Direction::Outgoing) {
+            stats.outgoing.add(&edge.weight().edge_type);
+        }
+        Some(stats)
+    }
+
+    /// The `n` nodes with the highest fan-in, highest first.
+    ///
+    /// Ties are broken by node ID so the result is deterministic.
+    pub fn top_by_fan_in(&self, n: usize) -> Vec<(NodeId, DegreeStats)> {
+        self.top_by_degree(n, DegreeStats::fan_in)
+    }
+
+    /// The `n` nodes with the highest fan-out, highest first.
+    ///
+    /// Ties are broken by node ID so the result is deterministic.
+    pub fn top_by_fan_out(&self, n: usize) -> Vec<(NodeId, DegreeStats)> {
+        self.top_by_degree(n, DegreeStats::fan_out)
+    }
+
+    fn top_by_degree(
+        &self,
+        n: usize,
+        key: fn(&DegreeStats) -> usize,
+    ) -> Vec<(NodeId, DegreeStats)> {
+        let mut ranked: Vec<(NodeId, DegreeStats)> = self
+            .graph
+            .node_indices()
+            .filter_map(|id| Some((id, self.degree_stats(id)?)))
+            .collect();
+        ranked.sort_by(|(a_id, a), (b_id, b)| {
+            key(b).cmp(&key(a)).then_with(|| {
+                let a_name = self.graph.node_weight(*a_id).map(|node| node.id.as_str());
+                let b_name = self.graph.node_weight(*b_id).map(|node| node.id.as_str());
+                a_name.cmp(&b_name)
+            })
+        });
+        ranked.truncate(n);
+        ranked
+    }
+
     // -----------------------------------------------------------------------
     // Lookup (all O(1) or O(k) where k = results count)
     // -----------------------------------------------------------------------
@@ -1178,6 +1540,24 @@ impl ProgramDependenceGraph {
         }
     }
 
+    /// Adds call edges that remember the call expression that produced them.
+    ///
+    /// # Arguments
+    ///
+    /// * `calls` - A vector of (caller, callee, call_target) tuples
+    pub fn add_call_edges_with_targets(&mut self, calls: Vec<(NodeId, NodeId, String)>) {
+        for (from, to, target) in calls {
+            self.add_edge(
+                from,
+                to,
+                Edge {
+                    edge_type: EdgeType::Call,
+                    metadata: EdgeMetadata::with_call_target(target),
+                },
+            );
+        }
+    }
+
     /// Adds multiple data flow edges to the graph in batch.
     ///
     /// # Arguments
@@ -1194,6 +1574,7 @@ impl ProgramDependenceGraph {
                         call_count: None,
                         variable_name: Some(var_name),
                         confidence: Some(confidence),
+                        call_target: None,
                     },
                 },
             );
@@ -1317,6 +1698,181 @@ impl ProgramDependenceGraph {
         combined.into_iter().collect()
     }
 
+    /// Extract the neighborhood of `symbol` within `k` hops as a new PDG.
+    ///
+    /// `symbol` is matched against node IDs first, then names. Edges are
+    /// followed in `filter.direction` and only if their type passes the
+    /// filter; nodes more than `k` hops away are never included. The result
+    /// holds every edge between included nodes that passes the type filter.
+    ///
+    /// Returns `None` if `symbol` is not in the graph.
+    pub fn k_hop_subgraph(
+        &self,
+        symbol: &str,
+        k: usize,
+        filter: &EdgeFilter,
+    ) -> Option<ProgramDependenceGraph> {
+        use petgraph::Direction as PD;
+
+        let start = self
+            .find_by_symbol(symbol)
+            .or_else(|| self.find_by_name(symbol))?;
+
+        let mut depth: HashMap<NodeId, usize> = HashMap::from([(start, 0)]);
+        let mut order = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let d = depth[&current];
+            if d >= k {
+                continue;
+            }
+            let mut next = Vec::new();
+            if filter.direction != EdgeDirection::Incoming {
+                next.extend(
+                    self.graph
+                        .edges_directed(current, PD::Outgoing)
+                        .filter(|e| filter.allows(e.weight()))
+                        .map(|e| e.target()),
+                );
+            }
+            if filter.direction != EdgeDirection::Outgoing {
+                next.extend(
+                    self.graph
+                        .edges_directed(current, PD::Incoming)
+                        .filter(|e| filter.allows(e.weight()))
+                        .map(|e| e.source()),
+                );
+            }
+            for neighbor in next {
+                if let std::collections::hash_map::Entry::Vacant(entry) = depth.entry(neighbor) {
+                    entry.insert(d + 1);
+                    order.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let mut sub = ProgramDependenceGraph::new();
+        let mut mapping: HashMap<NodeId, NodeId> = HashMap::new();
+        for &id in &order {
+            if let Some(node) = self.get_node(id) {
+                let new_id = sub.add_node(node.clone());
+                if let Some(embedding) = self.get_embedding(&node.id) {
+                    sub.set_embedding(&node.id, embedding.clone());
+                }
+                mapping.insert(id, new_id);
+            }
+        }
+        for &id in &order {
+            for edge in self.graph.edges_directed(id, PD::Outgoing) {
+                if !filter.allows(edge.weight()) {
+                    continue;
+                }
+                if let (Some(&from), Some(&to)) = (mapping.get(&id), mapping.get(&edge.target())) {
+                    sub.add_edge(from, to, edge.weight().clone());
+                }
+            }
+        }
+
+        Some(sub)
+    }
+
+    /// Find cycles among the edges passing `filter`'s type selection.
+    ///
+    /// Each cycle is a strongly connected component with more than one node,
+    /// or a single node with a self edge. Direction does not matter here.
+    pub fn find_cycles(&self, filter: &EdgeFilter) -> Vec<Vec<NodeId>> {
+        let mut graph = petgraph::graph::DiGraph::<NodeId, ()>::new();
+        let mut indices: HashMap<NodeId, petgraph::graph::NodeIndex> = HashMap::new();
+        for id in self.graph.node_indices() {
+            indices.insert(id, graph.add_node(id));
+        }
+
+        let mut self_loops = Vec::new();
+        for edge in self.graph.edge_references() {
+            if !filter.allows(edge.weight()) {
+                continue;
+            }
+            if edge.source() == edge.target() {
+                self_loops.push(vec![edge.source()]);
+            } else {
+                graph.update_edge(indices[&edge.source()], indices[&edge.target()], ());
+            }
+        }
+
+        let mut cycles: Vec<Vec<NodeId>> = petgraph::algo::tarjan_scc(&graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let mut ids: Vec<NodeId> = component.into_iter().map(|idx| graph[idx]).collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect();
+        self_loops.sort();
+        self_loops.dedup();
+        cycles.extend(self_loops);
+        cycles
+    }
+
+    /// Order nodes so that every edge passing `filter` goes from an earlier
+    /// node to a later one (callers before callees for call edges).
+    ///
+    /// With [`EdgeDirection::Incoming`] the order is reversed (callees first);
+    /// `Outgoing` and `Both` behave the same. Ties keep node insertion order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CycleError`] listing one cycle if the filtered graph is not
+    /// acyclic.
+    pub fn topo_order(&self, filter: &EdgeFilter) -> Result<Vec<NodeId>, CycleError> {
+        let mut in_degree: HashMap<NodeId, usize> =
+            self.graph.node_indices().map(|id| (id, 0)).collect();
+        for edge in self.graph.edge_references() {
+            if filter.allows(edge.weight()) {
+                *in_degree.entry(edge.target()).or_default() += 1;
+            }
+        }
+
+        let mut queue: VecDeque<NodeId> = self
+            .graph
+            .node_indices()
+            .filter(|id| in_degree[id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(in_degree.len());
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            for edge in self.graph.edges(current) {
+                if !filter.allows(edge.weight()) {
+                    continue;
+                }
+                if let Some(degree) = in_degree.get_mut(&edge.target()) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(edge.target());
+                    }
+                }
+            }
+        }
+
+        if order.len() < in_degree.len() {
+            let cycle = self
+                .find_cycles(filter)
+                .into_iter()
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|id| self.get_node(id).map(|node| node.id.clone()))
+                .collect();
+            return Err(CycleError { cycle });
+        }
+
+        if filter.direction == EdgeDirection::Incoming {
+            order.reverse();
+        }
+        Ok(order)
+    }
+
     fn bfs_directed(&self, start: NodeId, config: &TraversalConfig, dir: Direction) -> Vec<NodeId> {
         let mut visited: HashSet<NodeId> = HashSet::new();
         let mut queue: VecDeque<(NodeId, usize)> = VecDeque::new();
@@ -1387,20 +1943,41 @@ impl ProgramDependenceGraph {
 
     /// Serializes the PDG to a binary format.
     ///
-    /// Uses bincode for efficient serialization. The serialized format includes
-    /// all nodes, edges, and indexes.
+    /// Uses bincode for efficient serialization. Nodes and edges are keyed on
+    /// the stable `Node.id` rather than internal indices, so indexing the same
+    /// code twice yields identical bytes regardless of parse order.
     ///
     /// # Returns
     ///
     /// A Result containing the serialized bytes, or an error message if serialization fails.
     pub fn serialize(&self) -> Result<Vec<u8>, String> {
-        bincode::serialize(&SerializablePDG::from_pdg(self))
-            .map_err(|e| format!("Serialize failed: {}", e))
+        let body = bincode::serialize(&StablePDG::from_pdg(self))
+            .map_err(|e| format!("Serialize failed: {}", e))?;
+        let mut bytes = Vec::with_capacity(STABLE_FORMAT_MAGIC.len() + body.len());
+        bytes.extend_from_slice(STABLE_FORMAT_MAGIC);
+        bytes.extend(body);
+        Ok(bytes)
+    }
+
+    /// Exports the PDG as pretty-printed JSON in the same deterministic
+    /// layout as [`serialize`](Self::serialize), for diffing between runs.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&StablePDG::from_pdg(self))
+            .map_err(|e| format!("JSON export failed: {}", e))
+    }
+
+    /// Restores a PDG exported with [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str::<StablePDG>(json)
+            .map_err(|e| format!("JSON import failed: {}", e))
+            .and_then(|s| s.to_pdg())
     }
 
     /// Deserializes a PDG from binary data.
     ///
-    /// Restores a ProgramDependenceGraph from bytes previously serialized with `serialize()`.
+    /// Restores a ProgramDependenceGraph from bytes previously serialized with `serialize()`,
+    /// rebuilding the id → index mapping and lookup indexes. Bytes in the older
+    /// index-keyed format are still accepted.
     ///
     /// # Arguments
     ///
@@ -1410,6 +1987,12 @@ impl ProgramDependenceGraph {
     ///
     /// A Result containing the deserialized PDG, or an error message if deserialization fails.
     pub fn deserialize(data: &[u8]) -> Result<Self, String> {
+        if let Some(body) = data.strip_prefix(STABLE_FORMAT_MAGIC.as_slice()) {
+            return bincode::deserialize::<StablePDG>(body)
+                .map_err(|e| format!("Deserialize failed: {}", e))
+                .and_then(|s| s.to_pdg());
+        }
+        // Legacy index-keyed format
         bincode::deserialize::<SerializablePDG>(data)
             .map_err(|e| format!("Deserialize failed: {}", e))
             .and_then(|s| s.to_pdg())
@@ -1574,6 +2157,163 @@ mod tests {
         assert!(result.len() <= 3, "Should respect max_nodes cap");
     }
 
+    fn chain_graph() -> (ProgramDependenceGraph, Vec<NodeId>) {
+        let mut pdg = ProgramDependenceGraph::new();
+        let n: Vec<NodeId> = (0..5)
+            .map(|i| {
+                pdg.add_node(make_node(
+                    &format!("f:n{i}"),
+                    &format!("n{i}"),
+                    "f.rs",
+                    NodeType::Function,
+                ))
+            })
+            .collect();
+        // Chain: n0 → n1 → n2 → n3 → n4
+        for i in 0..4 {
+            pdg.add_call_edges(vec![(n[i], n[i + 1])]);
+        }
+        (pdg, n)
+    }
+
+    fn subgraph_names(pdg: &ProgramDependenceGraph) -> Vec<String> {
+        let mut names: Vec<String> = pdg
+            .node_indices()
+            .filter_map(|id| pdg.get_node(id))
+            .map(|node| node.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn k_hop_subgraph_respects_depth_and_direction() {
+        let (pdg, _) = chain_graph();
+        let both = EdgeFilter::default();
+
+        let one = pdg.k_hop_subgraph("f:n2", 1, &both).unwrap();
+        assert_eq!(subgraph_names(&one), vec!["n1", "n2", "n3"]);
+        assert_eq!(one.edge_count(), 2);
+
+        let two = pdg.k_hop_subgraph("n2", 2, &both).unwrap();
+        assert_eq!(subgraph_names(&two), vec!["n0", "n1", "n2", "n3", "n4"]);
+        assert_eq!(two.edge_count(), 4);
+
+        let forward = EdgeFilter::default().direction(EdgeDirection::Outgoing);
+        let down = pdg.k_hop_subgraph("f:n2", 2, &forward).unwrap();
+        assert_eq!(subgraph_names(&down), vec!["n2", "n3", "n4"]);
+
+        let zero = pdg.k_hop_subgraph("f:n2", 0, &both).unwrap();
+        assert_eq!(subgraph_names(&zero), vec!["n2"]);
+        assert!(pdg.k_hop_subgraph("missing", 1, &both).is_none());
+    }
+
+    #[test]
+    fn k_hop_subgraph_filters_edge_types() {
+        let (mut pdg, n) = chain_graph();
+        pdg.add_import_edges(vec![(n[2], n[0])]);
+
+        let calls_only = EdgeFilter::types(&[EdgeType::Call]);
+        let sub = pdg.k_hop_subgraph("f:n2", 1, &calls_only).unwrap();
+        assert_eq!(subgraph_names(&sub), vec!["n1", "n2", "n3"]);
+
+        let all = pdg
+            .k_hop_subgraph("f:n2", 1, &EdgeFilter::default())
+            .unwrap();
+        assert_eq!(subgraph_names(&all), vec!["n0", "n1", "n2", "n3"]);
+        // n0→n1 is a call edge between included nodes, so it is kept too
+        assert_eq!(all.edge_count(), 4);
+    }
+
+    #[test]
+    fn topo_order_puts_callers_before_callees() {
+        let mut pdg = ProgramDependenceGraph::new();
+        let main = pdg.add_node(make_node("f:main", "main", "f.rs", NodeType::Function));
+        let parse = pdg.add_node(make_node("f:parse", "parse", "f.rs", NodeType::Function));
+        let lex = pdg.add_node(make_node("f:lex", "lex", "f.rs", NodeType::Function));
+        let log = pdg.add_node(make_node("f:log", "log", "f.rs", NodeType::Function));
+        pdg.add_call_edges(vec![(main, parse), (parse, lex), (main, log), (lex, log)]);
+        // A non-call back edge must not matter with a call-only filter
+        pdg.add_import_edges(vec![(log, main)]);
+
+        let calls = EdgeFilter::types(&[EdgeType::Call]);
+        let order = pdg.topo_order(&calls).unwrap();
+        let pos = |id: NodeId| order.iter().position(|&n| n == id).unwrap();
+        assert_eq!(order.len(), 4);
+        for edge in pdg.edge_indices() {
+            if pdg.get_edge(edge).unwrap().edge_type == EdgeType::Call {
+                let (caller, callee) = pdg.edge_endpoints(edge).unwrap();
+                assert!(pos(caller) < pos(callee));
+            }
+        }
+
+        let reversed = pdg
+            .topo_order(&calls.clone().direction(EdgeDirection::Incoming))
+            .unwrap();
+        assert_eq!(reversed.first(), Some(&log));
+        assert_eq!(reversed.last(), Some(&main));
+    }
+
+    #[test]
+    fn topo_order_reports_cycle() {
+        let mut pdg = ProgramDependenceGraph::new();
+        let a = pdg.add_node(make_node("f:a", "a", "f.rs", NodeType::Function));
+        let b = pdg.add_node(make_node("f:b", "b", "f.rs", NodeType::Function));
+        let c = pdg.add_node(make_node("f:c", "c", "f.rs", NodeType::Function));
+        let d = pdg.add_node(make_node("f:d", "d", "f.rs", NodeType::Function));
+        pdg.add_call_edges(vec![(d, a), (a, b), (b, c), (c, a)]);
+
+        let filter = EdgeFilter::types(&[EdgeType::Call]);
+        assert_eq!(pdg.find_cycles(&filter), vec![vec![a, b, c]]);
+
+        let err = pdg.topo_order(&filter).unwrap_err();
+        let mut cycle = err.cycle.clone();
+        cycle.sort();
+        assert_eq!(cycle, vec!["f:a", "f:b", "f:c"]);
+        assert!(err.to_string().contains("f:a"));
+    }
+
+    #[test]
+    fn degree_stats_on_star_graph() {
+        let mut pdg = ProgramDependenceGraph::new();
+        let hub = pdg.add_node(make_node("f:hub", "hub", "f.rs", NodeType::Function));
+        let callers: Vec<NodeId> = (0..5)
+            .map(|i| {
+                pdg.add_node(make_node(
+                    &format!("f:caller{i}"),
+                    &format!("caller{i}"),
+                    "f.rs",
+                    NodeType::Function,
+                ))
+            })
+            .collect();
+        pdg.add_call_edges(callers.iter().map(|&c| (c, hub)).collect());
+        let module = pdg.add_node(make_node("mod:f", "f", "f.rs", NodeType::Module));
+        pdg.add_import_edges(vec![(module, hub)]);
+        pdg.add_containment_edges(vec![(module, callers[0])]);
+
+        let stats = pdg.degree_stats(hub).unwrap();
+        assert_eq!(stats.incoming.call, callers.len());
+        assert_eq!(stats.incoming.import, 1);
+        assert_eq!(stats.fan_in(), callers.len() + 1);
+        assert_eq!(stats.fan_out(), 0);
+
+        // Containment is reported but not counted as coupling
+        let first = pdg.degree_stats(callers[0]).unwrap();
+        assert_eq!(first.incoming.containment, 1);
+        assert_eq!(first.fan_in(), 0);
+        assert_eq!(first.fan_out(), 1);
+
+        let top_in = pdg.top_by_fan_in(1);
+        assert_eq!(top_in[0].0, hub);
+        let top_out: Vec<NodeId> = pdg
+            .top_by_fan_out(2)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(top_out, vec![callers[0], callers[1]]);
+    }
+
     #[test]
     fn traversal_filters_containment_edges() {
         let mut pdg = ProgramDependenceGraph::new();
@@ -1924,6 +2664,71 @@ mod tests {
         assert_eq!(restored.node_count(), 1);
     }
 
+    #[test]
+    fn serialization_is_independent_of_insertion_order() {
+        use crate::parse::prelude::{SignatureInfo, Visibility};
+
+        let sig = |name: &str, calls: &[&str]| SignatureInfo {
+            name: name.to_string(),
+            qualified_name: name.to_string(),
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+            is_method: false,
+            docstring: None,
+            calls: calls.iter().map(|c| c.to_string()).collect(),
+            imports: vec![],
+            byte_range: (0, 10),
+            cyclomatic_complexity: 1,
+            base_types: Vec::new(),
+        };
+        let fixture = [
+            ("a.rs", vec![sig("main", &["helper"]), sig("helper", &[])]),
+            ("b.rs", vec![sig("parse", &["lex"]), sig("lex", &[])]),
+        ];
+
+        // Index the fixture twice, visiting files in a different order each
+        // time (as parallel parsing may), and merge into one graph.
+        let index = |order: &[usize]| {
+            let mut pdg = ProgramDependenceGraph::new();
+            for &i in order {
+                let (file, sigs) = &fixture[i];
+                let file_pdg =
+                    crate::graph::extract_pdg_from_signatures(sigs.clone(), b"", file, "rust");
+                let mut mapping = HashMap::new();
+                for id in file_pdg.node_indices() {
+                    let node = file_pdg.get_node(id).unwrap().clone();
+                    mapping.insert(id, pdg.add_node(node));
+                }
+                for e in file_pdg.edge_indices() {
+                    let (from, to) = file_pdg.edge_endpoints(e).unwrap();
+                    let edge = file_pdg.get_edge(e).unwrap().clone();
+                    pdg.add_edge(mapping[&from], mapping[&to], edge);
+                }
+            }
+            pdg
+        };
+        let first = index(&[0, 1]);
+        let second = index(&[1, 0]);
+
+        let json = first.to_json().unwrap();
+        assert_eq!(json, second.to_json().unwrap());
+        assert_eq!(first.serialize().unwrap(), second.serialize().unwrap());
+
+        let restored = ProgramDependenceGraph::deserialize(&second.serialize().unwrap()).unwrap();
+        assert_eq!(restored.node_count(), first.node_count());
+        assert_eq!(restored.edge_count(), first.edge_count());
+        assert!(restored.find_by_name("lex").is_some());
+        assert_eq!(
+            ProgramDependenceGraph::from_json(&json)
+                .unwrap()
+                .to_json()
+                .unwrap(),
+            json
+        );
+    }
+
     #[test]
     fn bulk_import_edges_helper() {
         let mut pdg = ProgramDependenceGraph::new();
@@ -1,8 +1,10 @@
 // Gravity-based traversal algorithm
 
-use crate::graph::pdg::{NodeId, ProgramDependenceGraph};
+use crate::graph::pdg::{EdgeType, NodeId, ProgramDependenceGraph};
+use crate::tokenizer::{Tokenizer, TokenizerKind};
 use serde::{Deserialize, Serialize};
 use std::collections::{BinaryHeap, HashSet};
+use std::sync::Arc;
 
 /// Configuration for gravity traversal
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,18 @@ pub struct TraversalConfig {
 
     /// Weight for complexity
     pub complexity_weight: f64,
+
+    /// Maximum hop distance from an entry node (`None` = unlimited)
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Maximum number of nodes collected (`None` = unlimited)
+    #[serde(default)]
+    pub max_nodes: Option<usize>,
+
+    /// Only follow edges of these types (`None` = all edge types)
+    #[serde(default)]
+    pub allowed_edge_types: Option<Vec<EdgeType>>,
 }
 
 impl Default for TraversalConfig {
@@ -27,29 +41,63 @@ impl Default for TraversalConfig {
             distance_decay: 2.0,
             semantic_weight: 1.0,
             complexity_weight: 0.5,
+            max_depth: None,
+            max_nodes: None,
+            allowed_edge_types: None,
         }
     }
 }
 
+/// Nodes gathered by a gravity traversal
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraversalResult {
+    /// Collected nodes in expansion order
+    pub nodes: Vec<NodeId>,
+
+    /// True if `max_depth` or `max_nodes` stopped expansion while reachable
+    /// nodes remained
+    pub truncated: bool,
+}
+
 /// Gravity-based context traversal
 ///
 /// Uses a priority-weighted expansion based on the formula:
 /// Relevance(N) = (SemanticScore(N) * Complexity(N)) / (Distance(Entry, N)^2)
 pub struct GravityTraversal {
     config: TraversalConfig,
+    token_counter: Option<TokenCounter>,
+    tokenizer: Arc<dyn Tokenizer>,
 }
 
+/// Counts the tokens a node contributes to the expanded context
+pub type TokenCounter = Arc<dyn Fn(&crate::graph::pdg::Node) -> usize + Send + Sync>;
+
 impl GravityTraversal {
     /// Create a new gravity traversal with default config
     pub fn new() -> Self {
-        Self {
-            config: TraversalConfig::default(),
-        }
+        Self::with_config(TraversalConfig::default())
     }
 
     /// Create with custom config
     pub fn with_config(config: TraversalConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            token_counter: None,
+            tokenizer: TokenizerKind::default().build(),
+        }
+    }
+
+    /// Estimate node sizes with `tokenizer` when no token counter is set
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// Charge nodes against `max_tokens` using `counter` instead of the
+    /// tokenizer's byte-range estimate
+    pub fn with_token_counter(mut self, counter: TokenCounter) -> Self {
+        self.token_counter = Some(counter);
+        self
     }
 
     /// Expand context from entry nodes within token budget
@@ -58,10 +106,21 @@ impl GravityTraversal {
         pdg: &ProgramDependenceGraph,
         entry_nodes: Vec<NodeId>,
     ) -> Vec<NodeId> {
+        self.expand_context_bounded(pdg, entry_nodes).nodes
+    }
+
+    /// Expand context like [`expand_context`](Self::expand_context), also
+    /// reporting whether the depth or node cap cut the traversal short
+    pub fn expand_context_bounded(
+        &self,
+        pdg: &ProgramDependenceGraph,
+        entry_nodes: Vec<NodeId>,
+    ) -> TraversalResult {
         let mut pq = BinaryHeap::new();
         let mut visited = std::collections::HashSet::new();
         let mut context = Vec::new();
         let mut current_tokens = 0;
+        let mut truncated = false;
 
         // Initialize with entry nodes
         for &entry in &entry_nodes {
@@ -81,6 +140,15 @@ impl GravityTraversal {
                 continue;
             }
 
+            if self
+                .config
+                .max_nodes
+                .is_some_and(|max_nodes| context.len() >= max_nodes)
+            {
+                truncated = true;
+                break;
+            }
+
             if let Some(node) = pdg.get_node(wnode.id) {
                 let estimated_tokens = self.estimate_tokens(node);
 
@@ -102,7 +170,8 @@ impl GravityTraversal {
                     }
                     // Skip this node but continue trying others
                     visited.insert(wnode.id);
-                    self.enqueue_neighbors(pdg, &mut pq, &visited, wnode.id, wnode.distance);
+                    truncated |=
+                        self.enqueue_neighbors(pdg, &mut pq, &visited, wnode.id, wnode.distance);
                     continue;
                 }
 
@@ -111,13 +180,18 @@ impl GravityTraversal {
                 current_tokens += estimated_tokens;
 
                 // Add neighbors with decayed weight
-                self.enqueue_neighbors(pdg, &mut pq, &visited, wnode.id, wnode.distance);
+                truncated |=
+                    self.enqueue_neighbors(pdg, &mut pq, &visited, wnode.id, wnode.distance);
             }
         }
 
-        context
+        TraversalResult {
+            nodes: context,
+            truncated,
+        }
     }
 
+    /// Queue unvisited neighbors; returns true if `max_depth` held any back
     fn enqueue_neighbors(
         &self,
         pdg: &ProgramDependenceGraph,
@@ -125,8 +199,17 @@ impl GravityTraversal {
         visited: &HashSet<NodeId>,
         node_id: NodeId,
         distance: usize,
-    ) {
-        for neighbor in self.get_neighbors(pdg, node_id) {
+    ) -> bool {
+        let neighbors = self.get_neighbors(pdg, node_id);
+        if self
+            .config
+            .max_depth
+            .is_some_and(|max_depth| distance >= max_depth)
+        {
+            return neighbors.iter().any(|neighbor| !visited.contains(neighbor));
+        }
+
+        for neighbor in neighbors {
             if !visited.contains(&neighbor) {
                 let new_distance = distance + 1;
                 if let Some(nnode) = pdg.get_node(neighbor) {
@@ -140,6 +223,7 @@ impl GravityTraversal {
                 }
             }
         }
+        false
     }
 
     /// Calculate relevance score for a node
@@ -158,14 +242,17 @@ impl GravityTraversal {
 
     /// Estimate token count for a node
     fn estimate_tokens(&self, node: &crate::graph::pdg::Node) -> usize {
+        if let Some(counter) = &self.token_counter {
+            return counter(node);
+        }
         let range = node.byte_range.1.saturating_sub(node.byte_range.0);
-        // Rough estimate: ~4 characters per token. Ensure at least 10 tokens per node.
-        (range / 4).max(10)
+        // Only the byte range is known here. Ensure at least 10 tokens per node.
+        self.tokenizer.estimate_tokens_for_bytes(range).max(10)
     }
 
-    /// Get neighboring nodes
+    /// Get neighboring nodes reachable through the allowed edge types
     fn get_neighbors(&self, pdg: &ProgramDependenceGraph, node_id: NodeId) -> Vec<NodeId> {
-        pdg.neighbors(node_id)
+        pdg.neighbors_with_types(node_id, self.config.allowed_edge_types.as_deref())
     }
 }
 
@@ -224,4 +311,131 @@ mod tests {
         let result = traversal.expand_context(&pdg, vec![]);
         assert_eq!(result.len(), 0);
     }
+
+    fn add_test_node(pdg: &mut ProgramDependenceGraph, name: String) -> NodeId {
+        pdg.add_node(crate::graph::pdg::Node {
+            id: format!("big.rs:{}", name),
+            node_type: crate::graph::pdg::NodeType::Function,
+            name,
+            file_path: std::sync::Arc::from("big.rs"),
+            byte_range: (0, 40),
+            complexity: 1,
+            language: "rust".to_string(),
+        })
+    }
+
+    fn edge(edge_type: EdgeType) -> crate::graph::pdg::Edge {
+        crate::graph::pdg::Edge {
+            edge_type,
+            metadata: crate::graph::pdg::EdgeMetadata::empty(),
+        }
+    }
+
+    /// Chain `0 -> 1 -> ... -> n-1`, each node also calling a leaf
+    fn dense_graph(n: usize) -> (ProgramDependenceGraph, Vec<NodeId>) {
+        let mut pdg = ProgramDependenceGraph::new();
+        let chain: Vec<NodeId> = (0..n)
+            .map(|i| add_test_node(&mut pdg, format!("f{}", i)))
+            .collect();
+        let leaves: Vec<NodeId> = (0..n)
+            .map(|i| add_test_node(&mut pdg, format!("leaf{}", i)))
+            .collect();
+
+        for i in 0..n {
+            if i + 1 < n {
+                pdg.add_edge(chain[i], chain[i + 1], edge(EdgeType::Call));
+            }
+            pdg.add_edge(chain[i], leaves[i], edge(EdgeType::Call));
+        }
+        (pdg, chain)
+    }
+
+    #[test]
+    fn test_traversal_follows_only_allowed_edge_types() {
+        let mut pdg = ProgramDependenceGraph::new();
+        let entry = add_test_node(&mut pdg, "entry".to_string());
+        let callee = add_test_node(&mut pdg, "callee".to_string());
+        let nested = add_test_node(&mut pdg, "nested".to_string());
+        let data = add_test_node(&mut pdg, "data".to_string());
+        pdg.add_edge(entry, callee, edge(EdgeType::Call));
+        pdg.add_edge(callee, nested, edge(EdgeType::Call));
+        pdg.add_edge(entry, data, edge(EdgeType::DataDependency));
+
+        assert_eq!(
+            pdg.neighbors_with_types(entry, Some(&[EdgeType::Call])),
+            vec![callee]
+        );
+        assert_eq!(pdg.neighbors_with_types(entry, None).len(), 2);
+
+        let call_only = GravityTraversal::with_config(TraversalConfig {
+            allowed_edge_types: Some(vec![EdgeType::Call]),
+            ..TraversalConfig::default()
+        });
+        let nodes = call_only.expand_context(&pdg, vec![entry]);
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes.contains(&nested));
+        assert!(!nodes.contains(&data));
+
+        // Default config still follows every edge type
+        let nodes = GravityTraversal::new().expand_context(&pdg, vec![entry]);
+        assert!(nodes.contains(&data));
+    }
+
+    #[test]
+    fn test_traversal_stops_at_node_cap() {
+        let (pdg, chain) = dense_graph(2000);
+        let traversal = GravityTraversal::with_config(TraversalConfig {
+            max_tokens: 1_000_000,
+            max_nodes: Some(25),
+            ..TraversalConfig::default()
+        });
+
+        let result = traversal.expand_context_bounded(&pdg, vec![chain[0]]);
+        assert_eq!(result.nodes.len(), 25);
+        assert_eq!(result.nodes[0], chain[0]);
+        assert!(result.truncated);
+
+        // Without a cap the whole graph is reachable
+        let unbounded = GravityTraversal::with_config(TraversalConfig {
+            max_tokens: 1_000_000,
+            ..TraversalConfig::default()
+        })
+        .expand_context_bounded(&pdg, vec![chain[0]]);
+        assert_eq!(unbounded.nodes.len(), 4000);
+        assert!(!unbounded.truncated);
+    }
+
+    #[test]
+    fn test_traversal_respects_max_depth() {
+        let (pdg, chain) = dense_graph(50);
+        let traversal = GravityTraversal::with_config(TraversalConfig {
+            max_tokens: 1_000_000,
+            max_depth: Some(2),
+            ..TraversalConfig::default()
+        });
+
+        let result = traversal.expand_context_bounded(&pdg, vec![chain[0]]);
+        // f0, then f1 + leaf0, then f2 + leaf1
+        assert_eq!(result.nodes.len(), 5);
+        assert!(result.nodes.contains(&chain[2]));
+        assert!(!result.nodes.contains(&chain[3]));
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_traversal_budgets_with_configured_tokenizer() {
+        // 40-byte nodes: 14 subword tokens each, 10 at four bytes per token
+        let (pdg, chain) = dense_graph(10);
+        let config = TraversalConfig {
+            max_tokens: 150,
+            ..TraversalConfig::default()
+        };
+
+        let subword = GravityTraversal::with_config(config.clone());
+        assert_eq!(subword.expand_context(&pdg, vec![chain[0]]).len(), 10);
+
+        let char_estimate = GravityTraversal::with_config(config)
+            .with_tokenizer(TokenizerKind::CharEstimate.build());
+        assert_eq!(char_estimate.expand_context(&pdg, vec![chain[0]]).len(), 15);
+    }
 }
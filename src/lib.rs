@@ -46,6 +46,10 @@ pub mod storage;
 #[cfg(feature = "search")]
 pub mod search;
 
+// Token budget accounting, shared by graph traversal, search and phase output
+/// Token counting for token-budget accounting.
+pub mod tokenizer;
+
 // Extended modules
 #[cfg(feature = "phase")]
 pub mod phase;
@@ -59,6 +59,7 @@ impl CParser {
                                 imports: vec![],
                                 byte_range: (node.start_byte(), node.end_byte()),
                                 cyclomatic_complexity: 0,
+                                base_types: Vec::new(),
                             });
                         }
                     }
@@ -212,6 +213,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -116,6 +116,7 @@ mod tests {
             },
             byte_range: if with_range { (1, 3) } else { (0, 0) },
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         }
     }
 
@@ -127,6 +128,9 @@ mod tests {
             signatures: vec![signature(true, false, true), signature(false, true, false)],
             error: None,
             parse_time_ms: 1,
+            parse_duration: std::time::Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
             source_bytes: None,
         }];
 
@@ -62,6 +62,7 @@ impl CppParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -91,6 +92,7 @@ impl CppParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -118,6 +120,7 @@ impl CppParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -264,6 +267,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -66,6 +66,7 @@ impl CSharpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -98,6 +99,7 @@ impl CSharpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -125,6 +127,7 @@ impl CSharpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -152,6 +155,7 @@ impl CSharpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -351,6 +355,7 @@ fn extract_method_signature(
         imports: vec![],
         byte_range: (0, 0),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
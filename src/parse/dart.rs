@@ -142,6 +142,7 @@ fn visit_dart(
                     imports: Vec::new(),
                     byte_range: (node.start_byte(), node.end_byte()),
                     cyclomatic_complexity: 0,
+                    base_types: Vec::new(),
                 });
             }
         }
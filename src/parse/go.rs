@@ -85,6 +85,7 @@ impl GoParser {
                                         imports: vec![],
                                         byte_range: (child.start_byte(), child.end_byte()),
                                         cyclomatic_complexity: 0,
+                                        base_types: Vec::new(),
                                     });
                                 }
                             }
@@ -256,6 +257,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -315,6 +317,7 @@ fn extract_method_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
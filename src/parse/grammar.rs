@@ -161,6 +161,8 @@ pub enum LanguageId {
     Bash = 13,
     /// JSON data format
     Json = 14,
+    /// Objective-C programming language
+    ObjectiveC = 15,
 }
 
 impl LanguageId {
@@ -179,6 +181,7 @@ impl LanguageId {
             "cpp" | "cc" | "cxx" | "hpp" => Some(LanguageId::Cpp),
             "h" => Some(LanguageId::C), // Default .h to C (Cpp override handles .hpp)
             "c" => Some(LanguageId::C),
+            "m" => Some(LanguageId::ObjectiveC),
             "cs" => Some(LanguageId::CSharp),
             "rb" => Some(LanguageId::Ruby),
             "php" => Some(LanguageId::Php),
@@ -192,6 +195,19 @@ impl LanguageId {
         }
     }
 
+    /// Get the LanguageId for a file extension, using its contents to
+    /// disambiguate C-family headers
+    ///
+    /// `.h` files are shared by C, C++ and Objective-C; see
+    /// [`detect_header_language`]. Other extensions map as in `from_extension`.
+    pub fn from_extension_and_source(ext: &str, source: &[u8]) -> Option<Self> {
+        if ext.eq_ignore_ascii_case("h") {
+            Some(detect_header_language(source))
+        } else {
+            Self::from_extension(ext)
+        }
+    }
+
     /// Get the LanguageConfig for this language
     ///
     /// Provides access to the full language configuration including
@@ -213,6 +229,7 @@ impl LanguageId {
             LanguageId::C => &crate::parse::traits::languages::c::CONFIG,
             LanguageId::Bash => &crate::parse::traits::languages::bash::CONFIG,
             LanguageId::Json => &crate::parse::traits::languages::json::CONFIG,
+            LanguageId::ObjectiveC => &crate::parse::traits::languages::objc::CONFIG,
         }
     }
 
@@ -237,6 +254,7 @@ impl LanguageId {
             LanguageId::C => crate::parse::traits::languages::c::language(),
             LanguageId::Bash => crate::parse::traits::languages::bash::language(),
             LanguageId::Json => crate::parse::traits::languages::json::language(),
+            LanguageId::ObjectiveC => crate::parse::traits::languages::objc::language(),
         }
     }
 
@@ -249,6 +267,57 @@ impl LanguageId {
     }
 }
 
+/// Detect whether a `.h` header is C, C++ or Objective-C
+///
+/// Looks for Objective-C directives (`@interface`, `#import`, ...) first, then
+/// C++-only constructs (`class`, `namespace`, `template`, access specifiers,
+/// `std::`). Anything else is treated as C. `extern "C"` guards are common in
+/// C headers and are deliberately not treated as C++ markers.
+pub fn detect_header_language(source: &[u8]) -> LanguageId {
+    let sample = &source[..source.len().min(64 * 1024)];
+    let text = String::from_utf8_lossy(sample);
+
+    let mut is_cpp = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if [
+            "@interface",
+            "@implementation",
+            "@protocol",
+            "@end",
+            "#import",
+            "@import",
+        ]
+        .iter()
+        .any(|marker| trimmed.starts_with(marker))
+        {
+            return LanguageId::ObjectiveC;
+        }
+        if [
+            "class ",
+            "namespace ",
+            "template<",
+            "template <",
+            "using namespace",
+        ]
+        .iter()
+        .any(|marker| trimmed.starts_with(marker))
+            || ["public:", "private:", "protected:"]
+                .iter()
+                .any(|marker| trimmed.starts_with(marker))
+            || trimmed.contains("std::")
+        {
+            is_cpp = true;
+        }
+    }
+
+    if is_cpp {
+        LanguageId::Cpp
+    } else {
+        LanguageId::C
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +361,36 @@ mod tests {
         assert_eq!(LanguageId::from_extension("unknown"), None);
     }
 
+    #[test]
+    fn test_header_language_detection() {
+        assert_eq!(
+            LanguageId::from_extension_and_source("h", b"int add(int a, int b);\n"),
+            Some(LanguageId::C)
+        );
+        assert_eq!(
+            LanguageId::from_extension_and_source(
+                "h",
+                b"#ifdef __cplusplus\nextern \"C\" {\n#endif\nvoid f(void);\n"
+            ),
+            Some(LanguageId::C)
+        );
+        assert_eq!(
+            LanguageId::from_extension_and_source("h", b"namespace util {\nclass Foo {};\n}\n"),
+            Some(LanguageId::Cpp)
+        );
+        assert_eq!(
+            LanguageId::from_extension_and_source(
+                "h",
+                b"#import <Foundation/Foundation.h>\n@interface Foo : NSObject\n@end\n"
+            ),
+            Some(LanguageId::ObjectiveC)
+        );
+        assert_eq!(
+            LanguageId::from_extension_and_source("m", b""),
+            Some(LanguageId::ObjectiveC)
+        );
+    }
+
     #[test]
     fn test_language_id_case_insensitive() {
         assert_eq!(LanguageId::from_extension("PY"), Some(LanguageId::Python));
@@ -71,6 +71,7 @@ impl JavaParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: extract_java_bases(node, source),
                         });
                     }
 
@@ -104,6 +105,7 @@ impl JavaParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: extract_java_bases(node, source),
                         });
                     }
 
@@ -137,6 +139,7 @@ impl JavaParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -168,6 +171,7 @@ impl JavaParser {
                                     imports: vec![],
                                     byte_range: (0, 0),
                                     cyclomatic_complexity: 0,
+                                    base_types: Vec::new(),
                                 });
                             }
                         }
@@ -329,6 +333,7 @@ fn extract_method_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -368,9 +373,44 @@ fn extract_constructor_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
+/// Extract the types named in `extends` and `implements` clauses
+///
+/// Generic arguments are dropped, so `implements List<String>` yields `List`.
+fn extract_java_bases(node: &tree_sitter::Node<'_>, source: &[u8]) -> Vec<String> {
+    fn push_type(ty: tree_sitter::Node<'_>, source: &[u8], bases: &mut Vec<String>) {
+        if ty.kind() == "type_list" {
+            let mut cursor = ty.walk();
+            for child in ty.named_children(&mut cursor) {
+                push_type(child, source, bases);
+            }
+        } else if let Ok(text) = ty.utf8_text(source) {
+            let name = text.split('<').next().unwrap_or(text).trim();
+            if !name.is_empty() {
+                bases.push(name.to_string());
+            }
+        }
+    }
+
+    let mut bases = Vec::new();
+    let mut cursor = node.walk();
+    for clause in node.children(&mut cursor) {
+        if matches!(
+            clause.kind(),
+            "superclass" | "super_interfaces" | "extends_interfaces"
+        ) {
+            let mut clause_cursor = clause.walk();
+            for ty in clause.named_children(&mut clause_cursor) {
+                push_type(ty, source, &mut bases);
+            }
+        }
+    }
+    bases
+}
+
 /// Extract function calls from a Java node
 fn extract_java_calls(node: &tree_sitter::Node<'_>, source: &[u8]) -> Vec<String> {
     let mut calls = Vec::new();
@@ -805,6 +845,25 @@ mod tests {
         assert!(person.is_some());
     }
 
+    #[test]
+    fn test_java_extends_and_implements_captured() {
+        let source = b"public class Dog extends Animal implements Runnable, Comparable<Dog> {
+    public void run() {}
+}
+
+interface Walker extends Mover {
+    void walk();
+}";
+
+        let signatures = JavaParser::new().get_signatures(source).unwrap();
+
+        let dog = signatures.iter().find(|s| s.name == "Dog").unwrap();
+        assert_eq!(dog.base_types, vec!["Animal", "Runnable", "Comparable"]);
+
+        let walker = signatures.iter().find(|s| s.name == "Walker").unwrap();
+        assert_eq!(walker.base_types, vec!["Mover"]);
+    }
+
     #[test]
     fn test_java_interface_extraction() {
         let source = b"public interface Runnable {
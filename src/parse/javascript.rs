@@ -73,6 +73,7 @@ impl JavaScriptParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
 
                         let mut cursor = node.walk();
@@ -266,6 +267,7 @@ impl TypeScriptParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -301,6 +303,7 @@ impl TypeScriptParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
 
                         let mut cursor = node.walk();
@@ -546,6 +549,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -596,6 +600,7 @@ fn extract_ts_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -147,6 +147,7 @@ fn visit_kotlin(
                     imports: Vec::new(),
                     byte_range: (node.start_byte(), node.end_byte()),
                     cyclomatic_complexity: 0,
+                    base_types: Vec::new(),
                 });
             }
         }
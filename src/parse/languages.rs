@@ -9,6 +9,7 @@ pub use crate::parse::java::JavaParser;
 pub use crate::parse::javascript::{JavaScriptParser, TypeScriptParser};
 pub use crate::parse::json::JsonParser;
 pub use crate::parse::lua::LuaParser;
+pub use crate::parse::objc::ObjCParser;
 pub use crate::parse::php::PhpParser;
 pub use crate::parse::python::PythonParser;
 pub use crate::parse::ruby::RubyParser;
@@ -33,6 +34,7 @@ pub fn parser_for_language(
         "lua" => Some(Box::new(LuaParser::new())),
         "scala" => Some(Box::new(ScalaParser::new())),
         "c" => Some(Box::new(CParser::new())),
+        "objective-c" | "objc" => Some(Box::new(ObjCParser::new())),
         "bash" | "sh" => Some(Box::new(BashParser::new())),
         "json" => Some(Box::new(JsonParser::new())),
         _ => None,
@@ -114,6 +114,7 @@ fn visit(
                     imports: vec![],
                     byte_range: (0, 0),
                     cyclomatic_complexity: 0,
+                    base_types: Vec::new(),
                 });
             }
         }
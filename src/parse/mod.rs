@@ -66,6 +66,9 @@ pub mod scala;
 /// C language implementation.
 pub mod c;
 
+/// Objective-C language implementation.
+pub mod objc;
+
 /// Bash language implementation.
 pub mod bash;
 
@@ -87,6 +90,12 @@ mod debug_rust;
 /// Parser completeness scoring helpers.
 pub mod completeness;
 
+/// Ad-hoc tree-sitter queries over source code.
+pub mod query;
+
+/// Byte offset to line/column conversion.
+pub mod position;
+
 /// Re-exports of commonly used types.
 pub mod prelude;
 
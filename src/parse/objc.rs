@@ -0,0 +1,367 @@
+// Objective-C language parser implementation
+//
+// There is no Objective-C grammar in the tree-sitter dependency set, so this
+// parser combines two passes:
+// - `@interface`/`@implementation`/`@protocol` blocks are scanned directly for
+//   method declarations and definitions.
+// - Those blocks (and `#import` lines) are blanked out, preserving byte
+//   offsets, and the remaining plain C is parsed with the C grammar.
+
+use crate::parse::c::CParser;
+use crate::parse::traits::{
+    Block, CodeIntelligence, ComplexityMetrics, Edge, Graph, ImportInfo, Result, SignatureInfo,
+    Visibility,
+};
+use std::borrow::Cow;
+use tree_sitter::Parser;
+
+/// Objective-C parser with CodeIntelligence implementation
+pub struct ObjCParser {
+    c: CParser,
+}
+
+impl Default for ObjCParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ObjCParser {
+    /// Create a new Objective-C parser
+    pub fn new() -> Self {
+        Self { c: CParser::new() }
+    }
+}
+
+impl CodeIntelligence for ObjCParser {
+    fn get_signatures(&self, source: &[u8]) -> Result<Vec<SignatureInfo>> {
+        let mut parser = Parser::new();
+        self.get_signatures_with_parser(source, &mut parser)
+    }
+
+    fn get_signatures_with_parser(
+        &self,
+        source: &[u8],
+        parser: &mut tree_sitter::Parser,
+    ) -> Result<Vec<SignatureInfo>> {
+        let text = text_with_byte_offsets(source);
+        let blocks = find_objc_blocks(&text);
+
+        let masked = mask_ranges(source, &blocks, &import_line_ranges(&text));
+        let mut signatures = self.c.get_signatures_with_parser(&masked, parser)?;
+
+        for block in &blocks {
+            for method in extract_methods(&text, block) {
+                if let Some(existing) = signatures
+                    .iter_mut()
+                    .find(|sig| sig.qualified_name == method.qualified_name)
+                {
+                    // Prefer the @implementation definition over the declaration
+                    if block.is_implementation {
+                        *existing = method;
+                    }
+                } else {
+                    signatures.push(method);
+                }
+            }
+        }
+
+        let imports = extract_objc_imports(&text);
+        for sig in &mut signatures {
+            sig.imports = imports.clone();
+        }
+
+        signatures.sort_by_key(|sig| sig.byte_range.0);
+        Ok(signatures)
+    }
+
+    fn compute_cfg(&self, _source: &[u8], _node_id: usize) -> Result<Graph<Block, Edge>> {
+        // CFG computation for Objective-C not yet implemented
+        Ok(Graph {
+            blocks: vec![],
+            edges: vec![],
+            entry_block: 0,
+            exit_blocks: vec![],
+        })
+    }
+
+    fn extract_complexity(&self, node: &tree_sitter::Node<'_>) -> ComplexityMetrics {
+        self.c.extract_complexity(node)
+    }
+}
+
+/// `@interface`/`@implementation`/`@protocol` ... `@end` block
+#[derive(Debug, Clone)]
+struct ObjCBlock {
+    /// Class or protocol name
+    name: String,
+    /// Whether this block holds method definitions
+    is_implementation: bool,
+    /// Byte offset of the opening directive
+    start: usize,
+    /// Byte offset just past `@end`
+    end: usize,
+}
+
+/// View `source` as text whose byte offsets match the raw source
+///
+/// Unlike `String::from_utf8_lossy`, which widens each invalid byte into a
+/// three-byte U+FFFD, invalid bytes are replaced one for one with `?`, so
+/// offsets found in the text are valid in `source`.
+fn text_with_byte_offsets(source: &[u8]) -> Cow<'_, str> {
+    let mut invalid = match std::str::from_utf8(source) {
+        Ok(text) => return Cow::Borrowed(text),
+        Err(err) => err,
+    };
+    let mut bytes = source.to_vec();
+    let mut pos = 0;
+    loop {
+        let start = pos + invalid.valid_up_to();
+        let end = invalid.error_len().map_or(bytes.len(), |len| start + len);
+        bytes[start..end].fill(b'?');
+        pos = end;
+        match std::str::from_utf8(&bytes[pos..]) {
+            Ok(_) => break,
+            Err(err) => invalid = err,
+        }
+    }
+    Cow::Owned(String::from_utf8(bytes).expect("invalid UTF-8 bytes were replaced"))
+}
+
+/// Iterate over lines with their starting byte offsets
+fn lines_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+    text.split_inclusive('\n').map(move |line| {
+        let start = offset;
+        offset += line.len();
+        (start, line)
+    })
+}
+
+fn find_objc_blocks(text: &str) -> Vec<ObjCBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, bool, usize)> = None;
+
+    for (offset, line) in lines_with_offsets(text) {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if open.is_none() {
+            let directive = ["@interface", "@implementation", "@protocol"]
+                .into_iter()
+                .find(|d| trimmed.starts_with(d));
+            if let Some(directive) = directive {
+                let rest = trimmed[directive.len()..].trim();
+                // `@protocol Foo;` is a forward declaration, not a block
+                if directive == "@protocol" && rest.trim_end().ends_with(';') {
+                    continue;
+                }
+                let name: String = rest
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                open = Some((name, directive == "@implementation", offset + indent));
+            }
+        } else if trimmed.starts_with("@end") {
+            if let Some((name, is_implementation, start)) = open.take() {
+                blocks.push(ObjCBlock {
+                    name,
+                    is_implementation,
+                    start,
+                    end: offset + indent + "@end".len(),
+                });
+            }
+        }
+    }
+
+    blocks
+}
+
+fn import_line_ranges(text: &str) -> Vec<(usize, usize)> {
+    lines_with_offsets(text)
+        .filter(|(_, line)| {
+            let trimmed = line.trim_start();
+            trimmed.starts_with("#import") || trimmed.starts_with("@import")
+        })
+        .map(|(offset, line)| (offset, offset + line.trim_end().len()))
+        .collect()
+}
+
+/// Replace the given ranges with spaces, keeping newlines and byte offsets intact
+fn mask_ranges(source: &[u8], blocks: &[ObjCBlock], extra: &[(usize, usize)]) -> Vec<u8> {
+    let mut masked = source.to_vec();
+    let ranges = blocks
+        .iter()
+        .map(|b| (b.start, b.end))
+        .chain(extra.iter().copied());
+    for (start, end) in ranges {
+        for byte in masked.iter_mut().take(end.min(source.len())).skip(start) {
+            if *byte != b'\n' && *byte != b'\r' {
+                *byte = b' ';
+            }
+        }
+    }
+    masked
+}
+
+fn extract_methods(text: &str, block: &ObjCBlock) -> Vec<SignatureInfo> {
+    let mut methods = Vec::new();
+    let body = &text[block.start..block.end];
+    let mut search_from = 0;
+
+    for (offset, line) in lines_with_offsets(body) {
+        if offset < search_from {
+            continue;
+        }
+        let trimmed = line.trim_start();
+        if !(trimmed.starts_with('-') || trimmed.starts_with('+')) {
+            continue;
+        }
+
+        let method_start = offset + (line.len() - trimmed.len());
+        let header_end = match body[method_start..].find(['{', ';']) {
+            Some(pos) => method_start + pos,
+            None => continue,
+        };
+        let header = &body[method_start..header_end];
+        let Some((selector, parameters, return_type)) = parse_method_header(header) else {
+            continue;
+        };
+
+        let method_end = if body[header_end..].starts_with('{') {
+            matching_brace(body, header_end).unwrap_or(header_end + 1)
+        } else {
+            header_end + 1
+        };
+        search_from = method_end;
+
+        methods.push(SignatureInfo {
+            name: selector.clone(),
+            qualified_name: if block.name.is_empty() {
+                selector
+            } else {
+                format!("{}.{}", block.name, selector)
+            },
+            parameters,
+            return_type,
+            visibility: Visibility::Public,
+            is_async: false,
+            is_method: true,
+            docstring: None,
+            calls: vec![],
+            imports: vec![],
+            byte_range: (block.start + method_start, block.start + method_end),
+            cyclomatic_complexity: 0,
+            base_types: Vec::new(),
+        });
+    }
+
+    methods
+}
+
+/// Parse `- (Type)label:(Type)arg other:(Type)arg2` into selector, params and return type
+fn parse_method_header(header: &str) -> Option<(String, Vec<String>, Option<String>)> {
+    let mut rest = header.trim()[1..].trim_start();
+
+    let mut return_type = None;
+    if rest.starts_with('(') {
+        let close = rest.find(')')?;
+        return_type = Some(rest[1..close].trim().to_string());
+        rest = rest[close + 1..].trim_start();
+    }
+
+    let mut selector = String::new();
+    let mut parameters = Vec::new();
+
+    loop {
+        let label: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        rest = rest[label.len()..].trim_start();
+
+        if !rest.starts_with(':') {
+            if selector.is_empty() {
+                if label.is_empty() {
+                    return None;
+                }
+                selector = label;
+            }
+            break;
+        }
+
+        selector.push_str(&label);
+        selector.push(':');
+        rest = rest[1..].trim_start();
+
+        if rest.starts_with('(') {
+            let close = rest.find(')')?;
+            rest = rest[close + 1..].trim_start();
+        }
+        let arg: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        rest = rest[arg.len()..].trim_start();
+        if !arg.is_empty() {
+            parameters.push(arg);
+        }
+        if rest.is_empty() {
+            break;
+        }
+    }
+
+    Some((selector, parameters, return_type))
+}
+
+/// Find the byte offset just past the brace matching the one at `open`
+fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, ch) in text[open..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return Some(open + i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn extract_objc_imports(text: &str) -> Vec<ImportInfo> {
+    let mut imports = Vec::new();
+
+    for (_, line) in lines_with_offsets(text) {
+        let trimmed = line.trim();
+        let path = if let Some(rest) = trimmed
+            .strip_prefix("#import")
+            .or_else(|| trimmed.strip_prefix("#include"))
+        {
+            rest.trim()
+                .trim_start_matches(['<', '"'])
+                .trim_end_matches(['>', '"'])
+                .trim()
+        } else if let Some(rest) = trimmed.strip_prefix("@import") {
+            rest.trim().trim_end_matches(';').trim()
+        } else {
+            continue;
+        };
+
+        if path.is_empty() {
+            continue;
+        }
+        imports.push(ImportInfo {
+            path: path.to_string(),
+            alias: path
+                .rsplit('/')
+                .next()
+                .map(|name| name.trim_end_matches(".h").to_string()),
+        });
+    }
+
+    imports
+}
@@ -5,11 +5,13 @@
 
 use crate::parse::grammar::LanguageId;
 use crate::parse::languages::parser_for_language;
-use crate::parse::traits::SignatureInfo;
+use crate::parse::traits::{CodeIntelligence, SignatureInfo};
 use rayon::prelude::*;
 use std::cell::RefCell;
-use std::path::PathBuf;
-use std::time::Instant;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tree_sitter::Parser;
 
 thread_local! {
@@ -36,8 +38,89 @@ pub struct ParsingResult {
     /// Parsing error (if any)
     pub error: Option<String>,
 
-    /// Time taken to parse this file (milliseconds)
+    /// Time taken to process this file, including I/O (milliseconds)
     pub parse_time_ms: u64,
+
+    /// Time spent in the tree-sitter parse and signature extraction only
+    pub parse_duration: Duration,
+
+    /// Name of the fallback grammar that produced this result
+    /// (None when the primary grammar succeeded or parsing failed)
+    pub fallback_grammar: Option<String>,
+
+    /// Syntax errors the grammar recovered from; only collected when
+    /// [`ParallelParser::with_syntax_errors`] is enabled
+    pub syntax_errors: Vec<SyntaxErrorSpan>,
+}
+
+/// A region of a file the grammar could not parse cleanly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxErrorSpan {
+    /// Byte range of the offending region
+    pub byte_range: (usize, usize),
+
+    /// One-based line where the region starts
+    pub line: usize,
+
+    /// One-based column where the region starts
+    pub column: usize,
+
+    /// Description of the error
+    pub message: String,
+}
+
+/// Maximum syntax errors recorded per file
+const MAX_SYNTAX_ERRORS_PER_FILE: usize = 20;
+
+/// Collect the outermost ERROR and MISSING nodes of `source`
+fn collect_syntax_errors(
+    parser: &mut Parser,
+    language: LanguageId,
+    source: &[u8],
+) -> Vec<SyntaxErrorSpan> {
+    let Ok(grammar) = language.from_cache() else {
+        return Vec::new();
+    };
+    if parser.set_language(&grammar).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let root = tree.root_node();
+    let mut errors = Vec::new();
+    if !root.has_error() {
+        return errors;
+    }
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if errors.len() >= MAX_SYNTAX_ERRORS_PER_FILE {
+            break;
+        }
+        if node.is_error() || node.is_missing() {
+            let start = node.start_position();
+            let message = if node.is_missing() {
+                format!("missing `{}`", node.kind())
+            } else {
+                "syntax error".to_string()
+            };
+            errors.push(SyntaxErrorSpan {
+                byte_range: (node.start_byte(), node.end_byte()),
+                line: start.row + 1,
+                column: start.column + 1,
+                message,
+            });
+            continue;
+        }
+        if node.has_error() {
+            // Push in reverse so errors come out in source order
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+    }
+    errors
 }
 
 impl ParsingResult {
@@ -48,6 +131,7 @@ impl ParsingResult {
         signatures: Vec<SignatureInfo>,
         source_bytes: Vec<u8>,
         parse_time_ms: u64,
+        parse_duration: Duration,
     ) -> Self {
         Self {
             file_path,
@@ -56,6 +140,9 @@ impl ParsingResult {
             source_bytes: Some(source_bytes),
             error: None,
             parse_time_ms,
+            parse_duration,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
         }
     }
 
@@ -68,6 +155,9 @@ impl ParsingResult {
             source_bytes: None,
             error: Some(error),
             parse_time_ms: 0,
+            parse_duration: Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
         }
     }
 
@@ -127,13 +217,47 @@ impl ParsingStats {
     }
 }
 
+/// Alternative grammar to retry with when the primary grammar fails
+///
+/// Useful during language transitions, when the pinned grammar lags behind
+/// new syntax and a newer (or older) grammar build can still parse the file.
+#[derive(Debug, Clone)]
+pub struct FallbackGrammar {
+    /// Name recorded in [`ParsingResult::fallback_grammar`] on success
+    pub name: String,
+
+    /// Factory for the parser backed by the fallback grammar
+    pub parser: fn() -> Box<dyn CodeIntelligence>,
+}
+
+/// Build a dedicated rayon pool of `threads` parser threads
+pub fn build_parse_pool(threads: usize) -> Result<rayon::ThreadPool, rayon::ThreadPoolBuildError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("leparse-{}", i))
+        .build()
+}
+
 /// Parallel parser for processing multiple files concurrently
+///
+/// By default parsing runs on rayon's global pool, which uses every core.
+/// `with_threads` bounds parallelism with a dedicated pool, and
+/// `with_thread_pool` runs on a pool shared with other components.
 pub struct ParallelParser {
     /// Maximum number of threads to use (None = use rayon default)
     max_threads: Option<usize>,
 
+    /// Pool to run parsing on (None = rayon global pool)
+    pool: Option<Arc<rayon::ThreadPool>>,
+
     /// Whether to collect detailed statistics
     collect_stats: bool,
+
+    /// Grammars to retry with after a hard parse failure, per language
+    fallback_grammars: HashMap<LanguageId, FallbackGrammar>,
+
+    /// Whether to record syntax error spans in each result
+    capture_syntax_errors: bool,
 }
 
 impl Default for ParallelParser {
@@ -147,13 +271,91 @@ impl ParallelParser {
     pub fn new() -> Self {
         Self {
             max_threads: None,
+            pool: None,
             collect_stats: true,
+            fallback_grammars: HashMap::new(),
+            capture_syntax_errors: false,
         }
     }
 
+    /// Bound parsing to `threads` worker threads
+    ///
+    /// Builds a dedicated rayon pool of that size. A value of 0 keeps the
+    /// default (all cores). If the pool cannot be created, parsing falls back
+    /// to the global pool.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        if threads == 0 {
+            return self;
+        }
+        match build_parse_pool(threads) {
+            Ok(pool) => {
+                self.max_threads = Some(threads);
+                self.pool = Some(Arc::new(pool));
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to build {}-thread parse pool, using global pool: {}",
+                    threads,
+                    e
+                );
+            }
+        }
+        self
+    }
+
     /// Set the maximum number of threads to use
-    pub fn with_max_threads(mut self, max_threads: usize) -> Self {
-        self.max_threads = Some(max_threads);
+    ///
+    /// Equivalent to [`ParallelParser::with_threads`].
+    pub fn with_max_threads(self, max_threads: usize) -> Self {
+        self.with_threads(max_threads)
+    }
+
+    /// Run parsing on an existing rayon pool
+    ///
+    /// Lets several components (e.g. CLI indexing and the server) share one
+    /// bounded pool instead of each competing for every core.
+    pub fn with_thread_pool(mut self, pool: Arc<rayon::ThreadPool>) -> Self {
+        self.max_threads = Some(pool.current_num_threads());
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Number of worker threads parsing will use
+    pub fn num_threads(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map(|pool| pool.current_num_threads())
+            .unwrap_or_else(rayon::current_num_threads)
+    }
+
+    /// Retry files of `language` with a fallback grammar on hard parse failure
+    ///
+    /// The primary grammar is always tried first. When it returns an error,
+    /// `parser` is used instead and, if it succeeds, `name` is recorded in
+    /// the result. Registering a second fallback for the same language
+    /// replaces the first.
+    pub fn with_fallback_grammar(
+        mut self,
+        language: LanguageId,
+        name: impl Into<String>,
+        parser: fn() -> Box<dyn CodeIntelligence>,
+    ) -> Self {
+        self.fallback_grammars.insert(
+            language,
+            FallbackGrammar {
+                name: name.into(),
+                parser,
+            },
+        );
+        self
+    }
+
+    /// Record the syntax errors the grammar recovered from in each result
+    ///
+    /// Costs a second tree-sitter parse of every successfully parsed file,
+    /// so it is off by default.
+    pub fn with_syntax_errors(mut self, enabled: bool) -> Self {
+        self.capture_syntax_errors = enabled;
         self
     }
 
@@ -177,10 +379,16 @@ impl ParallelParser {
         let start_time = Instant::now();
 
         // Use parallel iterator to process files concurrently
-        let results: Vec<ParsingResult> = file_paths
-            .into_par_iter()
-            .map(|path| self.parse_single_file(path))
-            .collect();
+        let parse_all = || -> Vec<ParsingResult> {
+            file_paths
+                .into_par_iter()
+                .map(|path| self.parse_single_file(path))
+                .collect()
+        };
+        let results = match &self.pool {
+            Some(pool) => pool.install(parse_all),
+            None => parse_all(),
+        };
 
         let total_time = start_time.elapsed().as_millis() as u64;
         let stats = ParsingStats::from_results(&results, total_time);
@@ -199,6 +407,116 @@ impl ParallelParser {
         (results, stats)
     }
 
+    /// Parse multiple files in parallel, calling `on_parsed` as each finishes
+    ///
+    /// `on_parsed` runs on the worker threads, in completion order.
+    pub fn parse_files_with_progress(
+        &self,
+        file_paths: Vec<PathBuf>,
+        on_parsed: &(dyn Fn(&ParsingResult) + Sync),
+    ) -> Vec<ParsingResult> {
+        self.parse_files_until(file_paths, on_parsed, &|| false)
+    }
+
+    /// Parse files like [`parse_files_with_progress`](Self::parse_files_with_progress),
+    /// stopping early once `is_cancelled` returns true
+    ///
+    /// Files already being parsed when cancellation is requested finish
+    /// normally; files not yet started are left out of the results.
+    pub fn parse_files_until(
+        &self,
+        file_paths: Vec<PathBuf>,
+        on_parsed: &(dyn Fn(&ParsingResult) + Sync),
+        is_cancelled: &(dyn Fn() -> bool + Sync),
+    ) -> Vec<ParsingResult> {
+        let parse_all = || -> Vec<ParsingResult> {
+            file_paths
+                .into_par_iter()
+                .filter_map(|path| {
+                    if is_cancelled() {
+                        return None;
+                    }
+                    let result = self.parse_single_file(path);
+                    on_parsed(&result);
+                    Some(result)
+                })
+                .collect()
+        };
+        match &self.pool {
+            Some(pool) => pool.install(parse_all),
+            None => parse_all(),
+        }
+    }
+
+    /// Parse an in-memory buffer without touching the filesystem
+    ///
+    /// `path_hint` is only recorded in the result and, when `language` is
+    /// `None`, used to detect the language from its extension. This lets
+    /// unsaved editor buffers and edit previews be parsed directly.
+    pub fn parse_source(
+        &self,
+        path_hint: impl AsRef<Path>,
+        language: Option<LanguageId>,
+        source: &[u8],
+    ) -> ParsingResult {
+        let start_time = Instant::now();
+        let file_path = path_hint.as_ref().to_path_buf();
+
+        let language_id = match language {
+            Some(id) => id,
+            None => {
+                let extension = file_path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("");
+                match LanguageId::from_extension_and_source(extension, source) {
+                    Some(id) => id,
+                    None => {
+                        let ext = extension.to_string();
+                        return ParsingResult::failure(
+                            file_path,
+                            format!("Unsupported file extension: {}", ext),
+                        );
+                    }
+                }
+            }
+        };
+
+        self.parse_bytes(file_path, language_id, source.to_vec(), start_time)
+    }
+
+    /// Parse many in-memory buffers in parallel
+    ///
+    /// Each entry is a path hint and its contents, handled as in
+    /// [`parse_source`](Self::parse_source) with the language detected from
+    /// the path. Used when sources come from somewhere other than the working
+    /// tree, such as git objects.
+    pub fn parse_sources(&self, sources: Vec<(PathBuf, Vec<u8>)>) -> Vec<ParsingResult> {
+        let parse_all = || -> Vec<ParsingResult> {
+            sources
+                .into_par_iter()
+                .map(|(path, source)| {
+                    let start_time = Instant::now();
+                    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+                    match LanguageId::from_extension_and_source(extension, &source) {
+                        Some(id) => self.parse_bytes(path, id, source, start_time),
+                        None => {
+                            let ext = extension.to_string();
+                            ParsingResult::failure(
+                                path,
+                                format!("Unsupported file extension: {}", ext),
+                            )
+                        }
+                    }
+                })
+                .collect()
+        };
+        match &self.pool {
+            Some(pool) => pool.install(parse_all),
+            None => parse_all(),
+        }
+    }
+
     /// Parse a single file
     fn parse_single_file(&self, file_path: PathBuf) -> ParsingResult {
         let start_time = Instant::now();
@@ -209,28 +527,47 @@ impl ParallelParser {
             .and_then(|ext| ext.to_str())
             .unwrap_or("");
 
-        let language_id = match LanguageId::from_extension(extension) {
+        if LanguageId::from_extension(extension).is_none() {
+            let ext = extension.to_string();
+            return ParsingResult::failure(
+                file_path,
+                format!("Unsupported file extension: {}", ext),
+            );
+        }
+
+        // Read file contents
+        let source = match std::fs::read(&file_path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return ParsingResult::failure(file_path, format!("Failed to read file: {}", e))
+            }
+        };
+
+        // Disambiguate C-family headers by content
+        let language_id = match LanguageId::from_extension_and_source(extension, &source) {
             Some(id) => id,
             None => {
-                let ext = extension.to_string();
                 return ParsingResult::failure(
                     file_path,
-                    format!("Unsupported file extension: {}", ext),
-                );
+                    format!("Unsupported file extension: {}", extension),
+                )
             }
         };
 
+        self.parse_bytes(file_path, language_id, source, start_time)
+    }
+
+    /// Parse source bytes already in memory
+    fn parse_bytes(
+        &self,
+        file_path: PathBuf,
+        language_id: LanguageId,
+        source: Vec<u8>,
+        start_time: Instant,
+    ) -> ParsingResult {
         // Get language name for result
         let language_name = language_id.config().name.clone();
 
-        // Read file contents
-        let source = match std::fs::read(&file_path) {
-            Ok(contents) => contents,
-            Err(e) => {
-                return ParsingResult::failure(file_path, format!("Failed to read file: {}", e))
-            }
-        };
-
         // Get the language-specific parser factory
         let lang_parser = match parser_for_language(&language_name) {
             Some(p) => p,
@@ -242,18 +579,86 @@ impl ParallelParser {
             }
         };
 
-        // Use thread-local pooled parser
+        self.parse_with_grammars(
+            file_path,
+            language_id,
+            language_name,
+            lang_parser.as_ref(),
+            source,
+            start_time,
+        )
+    }
+
+    /// Parse with the primary grammar, retrying with the fallback on failure
+    fn parse_with_grammars(
+        &self,
+        file_path: PathBuf,
+        language_id: LanguageId,
+        language_name: String,
+        primary: &dyn CodeIntelligence,
+        source: Vec<u8>,
+        start_time: Instant,
+    ) -> ParsingResult {
+        // Use thread-local pooled parser; time the parse itself, not I/O
+        let parse_start = Instant::now();
         let result = THREAD_PARSER.with(|parser_cell| {
             let mut parser = parser_cell.borrow_mut();
-            lang_parser.get_signatures_with_parser(&source, &mut parser)
+            primary.get_signatures_with_parser(&source, &mut parser)
         });
 
+        let mut fallback_grammar = None;
+        let result = match (result, self.fallback_grammars.get(&language_id)) {
+            (Err(primary_err), Some(fallback)) => {
+                let fallback_parser = (fallback.parser)();
+                let retry = THREAD_PARSER.with(|parser_cell| {
+                    let mut parser = parser_cell.borrow_mut();
+                    fallback_parser.get_signatures_with_parser(&source, &mut parser)
+                });
+                match retry {
+                    Ok(signatures) => {
+                        tracing::debug!(
+                            "Parsed {} with fallback grammar '{}' after primary failure: {}",
+                            file_path.display(),
+                            fallback.name,
+                            primary_err
+                        );
+                        fallback_grammar = Some(fallback.name.clone());
+                        Ok(signatures)
+                    }
+                    Err(fallback_err) => Err(crate::parse::traits::Error::ParseFailed(format!(
+                        "{}; fallback grammar '{}' also failed: {}",
+                        primary_err, fallback.name, fallback_err
+                    ))),
+                }
+            }
+            (result, _) => result,
+        };
+        let parse_duration = parse_start.elapsed();
+
         // Process result
         let parse_time_ms = start_time.elapsed().as_millis() as u64;
 
         match result {
             Ok(signatures) => {
-                ParsingResult::success(file_path, language_name, signatures, source, parse_time_ms)
+                let mut parsed = ParsingResult::success(
+                    file_path,
+                    language_name,
+                    signatures,
+                    source,
+                    parse_time_ms,
+                    parse_duration,
+                );
+                if self.capture_syntax_errors && fallback_grammar.is_none() {
+                    parsed.syntax_errors = THREAD_PARSER.with(|parser_cell| {
+                        collect_syntax_errors(
+                            &mut parser_cell.borrow_mut(),
+                            language_id,
+                            parsed.source_bytes.as_deref().unwrap_or_default(),
+                        )
+                    });
+                }
+                parsed.fallback_grammar = fallback_grammar;
+                parsed
             }
             Err(e) => ParsingResult::failure(file_path, format!("Parse error: {}", e)),
         }
@@ -268,15 +673,49 @@ impl ParallelParser {
     pub fn failed_results(results: &[ParsingResult]) -> Vec<&ParsingResult> {
         results.iter().filter(|r| r.is_failure()).collect()
     }
+
+    /// Get the `n` successful results with the longest parse duration, slowest first
+    pub fn slowest_results(results: &[ParsingResult], n: usize) -> Vec<&ParsingResult> {
+        let mut successful = Self::successful_results(results);
+        successful.sort_by(|a, b| b.parse_duration.cmp(&a.parse_duration));
+        successful.truncate(n);
+        successful
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::python::PythonParser;
+    use crate::parse::traits::{Block, ComplexityMetrics, Edge, Error, Graph, Result};
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_syntax_errors_recorded_when_enabled() {
+        let source = b"def ok():\n    return 1\n\ndef broken(:\n    return\n";
+
+        let result = ParallelParser::new().parse_source("broken.py", None, source);
+        assert!(result.is_success());
+        assert!(result.syntax_errors.is_empty());
+
+        let result =
+            ParallelParser::new()
+                .with_syntax_errors(true)
+                .parse_source("broken.py", None, source);
+        assert!(result.is_success());
+        assert!(!result.syntax_errors.is_empty());
+        assert_eq!(result.syntax_errors[0].line, 4);
+
+        let clean = ParallelParser::new().with_syntax_errors(true).parse_source(
+            "ok.py",
+            None,
+            b"def ok():\n    return 1\n",
+        );
+        assert!(clean.syntax_errors.is_empty());
+    }
+
     #[test]
     fn test_parallel_parser_multiple_files() {
         let dir = tempdir().unwrap();
@@ -296,6 +735,140 @@ mod tests {
         assert!(results.iter().all(|r| r.is_success()));
     }
 
+    #[test]
+    fn test_parallel_parser_with_bounded_threads() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..8)
+            .map(|i| {
+                let path = dir.path().join(format!("mod_{}.py", i));
+                let mut file = File::create(&path).unwrap();
+                writeln!(file, "def func_{}(): pass", i).unwrap();
+                path
+            })
+            .collect();
+
+        let parser = ParallelParser::new().with_threads(2);
+        assert_eq!(parser.num_threads(), 2);
+
+        let results = parser.parse_files(paths);
+        assert_eq!(results.len(), 8);
+        assert!(results.iter().all(|r| r.is_success()));
+        for (i, result) in results.iter().enumerate() {
+            assert!(result
+                .signatures
+                .iter()
+                .any(|sig| sig.name == format!("func_{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_parallel_parser_shares_existing_pool() {
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+        let parser = ParallelParser::new().with_thread_pool(pool.clone());
+        assert_eq!(parser.num_threads(), 2);
+        assert_eq!(Arc::strong_count(&pool), 2);
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "fn shared() {{}}").unwrap();
+
+        let results = parser.parse_files(vec![path]);
+        assert!(results[0].is_success());
+    }
+
+    #[test]
+    fn test_parse_source_from_buffer() {
+        let dir = tempdir().unwrap();
+        let path_hint = dir.path().join("unsaved.py");
+        let parser = ParallelParser::new();
+
+        let result = parser.parse_source(&path_hint, None, b"def preview(x):\n    return x\n");
+        assert!(result.is_success());
+        assert!(
+            !path_hint.exists(),
+            "buffer parsing must not touch the filesystem"
+        );
+        assert_eq!(result.file_path, path_hint);
+        assert_eq!(result.language.as_deref(), Some("Python"));
+        assert!(result.signatures.iter().any(|sig| sig.name == "preview"));
+
+        // An explicit language overrides the path hint
+        let result =
+            parser.parse_source("scratch.txt", Some(LanguageId::Rust), b"fn buffered() {}\n");
+        assert!(result.is_success());
+        assert!(result.signatures.iter().any(|sig| sig.name == "buffered"));
+
+        let result = parser.parse_source("notes.unknown", None, b"hello");
+        assert!(result.is_failure());
+    }
+
+    /// Stand-in for a pinned grammar that cannot parse newer syntax
+    struct OutdatedGrammar;
+
+    impl CodeIntelligence for OutdatedGrammar {
+        fn get_signatures(&self, _source: &[u8]) -> Result<Vec<SignatureInfo>> {
+            Err(Error::ParseFailed("unsupported syntax".to_string()))
+        }
+
+        fn compute_cfg(&self, _source: &[u8], _node_id: usize) -> Result<Graph<Block, Edge>> {
+            Err(Error::ParseFailed("unsupported syntax".to_string()))
+        }
+
+        fn extract_complexity(&self, node: &tree_sitter::Node<'_>) -> ComplexityMetrics {
+            PythonParser::new().extract_complexity(node)
+        }
+    }
+
+    fn python_fallback() -> Box<dyn CodeIntelligence> {
+        Box::new(PythonParser::new())
+    }
+
+    #[test]
+    fn test_fallback_grammar_recorded_after_primary_failure() {
+        let source = b"def modern(x):\n    return x\n".to_vec();
+
+        // Without a fallback the primary failure is reported as-is
+        let parser = ParallelParser::new();
+        let result = parser.parse_with_grammars(
+            PathBuf::from("new_syntax.py"),
+            LanguageId::Python,
+            "Python".to_string(),
+            &OutdatedGrammar,
+            source.clone(),
+            Instant::now(),
+        );
+        assert!(result.is_failure());
+        assert!(result.fallback_grammar.is_none());
+
+        let parser = ParallelParser::new().with_fallback_grammar(
+            LanguageId::Python,
+            "python-next",
+            python_fallback,
+        );
+        let result = parser.parse_with_grammars(
+            PathBuf::from("new_syntax.py"),
+            LanguageId::Python,
+            "Python".to_string(),
+            &OutdatedGrammar,
+            source,
+            Instant::now(),
+        );
+        assert!(result.is_success());
+        assert_eq!(result.fallback_grammar.as_deref(), Some("python-next"));
+        assert!(result.signatures.iter().any(|sig| sig.name == "modern"));
+
+        // The primary grammar still wins when it succeeds
+        let result = parser.parse_source("ok.py", None, b"def stable(): pass\n");
+        assert!(result.is_success());
+        assert!(result.fallback_grammar.is_none());
+    }
+
     #[test]
     fn test_parallel_parser_with_error() {
         let dir = tempdir().unwrap();
@@ -325,6 +898,25 @@ mod tests {
         let _ = stats.total_time_ms;
     }
 
+    #[test]
+    fn test_parse_duration_is_recorded() {
+        let dir = tempdir().unwrap();
+        let small = dir.path().join("small.py");
+        let large = dir.path().join("large.py");
+        writeln!(File::create(&small).unwrap(), "def hello(): pass").unwrap();
+        let mut large_file = File::create(&large).unwrap();
+        for i in 0..500 {
+            writeln!(large_file, "def func_{}(a, b):\n    return a + b\n", i).unwrap();
+        }
+
+        let results = ParallelParser::new().parse_files(vec![small, large.clone()]);
+        assert!(results.iter().all(|r| r.parse_duration > Duration::ZERO));
+
+        let slowest = ParallelParser::slowest_results(&results, 1);
+        assert_eq!(slowest.len(), 1);
+        assert_eq!(slowest[0].file_path, large);
+    }
+
     #[test]
     fn test_parsing_result_includes_source_bytes() {
         let dir = tempdir().unwrap();
@@ -64,6 +64,7 @@ impl PhpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -96,6 +97,7 @@ impl PhpParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -275,6 +277,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (0, 0),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -0,0 +1,165 @@
+// Byte offset to line/column conversion
+//
+// Parsers and the PDG carry raw byte ranges. Editors and human-facing output
+// want 1-based line/column positions, so this module converts between the
+// two. Columns count Unicode scalar values rather than bytes, and `\r\n` is
+// treated as a single line break.
+
+use serde::{Deserialize, Serialize};
+
+/// 1-based line/column position in a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Position {
+    /// Line number (1-indexed)
+    pub line: usize,
+
+    /// Column number (1-indexed, in characters)
+    pub column: usize,
+}
+
+/// Start and end positions of a byte range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PositionRange {
+    /// Position of the first byte
+    pub start: Position,
+
+    /// Position just past the last byte
+    pub end: Position,
+}
+
+/// Precomputed line starts for repeated offset lookups on one source
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a [u8],
+    /// Byte offset at which each line begins
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index the line starts of `source`
+    pub fn new(source: &'a [u8]) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, &byte)| byte == b'\n')
+                .map(|(i, _)| i + 1),
+        );
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Number of lines in the source
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Position of `offset`; offsets past the end clamp to the end of input
+    ///
+    /// An offset inside a multibyte character resolves to that character's
+    /// column.
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source.len());
+        let line_idx = self
+            .line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1);
+        let line_start = self.line_starts[line_idx];
+
+        let mut prefix = &self.source[line_start..offset];
+        // The `\r` of a CRLF pair belongs to the line break, not the line
+        if prefix.last() == Some(&b'\r') && self.source.get(offset) == Some(&b'\n') {
+            prefix = &prefix[..prefix.len() - 1];
+        }
+        let chars = prefix.iter().filter(|&&b| (b & 0xC0) != 0x80).count();
+        let column = if self.is_continuation(offset) {
+            chars
+        } else {
+            chars + 1
+        };
+
+        Position {
+            line: line_idx + 1,
+            column: column.max(1),
+        }
+    }
+
+    /// Start and end positions of `range`
+    pub fn range(&self, range: (usize, usize)) -> PositionRange {
+        PositionRange {
+            start: self.position(range.0),
+            end: self.position(range.1),
+        }
+    }
+
+    fn is_continuation(&self, offset: usize) -> bool {
+        self.source.get(offset).is_some_and(|&b| (b & 0xC0) == 0x80)
+    }
+}
+
+/// Convert a byte offset in `source` to a 1-based line/column position
+///
+/// For many lookups on the same source, build a [`LineIndex`] once instead.
+pub fn byte_to_position(source: &[u8], offset: usize) -> Position {
+    LineIndex::new(source).position(offset)
+}
+
+/// Convert a byte range in `source` to 1-based line/column positions
+pub fn byte_range_to_positions(source: &[u8], range: (usize, usize)) -> PositionRange {
+    LineIndex::new(source).range(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize) -> Position {
+        Position { line, column }
+    }
+
+    #[test]
+    fn test_ascii_positions() {
+        let source = b"fn a() {}\nfn b() {}\n";
+        assert_eq!(byte_to_position(source, 0), pos(1, 1));
+        assert_eq!(byte_to_position(source, 3), pos(1, 4));
+        assert_eq!(byte_to_position(source, 10), pos(2, 1));
+        assert_eq!(byte_to_position(source, 13), pos(2, 4));
+        // Past the end clamps to end of input
+        assert_eq!(byte_to_position(source, 999), pos(3, 1));
+    }
+
+    #[test]
+    fn test_multibyte_columns_count_characters() {
+        let source = "let s = \"héllo\"; // ünïcode\nx".as_bytes();
+        let h = source.iter().position(|&b| b == b'h').unwrap();
+        let l = h + "hé".len();
+        assert_eq!(byte_to_position(source, h), pos(1, 10));
+        // "é" is two bytes but one column
+        assert_eq!(byte_to_position(source, l), pos(1, 12));
+        // An offset inside "é" resolves to the column of "é"
+        assert_eq!(byte_to_position(source, h + 2), pos(1, 11));
+
+        let x = source.len() - 1;
+        assert_eq!(byte_to_position(source, x), pos(2, 1));
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let source = b"first\r\nsecond\r\nthird";
+        let second = 7;
+        let third = 15;
+        assert_eq!(byte_to_position(source, second), pos(2, 1));
+        assert_eq!(byte_to_position(source, third + 2), pos(3, 3));
+        // End of line 1 (the `\r`) and the `\n` both report column 6
+        assert_eq!(byte_to_position(source, 5), pos(1, 6));
+        assert_eq!(byte_to_position(source, 6), pos(1, 6));
+
+        let range = byte_range_to_positions(source, (second, second + 6));
+        assert_eq!(range.start, pos(2, 1));
+        assert_eq!(range.end, pos(2, 7));
+        assert_eq!(LineIndex::new(source).line_count(), 3);
+    }
+}
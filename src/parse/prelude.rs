@@ -6,6 +6,8 @@ pub use crate::parse::ast::{
 pub use crate::parse::completeness::{score_languages, LanguageCompleteness};
 pub use crate::parse::languages::{parser_for_language, JavaScriptParser, PythonParser};
 pub use crate::parse::parallel::ParallelParser;
+pub use crate::parse::position::{byte_range_to_positions, byte_to_position, LineIndex, Position};
+pub use crate::parse::query::{run_query, QueryCapture};
 pub use crate::parse::traits::{
     CodeIntelligence, ComplexityMetrics, Edge, EdgeType, Error, Graph, ImportInfo, LanguageConfig,
     Parameter, QueryPatterns, Result, SignatureInfo, Visibility,
@@ -90,10 +90,25 @@ impl PythonParser {
                         class_path.push(name);
 
                         // Continue recursion into class body
+                        let first_member = signatures.len();
                         let mut cursor = node.walk();
                         for child in node.children(&mut cursor) {
                             visit_node(&child, source, signatures, &class_path);
                         }
+
+                        // Python has no class signatures, so direct methods
+                        // carry the explicit bases of their class
+                        let bases = extract_python_bases(node, source);
+                        if !bases.is_empty() {
+                            let class_prefix = format!("{}.", class_path.join("."));
+                            for sig in &mut signatures[first_member..] {
+                                if sig.qualified_name.strip_prefix(&class_prefix)
+                                    == Some(sig.name.as_str())
+                                {
+                                    sig.base_types = bases.clone();
+                                }
+                            }
+                        }
                     } else {
                         // Continue recursion without adding to path
                         let mut cursor = node.walk();
@@ -191,7 +206,7 @@ fn extract_python_imports(root: tree_sitter::Node<'_>, source: &[u8]) -> Vec<Imp
     let mut imports = Vec::new();
 
     fn add_import(imports: &mut Vec<ImportInfo>, path: &str, alias: Option<String>) {
-        let path = path.trim().trim_end_matches(';').trim();
+        let path = path.trim();
         if path.is_empty() {
             return;
         }
@@ -201,56 +216,56 @@ fn extract_python_imports(root: tree_sitter::Node<'_>, source: &[u8]) -> Vec<Imp
         });
     }
 
-    fn parse_import_text(imports: &mut Vec<ImportInfo>, text: &str) {
-        let text = text.trim();
-        if text.starts_with("import ") {
-            let rest = text.trim_start_matches("import ");
-            for part in rest.split(',') {
-                let part = part.trim();
-                if part.is_empty() {
-                    continue;
-                }
-                if let Some((path, alias)) = part.split_once(" as ") {
-                    add_import(imports, path.trim(), Some(alias.trim().to_string()));
-                } else {
-                    add_import(
-                        imports,
-                        part,
-                        part.split('.').next_back().map(|s| s.to_string()),
-                    );
-                }
-            }
-        } else if text.starts_with("from ") {
-            let rest = text.trim_start_matches("from ");
-            if let Some((module, items)) = rest.split_once(" import ") {
-                let module = module.trim();
-                for item in items.split(',') {
-                    let item = item.trim();
-                    if item.is_empty() || item == "*" {
-                        continue;
-                    }
-                    if let Some((name, alias)) = item.split_once(" as ") {
-                        let path = format!("{}.{}", module, name.trim());
-                        add_import(imports, &path, Some(alias.trim().to_string()));
-                    } else {
-                        let path = format!("{}.{}", module, item);
-                        add_import(
-                            imports,
-                            &path,
-                            item.split('.').next_back().map(|s| s.to_string()),
-                        );
-                    }
-                }
-            }
+    fn text<'a>(node: &tree_sitter::Node<'_>, source: &'a [u8]) -> Option<&'a str> {
+        node.utf8_text(source).ok().map(str::trim)
+    }
+
+    /// `(path, alias)` for a `name` child: `a.b` or `a.b as c`
+    fn imported_name(node: &tree_sitter::Node<'_>, source: &[u8]) -> Option<(String, String)> {
+        if node.kind() == "aliased_import" {
+            let path = text(&node.child_by_field_name("name")?, source)?;
+            let alias = text(&node.child_by_field_name("alias")?, source)?;
+            Some((path.to_string(), alias.to_string()))
+        } else {
+            let path = text(node, source)?;
+            let alias = path.rsplit('.').next().unwrap_or(path);
+            Some((path.to_string(), alias.to_string()))
         }
     }
 
     fn visit(node: &tree_sitter::Node<'_>, source: &[u8], imports: &mut Vec<ImportInfo>) {
         match node.kind() {
-            "import_statement" | "import_from_statement" => {
-                if let Ok(text) = node.utf8_text(source) {
-                    parse_import_text(imports, text);
+            // import a.b, c as d
+            "import_statement" => {
+                let mut cursor = node.walk();
+                for name in node.children_by_field_name("name", &mut cursor) {
+                    if let Some((path, alias)) = imported_name(&name, source) {
+                        add_import(imports, &path, Some(alias));
+                    }
+                }
+                return;
+            }
+            // from pkg.mod import a, b as c / from . import mod as m
+            "import_from_statement" => {
+                let Some(module) = node
+                    .child_by_field_name("module_name")
+                    .and_then(|m| text(&m, source))
+                else {
+                    return;
+                };
+                let mut cursor = node.walk();
+                for name in node.children_by_field_name("name", &mut cursor) {
+                    if let Some((item, alias)) = imported_name(&name, source) {
+                        // Relative modules such as `.` or `..` already end in a separator
+                        let path = if module.ends_with('.') {
+                            format!("{}{}", module, item)
+                        } else {
+                            format!("{}.{}", module, item)
+                        };
+                        add_import(imports, &path, Some(alias));
+                    }
                 }
+                return;
             }
             _ => {}
         }
@@ -342,9 +357,28 @@ fn extract_function_signature_with_path(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
+/// Extract the base classes listed in a `class C(Base, mod.Other)` header
+///
+/// Keyword arguments such as `metaclass=...` are not bases and are skipped.
+fn extract_python_bases(class_node: &tree_sitter::Node<'_>, source: &[u8]) -> Vec<String> {
+    let Some(superclasses) = class_node.child_by_field_name("superclasses") else {
+        return Vec::new();
+    };
+    let mut cursor = superclasses.walk();
+    let bases = superclasses
+        .named_children(&mut cursor)
+        .filter(|arg| matches!(arg.kind(), "identifier" | "attribute"))
+        .filter_map(|arg| arg.utf8_text(source).ok())
+        .map(|text| text.trim().to_string())
+        .filter(|text| text != "object")
+        .collect();
+    bases
+}
+
 /// Extract function calls from a Python node
 fn extract_python_calls(node: &tree_sitter::Node<'_>, source: &[u8]) -> Vec<String> {
     let mut calls = Vec::new();
@@ -755,6 +789,79 @@ def standalone_func():
         assert!(!standalone.is_empty());
     }
 
+    #[test]
+    fn test_class_bases_attached_to_methods() {
+        let source = b"
+class Dog(Animal, pets.Friendly, metaclass=Meta):
+    def speak(self):
+        def inner():
+            pass
+        return inner()
+
+class Plain:
+    def run(self):
+        pass
+";
+
+        let signatures = PythonParser::new().get_signatures(source).unwrap();
+        let speak = signatures
+            .iter()
+            .find(|sig| sig.qualified_name == "Dog.speak")
+            .unwrap();
+        assert_eq!(speak.base_types, vec!["Animal", "pets.Friendly"]);
+
+        let inner = signatures
+            .iter()
+            .find(|sig| sig.qualified_name == "Dog.speak.inner")
+            .unwrap();
+        assert!(inner.base_types.is_empty());
+
+        let run = signatures
+            .iter()
+            .find(|sig| sig.qualified_name == "Plain.run")
+            .unwrap();
+        assert!(run.base_types.is_empty());
+    }
+
+    #[test]
+    fn test_import_aliases_for_all_forms() {
+        let source = b"
+import os, numpy as np
+import xml.etree.ElementTree as ET
+from collections import OrderedDict as OD, deque
+from . import helpers as h
+from ..core.models import (
+    User,  # the account model
+    Group as Team,
+)
+from .sibling import *
+
+def run():
+    pass
+";
+
+        let signatures = PythonParser::new().get_signatures(source).unwrap();
+        let imports: Vec<(&str, Option<&str>)> = signatures[0]
+            .imports
+            .iter()
+            .map(|i| (i.path.as_str(), i.alias.as_deref()))
+            .collect();
+
+        assert_eq!(
+            imports,
+            vec![
+                ("os", Some("os")),
+                ("numpy", Some("np")),
+                ("xml.etree.ElementTree", Some("ET")),
+                ("collections.OrderedDict", Some("OD")),
+                ("collections.deque", Some("deque")),
+                (".helpers", Some("h")),
+                ("..core.models.User", Some("User")),
+                ("..core.models.Group", Some("Team")),
+            ]
+        );
+    }
+
     #[test]
     fn test_complexity_calculation() {
         let source = b"
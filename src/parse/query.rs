@@ -0,0 +1,183 @@
+// Ad-hoc tree-sitter queries
+//
+// Runs arbitrary tree-sitter query strings against source code without a
+// dedicated language module, e.g. finding every `unwrap()` call in Rust.
+// Compiled queries are cached per (language, query text), keeping the most
+// recently used ones.
+
+use crate::parse::grammar::LanguageId;
+use crate::parse::traits::{Error, Result};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+
+/// Maximum number of compiled queries kept in the cache
+pub const QUERY_CACHE_CAPACITY: usize = 128;
+
+/// Compiled queries keyed by language and query text
+static QUERY_CACHE: Lazy<QueryCache> = Lazy::new(|| QueryCache::new(QUERY_CACHE_CAPACITY));
+
+/// Least-recently-used cache of compiled queries
+struct QueryCache {
+    entries: Mutex<LruCache<(LanguageId, String), Arc<Query>>>,
+}
+
+impl QueryCache {
+    fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Return the cached query, compiling and caching it on a miss
+    ///
+    /// The lock is not held while compiling, so two threads may compile the
+    /// same query; the first one cached wins.
+    fn get_or_compile(&self, language: LanguageId, query: &str) -> Result<Arc<Query>> {
+        let key = (language, query.to_string());
+        if let Some(compiled) = self.lock()?.get(&key) {
+            return Ok(compiled.clone());
+        }
+
+        let compiled = Arc::new(Query::new(&language.from_cache()?, query).map_err(|e| {
+            Error::InvalidQuery {
+                row: e.row,
+                column: e.column,
+                message: e.message,
+            }
+        })?);
+
+        Ok(self.lock()?.get_or_insert(key, || compiled.clone()).clone())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, LruCache<(LanguageId, String), Arc<Query>>>> {
+        self.entries
+            .lock()
+            .map_err(|e| Error::ParseFailed(format!("Query cache lock poisoned: {}", e)))
+    }
+}
+
+/// A single capture produced by a query match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCapture {
+    /// Capture name without the leading `@`
+    pub capture_name: String,
+
+    /// Index of the query pattern that matched
+    pub pattern_index: usize,
+
+    /// Byte range of the captured node in the source
+    pub byte_range: (usize, usize),
+
+    /// Source text of the captured node
+    pub text: String,
+}
+
+/// Compile `query` for `language`, reusing a cached copy when available
+///
+/// Up to [`QUERY_CACHE_CAPACITY`] queries are cached; the least recently
+/// used one is dropped to make room.
+pub fn compile_query(language: LanguageId, query: &str) -> Result<Arc<Query>> {
+    QUERY_CACHE.get_or_compile(language, query)
+}
+
+/// Run a tree-sitter query over `source` and return every capture
+///
+/// Captures are returned in document order. Predicates supported by
+/// tree-sitter's query cursor (`#eq?`, `#match?`, ...) are applied.
+pub fn run_query(language: LanguageId, source: &[u8], query: &str) -> Result<Vec<QueryCapture>> {
+    let compiled = compile_query(language, query)?;
+
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.from_cache()?)
+        .map_err(|e| Error::ParseFailed(e.to_string()))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| Error::ParseFailed("Failed to parse source".to_string()))?;
+
+    let capture_names = compiled.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut captures = cursor.captures(&compiled, tree.root_node(), source);
+
+    let mut results = Vec::new();
+    while let Some((query_match, capture_index)) = captures.next() {
+        let capture = query_match.captures[*capture_index];
+        let node = capture.node;
+        results.push(QueryCapture {
+            capture_name: capture_names[capture.index as usize].to_string(),
+            pattern_index: query_match.pattern_index,
+            byte_range: (node.start_byte(), node.end_byte()),
+            text: node.utf8_text(source).unwrap_or_default().to_string(),
+        });
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_captures_python_function_names() {
+        let source = b"def alpha():\n    pass\n\nclass Box:\n    def beta(self):\n        pass\n";
+        let captures = run_query(
+            LanguageId::Python,
+            source,
+            "(function_definition name: (identifier) @name)",
+        )
+        .unwrap();
+
+        let names: Vec<&str> = captures.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+        assert!(captures.iter().all(|c| c.capture_name == "name"));
+        assert_eq!(captures[0].byte_range, (4, 9));
+    }
+
+    #[test]
+    fn test_query_compile_error_is_reported() {
+        let err =
+            run_query(LanguageId::Python, b"x = 1\n", "(function_definition @name").unwrap_err();
+        assert!(matches!(err, Error::InvalidQuery { .. }));
+    }
+
+    #[test]
+    fn test_compiled_queries_are_cached() {
+        let query = "(identifier) @id";
+        let first = compile_query(LanguageId::Rust, query).unwrap();
+        let second = compile_query(LanguageId::Rust, query).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_query_cache_evicts_least_recently_used() {
+        let cache = QueryCache::new(2);
+        let a = cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @a")
+            .unwrap();
+        let b = cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @b")
+            .unwrap();
+        // Touch `a` so `b` is the least recently used
+        cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @a")
+            .unwrap();
+        cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @c")
+            .unwrap();
+
+        assert_eq!(cache.lock().unwrap().len(), 2);
+        let a_again = cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @a")
+            .unwrap();
+        assert!(Arc::ptr_eq(&a, &a_again));
+        let b_again = cache
+            .get_or_compile(LanguageId::Rust, "(identifier) @b")
+            .unwrap();
+        assert!(!Arc::ptr_eq(&b, &b_again));
+    }
+}
@@ -64,6 +64,7 @@ impl RubyParser {
                             imports: vec![],
                             byte_range: (0, 0),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -240,6 +241,7 @@ fn extract_method_signature(
         imports: vec![],
         byte_range: (0, 0),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -28,6 +28,7 @@ impl RustParser {
         root: tree_sitter::Node<'_>,
     ) -> Vec<SignatureInfo> {
         let mut signatures = Vec::new();
+        let mut trait_impls: Vec<(String, String)> = Vec::new();
         let mut stack = vec![(root, Vec::<String>::new())];
 
         while let Some((node, parent_path)) = stack.pop() {
@@ -66,6 +67,7 @@ impl RustParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
 
                         let mut new_path = parent_path.clone();
@@ -76,6 +78,17 @@ impl RustParser {
                     }
                 }
                 "impl_item" => {
+                    let impl_type = |field: &str| {
+                        node.child_by_field_name(field)
+                            .and_then(|n| n.utf8_text(source).ok())
+                            .map(|text| text.split('<').next().unwrap_or(text).trim().to_string())
+                    };
+                    if let (Some(trait_name), Some(type_name)) =
+                        (impl_type("trait"), impl_type("type"))
+                    {
+                        trait_impls.push((type_name, trait_name));
+                    }
+
                     let mut cursor = node.walk();
                     for child in node.children(&mut cursor) {
                         if child.kind() == "declaration_list" {
@@ -134,6 +147,7 @@ impl RustParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
 
@@ -174,6 +188,7 @@ impl RustParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -201,6 +216,7 @@ impl RustParser {
                             imports: vec![],
                             byte_range: (node.start_byte(), node.end_byte()),
                             cyclomatic_complexity: 0,
+                            base_types: Vec::new(),
                         });
                     }
                 }
@@ -215,6 +231,23 @@ impl RustParser {
             }
         }
 
+        // `impl Trait for Type` names the trait as a base of the type declaration
+        for (type_name, trait_name) in trait_impls {
+            let short_type = type_name.rsplit("::").next().unwrap_or(&type_name);
+            for sig in signatures.iter_mut().filter(|sig| {
+                !sig.is_method
+                    && sig.name == short_type
+                    && sig
+                        .return_type
+                        .as_deref()
+                        .is_some_and(|rt| rt.starts_with("struct") || rt == "enum")
+            }) {
+                if !sig.base_types.contains(&trait_name) {
+                    sig.base_types.push(trait_name.clone());
+                }
+            }
+        }
+
         signatures
     }
 }
@@ -419,6 +452,7 @@ fn extract_function_signature(
         imports: vec![],
         byte_range: (node.start_byte(), node.end_byte()),
         cyclomatic_complexity: 0, // Will be populated by caller with extract_complexity
+        base_types: Vec::new(),
     })
 }
 
@@ -555,6 +589,7 @@ fn extract_import_signature(
         imports: vec![],
         byte_range: (0, 0),
         cyclomatic_complexity: 0,
+        base_types: Vec::new(),
     })
 }
 
@@ -1042,6 +1077,27 @@ pub struct Person {
         assert!(person.is_some());
     }
 
+    #[test]
+    fn test_rust_trait_impls_recorded_as_bases() {
+        let source = b"struct Wrapper<T>(T);
+
+impl<T> fmt::Display for Wrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T> Wrapper<T> {
+    fn get(&self) -> &T {
+        &self.0
+    }
+}";
+
+        let signatures = RustParser::new().get_signatures(source).unwrap();
+        let wrapper = signatures.iter().find(|s| s.name == "Wrapper").unwrap();
+        assert_eq!(wrapper.base_types, vec!["fmt::Display"]);
+    }
+
     #[test]
     fn test_rust_enum_extraction() {
         let source = b"enum Option<T> {
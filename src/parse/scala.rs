@@ -116,6 +116,7 @@ fn visit(
                     imports: vec![],
                     byte_range: (0, 0),
                     cyclomatic_complexity: 0,
+                    base_types: Vec::new(),
                 });
             }
         }
@@ -147,6 +148,7 @@ fn visit(
                     imports: vec![],
                     byte_range: (0, 0),
                     cyclomatic_complexity: 0,
+                    base_types: Vec::new(),
                 });
 
                 // Recurse into the body to find nested members
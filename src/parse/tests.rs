@@ -57,6 +57,16 @@ mod language_detection_tests {
         assert_eq!(config.unwrap().name, "Bash");
     }
 
+    #[test]
+    fn test_objc_extension_detection() {
+        let config = LanguageConfig::from_extension("m");
+        assert!(config.is_some());
+        assert_eq!(config.unwrap().name, "Objective-C");
+
+        // Objective-C++ needs a C++ grammar; it is not indexed
+        assert!(LanguageConfig::from_extension("mm").is_none());
+    }
+
     #[test]
     fn test_json_extension_detection() {
         let config = LanguageConfig::from_extension("json");
@@ -110,6 +120,90 @@ mod language_detection_tests {
     }
 }
 
+#[cfg(test)]
+mod c_family_tests {
+    use crate::parse::c::CParser;
+    use crate::parse::objc::ObjCParser;
+    use crate::parse::traits::CodeIntelligence;
+
+    #[test]
+    fn test_c_function_signatures() {
+        let source = b"#include <stdio.h>\n\nint add(int a, int b) {\n    return a + b;\n}\n";
+        let signatures = CParser::new().get_signatures(source).unwrap();
+
+        assert!(signatures.iter().any(|s| s.name == "add"));
+    }
+
+    #[test]
+    fn test_objc_method_signatures() {
+        let source = br#"#import <Foundation/Foundation.h>
+
+@interface Calculator : NSObject
+- (int)add:(int)a to:(int)b;
++ (instancetype)shared;
+@end
+
+@implementation Calculator
+- (int)add:(int)a to:(int)b {
+    if (a > 0) { return a + b; }
+    return b;
+}
+
++ (instancetype)shared {
+    return nil;
+}
+@end
+
+static int helper(int x) {
+    return x * 2;
+}
+"#;
+        let signatures = ObjCParser::new().get_signatures(source).unwrap();
+
+        let add = signatures
+            .iter()
+            .find(|s| s.qualified_name == "Calculator.add:to:")
+            .expect("selector should be qualified with its class");
+        assert!(add.is_method);
+        assert_eq!(add.parameters, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(add.return_type.as_deref(), Some("int"));
+        // Definition from @implementation wins over the @interface declaration
+        assert!(source[add.byte_range.0..add.byte_range.1].ends_with(b"}"));
+
+        assert!(signatures
+            .iter()
+            .any(|s| s.qualified_name == "Calculator.shared"));
+        assert!(signatures.iter().any(|s| s.name == "helper"));
+        assert_eq!(
+            signatures
+                .iter()
+                .filter(|s| s.qualified_name == "Calculator.add:to:")
+                .count(),
+            1
+        );
+        assert!(add
+            .imports
+            .iter()
+            .any(|i| i.path == "Foundation/Foundation.h"));
+    }
+
+    #[test]
+    fn test_objc_byte_ranges_survive_invalid_utf8() {
+        // Latin-1 encoded comment before the method
+        let source = b"// caf\xe9 \xe9t\xe9\n@implementation Cafe\n- (void)brew {\n}\n@end\n";
+        let signatures = ObjCParser::new().get_signatures(source).unwrap();
+
+        let brew = signatures
+            .iter()
+            .find(|s| s.qualified_name == "Cafe.brew")
+            .expect("method after invalid UTF-8 should be found");
+        assert_eq!(
+            &source[brew.byte_range.0..brew.byte_range.1],
+            b"- (void)brew {\n}"
+        );
+    }
+}
+
 #[cfg(all(test, feature = "cli"))]
 mod stack_overflow_regression_tests {
     use crate::cli::LeIndex;
@@ -25,6 +25,17 @@ pub enum Error {
     #[error("Unsupported language: {0}")]
     UnsupportedLanguage(String),
 
+    /// A tree-sitter query failed to compile
+    #[error("Invalid query at {row}:{column}: {message}")]
+    InvalidQuery {
+        /// Zero-based row of the offending query text
+        row: usize,
+        /// Zero-based column of the offending query text
+        column: usize,
+        /// Compiler message from tree-sitter
+        message: String,
+    },
+
     /// Input/Output error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -83,6 +94,13 @@ pub struct SignatureInfo {
     /// Cyclomatic complexity extracted from AST
     #[serde(default)]
     pub cyclomatic_complexity: u32,
+
+    /// Base classes, interfaces or implemented traits declared in source
+    ///
+    /// Set on type declarations; for methods, holds the bases of the
+    /// enclosing type when the parser has no separate type signature.
+    #[serde(default)]
+    pub base_types: Vec<String>,
 }
 
 /// Function parameter
@@ -577,6 +595,28 @@ pub mod languages {
         }
     }
 
+    /// Objective-C language support.
+    ///
+    /// No Objective-C grammar is bundled; the C grammar is used for the
+    /// C-compatible parts of the source (see `parse::objc`). Objective-C++
+    /// (`.mm`) is not supported, since the C grammar cannot parse its C++.
+    pub mod objc {
+        use super::{Language, LanguageConfig};
+        use once_cell::sync::Lazy;
+
+        /// Objective-C language configuration.
+        pub static CONFIG: Lazy<LanguageConfig> = Lazy::new(|| LanguageConfig {
+            name: "Objective-C".to_string(),
+            extensions: vec!["m".to_string()],
+            queries: LanguageConfig::default_queries(),
+        });
+
+        /// Get the tree-sitter language used for Objective-C.
+        pub fn language() -> Language {
+            tree_sitter_c::LANGUAGE.into()
+        }
+    }
+
     /// Bash language support.
     pub mod bash {
         use super::{Language, LanguageConfig};
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -15,10 +16,24 @@ pub struct CachedSummary<T> {
     /// Optional options-sensitive discriminator (phases 3..=5).
     #[serde(default)]
     pub options_hash: Option<String>,
+    /// Fingerprints of the upstream phase summaries the payload was computed
+    /// from, by phase number (see [`summary_fingerprint`]).
+    #[serde(default)]
+    pub upstream: BTreeMap<u8, String>,
     /// Stored payload.
     pub payload: T,
 }
 
+/// Content fingerprint of a phase summary.
+///
+/// Recorded by downstream phases so their cache entries are invalidated when
+/// an upstream summary changes under the same generation and options.
+pub fn summary_fingerprint<T: Serialize>(summary: &T) -> Result<String> {
+    Ok(blake3::hash(&serde_json::to_vec(summary)?)
+        .to_hex()
+        .to_string())
+}
+
 /// Lightweight file-backed cache for phase summaries.
 pub struct PhaseCache {
     root: PathBuf,
@@ -89,6 +104,30 @@ impl PhaseCache {
         Ok(Some(cached))
     }
 
+    /// Load a cached summary computed from the given upstream summaries.
+    ///
+    /// An entry recorded against different upstream fingerprints is a miss.
+    pub fn load_with_upstream<T: for<'de> Deserialize<'de>>(
+        &self,
+        project_id: &str,
+        generation: &str,
+        phase: u8,
+        options_hash: Option<&str>,
+        upstream: &BTreeMap<u8, String>,
+    ) -> Result<Option<CachedSummary<T>>> {
+        Ok(self
+            .load_with_options(project_id, generation, phase, options_hash)?
+            .filter(|cached| cached.upstream == *upstream))
+    }
+
+    /// Drop a cache entry, if present.
+    pub fn remove(&self, project_id: &str, generation: &str, phase: u8) -> Result<()> {
+        match fs::remove_file(self.path_for(project_id, generation, phase, None)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
     /// Persist a summary cache entry.
     pub fn save<T: Serialize>(
         &self,
@@ -108,6 +147,27 @@ impl PhaseCache {
         phase: u8,
         options_hash: Option<&str>,
         payload: &T,
+    ) -> Result<()> {
+        self.save_with_upstream(
+            project_id,
+            generation,
+            phase,
+            options_hash,
+            &BTreeMap::new(),
+            payload,
+        )
+    }
+
+    /// Persist a summary cache entry along with the fingerprints of the
+    /// upstream summaries it was computed from.
+    pub fn save_with_upstream<T: Serialize>(
+        &self,
+        project_id: &str,
+        generation: &str,
+        phase: u8,
+        options_hash: Option<&str>,
+        upstream: &BTreeMap<u8, String>,
+        payload: &T,
     ) -> Result<()> {
         let path = self.path_for(project_id, generation, phase, options_hash);
         if let Some(parent) = path.parent() {
@@ -119,6 +179,7 @@ impl PhaseCache {
             generation: generation.to_string(),
             phase,
             options_hash: options_hash.map(|v| v.to_string()),
+            upstream: upstream.clone(),
             payload,
         };
 
@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cooperative cancellation handle for phase analysis.
+///
+/// Clones share the same flag, so a caller can keep one handle and pass
+/// another into `run_phase_analysis_with_cancellation`. An optional deadline
+/// turns the token into a wall-clock budget checked at the same points.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    flag: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Create a token that is only cancelled explicitly.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a token that also expires once `deadline` is reached.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            flag: Arc::new(AtomicBool::new(false)),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Create a token that expires `timeout` from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Request cancellation for every clone of this token.
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+
+    /// True once cancellation was requested or the deadline elapsed.
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+            || self
+                .deadline
+                .map(|deadline| Instant::now() >= deadline)
+                .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn elapsed_deadline_reports_cancelled() {
+        let token = CancellationToken::with_timeout(Duration::from_millis(0));
+        assert!(token.is_cancelled());
+
+        let token = CancellationToken::with_timeout(Duration::from_secs(3600));
+        assert!(!token.is_cancelled());
+    }
+}
@@ -1,15 +1,19 @@
 use crate::graph::{extract_pdg_from_signatures, pdg::ProgramDependenceGraph};
 use crate::parse::{parallel::ParsingResult, prelude::ParallelParser, traits::SignatureInfo};
 use crate::phase::docs::{analyze_docs, DocsSummary};
-use crate::phase::freshness::{compute_freshness, FreshnessState};
+use crate::phase::freshness::{
+    compute_freshness, compute_symbol_freshness, symbol_hashes, FreshnessState, SymbolFreshness,
+};
 use crate::phase::options::PhaseOptions;
 use crate::phase::pdg_utils::merge_pdgs;
 use crate::phase::utils::{collect_files, hash_inventory};
 use crate::storage::{
     pdg_store::{
-        delete_file_data, get_indexed_files, load_pdg, pdg_exists, save_pdg, update_indexed_file,
+        delete_file_data, delete_symbol_hashes, get_indexed_files, get_symbol_hashes, load_pdg,
+        pdg_exists, replace_symbol_hashes, save_pdg, update_indexed_file,
     },
     schema::Storage,
+    UniqueProjectId,
 };
 use anyhow::{bail, Context, Result};
 use std::collections::HashMap;
@@ -31,6 +35,8 @@ pub struct PhaseExecutionContext {
     pub changed_files: Vec<PathBuf>,
     /// Deleted file paths detected by freshness checks.
     pub deleted_files: Vec<String>,
+    /// Symbol-level changes within the re-parsed files.
+    pub symbol_freshness: SymbolFreshness,
 
     /// Parse outputs reused by phases.
     pub parse_results: Vec<ParsingResult>,
@@ -57,8 +63,8 @@ impl PhaseExecutionContext {
             .canonicalize()
             .with_context(|| format!("failed to canonicalize root {}", options.root.display()))?;
 
-        let project_id = project_id(&root);
         let storage = open_storage(&root)?;
+        let project_id = project_id(&storage, &root);
         let collected = collect_files(&root, options)?;
         let inventory = hash_inventory(&collected.code_files)?;
 
@@ -72,6 +78,7 @@ impl PhaseExecutionContext {
             file_inventory: freshness.file_inventory.clone(),
             changed_files: freshness.changed_files.clone(),
             deleted_files: freshness.deleted_files.clone(),
+            symbol_freshness: SymbolFreshness::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg: ProgramDependenceGraph::new(),
@@ -109,6 +116,19 @@ impl PhaseExecutionContext {
                         );
                     }
                 }
+                let normalized = normalize_file_key(&self.root, path);
+                let stored = get_symbol_hashes(&self.storage, &self.project_id, &normalized)
+                    .unwrap_or_default();
+                self.symbol_freshness
+                    .extend(compute_symbol_freshness(&HashMap::new(), &stored));
+                if let Err(e) =
+                    delete_symbol_hashes(&mut self.storage, &self.project_id, &normalized)
+                {
+                    warn!(
+                        "Phase context: failed to delete symbol hashes for '{}' (deleted file): {}",
+                        normalized, e
+                    );
+                }
             }
 
             let parse_paths = freshness.changed_files.clone();
@@ -129,6 +149,11 @@ impl PhaseExecutionContext {
                     .collect::<HashMap<_, _>>();
 
                 for (file_path, (language, signatures)) in &self.signatures_by_file {
+                    let normalized = normalize_file_key(&self.root, file_path);
+                    let stored_symbols =
+                        get_symbol_hashes(&self.storage, &self.project_id, &normalized)
+                            .unwrap_or_default();
+
                     // Parse succeeded: now safe to replace stale file graph/state.
                     for key in equivalent_file_keys(&self.root, file_path) {
                         pdg.remove_file(&key);
@@ -155,7 +180,21 @@ impl PhaseExecutionContext {
                     );
                     merge_pdgs(&mut pdg, &file_pdg);
 
-                    let normalized = normalize_file_key(&self.root, file_path);
+                    let current_symbols = symbol_hashes(&file_pdg, file_path, source_bytes);
+                    self.symbol_freshness
+                        .extend(compute_symbol_freshness(&current_symbols, &stored_symbols));
+                    if let Err(e) = replace_symbol_hashes(
+                        &mut self.storage,
+                        &self.project_id,
+                        &normalized,
+                        &current_symbols,
+                    ) {
+                        warn!(
+                            "Phase context: failed to store symbol hashes for '{}' (incremental): {}",
+                            normalized, e
+                        );
+                    }
+
                     if let Some(hash) = inventory_hashes.get(&normalized) {
                         if let Err(e) = update_indexed_file(
                             &mut self.storage,
@@ -206,6 +245,24 @@ impl PhaseExecutionContext {
             let file_pdg =
                 extract_pdg_from_signatures(signatures.clone(), source_bytes, file_path, language);
             merge_pdgs(&mut pdg, &file_pdg);
+
+            // Nothing is reused on the cold path, but record the hashes for
+            // the next incremental run
+            let normalized = normalize_file_key(&self.root, file_path);
+            let current_symbols = symbol_hashes(&file_pdg, file_path, source_bytes);
+            self.symbol_freshness
+                .extend(compute_symbol_freshness(&current_symbols, &HashMap::new()));
+            if let Err(e) = replace_symbol_hashes(
+                &mut self.storage,
+                &self.project_id,
+                &normalized,
+                &current_symbols,
+            ) {
+                warn!(
+                    "Phase context: failed to store symbol hashes for '{}' (cold path): {}",
+                    normalized, e
+                );
+            }
         }
         crate::phase::pdg_utils::relink_external_import_edges(
             &mut pdg,
@@ -281,10 +338,14 @@ fn source_bytes_from_results(root: &Path, results: &[ParsingResult]) -> HashMap<
         .collect()
 }
 
-fn project_id(root: &Path) -> String {
-    root.file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or("unknown")
+/// Key the project's rows are stored under: the unique ID `LeIndex`
+/// recorded for it, or its path-hash ID if it was never opened by `LeIndex`
+fn project_id(storage: &Storage, root: &Path) -> String {
+    storage
+        .stored_project_id(root)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| UniqueProjectId::generate(root, &[]))
         .to_string()
 }
 
@@ -339,6 +400,14 @@ fn open_storage(root: &Path) -> Result<Storage> {
     let dir = root.join(".leindex");
     std::fs::create_dir_all(&dir).context("failed creating .leindex directory")?;
     let db_path = dir.join("leindex.db");
+    // Phase analysis has no access to the project's encryption key
+    if crate::storage::schema::is_encrypted_database(&db_path) {
+        return Err(crate::storage::StorageError::EncryptionUnsupported {
+            path: db_path.display().to_string(),
+            operation: "phase analysis",
+        }
+        .into());
+    }
     Storage::open(db_path).context("failed opening phase storage")
 }
 
@@ -365,9 +434,13 @@ mod tests {
                 imports: Vec::new(),
                 byte_range: (0, 10),
                 cyclomatic_complexity: 0,
+                base_types: Vec::new(),
             }],
             error: None,
             parse_time_ms: 1,
+            parse_duration: std::time::Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
             source_bytes: None,
         };
 
@@ -377,6 +450,9 @@ mod tests {
             signatures: Vec::new(),
             error: Some("Parse error: test".to_string()),
             parse_time_ms: 0,
+            parse_duration: std::time::Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
             source_bytes: None,
         };
 
@@ -404,9 +480,13 @@ mod tests {
                 imports: Vec::new(),
                 byte_range: (0, 1),
                 cyclomatic_complexity: 0,
+                base_types: Vec::new(),
             }],
             error: None,
             parse_time_ms: 1,
+            parse_duration: std::time::Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
             source_bytes: None,
         };
 
@@ -463,7 +543,7 @@ mod tests {
         let missing_file = root.join("src/missing.rs");
 
         let storage = open_storage(&root).expect("open storage");
-        let project_id = project_id(&root);
+        let project_id = project_id(&storage, &root);
 
         let mut context = PhaseExecutionContext {
             root: root.clone(),
@@ -472,6 +552,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: vec![missing_file.clone()],
             deleted_files: Vec::new(),
+            symbol_freshness: SymbolFreshness::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg: ProgramDependenceGraph::new(),
@@ -1,3 +1,4 @@
+use crate::tokenizer::Tokenizer;
 use serde::{Deserialize, Serialize};
 
 /// Output formatting mode for phase reports.
@@ -53,6 +54,15 @@ impl TokenFormatter {
         out.push_str("\n\n…[truncated]");
         out
     }
+
+    /// Truncate a string to a token budget as counted by `tokenizer`.
+    pub fn truncate_tokens(input: &str, max_tokens: usize, tokenizer: &dyn Tokenizer) -> String {
+        let kept = tokenizer.truncate_to_tokens(input, max_tokens);
+        if kept.len() == input.len() {
+            return input.to_string();
+        }
+        format!("{}\n\n…[truncated]", kept)
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +81,21 @@ mod tests {
         assert!(value.contains("truncated"));
     }
 
+    #[test]
+    fn truncate_tokens_uses_tokenizer_count() {
+        use crate::tokenizer::SubwordTokenizer;
+
+        let code = "a(b,c);d(e,f);g(h,i);";
+        assert_eq!(
+            TokenFormatter::truncate_tokens(code, 100, &SubwordTokenizer),
+            code
+        );
+        let value = TokenFormatter::truncate_tokens(code, 7, &SubwordTokenizer);
+        assert!(value.starts_with("a(b,c);"));
+        assert!(!value.contains("d("));
+        assert!(value.contains("truncated"));
+    }
+
     #[test]
     fn format_mode_parse_and_default_char_targets() {
         assert_eq!(FormatMode::parse("ultra"), Some(FormatMode::Ultra));
@@ -1,3 +1,4 @@
+use crate::graph::pdg::ProgramDependenceGraph;
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -68,6 +69,83 @@ pub fn compute_freshness(
     })
 }
 
+/// Symbol-level changes within re-parsed files.
+///
+/// A changed file only changes the symbols whose own source text changed;
+/// per-symbol phase results for the others can be reused.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolFreshness {
+    /// Symbols that are new or whose source text changed.
+    pub changed_symbols: HashSet<String>,
+    /// Symbols of re-parsed files whose source text is unchanged.
+    pub unchanged_symbols: HashSet<String>,
+    /// Symbols that no longer exist.
+    pub deleted_symbols: HashSet<String>,
+}
+
+impl SymbolFreshness {
+    /// Add the changes of another file.
+    pub fn extend(&mut self, other: SymbolFreshness) {
+        self.changed_symbols.extend(other.changed_symbols);
+        self.unchanged_symbols.extend(other.unchanged_symbols);
+        self.deleted_symbols.extend(other.deleted_symbols);
+    }
+
+    /// Whether any symbol is new, changed or deleted.
+    pub fn has_changes(&self) -> bool {
+        !self.changed_symbols.is_empty() || !self.deleted_symbols.is_empty()
+    }
+
+    /// Whether per-symbol results for `symbol_id` must be recomputed.
+    ///
+    /// Only symbols of re-parsed files were compared, so symbols of other
+    /// files count as changed too.
+    pub fn is_changed(&self, symbol_id: &str) -> bool {
+        !self.unchanged_symbols.contains(symbol_id)
+    }
+}
+
+/// Hash the source text of each node of `file_path`, keyed by node id.
+///
+/// Only the bytes in the node's `byte_range` are hashed, so a symbol that
+/// merely moved within the file keeps its hash.
+pub fn symbol_hashes(
+    pdg: &ProgramDependenceGraph,
+    file_path: &str,
+    source: &[u8],
+) -> HashMap<String, String> {
+    pdg.nodes_in_file(file_path)
+        .into_iter()
+        .filter_map(|id| pdg.get_node(id))
+        .map(|node| {
+            let (start, end) = node.byte_range;
+            let text = source.get(start..end).unwrap_or_default();
+            (node.id.clone(), blake3::hash(text).to_hex().to_string())
+        })
+        .collect()
+}
+
+/// Compare a file's current symbol hashes against the stored ones.
+pub fn compute_symbol_freshness(
+    current: &HashMap<String, String>,
+    stored: &HashMap<String, String>,
+) -> SymbolFreshness {
+    let mut freshness = SymbolFreshness::default();
+    for (id, hash) in current {
+        if stored.get(id) == Some(hash) {
+            freshness.unchanged_symbols.insert(id.clone());
+        } else {
+            freshness.changed_symbols.insert(id.clone());
+        }
+    }
+    freshness.deleted_symbols = stored
+        .keys()
+        .filter(|id| !current.contains_key(*id))
+        .cloned()
+        .collect();
+    freshness
+}
+
 fn normalize_key(root: &Path, key: &str) -> String {
     let path = Path::new(key);
 
@@ -123,4 +201,65 @@ mod tests {
         assert!(freshness.changed_files.is_empty());
         assert!(freshness.deleted_files.is_empty());
     }
+
+    fn two_function_pdg(file_path: &str, source: &str) -> ProgramDependenceGraph {
+        use crate::graph::pdg::{Node, NodeType};
+
+        let mut pdg = ProgramDependenceGraph::new();
+        for name in ["alpha", "beta"] {
+            let start = source.find(&format!("fn {}", name)).expect("function");
+            let end = start + source[start..].find('}').expect("body end") + 1;
+            pdg.add_node(Node {
+                id: format!("{}:{}", file_path, name),
+                node_type: NodeType::Function,
+                name: name.to_string(),
+                file_path: file_path.into(),
+                byte_range: (start, end),
+                complexity: 1,
+                language: "rust".to_string(),
+            });
+        }
+        pdg
+    }
+
+    #[test]
+    fn symbol_freshness_marks_only_the_edited_function() {
+        let before = "fn alpha() -> u32 {\n    1\n}\n\nfn beta() -> u32 {\n    2\n}\n";
+        let stored = symbol_hashes(
+            &two_function_pdg("src/lib.rs", before),
+            "src/lib.rs",
+            before.as_bytes(),
+        );
+
+        // Edit the body of beta only
+        let after = "fn alpha() -> u32 {\n    1\n}\n\nfn beta() -> u32 {\n    2 + 40\n}\n";
+        let current = symbol_hashes(
+            &two_function_pdg("src/lib.rs", after),
+            "src/lib.rs",
+            after.as_bytes(),
+        );
+        let freshness = compute_symbol_freshness(&current, &stored);
+        assert_eq!(
+            freshness.changed_symbols,
+            HashSet::from(["src/lib.rs:beta".to_string()])
+        );
+        assert_eq!(
+            freshness.unchanged_symbols,
+            HashSet::from(["src/lib.rs:alpha".to_string()])
+        );
+        assert!(freshness.deleted_symbols.is_empty());
+        assert!(freshness.is_changed("src/lib.rs:beta"));
+        assert!(!freshness.is_changed("src/lib.rs:alpha"));
+
+        // Text inserted above both functions moves them without changing them
+        let moved = "// header\nfn alpha() -> u32 {\n    1\n}\n\nfn beta() -> u32 {\n    2\n}\n";
+        let current = symbol_hashes(
+            &two_function_pdg("src/lib.rs", moved),
+            "src/lib.rs",
+            moved.as_bytes(),
+        );
+        assert!(compute_symbol_freshness(&current, &stored)
+            .changed_symbols
+            .is_empty());
+    }
 }
@@ -4,6 +4,8 @@
 
 /// Summary cache helpers.
 pub mod cache;
+/// Cooperative cancellation and deadlines for phase runs.
+pub mod cancel;
 /// Shared runtime context and incremental preparation pipeline.
 pub mod context;
 /// Optional markdown/text analysis utilities.
@@ -33,16 +35,20 @@ pub mod recommendations;
 /// Shared file/path utility helpers.
 pub mod utils;
 
+use crate::storage::analytics::save_unresolved_imports;
+use crate::tokenizer::TokenizerKind;
 use anyhow::Result;
-use cache::PhaseCache;
+use cache::{summary_fingerprint, PhaseCache};
 use context::PhaseExecutionContext;
 use format::TokenFormatter;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
+pub use cancel::CancellationToken;
 pub use format::FormatMode;
 pub use options::{DocsMode, PhaseOptions};
 pub use phase1::Phase1Summary;
-pub use phase2::Phase2Summary;
+pub use phase2::{Phase2Summary, UnresolvedImport};
 pub use phase3::Phase3Summary;
 pub use phase4::Phase4Summary;
 pub use phase5::Phase5Summary;
@@ -65,6 +71,14 @@ impl PhaseSelection {
             None
         }
     }
+
+    /// Whether `phase` runs under this selection.
+    pub fn includes(self, phase: u8) -> bool {
+        match self {
+            Self::Single(selected) => selected == phase,
+            Self::All => (1..=5).contains(&phase),
+        }
+    }
 }
 
 /// Top-level phase-analysis report payload.
@@ -78,6 +92,9 @@ pub struct PhaseAnalysisReport {
     pub executed_phases: Vec<u8>,
     /// Whether any summary cache hit occurred.
     pub cache_hit: bool,
+    /// Phases whose summary came from the cache.
+    #[serde(default)]
+    pub cached_phases: Vec<u8>,
     /// Changed file count.
     pub changed_files: usize,
     /// Deleted file count.
@@ -96,19 +113,46 @@ pub struct PhaseAnalysisReport {
 
     /// Human-readable report text.
     pub formatted_output: String,
+    /// True when the run stopped early because of cancellation or deadline.
+    ///
+    /// `executed_phases` then lists only the phases completed before the stop.
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 /// Run phase analysis for a project with incremental freshness and shared context.
 pub fn run_phase_analysis(
     options: PhaseOptions,
     selection: PhaseSelection,
+) -> Result<PhaseAnalysisReport> {
+    run_phase_analysis_with_cancellation(options, selection, &CancellationToken::new())
+}
+
+/// Run phase analysis, stopping between phases once `cancel` fires.
+///
+/// A cancelled run still returns `Ok` with the summaries completed so far and
+/// `cancelled` set, so servers can enforce request time limits without losing work.
+pub fn run_phase_analysis_with_cancellation(
+    options: PhaseOptions,
+    selection: PhaseSelection,
+    cancel: &CancellationToken,
+) -> Result<PhaseAnalysisReport> {
+    run_phases(options, selection, cancel, |_| {})
+}
+
+fn run_phases(
+    options: PhaseOptions,
+    selection: PhaseSelection,
+    cancel: &CancellationToken,
+    mut on_phase_complete: impl FnMut(u8),
 ) -> Result<PhaseAnalysisReport> {
     let options = options.normalized();
     let context = PhaseExecutionContext::prepare(&options)?;
     let cache = PhaseCache::new(&context.root);
 
     let mut executed_phases = Vec::new();
-    let mut cache_hit = false;
+    let mut cached_phases = Vec::new();
+    let mut cancelled = false;
 
     let mut phase1_summary = None;
     let mut phase2_summary = None;
@@ -116,35 +160,48 @@ pub fn run_phase_analysis(
     let mut phase4_summary = None;
     let mut phase5_summary = None;
 
-    if should_run(1, selection) {
+    if proceed(1, selection, cancel, &mut cancelled) {
         if let Some(cached) =
             cache.load::<Phase1Summary>(&context.project_id, &context.generation_hash, 1)?
         {
             phase1_summary = Some(cached.payload);
-            cache_hit = true;
+            cached_phases.push(1);
         } else {
             let value = phase1::run(&context);
             cache.save(&context.project_id, &context.generation_hash, 1, &value)?;
             phase1_summary = Some(value);
         }
+        if let Some(summary) = phase1_summary.as_mut() {
+            summary.apply_thresholds(&options);
+        }
         executed_phases.push(1);
+        on_phase_complete(1);
     }
 
-    if should_run(2, selection) {
+    if proceed(2, selection, cancel, &mut cancelled) {
         if let Some(cached) =
             cache.load::<Phase2Summary>(&context.project_id, &context.generation_hash, 2)?
         {
             phase2_summary = Some(cached.payload);
-            cache_hit = true;
+            cached_phases.push(2);
         } else {
             let value = phase2::run(&context);
             cache.save(&context.project_id, &context.generation_hash, 2, &value)?;
+            save_unresolved_imports(
+                &context.storage,
+                &context.project_id,
+                value
+                    .unresolved_imports
+                    .iter()
+                    .map(|import| (import.path.as_str(), import.file.as_str())),
+            )?;
             phase2_summary = Some(value);
         }
         executed_phases.push(2);
+        on_phase_complete(2);
     }
 
-    if should_run(3, selection) {
+    if proceed(3, selection, cancel, &mut cancelled) {
         let phase3_key = options_hash_for_phase(3, &options);
         if let Some(cached) = cache.load_with_options::<Phase3Summary>(
             &context.project_id,
@@ -153,7 +210,7 @@ pub fn run_phase_analysis(
             phase3_key.as_deref(),
         )? {
             phase3_summary = Some(cached.payload);
-            cache_hit = true;
+            cached_phases.push(3);
         } else {
             let value = phase3::run(&context, &options);
             cache.save_with_options(
@@ -166,9 +223,11 @@ pub fn run_phase_analysis(
             phase3_summary = Some(value);
         }
         executed_phases.push(3);
+        on_phase_complete(3);
     }
 
-    if should_run(4, selection) {
+    let mut symbol_impacts_saved = false;
+    if proceed(4, selection, cancel, &mut cancelled) {
         let phase4_key = options_hash_for_phase(4, &options);
         if let Some(cached) = cache.load_with_options::<Phase4Summary>(
             &context.project_id,
@@ -177,9 +236,24 @@ pub fn run_phase_analysis(
             phase4_key.as_deref(),
         )? {
             phase4_summary = Some(cached.payload);
-            cache_hit = true;
+            cached_phases.push(4);
         } else {
-            let value = phase4::run(&context, &options);
+            let previous = cache
+                .load::<phase4::SymbolImpacts>(
+                    &context.project_id,
+                    phase4::SYMBOL_IMPACTS_GENERATION,
+                    4,
+                )?
+                .map(|cached| cached.payload)
+                .unwrap_or_default();
+            let (value, impacts) = phase4::run_with_previous(&context, &options, &previous);
+            cache.save(
+                &context.project_id,
+                phase4::SYMBOL_IMPACTS_GENERATION,
+                4,
+                &impacts,
+            )?;
+            symbol_impacts_saved = true;
             cache.save_with_options(
                 &context.project_id,
                 &context.generation_hash,
@@ -190,9 +264,15 @@ pub fn run_phase_analysis(
             phase4_summary = Some(value);
         }
         executed_phases.push(4);
+        on_phase_complete(4);
+    }
+    // Symbol impacts can only be reused against the symbol hashes they were
+    // computed with; once those move on without a fresh phase 4, drop them
+    if !symbol_impacts_saved && context.symbol_freshness.has_changes() {
+        cache.remove(&context.project_id, phase4::SYMBOL_IMPACTS_GENERATION, 4)?;
     }
 
-    if should_run(5, selection) {
+    if proceed(5, selection, cancel, &mut cancelled) {
         let p1 = phase1_summary
             .clone()
             .unwrap_or_else(|| phase1::run(&context));
@@ -206,27 +286,38 @@ pub fn run_phase_analysis(
             .clone()
             .unwrap_or_else(|| phase4::run(&context, &options));
 
+        // Phase 5 is derived from the other summaries, and phase 1 applies
+        // option thresholds after caching, so key it on what it consumed too
+        let upstream = BTreeMap::from([
+            (1, summary_fingerprint(&p1)?),
+            (2, summary_fingerprint(&p2)?),
+            (3, summary_fingerprint(&p3)?),
+            (4, summary_fingerprint(&p4)?),
+        ]);
         let phase5_key = options_hash_for_phase(5, &options);
-        if let Some(cached) = cache.load_with_options::<Phase5Summary>(
+        if let Some(cached) = cache.load_with_upstream::<Phase5Summary>(
             &context.project_id,
             &context.generation_hash,
             5,
             phase5_key.as_deref(),
+            &upstream,
         )? {
             phase5_summary = Some(cached.payload);
-            cache_hit = true;
+            cached_phases.push(5);
         } else {
             let value = phase5::run(&context, &p1, &p2, &p3, &p4);
-            cache.save_with_options(
+            cache.save_with_upstream(
                 &context.project_id,
                 &context.generation_hash,
                 5,
                 phase5_key.as_deref(),
+                &upstream,
                 &value,
             )?;
             phase5_summary = Some(value);
         }
         executed_phases.push(5);
+        on_phase_complete(5);
     }
 
     let formatted_output = format_report(
@@ -237,14 +328,18 @@ pub fn run_phase_analysis(
         phase3_summary.as_ref(),
         phase4_summary.as_ref(),
         phase5_summary.as_ref(),
+        cancelled,
         options.max_output_chars,
+        options.max_output_tokens,
+        options.tokenizer,
     );
 
     Ok(PhaseAnalysisReport {
         project_id: context.project_id,
         generation: context.generation_hash,
         executed_phases,
-        cache_hit,
+        cache_hit: !cached_phases.is_empty(),
+        cached_phases,
         changed_files: context.changed_files.len(),
         deleted_files: context.deleted_files.len(),
         phase1: phase1_summary,
@@ -253,9 +348,27 @@ pub fn run_phase_analysis(
         phase4: phase4_summary,
         phase5: phase5_summary,
         formatted_output,
+        cancelled,
     })
 }
 
+/// Decide whether `phase` should start, latching `cancelled` once the token fires.
+fn proceed(
+    phase: u8,
+    selection: PhaseSelection,
+    cancel: &CancellationToken,
+    cancelled: &mut bool,
+) -> bool {
+    if *cancelled || !should_run(phase, selection) {
+        return false;
+    }
+    if cancel.is_cancelled() {
+        *cancelled = true;
+        return false;
+    }
+    true
+}
+
 fn should_run(phase: u8, selection: PhaseSelection) -> bool {
     match selection {
         PhaseSelection::Single(p) => p == phase,
@@ -289,7 +402,10 @@ fn format_report(
     phase3: Option<&Phase3Summary>,
     phase4: Option<&Phase4Summary>,
     phase5: Option<&Phase5Summary>,
+    cancelled: bool,
     max_chars: usize,
+    max_tokens: Option<usize>,
+    tokenizer: TokenizerKind,
 ) -> String {
     let mut lines = Vec::new();
     lines.push(format!(
@@ -304,15 +420,7 @@ fn format_report(
     ));
 
     if let Some(p1) = phase1 {
-        let avg_completeness = if p1.parser_completeness.is_empty() {
-            0.0
-        } else {
-            p1.parser_completeness
-                .iter()
-                .map(|entry| entry.score)
-                .sum::<f32>()
-                / p1.parser_completeness.len() as f32
-        };
+        let avg_completeness = p1.average_completeness();
 
         if p1.cache_hit {
             lines.push(format!(
@@ -327,6 +435,13 @@ fn format_report(
                 p1.total_files, p1.parsed_files, p1.parse_failures, p1.signatures, avg_completeness
             ));
         }
+        if !p1.meets_threshold {
+            lines.push(format!(
+                "phase1: threshold not met (parser_completeness_avg={:.2} parse_failure_ratio={:.2})",
+                avg_completeness,
+                p1.parse_failure_ratio()
+            ));
+        }
     }
 
     if let Some(p2) = phase2 {
@@ -334,6 +449,21 @@ fn format_report(
             "phase2: import_edges internal={} external={} unresolved_modules={}",
             p2.internal_import_edges, p2.external_import_edges, p2.unresolved_modules
         ));
+        if !p2.unresolved_imports.is_empty() {
+            let shown = p2
+                .unresolved_imports
+                .iter()
+                .take(5)
+                .map(|import| format!("{} ({})", import.path, import.file))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let more = p2.unresolved_imports.len().saturating_sub(5);
+            lines.push(if more > 0 {
+                format!("phase2: unresolved imports: {} (+{} more)", shown, more)
+            } else {
+                format!("phase2: unresolved imports: {}", shown)
+            });
+        }
     }
 
     if let Some(p3) = phase3 {
@@ -364,7 +494,20 @@ fn format_report(
         ));
     }
 
-    TokenFormatter::truncate(&lines.join("\n"), max_chars)
+    if cancelled {
+        lines.push(format!(
+            "cancelled: stopped after phases {:?}",
+            executed_phases
+        ));
+    }
+
+    let report = TokenFormatter::truncate(&lines.join("\n"), max_chars);
+    match max_tokens {
+        Some(max_tokens) => {
+            TokenFormatter::truncate_tokens(&report, max_tokens, tokenizer.build().as_ref())
+        }
+        None => report,
+    }
 }
 
 #[cfg(test)]
@@ -384,6 +527,10 @@ mod tests {
         );
         assert_eq!(PhaseSelection::from_number(0), None);
         assert_eq!(PhaseSelection::from_number(6), None);
+
+        assert!(PhaseSelection::All.includes(1));
+        assert!(PhaseSelection::Single(1).includes(1));
+        assert!(!PhaseSelection::Single(3).includes(1));
     }
 
     #[test]
@@ -407,6 +554,85 @@ mod tests {
         assert!(report.phase3.is_none());
     }
 
+    #[test]
+    fn max_output_tokens_caps_report_with_configured_tokenizer() {
+        use crate::tokenizer::Tokenizer;
+
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn f()->i32{1}\n").expect("write");
+
+        let report = run_phase_analysis(
+            PhaseOptions {
+                root: dir.path().to_path_buf(),
+                max_output_tokens: Some(20),
+                tokenizer: TokenizerKind::Subword,
+                ..PhaseOptions::default()
+            },
+            PhaseSelection::All,
+        )
+        .expect("phase run");
+
+        let kept = report
+            .formatted_output
+            .strip_suffix("\n\n…[truncated]")
+            .expect("report truncated");
+        assert!(crate::tokenizer::SubwordTokenizer.count_tokens(kept) <= 20);
+    }
+
+    #[test]
+    fn cancellation_after_phase_two_returns_partial_report() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn f()->i32{1}\n").expect("write");
+
+        let cancel = CancellationToken::new();
+        let report = run_phases(
+            PhaseOptions {
+                root: dir.path().to_path_buf(),
+                ..PhaseOptions::default()
+            },
+            PhaseSelection::All,
+            &cancel,
+            |phase| {
+                if phase == 2 {
+                    cancel.cancel();
+                }
+            },
+        )
+        .expect("phase run");
+
+        assert!(report.cancelled);
+        assert_eq!(report.executed_phases, vec![1, 2]);
+        assert!(report.phase1.is_some());
+        assert!(report.phase2.is_some());
+        assert!(report.phase3.is_none());
+        assert!(report.phase5.is_none());
+        assert!(report.formatted_output.contains("cancelled"));
+    }
+
+    #[test]
+    fn pre_cancelled_token_runs_no_phases() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn f()->i32{1}\n").expect("write");
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let report = run_phase_analysis_with_cancellation(
+            PhaseOptions {
+                root: dir.path().to_path_buf(),
+                ..PhaseOptions::default()
+            },
+            PhaseSelection::All,
+            &cancel,
+        )
+        .expect("phase run");
+
+        assert!(report.cancelled);
+        assert!(report.executed_phases.is_empty());
+    }
+
     #[test]
     fn phase3_cache_key_changes_with_top_n() {
         let dir = tempdir().expect("tempdir");
@@ -443,4 +669,46 @@ mod tests {
             "phase 3 cache must miss when top_n changes"
         );
     }
+
+    #[test]
+    fn phase5_cache_misses_when_an_upstream_summary_changes() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "use serde::Serialize;\npub fn a(){}\npub fn b(x:i32){ a() }\n",
+        )
+        .expect("write");
+        let options = || PhaseOptions {
+            root: dir.path().to_path_buf(),
+            ..PhaseOptions::default()
+        };
+
+        let first = run_phase_analysis(options(), PhaseSelection::All).expect("first run");
+        assert!(!first.cached_phases.contains(&5));
+        let second = run_phase_analysis(options(), PhaseSelection::All).expect("second run");
+        assert_eq!(second.generation, first.generation);
+        assert!(second.cached_phases.contains(&5));
+
+        // Change phase 2's summary without touching the generation or options
+        let cache = PhaseCache::new(&dir.path().canonicalize().expect("canonical root"));
+        let mut phase2 = cache
+            .load::<Phase2Summary>(&second.project_id, &second.generation, 2)
+            .expect("load phase 2")
+            .expect("phase 2 cached")
+            .payload;
+        phase2.unresolved_modules += 7;
+        cache
+            .save(&second.project_id, &second.generation, 2, &phase2)
+            .expect("save phase 2");
+
+        let third = run_phase_analysis(options(), PhaseSelection::All).expect("third run");
+        assert!(third.cached_phases.contains(&2));
+        assert!(
+            !third.cached_phases.contains(&5),
+            "phase 5 cache must miss when phase 2 changed"
+        );
+        let fourth = run_phase_analysis(options(), PhaseSelection::All).expect("fourth run");
+        assert!(fourth.cached_phases.contains(&5));
+    }
 }
@@ -1,4 +1,5 @@
 use crate::phase::format::FormatMode;
+use crate::tokenizer::TokenizerKind;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -59,6 +60,12 @@ pub struct PhaseOptions {
     pub top_n: usize,
     /// Max output characters.
     pub max_output_chars: usize,
+    /// Optional output budget in tokens, applied on top of `max_output_chars`.
+    #[serde(default)]
+    pub max_output_tokens: Option<usize>,
+    /// Tokenizer that counts `max_output_tokens`.
+    #[serde(default)]
+    pub tokenizer: TokenizerKind,
     /// Enable incremental freshness-aware updates.
     pub use_incremental_refresh: bool,
     /// Explicit opt-in for markdown/text analysis.
@@ -68,6 +75,12 @@ pub struct PhaseOptions {
     /// Keyword list used by phase-4 text signal hotspot heuristic.
     #[serde(default = "default_hotspot_keywords")]
     pub hotspot_keywords: Vec<String>,
+    /// Minimum average parser completeness score (0..=1) phase 1 must reach.
+    #[serde(default)]
+    pub min_parser_completeness: Option<f32>,
+    /// Maximum tolerated ratio of failed parses to parsed files (0..=1) in phase 1.
+    #[serde(default)]
+    pub max_parse_failure_ratio: Option<f32>,
 }
 
 impl Default for PhaseOptions {
@@ -80,10 +93,14 @@ impl Default for PhaseOptions {
             max_focus_files: 20,
             top_n: 10,
             max_output_chars: FormatMode::Balanced.default_max_chars(),
+            max_output_tokens: None,
+            tokenizer: TokenizerKind::default(),
             use_incremental_refresh: true,
             include_docs: false,
             docs_mode: DocsMode::Off,
             hotspot_keywords: default_hotspot_keywords(),
+            min_parser_completeness: None,
+            max_parse_failure_ratio: None,
         }
     }
 }
@@ -42,6 +42,19 @@ impl<R: PhaseRunner> OrchestrationEngine<R> {
         }
     }
 
+    /// Create an engine that delegates all phase execution to `runner`.
+    ///
+    /// Retry, timeout, and state tracking stay in the engine; the runner only
+    /// decides how a single selection is executed (locally, remotely, instrumented).
+    pub fn with_runner(runner: R) -> Self {
+        Self::new(runner)
+    }
+
+    /// Borrow the injected runner.
+    pub fn runner(&self) -> &R {
+        &self.runner
+    }
+
     /// Execute one orchestration request with retries.
     pub fn run(
         &mut self,
@@ -172,6 +185,7 @@ mod tests {
                     generation: "gen".to_string(),
                     executed_phases: vec![1],
                     cache_hit: false,
+                    cached_phases: Vec::new(),
                     changed_files: 0,
                     deleted_files: 0,
                     phase1: Some(Phase1Summary::default()),
@@ -180,6 +194,7 @@ mod tests {
                     phase4: None,
                     phase5: None,
                     formatted_output: "ok".to_string(),
+                    cancelled: false,
                 })
             }
         }
@@ -398,6 +413,74 @@ mod tests {
             .contains("timeout budget exceeded"));
     }
 
+    #[derive(Default)]
+    struct RecordingRunner {
+        calls: std::sync::Mutex<Vec<PhaseSelection>>,
+    }
+
+    impl PhaseRunner for RecordingRunner {
+        fn run(
+            &self,
+            _options: crate::phase::PhaseOptions,
+            selection: PhaseSelection,
+        ) -> Result<PhaseAnalysisReport> {
+            self.calls.lock().expect("calls lock").push(selection);
+            let executed_phases = match selection {
+                PhaseSelection::Single(phase) => vec![phase],
+                PhaseSelection::All => vec![1, 2, 3, 4, 5],
+            };
+            Ok(PhaseAnalysisReport {
+                project_id: "test".to_string(),
+                generation: "gen".to_string(),
+                executed_phases,
+                cache_hit: false,
+                cached_phases: Vec::new(),
+                changed_files: 0,
+                deleted_files: 0,
+                phase1: None,
+                phase2: None,
+                phase3: None,
+                phase4: None,
+                phase5: None,
+                formatted_output: "recorded".to_string(),
+                cancelled: false,
+            })
+        }
+    }
+
+    #[test]
+    fn injected_runner_is_invoked_in_request_order() {
+        let runner = std::sync::Arc::new(RecordingRunner::default());
+        let mut engine = OrchestrationEngine::with_runner(runner.clone());
+
+        for selection in [
+            PhaseSelection::Single(1),
+            PhaseSelection::Single(3),
+            PhaseSelection::All,
+        ] {
+            let report = engine.run(
+                OrchestrationContext::new(PhaseOptions::default()),
+                OrchestrationRequest {
+                    selection,
+                    ..OrchestrationRequest::default()
+                },
+            );
+            assert_eq!(report.state.status, RunStatus::Succeeded);
+            assert_eq!(report.formatted_output.as_deref(), Some("recorded"));
+        }
+
+        assert_eq!(engine.state.completed_phases, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            *runner.calls.lock().expect("calls lock"),
+            vec![
+                PhaseSelection::Single(1),
+                PhaseSelection::Single(3),
+                PhaseSelection::All
+            ]
+        );
+        assert_eq!(engine.runner().calls.lock().expect("calls lock").len(), 3);
+    }
+
     #[test]
     fn run_selection_executes_runner_path() {
         let runner = FlakyRunner {
@@ -8,6 +8,24 @@ pub trait PhaseRunner {
     fn run(&self, options: PhaseOptions, selection: PhaseSelection) -> Result<PhaseAnalysisReport>;
 }
 
+impl<R: PhaseRunner + ?Sized> PhaseRunner for &R {
+    fn run(&self, options: PhaseOptions, selection: PhaseSelection) -> Result<PhaseAnalysisReport> {
+        (**self).run(options, selection)
+    }
+}
+
+impl<R: PhaseRunner + ?Sized> PhaseRunner for Box<R> {
+    fn run(&self, options: PhaseOptions, selection: PhaseSelection) -> Result<PhaseAnalysisReport> {
+        (**self).run(options, selection)
+    }
+}
+
+impl<R: PhaseRunner + ?Sized> PhaseRunner for std::sync::Arc<R> {
+    fn run(&self, options: PhaseOptions, selection: PhaseSelection) -> Result<PhaseAnalysisReport> {
+        (**self).run(options, selection)
+    }
+}
+
 /// Default runner backed by `run_phase_analysis`.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DefaultPhaseRunner;
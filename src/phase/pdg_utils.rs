@@ -508,6 +508,7 @@ mod tests {
             imports,
             byte_range: (0, 10),
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         }
     }
 
@@ -524,6 +525,9 @@ mod tests {
             source_bytes: Some(src.to_vec()),
             error: None,
             parse_time_ms: 1,
+            parse_duration: std::time::Duration::ZERO,
+            fallback_grammar: None,
+            syntax_errors: Vec::new(),
         }
     }
 
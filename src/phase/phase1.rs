@@ -1,10 +1,11 @@
 use crate::parse::prelude::{score_languages, LanguageCompleteness};
 use crate::phase::context::PhaseExecutionContext;
+use crate::phase::options::PhaseOptions;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Structural scan output.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Phase1Summary {
     /// Number of source files considered.
     pub total_files: usize,
@@ -24,6 +25,66 @@ pub struct Phase1Summary {
     /// parse failure (parsed_files=0 because parsing broke).
     #[serde(default)]
     pub cache_hit: bool,
+    /// True when the run satisfies the configured completeness/failure thresholds.
+    #[serde(default = "default_meets_threshold")]
+    pub meets_threshold: bool,
+}
+
+fn default_meets_threshold() -> bool {
+    true
+}
+
+impl Default for Phase1Summary {
+    fn default() -> Self {
+        Self {
+            total_files: 0,
+            parsed_files: 0,
+            parse_failures: 0,
+            signatures: 0,
+            language_distribution: HashMap::new(),
+            parser_completeness: Vec::new(),
+            cache_hit: false,
+            meets_threshold: default_meets_threshold(),
+        }
+    }
+}
+
+impl Phase1Summary {
+    /// Mean parser completeness score across languages (0.0 when none were scored).
+    pub fn average_completeness(&self) -> f32 {
+        if self.parser_completeness.is_empty() {
+            return 0.0;
+        }
+        self.parser_completeness
+            .iter()
+            .map(|entry| entry.score)
+            .sum::<f32>()
+            / self.parser_completeness.len() as f32
+    }
+
+    /// Ratio of failed parses to files parsed in this run (0.0 when nothing was parsed).
+    pub fn parse_failure_ratio(&self) -> f32 {
+        if self.parsed_files == 0 {
+            return 0.0;
+        }
+        self.parse_failures as f32 / self.parsed_files as f32
+    }
+
+    /// Evaluate the thresholds in `options` and record the outcome in `meets_threshold`.
+    ///
+    /// Thresholds are applied after cache lookup so that changing them never
+    /// requires re-parsing.
+    pub fn apply_thresholds(&mut self, options: &PhaseOptions) {
+        let completeness_ok = options
+            .min_parser_completeness
+            .map(|min| self.average_completeness() >= min)
+            .unwrap_or(true);
+        let failures_ok = options
+            .max_parse_failure_ratio
+            .map(|max| self.parse_failure_ratio() <= max)
+            .unwrap_or(true);
+        self.meets_threshold = completeness_ok && failures_ok;
+    }
 }
 
 /// Run phase 1 structural scan.
@@ -74,6 +135,7 @@ pub fn run(context: &PhaseExecutionContext) -> Phase1Summary {
         language_distribution,
         parser_completeness,
         cache_hit,
+        meets_threshold: true,
     }
 }
 
@@ -188,6 +250,49 @@ mod tests {
         assert!((js.score - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn default_summary_meets_threshold() {
+        assert!(Phase1Summary::default().meets_threshold);
+    }
+
+    #[test]
+    fn failure_ratio_above_limit_fails_threshold() {
+        let mut summary = Phase1Summary {
+            total_files: 2,
+            parsed_files: 2,
+            parse_failures: 1,
+            parser_completeness: vec![LanguageCompleteness {
+                language: "rust".to_string(),
+                signatures: 3,
+                calls_ratio: 1.0,
+                imports_ratio: 1.0,
+                byte_range_ratio: 1.0,
+                score: 1.0,
+            }],
+            ..Phase1Summary::default()
+        };
+
+        summary.apply_thresholds(&PhaseOptions {
+            max_parse_failure_ratio: Some(0.25),
+            ..PhaseOptions::default()
+        });
+        assert!((summary.parse_failure_ratio() - 0.5).abs() < 0.001);
+        assert!(!summary.meets_threshold);
+
+        summary.apply_thresholds(&PhaseOptions {
+            max_parse_failure_ratio: Some(0.5),
+            min_parser_completeness: Some(0.9),
+            ..PhaseOptions::default()
+        });
+        assert!(summary.meets_threshold);
+
+        summary.apply_thresholds(&PhaseOptions {
+            min_parser_completeness: Some(1.1),
+            ..PhaseOptions::default()
+        });
+        assert!(!summary.meets_threshold);
+    }
+
     #[test]
     fn merge_completeness_empty_pdg() {
         let parse_scores = vec![LanguageCompleteness {
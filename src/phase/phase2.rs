@@ -13,6 +13,18 @@ pub struct Phase2Summary {
     pub unresolved_modules: usize,
     /// Confidence counters `(exact, heuristic, external)`.
     pub confidence_bands: (usize, usize, usize),
+    /// Import paths that fell back to synthetic external nodes, per importing file.
+    #[serde(default)]
+    pub unresolved_imports: Vec<UnresolvedImport>,
+}
+
+/// Import path that could not be resolved to a local node.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UnresolvedImport {
+    /// Import path as written in the source.
+    pub path: String,
+    /// File containing the import.
+    pub file: String,
 }
 
 /// Run phase 2 dependency-map analysis.
@@ -20,6 +32,7 @@ pub fn run(context: &PhaseExecutionContext) -> Phase2Summary {
     let mut internal = 0usize;
     let mut external = 0usize;
     let mut unresolved_modules = std::collections::HashSet::new();
+    let mut unresolved_imports = std::collections::BTreeSet::new();
     let mut exact = 0usize;
     let mut heuristic = 0usize;
 
@@ -31,7 +44,7 @@ pub fn run(context: &PhaseExecutionContext) -> Phase2Summary {
             continue;
         }
 
-        let Some((_, to)) = context.pdg.edge_endpoints(edge_idx) else {
+        let Some((from, to)) = context.pdg.edge_endpoints(edge_idx) else {
             continue;
         };
         let Some(target) = context.pdg.get_node(to) else {
@@ -42,6 +55,15 @@ pub fn run(context: &PhaseExecutionContext) -> Phase2Summary {
         if is_external {
             external += 1;
             unresolved_modules.insert(target.name.clone());
+            let file = context
+                .pdg
+                .get_node(from)
+                .map(|importer| importer.file_path.to_string())
+                .unwrap_or_else(|| target.file_path.to_string());
+            unresolved_imports.insert(UnresolvedImport {
+                path: target.name.clone(),
+                file,
+            });
         } else {
             internal += 1;
             // heuristic fallback: if this looks like a synthetic local heuristic module marker
@@ -58,6 +80,7 @@ pub fn run(context: &PhaseExecutionContext) -> Phase2Summary {
         external_import_edges: external,
         unresolved_modules: unresolved_modules.len(),
         confidence_bands: (exact, heuristic, external),
+        unresolved_imports: unresolved_imports.into_iter().collect(),
     }
 }
 
@@ -114,6 +137,7 @@ mod tests {
                 metadata: EdgeMetadata {
                     call_count: None,
                     confidence: None,
+                    call_target: None,
                     variable_name: None,
                 },
             },
@@ -126,6 +150,7 @@ mod tests {
                 metadata: EdgeMetadata {
                     call_count: None,
                     confidence: None,
+                    call_target: None,
                     variable_name: None,
                 },
             },
@@ -138,6 +163,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: Vec::new(),
             deleted_files: Vec::new(),
+            symbol_freshness: Default::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg,
@@ -151,5 +177,12 @@ mod tests {
         assert_eq!(summary.unresolved_modules, 1);
         assert_eq!(summary.confidence_bands.0, 1);
         assert_eq!(summary.confidence_bands.2, 1);
+        assert_eq!(
+            summary.unresolved_imports,
+            vec![UnresolvedImport {
+                path: "third.party.lib".to_string(),
+                file: "src/main.rs".to_string(),
+            }]
+        );
     }
 }
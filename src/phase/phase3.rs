@@ -108,6 +108,7 @@ mod tests {
                 metadata: EdgeMetadata {
                     call_count: None,
                     confidence: None,
+                    call_target: None,
                     variable_name: None,
                 },
             },
@@ -120,6 +121,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: Vec::new(),
             deleted_files: Vec::new(),
+            symbol_freshness: Default::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg,
@@ -1,8 +1,9 @@
-use crate::graph::pdg::TraversalConfig;
+use crate::graph::pdg::{NodeId, TraversalConfig};
 use crate::phase::context::PhaseExecutionContext;
 use crate::phase::options::PhaseOptions;
 use crate::search::HybridScorer;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// Single hotspot candidate.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,8 +25,28 @@ pub struct Phase4Summary {
     pub hotspots: Vec<Hotspot>,
 }
 
+/// Forward impact size of each node, by node id.
+pub type SymbolImpacts = HashMap<String, usize>;
+
+/// Cache generation [`SymbolImpacts`] are stored under; they carry over
+/// between generations, unlike phase summaries.
+pub const SYMBOL_IMPACTS_GENERATION: &str = "symbols";
+
 /// Run phase 4 critical-path analysis.
 pub fn run(context: &PhaseExecutionContext, options: &PhaseOptions) -> Phase4Summary {
+    run_with_previous(context, options, &SymbolImpacts::new()).0
+}
+
+/// Run phase 4, reusing the impact sizes of a previous run for symbols
+/// whose impact cannot have changed.
+///
+/// Returns the summary along with the impact size of every node, to pass as
+/// `previous` to the next run.
+pub fn run_with_previous(
+    context: &PhaseExecutionContext,
+    options: &PhaseOptions,
+    previous: &SymbolImpacts,
+) -> (Phase4Summary, SymbolImpacts) {
     let scorer = HybridScorer::new().with_weights_hybrid(0.45, 0.0, 0.45, 0.10);
     let keyword_signals = options
         .hotspot_keywords
@@ -33,6 +54,9 @@ pub fn run(context: &PhaseExecutionContext, options: &PhaseOptions) -> Phase4Sum
         .map(|k| k.to_ascii_lowercase())
         .collect::<Vec<_>>();
 
+    let config = TraversalConfig::for_impact_analysis();
+    let stale = stale_nodes(context);
+    let mut impacts = SymbolImpacts::new();
     let mut max_complexity = 1u32;
     let mut max_impact = 1usize;
     let mut raw = Vec::new();
@@ -42,10 +66,15 @@ pub fn run(context: &PhaseExecutionContext, options: &PhaseOptions) -> Phase4Sum
             continue;
         };
 
-        let impact = context
-            .pdg
-            .forward_impact(node_idx, &TraversalConfig::for_impact_analysis())
-            .len();
+        let reusable = stale
+            .as_ref()
+            .is_some_and(|stale| !stale.contains(&node_idx))
+            && !context.symbol_freshness.is_changed(&node.id);
+        let impact = match previous.get(&node.id) {
+            Some(&impact) if reusable => impact,
+            _ => context.pdg.forward_impact(node_idx, &config).len(),
+        };
+        impacts.insert(node.id.clone(), impact);
         max_complexity = max_complexity.max(node.complexity);
         max_impact = max_impact.max(impact);
         raw.push((node.id.clone(), node.complexity, impact, node.name.clone()));
@@ -90,14 +119,39 @@ pub fn run(context: &PhaseExecutionContext, options: &PhaseOptions) -> Phase4Sum
     });
 
     hotspots.truncate(options.top_n.max(1));
-    Phase4Summary { hotspots }
+    (Phase4Summary { hotspots }, impacts)
+}
+
+/// Nodes whose impact may differ from the previous run: the changed symbols
+/// and every node reaching one. `None` when symbols were deleted, since the
+/// edges to them are gone and their former callers cannot be found.
+fn stale_nodes(context: &PhaseExecutionContext) -> Option<HashSet<NodeId>> {
+    let freshness = &context.symbol_freshness;
+    if !freshness.deleted_symbols.is_empty() {
+        return None;
+    }
+    // Uncapped, so no node reaching a changed symbol is missed
+    let config = TraversalConfig {
+        max_nodes: None,
+        ..TraversalConfig::for_impact_analysis()
+    };
+    let mut stale = HashSet::new();
+    for node_idx in freshness
+        .changed_symbols
+        .iter()
+        .filter_map(|id| context.pdg.find_by_id(id))
+    {
+        if stale.insert(node_idx) {
+            stale.extend(context.pdg.backward_impact(node_idx, &config));
+        }
+    }
+    Some(stale)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::graph::pdg::{Node, NodeType, ProgramDependenceGraph};
-    use std::collections::HashMap;
     use std::sync::Arc;
 
     fn context_with_node(name: &str, complexity: u32) -> PhaseExecutionContext {
@@ -132,6 +186,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: Vec::new(),
             deleted_files: Vec::new(),
+            symbol_freshness: Default::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg,
@@ -140,6 +195,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn phase4_reuses_impact_of_symbols_that_cannot_have_changed() {
+        let mut context = context_with_node("standalone", 1);
+        let node = |name: &str| Node {
+            id: format!("src/lib.rs:{name}"),
+            node_type: NodeType::Function,
+            name: name.to_string(),
+            file_path: Arc::from("src/lib.rs"),
+            byte_range: (0, 1),
+            complexity: 1,
+            language: "rust".to_string(),
+        };
+        let caller = context.pdg.add_node(node("caller"));
+        let callee = context.pdg.add_node(node("callee"));
+        context.pdg.add_edge(
+            caller,
+            callee,
+            crate::graph::pdg::Edge {
+                edge_type: crate::graph::pdg::EdgeType::Call,
+                metadata: crate::graph::pdg::EdgeMetadata {
+                    call_count: None,
+                    confidence: None,
+                    call_target: None,
+                    variable_name: None,
+                },
+            },
+        );
+        // Only the callee's source changed
+        context.symbol_freshness.changed_symbols = ["src/lib.rs:callee".to_string()].into();
+        context.symbol_freshness.unchanged_symbols = [
+            "src/lib.rs:standalone".to_string(),
+            "src/lib.rs:caller".to_string(),
+        ]
+        .into();
+
+        let previous = SymbolImpacts::from([
+            ("src/lib.rs:standalone".to_string(), 7),
+            ("src/lib.rs:caller".to_string(), 7),
+            ("src/lib.rs:callee".to_string(), 7),
+        ]);
+        let options = PhaseOptions {
+            root: context.root.clone(),
+            ..PhaseOptions::default()
+        };
+        let (_, impacts) = run_with_previous(&context, &options, &previous);
+        let fresh = |idx| {
+            context
+                .pdg
+                .forward_impact(idx, &TraversalConfig::for_impact_analysis())
+                .len()
+        };
+        // Reused: unchanged and reaches nothing that changed
+        assert_eq!(impacts["src/lib.rs:standalone"], 7);
+        // Recomputed: changed, or calls something that changed
+        assert_eq!(impacts["src/lib.rs:callee"], fresh(callee));
+        assert_eq!(impacts["src/lib.rs:caller"], fresh(caller));
+
+        // Deleted symbols leave dangling callers nothing can find
+        context
+            .symbol_freshness
+            .deleted_symbols
+            .insert("src/lib.rs:gone".to_string());
+        let (_, impacts) = run_with_previous(&context, &options, &previous);
+        let standalone = context.pdg.find_by_id("src/lib.rs:standalone").unwrap();
+        assert_eq!(impacts["src/lib.rs:standalone"], fresh(standalone));
+    }
+
     #[test]
     fn phase4_uses_configurable_hotspot_keywords() {
         let context = context_with_node("payment_authorizer", 10);
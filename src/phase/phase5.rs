@@ -123,6 +123,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: Vec::new(),
             deleted_files: Vec::new(),
+            symbol_freshness: Default::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg: crate::graph::pdg::ProgramDependenceGraph::new(),
@@ -174,6 +175,7 @@ mod tests {
             file_inventory: Vec::new(),
             changed_files: Vec::new(),
             deleted_files: Vec::new(),
+            symbol_freshness: Default::default(),
             parse_results: Vec::new(),
             signatures_by_file: HashMap::new(),
             pdg: crate::graph::pdg::ProgramDependenceGraph::new(),
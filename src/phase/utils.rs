@@ -19,8 +19,8 @@ pub fn collect_files(root: &Path, options: &PhaseOptions) -> Result<CollectedFil
     // Must stay in sync with crate::parse::grammar::LanguageId::from_extension.
     let code_exts = [
         "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", // Main languages
-        "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", // Systems languages
-        "cs",  // C#
+        "go", "java", "cpp", "cc", "cxx", "c", "h", "hpp", "m",  // Systems languages
+        "cs", // C#
         "rb", "php", "lua", "scala", "sc", // Scripting languages
         "sh", "bash", // Shell
         "json", // Data
@@ -0,0 +1,157 @@
+// Bloom-filter prefilter for the inverted text index
+//
+// Candidate generation normally unions the posting sets of every query token
+// into one set of node IDs, which on million-node indexes allocates a set as
+// large as the matching corpus on every query. With the prefilter enabled,
+// each token also has a bloom filter over its node IDs: a node is tested
+// against the filters first (a few bit probes), and only the nodes that pass
+// are checked against the exact posting sets, so no per-query set is built.
+// Filters never give false negatives, and the exact check removes their false
+// positives, so results are identical either way.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// Default false-positive rate of a token's filter at its sized capacity
+pub const DEFAULT_BLOOM_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Smallest capacity a filter is sized for, so small posting sets that grow
+/// are not rebuilt on every insert
+const MIN_BLOOM_CAPACITY: usize = 64;
+
+/// Fixed-size bloom filter over strings
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    hashes: u32,
+    capacity: usize,
+}
+
+impl BloomFilter {
+    /// Create a filter holding `capacity` items at `false_positive_rate`
+    pub fn with_capacity(capacity: usize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.max(1);
+        let items = capacity as f64;
+        let rate = false_positive_rate.clamp(1e-9, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bit_count = (-(items * rate.ln()) / (ln2 * ln2)).ceil().max(64.0) as usize;
+        let hashes = ((bit_count as f64 / items) * ln2).round().clamp(1.0, 16.0) as u32;
+        Self {
+            bits: vec![0; bit_count.div_ceil(64)],
+            hashes,
+            capacity,
+        }
+    }
+
+    /// Number of items the filter was sized for
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Add `item` to the filter
+    pub fn insert(&mut self, item: &str) {
+        for bit in self.bit_positions(item) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// False when `item` was never inserted; true when it probably was
+    pub fn might_contain(&self, item: &str) -> bool {
+        self.bit_positions(item)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Bit positions of `item`, by double hashing one 64-bit hash
+    fn bit_positions(&self, item: &str) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+        let (h1, h2) = (hash & 0xffff_ffff, (hash >> 32) | 1);
+        let bit_count = (self.bits.len() * 64) as u64;
+        (0..self.hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bit_count) as usize)
+    }
+}
+
+/// Per-token bloom filters mirroring the inverted text index
+#[derive(Debug, Clone)]
+pub struct BloomPrefilter {
+    false_positive_rate: f64,
+    filters: HashMap<String, BloomFilter>,
+}
+
+impl BloomPrefilter {
+    /// Build filters for every token of `text_index`
+    pub fn build(text_index: &HashMap<String, HashSet<String>>, false_positive_rate: f64) -> Self {
+        let mut prefilter = Self {
+            false_positive_rate,
+            filters: HashMap::with_capacity(text_index.len()),
+        };
+        for (token, node_ids) in text_index {
+            let filter = prefilter.sized_filter(node_ids);
+            prefilter.filters.insert(token.clone(), filter);
+        }
+        prefilter
+    }
+
+    /// False-positive rate filters are sized for
+    pub fn false_positive_rate(&self) -> f64 {
+        self.false_positive_rate
+    }
+
+    /// Record that `node_id` was added to `postings`, the posting set of `token`
+    ///
+    /// A filter that has grown past its capacity is rebuilt from `postings`
+    /// so its false-positive rate stays bounded. Removals are not mirrored: a
+    /// removed node only adds false positives, which the exact posting-set
+    /// check filters out.
+    pub fn insert(&mut self, token: &str, node_id: &str, postings: &HashSet<String>) {
+        match self.filters.get_mut(token) {
+            Some(filter) if postings.len() <= filter.capacity() => filter.insert(node_id),
+            _ => {
+                let filter = self.sized_filter(postings);
+                self.filters.insert(token.to_string(), filter);
+            }
+        }
+    }
+
+    /// Filter of `token`, if it has one
+    pub fn get(&self, token: &str) -> Option<&BloomFilter> {
+        self.filters.get(token)
+    }
+
+    /// Drop every filter
+    pub fn clear(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Filter holding `postings`, with room for them to double
+    fn sized_filter(&self, postings: &HashSet<String>) -> BloomFilter {
+        let capacity = (postings.len() * 2).max(MIN_BLOOM_CAPACITY);
+        let mut filter = BloomFilter::with_capacity(capacity, self.false_positive_rate);
+        for node_id in postings {
+            filter.insert(node_id);
+        }
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter_has_no_false_negatives_and_few_false_positives() {
+        let mut filter = BloomFilter::with_capacity(1_000, 0.01);
+        for i in 0..1_000 {
+            filter.insert(&format!("node_{}", i));
+        }
+        assert!((0..1_000).all(|i| filter.might_contain(&format!("node_{}", i))));
+
+        let false_positives = (0..10_000)
+            .filter(|i| filter.might_contain(&format!("other_{}", i)))
+            .count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+    }
+}
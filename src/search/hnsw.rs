@@ -3,6 +3,7 @@
 // This module provides an HNSW (Hierarchical Navigable Small World) index
 // for fast approximate nearest neighbor search in high-dimensional vector spaces.
 
+use crate::search::vector::first_non_finite;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
@@ -25,6 +26,12 @@ pub use hnsw_rs::prelude::{DistCosine, Hnsw, Neighbour};
 /// you call `remove()`, the node is marked as deleted and filtered from
 /// search results, but it still occupies capacity in the underlying graph.
 /// Use `rebuild()` to permanently remove deleted nodes and reclaim capacity.
+///
+/// # Optimization
+///
+/// Graph quality drifts as vectors are inserted one at a time. `optimize()`
+/// rebuilds the graph from the stored embeddings; set
+/// `HNSWParams::optimize_after_inserts` to run it automatically.
 pub struct HNSWIndex {
     /// HNSW structure
     hnsw: Hnsw<'static, f32, DistCosine>,
@@ -38,6 +45,12 @@ pub struct HNSWIndex {
     /// Deleted internal IDs (tombstone pattern - nodes removed but still in graph)
     deleted: HashSet<usize>,
 
+    /// Active embeddings by internal ID, kept so the graph can be rebuilt
+    vectors: HashMap<usize, Vec<f32>>,
+
+    /// Inserts since the graph was last built or optimized
+    inserts_since_optimize: usize,
+
     /// Next available internal ID
     next_id: usize,
 
@@ -86,6 +99,11 @@ pub struct HNSWParams {
     pub max_layer: usize,
     /// Whether to use INT8 quantization (reduces memory usage by ~74%)
     pub quantized: bool,
+
+    /// Rebuild the graph automatically after this many inserts
+    /// `None` disables automatic optimization (default)
+    #[serde(default)]
+    pub optimize_after_inserts: Option<usize>,
 }
 
 impl Default for HNSWParams {
@@ -97,6 +115,7 @@ impl Default for HNSWParams {
             max_elements: 100_000,
             max_layer: 16,
             quantized: false,
+            optimize_after_inserts: None,
         }
     }
 }
@@ -143,6 +162,13 @@ impl HNSWParams {
         self
     }
 
+    /// Optimize the graph automatically after `inserts` inserts
+    #[must_use]
+    pub fn with_optimize_after_inserts(mut self, inserts: usize) -> Self {
+        self.optimize_after_inserts = Some(inserts);
+        self
+    }
+
     /// Validate parameters
     ///
     /// # Returns
@@ -172,6 +198,11 @@ impl HNSWParams {
                 "max_layer must be > 0".to_string(),
             ));
         }
+        if self.optimize_after_inserts == Some(0) {
+            return Err(IndexError::InvalidParameter(
+                "optimize_after_inserts must be > 0".to_string(),
+            ));
+        }
         Ok(())
     }
 }
@@ -238,6 +269,8 @@ impl HNSWIndex {
             id_map: HashMap::new(),
             reverse_map: HashMap::new(),
             deleted: HashSet::new(),
+            vectors: HashMap::new(),
+            inserts_since_optimize: 0,
             next_id: 0,
             dimension,
             params,
@@ -255,7 +288,11 @@ impl HNSWIndex {
     ///
     /// # Returns
     ///
-    /// `Ok(())` if successful, `Err(IndexError)` if dimension mismatch or node exists
+    /// `Ok(())` if successful, `Err(IndexError)` if dimension mismatch, node
+    /// exists, or the embedding contains NaN or infinite values
+    ///
+    /// Triggers [`optimize`](Self::optimize) once `optimize_after_inserts`
+    /// inserts have accumulated.
     pub fn insert(&mut self, node_id: String, embedding: Vec<f32>) -> Result<(), IndexError> {
         if embedding.len() != self.dimension {
             return Err(IndexError::DimensionMismatch {
@@ -268,6 +305,13 @@ impl HNSWIndex {
             return Err(IndexError::NodeExists(node_id));
         }
 
+        if let Some(idx) = first_non_finite(&embedding) {
+            return Err(IndexError::InsertionFailed(format!(
+                "non-finite value {} at index {} for node {}",
+                embedding[idx], idx, node_id
+            )));
+        }
+
         let internal_id = self.next_id;
         self.next_id += 1;
 
@@ -277,7 +321,17 @@ impl HNSWIndex {
         // Update mappings
         self.id_map.insert(internal_id, node_id.clone());
         self.reverse_map.insert(node_id, internal_id);
+        self.vectors.insert(internal_id, embedding);
         self.count += 1;
+        self.inserts_since_optimize += 1;
+
+        if self
+            .params
+            .optimize_after_inserts
+            .is_some_and(|every| every > 0 && self.inserts_since_optimize >= every)
+        {
+            self.optimize()?;
+        }
 
         Ok(())
     }
@@ -323,7 +377,34 @@ impl HNSWIndex {
     /// - Time complexity: O(log N) for HNSW vs O(N) for brute-force
     /// - Space complexity: O(k) for results
     pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(String, f32)> {
-        if query.len() != self.dimension {
+        self.search_ef(query, top_k, self.params.ef_search.max(top_k))
+    }
+
+    /// Search for nearest neighbors with a per-query `ef`
+    ///
+    /// Like [`search`](Self::search), but overrides `HNSWParams::ef_search`
+    /// for this query only. Higher values trade latency for recall.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IndexError::InvalidParameter` if `ef < top_k` or `ef == 0`.
+    pub fn search_with_ef(
+        &self,
+        query: &[f32],
+        top_k: usize,
+        ef: usize,
+    ) -> Result<Vec<(String, f32)>, IndexError> {
+        if ef == 0 || ef < top_k {
+            return Err(IndexError::InvalidParameter(format!(
+                "ef ({}) must be > 0 and >= top_k ({})",
+                ef, top_k
+            )));
+        }
+        Ok(self.search_ef(query, top_k, ef))
+    }
+
+    fn search_ef(&self, query: &[f32], top_k: usize, ef: usize) -> Vec<(String, f32)> {
+        if query.len() != self.dimension || first_non_finite(query).is_some() {
             return Vec::new();
         }
 
@@ -332,8 +413,7 @@ impl HNSWIndex {
         }
 
         // Search using HNSW
-        let ef_search = self.params.ef_search.max(top_k);
-        let results = self.hnsw.search(query, top_k, ef_search);
+        let results = self.hnsw.search(query, top_k, ef);
 
         // Convert internal IDs to node IDs and calculate similarity
         // Filter out deleted nodes (tombstone pattern)
@@ -342,8 +422,8 @@ impl HNSWIndex {
             let internal_id = neighbour.d_id;
             let dist = neighbour.distance;
 
-            // Skip deleted nodes
-            if self.deleted.contains(&internal_id) {
+            // Skip deleted nodes and anything that scored NaN
+            if self.deleted.contains(&internal_id) || !dist.is_finite() {
                 continue;
             }
 
@@ -401,6 +481,7 @@ impl HNSWIndex {
     pub fn remove(&mut self, node_id: &str) -> bool {
         if let Some(internal_id) = self.reverse_map.remove(node_id) {
             self.id_map.remove(&internal_id);
+            self.vectors.remove(&internal_id);
             self.deleted.insert(internal_id);
             self.count -= 1;
             true
@@ -421,6 +502,8 @@ impl HNSWIndex {
         self.id_map.clear();
         self.reverse_map.clear();
         self.deleted.clear();
+        self.vectors.clear();
+        self.inserts_since_optimize = 0;
         self.next_id = 0;
         self.count = 0;
     }
@@ -430,6 +513,8 @@ impl HNSWIndex {
     /// This creates a new HNSW graph with only the active nodes, reclaiming
     /// capacity occupied by deleted nodes. This is an expensive operation
     /// that should be called periodically when many deletions have occurred.
+    /// It is a no-op when nothing was deleted; use [`optimize`](Self::optimize)
+    /// to rebuild unconditionally.
     ///
     /// # Performance
     ///
@@ -456,19 +541,32 @@ impl HNSWIndex {
             });
         }
 
-        let start = std::time::Instant::now();
+        self.optimize()
+    }
 
-        // Collect all active nodes with their embeddings
-        // Note: HNSW doesn't support retrieval, so we need to track embeddings separately
-        // This is a known limitation - in production, maintain an external embedding store
+    /// Rebuild the graph from the stored embeddings
+    ///
+    /// Re-inserts every active vector into a fresh graph, restoring the
+    /// connectivity that one-by-one inserts erode and dropping tombstoned
+    /// nodes. Internal IDs are compacted; node IDs and vectors are preserved.
+    ///
+    /// # Performance
+    ///
+    /// - Time complexity: O(N log N) where N is the number of active nodes
+    /// - Space complexity: O(N) for the new graph
+    pub fn optimize(&mut self) -> Result<RebuildStats, IndexError> {
+        let start = std::time::Instant::now();
+        let deleted_count = self.deleted.len();
 
-        // For now, we can only clear the deleted set and log a warning
-        tracing::warn!(
-            "HNSW rebuild called with {} deleted nodes. Note: Full rebuild requires external embedding store.",
-            deleted_count
-        );
+        let mut active_ids: Vec<usize> = self.id_map.keys().copied().collect();
+        active_ids.sort_unstable();
+        if let Some(missing) = active_ids.iter().find(|id| !self.vectors.contains_key(id)) {
+            return Err(IndexError::InsertionFailed(format!(
+                "no stored vector for internal id {}",
+                missing
+            )));
+        }
 
-        // Create new HNSW without deleted nodes
         self.hnsw = Hnsw::new(
             self.params.m,
             self.max_elements,
@@ -476,27 +574,43 @@ impl HNSWIndex {
             self.params.ef_construction,
             DistCosine {},
         );
+        self.reverse_map.clear();
+        let mut id_map = HashMap::with_capacity(active_ids.len());
+        let mut vectors = HashMap::with_capacity(active_ids.len());
+
+        for old_id in active_ids {
+            let (Some(node_id), Some(embedding)) =
+                (self.id_map.remove(&old_id), self.vectors.remove(&old_id))
+            else {
+                continue;
+            };
+            let new_id = id_map.len();
+            self.hnsw.insert((&embedding, new_id));
+            self.reverse_map.insert(node_id.clone(), new_id);
+            id_map.insert(new_id, node_id);
+            vectors.insert(new_id, embedding);
+        }
+
+        self.id_map = id_map;
+        self.vectors = vectors;
+        self.deleted.clear();
+        self.next_id = self.vectors.len();
+        self.count = self.vectors.len();
+        self.inserts_since_optimize = 0;
 
-        // Clear deleted set since we've rebuilt
         let stats = RebuildStats {
-            active: active_count,
+            active: self.count,
             deleted: deleted_count,
             duration_ms: start.elapsed().as_millis() as u64,
         };
 
         tracing::info!(
-            "HNSW rebuild complete: {} active nodes, {} deleted nodes removed in {}ms",
+            "HNSW optimize complete: {} active nodes, {} deleted nodes removed in {}ms",
             stats.active,
             stats.deleted,
             stats.duration_ms
         );
 
-        // Note: In a production system, you would need to re-insert all active embeddings here
-        // Since HNSW doesn't support retrieval, you'd need to maintain a separate HashMap
-        // of node_id -> embedding that gets updated on insert/remove
-
-        self.deleted.clear();
-
         Ok(stats)
     }
 
@@ -508,13 +622,13 @@ impl HNSWIndex {
     ///
     /// # Returns
     ///
-    /// `None` - HNSW doesn't support vector retrieval by design
+    /// The stored embedding, or `None` if the node is not indexed
     ///
-    /// Note: HNSW is optimized for search, not retrieval. If you need
-    /// to retrieve vectors, maintain a separate HashMap.
-    pub fn get(&self, _node_id: &str) -> Option<&Vec<f32>> {
-        // HNSW doesn't support vector retrieval
-        None
+    /// Served from the copy kept for [`optimize`](Self::optimize).
+    pub fn get(&self, node_id: &str) -> Option<&Vec<f32>> {
+        self.reverse_map
+            .get(node_id)
+            .and_then(|internal_id| self.vectors.get(internal_id))
     }
 
     /// Get the HNSW parameters
@@ -529,6 +643,7 @@ impl HNSWIndex {
         // Rough estimate: each node uses ~O(m * dimension) space
         self.count * self.params.m * self.dimension * 4 + // edges
             self.count * self.dimension * 4 + // vectors
+            self.vectors.len() * (std::mem::size_of::<(usize, Vec<f32>)>() + self.dimension * 4) + // stored copies
             self.id_map.len() * (std::mem::size_of::<usize>() + std::mem::size_of::<String>()) +
             self.reverse_map.len() * (std::mem::size_of::<String>() + std::mem::size_of::<usize>())
     }
@@ -580,6 +695,7 @@ pub enum IndexError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
     #[test]
     fn test_hnsw_index_creation() {
@@ -643,6 +759,21 @@ mod tests {
         assert!(results[0].1 > 0.9);
     }
 
+    #[test]
+    fn test_hnsw_rejects_non_finite_embedding() {
+        let mut index = HNSWIndex::new(3);
+        index.insert("a".to_string(), vec![1.0, 0.0, 0.0]).unwrap();
+
+        let result = index.insert("nan".to_string(), vec![f32::NAN, 1.0, 0.0]);
+        assert!(matches!(result, Err(IndexError::InsertionFailed(_))));
+        assert_eq!(index.len(), 1);
+
+        let results = index.search(&[1.0, 0.0, 0.0], 5);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "a");
+        assert!(index.search(&[f32::NAN, 0.0, 0.0], 5).is_empty());
+    }
+
     #[test]
     fn test_hnsw_search_empty_index() {
         let index = HNSWIndex::new(3);
@@ -777,13 +908,19 @@ mod tests {
     }
 
     #[test]
-    fn test_hnsw_get_returns_none() {
+    fn test_hnsw_get_returns_stored_vector() {
         let mut index = HNSWIndex::new(3);
         index
             .insert("test".to_string(), vec![0.1, 0.2, 0.3])
             .unwrap();
 
-        // HNSW doesn't support vector retrieval
+        assert_eq!(index.get("test"), Some(&vec![0.1, 0.2, 0.3]));
+        assert!(index.get("missing").is_none());
+
+        // Optimize compacts internal IDs but keeps the lookup
+        index.optimize().unwrap();
+        assert_eq!(index.get("test"), Some(&vec![0.1, 0.2, 0.3]));
+        index.remove("test");
         assert!(index.get("test").is_none());
     }
 
@@ -798,6 +935,150 @@ mod tests {
         assert!(index.rebuild().is_ok());
     }
 
+    fn random_vector(rng: &mut StdRng, dimension: usize) -> Vec<f32> {
+        (0..dimension).map(|_| rng.gen::<f32>() - 0.5).collect()
+    }
+
+    fn brute_force_top_k(vectors: &[(String, Vec<f32>)], query: &[f32], k: usize) -> Vec<String> {
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let mut scored: Vec<(&str, f32)> = vectors
+            .iter()
+            .map(|(id, v)| {
+                let dot: f32 = v.iter().zip(query).map(|(a, b)| a * b).sum();
+                (id.as_str(), dot / (norm(v) * norm(query)))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored
+            .into_iter()
+            .take(k)
+            .map(|(id, _)| id.to_string())
+            .collect()
+    }
+
+    fn recall_at_k(
+        search: impl Fn(&[f32], usize) -> Vec<(String, f32)>,
+        vectors: &[(String, Vec<f32>)],
+        queries: &[Vec<f32>],
+    ) -> f32 {
+        let k = 10;
+        let mut hits = 0;
+        for query in queries {
+            let expected: HashSet<String> =
+                brute_force_top_k(vectors, query, k).into_iter().collect();
+            hits += search(query, k)
+                .into_iter()
+                .filter(|(id, _)| expected.contains(id))
+                .count();
+        }
+        hits as f32 / (queries.len() * k) as f32
+    }
+
+    #[test]
+    fn test_hnsw_optimize_preserves_vectors_and_recall() {
+        let params = HNSWParams::new().with_m(8).with_ef_construction(16);
+        let mut index = HNSWIndex::with_params(32, params);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vectors: Vec<(String, Vec<f32>)> = (0..1000)
+            .map(|i| (format!("node_{}", i), random_vector(&mut rng, 32)))
+            .collect();
+        for (id, vector) in &vectors {
+            index.insert(id.clone(), vector.clone()).unwrap();
+        }
+        // Tombstones are dropped by optimize, the survivors must all remain
+        for (id, _) in &vectors[..100] {
+            assert!(index.remove(id));
+        }
+        let active = &vectors[100..];
+
+        let queries: Vec<Vec<f32>> = (0..20).map(|_| random_vector(&mut rng, 32)).collect();
+        let recall_before = recall_at_k(|q, k| index.search(q, k), active, &queries);
+
+        let stats = index.optimize().unwrap();
+        assert_eq!(stats.active, 900);
+        assert_eq!(stats.deleted, 100);
+        assert_eq!(index.len(), 900);
+
+        let recall_after = recall_at_k(|q, k| index.search(q, k), active, &queries);
+        assert!(
+            recall_after >= recall_before - 0.05,
+            "recall dropped from {} to {}",
+            recall_before,
+            recall_after
+        );
+
+        // Every surviving vector is still indexed under its node ID
+        for (id, vector) in active.iter().step_by(50) {
+            let results = index.search(vector, 10);
+            assert!(results.iter().any(|(found, _)| found == id));
+        }
+        assert!(index
+            .search(&vectors[0].1, 10)
+            .iter()
+            .all(|(id, _)| id != &vectors[0].0));
+    }
+
+    #[test]
+    fn test_hnsw_search_with_ef_trades_recall() {
+        let params = HNSWParams::new().with_m(4).with_ef_construction(8);
+        let mut index = HNSWIndex::with_params(32, params);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vectors: Vec<(String, Vec<f32>)> = (0..2000)
+            .map(|i| (format!("node_{}", i), random_vector(&mut rng, 32)))
+            .collect();
+        for (id, vector) in &vectors {
+            index.insert(id.clone(), vector.clone()).unwrap();
+        }
+
+        let queries: Vec<Vec<f32>> = (0..20).map(|_| random_vector(&mut rng, 32)).collect();
+        let recall_low = recall_at_k(
+            |q, k| index.search_with_ef(q, k, k).unwrap(),
+            &vectors,
+            &queries,
+        );
+        let recall_high = recall_at_k(
+            |q, k| index.search_with_ef(q, k, 400).unwrap(),
+            &vectors,
+            &queries,
+        );
+        assert!(
+            recall_high >= recall_low,
+            "ef=400 recall {} below ef=top_k recall {}",
+            recall_high,
+            recall_low
+        );
+
+        assert!(matches!(
+            index.search_with_ef(&queries[0], 10, 5),
+            Err(IndexError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_hnsw_optimizes_after_configured_inserts() {
+        let params = HNSWParams::new().with_optimize_after_inserts(10);
+        let mut index = HNSWIndex::with_params(8, params);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vectors: Vec<Vec<f32>> = (0..25).map(|_| random_vector(&mut rng, 8)).collect();
+        for (i, vector) in vectors.iter().enumerate() {
+            index.insert(format!("node_{}", i), vector.clone()).unwrap();
+        }
+        assert_eq!(index.inserts_since_optimize, 5);
+        assert_eq!(index.len(), 25);
+        for (i, vector) in vectors.iter().enumerate() {
+            let results = index.search(vector, 5);
+            assert!(results.iter().any(|(id, _)| *id == format!("node_{}", i)));
+        }
+
+        assert!(HNSWParams::new()
+            .with_optimize_after_inserts(0)
+            .validate()
+            .is_err());
+    }
+
     #[test]
     fn test_hnsw_estimated_memory() {
         let mut index = HNSWIndex::new(768);
@@ -806,8 +1087,8 @@ mod tests {
         let memory = index.estimated_memory_bytes();
         assert!(memory > 0);
 
-        // Should be at least: vector + overhead
-        let min_expected = 768 * 4; // vector
+        // Should be at least: graph vector + stored copy
+        let min_expected = 2 * 768 * 4;
         assert!(memory >= min_expected);
     }
 
@@ -5,6 +5,8 @@
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
+/// Bloom-filter prefilter for the inverted text index.
+pub mod bloom;
 /// Hierarchical Navigable Small World (HNSW) implementation for vector search.
 pub mod hnsw;
 /// INT8 Quantization system.
@@ -18,6 +20,8 @@ pub mod ranking;
 pub mod search;
 /// Semantic analysis and embedding generation.
 pub mod semantic;
+/// Token counting for token-budget accounting.
+pub use crate::tokenizer;
 /// Vector storage and indexing.
 pub mod vector;
 
@@ -25,16 +29,18 @@ pub mod vector;
 #[cfg(feature = "onnx")]
 pub mod onnx;
 
+pub use bloom::{BloomFilter, BloomPrefilter, DEFAULT_BLOOM_FALSE_POSITIVE_RATE};
 pub use hnsw::{HNSWIndex, HNSWParams, IndexError};
 pub use query::{ParsedQuery, QueryIntent, QueryParser};
 pub use ranking::{HybridScorer, Score};
 pub use search::{
     CompactNodeMetadata, CompactTokenIndex, ContentPruner, IndexingAdmissionGate,
     Int8PromotionDecision, Int8QualityGate, Int8QualityReport, Int8QualityThresholds, NodeInfo,
-    PruningDecision, SearchEngine, SearchQuery, SearchResult, SemanticEntry, StagedRetrievalConfig,
-    StagedRetrievalMetrics, WorkHoister,
+    PruningDecision, RecencyBoost, ScoreFusion, SearchEngine, SearchQuery, SearchResult,
+    SemanticEntry, StagedRetrievalConfig, StagedRetrievalMetrics, TextIndexConfig, WorkHoister,
 };
 pub use semantic::SemanticProcessor;
+pub use tokenizer::{CharEstimateTokenizer, SubwordTokenizer, Tokenizer, TokenizerKind};
 pub use vector::VectorIndex;
 
 #[cfg(feature = "onnx")]
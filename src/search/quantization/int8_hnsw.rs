@@ -257,6 +257,14 @@ impl Int8HnswIndex {
             return Err(Int8HnswError::NodeExists(node_id));
         }
 
+        // Quantization panics on non-finite input, so reject it up front
+        if let Some(idx) = crate::search::vector::first_non_finite(&embedding) {
+            return Err(Int8HnswError::InsertionFailed(format!(
+                "non-finite value {} at index {} for node {}",
+                embedding[idx], idx, node_id
+            )));
+        }
+
         // Quantize the vector to INT8
         let quantized: Int8QuantizedVector = embedding.quantize();
 
@@ -306,7 +314,33 @@ impl Int8HnswIndex {
     /// # Returns
     /// Vector of (node_id, similarity_score) pairs, sorted by similarity (descending)
     pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(String, f32)> {
-        if query.len() != self.dimension {
+        self.search_ef(query, top_k, self.params.ef_search.max(top_k))
+    }
+
+    /// Search for nearest neighbors with a per-query `ef`
+    ///
+    /// Overrides `ef_search` for this query only.
+    ///
+    /// # Errors
+    /// Returns `Int8HnswError::InvalidParameter` if `ef < top_k` or `ef == 0`.
+    pub fn search_with_ef(
+        &self,
+        query: &[f32],
+        top_k: usize,
+        ef: usize,
+    ) -> Result<Vec<(String, f32)>, Int8HnswError> {
+        if ef == 0 || ef < top_k {
+            return Err(Int8HnswError::InvalidParameter(format!(
+                "ef ({}) must be > 0 and >= top_k ({})",
+                ef, top_k
+            )));
+        }
+        Ok(self.search_ef(query, top_k, ef))
+    }
+
+    fn search_ef(&self, query: &[f32], top_k: usize, ef: usize) -> Vec<(String, f32)> {
+        if query.len() != self.dimension || crate::search::vector::first_non_finite(query).is_some()
+        {
             return Vec::new();
         }
 
@@ -326,10 +360,9 @@ impl Int8HnswIndex {
         );
 
         // Search using HNSW
-        let ef_search = self.params.ef_search.max(top_k);
         let results = self
             .hnsw
-            .search(std::slice::from_ref(&dummy_query), top_k, ef_search);
+            .search(std::slice::from_ref(&dummy_query), top_k, ef);
 
         // Clear the ADC query context after search
         clear_adc_query_context();
@@ -16,7 +16,7 @@ use crate::search::ranking::QueryType;
 use crate::search::search::SearchQuery;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use unicode_normalization::UnicodeNormalization;
 
 // ============================================================================
@@ -29,6 +29,12 @@ pub const MAX_QUERY_LENGTH: usize = 500;
 /// Minimum query length in characters
 pub const MIN_QUERY_LENGTH: usize = 1;
 
+/// Default shortest meaningful query, in [`meaningful_query_length`] characters
+///
+/// Content tokenization drops shorter tokens, so shorter queries are matched
+/// as symbol-name prefixes instead.
+pub const DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH: usize = 2;
+
 /// Maximum top_k value (prevents memory exhaustion)
 pub const MAX_TOP_K: usize = 1000;
 
@@ -102,6 +108,167 @@ static STOP_WORDS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     .collect()
 });
 
+// ============================================================================
+// QUERY EXPANSION
+// ============================================================================
+
+/// Common code abbreviations and the words they stand for.
+///
+/// Looked up in both directions, so "auth" expands to "authentication" and
+/// "authentication" expands to "auth".
+static ABBREVIATIONS: Lazy<HashMap<&'static str, Vec<&'static str>>> = Lazy::new(|| {
+    let pairs: &[(&str, &[&str])] = &[
+        ("auth", &["authentication", "authorization", "authenticate"]),
+        ("config", &["configuration", "configure"]),
+        ("cfg", &["config", "configuration"]),
+        ("db", &["database"]),
+        ("msg", &["message"]),
+        ("req", &["request"]),
+        ("resp", &["response"]),
+        ("err", &["error"]),
+        ("ctx", &["context"]),
+        ("impl", &["implementation"]),
+        ("init", &["initialize", "initialization"]),
+        ("param", &["parameter"]),
+        ("arg", &["argument"]),
+        ("util", &["utility", "utilities"]),
+        ("repo", &["repository"]),
+        ("dir", &["directory"]),
+        ("env", &["environment"]),
+        ("func", &["function"]),
+        ("idx", &["index"]),
+        ("conn", &["connection"]),
+        ("doc", &["document", "documentation"]),
+        ("info", &["information"]),
+        ("tmp", &["temporary"]),
+        ("val", &["value"]),
+    ];
+
+    let mut map: HashMap<&'static str, Vec<&'static str>> = HashMap::new();
+    for &(short, long) in pairs {
+        map.entry(short).or_default().extend_from_slice(long);
+        for &word in long {
+            map.entry(word).or_default().push(short);
+        }
+    }
+    map
+});
+
+/// Length in characters of the longest alphanumeric run in `query`
+///
+/// This is what decides whether a query has any token long enough to
+/// survive content tokenization.
+pub fn meaningful_query_length(query: &str) -> usize {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|run| run.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Split an identifier into lowercase subtokens
+///
+/// Handles snake_case, kebab-case and camelCase, keeping acronyms together
+/// (`parseHTTPResponse` -> `parse`, `http`, `response`).
+pub fn split_identifier(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        // `current` is non-empty, so the previous char is alphanumeric
+        let boundary = c.is_uppercase() && !current.is_empty() && {
+            let prev = chars[i - 1];
+            prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase()))
+        };
+        if boundary {
+            parts.push(std::mem::take(&mut current));
+        }
+        current.extend(c.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Alternative match terms for a raw query
+///
+/// Compound identifiers contribute their subtokens plus the joined form, so
+/// `getUser` yields `get`, `user` and `get_user` yields `getuser`. With
+/// `abbreviations` set, known abbreviations expand to their long forms and
+/// back. Stop words and duplicates are dropped.
+pub fn expand_query_terms(query: &str, abbreviations: bool) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut expansions = Vec::new();
+    let mut push = |term: String| {
+        if term.len() >= 2 && !STOP_WORDS.contains(term.as_str()) && seen.insert(term.clone()) {
+            expansions.push(term);
+        }
+    };
+
+    for word in query.split_whitespace() {
+        let parts = split_identifier(word);
+        if parts.len() > 1 {
+            for part in &parts {
+                push(part.clone());
+            }
+            push(parts.concat());
+        }
+
+        if abbreviations {
+            let whole = parts.concat();
+            for term in parts.iter().chain(std::iter::once(&whole)) {
+                if let Some(alternatives) = ABBREVIATIONS.get(term.as_str()) {
+                    for alternative in alternatives {
+                        push(alternative.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    expansions
+}
+
+/// Quoted phrases in a query, split into index tokens
+///
+/// Tokens are lowercased and filtered the same way as the inverted index
+/// (alphanumeric runs of 2+ chars). An unterminated quote is ignored.
+pub fn extract_phrases(query: &str) -> Vec<Vec<String>> {
+    let segments: Vec<&str> = query.split('"').collect();
+    // An even segment count means the last quote was never closed
+    let closed = if segments.len() % 2 == 0 {
+        segments.len() - 1
+    } else {
+        segments.len()
+    };
+
+    segments[..closed]
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|phrase| {
+            phrase
+                .split(|c: char| !c.is_alphanumeric())
+                .map(|token| token.to_ascii_lowercase())
+                .filter(|token| token.len() >= 2)
+                .collect::<Vec<_>>()
+        })
+        .filter(|tokens| !tokens.is_empty())
+        .collect()
+}
+
 // ============================================================================
 // QUERY INTENT ENUM
 // ============================================================================
@@ -158,6 +325,12 @@ pub struct ParsedQuery {
 
     /// Token budget for context expansion (validated)
     pub token_budget: Option<usize>,
+
+    /// Alternative match terms from identifier splitting and abbreviations
+    pub expansions: Vec<String>,
+
+    /// Quoted phrases whose tokens must appear adjacently
+    pub phrases: Vec<Vec<String>>,
 }
 
 impl ParsedQuery {
@@ -214,8 +387,11 @@ impl ParsedQuery {
 /// let parsed = parser.parse("show me how authentication works", 10)?;
 /// ```
 pub struct QueryParser {
-    // No internal state - all patterns are static
-    // This struct exists for API compatibility and future extensibility
+    // All patterns are static; the only state is expansion configuration
+    /// Whether query expansion includes common abbreviations
+    expand_abbreviations: bool,
+    /// Shortest meaningful query; shorter ones become symbol-prefix lookups
+    min_query_length: usize,
 }
 
 impl QueryParser {
@@ -225,7 +401,29 @@ impl QueryParser {
     /// All regex patterns are pre-compiled in statics.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Result<Self, Error> {
-        Ok(Self {})
+        Ok(Self {
+            expand_abbreviations: true,
+            min_query_length: DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH,
+        })
+    }
+
+    /// Enable or disable abbreviation expansion (enabled by default)
+    ///
+    /// camelCase/snake_case splitting is always applied.
+    pub fn with_abbreviation_expansion(mut self, enabled: bool) -> Self {
+        self.expand_abbreviations = enabled;
+        self
+    }
+
+    /// Set the shortest meaningful query length
+    ///
+    /// Queries whose [`meaningful_query_length`] is below it are kept whole
+    /// as a single term instead of being tokenized, so the search engine can
+    /// match them as symbol-name prefixes (see
+    /// [`SearchEngine::set_min_query_length`](crate::search::search::SearchEngine::set_min_query_length)).
+    pub fn with_min_query_length(mut self, min_query_length: usize) -> Self {
+        self.min_query_length = min_query_length;
+        self
     }
 
     /// Parse a natural language query with full validation
@@ -257,6 +455,24 @@ impl QueryParser {
         // Step 2: Validate and normalize top_k
         let top_k = self.validate_top_k(default_top_k)?;
 
+        // Over-short queries ("q") would lose every term to tokenization, so
+        // they are kept whole for symbol-prefix matching
+        if (1..self.min_query_length).contains(&meaningful_query_length(&query)) {
+            let parsed = ParsedQuery {
+                terms: vec![query.to_lowercase()],
+                original: query,
+                intent: QueryIntent::Text,
+                query_type: QueryType::Text,
+                expand_context: false,
+                top_k,
+                token_budget: None,
+                expansions: Vec::new(),
+                phrases: Vec::new(),
+            };
+            parsed.validate()?;
+            return Ok(parsed);
+        }
+
         // Step 3: Detect intent
         let intent = self.detect_intent(&query);
 
@@ -284,7 +500,11 @@ impl QueryParser {
             None
         };
 
-        // Step 9: Build parsed query
+        // Step 9: Expand identifiers and abbreviations into alternative terms
+        let expansions = expand_query_terms(&query, self.expand_abbreviations);
+        let phrases = extract_phrases(&query);
+
+        // Step 10: Build parsed query
         let parsed = ParsedQuery {
             original: query,
             terms,
@@ -293,9 +513,11 @@ impl QueryParser {
             expand_context,
             top_k,
             token_budget,
+            expansions,
+            phrases,
         };
 
-        // Step 10: Validate the parsed query
+        // Step 11: Validate the parsed query
         parsed.validate()?;
 
         Ok(parsed)
@@ -500,7 +722,8 @@ impl QueryParser {
         text.split_whitespace()
             .map(|s| s.to_lowercase())
             .map(|s| {
-                s.trim_end_matches(|c: char| !c.is_alphanumeric())
+                s.trim_start_matches('"')
+                    .trim_end_matches(|c: char| !c.is_alphanumeric())
                     .to_string()
             })
             .filter(|s| s.len() >= 3)
@@ -518,12 +741,27 @@ impl QueryParser {
     }
 
     /// Build a SearchQuery from a parsed query
+    ///
+    /// Quoted phrases are carried over as quoted text so the search engine
+    /// can enforce adjacency.
     pub fn build_search_query(&self, parsed: &ParsedQuery) -> SearchQuery {
         // Reconstruct query string from terms
         let query_text = if parsed.terms.is_empty() {
             parsed.original.clone()
         } else {
-            parsed.terms.join(" ")
+            let mut parts: Vec<String> = parsed
+                .terms
+                .iter()
+                .filter(|term| !parsed.phrases.iter().any(|phrase| phrase.contains(term)))
+                .cloned()
+                .collect();
+            parts.extend(
+                parsed
+                    .phrases
+                    .iter()
+                    .map(|phrase| format!("\"{}\"", phrase.join(" "))),
+            );
+            parts.join(" ")
         };
 
         SearchQuery {
@@ -532,10 +770,9 @@ impl QueryParser {
             token_budget: parsed.token_budget,
             semantic: matches!(parsed.query_type, QueryType::Semantic),
             expand_context: parsed.expand_context,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
             query_type: Some(parsed.query_type),
+            expansions: parsed.expansions.clone(),
+            ..Default::default()
         }
     }
 
@@ -730,6 +967,69 @@ mod tests {
         assert!(search_query.token_budget.is_some());
     }
 
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(split_identifier("getUser"), vec!["get", "user"]);
+        assert_eq!(split_identifier("get_user"), vec!["get", "user"]);
+        assert_eq!(
+            split_identifier("parseHTTPResponse"),
+            vec!["parse", "http", "response"]
+        );
+        assert_eq!(split_identifier("lookup"), vec!["lookup"]);
+    }
+
+    #[test]
+    fn test_expand_query_terms() {
+        let expansions = expand_query_terms("getUser", false);
+        assert_eq!(expansions, vec!["get", "user", "getuser"]);
+
+        let expansions = expand_query_terms("get_user", false);
+        assert!(expansions.contains(&"getuser".to_string()));
+
+        let expansions = expand_query_terms("auth", true);
+        assert!(expansions.contains(&"authentication".to_string()));
+        assert!(expand_query_terms("auth", false).is_empty());
+    }
+
+    #[test]
+    fn test_parse_records_expansions() {
+        let parser = QueryParser::new().unwrap();
+        let parsed = parser.parse("find getUser", 10).unwrap();
+        assert!(parsed.expansions.contains(&"user".to_string()));
+        assert_eq!(
+            parser.build_search_query(&parsed).expansions,
+            parsed.expansions
+        );
+
+        let parser = QueryParser::new()
+            .unwrap()
+            .with_abbreviation_expansion(false);
+        let parsed = parser.parse("auth handler", 10).unwrap();
+        assert!(parsed.expansions.is_empty());
+    }
+
+    #[test]
+    fn test_extract_phrases() {
+        assert_eq!(
+            extract_phrases(r#"read "open file" and "Parse-Config" "#),
+            vec![vec!["open", "file"], vec!["parse", "config"]]
+        );
+        assert!(extract_phrases("no quotes here").is_empty());
+        // Unterminated quote is ignored
+        assert!(extract_phrases(r#"open "file"#).is_empty());
+    }
+
+    #[test]
+    fn test_build_search_query_keeps_phrases() {
+        let parser = QueryParser::new().unwrap();
+        let parsed = parser.parse(r#"cache "open file""#, 10).unwrap();
+        assert_eq!(parsed.phrases, vec![vec!["open", "file"]]);
+
+        let search_query = parser.build_search_query(&parsed);
+        assert!(search_query.query.contains(r#""open file""#));
+        assert!(search_query.query.contains("cache"));
+    }
+
     #[test]
     fn test_tokenize() {
         let parser = QueryParser::new().unwrap();
@@ -849,6 +1149,23 @@ mod tests {
         assert_eq!(query1.unwrap().terms, query2.unwrap().terms);
     }
 
+    #[test]
+    fn test_short_query_is_kept_whole() {
+        let parser = QueryParser::new().unwrap();
+        let parsed = parser.parse("Q", 10).unwrap();
+        assert_eq!(parsed.terms, ["q"]);
+        assert!(parsed.expansions.is_empty());
+        assert_eq!(parser.build_search_query(&parsed).query, "q");
+
+        // Raising the minimum keeps longer queries whole too
+        let parser = QueryParser::new().unwrap().with_min_query_length(4);
+        assert_eq!(parser.parse("how", 10).unwrap().terms, ["how"]);
+        assert!(matches!(
+            parser.parse("?!", 10),
+            Err(Error::NoMeaningfulTerms { .. })
+        ));
+    }
+
     #[test]
     fn test_no_meaningful_terms() {
         let parser = QueryParser::new().unwrap();
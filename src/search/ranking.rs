@@ -117,17 +117,50 @@ impl HybridScorer {
         }
     }
 
-    /// Set custom weights (legacy method for compatibility)
-    #[deprecated(since = "1.6.4", note = "Use with_weights_hybrid instead")]
+    /// Set semantic/structural/text weights, normalized to sum to 1
+    ///
+    /// `semantic` maps to the TF-IDF component and the neural weight is
+    /// cleared; use [`with_weights_hybrid`](Self::with_weights_hybrid) to
+    /// weight neural similarity separately. Negative weights are treated as
+    /// zero, and all-zero weights leave the scorer unchanged.
     pub fn with_weights(mut self, semantic: f32, structural: f32, text: f32) -> Self {
-        // Map legacy semantic to tfidf for backward compatibility
-        self.tfidf_weight = semantic;
-        self.neural_weight = 0.0;
-        self.structural_weight = structural;
-        self.text_weight = text;
+        self.set_weights(semantic, structural, text);
         self
     }
 
+    /// In-place variant of [`with_weights`](Self::with_weights)
+    pub fn set_weights(&mut self, semantic: f32, structural: f32, text: f32) {
+        let (semantic, structural, text) = (semantic.max(0.0), structural.max(0.0), text.max(0.0));
+        let total = semantic + structural + text;
+        if total <= 0.0 {
+            return;
+        }
+        self.tfidf_weight = semantic / total;
+        self.neural_weight = 0.0;
+        self.structural_weight = structural / total;
+        self.text_weight = text / total;
+    }
+
+    /// Weight of the TF-IDF component
+    pub fn tfidf_weight(&self) -> f32 {
+        self.tfidf_weight
+    }
+
+    /// Weight of the neural component
+    pub fn neural_weight(&self) -> f32 {
+        self.neural_weight
+    }
+
+    /// Weight of the structural component
+    pub fn structural_weight(&self) -> f32 {
+        self.structural_weight
+    }
+
+    /// Weight of the text match component
+    pub fn text_weight(&self) -> f32 {
+        self.text_weight
+    }
+
     /// Set custom hybrid weights
     pub fn with_weights_hybrid(
         mut self,
@@ -312,7 +345,6 @@ mod tests {
 
     #[test]
     fn test_custom_weights_legacy() {
-        #[allow(deprecated)]
         let scorer = HybridScorer::new().with_weights(0.3, 0.5, 0.2);
         #[allow(deprecated)]
         let score = scorer.score(0.8, 0.6, 0.4);
@@ -320,6 +352,36 @@ mod tests {
         assert!((score.overall - 0.62).abs() < 0.01);
     }
 
+    #[test]
+    fn test_with_weights_normalizes() {
+        let scorer = HybridScorer::new().with_weights(2.0, 1.0, 1.0);
+        assert!((scorer.tfidf_weight() - 0.5).abs() < 1e-6);
+        assert_eq!(scorer.neural_weight(), 0.0);
+        assert!((scorer.structural_weight() - 0.25).abs() < 1e-6);
+        assert!((scorer.text_weight() - 0.25).abs() < 1e-6);
+
+        // All-zero weights are ignored
+        let unchanged = scorer.with_weights(0.0, 0.0, 0.0);
+        assert_eq!(unchanged, scorer);
+    }
+
+    #[test]
+    fn test_text_weight_favors_keyword_match() {
+        let margin = |scorer: HybridScorer| {
+            let keyword = scorer.score_hybrid(0.0, 0.0, 0.0, 1.0).overall;
+            let semantic = scorer.score_hybrid(1.0, 0.0, 0.0, 0.0).overall;
+            keyword - semantic
+        };
+
+        let recall = HybridScorer::new().with_weights(0.6, 0.1, 0.3);
+        let mut precision = recall;
+        precision.set_weights(0.2, 0.1, 0.7);
+
+        assert!(margin(recall) < 0.0);
+        assert!(margin(precision) > 0.0);
+        assert!(margin(precision) > margin(recall));
+    }
+
     #[test]
     fn test_custom_weights_hybrid() {
         let scorer = HybridScorer::new().with_weights_hybrid(0.3, 0.4, 0.2, 0.1);
@@ -3,22 +3,32 @@
 // # Thread Safety
 //
 // `SearchEngine` is NOT thread-safe for concurrent writes. However:
-// - `&SearchEngine` (shared reference) can be safely used for concurrent reads
+// - `&SearchEngine` (shared reference) can be safely used for concurrent reads,
+//   including `search`/`search_staged` (the result cache sits behind a mutex)
 // - `&mut SearchEngine` requires exclusive access for writes
 // - VectorIndex uses internal HashMap which is not thread-safe
 //
-// For concurrent access, wrap in `Arc<RwLock<SearchEngine>>`.
+// For concurrent access, wrap in `Arc<RwLock<SearchEngine>>`; searches only
+// need the read lock. `search_async` runs a search on tokio's blocking pool.
 
+use crate::graph::pdg::NodeType;
+use crate::search::bloom::{BloomFilter, BloomPrefilter};
 use crate::search::hnsw::{HNSWIndex, HNSWParams};
 use crate::search::quantization::int8_hnsw::{Int8HnswIndex, Int8HnswParams};
-use crate::search::query::{MAX_EMBEDDING_DIMENSION, MIN_EMBEDDING_DIMENSION};
+use crate::search::query::{
+    meaningful_query_length, split_identifier, DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH,
+    MAX_EMBEDDING_DIMENSION, MIN_EMBEDDING_DIMENSION,
+};
 use crate::search::ranking::{HybridScorer, Score};
+use crate::search::tokenizer::{Tokenizer, TokenizerKind};
 use crate::search::vector::VectorIndex;
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime};
 
 // ============================================================================
 // CONSTANTS & VALIDATION
@@ -441,6 +451,18 @@ impl VectorIndexImpl {
         }
     }
 
+    /// Search with a per-query HNSW `ef`, raised to at least `top_k`
+    ///
+    /// Brute-force search is exact, so `ef` is ignored there.
+    pub fn search_with_ef(&self, query: &[f32], top_k: usize, ef: usize) -> Vec<(String, f32)> {
+        let ef = ef.max(top_k).max(1);
+        match self {
+            Self::BruteForce(idx) => idx.search(query, top_k),
+            Self::HNSW(idx) => idx.search_with_ef(query, top_k, ef).unwrap_or_default(),
+            Self::HNSWQuantized(idx) => idx.search_with_ef(query, top_k, ef).unwrap_or_default(),
+        }
+    }
+
     /// Insert a vector into the index
     pub fn insert(&mut self, node_id: String, vector: Vec<f32>) -> Result<(), VectorIndexError> {
         match self {
@@ -565,6 +587,12 @@ pub struct NodeInfo {
     ///
     /// Backward-compatible: `None` falls back to `content.split()` tokenization.
     pub pre_tokenized: Option<Vec<String>>,
+
+    /// PDG node type, used by `SearchQuery::node_types` facets.
+    ///
+    /// `None` when the producer did not know the type; such nodes are
+    /// excluded whenever a facet is requested.
+    pub node_type: Option<NodeType>,
 }
 
 // ---------------------------------------------------------------------------
@@ -601,6 +629,9 @@ struct NodeInfoRepr {
 
     #[serde(default)]
     pre_tokenized: Option<Vec<String>>,
+
+    #[serde(default)]
+    node_type: Option<NodeType>,
 }
 
 impl<'de> Deserialize<'de> for NodeInfo {
@@ -638,6 +669,7 @@ impl<'de> Deserialize<'de> for NodeInfo {
             complexity: repr.complexity,
             signature: repr.signature,
             pre_tokenized: repr.pre_tokenized,
+            node_type: repr.node_type,
         })
     }
 }
@@ -661,6 +693,7 @@ impl Serialize for NodeInfo {
             complexity: u32,
             signature: &'a Option<String>,
             pre_tokenized: &'a Option<Vec<String>>,
+            node_type: &'a Option<NodeType>,
         }
 
         NodeInfoNew {
@@ -675,6 +708,7 @@ impl Serialize for NodeInfo {
             complexity: self.complexity,
             signature: &self.signature,
             pre_tokenized: &self.pre_tokenized,
+            node_type: &self.node_type,
         }
         .serialize(serializer)
     }
@@ -690,12 +724,23 @@ struct TextQueryPreprocessed {
     query_lower: String,
     /// Query tokens for overlap calculation
     query_tokens: HashSet<String>,
+    /// Expansion terms not already among the query tokens
+    expansion_tokens: HashSet<String>,
+    /// Indexed tokens close to a query token (fuzzy mode only)
+    fuzzy_tokens: HashSet<String>,
+    /// Whether symbol names close to the query score (fuzzy mode)
+    fuzzy: bool,
+    /// Whether the query is too short to tokenize and only matches symbol-name prefixes
+    symbol_prefix: bool,
+    /// Quoted phrases (token sequences) that must appear adjacently
+    phrases: Vec<Vec<String>>,
 }
 
 impl TextQueryPreprocessed {
     /// Create pre-computed query data
     fn from_query(query: &str) -> Self {
-        let query_lower = query.to_ascii_lowercase();
+        // Phrase quotes are syntax, not part of the text to match
+        let query_lower = query.replace('"', "").trim().to_ascii_lowercase();
         // Tokenize using the same logic as the content indexing
         let query_tokens: HashSet<_> = query
             .split(|c: char| !c.is_alphanumeric())
@@ -706,10 +751,242 @@ impl TextQueryPreprocessed {
         Self {
             query_lower,
             query_tokens,
+            expansion_tokens: HashSet::new(),
+            fuzzy_tokens: HashSet::new(),
+            fuzzy: false,
+            symbol_prefix: false,
+            phrases: crate::search::query::extract_phrases(query),
+        }
+    }
+
+    /// Attach expansion terms from `SearchQuery::expansions`
+    fn with_expansions(mut self, expansions: &[String]) -> Self {
+        self.expansion_tokens = expansions
+            .iter()
+            .map(|term| term.to_ascii_lowercase())
+            .filter(|term| !self.query_tokens.contains(term))
+            .collect();
+        self
+    }
+
+    /// Enable fuzzy matching, adding the `vocabulary` tokens close to a query token
+    fn with_fuzzy_matches(mut self, vocabulary: &FuzzyVocabulary) -> Self {
+        self.fuzzy = true;
+        let mut fuzzy_tokens = HashSet::new();
+        for query_token in &self.query_tokens {
+            for token in vocabulary.candidates(query_token) {
+                if !self.query_tokens.contains(token)
+                    && !self.expansion_tokens.contains(token)
+                    && fuzzy_similarity(query_token, token) > 0.0
+                {
+                    fuzzy_tokens.insert(token.clone());
+                }
+            }
+        }
+        self.fuzzy_tokens = fuzzy_tokens;
+        self
+    }
+
+    /// Tokens used for inverted-index candidate lookup
+    fn candidate_tokens(&self) -> impl Iterator<Item = &String> {
+        self.query_tokens
+            .iter()
+            .chain(&self.expansion_tokens)
+            .chain(&self.fuzzy_tokens)
+    }
+
+    /// Best fuzzy similarity between the query and a symbol name
+    ///
+    /// Compares the whole query with the whole name and each query token
+    /// with each identifier subtoken, so `athenticate` matches
+    /// `authenticate_user`.
+    fn fuzzy_name_similarity(&self, symbol_lower: &str, symbol_name: &str) -> f32 {
+        let subtokens = split_identifier(symbol_name);
+        self.query_tokens
+            .iter()
+            .flat_map(|query_token| {
+                subtokens
+                    .iter()
+                    .filter(move |subtoken| *subtoken != query_token)
+                    .map(move |subtoken| fuzzy_similarity(query_token, subtoken))
+            })
+            .fold(fuzzy_similarity(&self.query_lower, symbol_lower), f32::max)
+    }
+
+    /// Score of a symbol name against an over-short query
+    ///
+    /// The whole name starting with the query beats one of its identifier
+    /// subtokens starting with it, so `q` ranks `query_parser` above
+    /// `parse_query`.
+    fn symbol_prefix_score(&self, symbol_lower: &str, symbol_name: &str) -> f32 {
+        if symbol_lower == self.query_lower {
+            1.0
+        } else if symbol_lower.starts_with(&self.query_lower) {
+            0.7
+        } else if split_identifier(symbol_name)
+            .iter()
+            .any(|subtoken| subtoken.starts_with(&self.query_lower))
+        {
+            0.4
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Normalized Damerau-Levenshtein similarity of two lowercase words
+///
+/// Zero below [`FUZZY_MIN_SIMILARITY`], so only near misses (a typo or two
+/// in a long name) count. Words shorter than [`FUZZY_MIN_WORD_LEN`] never
+/// match, since any short word is a typo away from many others.
+fn fuzzy_similarity(a: &str, b: &str) -> f32 {
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let longest = len_a.max(len_b);
+    if len_a.min(len_b) < FUZZY_MIN_WORD_LEN {
+        return 0.0;
+    }
+    // The length difference alone bounds the distance from below
+    if 1.0 - len_a.abs_diff(len_b) as f32 / (longest as f32) < FUZZY_MIN_SIMILARITY {
+        return 0.0;
+    }
+    let similarity = strsim::normalized_damerau_levenshtein(a, b) as f32;
+    if similarity < FUZZY_MIN_SIMILARITY {
+        0.0
+    } else {
+        similarity
+    }
+}
+
+/// Text index tokens bucketed by length in characters
+///
+/// Two words whose lengths differ too much can never reach
+/// [`FUZZY_MIN_SIMILARITY`], so a query token is only compared with the
+/// buckets inside its length window instead of the whole vocabulary.
+#[derive(Debug, Default)]
+struct FuzzyVocabulary {
+    by_length: BTreeMap<usize, Vec<String>>,
+}
+
+impl FuzzyVocabulary {
+    fn new<'a>(tokens: impl Iterator<Item = &'a String>) -> Self {
+        let mut by_length: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for token in tokens {
+            let len = token.chars().count();
+            if len >= FUZZY_MIN_WORD_LEN {
+                by_length.entry(len).or_default().push(token.clone());
+            }
+        }
+        Self { by_length }
+    }
+
+    /// Tokens whose length lets them fuzzily match `query_token`
+    ///
+    /// The window is slightly wider than strictly needed;
+    /// [`fuzzy_similarity`] makes the exact call.
+    fn candidates<'a>(&'a self, query_token: &str) -> impl Iterator<Item = &'a String> {
+        let len = query_token.chars().count() as f32;
+        let shortest = (len * FUZZY_MIN_SIMILARITY).floor() as usize;
+        let longest = (len / FUZZY_MIN_SIMILARITY).ceil() as usize;
+        self.by_length
+            .range(shortest.max(FUZZY_MIN_WORD_LEN)..=longest)
+            .flat_map(|(_, tokens)| tokens)
+    }
+}
+
+/// The lines of `source` covering `byte_range`, plus `extra_lines` on each side
+///
+/// `None` when the range is empty or does not fit the source (e.g. the file
+/// changed since it was indexed).
+fn inline_context(source: &str, byte_range: (usize, usize), extra_lines: usize) -> Option<&str> {
+    let (start, end) = byte_range;
+    if start >= end || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+        return None;
+    }
+
+    let mut context_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    for _ in 0..extra_lines {
+        if context_start == 0 {
+            break;
+        }
+        context_start = source[..context_start - 1].rfind('\n').map_or(0, |i| i + 1);
+    }
+
+    let mut context_end = source[end..].find('\n').map_or(source.len(), |i| end + i);
+    for _ in 0..extra_lines {
+        if context_end == source.len() {
+            break;
+        }
+        context_end = source[context_end + 1..]
+            .find('\n')
+            .map_or(source.len(), |i| context_end + 1 + i);
+    }
+    Some(&source[context_start..context_end])
+}
+
+/// Nodes containing at least one candidate token of a query
+enum TextCandidates<'a> {
+    /// Union of the tokens' posting sets
+    Exact(HashSet<&'a str>),
+    /// The tokens' posting sets, each behind its bloom filter if it has one
+    Bloom(Vec<(Option<&'a BloomFilter>, &'a HashSet<String>)>),
+}
+
+impl TextCandidates<'_> {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Exact(ids) => ids.is_empty(),
+            Self::Bloom(postings) => postings.is_empty(),
+        }
+    }
+
+    fn contains(&self, node_id: &str) -> bool {
+        match self {
+            Self::Exact(ids) => ids.contains(node_id),
+            Self::Bloom(postings) => postings.iter().any(|(filter, ids)| {
+                filter.map_or(true, |filter| filter.might_contain(node_id)) && ids.contains(node_id)
+            }),
         }
     }
 }
 
+/// Whether `phrase` occurs as consecutive tokens in a node
+///
+/// `positions` maps each token to its sorted positions in the node's token
+/// sequence.
+fn contains_phrase(positions: &HashMap<String, Vec<u32>>, phrase: &[String]) -> bool {
+    let Some((first, rest)) = phrase.split_first() else {
+        return true;
+    };
+    let Some(starts) = positions.get(first) else {
+        return false;
+    };
+    starts.iter().any(|&start| {
+        rest.iter().enumerate().all(|(offset, token)| {
+            positions
+                .get(token)
+                .is_some_and(|p| p.binary_search(&(start + offset as u32 + 1)).is_ok())
+        })
+    })
+}
+
+/// Text score scale for nodes matched only through query expansion
+///
+/// Kept well below a direct match so expansions widen recall without
+/// reordering results that match the query as written.
+const EXPANSION_MATCH_WEIGHT: f32 = 0.5;
+
+/// Text score scale for symbol names matched only fuzzily
+///
+/// A fuzzy match scores below an exact or substring name match.
+const FUZZY_MATCH_WEIGHT: f32 = 0.5;
+
+/// Minimum normalized similarity for a fuzzy match (one edit in ten characters
+/// scores 0.9, one in five 0.8)
+const FUZZY_MIN_SIMILARITY: f32 = 0.8;
+
+/// Shortest word considered for fuzzy matching
+const FUZZY_MIN_WORD_LEN: usize = 4;
+
 // ============================================================================
 // SEARCH QUERY
 // ============================================================================
@@ -727,12 +1004,18 @@ pub struct SearchQuery {
     pub top_k: usize,
 
     /// Token budget for context expansion (validated by QueryParser)
+    ///
+    /// Also caps the inline context of all results together.
     pub token_budget: Option<usize>,
 
     /// Whether to use semantic search
     pub semantic: bool,
 
-    /// Whether to expand context using graph traversal
+    /// Whether to return each result's source inline in `context`
+    ///
+    /// The context is the node's lines plus
+    /// [`context_lines`](SearchEngine::context_lines) on each side, read from
+    /// the file on disk; graph expansion is left to the caller.
     pub expand_context: bool,
 
     /// Optional query embedding for semantic search (TF-IDF)
@@ -749,8 +1032,86 @@ pub struct SearchQuery {
 
     /// Query type for adaptive ranking
     pub query_type: Option<crate::search::ranking::QueryType>,
+
+    /// How keyword and semantic signals are combined into the final ranking
+    #[serde(default)]
+    pub fusion: ScoreFusion,
+
+    /// Alternative match terms (identifier subtokens, abbreviations)
+    ///
+    /// Only widen the inverted-index candidate set; see
+    /// [`expand_query_terms`](crate::search::query::expand_query_terms).
+    #[serde(default)]
+    pub expansions: Vec<String>,
+
+    /// Collapse results sharing `(file_path, symbol_name)` into the best-scoring one
+    #[serde(default)]
+    pub dedup_symbols: bool,
+
+    /// Per-query HNSW `ef`; `None` uses the index's `ef_search`
+    ///
+    /// Must be at least `top_k`. Ignored by the brute-force index.
+    #[serde(default)]
+    pub ef_search: Option<usize>,
+
+    /// Symbol-kind facet: only return nodes of these PDG node types
+    ///
+    /// `None` returns every kind. Nodes indexed without a type never match
+    /// a facet.
+    #[serde(default)]
+    pub node_types: Option<HashSet<NodeType>>,
+
+    /// Also match names within a small edit distance of the query
+    ///
+    /// Catches typos and half-remembered names (`athenticate` finds
+    /// `authenticate`) at some cost in precision, so it is off by default.
+    #[serde(default)]
+    pub fuzzy: bool,
+}
+
+impl Default for SearchQuery {
+    /// An empty keyword query for the top 10 results, with every option off
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            top_k: 10,
+            token_budget: None,
+            semantic: false,
+            expand_context: false,
+            query_embedding: None,
+            query_neural_embedding: None,
+            threshold: None,
+            query_type: None,
+            fusion: ScoreFusion::Linear,
+            expansions: Vec::new(),
+            dedup_symbols: false,
+            ef_search: None,
+            node_types: None,
+            fuzzy: false,
+        }
+    }
+}
+
+/// Strategy for merging keyword and semantic rankings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreFusion {
+    /// Weighted sum of the individual signal scores
+    #[default]
+    Linear,
+
+    /// Reciprocal Rank Fusion over the keyword and semantic rankings
+    ///
+    /// Each result scores `Σ 1 / (RRF_K + rank)` across the rankings it
+    /// appears in, so a result that is good on both signals beats one that
+    /// only dominates a single signal. `threshold` still filters on the
+    /// linear score before fusion.
+    Rrf,
 }
 
+/// Rank offset for Reciprocal Rank Fusion (the value from the original RRF paper)
+pub const RRF_K: f32 = 60.0;
+
 // ============================================================================
 // SEARCH RESULT
 // ============================================================================
@@ -807,7 +1168,7 @@ pub struct SearchResult {
     /// Relevance score
     pub score: Score,
 
-    /// Expanded context (if requested)
+    /// Source of the matched node and its surrounding lines (if requested)
     pub context: Option<String>,
 
     /// Byte range in source
@@ -910,7 +1271,8 @@ impl CompactNodeMetadata {
 ///
 /// # Thread Safety
 ///
-/// - Reads (`&SearchEngine`) are thread-safe for concurrent access
+/// - Reads (`&SearchEngine`), including `search`, are thread-safe for
+///   concurrent access; `search_async` runs a search on tokio's blocking pool
 /// - Writes (`&mut SearchEngine`) require exclusive access
 /// - The internal VectorIndexImpl is NOT thread-safe for concurrent writes
 ///
@@ -926,6 +1288,8 @@ impl CompactNodeMetadata {
 pub struct SearchEngine {
     nodes: Vec<NodeInfo>,
     scorer: HybridScorer,
+    /// Caller-pinned weights that replace the per-query adaptive weights
+    weight_override: Option<HybridScorer>,
     vector_index: VectorIndexImpl,
     /// Complexity cache for O(1) lookups (fixes O(n²) bug)
     complexity_cache: HashMap<String, u32>,
@@ -938,18 +1302,209 @@ pub struct SearchEngine {
     /// Per-node token cache: node_id -> set of normalized tokens
     /// Populated during index_nodes() to avoid re-tokenization in scoring
     node_tokens: HashMap<String, HashSet<String>>,
+    /// Per-node token positions for phrase matching: node_id -> token -> positions
+    node_positions: HashMap<String, HashMap<String, Vec<u32>>>,
     /// Result cache for repeated queries (A+ Section 8.1: bounded by entries and bytes)
-    search_cache: LruCache<String, Vec<SearchResult>>,
-    /// Tracked byte estimate for the search cache
-    search_cache_bytes: usize,
+    ///
+    /// Behind a mutex so searches can run through `&self`.
+    search_cache: Mutex<SearchCache>,
+    /// Stopwords and document-frequency cutoff applied to `text_index`
+    text_index_config: TextIndexConfig,
+    /// Tokens dropped from `text_index` by the document-frequency cutoff
+    pruned_tokens: HashSet<String>,
+    /// Boost for results in recently edited files (off when `None`)
+    recency_boost: Option<RecencyBoost>,
+    /// Lines around a node included in inline result context
+    context_lines: usize,
+    /// Counts inline result context against the query's token budget
+    tokenizer: Arc<dyn Tokenizer>,
+    /// Per-token bloom filters checked before the posting sets (off when `None`)
+    bloom_prefilter: Option<BloomPrefilter>,
+    /// Shorter queries are matched as symbol-name prefixes
+    min_query_length: usize,
 }
 
+/// Lines of surrounding source included on each side of inline result context
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
 // A+ Search cache budget constants (Section 8.1)
 /// Maximum entries in the search cache.
 pub const SEARCH_CACHE_MAX_ENTRIES: usize = 256;
 /// Maximum total bytes for the search cache.
 pub const SEARCH_CACHE_MAX_BYTES: usize = 16 * 1024 * 1024; // 16 MiB
 
+/// Search result cache bounded by entry count and tracked byte size
+struct SearchCache {
+    entries: LruCache<String, Vec<SearchResult>>,
+    /// Tracked byte estimate for `entries`
+    bytes: usize,
+    /// Fuzzy matching vocabulary, built on the first fuzzy query after a change
+    vocabulary: Option<Arc<FuzzyVocabulary>>,
+}
+
+impl SearchCache {
+    fn new() -> Self {
+        Self {
+            entries: LruCache::new(NonZeroUsize::new(SEARCH_CACHE_MAX_ENTRIES).unwrap()),
+            bytes: 0,
+            vocabulary: None,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<Vec<SearchResult>> {
+        self.entries.get(key).cloned()
+    }
+
+    /// Cache results with byte-budget enforcement (A+ Section 8.1)
+    fn insert(&mut self, key: String, results: &[SearchResult]) {
+        let results_bytes = SearchEngine::estimate_search_results_bytes(results);
+        // Guard: skip insertion if a single entry exceeds the cache budget.
+        if results_bytes >= SEARCH_CACHE_MAX_BYTES {
+            return;
+        }
+        // If replacing an existing entry, subtract its bytes first
+        if let Some(existing) = self.entries.get(&key) {
+            self.bytes = self
+                .bytes
+                .saturating_sub(SearchEngine::estimate_search_results_bytes(existing));
+        }
+        // Evict until there is room
+        while self.bytes + results_bytes > SEARCH_CACHE_MAX_BYTES && !self.entries.is_empty() {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.bytes = self
+                    .bytes
+                    .saturating_sub(SearchEngine::estimate_search_results_bytes(&evicted));
+            }
+        }
+        self.bytes += results_bytes;
+        self.entries.put(key, results.to_vec());
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+        self.vocabulary = None;
+    }
+}
+
+// ============================================================================
+// TEXT INDEX FILTERING
+// ============================================================================
+
+/// Tokens that carry little signal in source code
+///
+/// Used by [`TextIndexConfig::for_code`].
+pub const CODE_STOPWORDS: &[&str] = &[
+    "self", "let", "mut", "pub", "fn", "the", "return", "if", "else", "for", "in", "use", "this",
+    "var", "const", "def", "new", "true", "false", "none", "null", "to", "of", "and", "or",
+];
+
+/// Controls which tokens enter the inverted text index.
+///
+/// Excluded tokens are still kept in the per-node token cache, so they
+/// count toward text scoring; they just stop generating candidates. A query
+/// made only of excluded tokens finds nodes through expansions or semantic
+/// search alone.
+#[derive(Debug, Clone)]
+pub struct TextIndexConfig {
+    /// Lowercase tokens that are never indexed.
+    pub stopwords: HashSet<String>,
+
+    /// Drop tokens that appear in more than this fraction (0-1) of nodes.
+    ///
+    /// `None` disables the cutoff.
+    pub max_document_frequency: Option<f32>,
+
+    /// Minimum number of indexed nodes before the frequency cutoff applies.
+    ///
+    /// In small indexes nearly every token looks "common", so the cutoff
+    /// only kicks in once there are enough nodes for the ratio to mean
+    /// something.
+    pub min_nodes_for_cutoff: usize,
+}
+
+impl Default for TextIndexConfig {
+    fn default() -> Self {
+        Self {
+            stopwords: HashSet::new(),
+            max_document_frequency: None,
+            min_nodes_for_cutoff: 50,
+        }
+    }
+}
+
+impl TextIndexConfig {
+    /// Create a config with [`CODE_STOPWORDS`] and a 50% frequency cutoff.
+    pub fn for_code() -> Self {
+        Self {
+            stopwords: CODE_STOPWORDS.iter().map(|w| w.to_string()).collect(),
+            max_document_frequency: Some(0.5),
+            ..Default::default()
+        }
+    }
+}
+
+/// Ranking boost for results in recently edited files
+///
+/// A result in a file edited at `now` has its score multiplied by
+/// `1 + max_boost`; the extra part halves every `half_life`. Files without a
+/// recorded edit time get no boost. The boost applies after score fusion and
+/// is not clamped, so boosted scores can exceed 1.0.
+#[derive(Debug, Clone)]
+pub struct RecencyBoost {
+    /// Last edit time of each file, keyed by the indexed file path
+    pub last_edited: HashMap<String, SystemTime>,
+    /// Time file ages are measured from
+    ///
+    /// Set to the current time by [`new`](Self::new); override it to rank
+    /// against a fixed point, e.g. the time of the last index run.
+    pub now: SystemTime,
+    /// Extra score fraction for a file edited at `now` (0.2 = +20%)
+    pub max_boost: f32,
+    /// Age at which the boost has halved
+    pub half_life: Duration,
+}
+
+impl RecencyBoost {
+    /// Boost files by the given edit times, measured from now
+    ///
+    /// Defaults to a +20% boost halving every hour, which favours files
+    /// touched in the current session without burying older results.
+    pub fn new(last_edited: HashMap<String, SystemTime>) -> Self {
+        Self {
+            last_edited,
+            now: SystemTime::now(),
+            max_boost: 0.2,
+            half_life: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Boost files by their filesystem modification time
+    ///
+    /// Files whose metadata cannot be read are left out.
+    pub fn from_file_mtimes<'a>(file_paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let last_edited = file_paths
+            .into_iter()
+            .filter_map(|path| {
+                let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+                Some((path.to_string(), modified))
+            })
+            .collect();
+        Self::new(last_edited)
+    }
+
+    /// Score multiplier for a result in `file_path`
+    fn factor(&self, file_path: &str) -> f32 {
+        let Some(edited) = self.last_edited.get(file_path) else {
+            return 1.0;
+        };
+        // Edits stamped after `now` (clock skew) count as just made
+        let age = self.now.duration_since(*edited).unwrap_or_default();
+        let half_lives = age.as_secs_f64() / self.half_life.as_secs_f64().max(1.0);
+        1.0 + self.max_boost * 0.5f64.powf(half_lives) as f32
+    }
+}
+
 // ============================================================================
 // STAGED RETRIEVAL (Plan 2 — VAL-BPHASE-044, VAL-BPHASE-045)
 // ============================================================================
@@ -1307,6 +1862,7 @@ impl SearchEngine {
         Self {
             nodes: Vec::new(),
             scorer: HybridScorer::new(),
+            weight_override: None,
             vector_index: VectorIndexImpl::BruteForce(VectorIndex::new(
                 DEFAULT_EMBEDDING_DIMENSION,
             )),
@@ -1314,8 +1870,15 @@ impl SearchEngine {
             text_index: HashMap::new(),
             node_id_to_idx: HashMap::new(),
             node_tokens: HashMap::new(),
-            search_cache: LruCache::new(NonZeroUsize::new(SEARCH_CACHE_MAX_ENTRIES).unwrap()),
-            search_cache_bytes: 0,
+            node_positions: HashMap::new(),
+            search_cache: Mutex::new(SearchCache::new()),
+            text_index_config: TextIndexConfig::default(),
+            pruned_tokens: HashSet::new(),
+            recency_boost: None,
+            context_lines: DEFAULT_CONTEXT_LINES,
+            tokenizer: TokenizerKind::default().build(),
+            bloom_prefilter: None,
+            min_query_length: DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH,
         }
     }
 
@@ -1349,13 +1912,21 @@ impl SearchEngine {
         Self {
             nodes: Vec::new(),
             scorer: HybridScorer::new(),
+            weight_override: None,
             vector_index: VectorIndexImpl::BruteForce(VectorIndex::new(dimension)),
             complexity_cache: HashMap::new(),
             text_index: HashMap::new(),
             node_id_to_idx: HashMap::new(),
             node_tokens: HashMap::new(),
-            search_cache: LruCache::new(NonZeroUsize::new(SEARCH_CACHE_MAX_ENTRIES).unwrap()),
-            search_cache_bytes: 0,
+            node_positions: HashMap::new(),
+            search_cache: Mutex::new(SearchCache::new()),
+            text_index_config: TextIndexConfig::default(),
+            pruned_tokens: HashSet::new(),
+            recency_boost: None,
+            context_lines: DEFAULT_CONTEXT_LINES,
+            tokenizer: TokenizerKind::default().build(),
+            bloom_prefilter: None,
+            min_query_length: DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH,
         }
     }
 
@@ -1400,11 +1971,105 @@ impl SearchEngine {
         self.nodes.clear();
         self.complexity_cache.clear();
         self.text_index.clear();
-        self.search_cache.clear();
-        self.search_cache_bytes = 0;
+        self.invalidate_search_cache();
         self.node_id_to_idx.clear();
         self.node_tokens.clear();
+        self.node_positions.clear();
         self.vector_index.clear();
+        self.pruned_tokens.clear();
+        if let Some(prefilter) = &mut self.bloom_prefilter {
+            prefilter.clear();
+        }
+    }
+
+    /// Get the inverted text index configuration
+    #[must_use]
+    pub fn text_index_config(&self) -> &TextIndexConfig {
+        &self.text_index_config
+    }
+
+    /// Replace the inverted text index configuration
+    ///
+    /// The index is rebuilt from the per-node token cache, so this can be
+    /// called before or after nodes are indexed.
+    pub fn set_text_index_config(&mut self, config: TextIndexConfig) {
+        self.text_index_config = config;
+        self.pruned_tokens.clear();
+        self.invalidate_search_cache();
+
+        self.text_index.clear();
+        for (node_id, tokens) in &self.node_tokens {
+            for token in tokens {
+                if !self.text_index_config.stopwords.contains(token) {
+                    self.text_index
+                        .entry(token.clone())
+                        .or_default()
+                        .insert(node_id.clone());
+                }
+            }
+        }
+        self.prune_common_tokens();
+        if let Some(prefilter) = &self.bloom_prefilter {
+            self.bloom_prefilter = Some(BloomPrefilter::build(
+                &self.text_index,
+                prefilter.false_positive_rate(),
+            ));
+        }
+    }
+
+    /// Check candidates against per-token bloom filters before the exact
+    /// posting sets
+    ///
+    /// Saves building a set of every matching node ID per query on very large
+    /// indexes; results are unchanged, since the filters have no false
+    /// negatives and their false positives fail the exact check.
+    /// `false_positive_rate` (e.g.
+    /// [`DEFAULT_BLOOM_FALSE_POSITIVE_RATE`](crate::search::bloom::DEFAULT_BLOOM_FALSE_POSITIVE_RATE))
+    /// sizes each token's filter. Staged search keeps using the posting sets
+    /// directly.
+    pub fn enable_bloom_prefilter(&mut self, false_positive_rate: f64) {
+        self.bloom_prefilter = Some(BloomPrefilter::build(&self.text_index, false_positive_rate));
+    }
+
+    /// Stop using the bloom-filter prefilter and free its filters
+    pub fn disable_bloom_prefilter(&mut self) {
+        self.bloom_prefilter = None;
+    }
+
+    /// Whether candidates go through the bloom-filter prefilter
+    #[must_use]
+    pub fn bloom_prefilter_enabled(&self) -> bool {
+        self.bloom_prefilter.is_some()
+    }
+
+    /// Whether `token` belongs in the inverted text index
+    fn indexes_token(&self, token: &str) -> bool {
+        !self.text_index_config.stopwords.contains(token) && !self.pruned_tokens.contains(token)
+    }
+
+    /// Drop tokens above the configured document-frequency cutoff
+    ///
+    /// Pruned tokens stay out of the index for later appends until the next
+    /// full reindex or config change.
+    fn prune_common_tokens(&mut self) {
+        let Some(max_df) = self.text_index_config.max_document_frequency else {
+            return;
+        };
+        let live = self.nodes.len();
+        if live == 0 || live < self.text_index_config.min_nodes_for_cutoff {
+            return;
+        }
+
+        let common: Vec<String> = self
+            .text_index
+            .iter()
+            .filter(|(_, node_ids)| node_ids.len() as f32 / live as f32 > max_df)
+            .map(|(token, _)| token.clone())
+            .collect();
+        for token in common {
+            self.text_index.remove(&token);
+            self.pruned_tokens.insert(token);
+        }
     }
 
     /// Append nodes to the existing index without clearing.
@@ -1439,8 +2104,7 @@ impl SearchEngine {
             );
         }
 
-        self.search_cache.clear();
-        self.search_cache_bytes = 0;
+        self.invalidate_search_cache();
 
         // Build node_id_to_idx for O(1) node lookups (A1 optimization)
         // Build complexity cache, inverted index, and token cache before taking ownership
@@ -1453,50 +2117,25 @@ impl SearchEngine {
             // Build inverted index for O(1) text lookups
             // This maps each token to the set of node IDs containing it
             // Also build per-node token cache for scoring (T14 optimization)
-            //
-            // R8: Use pre-tokenized tokens when available to skip re-tokenization.
-            // Falls back to content-based tokenization for backward compatibility.
-            let mut tokens = HashSet::new();
-            if let Some(pre_tok) = &node.pre_tokenized {
-                // Use pre-computed tokens directly (already lowercased, filtered >= 2 chars)
-                for token in pre_tok {
-                    self.text_index
-                        .entry(token.clone())
-                        .or_default()
-                        .insert(node.node_id.clone());
-                    tokens.insert(token.clone());
-                }
-            } else {
-                for token in node.content.split(|c: char| !c.is_alphanumeric()) {
-                    let normalized_token: String = token.to_ascii_lowercase();
-                    // Skip empty tokens and very short ones (< 2 chars) to reduce noise
-                    if normalized_token.len() >= 2 {
-                        self.text_index
-                            .entry(normalized_token.clone())
-                            .or_default()
-                            .insert(node.node_id.clone());
-                        tokens.insert(normalized_token);
-                    }
-                }
-            }
-            self.node_tokens.insert(node.node_id.clone(), tokens);
+            self.index_node_tokens(node);
         }
 
         // Build vector index from TF-IDF embeddings — clone only embeddings (A4 optimization)
         // All other node content is moved via ownership, avoiding a full Vec clone
         for node in nodes.iter_mut() {
             // Use tfidf_embedding (always present) instead of optional embedding
-            if !node.tfidf_embedding.is_empty() {
-                if let Err(e) = self
-                    .vector_index
-                    .insert(node.node_id.clone(), node.tfidf_embedding.clone())
-                {
-                    tracing::warn!(
-                        "Failed to insert TF-IDF embedding for node {}: {:?}",
-                        node.node_id,
-                        e
-                    );
-                }
+            if !self.accept_embedding(node) {
+                continue;
+            }
+            if let Err(e) = self
+                .vector_index
+                .insert(node.node_id.clone(), node.tfidf_embedding.clone())
+            {
+                tracing::warn!(
+                    "Failed to insert TF-IDF embedding for node {}: {:?}",
+                    node.node_id,
+                    e
+                );
             }
         }
 
@@ -1516,6 +2155,70 @@ impl SearchEngine {
 
         // Append nodes to storage
         self.nodes.extend(nodes);
+        self.prune_common_tokens();
+    }
+
+    /// Add a node's tokens to the inverted index, token cache and position map.
+    ///
+    /// R8: Uses pre-tokenized tokens when available to skip re-tokenization.
+    /// Falls back to content-based tokenization for backward compatibility.
+    fn index_node_tokens(&mut self, node: &NodeInfo) {
+        let sequence: Vec<String> = match &node.pre_tokenized {
+            // Pre-computed tokens are already lowercased and filtered >= 2 chars
+            Some(pre_tok) => pre_tok.clone(),
+            // Skip empty tokens and very short ones (< 2 chars) to reduce noise
+            None => node
+                .content
+                .split(|c: char| !c.is_alphanumeric())
+                .map(|token| token.to_ascii_lowercase())
+                .filter(|token| token.len() >= 2)
+                .collect(),
+        };
+
+        let mut tokens = HashSet::new();
+        let mut positions: HashMap<String, Vec<u32>> = HashMap::new();
+        for (position, token) in sequence.into_iter().enumerate() {
+            if self.indexes_token(&token) {
+                let postings = self.text_index.entry(token.clone()).or_default();
+                postings.insert(node.node_id.clone());
+                if let Some(prefilter) = &mut self.bloom_prefilter {
+                    prefilter.insert(&token, &node.node_id, postings);
+                }
+            }
+            positions
+                .entry(token.clone())
+                .or_default()
+                .push(position as u32);
+            tokens.insert(token);
+        }
+        self.node_tokens.insert(node.node_id.clone(), tokens);
+        self.node_positions.insert(node.node_id.clone(), positions);
+    }
+
+    /// Check a node's TF-IDF embedding against the configured dimension.
+    ///
+    /// Returns `true` if the embedding can go into the vector index. A
+    /// non-empty embedding of the wrong length (e.g. loaded from an index built
+    /// with a different `embedding_dim`) is logged and dropped, so the node
+    /// stays searchable by text instead of poisoning the vector index.
+    fn accept_embedding(&self, node: &mut NodeInfo) -> bool {
+        if node.tfidf_embedding.is_empty() {
+            return false;
+        }
+        let expected = self.embedding_dim();
+        if node.tfidf_embedding.len() != expected {
+            tracing::warn!(
+                "Skipping embedding for node {}: {}",
+                node.node_id,
+                Error::DimensionMismatch {
+                    expected,
+                    got: node.tfidf_embedding.len(),
+                }
+            );
+            node.tfidf_embedding.clear();
+            return false;
+        }
+        true
     }
 
     /// Restore neural embeddings from a persisted mmap embedding index.
@@ -1587,8 +2290,7 @@ impl SearchEngine {
     /// ```
     pub fn incremental_reindex(&mut self, delta: TextIndexDelta) {
         // Invalidate search cache — results may change
-        self.search_cache.clear();
-        self.search_cache_bytes = 0;
+        self.invalidate_search_cache();
 
         // Phase 1: Remove nodes
         for node_id in &delta.removed_node_ids {
@@ -1599,6 +2301,7 @@ impl SearchEngine {
         for node in delta.updated_nodes {
             self.add_node_to_index(node);
         }
+        self.prune_common_tokens();
 
         // Verify we don't exceed limits
         if self.nodes.len() > MAX_NODES {
@@ -1619,6 +2322,8 @@ impl SearchEngine {
             return; // Node not in index, nothing to do
         };
 
+        self.node_positions.remove(node_id);
+
         // Remove from text_index: for each token the node contributed to,
         // remove the node_id from the token's set. Clean up empty sets.
         if let Some(tokens) = self.node_tokens.remove(node_id) {
@@ -1665,31 +2370,7 @@ impl SearchEngine {
         let new_idx = self.nodes.len();
 
         // Build inverted index entries and token cache for this node
-        //
-        // R8: Use pre-tokenized tokens when available to skip re-tokenization.
-        // Falls back to content-based tokenization for backward compatibility.
-        let mut tokens = HashSet::new();
-        if let Some(pre_tok) = &node.pre_tokenized {
-            for token in pre_tok {
-                self.text_index
-                    .entry(token.clone())
-                    .or_default()
-                    .insert(node_id.clone());
-                tokens.insert(token.clone());
-            }
-        } else {
-            for token in node.content.split(|c: char| !c.is_alphanumeric()) {
-                let normalized_token: String = token.to_ascii_lowercase();
-                if normalized_token.len() >= 2 {
-                    self.text_index
-                        .entry(normalized_token.clone())
-                        .or_default()
-                        .insert(node_id.clone());
-                    tokens.insert(normalized_token);
-                }
-            }
-        }
-        self.node_tokens.insert(node_id.clone(), tokens);
+        self.index_node_tokens(&node);
 
         // Update node_id_to_idx
         self.node_id_to_idx.insert(node_id.clone(), new_idx);
@@ -1699,7 +2380,7 @@ impl SearchEngine {
             .insert(node_id.clone(), node.complexity);
 
         // Insert TF-IDF embedding into vector index (always present)
-        if !node.tfidf_embedding.is_empty() {
+        if self.accept_embedding(&mut node) {
             if let Err(e) = self
                 .vector_index
                 .insert(node_id.clone(), node.tfidf_embedding.clone())
@@ -1760,6 +2441,25 @@ impl SearchEngine {
             .collect()
     }
 
+    /// Return the indexed metadata for a live node.
+    ///
+    /// Node content is cleared at index time, so only the metadata and
+    /// embeddings are populated.
+    pub fn node_info(&self, node_id: &str) -> Option<&NodeInfo> {
+        self.node_id_to_idx
+            .get(node_id)
+            .and_then(|&idx| self.nodes.get(idx))
+    }
+
+    /// Return the TF-IDF embedding for a live node, if it has one.
+    pub fn node_embedding(&self, node_id: &str) -> Option<&[f32]> {
+        self.node_id_to_idx
+            .get(node_id)
+            .and_then(|&idx| self.nodes.get(idx))
+            .map(|n| n.tfidf_embedding.as_slice())
+            .filter(|e| !e.is_empty())
+    }
+
     /// Check if the index is empty
     ///
     /// # Returns
@@ -1819,14 +2519,32 @@ impl SearchEngine {
     ///
     /// Part of the B-phase memory accounting surface (VAL-BPHASE-024).
     pub fn search_cache_len(&self) -> usize {
-        self.search_cache.len()
+        self.cache().entries.len()
     }
 
     /// Return the tracked byte estimate for the search cache.
     ///
     /// Part of the B-phase memory accounting surface (VAL-BPHASE-024).
     pub fn search_cache_bytes(&self) -> usize {
-        self.search_cache_bytes
+        self.cache().bytes
+    }
+
+    /// Lock the result cache
+    ///
+    /// A panic while holding the lock cannot leave the cache inconsistent
+    /// in a way that matters, so poisoning is ignored.
+    fn cache(&self) -> MutexGuard<'_, SearchCache> {
+        self.search_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Drop all cached search results and the fuzzy vocabulary
+    fn invalidate_search_cache(&mut self) {
+        self.search_cache
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
     }
 
     /// Produce a compact, row-oriented snapshot of the resident search
@@ -1961,24 +2679,34 @@ impl SearchEngine {
     ///
     /// # Errors
     ///
-    /// Returns `Error::QueryFailed` if the search operation fails.
-    pub fn search(&mut self, query: SearchQuery) -> Result<Vec<SearchResult>, Error> {
+    /// - `Error::EmptyQuery` if the query has no text and is not semantic
+    /// - `Error::InvalidEfSearch` if `ef_search` is zero or below `top_k`
+    pub fn search(&self, query: SearchQuery) -> Result<Vec<SearchResult>, Error> {
+        Self::validate_query(&query)?;
         if self.nodes.is_empty() {
             return Ok(Vec::new());
         }
 
         // Check cache first
         let cache_key = format!(
-            "{}:{}:{:?}:{}:{:?}:neural={}",
+            "{}:{}:{:?}:{}:{:?}:{:?}:{:?}:{}:{:?}:{:?}:fuzzy={}:context={}/{:?}:neural={}",
             query.query,
             query.top_k,
             query.threshold,
             query.semantic,
             query.query_type,
+            query.fusion,
+            query.expansions,
+            query.dedup_symbols,
+            query.ef_search,
+            Self::node_types_cache_key(&query),
+            query.fuzzy,
+            query.expand_context,
+            query.token_budget,
             query.query_neural_embedding.is_some()
         );
-        if let Some(cached) = self.search_cache.get(&cache_key) {
-            return Ok(cached.clone());
+        if let Some(cached) = self.cache().get(&cache_key) {
+            return Ok(cached);
         }
 
         let mut results = Vec::new();
@@ -2010,8 +2738,7 @@ impl SearchEngine {
                 // coverage of relevant nodes. Text index matches may not
                 // overlap with top vector results, so we need a larger pool.
                 let vector_search_k = (query.top_k * 10).max(100);
-                self.vector_index
-                    .search(&emb, vector_search_k)
+                self.vector_search(&emb, vector_search_k, query.ef_search)
                     .into_iter()
                     .collect()
             } else {
@@ -2023,24 +2750,15 @@ impl SearchEngine {
 
         // Pre-compute query data for optimized text scoring
         // This reduces allocations from O(N) to O(1) per search
-        let text_query = TextQueryPreprocessed::from_query(&query.query);
+        let text_query = self.preprocess_text_query(&query);
 
         // Use inverted index to filter candidates - only check nodes that contain query terms
         // This reduces search complexity from O(N) to O(M) where M is number of matching nodes
-        let candidates = if text_query.query_tokens.is_empty() {
-            // No query tokens, check all nodes
+        let candidates = if text_query.query_tokens.is_empty() || text_query.symbol_prefix {
+            // No query tokens (or only symbol names matter), check all nodes
             self.nodes.iter().collect::<Vec<_>>()
         } else {
-            // Build candidate set using inverted index - O(1) per token lookup
-            let mut candidate_ids: HashSet<&str> = HashSet::new();
-
-            for token in &text_query.query_tokens {
-                if let Some(node_ids) = self.text_index.get(token) {
-                    for node_id in node_ids {
-                        candidate_ids.insert(node_id.as_str());
-                    }
-                }
-            }
+            let candidate_ids = self.text_candidates(&text_query);
 
             // If no matches in inverted index, return empty results early
             if candidate_ids.is_empty() && !query.semantic {
@@ -2074,6 +2792,10 @@ impl SearchEngine {
         };
 
         for node in candidates {
+            if !Self::matches_node_types(&query, node) {
+                continue;
+            }
+
             let text_score = self.calculate_text_score_optimized(
                 &text_query,
                 &node.node_id,
@@ -2088,8 +2810,11 @@ impl SearchEngine {
                 0.0
             };
 
-            // For now, if no text match and not semantic, skip
-            if text_score == 0.0 && !query.semantic && tfidf_score == 0.0 {
+            // For now, if no text match and not semantic, skip; over-short
+            // queries only ever match by symbol name
+            if text_score == 0.0
+                && (text_query.symbol_prefix || (!query.semantic && tfidf_score == 0.0))
+            {
                 continue;
             }
 
@@ -2127,6 +2852,11 @@ impl SearchEngine {
             }
         }
 
+        if query.fusion == ScoreFusion::Rrf {
+            Self::apply_rrf(&mut results);
+        }
+        self.apply_recency_boost(&mut results);
+
         // Sort by score (descending)
         results.sort_by(|a, b| {
             b.score
@@ -2135,6 +2865,10 @@ impl SearchEngine {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
+        if query.dedup_symbols {
+            Self::dedup_by_symbol(&mut results);
+        }
+
         // Take top_k
         let top_k = results.into_iter().take(query.top_k).collect::<Vec<_>>();
 
@@ -2142,36 +2876,31 @@ impl SearchEngine {
         for (i, result) in final_results.iter_mut().enumerate() {
             result.rank = i + 1;
         }
+        self.attach_inline_context(&query, &mut final_results);
 
-        // Cache results with byte-budget enforcement (A+ Section 8.1)
-        {
-            let results_bytes = Self::estimate_search_results_bytes(&final_results);
-            // Guard: skip insertion if a single entry exceeds the cache budget.
-            if results_bytes < SEARCH_CACHE_MAX_BYTES {
-                // If replacing an existing entry, subtract its bytes first
-                if let Some(existing) = self.search_cache.get(&cache_key) {
-                    self.search_cache_bytes = self
-                        .search_cache_bytes
-                        .saturating_sub(Self::estimate_search_results_bytes(existing));
-                }
-                // Evict until there is room
-                while self.search_cache_bytes + results_bytes > SEARCH_CACHE_MAX_BYTES
-                    && !self.search_cache.is_empty()
-                {
-                    if let Some((_, evicted)) = self.search_cache.pop_lru() {
-                        self.search_cache_bytes = self
-                            .search_cache_bytes
-                            .saturating_sub(Self::estimate_search_results_bytes(&evicted));
-                    }
-                }
-                self.search_cache_bytes += results_bytes;
-                self.search_cache.put(cache_key, final_results.clone());
-            }
-        }
+        self.cache().insert(cache_key, &final_results);
 
         Ok(final_results)
     }
 
+    /// Run [`search`](Self::search) on tokio's blocking thread pool
+    ///
+    /// Lets async callers (e.g. leserve handlers) run concurrent searches
+    /// against a shared engine without stalling the runtime.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`search`](Self::search); a search task that panics is
+    /// reported as `Error::TaskFailed`.
+    pub async fn search_async(
+        self: Arc<Self>,
+        query: SearchQuery,
+    ) -> Result<Vec<SearchResult>, Error> {
+        tokio::task::spawn_blocking(move || self.search(query))
+            .await
+            .map_err(|e| Error::TaskFailed(e.to_string()))?
+    }
+
     /// Execute a staged retrieval search: coarse candidate generation followed
     /// by exact rerank (Plan 2 — VAL-BPHASE-044, VAL-BPHASE-045).
     ///
@@ -2200,7 +2929,7 @@ impl SearchEngine {
     ///
     /// Same error conditions as [`search`](Self::search).
     pub fn search_staged(
-        &mut self,
+        &self,
         query: SearchQuery,
         config: &StagedRetrievalConfig,
     ) -> Result<(Vec<SearchResult>, StagedRetrievalMetrics), Error> {
@@ -2219,6 +2948,7 @@ impl SearchEngine {
             ));
         }
 
+        Self::validate_query(&query)?;
         if self.nodes.is_empty() {
             return Ok((
                 Vec::new(),
@@ -2231,19 +2961,27 @@ impl SearchEngine {
 
         // Check staged-search cache (key includes query, top_k, threshold, semantic, coarse_multiplier, query_type)
         let cache_key = format!(
-            "staged:{}:{}:{:?}:{}:{:?}:{:?}:neural={}",
+            "staged:{}:{}:{:?}:{}:{:?}:{:?}:{:?}:{:?}:{}:{:?}:{:?}:fuzzy={}:context={}/{:?}:neural={}",
             query.query,
             query.top_k,
             query.threshold,
             query.semantic,
             config.coarse_multiplier,
             query.query_type,
+            query.fusion,
+            query.expansions,
+            query.dedup_symbols,
+            query.ef_search,
+            Self::node_types_cache_key(&query),
+            query.fuzzy,
+            query.expand_context,
+            query.token_budget,
             query.query_neural_embedding.is_some()
         );
-        if let Some(cached) = self.search_cache.get(&cache_key) {
+        if let Some(cached) = self.cache().get(&cache_key) {
             let count = cached.len();
             return Ok((
-                cached.clone(),
+                cached,
                 StagedRetrievalMetrics {
                     coarse_candidates: 0,
                     exact_scored: count,
@@ -2273,9 +3011,13 @@ impl SearchEngine {
         let coarse_top_k = query.top_k.saturating_mul(config.coarse_multiplier);
 
         // Start with text-index candidates (always included)
-        let text_query = TextQueryPreprocessed::from_query(&query.query);
+        let text_query = self.preprocess_text_query(&query);
         let mut coarse_candidate_ids: HashSet<String> = HashSet::new();
-        for token in &text_query.query_tokens {
+        if text_query.symbol_prefix {
+            // Symbol-name prefixes are not in the text index
+            coarse_candidate_ids.extend(self.nodes.iter().map(|node| node.node_id.clone()));
+        }
+        for token in text_query.candidate_tokens() {
             if let Some(node_ids) = self.text_index.get(token) {
                 for id in node_ids {
                     coarse_candidate_ids.insert(id.clone());
@@ -2286,7 +3028,7 @@ impl SearchEngine {
         // Add vector-similarity candidates if semantic search is requested
         let vector_results: HashMap<String, f32> = if query.semantic {
             if let Some(ref emb) = query.query_embedding {
-                let vec_hits = self.vector_index.search(emb, coarse_top_k);
+                let vec_hits = self.vector_search(emb, coarse_top_k, query.ef_search);
                 for (id, _) in &vec_hits {
                     coarse_candidate_ids.insert(id.clone());
                 }
@@ -2319,6 +3061,9 @@ impl SearchEngine {
             if !coarse_candidate_ids.contains(&node.node_id) {
                 continue;
             }
+            if !Self::matches_node_types(&query, node) {
+                continue;
+            }
 
             let text_score = self.calculate_text_score_optimized(
                 &text_query,
@@ -2333,7 +3078,9 @@ impl SearchEngine {
                 0.0
             };
 
-            if text_score == 0.0 && !query.semantic && tfidf_score == 0.0 {
+            if text_score == 0.0
+                && (text_query.symbol_prefix || (!query.semantic && tfidf_score == 0.0))
+            {
                 continue;
             }
 
@@ -2370,6 +3117,11 @@ impl SearchEngine {
 
         metrics.exact_scored = results.len();
 
+        if query.fusion == ScoreFusion::Rrf {
+            Self::apply_rrf(&mut results);
+        }
+        self.apply_recency_boost(&mut results);
+
         // Sort by score (descending)
         results.sort_by(|a, b| {
             b.score
@@ -2378,6 +3130,10 @@ impl SearchEngine {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
+        if query.dedup_symbols {
+            Self::dedup_by_symbol(&mut results);
+        }
+
         // Take top_k
         let mut final_results: Vec<SearchResult> = results.into_iter().take(query.top_k).collect();
         for (i, result) in final_results.iter_mut().enumerate() {
@@ -2385,30 +3141,9 @@ impl SearchEngine {
         }
 
         metrics.results_returned = final_results.len();
+        self.attach_inline_context(&query, &mut final_results);
 
-        // Cache results with byte-budget enforcement
-        {
-            let results_bytes = Self::estimate_search_results_bytes(&final_results);
-            // Guard: skip insertion if a single entry exceeds the cache budget.
-            if results_bytes < SEARCH_CACHE_MAX_BYTES {
-                if let Some(existing) = self.search_cache.get(&cache_key) {
-                    self.search_cache_bytes = self
-                        .search_cache_bytes
-                        .saturating_sub(Self::estimate_search_results_bytes(existing));
-                }
-                while self.search_cache_bytes + results_bytes > SEARCH_CACHE_MAX_BYTES
-                    && !self.search_cache.is_empty()
-                {
-                    if let Some((_, evicted)) = self.search_cache.pop_lru() {
-                        self.search_cache_bytes = self
-                            .search_cache_bytes
-                            .saturating_sub(Self::estimate_search_results_bytes(&evicted));
-                    }
-                }
-                self.search_cache_bytes += results_bytes;
-                self.search_cache.put(cache_key, final_results.clone());
-            }
-        }
+        self.cache().insert(cache_key, &final_results);
 
         Ok((final_results, metrics))
     }
@@ -2488,10 +3223,12 @@ impl SearchEngine {
             }
         };
 
-        let mut score = self
-            .scorer
-            .with_weights_hybrid(w_tfidf, w_neural, w_structural, w_text)
-            .score_hybrid(tfidf_score, neural_score, structural_score, text_score);
+        let scorer = self.weight_override.unwrap_or_else(|| {
+            self.scorer
+                .with_weights_hybrid(w_tfidf, w_neural, w_structural, w_text)
+        });
+        let mut score =
+            scorer.score_hybrid(tfidf_score, neural_score, structural_score, text_score);
 
         // --- Post-scoring adjustments (shared logic) ---
 
@@ -2521,6 +3258,184 @@ impl SearchEngine {
         score
     }
 
+    /// Multiply each result's fused score by its file's recency factor
+    ///
+    /// Runs after [`apply_rrf`](Self::apply_rrf) so the boost survives fusion,
+    /// and is not clamped so results already scoring 1.0 still separate.
+    fn apply_recency_boost(&self, results: &mut [SearchResult]) {
+        let Some(recency) = &self.recency_boost else {
+            return;
+        };
+        for result in results {
+            result.score.overall *= recency.factor(&result.file_path);
+        }
+    }
+
+    /// Replace each result's overall score with its Reciprocal Rank Fusion score
+    ///
+    /// Results are ranked independently by keyword match and by semantic
+    /// similarity (the stronger of TF-IDF and neural); a result only
+    /// contributes to a ranking when its signal is non-zero. The fused score
+    /// is scaled by the best achievable value so it stays in 0-1.
+    fn apply_rrf(results: &mut [SearchResult]) {
+        fn ranks(results: &[SearchResult], signal: impl Fn(&Score) -> f32) -> Vec<Option<usize>> {
+            let mut order: Vec<usize> = (0..results.len())
+                .filter(|&i| signal(&results[i].score) > 0.0)
+                .collect();
+            order.sort_by(|&a, &b| {
+                signal(&results[b].score)
+                    .partial_cmp(&signal(&results[a].score))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| results[a].node_id.cmp(&results[b].node_id))
+            });
+            let mut ranks = vec![None; results.len()];
+            for (rank, idx) in order.into_iter().enumerate() {
+                ranks[idx] = Some(rank + 1);
+            }
+            ranks
+        }
+
+        let keyword = ranks(results, |score| score.text_match);
+        let semantic = ranks(results, |score| score.tfidf.max(score.neural));
+        let best = 2.0 / (RRF_K + 1.0);
+
+        for (i, result) in results.iter_mut().enumerate() {
+            let fused: f32 = [keyword[i], semantic[i]]
+                .into_iter()
+                .flatten()
+                .map(|rank| 1.0 / (RRF_K + rank as f32))
+                .sum();
+            result.score.overall = fused / best;
+        }
+    }
+
+    /// Reject queries that cannot rank anything, or an `ef_search` below `top_k`
+    fn validate_query(query: &SearchQuery) -> Result<(), Error> {
+        if query.query.trim().is_empty() && !query.semantic {
+            return Err(Error::EmptyQuery);
+        }
+        match query.ef_search {
+            Some(ef) if ef == 0 || ef < query.top_k => Err(Error::InvalidEfSearch {
+                ef_search: ef,
+                top_k: query.top_k,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether `node` passes the query's symbol-kind facet
+    fn matches_node_types(query: &SearchQuery, node: &NodeInfo) -> bool {
+        match &query.node_types {
+            Some(kinds) => node
+                .node_type
+                .as_ref()
+                .is_some_and(|kind| kinds.contains(kind)),
+            None => true,
+        }
+    }
+
+    /// Order-independent cache-key fragment for the symbol-kind facet
+    fn node_types_cache_key(query: &SearchQuery) -> Option<Vec<String>> {
+        query.node_types.as_ref().map(|kinds| {
+            let mut names: Vec<String> = kinds.iter().map(|kind| format!("{:?}", kind)).collect();
+            names.sort();
+            names
+        })
+    }
+
+    /// Vector search honoring the query's `ef_search` override
+    fn vector_search(
+        &self,
+        embedding: &[f32],
+        top_k: usize,
+        ef: Option<usize>,
+    ) -> Vec<(String, f32)> {
+        match ef {
+            Some(ef) => self.vector_index.search_with_ef(embedding, top_k, ef),
+            None => self.vector_index.search(embedding, top_k),
+        }
+    }
+
+    /// Fill each result's `context` with its source when the query asks for it
+    ///
+    /// Results are filled in rank order until `token_budget` runs out; the
+    /// one that crosses it is truncated and the rest keep `None`, as do
+    /// results whose file can no longer be read.
+    fn attach_inline_context(&self, query: &SearchQuery, results: &mut [SearchResult]) {
+        if !query.expand_context {
+            return;
+        }
+        let mut remaining = query.token_budget.unwrap_or(usize::MAX);
+        let mut sources: HashMap<String, Option<String>> = HashMap::new();
+        for result in results {
+            if remaining == 0 {
+                break;
+            }
+            let source = sources
+                .entry(result.file_path.clone())
+                .or_insert_with(|| std::fs::read_to_string(&result.file_path).ok());
+            let Some(context) = source
+                .as_deref()
+                .and_then(|source| inline_context(source, result.byte_range, self.context_lines))
+            else {
+                continue;
+            };
+            let context = self.tokenizer.truncate_to_tokens(context, remaining);
+            if context.is_empty() {
+                break;
+            }
+            remaining = remaining.saturating_sub(self.tokenizer.count_tokens(context));
+            result.context = Some(context.to_string());
+        }
+    }
+
+    /// Keep only the first result per `(file_path, symbol_name)`
+    ///
+    /// Expects results sorted by descending score, so the survivor is the
+    /// best-scoring duplicate. Ranks are assigned afterwards by the caller.
+    fn dedup_by_symbol(results: &mut Vec<SearchResult>) {
+        let mut seen = HashSet::new();
+        results.retain(|r| seen.insert((r.file_path.clone(), r.symbol_name.clone())));
+    }
+
+    /// Look up the nodes matching any candidate token through the inverted index
+    fn text_candidates<'a>(&'a self, text_query: &'a TextQueryPreprocessed) -> TextCandidates<'a> {
+        let postings = text_query
+            .candidate_tokens()
+            .filter_map(|token| Some((token, self.text_index.get(token)?)))
+            .filter(|(_, ids)| !ids.is_empty());
+        match &self.bloom_prefilter {
+            Some(prefilter) => TextCandidates::Bloom(
+                postings
+                    .map(|(token, ids)| (prefilter.get(token), ids))
+                    .collect(),
+            ),
+            None => TextCandidates::Exact(
+                postings
+                    .flat_map(|(_, ids)| ids.iter().map(String::as_str))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Pre-compute the text matching data of a query
+    fn preprocess_text_query(&self, query: &SearchQuery) -> TextQueryPreprocessed {
+        let mut text_query =
+            TextQueryPreprocessed::from_query(&query.query).with_expansions(&query.expansions);
+        text_query.symbol_prefix =
+            (1..self.min_query_length).contains(&meaningful_query_length(&text_query.query_lower));
+        if query.fuzzy {
+            let vocabulary = Arc::clone(
+                self.cache()
+                    .vocabulary
+                    .get_or_insert_with(|| Arc::new(FuzzyVocabulary::new(self.text_index.keys()))),
+            );
+            text_query.with_fuzzy_matches(&vocabulary)
+        } else {
+            text_query
+        }
+    }
+
     /// Optimized text score calculation using cached node tokens and pre-computed query data
     ///
     /// Uses the node_tokens HashMap for O(1) token overlap calculation instead of
@@ -2538,14 +3453,31 @@ impl SearchEngine {
         symbol_name: &str,
         file_path: &str,
     ) -> f32 {
+        // Quoted phrases are hard requirements: no adjacency, no text score
+        if !precomputed.phrases.is_empty() {
+            let Some(positions) = self.node_positions.get(node_id) else {
+                return 0.0;
+            };
+            if !precomputed
+                .phrases
+                .iter()
+                .all(|phrase| contains_phrase(positions, phrase))
+            {
+                return 0.0;
+            }
+        }
+
         let symbol_lower = symbol_name.to_ascii_lowercase();
+        if precomputed.symbol_prefix {
+            return precomputed.symbol_prefix_score(&symbol_lower, symbol_name);
+        }
 
         // Detect fully-qualified external references (e.g., "crate::module::function_name").
         // These should rank lower than actual definitions.
         let is_qualified_ref = symbol_lower.contains("::") || symbol_lower.contains('.');
 
         // Exact symbol name match: maximum boost
-        let symbol_boost = if symbol_lower == precomputed.query_lower {
+        let mut symbol_boost = if symbol_lower == precomputed.query_lower {
             1.0
         } else if symbol_lower.contains(&precomputed.query_lower) {
             // Query is a substring of symbol name (e.g., query "tool_call" in "handle_tool_call")
@@ -2561,6 +3493,10 @@ impl SearchEngine {
         } else {
             0.0
         };
+        if symbol_boost == 0.0 && precomputed.fuzzy {
+            symbol_boost =
+                FUZZY_MATCH_WEIGHT * precomputed.fuzzy_name_similarity(&symbol_lower, symbol_name);
+        }
 
         // Penalty for test-related files to address Limitation 4
         let test_penalty =
@@ -2574,10 +3510,11 @@ impl SearchEngine {
         // Tokens were cached during index_nodes() — no re-tokenization needed.
         // This avoids iterating over each query token and checking the inverted index,
         // replacing it with a single set intersection on pre-cached per-node tokens.
-        let base_score = if precomputed.query_tokens.is_empty() {
+        let node_tokens = self.node_tokens.get(node_id);
+        let mut base_score = if precomputed.query_tokens.is_empty() {
             // No meaningful tokens in query
             0.0
-        } else if let Some(node_tokens) = self.node_tokens.get(node_id) {
+        } else if let Some(node_tokens) = node_tokens {
             // Count overlap between query tokens and cached node tokens
             let matching = precomputed.query_tokens.intersection(node_tokens).count();
             matching as f32 / precomputed.query_tokens.len() as f32
@@ -2585,6 +3522,18 @@ impl SearchEngine {
             0.0
         };
 
+        // Expansion terms only score nodes the query as written missed entirely
+        if base_score == 0.0 && symbol_boost == 0.0 && !precomputed.expansion_tokens.is_empty() {
+            if let Some(node_tokens) = node_tokens {
+                let matching = precomputed
+                    .expansion_tokens
+                    .intersection(node_tokens)
+                    .count();
+                base_score = EXPANSION_MATCH_WEIGHT * matching as f32
+                    / precomputed.expansion_tokens.len() as f32;
+            }
+        }
+
         ((base_score + symbol_boost) - test_penalty).clamp(0.0, 1.0)
     }
 
@@ -2611,7 +3560,8 @@ impl SearchEngine {
     ///
     /// # Errors
     ///
-    /// Returns `Error::QueryFailed` if dimension mismatch or search fails.
+    /// Returns `Error::DimensionMismatch` if `query_embedding` does not match
+    /// the index dimension.
     pub fn semantic_search(
         &self,
         query_embedding: &[f32],
@@ -2624,11 +3574,10 @@ impl SearchEngine {
 
         // Validate embedding dimension (only needed when we actually have embeddings)
         if query_embedding.len() != self.vector_index.dimension() {
-            return Err(Error::QueryFailed(format!(
-                "Embedding dimension mismatch: expected {}, got {}",
-                self.vector_index.dimension(),
-                query_embedding.len()
-            )));
+            return Err(Error::DimensionMismatch {
+                expected: self.vector_index.dimension(),
+                got: query_embedding.len(),
+            });
         }
 
         // Perform vector similarity search
@@ -2672,6 +3621,14 @@ impl SearchEngine {
         &self.vector_index
     }
 
+    /// Get the configured embedding dimension
+    ///
+    /// Every TF-IDF embedding indexed by this engine must have this length.
+    #[must_use]
+    pub fn embedding_dim(&self) -> usize {
+        self.vector_index.dimension()
+    }
+
     /// Get mutable access to the vector index
     ///
     /// This allows direct manipulation of the vector index.
@@ -2691,11 +3648,91 @@ impl SearchEngine {
         &mut self.vector_index
     }
 
+    /// Get the scorer weights pinned with [`set_scorer`](Self::set_scorer)
+    ///
+    /// `None` means weights are chosen per query from its type and the
+    /// available signals.
+    #[must_use]
+    pub fn scorer(&self) -> Option<&HybridScorer> {
+        self.weight_override.as_ref()
+    }
+
+    /// Pin the scoring weights for every query
+    ///
+    /// Overrides the adaptive per-query weights, e.g. to bias toward keyword
+    /// precision (`HybridScorer::new().with_weights(0.2, 0.1, 0.7)`) or
+    /// semantic recall. Name-match boosts and penalties still apply on top.
+    pub fn set_scorer(&mut self, scorer: HybridScorer) {
+        self.weight_override = Some(scorer);
+        self.invalidate_search_cache();
+    }
+
+    /// Return to adaptive per-query weights
+    pub fn reset_scorer(&mut self) {
+        self.weight_override = None;
+        self.invalidate_search_cache();
+    }
+
+    /// Lines of surrounding source included on each side of inline context
+    #[must_use]
+    pub fn context_lines(&self) -> usize {
+        self.context_lines
+    }
+
+    /// Set how many surrounding lines inline result context includes
+    pub fn set_context_lines(&mut self, lines: usize) {
+        self.context_lines = lines;
+        self.invalidate_search_cache();
+    }
+
+    /// Tokenizer that counts inline context against the token budget
+    #[must_use]
+    pub fn tokenizer(&self) -> Arc<dyn Tokenizer> {
+        Arc::clone(&self.tokenizer)
+    }
+
+    /// Set the tokenizer that counts inline context against the token budget
+    pub fn set_tokenizer(&mut self, tokenizer: Arc<dyn Tokenizer>) {
+        self.tokenizer = tokenizer;
+        self.invalidate_search_cache();
+    }
+
+    /// Shortest meaningful query length
+    #[must_use]
+    pub fn min_query_length(&self) -> usize {
+        self.min_query_length
+    }
+
+    /// Set the shortest meaningful query length
+    ///
+    /// Queries whose longest alphanumeric run is shorter than this (by default
+    /// [`DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH`], so one-character queries) skip
+    /// content tokenization and match symbol names by prefix instead.
+    pub fn set_min_query_length(&mut self, min_query_length: usize) {
+        self.min_query_length = min_query_length;
+        self.invalidate_search_cache();
+    }
+
+    /// Get the recency boost set with [`set_recency_boost`](Self::set_recency_boost)
+    #[must_use]
+    pub fn recency_boost(&self) -> Option<&RecencyBoost> {
+        self.recency_boost.as_ref()
+    }
+
+    /// Boost results in recently edited files, or stop boosting with `None`
+    ///
+    /// Off by default, so batch and CI searches rank purely on content.
+    pub fn set_recency_boost(&mut self, boost: Option<RecencyBoost>) {
+        self.recency_boost = boost;
+        self.invalidate_search_cache();
+    }
+
     /// Enable HNSW for faster approximate search
     ///
     /// This converts the vector index from brute-force to HNSW-based.
-    /// Existing indexed vectors are **NOT** automatically migrated - you must
-    /// re-index your data after enabling HNSW.
+    /// Existing indexed vectors are **NOT** automatically migrated - call
+    /// [`migrate_vector_index`](Self::migrate_vector_index) or re-index your
+    /// data after enabling HNSW.
     ///
     /// # Arguments
     ///
@@ -2779,6 +3816,30 @@ impl SearchEngine {
         matches!(self.vector_index, VectorIndexImpl::HNSWQuantized(_))
     }
 
+    /// Insert the indexed nodes' embeddings into the current vector index
+    ///
+    /// Carries vectors over after switching index type (e.g. with
+    /// [`enable_hnsw`](Self::enable_hnsw)) without re-indexing. Returns the
+    /// number of vectors migrated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::IndexMigrationFailed` when the index rejects an
+    /// embedding; the vectors migrated before it stay in the index.
+    pub fn migrate_vector_index(&mut self) -> Result<usize, Error> {
+        self.vector_index.clear();
+        let embeddings = self.collect_embeddings();
+        let total = embeddings.len();
+        for (migrated, (node_id, embedding)) in embeddings.into_iter().enumerate() {
+            if let Err(e) = self.vector_index.insert(node_id, embedding) {
+                tracing::warn!("Vector index migration stopped: {}", e);
+                return Err(Error::IndexMigrationFailed { migrated, total });
+            }
+        }
+        self.invalidate_search_cache();
+        Ok(total)
+    }
+
     /// Estimate memory usage in bytes
     #[must_use]
     pub fn estimated_memory_bytes(&self) -> usize {
@@ -2792,8 +3853,25 @@ impl SearchEngine {
             .values()
             .map(|set| set.len() * std::mem::size_of::<String>())
             .sum::<usize>();
+        // Phrase matching positions: one token key and position list per
+        // distinct token of each node
+        let positions_size = self
+            .node_positions
+            .values()
+            .flat_map(|tokens| tokens.iter())
+            .map(|(token, positions)| {
+                std::mem::size_of::<String>()
+                    + token.len()
+                    + std::mem::size_of::<Vec<u32>>()
+                    + positions.len() * std::mem::size_of::<u32>()
+            })
+            .sum::<usize>();
 
-        nodes_size + cache_size + text_index_size + self.vector_index.estimated_memory_bytes()
+        nodes_size
+            + cache_size
+            + text_index_size
+            + positions_size
+            + self.vector_index.estimated_memory_bytes()
     }
 
     /// Estimate byte size of a slice of search results for cache accounting.
@@ -2893,9 +3971,29 @@ pub struct SemanticEntry {
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     /// Query execution failed
+    ///
+    /// No longer returned by `SearchEngine`, which reports the typed variants
+    /// below; kept so existing matches on it still compile.
     #[error("Query failed: {0}")]
     QueryFailed(String),
 
+    /// Query has no text and asks for no semantic ranking
+    #[error("Query cannot be empty")]
+    EmptyQuery,
+
+    /// Per-query `ef_search` is zero or smaller than `top_k`
+    #[error("Query failed: ef_search ({ef_search}) must be > 0 and >= top_k ({top_k})")]
+    InvalidEfSearch {
+        /// Requested HNSW search width
+        ef_search: usize,
+        /// Requested result count
+        top_k: usize,
+    },
+
+    /// A search running on the blocking pool panicked or was cancelled
+    #[error("Query failed: search task failed: {0}")]
+    TaskFailed(String),
+
     /// Index is empty
     #[error("Index is empty")]
     EmptyIndex,
@@ -2908,11 +4006,34 @@ pub enum Error {
         /// Actual dimension received
         got: usize,
     },
+
+    /// The vector index rejected an embedding while being migrated
+    #[error("Index migration failed: {migrated} of {total} vectors migrated")]
+    IndexMigrationFailed {
+        /// Vectors inserted before the failure
+        migrated: usize,
+        /// Vectors that needed migrating
+        total: usize,
+    },
+}
+
+impl Error {
+    /// Whether the caller's query, not the index, caused the error
+    ///
+    /// Servers report these as invalid parameters rather than failures.
+    #[must_use]
+    pub fn is_invalid_query(&self) -> bool {
+        matches!(
+            self,
+            Error::EmptyQuery | Error::InvalidEfSearch { .. } | Error::DimensionMismatch { .. }
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::tokenizer::SubwordTokenizer;
 
     fn create_test_nodes() -> Vec<NodeInfo> {
         vec![
@@ -2928,6 +4049,7 @@ mod tests {
                 complexity: 2,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "func2".to_string(),
@@ -2941,10 +4063,29 @@ mod tests {
                 complexity: 2,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
         ]
     }
 
+    /// A function node with default metadata, for tests that vary only a few fields
+    fn node(node_id: &str, file_path: &str, symbol_name: &str, content: &str) -> NodeInfo {
+        NodeInfo {
+            node_id: node_id.to_string(),
+            file_path: file_path.to_string(),
+            symbol_name: symbol_name.to_string(),
+            language: "rust".to_string(),
+            content: content.to_string(),
+            byte_range: (0, 0),
+            tfidf_embedding: vec![],
+            neural_embedding: None,
+            complexity: 1,
+            signature: None,
+            pre_tokenized: None,
+            node_type: Some(NodeType::Function),
+        }
+    }
+
     #[test]
     fn test_search_engine_creation() {
         let engine = SearchEngine::new();
@@ -2962,79 +4103,685 @@ mod tests {
     }
 
     #[test]
-    fn test_search_empty_index() {
-        let mut engine = SearchEngine::new();
-        let query = SearchQuery {
-            query: "test".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+    fn test_search_empty_index() {
+        let engine = SearchEngine::new();
+        let query = SearchQuery {
+            query: "test".to_string(),
+            ..Default::default()
+        };
+        let results = engine.search(query).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_semantic_search_empty_index() {
+        let engine = SearchEngine::new();
+        let results = engine.semantic_search(&[0.1, 0.2, 0.3], 10).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_with_results() {
+        let mut engine = SearchEngine::new();
+        let nodes = create_test_nodes();
+        engine.index_nodes(nodes);
+
+        let query = SearchQuery {
+            query: "func1".to_string(),
+            ..Default::default()
+        };
+        let results = engine.search(query).unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0].node_id, "func1");
+    }
+
+    #[test]
+    fn test_semantic_search() {
+        let mut engine = SearchEngine::with_dimension(3);
+        let nodes = create_test_nodes();
+        engine.index_nodes(nodes);
+
+        // Search with query vector similar to func1
+        let results = engine.semantic_search(&[1.0, 0.0, 0.0], 1).unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0].node_id, "func1");
+    }
+
+    #[test]
+    fn test_dimension_validation() {
+        let engine = SearchEngine::with_dimension(128);
+        assert_eq!(engine.vector_index().dimension(), 128);
+    }
+
+    #[test]
+    fn test_dimension_mismatch_error() {
+        let mut engine = SearchEngine::with_dimension(3);
+        let nodes = create_test_nodes();
+        engine.index_nodes(nodes);
+
+        // Try searching with wrong dimension
+        let err = engine.semantic_search(&[0.1, 0.2], 10).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::DimensionMismatch {
+                expected: 3,
+                got: 2
+            }
+        ));
+        assert!(err.is_invalid_query());
+        assert_eq!(err.to_string(), "Dimension mismatch: expected 3, got 2");
+    }
+
+    #[test]
+    fn test_empty_non_semantic_query_is_rejected() {
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(create_test_nodes());
+
+        let query = SearchQuery {
+            query: "   ".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            engine.search(query.clone()),
+            Err(Error::EmptyQuery)
+        ));
+        assert!(!Error::TaskFailed("cancelled".to_string()).is_invalid_query());
+        let legacy = Error::QueryFailed("backend unavailable".to_string());
+        assert!(!legacy.is_invalid_query());
+        assert_eq!(legacy.to_string(), "Query failed: backend unavailable");
+
+        // A semantic query may rank by embedding alone
+        let semantic = SearchQuery {
+            semantic: true,
+            ..query
+        };
+        assert!(engine.search(semantic).is_ok());
+    }
+
+    #[test]
+    fn test_migrate_vector_index_reports_progress() {
+        let mut engine = SearchEngine::with_dimension(3);
+        engine.index_nodes(create_test_nodes());
+        engine.enable_hnsw(None);
+        assert!(engine.vector_index.is_empty());
+
+        assert_eq!(engine.migrate_vector_index().unwrap(), 2);
+        assert_eq!(engine.vector_index.len(), 2);
+        assert_eq!(
+            engine.semantic_search(&[1.0, 0.0, 0.0], 1).unwrap().len(),
+            1
+        );
+
+        // An embedding the index rejects stops the migration
+        engine.nodes[1].tfidf_embedding = vec![0.0, 1.0];
+        let err = engine.migrate_vector_index().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::IndexMigrationFailed {
+                migrated: 1,
+                total: 2
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Index migration failed: 1 of 2 vectors migrated"
+        );
+    }
+
+    #[test]
+    fn test_mismatched_embedding_is_skipped() {
+        let mut engine = SearchEngine::with_dimension(3);
+        engine.enable_hnsw(None);
+        let mut nodes = create_test_nodes();
+        // As if loaded from an index built with a different embedding_dim
+        nodes[1].tfidf_embedding = vec![0.0, 1.0, 0.0, 0.0];
+        engine.index_nodes(nodes);
+
+        assert_eq!(engine.embedding_dim(), 3);
+        assert_eq!(engine.node_count(), 2);
+        assert_eq!(engine.vector_index().len(), 1);
+        let persisted: Vec<String> = engine
+            .collect_embeddings()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(persisted, vec!["func1".to_string()]);
+
+        // The skipped node is still reachable through text search
+        let results = engine
+            .search(SearchQuery {
+                query: "world".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(results.iter().any(|r| r.node_id == "func2"));
+    }
+
+    #[test]
+    fn test_rrf_balances_keyword_and_semantic_rankings() {
+        fn result(node_id: &str, text_match: f32, tfidf: f32) -> SearchResult {
+            SearchResult {
+                rank: 0,
+                node_id: node_id.to_string(),
+                file_path: "lib.rs".to_string(),
+                symbol_name: node_id.to_string(),
+                symbol_type: None,
+                signature: None,
+                complexity: 1,
+                caller_count: None,
+                dependency_count: None,
+                language: "rust".to_string(),
+                // Keyword-heavy linear blend
+                score: Score {
+                    overall: 0.85 * text_match + 0.15 * tfidf,
+                    tfidf,
+                    neural: 0.0,
+                    structural: 0.0,
+                    text_match,
+                },
+                context: None,
+                byte_range: (0, 0),
+                line_number: None,
+            }
+        }
+        fn order(results: &[SearchResult]) -> Vec<&str> {
+            let mut sorted: Vec<&SearchResult> = results.iter().collect();
+            sorted.sort_by(|a, b| b.score.overall.partial_cmp(&a.score.overall).unwrap());
+            sorted.iter().map(|r| r.node_id.as_str()).collect()
+        }
+
+        // `keyword_only` wins the blend on text match alone; `balanced` is
+        // a close second on keywords and the best semantic hit.
+        let mut results = vec![
+            result("keyword_only", 1.0, 0.0),
+            result("mostly_keyword", 0.9, 0.1),
+            result("balanced", 0.5, 1.0),
+        ];
+        assert_eq!(
+            order(&results),
+            vec!["keyword_only", "mostly_keyword", "balanced"]
+        );
+
+        SearchEngine::apply_rrf(&mut results);
+        assert_eq!(
+            order(&results),
+            vec!["balanced", "mostly_keyword", "keyword_only"]
+        );
+        assert!(results.iter().all(|r| r.score.overall <= 1.0));
+    }
+
+    #[test]
+    fn test_identifier_expansion_matches_across_naming_styles() {
+        fn function(name: &str) -> NodeInfo {
+            node(name, "users.rs", name, &format!("fn {}() {{}}", name))
+        }
+        fn ids(results: Vec<SearchResult>) -> Vec<String> {
+            results.into_iter().map(|r| r.node_id).collect()
+        }
+
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![
+            function("get_user"),
+            function("getAccount"),
+            function("load_user"),
+        ]);
+        let parser = crate::search::query::QueryParser::new().unwrap();
+
+        let mut query = parser.build_search_query(&parser.parse("getUser", 10).unwrap());
+        query.semantic = false;
+        assert_eq!(ids(engine.search(query).unwrap())[0], "get_user");
+
+        // Without expansions the camelCase query has no matching token
+        let mut query = parser.build_search_query(&parser.parse("getUser", 10).unwrap());
+        query.semantic = false;
+        query.expansions.clear();
+        assert!(engine.search(query).unwrap().is_empty());
+
+        // snake_case query finds the camelCase symbol through the joined form
+        let mut query = parser.build_search_query(&parser.parse("get_account", 10).unwrap());
+        query.semantic = false;
+        assert!(ids(engine.search(query).unwrap()).contains(&"getAccount".to_string()));
+    }
+
+    #[test]
+    fn test_text_index_drops_stopwords_and_common_tokens() {
+        let nodes: Vec<NodeInfo> = (0..4)
+            .map(|i| {
+                let name = format!("node{}", i);
+                let content = if i == 0 {
+                    "let value = self.handle(zebra)".to_string()
+                } else {
+                    format!("let value = self.handle(item{})", i)
+                };
+                node(&name, "lib.rs", &name, &content)
+            })
+            .collect();
+
+        let mut engine = SearchEngine::new();
+        engine.set_text_index_config(TextIndexConfig {
+            stopwords: ["let".to_string()].into_iter().collect(),
+            max_document_frequency: Some(0.5),
+            min_nodes_for_cutoff: 1,
+        });
+        engine.index_nodes(nodes);
+
+        assert!(!engine.text_index.contains_key("let"));
+        assert!(!engine.text_index.contains_key("self"));
+        assert!(!engine.text_index.contains_key("handle"));
+        assert_eq!(engine.token_lookup("zebra").map(|ids| ids.len()), Some(1));
+        // Common tokens still count toward scoring
+        assert!(engine.node_tokens("node0").unwrap().contains("self"));
+        engine.validate_coherence().unwrap();
+
+        // Relaxing the config rebuilds the index from the token cache
+        engine.set_text_index_config(TextIndexConfig::default());
+        assert_eq!(engine.token_lookup("self").map(|ids| ids.len()), Some(4));
+    }
+
+    #[test]
+    fn test_quoted_phrase_requires_adjacent_tokens() {
+        let function = |name, content| node(name, "io.rs", name, content);
+
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![
+            function("adjacent", "fn load() { open file now }"),
+            function("apart", "fn load() { open socket then read file }"),
+        ]);
+        let parser = crate::search::query::QueryParser::new().unwrap();
+
+        let mut query = parser.build_search_query(&parser.parse("\"open file\"", 10).unwrap());
+        query.semantic = false;
+        let ids: Vec<String> = engine
+            .search(query)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.node_id)
+            .collect();
+        assert_eq!(ids, vec!["adjacent"]);
+
+        // Unquoted terms match both nodes as before
+        let mut query = parser.build_search_query(&parser.parse("open file", 10).unwrap());
+        query.semantic = false;
+        assert_eq!(engine.search(query).unwrap().len(), 2);
+
+        // Token positions count toward the memory estimate
+        let with_positions = engine.estimated_memory_bytes();
+        engine.node_positions.clear();
+        assert!(engine.estimated_memory_bytes() < with_positions);
+    }
+
+    #[test]
+    fn test_ef_search_must_cover_top_k() {
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(create_test_nodes());
+
+        let query = |ef_search| SearchQuery {
+            query: "hello".to_string(),
+            ef_search,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            engine.search(query(Some(5))),
+            Err(Error::InvalidEfSearch {
+                ef_search: 5,
+                top_k: 10
+            })
+        ));
+        let results = engine.search(query(Some(64))).unwrap();
+        assert_eq!(results[0].node_id, "func1");
+    }
+
+    #[test]
+    fn test_dedup_symbols_keeps_best_scoring_duplicate() {
+        let overload = |node_id: &str, complexity| NodeInfo {
+            complexity,
+            ..node(node_id, "config.rs", "parse_config", "fn parse_config() {}")
+        };
+        let query = |dedup_symbols| SearchQuery {
+            // Not the exact symbol name, so the name boost doesn't saturate both at 1.0
+            query: "parse config".to_string(),
+            dedup_symbols,
+            ..Default::default()
+        };
+
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![
+            overload("overload_simple", 1),
+            overload("overload_complex", 40),
+        ]);
+
+        assert_eq!(engine.search(query(false)).unwrap().len(), 2);
+
+        let results = engine.search(query(true)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].node_id, "overload_complex");
+        assert_eq!(results[0].rank, 1);
+    }
+
+    #[test]
+    fn test_node_type_facet_filters_results() {
+        let typed = |node_id: &str, node_type| NodeInfo {
+            node_type,
+            ..node(
+                node_id,
+                "auth.py",
+                node_id,
+                &format!("def {}(): authenticate()", node_id),
+            )
+        };
+        let query = |node_types| SearchQuery {
+            query: "authenticate".to_string(),
+            node_types,
+            ..Default::default()
+        };
+
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![
+            typed("auth_module", Some(NodeType::Module)),
+            typed("auth_function", Some(NodeType::Function)),
+            typed("auth_method", Some(NodeType::Method)),
+            typed("auth_untyped", None),
+        ]);
+
+        assert_eq!(engine.search(query(None)).unwrap().len(), 4);
+
+        let methods_only = HashSet::from([NodeType::Method]);
+        let results = engine.search(query(Some(methods_only))).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].node_id, "auth_method");
+
+        let results = engine
+            .search(query(Some(HashSet::from([NodeType::Class]))))
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_vocabulary_only_offers_tokens_of_similar_length() {
+        let tokens: Vec<String> = [
+            "auth",
+            "authenticate",
+            "athenticate",
+            "authentication_token",
+        ]
+        .iter()
+        .map(|t| t.to_string())
+        .collect();
+        let vocabulary = FuzzyVocabulary::new(tokens.iter());
+        let candidates: HashSet<&String> = vocabulary.candidates("athenticate").collect();
+        assert_eq!(
+            candidates,
+            HashSet::from([&tokens[1], &tokens[2]]),
+            "short and much longer tokens are never compared"
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_mode_finds_symbol_despite_typo() {
+        let function = |symbol_name, content| node(symbol_name, "auth.py", symbol_name, content);
+        let query = |text: &str, fuzzy| SearchQuery {
+            query: text.to_string(),
+            fuzzy,
+            ..Default::default()
+        };
+
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![
+            function("authenticate", "def authenticate(user): return check(user)"),
+            function(
+                "authorize",
+                "def authorize(user, role): return role in user.roles",
+            ),
+            function("render_page", "def render_page(template): return template"),
+        ]);
+
+        // Off by default: the typo matches nothing
+        assert!(engine
+            .search(query("athenticate", false))
+            .unwrap()
+            .is_empty());
+
+        for typo in ["athenticate", "authenticste"] {
+            let results = engine.search(query(typo, true)).unwrap();
+            assert_eq!(results[0].symbol_name, "authenticate", "{}", typo);
+            assert!(results.iter().all(|r| r.symbol_name != "render_page"));
+        }
+
+        // An exact match still outranks a fuzzy one
+        let results = engine.search(query("authorize", true)).unwrap();
+        assert_eq!(results[0].symbol_name, "authorize");
+    }
+
+    #[test]
+    fn test_recency_boost_ranks_recently_edited_file_first() {
+        let check_input = |file_path: &str| {
+            node(
+                &format!("{}:check_input", file_path),
+                file_path,
+                "check_input",
+                "fn check_input() { validate(token) }",
+            )
+        };
+        let query = |fusion: ScoreFusion| SearchQuery {
+            query: "validate".to_string(),
+            fusion,
+            ..Default::default()
         };
-        let results = engine.search(query).unwrap();
-        assert!(results.is_empty());
-    }
 
-    #[test]
-    fn test_semantic_search_empty_index() {
-        let engine = SearchEngine::new();
-        let results = engine.semantic_search(&[0.1, 0.2, 0.3], 10).unwrap();
-        assert!(results.is_empty());
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![check_input("src/a.rs"), check_input("src/b.rs")]);
+        let baseline = engine.search(query(ScoreFusion::Linear)).unwrap();
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline[0].score.overall, baseline[1].score.overall);
+
+        // Edit the file that loses the tie, so only the boost can lift it
+        let (older, recent) = (baseline[0].file_path.clone(), baseline[1].file_path.clone());
+        let now = SystemTime::now();
+        let mut boost = RecencyBoost::new(HashMap::from([
+            (recent.clone(), now - Duration::from_secs(60)),
+            (older.clone(), now - Duration::from_secs(30 * 24 * 60 * 60)),
+        ]));
+        boost.now = now;
+        engine.set_recency_boost(Some(boost));
+
+        // The boost survives rank fusion and is not clamped at 1.0
+        for fusion in [ScoreFusion::Linear, ScoreFusion::Rrf] {
+            let boosted = engine.search(query(fusion)).unwrap();
+            assert_eq!(boosted[0].file_path, recent);
+            assert!(boosted[0].score.overall > boosted[1].score.overall);
+        }
+
+        engine.set_recency_boost(None);
+        let reset = engine.search(query(ScoreFusion::Linear)).unwrap();
+        assert_eq!(reset[0].score.overall, reset[1].score.overall);
     }
 
     #[test]
-    fn test_search_with_results() {
+    fn test_single_char_query_matches_symbol_prefixes() {
+        let function = |symbol_name: &str| {
+            node(
+                &format!("src/lib.rs:{}", symbol_name),
+                "src/lib.rs",
+                symbol_name,
+                &format!("fn {}(input: &str) -> usize {{ input.len() }}", symbol_name),
+            )
+        };
+        let query = |query: &str| SearchQuery {
+            query: query.to_string(),
+            ..Default::default()
+        };
+
         let mut engine = SearchEngine::new();
-        let nodes = create_test_nodes();
-        engine.index_nodes(nodes);
+        engine.index_nodes(vec![
+            function("parse_query"),
+            function("seq_len"),
+            function("query_parser"),
+        ]);
+        assert_eq!(
+            engine.min_query_length(),
+            DEFAULT_MIN_MEANINGFUL_QUERY_LENGTH
+        );
 
-        let query = SearchQuery {
-            query: "func1".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+        let names = |results: Vec<SearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.symbol_name).collect()
         };
-        let results = engine.search(query).unwrap();
-        assert!(!results.is_empty());
-        assert_eq!(results[0].node_id, "func1");
+        // `seq_len` contains a `q` but no name part starts with one
+        let results = names(engine.search(query("Q")).unwrap());
+        assert_eq!(results, ["query_parser", "parse_query"]);
+
+        // Raising the minimum turns a two-character query into a prefix lookup
+        engine.set_min_query_length(3);
+        let results = names(engine.search(query("pa")).unwrap());
+        assert_eq!(results, ["parse_query", "query_parser"]);
     }
 
     #[test]
-    fn test_semantic_search() {
-        let mut engine = SearchEngine::with_dimension(3);
-        let nodes = create_test_nodes();
-        engine.index_nodes(nodes);
+    fn test_expand_context_returns_node_source_inline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("billing.rs");
+        let source = "use std::fmt;\n\n// Totals an invoice\nfn invoice_total(lines: &[u64]) -> u64 {\n    lines.iter().sum()\n}\n\nfn unrelated() {}\n";
+        std::fs::write(&path, source).unwrap();
+        let start = source.find("fn invoice_total").unwrap();
+        let end = source.find("}\n").unwrap() + 1;
 
-        // Search with query vector similar to func1
-        let results = engine.semantic_search(&[1.0, 0.0, 0.0], 1).unwrap();
-        assert!(!results.is_empty());
-        assert_eq!(results[0].node_id, "func1");
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(vec![NodeInfo {
+            byte_range: (start, end),
+            ..node(
+                "invoice_total",
+                &path.to_string_lossy(),
+                "invoice_total",
+                &source[start..end],
+            )
+        }]);
+        engine.set_context_lines(1);
+        let query = |expand_context, token_budget| SearchQuery {
+            query: "invoice_total".to_string(),
+            token_budget,
+            expand_context,
+            ..Default::default()
+        };
+
+        assert_eq!(engine.search(query(false, None)).unwrap()[0].context, None);
+
+        let results = engine.search(query(true, None)).unwrap();
+        let context = results[0].context.as_deref().unwrap();
+        assert!(context.contains(&source[start..end]), "{}", context);
+        assert!(context.starts_with("// Totals an invoice"), "{}", context);
+        assert!(!context.contains("use std::fmt") && !context.contains("unrelated"));
+
+        // The token budget caps the inline context
+        let results = engine.search(query(true, Some(4))).unwrap();
+        let context = results[0].context.as_deref().unwrap();
+        assert!(SubwordTokenizer.count_tokens(context) <= 4);
+        assert!(context.starts_with("//"));
+
+        // ...as counted by the engine's tokenizer
+        engine.set_tokenizer(TokenizerKind::CharEstimate.build());
+        let results = engine.search(query(true, Some(4))).unwrap();
+        let char_context = results[0].context.as_deref().unwrap();
+        assert!(char_context.len() < 20, "{}", char_context);
+        assert!(char_context.len() > context.len(), "{}", char_context);
     }
 
     #[test]
-    fn test_dimension_validation() {
-        let engine = SearchEngine::with_dimension(128);
-        assert_eq!(engine.vector_index().dimension(), 128);
+    fn test_bloom_prefilter_returns_identical_results() {
+        const WORDS: [&str; 12] = [
+            "parse", "config", "token", "render", "cache", "index", "query", "graph", "store",
+            "merge", "split", "flush",
+        ];
+        fn corpus(range: std::ops::Range<usize>) -> Vec<NodeInfo> {
+            range
+                .map(|i| {
+                    let symbol_name = format!("{}_{}", WORDS[i % WORDS.len()], i);
+                    let content = format!(
+                        "fn {}() {{ {}(); {}(); }}",
+                        symbol_name,
+                        WORDS[(i * 7 + 3) % WORDS.len()],
+                        WORDS[(i * 5 + 1) % WORDS.len()]
+                    );
+                    NodeInfo {
+                        complexity: (i % 9) as u32 + 1,
+                        ..node(
+                            &format!("node_{}", i),
+                            &format!("src/file_{}.rs", i % 37),
+                            &symbol_name,
+                            &content,
+                        )
+                    }
+                })
+                .collect()
+        }
+        let query = |text: &str| SearchQuery {
+            query: text.to_string(),
+            top_k: 1000,
+            ..Default::default()
+        };
+        let ranked = |engine: &SearchEngine, text: &str| -> Vec<(String, f32)> {
+            let mut results: Vec<(String, f32)> = engine
+                .search(query(text))
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.node_id, r.score.overall))
+                .collect();
+            results.sort_by(|a, b| a.0.cmp(&b.0));
+            results
+        };
+
+        let mut plain = SearchEngine::new();
+        let mut bloom = SearchEngine::new();
+        // A high false-positive rate exercises the exact check behind the filters
+        bloom.enable_bloom_prefilter(0.3);
+        for engine in [&mut plain, &mut bloom] {
+            engine.index_nodes(corpus(0..400));
+            // Growing posting sets past their filter's capacity rebuilds it
+            engine.append_nodes(corpus(400..900));
+        }
+        assert!(bloom.bloom_prefilter_enabled());
+
+        for text in [
+            "parse",
+            "render cache",
+            "graph store merge",
+            "flush_7",
+            "missing",
+        ] {
+            let expected = ranked(&plain, text);
+            assert_eq!(ranked(&bloom, text), expected, "{}", text);
+        }
+        assert!(!ranked(&plain, "render cache").is_empty());
     }
 
-    #[test]
-    fn test_dimension_mismatch_error() {
-        let mut engine = SearchEngine::with_dimension(3);
-        let nodes = create_test_nodes();
-        engine.index_nodes(nodes);
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_searches_on_shared_engine() {
+        let mut engine = SearchEngine::new();
+        engine.index_nodes(create_test_nodes());
+        let engine = Arc::new(engine);
 
-        // Try searching with wrong dimension
-        let result = engine.semantic_search(&[0.1, 0.2], 10);
-        assert!(result.is_err());
+        let query = |text: &str| SearchQuery {
+            query: text.to_string(),
+            ..Default::default()
+        };
+
+        let tasks: Vec<_> = (0..16)
+            .map(|i| {
+                let (text, expected) = if i % 2 == 0 {
+                    ("hello", "func1")
+                } else {
+                    ("world", "func2")
+                };
+                let search = Arc::clone(&engine).search_async(query(text));
+                tokio::spawn(async move { (search.await.unwrap(), expected) })
+            })
+            .collect();
+
+        for task in tasks {
+            let (results, expected) = task.await.unwrap();
+            assert_eq!(results[0].node_id, expected);
+        }
+        // Both distinct queries were cached despite concurrent access
+        assert_eq!(engine.search_cache_len(), 2);
     }
 
     #[test]
@@ -3053,13 +4800,7 @@ mod tests {
         let query = SearchQuery {
             query: "fn".to_string(),
             top_k: 1,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert_eq!(results.len(), 1);
@@ -3073,14 +4814,8 @@ mod tests {
 
         let query = SearchQuery {
             query: "nonexistent".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
             threshold: Some(0.5),
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(results.is_empty());
@@ -3138,6 +4873,7 @@ mod tests {
             complexity: 1,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
         assert_eq!(engine.node_id_to_idx.len(), 1);
         assert_eq!(engine.node_id_to_idx.get("new_func"), Some(&0));
@@ -3166,14 +4902,7 @@ mod tests {
         // But text search should still work via inverted index
         let query = SearchQuery {
             query: "func1".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(
@@ -3245,6 +4974,7 @@ mod tests {
             complexity: 1,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
         assert_eq!(engine.node_tokens.len(), 1);
         assert!(engine.node_tokens.contains_key("new_func"));
@@ -3267,14 +4997,7 @@ mod tests {
         // Search for a term that appears in content — should still find it via cached tokens
         let query = SearchQuery {
             query: "println hello".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
 
@@ -3312,6 +5035,7 @@ mod tests {
                 complexity: 3,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         };
         engine.incremental_reindex(delta);
@@ -3325,14 +5049,7 @@ mod tests {
         // Search should find the new node
         let query = SearchQuery {
             query: "func3".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty());
@@ -3379,14 +5096,7 @@ mod tests {
         // Search for func1 should not find it
         let query = SearchQuery {
             query: "func1".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(
@@ -3416,6 +5126,7 @@ mod tests {
                 complexity: 5,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         };
         engine.incremental_reindex(delta);
@@ -3433,14 +5144,7 @@ mod tests {
         // Search for new content should work
         let query = SearchQuery {
             query: "new_logic".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty());
@@ -3468,6 +5172,7 @@ mod tests {
                     complexity: 1,
                     signature: None,
                     pre_tokenized: None,
+                    node_type: None,
                 },
                 NodeInfo {
                     node_id: "func4".to_string(),
@@ -3481,6 +5186,7 @@ mod tests {
                     complexity: 2,
                     signature: None,
                     pre_tokenized: None,
+                    node_type: None,
                 },
             ],
         };
@@ -3500,14 +5206,7 @@ mod tests {
         // Search for func2 should still work
         let query = SearchQuery {
             query: "func2".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty());
@@ -3547,6 +5246,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "unique2".to_string(),
@@ -3560,6 +5260,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
         ]);
 
@@ -3609,6 +5310,7 @@ mod tests {
                 complexity: 4,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         };
         engine_inc.incremental_reindex(delta);
@@ -3627,6 +5329,7 @@ mod tests {
                 complexity: 2,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "func3".to_string(),
@@ -3640,6 +5343,7 @@ mod tests {
                 complexity: 4,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
         ]);
 
@@ -3659,14 +5363,7 @@ mod tests {
         // Search should produce same results
         let query = SearchQuery {
             query: "func2".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let inc_results = engine_inc.search(query.clone()).unwrap();
         let full_results = engine_full.search(query).unwrap();
@@ -3705,6 +5402,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         };
         engine.incremental_reindex(delta);
@@ -3736,6 +5434,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         });
 
@@ -3793,6 +5492,7 @@ mod tests {
                 complexity: 3,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             }],
         });
 
@@ -3845,6 +5545,7 @@ mod tests {
             complexity: 3,
             signature: None,
             pre_tokenized: Some(search_tokens),
+            node_type: None,
         }]);
 
         // Engine with re-tokenization (pre_tokenized = None)
@@ -3861,6 +5562,7 @@ mod tests {
             complexity: 3,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
 
         // Both inverted indexes should be identical
@@ -3876,14 +5578,7 @@ mod tests {
         // Search for "calculate" should find the node in both
         let query = SearchQuery {
             query: "calculate".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results_pre = engine_pre.search(query.clone()).unwrap();
         let results_fallback = engine_fallback.search(query).unwrap();
@@ -3909,6 +5604,7 @@ mod tests {
             complexity: 1,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
 
         // Should still find via content-based tokenization
@@ -3918,14 +5614,7 @@ mod tests {
 
         let query = SearchQuery {
             query: "legacy".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty());
@@ -3963,6 +5652,7 @@ mod tests {
             complexity: 5,
             signature: None,
             pre_tokenized: Some(tokens),
+            node_type: None,
         }]);
 
         // Engine B: content-based
@@ -3979,6 +5669,7 @@ mod tests {
             complexity: 5,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
 
         // Both should have identical text_index entries
@@ -4021,6 +5712,7 @@ mod tests {
                 complexity: 4,
                 signature: None,
                 pre_tokenized: Some(tokens),
+                node_type: None,
             }],
         };
         engine.incremental_reindex(delta);
@@ -4036,14 +5728,7 @@ mod tests {
         // Search should find the new node
         let query = SearchQuery {
             query: "compute metrics".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty());
@@ -4069,6 +5754,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             })
             .collect();
         engine.index_nodes(nodes);
@@ -4077,31 +5763,24 @@ mod tests {
         for i in 0..300 {
             let query = SearchQuery {
                 query: format!("query_{}", i),
-                top_k: 10,
-                token_budget: None,
-                semantic: false,
-                expand_context: false,
-                query_embedding: None,
-                query_neural_embedding: None,
-                threshold: None,
-                query_type: None,
+                ..Default::default()
             };
             let _ = engine.search(query);
         }
 
         // Cache should not exceed entry limit
         assert!(
-            engine.search_cache.len() <= SEARCH_CACHE_MAX_ENTRIES,
+            engine.search_cache_len() <= SEARCH_CACHE_MAX_ENTRIES,
             "search cache entries ({}) should not exceed max ({})",
-            engine.search_cache.len(),
+            engine.search_cache_len(),
             SEARCH_CACHE_MAX_ENTRIES
         );
 
         // Cache bytes should not exceed byte limit
         assert!(
-            engine.search_cache_bytes <= SEARCH_CACHE_MAX_BYTES,
+            engine.search_cache_bytes() <= SEARCH_CACHE_MAX_BYTES,
             "search cache bytes ({}) should not exceed max ({})",
-            engine.search_cache_bytes,
+            engine.search_cache_bytes(),
             SEARCH_CACHE_MAX_BYTES
         );
 
@@ -4109,13 +5788,7 @@ mod tests {
         let query = SearchQuery {
             query: "symbol_0".to_string(),
             top_k: 5,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let results = engine.search(query).unwrap();
         assert!(!results.is_empty(), "search should still return results");
@@ -4499,6 +6172,7 @@ mod tests {
             complexity: 3,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
 
         engine_b.index_nodes(vec![NodeInfo {
@@ -4513,6 +6187,7 @@ mod tests {
             complexity: 3,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }]);
 
         // Both engines should produce identical semantic search results
@@ -4588,6 +6263,7 @@ mod tests {
             complexity: 1,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         }
     }
 
@@ -4784,6 +6460,7 @@ mod tests {
             complexity: 3,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         };
 
         let mut engine = SearchEngine::with_dimension(3);
@@ -4792,14 +6469,9 @@ mod tests {
         // Search with a vector close to the indexed embedding
         let query = SearchQuery {
             query: "dedup_fn".into(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(vec![0.9, 0.1, 0.0]),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let results = engine.search(query).unwrap();
@@ -4825,6 +6497,7 @@ mod tests {
             complexity: 4,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         };
 
         // Serialize and deserialize the node to verify the round-trip
@@ -4837,14 +6510,9 @@ mod tests {
 
         let query2 = SearchQuery {
             query: "dedup_fn_v2".into(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(vec![0.0, 0.9, 0.1]),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let results2 = engine2.search(query2).unwrap();
@@ -4877,6 +6545,7 @@ mod tests {
                 complexity: 2,
                 signature: Some("fn clear_fn_1()".into()),
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "clear_node_2".into(),
@@ -4890,6 +6559,7 @@ mod tests {
                 complexity: 3,
                 signature: Some("fn clear_fn_2()".into()),
                 pre_tokenized: None,
+                node_type: None,
             },
         ];
 
@@ -4909,14 +6579,7 @@ mod tests {
         // Verify search still works after content clearing (uses inverted index)
         let query = SearchQuery {
             query: "clear_fn".into(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let results = engine.search(query).unwrap();
@@ -4969,6 +6632,7 @@ mod tests {
             complexity: 5,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         };
 
         let archive_node = NodeInfo {
@@ -4983,6 +6647,7 @@ mod tests {
             complexity: 5,
             signature: None,
             pre_tokenized: None,
+            node_type: None,
         };
 
         let mut engine = SearchEngine::new();
@@ -4990,14 +6655,9 @@ mod tests {
 
         let query = SearchQuery {
             query: "index_project".to_string(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(vec![1.0, 0.0, 0.0]),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let results = engine.search(query).unwrap();
@@ -5061,6 +6721,7 @@ mod tests {
                 complexity: 5,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "conceptual_match".to_string(),
@@ -5075,6 +6736,7 @@ mod tests {
                 complexity: 10,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
         ];
 
@@ -5084,28 +6746,20 @@ mod tests {
         // Search with exact mode
         let exact_query = SearchQuery {
             query: "exact_match".to_string(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(vec![0.5, 0.3, 0.0]),
-            query_neural_embedding: None,
-            threshold: None,
             query_type: Some(crate::search::ranking::QueryType::Exact),
+            ..Default::default()
         };
         let exact_results = engine.search(exact_query).unwrap();
 
         // Search with semantic mode
         let semantic_query = SearchQuery {
             query: "exact_match".to_string(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(vec![0.5, 0.3, 0.0]),
-            query_neural_embedding: None,
-            threshold: None,
             query_type: Some(crate::search::ranking::QueryType::Semantic),
+            ..Default::default()
         };
         let semantic_results = engine.search(semantic_query).unwrap();
 
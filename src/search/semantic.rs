@@ -3,15 +3,27 @@
 use crate::graph::pdg::ProgramDependenceGraph;
 use crate::graph::traversal::{GravityTraversal, TraversalConfig};
 use crate::search::search::{NodeInfo, SemanticEntry};
+use crate::tokenizer::{Tokenizer, TokenizerKind};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Semantic processor for vector-AST synergy
-pub struct SemanticProcessor {}
+pub struct SemanticProcessor {
+    tokenizer: Arc<dyn Tokenizer>,
+}
 
 impl SemanticProcessor {
     /// Create a new semantic processor
     pub fn new() -> Self {
-        Self {}
+        Self {
+            tokenizer: TokenizerKind::default().build(),
+        }
+    }
+
+    /// Charge expanded nodes against the token budget with `tokenizer`
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
     }
 
     /// Process semantic entry and expand context using PDG
@@ -27,7 +39,8 @@ impl SemanticProcessor {
             ..Default::default()
         };
 
-        let traversal = GravityTraversal::with_config(config);
+        let traversal =
+            GravityTraversal::with_config(config).with_tokenizer(Arc::clone(&self.tokenizer));
 
         // Map node_id string to graph NodeId
         let entry_node_id = pdg.find_by_symbol(&entry.node_id).ok_or_else(|| {
@@ -80,7 +93,6 @@ mod tests {
     use super::*;
     use crate::graph::pdg::{Node as GraphNode, NodeType};
     use crate::search::search::EntryType;
-    use std::sync::Arc;
 
     #[tokio::test]
     async fn test_semantic_context_expansion() {
@@ -122,6 +134,7 @@ mod tests {
                 neural_embedding: None,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
             NodeInfo {
                 node_id: "func2".to_string(),
@@ -135,6 +148,7 @@ mod tests {
                 neural_embedding: None,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             },
         ];
 
@@ -53,7 +53,8 @@ impl VectorIndex {
     ///
     /// # Returns
     ///
-    /// `Ok(())` if successful, `Err(Error)` if dimension mismatch
+    /// `Ok(())` if successful, `Err(Error)` if dimension mismatch or the
+    /// embedding contains NaN or infinite values
     ///
     /// # Example
     ///
@@ -68,6 +69,13 @@ impl VectorIndex {
             });
         }
 
+        if let Some(idx) = first_non_finite(&embedding) {
+            return Err(Error::InvalidEmbedding(format!(
+                "non-finite value {} at index {} for node {}",
+                embedding[idx], idx, node_id
+            )));
+        }
+
         self.embeddings.insert(node_id, embedding);
         self.count += 1;
         Ok(())
@@ -125,11 +133,12 @@ impl VectorIndex {
     /// }
     /// ```
     pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(String, f32)> {
-        if query.len() != self.dimension {
+        if query.len() != self.dimension || first_non_finite(query).is_some() {
             return Vec::new();
         }
 
-        // Calculate cosine similarity for all vectors
+        // Calculate cosine similarity for all vectors, skipping any that
+        // score NaN so they cannot disturb the ordering
         let mut results: Vec<(String, f32)> = self
             .embeddings
             .iter()
@@ -137,6 +146,7 @@ impl VectorIndex {
                 let similarity = cosine_similarity(query, embedding);
                 (node_id.clone(), similarity)
             })
+            .filter(|(_, similarity)| similarity.is_finite())
             .collect();
 
         // Sort by similarity (descending)
@@ -253,6 +263,11 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     dot_product / (norm_a * norm_b)
 }
 
+/// Index of the first NaN or infinite component, if any
+pub fn first_non_finite(embedding: &[f32]) -> Option<usize> {
+    embedding.iter().position(|value| !value.is_finite())
+}
+
 /// Vector search errors
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -637,7 +652,7 @@ impl MmapEmbeddingIndex {
         }
 
         let query_norm: f32 = query.iter().map(|v| v * v).sum::<f32>().sqrt();
-        if query_norm < 1e-9 {
+        if !query_norm.is_finite() || query_norm < 1e-9 {
             return Vec::new();
         }
 
@@ -652,7 +667,7 @@ impl MmapEmbeddingIndex {
                     return None;
                 }
                 let similarity = dot / (query_norm * emb_norm);
-                Some((id, similarity))
+                similarity.is_finite().then_some((id, similarity))
             })
             .collect();
 
@@ -967,6 +982,25 @@ mod tests {
         assert_eq!(results[0].0, "test");
     }
 
+    #[test]
+    fn test_non_finite_embeddings_rejected() {
+        let mut index = VectorIndex::new(3);
+        index.insert("a".to_string(), vec![1.0, 0.0, 0.0]).unwrap();
+        index.insert("b".to_string(), vec![0.5, 0.5, 0.0]).unwrap();
+
+        let result = index.insert("nan".to_string(), vec![1.0, f32::NAN, 0.0]);
+        assert!(matches!(result, Err(Error::InvalidEmbedding(_))));
+        let result = index.insert("inf".to_string(), vec![f32::INFINITY, 0.0, 0.0]);
+        assert!(matches!(result, Err(Error::InvalidEmbedding(_))));
+        assert_eq!(index.len(), 2);
+
+        // Ranking is unaffected and a non-finite query is answered with nothing
+        let results = index.search(&[1.0, 0.0, 0.0], 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "a");
+        assert!(index.search(&[f32::NAN, 0.0, 0.0], 10).is_empty());
+    }
+
     #[test]
     fn test_search_empty_index() {
         let index = VectorIndex::new(3);
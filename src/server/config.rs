@@ -9,7 +9,9 @@ pub const DEFAULT_HOST: &str = "127.0.0.1";
 /// Default port number
 pub const DEFAULT_PORT: u16 = 47269;
 
-/// Default CORS origins (localhost for development)
+/// Dashboard dev-server origins, for use in `cors_origins` during development
+///
+/// Not enabled by default: an empty `cors_origins` means same-origin only.
 pub const DEFAULT_CORS_ORIGINS: &[&str] = &[
     "http://localhost:5173",
     "http://localhost:5174",
@@ -17,6 +19,12 @@ pub const DEFAULT_CORS_ORIGINS: &[&str] = &[
     "http://127.0.0.1:5174",
 ];
 
+/// Default CORS methods allowed for cross-origin requests
+pub const DEFAULT_CORS_METHODS: &[&str] = &["GET", "POST", "OPTIONS"];
+
+/// Default CORS request headers allowed for cross-origin requests
+pub const DEFAULT_CORS_HEADERS: &[&str] = &["content-type", "authorization", "if-none-match"];
+
 /// Maximum number of WebSocket connections
 pub const MAX_WS_CONNECTIONS: usize = 100;
 
@@ -32,9 +40,22 @@ pub struct ServerConfig {
     /// Server port
     pub port: u16,
 
-    /// Allowed CORS origins
+    /// Allowed CORS origins (empty = same-origin only, `*` = any origin)
+    #[serde(default)]
     pub cors_origins: Vec<String>,
 
+    /// Allowed CORS methods
+    #[serde(default = "ServerConfig::default_cors_methods")]
+    pub cors_methods: Vec<String>,
+
+    /// Allowed CORS request headers
+    #[serde(default = "ServerConfig::default_cors_headers")]
+    pub cors_headers: Vec<String>,
+
+    /// Whether cross-origin requests may carry credentials
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+
     /// Path to SQLite database
     pub db_path: String,
 
@@ -49,6 +70,10 @@ pub struct ServerConfig {
 
     /// Log level for tracing
     pub log_level: String,
+
+    /// Bearer token required by `GET /metrics` (unauthenticated when unset)
+    #[serde(default)]
+    pub metrics_token: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -56,12 +81,16 @@ impl Default for ServerConfig {
         Self {
             host: Self::default_host(),
             port: Self::default_port(),
-            cors_origins: Self::default_cors_origins(),
+            cors_origins: Vec::new(),
+            cors_methods: Self::default_cors_methods(),
+            cors_headers: Self::default_cors_headers(),
+            cors_allow_credentials: false,
             db_path: Self::default_db_path(),
             max_ws_connections: Self::default_max_ws(),
             ws_heartbeat_interval_secs: Self::default_heartbeat(),
             enable_logging: Self::default_logging(),
             log_level: Self::default_log_level(),
+            metrics_token: None,
         }
     }
 }
@@ -77,9 +106,14 @@ impl ServerConfig {
         DEFAULT_PORT
     }
 
-    /// Default CORS origins
-    fn default_cors_origins() -> Vec<String> {
-        DEFAULT_CORS_ORIGINS.iter().map(|s| s.to_string()).collect()
+    /// Default CORS methods
+    fn default_cors_methods() -> Vec<String> {
+        DEFAULT_CORS_METHODS.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Default CORS headers
+    fn default_cors_headers() -> Vec<String> {
+        DEFAULT_CORS_HEADERS.iter().map(|s| s.to_string()).collect()
     }
 
     /// Default database path
@@ -114,6 +148,8 @@ impl ServerConfig {
     /// - `LESERVE_PORT` - Server port
     /// - `LESERVE_DB_PATH` - Database path
     /// - `LESERVE_LOG_LEVEL` - Log level (trace, debug, info, warn, error)
+    /// - `LESERVE_METRICS_TOKEN` - Bearer token required by `/metrics`
+    /// - `LESERVE_CORS_ORIGINS` - Comma-separated allowed CORS origins
     ///
     /// # Returns
     ///
@@ -140,6 +176,21 @@ impl ServerConfig {
             config.log_level = log_level;
         }
 
+        if let Ok(origins) = std::env::var("LESERVE_CORS_ORIGINS") {
+            config.cors_origins = origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        if let Ok(token) = std::env::var("LESERVE_METRICS_TOKEN") {
+            if !token.is_empty() {
+                config.metrics_token = Some(token);
+            }
+        }
+
         config
     }
 
@@ -202,6 +253,30 @@ impl ServerConfig {
             return Err("Heartbeat interval must be greater than zero".to_string());
         }
 
+        // Validate CORS settings
+        let wildcard_origin = self.cors_origins.iter().any(|o| o == "*");
+        if wildcard_origin && self.cors_allow_credentials {
+            return Err(
+                "CORS wildcard origin '*' cannot be combined with allow_credentials".to_string(),
+            );
+        }
+        if wildcard_origin && self.cors_origins.len() > 1 {
+            return Err("CORS wildcard origin '*' must be the only origin".to_string());
+        }
+        for origin in &self.cors_origins {
+            if origin != "*" && !(origin.starts_with("http://") || origin.starts_with("https://")) {
+                return Err(format!(
+                    "Invalid CORS origin: {}. Must start with http:// or https://",
+                    origin
+                ));
+            }
+        }
+        for method in &self.cors_methods {
+            if method.is_empty() || !method.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("Invalid CORS method: {}", method));
+            }
+        }
+
         // Validate log level
         match self.log_level.as_str() {
             "trace" | "debug" | "info" | "warn" | "error" => {}
@@ -226,7 +301,8 @@ mod tests {
         let config = ServerConfig::default();
         assert_eq!(config.host, DEFAULT_HOST);
         assert_eq!(config.port, DEFAULT_PORT);
-        assert!(!config.cors_origins.is_empty());
+        assert!(config.cors_origins.is_empty());
+        assert!(!config.cors_allow_credentials);
         assert_eq!(config.db_path, "leindex.db");
         assert_eq!(config.max_ws_connections, MAX_WS_CONNECTIONS);
         assert_eq!(
@@ -312,6 +388,22 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_config_validate_rejects_wildcard_with_credentials() {
+        let config = ServerConfig {
+            cors_origins: vec!["*".to_string()],
+            cors_allow_credentials: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            cors_origins: vec!["*".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_config_validate_invalid_log_level() {
         let config = ServerConfig {
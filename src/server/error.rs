@@ -54,6 +54,11 @@ impl ApiError {
         Self::new(StatusCode::BAD_REQUEST, message)
     }
 
+    /// 401 Unauthorized
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::with_code(StatusCode::UNAUTHORIZED, message, "UNAUTHORIZED")
+    }
+
     /// 404 Not Found
     pub fn not_found(resource: impl Into<String>) -> Self {
         Self::with_code(
@@ -2,18 +2,25 @@
 
 use crate::storage::Storage;
 use axum::{
-    extract::{ws::WebSocketUpgrade, Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{ws::WebSocketUpgrade, MatchedPath, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
     Json, Router,
 };
 use futures::stream::StreamExt;
 use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tracing::{error, info};
+use std::time::Instant;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::{error, info, warn, Instrument};
 
 use crate::server::config::ServerConfig;
 use crate::server::error::{ApiError, ApiResult};
+use crate::server::metrics::{
+    render_cache_metrics, render_index_duration_metrics, MetricsRegistry,
+};
 use crate::server::responses::{
     CacheOverviewResponse, CodebaseDetailResponse, CodebaseListResponse, CodebaseResponse,
     DashboardCodebaseMetricsResponse, DashboardOverviewResponse,
@@ -45,6 +52,8 @@ pub struct SearchQuery {
 /// Note: AppState fields are documented for their purposes:
 ///   - `storage`: Arc<Mutex<Storage>> provides thread-safe storage access requiring mutex lock
 ///   - `config`: Arc<ServerConfig> provides immutable server configuration
+///   - `metrics`: Arc<MetricsRegistry> collects request and search metrics
+///   - `ws`: WsManager shared by all WebSocket connections for broadcasts
 ///     Handlers must lock the mutex before accessing storage.
 #[derive(Clone)]
 pub struct AppState {
@@ -53,6 +62,12 @@ pub struct AppState {
 
     /// Immutable server configuration
     pub config: Arc<ServerConfig>,
+
+    /// Shared metrics registry exposed on `/metrics`
+    pub metrics: Arc<MetricsRegistry>,
+
+    /// WebSocket connection manager and event broadcaster
+    pub ws: WsManager,
 }
 
 impl AppState {
@@ -61,6 +76,8 @@ impl AppState {
         Self {
             storage: Arc::new(Mutex::new(storage)),
             config: Arc::new(config),
+            metrics: Arc::new(MetricsRegistry::new()),
+            ws: WsManager::new(),
         }
     }
 
@@ -69,6 +86,8 @@ impl AppState {
         Self {
             storage,
             config: Arc::new(config),
+            metrics: Arc::new(MetricsRegistry::new()),
+            ws: WsManager::new(),
         }
     }
 }
@@ -697,12 +716,14 @@ pub async fn search(
     Query(params): Query<SearchQuery>,
     State(state): State<AppState>,
 ) -> ApiResult<Json<SearchResultsResponse>> {
+    let started = Instant::now();
     let query = params.q.as_deref().unwrap_or_default();
     let limit = params.limit.unwrap_or(20);
 
     info!("Searching: q='{}', limit={}", query, limit);
 
     if query.trim().is_empty() {
+        state.metrics.record_search(started.elapsed());
         return Ok(Json(SearchResultsResponse::empty()));
     }
 
@@ -771,9 +792,140 @@ pub async fn search(
         })?;
 
     info!("Search returned {} results", results.len());
+    state.metrics.record_search(started.elapsed());
+    // Storage guard is still held, so publish synchronously instead of awaiting
+    let _ = state.ws.broadcaster.send(WsEvent::SearchCompleted {
+        query: query.to_string(),
+        result_count: results.len(),
+    });
     Ok(Json(SearchResultsResponse { results }))
 }
 
+/// GET /metrics - Prometheus text exposition
+///
+/// Unauthenticated unless `metrics_token` is configured, in which case the
+/// request must carry `Authorization: Bearer <token>`.
+pub async fn metrics_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    if let Some(expected) = state.config.metrics_token.as_deref() {
+        let provided = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        // blake3::Hash compares in constant time, and hashing first hides the
+        // token length as well
+        let authorized = provided.is_some_and(|provided| {
+            blake3::hash(provided.as_bytes()) == blake3::hash(expected.as_bytes())
+        });
+        if !authorized {
+            return Err(ApiError::unauthorized("Invalid or missing metrics token"));
+        }
+    }
+
+    let mut body = state.metrics.render();
+
+    let storage = state.storage.lock().map_err(|e| {
+        error!("Failed to acquire storage lock: {}", e);
+        ApiError::internal(format!("Storage lock error: {}", e))
+    })?;
+    // Storage-backed series are left out of the scrape when their query
+    // fails, rather than reported as zeros
+    match storage.conn().query_row(
+        "SELECT cache_hits, cache_misses FROM cache_telemetry WHERE id = 1",
+        [],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+    ) {
+        Ok((hits, misses)) => body.push_str(&render_cache_metrics(hits, misses)),
+        Err(e) => warn!("Skipping cache metrics, telemetry query failed: {}", e),
+    }
+    match crate::storage::index_runs::index_run_durations(&storage) {
+        Ok(durations) => body.push_str(&render_index_duration_metrics(&durations)),
+        Err(e) => warn!("Skipping index duration metrics, query failed: {}", e),
+    }
+    drop(storage);
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response())
+}
+
+/// Middleware recording request counts and latency per matched route
+pub async fn track_requests(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+    state.metrics.record_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// Header carrying the request ID on requests and responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied request ID that is echoed back as-is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Monotonic counter for generating HTTP request IDs
+static HTTP_REQUEST_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Middleware scoping each request in an `http_request` tracing span
+///
+/// A well-formed `x-request-id` from the caller is reused; otherwise a
+/// `"http-<pid>-<seq>"` ID is generated. The ID is recorded on the span, so
+/// every event logged while handling the request carries it, and is echoed
+/// in the `x-request-id` response header (including on error responses).
+pub async fn trace_request(req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let seq = HTTP_REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed);
+            format!("http-{}-{}", std::process::id(), seq)
+        });
+    let span = tracing::info_span!(
+        "http_request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path()
+    );
+
+    let started = Instant::now();
+    let mut response = next.run(req).instrument(span.clone()).await;
+    span.in_scope(|| {
+        tracing::debug!(
+            status = response.status().as_u16(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Request completed"
+        );
+    });
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    response
+}
+
 /// GET /api/health - Health check endpoint
 pub async fn health_check(State(_state): State<AppState>) -> ApiResult<Json<serde_json::Value>> {
     Ok(Json(serde_json::json!({
@@ -784,22 +936,61 @@ pub async fn health_check(State(_state): State<AppState>) -> ApiResult<Json<serd
     })))
 }
 
+/// GET /healthz - Liveness probe
+///
+/// Succeeds whenever the process is able to serve requests.
+pub async fn healthz() -> ApiResult<Json<serde_json::Value>> {
+    Ok(Json(serde_json::json!({ "status": "ok" })))
+}
+
+/// GET /readyz - Readiness probe
+///
+/// Returns 503 until storage answers queries and at least one project
+/// has been indexed, so orchestrators hold traffic until then.
+pub async fn readyz(State(state): State<AppState>) -> ApiResult<Json<serde_json::Value>> {
+    let storage = state.storage.lock().map_err(|e| {
+        error!("Failed to acquire storage lock: {}", e);
+        ApiError::unavailable(format!("Storage lock error: {}", e))
+    })?;
+
+    let projects: i64 = storage
+        .conn()
+        .query_row("SELECT COUNT(*) FROM project_metadata", [], |row| {
+            row.get(0)
+        })
+        .map_err(|e| {
+            error!("Readiness storage query failed: {}", e);
+            ApiError::unavailable(format!("Storage unavailable: {}", e))
+        })?;
+
+    if projects == 0 {
+        return Err(ApiError::unavailable("No indexed projects loaded"));
+    }
+
+    Ok(Json(serde_json::json!({
+        "status": "ready",
+        "projects": projects,
+    })))
+}
+
 /// GET /ws - WebSocket endpoint for real-time updates
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> impl IntoResponse {
     ws.on_upgrade(|mut socket| async move {
-        // Create WebSocket manager
-        let manager = WsManager::new();
+        // Shared WebSocket manager so broadcasts reach every connection
+        let manager = state.ws.clone();
 
-        // Generate simple connection ID
+        // Generate connection ID unique within this process
+        static NEXT_CONN: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
         let conn_id = format!(
-            "ws_{}",
+            "ws_{}_{}",
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
-                .as_millis()
+                .as_millis(),
+            NEXT_CONN.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
         );
 
         // Register connection
@@ -808,22 +999,10 @@ pub async fn websocket_handler(
         // Subscribe to broadcast channel
         let mut rx = manager.broadcaster.subscribe();
 
-        // Send heartbeat every 30 seconds
-        let mut heartbeat_interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
-        let heartbeat_manager = manager.clone();
-
-        tokio::spawn(async move {
-            loop {
-                heartbeat_interval.tick().await;
-                let event = WsEvent::Heartbeat {
-                    timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_millis() as u64,
-                };
-                heartbeat_manager.broadcast(event).await;
-            }
-        });
+        // Send heartbeat to this connection at the configured interval
+        let mut heartbeat_interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            state.config.ws_heartbeat_interval_secs,
+        ));
 
         // Handle messages
         loop {
@@ -864,16 +1043,35 @@ pub async fn websocket_handler(
                         None => break,
                     }
                 }
-                // Handle outgoing broadcast events
+                // Send periodic heartbeat
+                _ = heartbeat_interval.tick() => {
+                    let event = WsEvent::Heartbeat {
+                        timestamp: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_millis() as u64,
+                    };
+                    use axum::extract::ws::Message;
+                    if socket.send(Message::Text(event.to_json())).await.is_err() {
+                        break;
+                    }
+                }
+                // Handle outgoing broadcast events, filtered by subscription
                 result = rx.recv() => {
                     match result {
                         Ok(event) => {
+                            if !manager.should_deliver(&conn_id, &event).await {
+                                continue;
+                            }
                             let json = event.to_json();
                             use axum::extract::ws::Message;
                             if socket.send(Message::Text(json)).await.is_err() {
                                 break;
                             }
                         }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!("WebSocket {} lagged by {} events", conn_id, skipped);
+                        }
                         Err(_) => break,
                     }
                 }
@@ -889,6 +1087,8 @@ pub async fn websocket_handler(
 pub fn create_router() -> Router<AppState> {
     Router::new()
         .route("/api/health", axum::routing::get(health_check))
+        .route("/healthz", axum::routing::get(healthz))
+        .route("/readyz", axum::routing::get(readyz))
         .route(
             "/api/dashboard/overview",
             axum::routing::get(dashboard_overview),
@@ -906,6 +1106,132 @@ pub fn create_router() -> Router<AppState> {
         .route("/api/search", axum::routing::get(search))
         .route("/ws", axum::routing::get(websocket_handler))
         .route("/ws/events", axum::routing::get(websocket_handler))
+        .route("/metrics", axum::routing::get(metrics_handler))
+}
+
+/// Middleware adding ETags to successful `GET /api/*` responses
+///
+/// The tag is a BLAKE3 hash of the response body, so it changes whenever the
+/// underlying index data changes (e.g. after a re-index). A request whose
+/// `If-None-Match` matches the current tag receives `304 Not Modified`.
+pub async fn conditional_get(req: Request, next: Next) -> Response {
+    let is_read = req.method() == axum::http::Method::GET && req.uri().path().starts_with("/api/");
+    let if_none_match = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let response = next.run(req).await;
+    if !is_read || response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to buffer response body for ETag: {}", e);
+            return ApiError::internal("Failed to read response body").into_response();
+        }
+    };
+
+    let hash = blake3::hash(&bytes).to_hex();
+    let etag = format!("\"{}\"", &hash.as_str()[..32]);
+
+    if let Some(candidates) = if_none_match {
+        let matched = candidates
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag);
+        if matched {
+            let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+            if let Ok(value) = etag.parse() {
+                not_modified.headers_mut().insert(header::ETAG, value);
+            }
+            return not_modified;
+        }
+    }
+
+    if let Ok(value) = etag.parse() {
+        parts.headers.insert(header::ETAG, value);
+    }
+    Response::from_parts(parts, axum::body::Body::from(bytes))
+}
+
+/// Build the CORS layer described by the server configuration
+///
+/// Returns `None` when no origins are configured, leaving the server
+/// same-origin only. Invalid method or header names are skipped with a
+/// warning; `ServerConfig::validate` rejects the unsafe combinations.
+pub fn cors_layer(config: &ServerConfig) -> Option<CorsLayer> {
+    if config.cors_origins.is_empty() {
+        return None;
+    }
+
+    let origins = if config.cors_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(config.cors_origins.iter().filter_map(
+            |origin| match HeaderValue::from_str(origin) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid CORS origin {}: {}", origin, e);
+                    None
+                }
+            },
+        ))
+    };
+
+    let methods = config
+        .cors_methods
+        .iter()
+        .filter_map(|method| match Method::from_bytes(method.as_bytes()) {
+            Ok(method) => Some(method),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid CORS method {}: {}", method, e);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let headers = config
+        .cors_headers
+        .iter()
+        .filter_map(|name| match HeaderName::from_bytes(name.as_bytes()) {
+            Ok(name) => Some(name),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid CORS header {}: {}", name, e);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(config.cors_allow_credentials),
+    )
+}
+
+/// Create the full application with state, metrics and CORS middleware applied
+pub fn create_app(state: AppState) -> Router {
+    let cors = cors_layer(&state.config);
+    let app = create_router()
+        .route_layer(axum::middleware::from_fn(conditional_get))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            track_requests,
+        ))
+        .layer(axum::middleware::from_fn(trace_request))
+        .with_state(state);
+
+    match cors {
+        Some(cors) => app.layer(cors),
+        None => app,
+    }
 }
 
 #[cfg(test)]
@@ -923,4 +1249,253 @@ mod tests {
         assert!(query.q.is_none());
         assert!(query.limit.is_none());
     }
+
+    fn test_state(dir: &tempfile::TempDir) -> AppState {
+        let storage = Storage::open(dir.path().join("server.db")).expect("open storage");
+        AppState::new(storage, ServerConfig::default())
+    }
+
+    async fn get(app: Router, uri: &str) -> Response {
+        use tower::ServiceExt;
+
+        app.oneshot(
+            axum::http::Request::builder()
+                .uri(uri)
+                .body(axum::body::Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("router response")
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        String::from_utf8(bytes.to_vec()).expect("utf-8 body")
+    }
+
+    #[tokio::test]
+    async fn test_metrics_counts_search_requests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = test_state(&dir);
+        // An indexing process sharing the database finished one run
+        crate::storage::index_runs::record_index_run(
+            &mut state.storage.lock().expect("storage lock"),
+            "proj",
+            std::time::Duration::from_millis(1500),
+        )
+        .expect("record index run");
+        let app = create_app(state);
+
+        let response = get(app.clone(), "/api/search?q=main").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get(app, "/metrics").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let text = body_text(response).await;
+        assert!(text.contains("leindex_search_requests_total 1"));
+        assert!(text.contains(
+            "leindex_http_requests_total{method=\"GET\",route=\"/api/search\",status=\"200\"} 1"
+        ));
+        assert!(text.contains("leindex_cache_hit_ratio"));
+        assert!(text.contains("# TYPE leindex_index_duration_seconds histogram"));
+        assert!(text.contains("leindex_index_duration_seconds_bucket{le=\"2.5\"} 1"));
+        assert!(text.contains("leindex_index_duration_seconds_count 1"));
+    }
+
+    #[tokio::test]
+    async fn test_request_id_header_generated_or_echoed() {
+        use tower::ServiceExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let app = create_app(test_state(&dir));
+
+        let response = get(app.clone(), "/api/codebases/missing").await;
+        assert!(!response.status().is_success());
+        let generated = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .expect("request id header on error response");
+        assert!(generated.starts_with("http-"));
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/healthz")
+                    .header(REQUEST_ID_HEADER, "client-trace-7")
+                    .body(axum::body::Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("router response");
+        assert_eq!(
+            response
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|value| value.to_str().ok()),
+            Some("client-trace-7")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readyz_waits_for_indexed_project() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = test_state(&dir);
+        let app = create_app(state.clone());
+
+        let response = get(app.clone(), "/healthz").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get(app.clone(), "/readyz").await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        state
+            .storage
+            .lock()
+            .expect("storage lock")
+            .conn()
+            .execute(
+                "INSERT INTO project_metadata (unique_project_id, base_name, path_hash, canonical_path)
+                 VALUES ('demo_00000000_0', 'demo', '00000000', '/tmp/demo')",
+                [],
+            )
+            .expect("insert project");
+
+        let response = get(app, "/readyz").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_text(response).await.contains("\"ready\""));
+    }
+
+    #[tokio::test]
+    async fn test_etag_returns_not_modified_until_reindex() {
+        use tower::ServiceExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = test_state(&dir);
+        let app = create_app(state.clone());
+
+        let response = get(app.clone(), "/api/codebases").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .expect("etag header")
+            .to_str()
+            .expect("ascii etag")
+            .to_string();
+
+        let conditional = |tag: String| {
+            axum::http::Request::builder()
+                .uri("/api/codebases")
+                .header(header::IF_NONE_MATCH, tag)
+                .body(axum::body::Body::empty())
+                .expect("build request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(conditional(etag.clone()))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        state
+            .storage
+            .lock()
+            .expect("storage lock")
+            .conn()
+            .execute(
+                "INSERT INTO project_metadata (unique_project_id, base_name, path_hash, canonical_path)
+                 VALUES ('demo_00000000_0', 'demo', '00000000', '/tmp/demo')",
+                [],
+            )
+            .expect("insert project");
+
+        let response = app
+            .oneshot(conditional(etag.clone()))
+            .await
+            .expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(
+            response.headers().get(header::ETAG).expect("etag header"),
+            etag.as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_returns_configured_origin() {
+        use tower::ServiceExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let storage = Storage::open(dir.path().join("server.db")).expect("open storage");
+        let config = ServerConfig {
+            cors_origins: vec!["http://localhost:5173".to_string()],
+            ..Default::default()
+        };
+        let app = create_app(AppState::new(storage, config));
+
+        let preflight = |origin: &str| {
+            axum::http::Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/api/codebases")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .body(axum::body::Body::empty())
+                .expect("build request")
+        };
+
+        let response = app
+            .clone()
+            .oneshot(preflight("http://localhost:5173"))
+            .await
+            .expect("router response");
+        assert_eq!(
+            response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .expect("allow-origin header"),
+            "http://localhost:5173"
+        );
+
+        let response = app
+            .oneshot(preflight("http://evil.example"))
+            .await
+            .expect("router response");
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_requires_configured_token() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let storage = Storage::open(dir.path().join("server.db")).expect("open storage");
+        let config = ServerConfig {
+            metrics_token: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let app = create_app(AppState::new(storage, config));
+
+        let response = get(app.clone(), "/metrics").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let scrape = |token: &str| {
+            use tower::ServiceExt;
+
+            app.clone().oneshot(
+                axum::http::Request::builder()
+                    .uri("/metrics")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .body(axum::body::Body::empty())
+                    .expect("build request"),
+            )
+        };
+        let response = scrape("secres").await.expect("router response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = scrape("secret").await.expect("router response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
@@ -0,0 +1,295 @@
+//! Lightweight Prometheus-style metrics registry
+//!
+//! Counters and histograms are kept in-process and rendered in the Prometheus
+//! text exposition format by `GET /metrics`. No external metrics crate is used;
+//! the registry only tracks the handful of series leserve exposes.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Histogram bucket upper bounds in seconds
+pub const LATENCY_BUCKETS_SECONDS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Cumulative histogram with fixed buckets
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// Bucket upper bounds in seconds
+    bounds: &'static [f64],
+
+    /// Observation count per bucket (non-cumulative)
+    counts: Vec<u64>,
+
+    /// Sum of all observed values
+    sum: f64,
+
+    /// Total number of observations
+    count: u64,
+}
+
+impl Histogram {
+    /// Create an empty histogram with the given bucket bounds
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Record one observation in seconds
+    pub fn observe(&mut self, value: f64) {
+        if let Some(idx) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[idx] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Total number of observations
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (bound, count) in self.bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{}{}le=\"{}\"}} {}",
+                name, labels, sep, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+            name, labels, sep, self.count
+        );
+        let braces = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels)
+        };
+        let _ = writeln!(out, "{}_sum{} {}", name, braces, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, braces, self.count);
+    }
+}
+
+/// Key for the per-route request counter
+type RouteKey = (String, String, u16);
+
+/// In-process metrics registry shared by all handlers
+#[derive(Debug)]
+pub struct MetricsRegistry {
+    /// Requests by (method, route, status)
+    requests: Mutex<BTreeMap<RouteKey, u64>>,
+
+    /// Request latency by route
+    request_latency: Mutex<BTreeMap<String, Histogram>>,
+
+    /// Number of search requests served
+    search_requests: AtomicU64,
+
+    /// Search latency
+    search_latency: Mutex<Histogram>,
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            requests: Mutex::new(BTreeMap::new()),
+            request_latency: Mutex::new(BTreeMap::new()),
+            search_requests: AtomicU64::new(0),
+            search_latency: Mutex::new(Histogram::new(LATENCY_BUCKETS_SECONDS)),
+        }
+    }
+
+    /// Record a completed HTTP request
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        if let Ok(mut requests) = self.requests.lock() {
+            *requests
+                .entry((method.to_string(), route.to_string(), status))
+                .or_insert(0) += 1;
+        }
+        if let Ok(mut latency) = self.request_latency.lock() {
+            latency
+                .entry(route.to_string())
+                .or_insert_with(|| Histogram::new(LATENCY_BUCKETS_SECONDS))
+                .observe(elapsed.as_secs_f64());
+        }
+    }
+
+    /// Record a completed search
+    pub fn record_search(&self, elapsed: Duration) {
+        self.search_requests.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut histogram) = self.search_latency.lock() {
+            histogram.observe(elapsed.as_secs_f64());
+        }
+    }
+
+    /// Number of search requests recorded so far
+    pub fn search_count(&self) -> u64 {
+        self.search_requests.load(Ordering::Relaxed)
+    }
+
+    /// Render all registered series in Prometheus text format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str(
+            "# HELP leindex_http_requests_total HTTP requests by method, route and status\n",
+        );
+        out.push_str("# TYPE leindex_http_requests_total counter\n");
+        if let Ok(requests) = self.requests.lock() {
+            for ((method, route, status), count) in requests.iter() {
+                let _ = writeln!(
+                    out,
+                    "leindex_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                    escape_label(method),
+                    escape_label(route),
+                    status,
+                    count
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP leindex_http_request_duration_seconds HTTP request latency by route\n",
+        );
+        out.push_str("# TYPE leindex_http_request_duration_seconds histogram\n");
+        if let Ok(latency) = self.request_latency.lock() {
+            for (route, histogram) in latency.iter() {
+                histogram.render(
+                    &mut out,
+                    "leindex_http_request_duration_seconds",
+                    &format!("route=\"{}\"", escape_label(route)),
+                );
+            }
+        }
+
+        out.push_str("# HELP leindex_search_requests_total Search requests served\n");
+        out.push_str("# TYPE leindex_search_requests_total counter\n");
+        let _ = writeln!(out, "leindex_search_requests_total {}", self.search_count());
+
+        out.push_str("# HELP leindex_search_duration_seconds Search latency\n");
+        out.push_str("# TYPE leindex_search_duration_seconds histogram\n");
+        if let Ok(histogram) = self.search_latency.lock() {
+            histogram.render(&mut out, "leindex_search_duration_seconds", "");
+        }
+
+        out
+    }
+}
+
+/// Render cache telemetry gauges in Prometheus text format
+///
+/// Values come from the persisted `cache_telemetry` counters, the same source
+/// that backs the cache section of `Diagnostics`.
+pub fn render_cache_metrics(hits: i64, misses: i64) -> String {
+    let mut out = String::new();
+    let total = hits + misses;
+    let ratio = if total > 0 {
+        hits as f64 / total as f64
+    } else {
+        0.0
+    };
+
+    out.push_str("# HELP leindex_cache_hits_total Analysis cache hits\n");
+    out.push_str("# TYPE leindex_cache_hits_total counter\n");
+    let _ = writeln!(out, "leindex_cache_hits_total {}", hits);
+    out.push_str("# HELP leindex_cache_misses_total Analysis cache misses\n");
+    out.push_str("# TYPE leindex_cache_misses_total counter\n");
+    let _ = writeln!(out, "leindex_cache_misses_total {}", misses);
+    out.push_str("# HELP leindex_cache_hit_ratio Analysis cache hit rate in [0, 1]\n");
+    out.push_str("# TYPE leindex_cache_hit_ratio gauge\n");
+    let _ = writeln!(out, "leindex_cache_hit_ratio {}", ratio);
+
+    out
+}
+
+/// Render the indexing run duration histogram in Prometheus text format
+///
+/// leserve does not index projects itself; the durations are the runs that
+/// indexing processes persisted to the shared `index_runs` table.
+pub fn render_index_duration_metrics(durations: &[Duration]) -> String {
+    let mut histogram = Histogram::new(LATENCY_BUCKETS_SECONDS);
+    for duration in durations {
+        histogram.observe(duration.as_secs_f64());
+    }
+
+    let mut out = String::new();
+    out.push_str("# HELP leindex_index_duration_seconds Indexing run duration\n");
+    out.push_str("# TYPE leindex_index_duration_seconds histogram\n");
+    histogram.render(&mut out, "leindex_index_duration_seconds", "");
+    out
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(&[0.1, 1.0]);
+        histogram.observe(0.05);
+        histogram.observe(0.5);
+        histogram.observe(5.0);
+
+        let mut out = String::new();
+        histogram.render(&mut out, "h", "");
+        assert!(out.contains("h_bucket{le=\"0.1\"} 1"));
+        assert!(out.contains("h_bucket{le=\"1\"} 2"));
+        assert!(out.contains("h_bucket{le=\"+Inf\"} 3"));
+        assert!(out.contains("h_count 3"));
+    }
+
+    #[test]
+    fn test_registry_renders_route_counters() {
+        let registry = MetricsRegistry::new();
+        registry.record_request("GET", "/api/search", 200, Duration::from_millis(3));
+        registry.record_request("GET", "/api/search", 200, Duration::from_millis(4));
+
+        let out = registry.render();
+        assert!(out.contains(
+            "leindex_http_requests_total{method=\"GET\",route=\"/api/search\",status=\"200\"} 2"
+        ));
+        assert!(out.contains("leindex_search_requests_total 0"));
+    }
+
+    #[test]
+    fn test_index_duration_metrics_bucket_runs() {
+        let out =
+            render_index_duration_metrics(&[Duration::from_millis(800), Duration::from_secs(45)]);
+        assert!(out.contains("leindex_index_duration_seconds_bucket{le=\"1\"} 1"));
+        assert!(out.contains("leindex_index_duration_seconds_bucket{le=\"60\"} 2"));
+        assert!(out.contains("leindex_index_duration_seconds_count 2"));
+    }
+
+    #[test]
+    fn test_cache_metrics_ratio() {
+        let out = render_cache_metrics(3, 1);
+        assert!(out.contains("leindex_cache_hit_ratio 0.75"));
+    }
+}
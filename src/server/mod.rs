@@ -20,12 +20,16 @@ pub mod websocket;
 /// API response types matching frontend contract
 pub mod responses;
 
+/// Prometheus-style metrics registry
+pub mod metrics;
+
 #[allow(clippy::module_inception)]
 /// Server instance management
 pub mod server;
 
 pub use config::ServerConfig;
 pub use error::{ApiError, ApiResult};
+pub use metrics::MetricsRegistry;
 pub use server::LeIndexServer;
 
 #[doc(hidden)]
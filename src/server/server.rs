@@ -9,7 +9,7 @@ use tracing::{error, info};
 
 use crate::server::config::ServerConfig;
 use crate::server::error::ApiError;
-use crate::server::handlers::{create_router, AppState};
+use crate::server::handlers::{create_app, AppState};
 use crate::storage::Storage;
 use walkdir::WalkDir;
 
@@ -62,6 +62,17 @@ impl LeIndexServer {
                 error!("Failed to ingest {:?}: {}", db_path, e);
             }
         }
+        if let Err(e) = storage.close() {
+            error!("Failed to checkpoint storage after ingest: {}", e);
+        }
+        drop(storage);
+
+        // Handlers only read, so serve from a read-only connection that
+        // cannot write by accident or take write locks from the indexer
+        let storage = Storage::open_read_only(&config.db_path).map_err(|e| {
+            error!("Failed to open storage read-only: {}", e);
+            ApiError::internal(format!("Failed to open storage read-only: {}", e))
+        })?;
 
         Ok(Self {
             config,
@@ -91,8 +102,8 @@ impl LeIndexServer {
         // Build application state (clone Arc<Mutex<Storage>>)
         let state = AppState::new_from_arc(Arc::clone(&self.storage), self.config.clone());
 
-        // Build router
-        let app = create_router().with_state(state);
+        // Build router with metrics middleware
+        let app = create_app(state);
 
         // Create server
         let listener = tokio::net::TcpListener::bind(addr).await.map_err(|e| {
@@ -227,6 +238,17 @@ fn discover_leindex_dbs() -> Vec<PathBuf> {
 
 /// Attach a project database and copy its contents into the server database.
 fn ingest_project_db(target: &mut Storage, project_db: &Path) -> Result<(), ApiError> {
+    // ATTACH has no way to pass the project's encryption key
+    if crate::storage::schema::is_encrypted_database(project_db) {
+        return Err(ApiError::internal(
+            crate::storage::StorageError::EncryptionUnsupported {
+                path: project_db.display().to_string(),
+                operation: "server ingest",
+            }
+            .to_string(),
+        ));
+    }
+
     let db_str = project_db
         .to_str()
         .ok_or_else(|| ApiError::internal("Invalid project db path"))?
@@ -1,7 +1,7 @@
 //! WebSocket event broadcasting
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tracing::info;
@@ -12,22 +12,51 @@ pub const MAX_WS_MESSAGE_SIZE: usize = 1_000_000;
 /// Maximum WebSocket frame size (16KB) to prevent memory exhaustion
 pub const MAX_WS_FRAME_SIZE: usize = 16_384;
 
+/// Event topics a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WsTopic {
+    /// Project registry changes and indexing progress
+    Index,
+
+    /// Search activity
+    Search,
+
+    /// Phase analysis activity
+    Analyze,
+}
+
+impl WsTopic {
+    /// Every topic, delivered to connections that never picked any
+    pub const ALL: [WsTopic; 3] = [WsTopic::Index, WsTopic::Search, WsTopic::Analyze];
+}
+
 /// Client-to-server WebSocket message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum WsClientMessage {
-    /// Subscribe to project updates
+    /// Subscribe to project updates and/or event topics
     #[serde(rename = "subscribe")]
     Subscribe {
         /// Project ID to subscribe to
-        project_id: String,
+        #[serde(default)]
+        project_id: Option<String>,
+
+        /// Topics to receive (all topics until the connection picks some)
+        #[serde(default)]
+        topics: Vec<WsTopic>,
     },
 
-    /// Unsubscribe from project updates
+    /// Unsubscribe from project updates and/or event topics
     #[serde(rename = "unsubscribe")]
     Unsubscribe {
         /// Project ID to unsubscribe from
-        project_id: String,
+        #[serde(default)]
+        project_id: Option<String>,
+
+        /// Topics to stop receiving
+        #[serde(default)]
+        topics: Vec<WsTopic>,
     },
 
     /// Ping/heartbeat
@@ -88,6 +117,26 @@ pub enum WsEvent {
         current_file: String,
     },
 
+    /// Search completed
+    #[serde(rename = "search.completed")]
+    SearchCompleted {
+        /// Search query string
+        query: String,
+
+        /// Number of results returned
+        result_count: usize,
+    },
+
+    /// Phase analysis completed
+    #[serde(rename = "analyze.completed")]
+    AnalysisCompleted {
+        /// Unique codebase identifier
+        codebase_id: String,
+
+        /// Phases that were executed
+        executed_phases: Vec<u8>,
+    },
+
     /// Heartbeat/ping
     Heartbeat {
         /// Unix timestamp in milliseconds
@@ -103,10 +152,37 @@ impl WsEvent {
             Self::ProjectUpdated { .. } => "project_updated",
             Self::ProjectRemoved { .. } => "project_removed",
             Self::IndexingProgress { .. } => "indexing.progress",
+            Self::SearchCompleted { .. } => "search.completed",
+            Self::AnalysisCompleted { .. } => "analyze.completed",
             Self::Heartbeat { .. } => "heartbeat",
         }
     }
 
+    /// Topic this event belongs to (`None` for control events sent to everyone)
+    pub fn topic(&self) -> Option<WsTopic> {
+        match self {
+            Self::ProjectAdded { .. }
+            | Self::ProjectUpdated { .. }
+            | Self::ProjectRemoved { .. }
+            | Self::IndexingProgress { .. } => Some(WsTopic::Index),
+            Self::SearchCompleted { .. } => Some(WsTopic::Search),
+            Self::AnalysisCompleted { .. } => Some(WsTopic::Analyze),
+            Self::Heartbeat { .. } => None,
+        }
+    }
+
+    /// Codebase the event refers to, if any
+    pub fn codebase_id(&self) -> Option<&str> {
+        match self {
+            Self::ProjectAdded { codebase_id, .. }
+            | Self::ProjectUpdated { codebase_id, .. }
+            | Self::ProjectRemoved { codebase_id }
+            | Self::IndexingProgress { codebase_id, .. }
+            | Self::AnalysisCompleted { codebase_id, .. } => Some(codebase_id),
+            Self::SearchCompleted { .. } | Self::Heartbeat { .. } => None,
+        }
+    }
+
     /// Convert to JSON string
     pub fn to_json(&self) -> String {
         serde_json::to_string(self).expect("Failed to serialize WebSocket event to JSON")
@@ -128,6 +204,9 @@ pub struct ConnectionState {
     /// Subscribed project IDs (empty = all projects)
     pub subscriptions: Vec<String>,
 
+    /// Subscribed topics (`None` = all topics; an empty set = no topics)
+    pub topics: Option<HashSet<WsTopic>>,
+
     /// Client IP address
     pub ip_addr: Option<String>,
 }
@@ -138,6 +217,7 @@ impl ConnectionState {
         Self {
             id,
             subscriptions: Vec::new(),
+            topics: None,
             ip_addr,
         }
     }
@@ -159,6 +239,50 @@ impl ConnectionState {
     pub fn unsubscribe(&mut self, project_id: &str) {
         self.subscriptions.retain(|s| s != project_id);
     }
+
+    /// Add topic subscriptions
+    ///
+    /// The first topics subscribed narrow delivery from all topics to just
+    /// those; an empty list leaves the subscriptions unchanged.
+    pub fn subscribe_topics(&mut self, topics: impl IntoIterator<Item = WsTopic>) {
+        let mut topics = topics.into_iter().peekable();
+        if topics.peek().is_some() {
+            self.topics.get_or_insert_with(HashSet::new).extend(topics);
+        }
+    }
+
+    /// Remove topic subscriptions
+    ///
+    /// Unsubscribing from every topic leaves the connection with none, rather
+    /// than falling back to all topics.
+    pub fn unsubscribe_topics(&mut self, topics: &[WsTopic]) {
+        if topics.is_empty() {
+            return;
+        }
+        self.topics
+            .get_or_insert_with(|| WsTopic::ALL.into_iter().collect())
+            .retain(|topic| !topics.contains(topic));
+    }
+
+    /// Check if connection is subscribed to a topic
+    pub fn is_subscribed_to_topic(&self, topic: WsTopic) -> bool {
+        self.topics
+            .as_ref()
+            .map_or(true, |topics| topics.contains(&topic))
+    }
+
+    /// Check if an event should be delivered to this connection
+    pub fn wants(&self, event: &WsEvent) -> bool {
+        let topic_ok = event
+            .topic()
+            .map(|topic| self.is_subscribed_to_topic(topic))
+            .unwrap_or(true);
+        let project_ok = event
+            .codebase_id()
+            .map(|id| self.is_subscribed_to(id))
+            .unwrap_or(true);
+        topic_ok && project_ok
+    }
 }
 
 /// WebSocket connection manager
@@ -213,10 +337,22 @@ impl WsManager {
 
     /// Broadcast event to specific project subscribers
     pub async fn broadcast_to_project(&self, _project_id: &str, event: WsEvent) {
-        // For now, broadcast to all (filtering done per connection)
+        // Filtering by project and topic is done per connection
         self.broadcast(event).await;
     }
 
+    /// Check whether an event should be sent to a connection
+    ///
+    /// Unknown connections receive nothing.
+    pub async fn should_deliver(&self, conn_id: &str, event: &WsEvent) -> bool {
+        self.connections
+            .read()
+            .await
+            .get(conn_id)
+            .map(|state| state.wants(event))
+            .unwrap_or(false)
+    }
+
     /// Get number of active connections
     pub async fn connection_count(&self) -> usize {
         self.connections.read().await.len()
@@ -230,24 +366,30 @@ impl WsManager {
     /// Handle client message and update connection state
     pub async fn handle_client_message(&self, conn_id: &str, message: WsClientMessage) {
         match message {
-            WsClientMessage::Subscribe { project_id } => {
+            WsClientMessage::Subscribe { project_id, topics } => {
                 info!(
-                    "Connection {} subscribing to project {}",
-                    conn_id, project_id
+                    "Connection {} subscribing to project {:?}, topics {:?}",
+                    conn_id, project_id, topics
                 );
                 let mut connections = self.connections.write().await;
                 if let Some(state) = connections.get_mut(conn_id) {
-                    state.subscribe(project_id);
+                    if let Some(project_id) = project_id {
+                        state.subscribe(project_id);
+                    }
+                    state.subscribe_topics(topics);
                 }
             }
-            WsClientMessage::Unsubscribe { project_id } => {
+            WsClientMessage::Unsubscribe { project_id, topics } => {
                 info!(
-                    "Connection {} unsubscribing from project {}",
-                    conn_id, project_id
+                    "Connection {} unsubscribing from project {:?}, topics {:?}",
+                    conn_id, project_id, topics
                 );
                 let mut connections = self.connections.write().await;
                 if let Some(state) = connections.get_mut(conn_id) {
-                    state.unsubscribe(&project_id);
+                    if let Some(project_id) = project_id {
+                        state.unsubscribe(&project_id);
+                    }
+                    state.unsubscribe_topics(&topics);
                 }
             }
             WsClientMessage::Ping { timestamp } => {
@@ -354,6 +496,125 @@ mod tests {
         assert!(state.is_subscribed_to("any_project"));
     }
 
+    #[test]
+    fn test_subscribe_message_parses_topics() {
+        let message: WsClientMessage =
+            serde_json::from_str(r#"{"type":"subscribe","topics":["index","search"]}"#)
+                .expect("subscribe message should parse");
+        match message {
+            WsClientMessage::Subscribe { project_id, topics } => {
+                assert!(project_id.is_none());
+                assert_eq!(topics, vec![WsTopic::Index, WsTopic::Search]);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unsubscribing_every_topic_delivers_none() {
+        let mut state = ConnectionState::new("conn".to_string(), None);
+        assert!(WsTopic::ALL
+            .iter()
+            .all(|topic| state.is_subscribed_to_topic(*topic)));
+
+        state.subscribe_topics([WsTopic::Search]);
+        state.unsubscribe_topics(&[WsTopic::Search]);
+        assert!(WsTopic::ALL
+            .iter()
+            .all(|topic| !state.is_subscribed_to_topic(*topic)));
+
+        // Unsubscribing without a prior subscribe narrows from all topics
+        let mut state = ConnectionState::new("conn".to_string(), None);
+        state.unsubscribe_topics(&[WsTopic::Index]);
+        assert!(!state.is_subscribed_to_topic(WsTopic::Index));
+        assert!(state.is_subscribed_to_topic(WsTopic::Search));
+    }
+
+    #[tokio::test]
+    async fn test_topic_filtering_per_connection() {
+        let manager = WsManager::new();
+        manager
+            .register_connection("indexer".to_string(), None)
+            .await;
+        manager
+            .register_connection("searcher".to_string(), None)
+            .await;
+        manager
+            .register_connection("legacy".to_string(), None)
+            .await;
+
+        let mut indexer_rx = manager.broadcaster.subscribe();
+        let mut searcher_rx = manager.broadcaster.subscribe();
+        let mut legacy_rx = manager.broadcaster.subscribe();
+
+        manager
+            .handle_client_message(
+                "indexer",
+                WsClientMessage::Subscribe {
+                    project_id: None,
+                    topics: vec![WsTopic::Index],
+                },
+            )
+            .await;
+        manager
+            .handle_client_message(
+                "searcher",
+                WsClientMessage::Subscribe {
+                    project_id: None,
+                    topics: vec![WsTopic::Search],
+                },
+            )
+            .await;
+
+        manager
+            .broadcast(WsEvent::IndexingProgress {
+                codebase_id: "proj_1".to_string(),
+                phase: 1,
+                percent: 10,
+                current_file: "src/lib.rs".to_string(),
+            })
+            .await;
+        manager
+            .broadcast(WsEvent::SearchCompleted {
+                query: "main".to_string(),
+                result_count: 3,
+            })
+            .await;
+        manager
+            .broadcast(WsEvent::AnalysisCompleted {
+                codebase_id: "proj_1".to_string(),
+                executed_phases: vec![1, 2],
+            })
+            .await;
+
+        async fn delivered(
+            manager: &WsManager,
+            conn_id: &str,
+            rx: &mut broadcast::Receiver<WsEvent>,
+        ) -> Vec<&'static str> {
+            let mut types = Vec::new();
+            while let Ok(event) = rx.try_recv() {
+                if manager.should_deliver(conn_id, &event).await {
+                    types.push(event.event_type());
+                }
+            }
+            types
+        }
+
+        assert_eq!(
+            delivered(&manager, "indexer", &mut indexer_rx).await,
+            vec!["indexing.progress"]
+        );
+        assert_eq!(
+            delivered(&manager, "searcher", &mut searcher_rx).await,
+            vec!["search.completed"]
+        );
+        assert_eq!(
+            delivered(&manager, "legacy", &mut legacy_rx).await,
+            vec!["indexing.progress", "search.completed", "analyze.completed"]
+        );
+    }
+
     #[tokio::test]
     async fn test_ws_manager_new() {
         let manager = WsManager::new();
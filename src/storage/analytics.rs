@@ -1,8 +1,17 @@
 // DuckDB analytics integration
 
 use crate::storage::schema::Storage;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::DiGraph;
 use rusqlite::{params, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Complexity threshold used when counting hotspots in snapshots
+pub const SNAPSHOT_HOTSPOT_THRESHOLD: i32 = 10;
+
+/// Number of hotspots listed in a [`ProjectSummary`]
+pub const SUMMARY_HOTSPOT_LIMIT: usize = 10;
 
 /// Analytics for graph metrics
 pub struct Analytics {
@@ -112,6 +121,581 @@ impl Analytics {
 
         Ok(hotspots)
     }
+
+    /// Record a timestamped metrics snapshot for a project
+    ///
+    /// `generation` identifies the indexing run the snapshot belongs to.
+    pub fn snapshot(&self, project_id: &str, generation: &str) -> SqliteResult<AnalyticsSnapshot> {
+        let taken_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        self.snapshot_at(project_id, generation, taken_at)
+    }
+
+    /// Record a metrics snapshot with an explicit Unix timestamp
+    pub fn snapshot_at(
+        &self,
+        project_id: &str,
+        generation: &str,
+        taken_at: i64,
+    ) -> SqliteResult<AnalyticsSnapshot> {
+        let conn = self.storage.conn();
+
+        let (node_count, avg_complexity, max_complexity): (i64, f64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(AVG(complexity), 0.0), COALESCE(MAX(complexity), 0)
+             FROM intel_nodes WHERE project_id = ?1",
+            params![project_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let edge_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM intel_edges e
+             INNER JOIN intel_nodes n ON e.caller_id = n.id
+             WHERE n.project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let hotspot_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM (
+                SELECT n.id, COUNT(e.callee_id) as fan_out
+                FROM intel_nodes n
+                LEFT JOIN intel_edges e ON n.id = e.caller_id
+                WHERE n.project_id = ?1 AND n.complexity >= ?2
+                GROUP BY n.id
+                HAVING fan_out > ?3
+            )",
+            params![
+                project_id,
+                SNAPSHOT_HOTSPOT_THRESHOLD,
+                SNAPSHOT_HOTSPOT_THRESHOLD / 2
+            ],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "INSERT INTO analytics_snapshots
+                (project_id, generation, taken_at, node_count, edge_count,
+                 avg_complexity, max_complexity, hotspot_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                project_id,
+                generation,
+                taken_at,
+                node_count,
+                edge_count,
+                avg_complexity,
+                max_complexity,
+                hotspot_count
+            ],
+        )?;
+
+        Ok(AnalyticsSnapshot {
+            project_id: project_id.to_string(),
+            generation: generation.to_string(),
+            taken_at,
+            node_count,
+            edge_count,
+            avg_complexity,
+            max_complexity,
+            hotspot_count,
+        })
+    }
+
+    /// Get the series of one metric for a project since a Unix timestamp
+    ///
+    /// Points are returned oldest first.
+    pub fn trend(
+        &self,
+        project_id: &str,
+        metric: TrendMetric,
+        since: i64,
+    ) -> SqliteResult<Vec<TrendPoint>> {
+        let sql = format!(
+            "SELECT taken_at, generation, CAST({} AS REAL)
+             FROM analytics_snapshots
+             WHERE project_id = ?1 AND taken_at >= ?2
+             ORDER BY taken_at ASC, id ASC",
+            metric.column()
+        );
+        let mut stmt = self.storage.conn().prepare(&sql)?;
+
+        let points = stmt
+            .query_map(params![project_id, since], |row| {
+                Ok(TrendPoint {
+                    taken_at: row.get(0)?,
+                    generation: row.get(1)?,
+                    value: row.get(2)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(points)
+    }
+
+    /// List imports that phase 2 could not resolve to local nodes
+    pub fn unresolved_imports(
+        &self,
+        project_id: &str,
+    ) -> SqliteResult<Vec<UnresolvedImportRecord>> {
+        let mut stmt = self.storage.conn().prepare(
+            "SELECT import_path, file_path FROM unresolved_imports
+             WHERE project_id = ?1
+             ORDER BY import_path, file_path",
+        )?;
+
+        let imports = stmt
+            .query_map(params![project_id], |row| {
+                Ok(UnresolvedImportRecord {
+                    import_path: row.get(0)?,
+                    file_path: row.get(1)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(imports)
+    }
+
+    /// Find dependency cycles among a project's nodes
+    ///
+    /// Each cycle is a strongly connected component with more than one node,
+    /// or a single node with a self edge, given as database node IDs.
+    pub fn find_cycles(&self, project_id: &str) -> SqliteResult<Vec<Vec<i64>>> {
+        let conn = self.storage.conn();
+
+        let mut graph = DiGraph::<i64, ()>::new();
+        let mut indices = HashMap::new();
+        let mut stmt = conn.prepare("SELECT id FROM intel_nodes WHERE project_id = ?1")?;
+        for id in stmt.query_map(params![project_id], |row| row.get::<_, i64>(0))? {
+            let id = id?;
+            indices.insert(id, graph.add_node(id));
+        }
+
+        let mut self_loops = Vec::new();
+        let mut stmt = conn.prepare(
+            "SELECT e.caller_id, e.callee_id FROM intel_edges e
+             INNER JOIN intel_nodes n ON e.caller_id = n.id
+             WHERE n.project_id = ?1",
+        )?;
+        let edges = stmt.query_map(params![project_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for edge in edges {
+            let (caller, callee) = edge?;
+            if let (Some(&from), Some(&to)) = (indices.get(&caller), indices.get(&callee)) {
+                if from == to {
+                    self_loops.push(caller);
+                } else {
+                    graph.update_edge(from, to, ());
+                }
+            }
+        }
+
+        let mut cycles: Vec<Vec<i64>> = tarjan_scc(&graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let mut ids: Vec<i64> = component.into_iter().map(|idx| graph[idx]).collect();
+                ids.sort_unstable();
+                ids
+            })
+            .collect();
+        self_loops.sort_unstable();
+        self_loops.dedup();
+        cycles.extend(self_loops.into_iter().map(|id| vec![id]));
+
+        Ok(cycles)
+    }
+
+    /// One-call overview of a project for dashboards
+    ///
+    /// Hotspots use [`SNAPSHOT_HOTSPOT_THRESHOLD`] like snapshots do, with at
+    /// most [`SUMMARY_HOTSPOT_LIMIT`] listed, most complex first.
+    pub fn project_summary(&self, project_id: &str) -> SqliteResult<ProjectSummary> {
+        let conn = self.storage.conn();
+
+        let file_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM indexed_files WHERE project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let node_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM intel_nodes WHERE project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let edge_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM intel_edges e
+             INNER JOIN intel_nodes n ON e.caller_id = n.id
+             WHERE n.project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT language, COUNT(*) as node_count, COUNT(DISTINCT file_path)
+             FROM intel_nodes WHERE project_id = ?1
+             GROUP BY language
+             ORDER BY node_count DESC, language",
+        )?;
+        let languages = stmt
+            .query_map(params![project_id], |row| {
+                Ok(LanguageCount {
+                    language: row.get(0)?,
+                    node_count: row.get(1)?,
+                    file_count: row.get(2)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT
+                n.id,
+                n.symbol_name,
+                n.file_path,
+                n.complexity,
+                COUNT(e.callee_id) as fan_out
+                FROM intel_nodes n
+                LEFT JOIN intel_edges e ON n.id = e.caller_id
+                WHERE n.project_id = ?1 AND n.complexity >= ?2
+                GROUP BY n.id
+                HAVING fan_out > ?3
+                ORDER BY n.complexity DESC, fan_out DESC
+                LIMIT ?4",
+        )?;
+        let hotspots = stmt
+            .query_map(
+                params![
+                    project_id,
+                    SNAPSHOT_HOTSPOT_THRESHOLD,
+                    SNAPSHOT_HOTSPOT_THRESHOLD / 2,
+                    SUMMARY_HOTSPOT_LIMIT as i64
+                ],
+                |row| {
+                    Ok(Hotspot {
+                        node_id: row.get(0)?,
+                        symbol_name: row.get(1)?,
+                        file_path: row.get(2)?,
+                        complexity: row.get(3)?,
+                        fan_out: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        let unresolved_import_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM unresolved_imports WHERE project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        Ok(ProjectSummary {
+            project_id: project_id.to_string(),
+            file_count,
+            node_count,
+            edge_count,
+            languages,
+            hotspots,
+            cycle_count: self.find_cycles(project_id)?.len(),
+            unresolved_import_count,
+        })
+    }
+
+    /// Composite project complexity index using the default weights
+    pub fn project_complexity(&self, project_id: &str) -> SqliteResult<ProjectComplexity> {
+        self.project_complexity_with_weights(project_id, &ComplexityWeights::default())
+    }
+
+    /// Composite project complexity index (0–100) using custom weights
+    ///
+    /// Each signal is first normalized to `[0, 1]`:
+    /// - average complexity against [`AVG_COMPLEXITY_CEILING`]
+    /// - maximum complexity against [`MAX_COMPLEXITY_CEILING`]
+    /// - share of nodes that sit on a dependency cycle
+    /// - coupling: the mean of average fan-out against [`AVG_FAN_OUT_CEILING`]
+    ///   and maximum fan-in against [`MAX_FAN_IN_CEILING`]
+    ///
+    /// The score is the weighted mean of those signals scaled to 100.
+    pub fn project_complexity_with_weights(
+        &self,
+        project_id: &str,
+        weights: &ComplexityWeights,
+    ) -> SqliteResult<ProjectComplexity> {
+        let conn = self.storage.conn();
+
+        let (node_count, avg_complexity, max_complexity): (i64, f64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(AVG(complexity), 0.0), COALESCE(MAX(complexity), 0)
+             FROM intel_nodes WHERE project_id = ?1",
+            params![project_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let edge_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM intel_edges e
+             INNER JOIN intel_nodes n ON e.caller_id = n.id
+             WHERE n.project_id = ?1",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let max_fan_in: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(fan_in), 0) FROM (
+                SELECT e.callee_id, COUNT(*) AS fan_in
+                FROM intel_edges e
+                INNER JOIN intel_nodes n ON e.callee_id = n.id
+                WHERE n.project_id = ?1
+                GROUP BY e.callee_id
+            )",
+            params![project_id],
+            |row| row.get(0),
+        )?;
+
+        let cycles = self.find_cycles(project_id)?;
+        let nodes_in_cycles: usize = cycles.iter().map(Vec::len).sum();
+
+        let avg_fan_out = if node_count > 0 {
+            edge_count as f64 / node_count as f64
+        } else {
+            0.0
+        };
+        let cycle_share = if node_count > 0 {
+            nodes_in_cycles as f64 / node_count as f64
+        } else {
+            0.0
+        };
+
+        let signals = [
+            (
+                weights.avg_complexity,
+                avg_complexity / AVG_COMPLEXITY_CEILING,
+            ),
+            (
+                weights.max_complexity,
+                max_complexity as f64 / MAX_COMPLEXITY_CEILING,
+            ),
+            (weights.cycles, cycle_share),
+            (
+                weights.coupling,
+                (avg_fan_out / AVG_FAN_OUT_CEILING).min(1.0) * 0.5
+                    + (max_fan_in as f64 / MAX_FAN_IN_CEILING).min(1.0) * 0.5,
+            ),
+        ];
+        let total_weight: f64 = signals.iter().map(|(w, _)| w.max(0.0)).sum();
+        let score = if total_weight > 0.0 && node_count > 0 {
+            signals
+                .iter()
+                .map(|(w, v)| w.max(0.0) * v.clamp(0.0, 1.0))
+                .sum::<f64>()
+                / total_weight
+                * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(ProjectComplexity {
+            project_id: project_id.to_string(),
+            score,
+            node_count,
+            avg_complexity,
+            max_complexity,
+            cycle_count: cycles.len(),
+            nodes_in_cycles,
+            avg_fan_out,
+            max_fan_in,
+        })
+    }
+}
+
+/// Replace the stored unresolved imports of a project
+///
+/// Each item is an `(import_path, file_path)` pair.
+pub fn save_unresolved_imports<'a>(
+    storage: &Storage,
+    project_id: &str,
+    imports: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> SqliteResult<()> {
+    let tx = storage.conn().unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM unresolved_imports WHERE project_id = ?1",
+        params![project_id],
+    )?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT OR IGNORE INTO unresolved_imports (project_id, import_path, file_path)
+             VALUES (?1, ?2, ?3)",
+        )?;
+        for (import_path, file_path) in imports {
+            stmt.execute(params![project_id, import_path, file_path])?;
+        }
+    }
+    tx.commit()
+}
+
+/// Unresolved import recorded by phase 2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedImportRecord {
+    /// Import path as written in the source
+    pub import_path: String,
+    /// File containing the import
+    pub file_path: String,
+}
+
+/// Average complexity mapped to the top of the scale
+pub const AVG_COMPLEXITY_CEILING: f64 = 20.0;
+
+/// Maximum complexity mapped to the top of the scale
+pub const MAX_COMPLEXITY_CEILING: f64 = 50.0;
+
+/// Average fan-out mapped to the top of the scale
+pub const AVG_FAN_OUT_CEILING: f64 = 5.0;
+
+/// Maximum fan-in mapped to the top of the scale
+pub const MAX_FAN_IN_CEILING: f64 = 20.0;
+
+/// Relative weights of the signals in the composite complexity index
+///
+/// Weights need not sum to one; the index is normalized by their total.
+/// Negative weights are treated as zero.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityWeights {
+    /// Weight of average node complexity (default 0.30)
+    pub avg_complexity: f64,
+    /// Weight of maximum node complexity (default 0.20)
+    pub max_complexity: f64,
+    /// Weight of the share of nodes on dependency cycles (default 0.25)
+    pub cycles: f64,
+    /// Weight of fan-in/fan-out coupling (default 0.25)
+    pub coupling: f64,
+}
+
+impl Default for ComplexityWeights {
+    fn default() -> Self {
+        Self {
+            avg_complexity: 0.30,
+            max_complexity: 0.20,
+            cycles: 0.25,
+            coupling: 0.25,
+        }
+    }
+}
+
+/// Composite complexity index for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectComplexity {
+    /// Project the index was computed for
+    pub project_id: String,
+    /// Composite score in `[0, 100]`; higher means more complex
+    pub score: f64,
+    /// Number of nodes
+    pub node_count: i64,
+    /// Average node complexity
+    pub avg_complexity: f64,
+    /// Maximum node complexity
+    pub max_complexity: i64,
+    /// Number of dependency cycles
+    pub cycle_count: usize,
+    /// Number of nodes that sit on a cycle
+    pub nodes_in_cycles: usize,
+    /// Average outgoing edges per node
+    pub avg_fan_out: f64,
+    /// Largest number of incoming edges on a single node
+    pub max_fan_in: i64,
+}
+
+/// Overview of one project, see [`Analytics::project_summary`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSummary {
+    /// Project the summary was computed for
+    pub project_id: String,
+    /// Number of files tracked in `indexed_files`
+    pub file_count: i64,
+    /// Number of nodes
+    pub node_count: i64,
+    /// Number of edges originating in the project
+    pub edge_count: i64,
+    /// Nodes and files per language, largest first
+    pub languages: Vec<LanguageCount>,
+    /// Top hotspot nodes, most complex first
+    pub hotspots: Vec<Hotspot>,
+    /// Number of dependency cycles
+    pub cycle_count: usize,
+    /// Number of imports phase 2 could not resolve
+    pub unresolved_import_count: i64,
+}
+
+/// Per-language node and file counts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageCount {
+    /// Language name as stored on the nodes
+    pub language: String,
+    /// Number of nodes in this language
+    pub node_count: i64,
+    /// Number of files with nodes in this language
+    pub file_count: i64,
+}
+
+/// Metric tracked across analytics snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendMetric {
+    /// Number of nodes in the project
+    NodeCount,
+    /// Number of edges originating in the project
+    EdgeCount,
+    /// Average node complexity
+    AvgComplexity,
+    /// Maximum node complexity
+    MaxComplexity,
+    /// Number of hotspot nodes
+    HotspotCount,
+}
+
+impl TrendMetric {
+    /// Column in `analytics_snapshots` holding this metric
+    fn column(self) -> &'static str {
+        match self {
+            Self::NodeCount => "node_count",
+            Self::EdgeCount => "edge_count",
+            Self::AvgComplexity => "avg_complexity",
+            Self::MaxComplexity => "max_complexity",
+            Self::HotspotCount => "hotspot_count",
+        }
+    }
+}
+
+/// Stored metrics snapshot for one indexing run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsSnapshot {
+    /// Project the snapshot belongs to
+    pub project_id: String,
+    /// Indexing generation identifier
+    pub generation: String,
+    /// Unix timestamp (seconds) when the snapshot was taken
+    pub taken_at: i64,
+    /// Number of nodes
+    pub node_count: i64,
+    /// Number of edges
+    pub edge_count: i64,
+    /// Average node complexity
+    pub avg_complexity: f64,
+    /// Maximum node complexity
+    pub max_complexity: i64,
+    /// Number of hotspot nodes
+    pub hotspot_count: i64,
+}
+
+/// One point of a metric trend series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    /// Unix timestamp (seconds) of the snapshot
+    pub taken_at: i64,
+    /// Indexing generation identifier
+    pub generation: String,
+    /// Metric value
+    pub value: f64,
 }
 
 /// Node type count
@@ -172,4 +756,237 @@ mod tests {
         let counts = analytics.count_nodes_by_type().unwrap();
         assert_eq!(counts.len(), 0);
     }
+
+    fn insert_node(storage: &Storage, project_id: &str, name: &str, complexity: i32) {
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO intel_nodes
+                    (project_id, file_path, node_id, symbol_name, qualified_name,
+                     node_type, complexity, content_hash, created_at, updated_at)
+                 VALUES (?1, 'src/lib.rs', ?2, ?2, ?2, 'function', ?3, ?2, 0, 0)",
+                params![project_id, name, complexity],
+            )
+            .unwrap();
+    }
+
+    fn node_id(storage: &Storage, project_id: &str, name: &str) -> i64 {
+        storage
+            .conn()
+            .query_row(
+                "SELECT id FROM intel_nodes WHERE project_id = ?1 AND symbol_name = ?2",
+                params![project_id, name],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn insert_edge(storage: &Storage, project_id: &str, from: &str, to: &str) {
+        let caller = node_id(storage, project_id, from);
+        let callee = node_id(storage, project_id, to);
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO intel_edges (caller_id, callee_id, edge_type) VALUES (?1, ?2, 'call')",
+                params![caller, callee],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_coupled_graph_scores_higher_than_flat() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+
+        for name in ["a", "b", "c", "d"] {
+            insert_node(&storage, "flat", name, 3);
+            insert_node(&storage, "coupled", name, 3);
+        }
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("d", "a"), ("b", "a")] {
+            insert_edge(&storage, "coupled", from, to);
+        }
+
+        let analytics = Analytics::new(storage);
+        let flat = analytics.project_complexity("flat").unwrap();
+        let coupled = analytics.project_complexity("coupled").unwrap();
+
+        assert_eq!(flat.cycle_count, 0);
+        assert_eq!(coupled.cycle_count, 1);
+        assert_eq!(coupled.nodes_in_cycles, 3);
+        assert_eq!(coupled.max_fan_in, 3);
+        assert!(coupled.score > flat.score);
+        assert!((0.0..=100.0).contains(&coupled.score));
+
+        let complexity_only = ComplexityWeights {
+            avg_complexity: 1.0,
+            max_complexity: 0.0,
+            cycles: 0.0,
+            coupling: 0.0,
+        };
+        let flat = analytics
+            .project_complexity_with_weights("flat", &complexity_only)
+            .unwrap();
+        let coupled = analytics
+            .project_complexity_with_weights("coupled", &complexity_only)
+            .unwrap();
+        assert_eq!(flat.score, coupled.score);
+    }
+
+    #[test]
+    fn test_unresolved_imports_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        save_unresolved_imports(
+            &storage,
+            "proj",
+            [("left_pad", "src/a.js"), ("serde_yaml", "src/lib.rs")],
+        )
+        .unwrap();
+        save_unresolved_imports(&storage, "proj", [("serde_yaml", "src/lib.rs")]).unwrap();
+
+        let analytics = Analytics::new(storage);
+        let imports = analytics.unresolved_imports("proj").unwrap();
+        assert_eq!(
+            imports,
+            vec![UnresolvedImportRecord {
+                import_path: "serde_yaml".to_string(),
+                file_path: "src/lib.rs".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trend_returns_snapshots_in_time_order() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        insert_node(&storage, "proj", "a", 4);
+        let analytics = Analytics::new(storage);
+
+        analytics.snapshot_at("proj", "gen-1", 100).unwrap();
+        insert_node(&analytics.storage, "proj", "b", 8);
+        analytics.snapshot_at("proj", "gen-2", 200).unwrap();
+        analytics.snapshot_at("other", "gen-1", 150).unwrap();
+
+        let trend = analytics.trend("proj", TrendMetric::NodeCount, 0).unwrap();
+        assert_eq!(trend.len(), 2);
+        assert_eq!(trend[0].generation, "gen-1");
+        assert_eq!(trend[0].value, 1.0);
+        assert_eq!(trend[1].generation, "gen-2");
+        assert_eq!(trend[1].value, 2.0);
+
+        let complexity = analytics
+            .trend("proj", TrendMetric::AvgComplexity, 150)
+            .unwrap();
+        assert_eq!(complexity.len(), 1);
+        assert_eq!(complexity[0].value, 6.0);
+    }
+
+    #[test]
+    fn test_project_summary_matches_seeded_rows() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        for file_path in ["src/lib.rs", "src/app.py", "README.md"] {
+            storage
+                .conn()
+                .execute(
+                    "INSERT INTO indexed_files (file_path, project_id, file_hash, last_indexed)
+                     VALUES (?1, 'proj', 'h', 0)",
+                    params![file_path],
+                )
+                .unwrap();
+        }
+        for (name, file_path, language, complexity) in [
+            ("a", "src/lib.rs", "rust", 12),
+            ("b", "src/lib.rs", "rust", 3),
+            ("c", "src/lib.rs", "rust", 15),
+            ("d", "src/app.py", "python", 2),
+        ] {
+            storage
+                .conn()
+                .execute(
+                    "INSERT INTO intel_nodes
+                        (project_id, file_path, node_id, symbol_name, qualified_name, language,
+                         node_type, complexity, content_hash, created_at, updated_at)
+                     VALUES ('proj', ?1, ?2, ?2, ?2, ?3, 'function', ?4, ?2, 0, 0)",
+                    params![file_path, name, language, complexity],
+                )
+                .unwrap();
+        }
+        // Six outgoing edges make `a` a hotspot; a <-> b is a cycle
+        for (from, to, edge_type) in [
+            ("a", "b", "call"),
+            ("a", "b", "import"),
+            ("a", "c", "call"),
+            ("a", "c", "import"),
+            ("a", "d", "call"),
+            ("a", "d", "import"),
+            ("b", "a", "call"),
+        ] {
+            storage
+                .conn()
+                .execute(
+                    "INSERT INTO intel_edges (caller_id, callee_id, edge_type) VALUES (?1, ?2, ?3)",
+                    params![
+                        node_id(&storage, "proj", from),
+                        node_id(&storage, "proj", to),
+                        edge_type
+                    ],
+                )
+                .unwrap();
+        }
+        save_unresolved_imports(
+            &storage,
+            "proj",
+            [("left_pad", "src/app.py"), ("serde_yaml", "src/lib.rs")],
+        )
+        .unwrap();
+
+        // Another project's rows stay out of the summary
+        insert_node(&storage, "other", "x", 30);
+        insert_node(&storage, "other", "y", 1);
+        insert_edge(&storage, "other", "x", "y");
+        insert_edge(&storage, "other", "y", "x");
+        save_unresolved_imports(&storage, "other", [("missing", "src/lib.rs")]).unwrap();
+
+        let analytics = Analytics::new(storage);
+        let summary = analytics.project_summary("proj").unwrap();
+
+        assert_eq!(summary.project_id, "proj");
+        assert_eq!(summary.file_count, 3);
+        assert_eq!(summary.node_count, 4);
+        assert_eq!(summary.edge_count, 7);
+        assert_eq!(
+            summary.languages,
+            [
+                LanguageCount {
+                    language: "rust".to_string(),
+                    node_count: 3,
+                    file_count: 1,
+                },
+                LanguageCount {
+                    language: "python".to_string(),
+                    node_count: 1,
+                    file_count: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            summary.languages.iter().map(|l| l.node_count).sum::<i64>(),
+            summary.node_count
+        );
+        assert_eq!(summary.hotspots.len(), 1);
+        assert_eq!(summary.hotspots[0].symbol_name, "a");
+        assert_eq!(summary.hotspots[0].complexity, 12);
+        assert_eq!(summary.hotspots[0].fan_out, 6);
+        assert_eq!(summary.cycle_count, 1);
+        assert_eq!(
+            summary.cycle_count,
+            analytics.find_cycles("proj").unwrap().len()
+        );
+        assert_eq!(summary.unresolved_import_count, 2);
+
+        let empty = analytics.project_summary("missing").unwrap();
+        assert_eq!(empty.node_count, 0);
+        assert!(empty.languages.is_empty() && empty.hotspots.is_empty());
+    }
 }
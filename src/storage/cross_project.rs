@@ -3,8 +3,11 @@
 // This module provides cross-project resolution capabilities, enabling
 // symbols to be resolved across project boundaries with lazy PDG loading.
 
-use crate::graph::pdg::{EdgeId, NodeId, ProgramDependenceGraph};
-use crate::storage::global_symbols::{GlobalSymbol, GlobalSymbolId};
+use crate::graph::cross_project::CrossProjectPDG;
+use crate::graph::extraction::normalize_symbol;
+use crate::graph::pdg::{EdgeId, EdgeType, NodeId, NodeType, ProgramDependenceGraph};
+use crate::parse::traits::SignatureInfo;
+use crate::storage::global_symbols::{GlobalSymbol, GlobalSymbolId, GlobalSymbolTable};
 use crate::storage::pdg_store::{load_pdg, PdgStoreError};
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
@@ -144,16 +147,23 @@ impl CrossProjectResolver {
             }
         }
 
-        // Merge all PDGs - use the root PDG directly
-        let mut merged_pdg = root_pdg;
-        for (project_id, pdg) in &self.pdg_cache {
-            if project_id != root_project_id {
-                Self::merge_pdgs(&mut merged_pdg, pdg)
-                    .map_err(|e| ResolutionError::MergeError(format!("{:?}", e)))?;
-            }
-        }
-
-        Ok(merged_pdg)
+        // Merge all external PDGs into the root PDG as one transaction; the
+        // cache never holds more than max_depth projects. Sorted so a symbol
+        // shared by several projects always ends up with the same owner.
+        let mut externals: Vec<(&str, &ProgramDependenceGraph)> = self
+            .pdg_cache
+            .iter()
+            .filter(|(project_id, _)| project_id.as_str() != root_project_id)
+            .map(|(project_id, pdg)| (project_id.as_str(), pdg))
+            .collect();
+        externals.sort_unstable_by_key(|(project_id, _)| *project_id);
+        let mut merged =
+            CrossProjectPDG::with_max_depth(root_project_id.to_string(), root_pdg, self.max_depth);
+        merged
+            .merge_all(&externals)
+            .map_err(|e| ResolutionError::MergeError(e.to_string()))?;
+
+        Ok(merged.merged_pdg)
     }
 
     /// Collect all external projects referenced by a PDG
@@ -188,42 +198,6 @@ impl CrossProjectResolver {
         Ok(())
     }
 
-    /// Merge external PDG into root PDG
-    fn merge_pdgs(
-        root_pdg: &mut ProgramDependenceGraph,
-        external_pdg: &ProgramDependenceGraph,
-    ) -> Result<(), MergeError> {
-        // Track ID mappings to avoid conflicts
-        let mut node_map = HashMap::new();
-
-        // Add all nodes from external PDG
-        for node_id in external_pdg.node_indices() {
-            if let Some(node) = external_pdg.get_node(node_id) {
-                // Add node to root PDG - returns the new NodeId
-                let new_id = root_pdg.add_node(node.clone());
-
-                // Store mapping
-                node_map.insert(node_id, new_id);
-            }
-        }
-
-        // Add all edges from external PDG
-        for edge_id in external_pdg.edge_indices() {
-            if let Some(edge) = external_pdg.get_edge(edge_id) {
-                // Get edge endpoints
-                if let Some((source, target)) = external_pdg.edge_endpoints(edge_id) {
-                    let new_source = node_map.get(&source).copied().unwrap_or(source);
-                    let new_target = node_map.get(&target).copied().unwrap_or(target);
-
-                    // Add edge to root PDG (returns Option<EdgeId>)
-                    root_pdg.add_edge(new_source, new_target, edge.clone());
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     /// Track which external symbols are used by a project
     pub fn track_external_usage(
         &self,
@@ -325,6 +299,130 @@ impl CrossProjectResolver {
     }
 }
 
+/// Add call edges for qualified call targets found in the global symbol table
+///
+/// Post-pass after `resolve_cross_file_call_edges_for_files`: a call such as
+/// `native::compute` from one language file may target a symbol indexed from
+/// another file. Only calls with at least two segments are tried, the global
+/// symbol's name must equal the call's qualified name (after
+/// `normalize_symbol`), and the symbol must belong to `project_id`, the
+/// project of `pdg`, and map to exactly one PDG node in a different file than
+/// the caller. Calls that already have a call edge to a node with the same
+/// bare name are left alone.
+///
+/// Returns the number of call edges added.
+pub fn resolve_call_edges_with_symbol_table(
+    pdg: &mut ProgramDependenceGraph,
+    project_id: &str,
+    signatures: &[(String, SignatureInfo)],
+    symbol_table: &GlobalSymbolTable<'_>,
+) -> Result<usize, ResolutionError> {
+    let mut call_targets: HashMap<NodeId, HashSet<NodeId>> = HashMap::new();
+    for edge_id in pdg.edge_indices() {
+        let is_call = pdg
+            .get_edge(edge_id)
+            .is_some_and(|edge| edge.edge_type == EdgeType::Call);
+        if let (true, Some((from, to))) = (is_call, pdg.edge_endpoints(edge_id)) {
+            call_targets.entry(from).or_default().insert(to);
+        }
+    }
+
+    let mut lookups: HashMap<String, Vec<GlobalSymbol>> = HashMap::new();
+    let mut new_edges = Vec::new();
+
+    for (file_path, sig) in signatures {
+        let Some(caller) = pdg.find_by_id(&format!("{}:{}", file_path, sig.qualified_name)) else {
+            continue;
+        };
+
+        for call_target in &sig.calls {
+            let normalized = normalize_symbol(call_target);
+            let Some((_, last)) = normalized.rsplit_once('.') else {
+                continue;
+            };
+
+            let existing = call_targets.entry(caller).or_default();
+            if existing
+                .iter()
+                .any(|id| pdg.get_node(*id).is_some_and(|node| node.name == last))
+            {
+                continue;
+            }
+
+            if !lookups.contains_key(&normalized) {
+                let mut symbols = symbol_table.resolve_by_name(call_target)?;
+                if normalized != *call_target {
+                    symbols.extend(symbol_table.resolve_by_name(&normalized)?);
+                }
+                symbols.retain(|symbol| normalize_symbol(&symbol.symbol_name) == normalized);
+                lookups.insert(normalized.clone(), symbols);
+            }
+
+            let targets: HashSet<NodeId> = lookups[&normalized]
+                .iter()
+                .filter(|symbol| symbol.file_path != *file_path)
+                .filter_map(|symbol| symbol_node(pdg, project_id, symbol, last))
+                .collect();
+
+            // Ambiguous matches are skipped rather than guessed
+            let mut targets = targets.into_iter();
+            let (Some(target), None) = (targets.next(), targets.next()) else {
+                continue;
+            };
+            if target != caller && existing.insert(target) {
+                new_edges.push((caller, target, call_target.clone()));
+            }
+        }
+    }
+
+    let added = new_edges.len();
+    if added > 0 {
+        tracing::debug!("Global symbol call resolution: added {} new edges", added);
+        pdg.add_call_edges_with_targets(new_edges);
+    }
+    Ok(added)
+}
+
+/// Locate the PDG node for a global symbol by file and byte range, falling
+/// back to the only node in that file named `name`
+///
+/// `pdg` holds only the nodes of `project_id`; symbols of other projects
+/// never map to it, even when their file paths coincide.
+fn symbol_node(
+    pdg: &ProgramDependenceGraph,
+    project_id: &str,
+    symbol: &GlobalSymbol,
+    name: &str,
+) -> Option<NodeId> {
+    if symbol.project_id != project_id {
+        return None;
+    }
+
+    let candidates: Vec<NodeId> = pdg
+        .nodes_in_file(&symbol.file_path)
+        .into_iter()
+        .filter(|id| {
+            pdg.get_node(*id)
+                .is_some_and(|node| node.node_type != NodeType::External)
+        })
+        .collect();
+
+    if let Some(id) = candidates.iter().copied().find(|id| {
+        pdg.get_node(*id)
+            .is_some_and(|node| node.byte_range == symbol.byte_range)
+    }) {
+        return Some(id);
+    }
+
+    let mut named = candidates
+        .into_iter()
+        .filter(|id| pdg.get_node(*id).is_some_and(|node| node.name == name));
+    match (named.next(), named.next()) {
+        (Some(id), None) => Some(id),
+        _ => None,
+    }
+}
+
 /// Resolved symbol with context
 #[derive(Debug, Clone)]
 pub struct ResolvedSymbol {
@@ -456,6 +554,95 @@ mod tests {
         assert!(!results[0].is_local); // Not local to proj_b
     }
 
+    #[test]
+    fn test_call_resolves_through_global_symbol_table() {
+        use crate::graph::pdg::Node;
+        use crate::parse::traits::Visibility;
+
+        let resolver = create_test_resolver();
+        let symbol_table = crate::storage::GlobalSymbolTable::new(resolver.storage());
+
+        let node = |file: &str, qname: &str, name: &str, byte_range| Node {
+            id: format!("{}:{}", file, qname),
+            node_type: NodeType::Function,
+            name: name.to_string(),
+            file_path: Arc::from(file),
+            byte_range,
+            complexity: 1,
+            language: "python".to_string(),
+        };
+        let mut pdg = ProgramDependenceGraph::new();
+        let caller = pdg.add_node(node("app/main.py", "main", "main", (0, 40)));
+        let native = pdg.add_node(node("lib/native.c", "compute", "compute", (10, 80)));
+
+        symbol_table
+            .upsert_symbol(&GlobalSymbol {
+                symbol_id: GlobalSymbolTable::generate_symbol_id("proj_a", "native.compute", None),
+                project_id: "proj_a".to_string(),
+                symbol_name: "native.compute".to_string(),
+                symbol_type: crate::storage::global_symbols::SymbolType::Function,
+                signature: None,
+                file_path: "lib/native.c".to_string(),
+                byte_range: (10, 80),
+                complexity: 1,
+                is_public: true,
+            })
+            .unwrap();
+
+        let main_sig = SignatureInfo {
+            name: "main".to_string(),
+            qualified_name: "main".to_string(),
+            parameters: vec![],
+            return_type: None,
+            visibility: Visibility::Public,
+            is_async: false,
+            is_method: false,
+            docstring: None,
+            // Bare and unknown targets must not be guessed
+            calls: vec![
+                "native::compute".to_string(),
+                "compute".to_string(),
+                "other.compute".to_string(),
+            ],
+            imports: vec![],
+            byte_range: (0, 40),
+            cyclomatic_complexity: 0,
+            base_types: Vec::new(),
+        };
+        let signatures = vec![("app/main.py".to_string(), main_sig)];
+
+        // The same symbol in another project never maps to this project's PDG
+        symbol_table
+            .upsert_symbol(&GlobalSymbol {
+                symbol_id: GlobalSymbolTable::generate_symbol_id("proj_b", "native.compute", None),
+                project_id: "proj_b".to_string(),
+                symbol_name: "native.compute".to_string(),
+                symbol_type: crate::storage::global_symbols::SymbolType::Function,
+                signature: None,
+                file_path: "lib/native.c".to_string(),
+                byte_range: (10, 80),
+                complexity: 1,
+                is_public: true,
+            })
+            .unwrap();
+        let added =
+            resolve_call_edges_with_symbol_table(&mut pdg, "proj_c", &signatures, &symbol_table)
+                .unwrap();
+        assert_eq!(added, 0);
+
+        let added =
+            resolve_call_edges_with_symbol_table(&mut pdg, "proj_a", &signatures, &symbol_table)
+                .unwrap();
+        assert_eq!(added, 1);
+        assert_eq!(pdg.neighbors(caller), vec![native]);
+
+        // Re-running does not duplicate the edge
+        let added =
+            resolve_call_edges_with_symbol_table(&mut pdg, "proj_a", &signatures, &symbol_table)
+                .unwrap();
+        assert_eq!(added, 0);
+    }
+
     #[test]
     fn test_load_external_pdg() {
         let temp_file = NamedTempFile::new().unwrap();
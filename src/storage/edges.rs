@@ -66,6 +66,22 @@ pub struct EdgeMetadata {
     pub variable_name: Option<String>,
     /// Confidence score for inferred edges (inheritance, type deps)
     pub confidence: Option<f32>,
+    /// Call expression that produced the edge (for call edges)
+    #[serde(default)]
+    pub call_target: Option<String>,
+}
+
+fn edge_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<EdgeRecord> {
+    let edge_type_str: String = row.get(2)?;
+    let metadata_json: Option<String> = row.get(3)?;
+    let metadata = metadata_json.and_then(|json| serde_json::from_str(&json).ok());
+
+    Ok(EdgeRecord {
+        caller_id: row.get(0)?,
+        callee_id: row.get(1)?,
+        edge_type: EdgeType::from_str_name(&edge_type_str).unwrap_or(EdgeType::Call),
+        metadata,
+    })
 }
 
 /// Edge store for CRUD operations
@@ -127,18 +143,7 @@ impl<'a> EdgeStore<'a> {
         )?;
 
         let edges = stmt
-            .query_map(params![caller_id], |row| {
-                let edge_type_str: String = row.get(2)?;
-                let metadata_json: Option<String> = row.get(3)?;
-                let metadata = metadata_json.and_then(|json| serde_json::from_str(&json).ok());
-
-                Ok(EdgeRecord {
-                    caller_id: row.get(0)?,
-                    callee_id: row.get(1)?,
-                    edge_type: EdgeType::from_str_name(&edge_type_str).unwrap_or(EdgeType::Call),
-                    metadata,
-                })
-            })?
+            .query_map(params![caller_id], edge_from_row)?
             .collect::<SqliteResult<Vec<_>>>()?;
 
         Ok(edges)
@@ -152,18 +157,49 @@ impl<'a> EdgeStore<'a> {
         )?;
 
         let edges = stmt
-            .query_map(params![callee_id], |row| {
-                let edge_type_str: String = row.get(2)?;
-                let metadata_json: Option<String> = row.get(3)?;
-                let metadata = metadata_json.and_then(|json| serde_json::from_str(&json).ok());
-
-                Ok(EdgeRecord {
-                    caller_id: row.get(0)?,
-                    callee_id: row.get(1)?,
-                    edge_type: EdgeType::from_str_name(&edge_type_str).unwrap_or(EdgeType::Call),
-                    metadata,
-                })
-            })?
+            .query_map(params![callee_id], edge_from_row)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(edges)
+    }
+
+    /// Get outgoing edges of a node, optionally only those of one type
+    pub fn edges_from(
+        &self,
+        node_id: i64,
+        edge_type: Option<EdgeType>,
+    ) -> SqliteResult<Vec<EdgeRecord>> {
+        let mut stmt = self.storage.conn().prepare(
+            "SELECT caller_id, callee_id, edge_type, metadata
+             FROM intel_edges WHERE caller_id = ?1 AND (?2 IS NULL OR edge_type = ?2)",
+        )?;
+
+        let edges = stmt
+            .query_map(
+                params![node_id, edge_type.as_ref().map(EdgeType::as_str)],
+                edge_from_row,
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(edges)
+    }
+
+    /// Get incoming edges of a node, optionally only those of one type
+    pub fn edges_to(
+        &self,
+        node_id: i64,
+        edge_type: Option<EdgeType>,
+    ) -> SqliteResult<Vec<EdgeRecord>> {
+        let mut stmt = self.storage.conn().prepare(
+            "SELECT caller_id, callee_id, edge_type, metadata
+             FROM intel_edges WHERE callee_id = ?1 AND (?2 IS NULL OR edge_type = ?2)",
+        )?;
+
+        let edges = stmt
+            .query_map(
+                params![node_id, edge_type.as_ref().map(EdgeType::as_str)],
+                edge_from_row,
+            )?
             .collect::<SqliteResult<Vec<_>>>()?;
 
         Ok(edges)
@@ -177,18 +213,7 @@ impl<'a> EdgeStore<'a> {
         )?;
 
         let edges = stmt
-            .query_map(params![edge_type.as_str()], |row| {
-                let edge_type_str: String = row.get(2)?;
-                let metadata_json: Option<String> = row.get(3)?;
-                let metadata = metadata_json.and_then(|json| serde_json::from_str(&json).ok());
-
-                Ok(EdgeRecord {
-                    caller_id: row.get(0)?,
-                    callee_id: row.get(1)?,
-                    edge_type: EdgeType::from_str_name(&edge_type_str).unwrap_or(EdgeType::Call),
-                    metadata,
-                })
-            })?
+            .query_map(params![edge_type.as_str()], edge_from_row)?
             .collect::<SqliteResult<Vec<_>>>()?;
 
         Ok(edges)
@@ -255,6 +280,7 @@ mod tests {
                 call_count: Some(5),
                 variable_name: None,
                 confidence: None,
+                call_target: None,
             }),
         };
 
@@ -264,4 +290,89 @@ mod tests {
         assert_eq!(edges.len(), 1);
         assert_eq!(edges[0].callee_id, id2);
     }
+
+    #[test]
+    fn test_edges_from_and_to_filter_by_type() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+
+        let mut node_store = crate::storage::nodes::NodeStore::new(&mut storage);
+        let ids: Vec<i64> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                node_store
+                    .insert(&crate::storage::nodes::NodeRecord {
+                        id: None,
+                        project_id: "p1".to_string(),
+                        file_path: format!("{}.py", name),
+                        node_id: format!("{}.py:{}", name, name),
+                        symbol_name: name.to_string(),
+                        qualified_name: name.to_string(),
+                        language: "python".to_string(),
+                        node_type: crate::storage::nodes::NodeType::Function,
+                        signature: None,
+                        complexity: None,
+                        content_hash: name.to_string(),
+                        embedding: None,
+                        byte_range_start: Some(0),
+                        byte_range_end: Some(100),
+                        embedding_format: None,
+                    })
+                    .unwrap()
+            })
+            .collect();
+        let (a, b, c) = (ids[0], ids[1], ids[2]);
+
+        let edge = |caller_id, callee_id, edge_type| EdgeRecord {
+            caller_id,
+            callee_id,
+            edge_type,
+            metadata: None,
+        };
+        let mut store = EdgeStore::new(&mut storage);
+        store
+            .batch_insert(&[
+                edge(a, b, EdgeType::Call),
+                edge(a, b, EdgeType::Import),
+                edge(a, c, EdgeType::Import),
+                edge(b, c, EdgeType::Call),
+                edge(c, a, EdgeType::Containment),
+            ])
+            .unwrap();
+
+        let pairs = |edges: Vec<EdgeRecord>| {
+            let mut pairs: Vec<(i64, i64, &'static str)> = edges
+                .iter()
+                .map(|e| (e.caller_id, e.callee_id, e.edge_type.as_str()))
+                .collect();
+            pairs.sort();
+            pairs
+        };
+
+        assert_eq!(
+            pairs(store.edges_from(a, Some(EdgeType::Import)).unwrap()),
+            [(a, b, "import"), (a, c, "import")]
+        );
+        assert_eq!(
+            pairs(store.edges_from(a, None).unwrap()),
+            [(a, b, "call"), (a, b, "import"), (a, c, "import")]
+        );
+        assert!(store
+            .edges_from(b, Some(EdgeType::Import))
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            pairs(store.edges_to(c, Some(EdgeType::Call)).unwrap()),
+            [(b, c, "call")]
+        );
+        assert_eq!(
+            pairs(store.edges_to(c, None).unwrap()),
+            [(a, c, "import"), (b, c, "call")]
+        );
+        assert_eq!(
+            pairs(store.edges_to(a, Some(EdgeType::Containment)).unwrap()),
+            [(c, a, "containment")]
+        );
+    }
 }
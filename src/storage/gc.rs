@@ -0,0 +1,264 @@
+// Storage garbage collection
+//
+// Incremental runs replace rows file by file, and projects come and go, so
+// over time the database accumulates rows nothing refers to any more: nodes
+// (with their embeddings) of files that are no longer indexed, edges whose
+// endpoints are gone, and global symbols of removed projects. `gc` deletes
+// them database-wide in one transaction.
+
+use crate::storage::schema::Storage;
+use rusqlite::{Result as SqliteResult, Transaction};
+use serde::{Deserialize, Serialize};
+
+/// Projects that still have data in this database
+const LIVE_PROJECTS: &str = "SELECT project_id FROM intel_nodes
+     UNION SELECT project_id FROM indexed_files
+     UNION SELECT unique_project_id FROM project_metadata";
+
+/// Rows removed by [`gc`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcSummary {
+    /// Node rows, and the embeddings stored on them, whose file is no longer
+    /// tracked in `indexed_files`
+    pub orphan_nodes_removed: usize,
+    /// Edge rows whose caller or callee node no longer exists
+    pub dangling_edges_removed: usize,
+    /// Global symbols whose project has no data left in the database
+    pub stale_global_symbols_removed: usize,
+    /// External references whose source or target symbol no longer exists
+    pub dangling_external_refs_removed: usize,
+}
+
+impl GcSummary {
+    /// Total number of rows removed
+    pub fn total(&self) -> usize {
+        self.orphan_nodes_removed
+            + self.dangling_edges_removed
+            + self.stale_global_symbols_removed
+            + self.dangling_external_refs_removed
+    }
+}
+
+/// Delete orphaned and dangling rows across all projects in the database
+///
+/// Orphan nodes are only removed for projects that track indexed files at
+/// all, like [`verify`](crate::storage::pdg_store::verify) reports them.
+/// Live data is never touched, so it is safe to run at any time; running it
+/// twice in a row removes nothing the second time.
+pub fn gc(storage: &mut Storage) -> SqliteResult<GcSummary> {
+    storage.retry_on_busy(|storage| {
+        let tx = storage.conn_mut().transaction()?;
+        let summary = collect(&tx)?;
+        tx.commit()?;
+        Ok(summary)
+    })
+}
+
+/// Count what [`gc`] would remove without removing it
+pub fn gc_dry_run(storage: &mut Storage) -> SqliteResult<GcSummary> {
+    // Run the same deletes and roll them back, so the counts include rows
+    // that only become garbage once earlier steps have run
+    let tx = storage.conn_mut().transaction()?;
+    let summary = collect(&tx)?;
+    tx.rollback()?;
+    Ok(summary)
+}
+
+fn collect(tx: &Transaction<'_>) -> SqliteResult<GcSummary> {
+    // Nodes first: edges and symbols they leave behind are collected below
+    let orphan_nodes_removed = tx.execute(
+        "DELETE FROM intel_nodes
+         WHERE project_id IN (SELECT DISTINCT project_id FROM indexed_files)
+           AND NOT EXISTS (
+               SELECT 1 FROM indexed_files f
+               WHERE f.project_id = intel_nodes.project_id
+                 AND f.file_path = intel_nodes.file_path
+           )",
+        [],
+    )?;
+    if orphan_nodes_removed > 0 {
+        // Keep the checksums load_pdg verifies in step with the rows
+        let projects: Vec<String> = tx
+            .prepare("SELECT project_id FROM pdg_checksums")?
+            .query_map([], |row| row.get(0))?
+            .collect::<SqliteResult<_>>()?;
+        for project_id in projects {
+            crate::storage::pdg_store::refresh_checksum(tx, &project_id)?;
+        }
+    }
+
+    let dangling_edges_removed = tx.execute(
+        "DELETE FROM intel_edges
+         WHERE caller_id NOT IN (SELECT id FROM intel_nodes)
+            OR callee_id NOT IN (SELECT id FROM intel_nodes)",
+        [],
+    )?;
+
+    let stale_global_symbols_removed = tx.execute(
+        &format!(
+            "DELETE FROM global_symbols WHERE project_id NOT IN ({})",
+            LIVE_PROJECTS
+        ),
+        [],
+    )?;
+
+    let dangling_external_refs_removed = tx.execute(
+        "DELETE FROM external_refs
+         WHERE source_symbol_id NOT IN (SELECT symbol_id FROM global_symbols)
+            OR target_symbol_id NOT IN (SELECT symbol_id FROM global_symbols)",
+        [],
+    )?;
+
+    Ok(GcSummary {
+        orphan_nodes_removed,
+        dangling_edges_removed,
+        stale_global_symbols_removed,
+        dangling_external_refs_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+    use tempfile::NamedTempFile;
+
+    fn insert_node(storage: &Storage, project_id: &str, file_path: &str, node_id: &str) -> i64 {
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO intel_nodes (project_id, file_path, node_id, symbol_name,
+                 qualified_name, node_type, content_hash, embedding, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?3, ?3, 'function', 'hash', X'00010203', 0, 0)",
+                params![project_id, file_path, node_id],
+            )
+            .unwrap();
+        storage.conn().last_insert_rowid()
+    }
+
+    fn insert_edge(storage: &Storage, caller_id: i64, callee_id: i64) {
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO intel_edges (caller_id, callee_id, edge_type) VALUES (?1, ?2, 'call')",
+                params![caller_id, callee_id],
+            )
+            .unwrap();
+    }
+
+    fn insert_symbol(storage: &Storage, symbol_id: &str, project_id: &str) {
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO global_symbols (symbol_id, project_id, symbol_name, symbol_type,
+                 signature, file_path)
+                 VALUES (?1, ?2, ?1, 'function', ?1, 'lib.rs')",
+                params![symbol_id, project_id],
+            )
+            .unwrap();
+    }
+
+    fn insert_ref(storage: &Storage, ref_id: &str, source: &str, target: &str) {
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO external_refs (ref_id, source_project_id, source_symbol_id,
+                 target_project_id, target_symbol_id, ref_type)
+                 VALUES (?1, 'live', ?2, 'live', ?3, 'call')",
+                params![ref_id, source, target],
+            )
+            .unwrap();
+    }
+
+    fn count(storage: &Storage, table: &str) -> i64 {
+        storage
+            .conn()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_gc_removes_only_orphaned_rows() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO indexed_files (file_path, project_id, file_hash, last_indexed)
+                 VALUES ('src/a.rs', 'live', 'h', 0)",
+                [],
+            )
+            .unwrap();
+
+        // Live data: two nodes of an indexed file and the edge between them
+        let a = insert_node(&storage, "live", "src/a.rs", "a");
+        let b = insert_node(&storage, "live", "src/a.rs", "b");
+        insert_edge(&storage, a, b);
+        insert_symbol(&storage, "live_sym", "live");
+        insert_symbol(&storage, "live_sym2", "live");
+        insert_ref(&storage, "live_ref", "live_sym", "live_sym2");
+
+        // Garbage: a node of a deleted file (with its embedding) and the
+        // edge to it, an edge to a node ID that never existed, symbols of a
+        // removed project and a reference to one of them
+        let deleted = insert_node(&storage, "live", "src/deleted.rs", "gone");
+        insert_edge(&storage, a, deleted);
+        insert_edge(&storage, 9_999, b);
+        insert_symbol(&storage, "gone_sym", "removed_project");
+        insert_symbol(&storage, "gone_sym2", "removed_project");
+        insert_ref(&storage, "gone_ref", "live_sym", "gone_sym");
+
+        let preview = gc_dry_run(&mut storage).unwrap();
+        assert_eq!(count(&storage, "intel_nodes"), 3, "dry run removes nothing");
+
+        let summary = gc(&mut storage).unwrap();
+        assert_eq!(preview, summary);
+        assert_eq!(
+            summary,
+            GcSummary {
+                orphan_nodes_removed: 1,
+                dangling_edges_removed: 2,
+                stale_global_symbols_removed: 2,
+                dangling_external_refs_removed: 1,
+            }
+        );
+        assert_eq!(summary.total(), 6);
+
+        let nodes: Vec<String> = storage
+            .conn()
+            .prepare("SELECT node_id FROM intel_nodes ORDER BY node_id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<SqliteResult<_>>()
+            .unwrap();
+        assert_eq!(nodes, ["a", "b"]);
+        let edge: (i64, i64) = storage
+            .conn()
+            .query_row("SELECT caller_id, callee_id FROM intel_edges", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(edge, (a, b));
+        assert_eq!(count(&storage, "global_symbols"), 2);
+        assert_eq!(count(&storage, "external_refs"), 1);
+        assert_eq!(count(&storage, "indexed_files"), 1);
+
+        assert_eq!(gc(&mut storage).unwrap().total(), 0);
+    }
+
+    #[test]
+    fn test_gc_keeps_nodes_of_projects_without_file_records() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let a = insert_node(&storage, "untracked", "src/a.rs", "a");
+        let b = insert_node(&storage, "untracked", "src/b.rs", "b");
+        insert_edge(&storage, a, b);
+
+        assert_eq!(gc(&mut storage).unwrap(), GcSummary::default());
+        assert_eq!(count(&storage, "intel_nodes"), 2);
+        assert_eq!(count(&storage, "intel_edges"), 1);
+    }
+}
@@ -0,0 +1,405 @@
+// Symbol and edge sets of past indexing generations
+//
+// With `indexing.record_generations` enabled, each indexing run records the
+// node and edge sets it produced under the run's generation hash (see
+// `phase::freshness`), keyed by stable node IDs. Two recorded generations can
+// then be diffed into added, removed and modified symbols and added and
+// removed edges. Only the most recent `GENERATIONS_KEPT` generations of a
+// project are retained.
+
+use crate::storage::schema::Storage;
+use rusqlite::{params, OptionalExtension, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Generations retained per project; older ones are pruned on record
+pub const GENERATIONS_KEPT: usize = 10;
+
+/// A symbol as recorded in a generation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationNode {
+    /// Stable node ID
+    pub node_id: String,
+
+    /// Symbol name
+    pub symbol_name: String,
+
+    /// File containing the symbol
+    pub file_path: String,
+
+    /// Node type (e.g. `function`, `class`)
+    pub node_type: String,
+
+    /// Hash of the symbol's content; differs when the symbol was modified
+    pub fingerprint: String,
+}
+
+/// An edge as recorded in a generation, keyed by its endpoints' node IDs
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GenerationEdge {
+    /// Node ID of the source
+    pub caller_id: String,
+
+    /// Node ID of the target
+    pub callee_id: String,
+
+    /// Edge type (e.g. `call`, `import`)
+    pub edge_type: String,
+}
+
+/// Summary of a recorded generation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationInfo {
+    /// Generation hash
+    pub generation: String,
+
+    /// Unix timestamp of the indexing run that recorded it
+    pub recorded_at: i64,
+
+    /// Number of symbols recorded
+    pub node_count: usize,
+
+    /// Number of edges recorded
+    pub edge_count: usize,
+}
+
+/// A symbol present in both generations whose content changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModifiedNode {
+    /// The symbol in the older generation
+    pub before: GenerationNode,
+
+    /// The symbol in the newer generation
+    pub after: GenerationNode,
+}
+
+/// Differences between two recorded generations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationDiff {
+    /// Generation diffed from
+    pub from: String,
+
+    /// Generation diffed to
+    pub to: String,
+
+    /// Symbols only in `to`
+    pub added_nodes: Vec<GenerationNode>,
+
+    /// Symbols only in `from`
+    pub removed_nodes: Vec<GenerationNode>,
+
+    /// Symbols in both whose fingerprint changed
+    pub modified_nodes: Vec<ModifiedNode>,
+
+    /// Edges only in `to`
+    pub added_edges: Vec<GenerationEdge>,
+
+    /// Edges only in `from`
+    pub removed_edges: Vec<GenerationEdge>,
+}
+
+impl GenerationDiff {
+    /// Whether the two generations have the same symbols and edges
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.modified_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// Record the symbols and edges of `generation`
+///
+/// Re-recording an existing generation replaces its rows and makes it the
+/// most recent one. Generations beyond [`GENERATIONS_KEPT`] are pruned,
+/// oldest first.
+pub fn record_generation(
+    storage: &Storage,
+    project_id: &str,
+    generation: &str,
+    nodes: &[GenerationNode],
+    edges: &[GenerationEdge],
+) -> SqliteResult<()> {
+    let tx = storage.conn().unchecked_transaction()?;
+    delete_generation_rows(&tx, project_id, generation)?;
+
+    let recorded_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    tx.execute(
+        "INSERT INTO index_generations (project_id, generation, recorded_at, node_count, edge_count)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            project_id,
+            generation,
+            recorded_at,
+            nodes.len() as i64,
+            edges.len() as i64
+        ],
+    )?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO generation_nodes
+             (project_id, generation, node_id, symbol_name, file_path, node_type, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for node in nodes {
+            insert.execute(params![
+                project_id,
+                generation,
+                node.node_id,
+                node.symbol_name,
+                node.file_path,
+                node.node_type,
+                node.fingerprint,
+            ])?;
+        }
+        let mut insert = tx.prepare(
+            "INSERT OR IGNORE INTO generation_edges
+             (project_id, generation, caller_id, callee_id, edge_type)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for edge in edges {
+            insert.execute(params![
+                project_id,
+                generation,
+                edge.caller_id,
+                edge.callee_id,
+                edge.edge_type,
+            ])?;
+        }
+    }
+
+    let stale: Vec<String> = {
+        let mut stmt = tx.prepare(
+            "SELECT generation FROM index_generations WHERE project_id = ?1
+             ORDER BY id DESC LIMIT -1 OFFSET ?2",
+        )?;
+        let rows = stmt.query_map(params![project_id, GENERATIONS_KEPT as i64], |row| {
+            row.get(0)
+        })?;
+        rows.collect::<SqliteResult<_>>()?
+    };
+    for old in &stale {
+        delete_generation_rows(&tx, project_id, old)?;
+    }
+    tx.commit()
+}
+
+fn delete_generation_rows(
+    tx: &rusqlite::Transaction<'_>,
+    project_id: &str,
+    generation: &str,
+) -> SqliteResult<()> {
+    for table in ["index_generations", "generation_nodes", "generation_edges"] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE project_id = ?1 AND generation = ?2",
+                table
+            ),
+            params![project_id, generation],
+        )?;
+    }
+    Ok(())
+}
+
+/// List the recorded generations of a project, oldest first
+pub fn list_generations(storage: &Storage, project_id: &str) -> SqliteResult<Vec<GenerationInfo>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT generation, recorded_at, node_count, edge_count FROM index_generations
+         WHERE project_id = ?1 ORDER BY id",
+    )?;
+    let generations = stmt
+        .query_map(params![project_id], |row| {
+            Ok(GenerationInfo {
+                generation: row.get(0)?,
+                recorded_at: row.get(1)?,
+                node_count: row.get::<_, i64>(2)? as usize,
+                edge_count: row.get::<_, i64>(3)? as usize,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(generations)
+}
+
+/// Whether `generation` is recorded for a project
+pub fn has_generation(storage: &Storage, project_id: &str, generation: &str) -> SqliteResult<bool> {
+    storage
+        .conn()
+        .query_row(
+            "SELECT 1 FROM index_generations WHERE project_id = ?1 AND generation = ?2",
+            params![project_id, generation],
+            |_| Ok(()),
+        )
+        .optional()
+        .map(|found| found.is_some())
+}
+
+fn load_nodes(
+    storage: &Storage,
+    project_id: &str,
+    generation: &str,
+) -> SqliteResult<BTreeMap<String, GenerationNode>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT node_id, symbol_name, file_path, node_type, fingerprint FROM generation_nodes
+         WHERE project_id = ?1 AND generation = ?2",
+    )?;
+    let nodes = stmt
+        .query_map(params![project_id, generation], |row| {
+            Ok(GenerationNode {
+                node_id: row.get(0)?,
+                symbol_name: row.get(1)?,
+                file_path: row.get(2)?,
+                node_type: row.get(3)?,
+                fingerprint: row.get(4)?,
+            })
+        })?
+        .map(|node| node.map(|node| (node.node_id.clone(), node)))
+        .collect::<SqliteResult<_>>()?;
+    Ok(nodes)
+}
+
+fn load_edges(
+    storage: &Storage,
+    project_id: &str,
+    generation: &str,
+) -> SqliteResult<BTreeSet<GenerationEdge>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT caller_id, callee_id, edge_type FROM generation_edges
+         WHERE project_id = ?1 AND generation = ?2",
+    )?;
+    let edges = stmt
+        .query_map(params![project_id, generation], |row| {
+            Ok(GenerationEdge {
+                caller_id: row.get(0)?,
+                callee_id: row.get(1)?,
+                edge_type: row.get(2)?,
+            })
+        })?
+        .collect::<SqliteResult<_>>()?;
+    Ok(edges)
+}
+
+/// Diff the symbol and edge sets of two recorded generations
+///
+/// Results are sorted by node ID (edges by caller, callee and type). A
+/// generation that was never recorded has empty sets, so check
+/// [`has_generation`] first to tell the two apart.
+pub fn diff_generations(
+    storage: &Storage,
+    project_id: &str,
+    from: &str,
+    to: &str,
+) -> SqliteResult<GenerationDiff> {
+    let old_nodes = load_nodes(storage, project_id, from)?;
+    let mut new_nodes = load_nodes(storage, project_id, to)?;
+    let old_edges = load_edges(storage, project_id, from)?;
+    let new_edges = load_edges(storage, project_id, to)?;
+
+    let mut diff = GenerationDiff {
+        from: from.to_string(),
+        to: to.to_string(),
+        ..Default::default()
+    };
+    for (node_id, before) in old_nodes {
+        match new_nodes.remove(&node_id) {
+            Some(after) if after.fingerprint != before.fingerprint => {
+                diff.modified_nodes.push(ModifiedNode { before, after });
+            }
+            Some(_) => {}
+            None => diff.removed_nodes.push(before),
+        }
+    }
+    diff.added_nodes = new_nodes.into_values().collect();
+    diff.added_edges = new_edges.difference(&old_edges).cloned().collect();
+    diff.removed_edges = old_edges.difference(&new_edges).cloned().collect();
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn node(id: &str, fingerprint: &str) -> GenerationNode {
+        GenerationNode {
+            node_id: id.to_string(),
+            symbol_name: id.to_string(),
+            file_path: "lib.py".to_string(),
+            node_type: "function".to_string(),
+            fingerprint: fingerprint.to_string(),
+        }
+    }
+
+    fn edge(caller: &str, callee: &str) -> GenerationEdge {
+        GenerationEdge {
+            caller_id: caller.to_string(),
+            callee_id: callee.to_string(),
+            edge_type: "call".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_generations_reports_node_and_edge_changes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+
+        record_generation(
+            &storage,
+            "proj",
+            "gen-a",
+            &[node("a", "1"), node("b", "1"), node("c", "1")],
+            &[edge("a", "b")],
+        )
+        .unwrap();
+        record_generation(
+            &storage,
+            "proj",
+            "gen-b",
+            &[node("a", "1"), node("b", "2"), node("d", "1")],
+            &[edge("a", "d")],
+        )
+        .unwrap();
+
+        let diff = diff_generations(&storage, "proj", "gen-a", "gen-b").unwrap();
+        assert_eq!(diff.added_nodes, vec![node("d", "1")]);
+        assert_eq!(diff.removed_nodes, vec![node("c", "1")]);
+        assert_eq!(diff.modified_nodes.len(), 1);
+        assert_eq!(diff.modified_nodes[0].after, node("b", "2"));
+        assert_eq!(diff.added_edges, vec![edge("a", "d")]);
+        assert_eq!(diff.removed_edges, vec![edge("a", "b")]);
+        assert!(diff_generations(&storage, "proj", "gen-b", "gen-b")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_record_generation_prunes_oldest() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+
+        for i in 0..GENERATIONS_KEPT + 2 {
+            record_generation(
+                &storage,
+                "proj",
+                &format!("gen-{}", i),
+                &[node("a", "1")],
+                &[],
+            )
+            .unwrap();
+        }
+        let generations = list_generations(&storage, "proj").unwrap();
+        assert_eq!(generations.len(), GENERATIONS_KEPT);
+        assert_eq!(generations[0].generation, "gen-2");
+        assert!(!has_generation(&storage, "proj", "gen-0").unwrap());
+        assert!(has_generation(&storage, "proj", "gen-11").unwrap());
+
+        // Re-recording moves a generation to the end
+        record_generation(&storage, "proj", "gen-5", &[], &[]).unwrap();
+        let generations = list_generations(&storage, "proj").unwrap();
+        assert_eq!(generations.last().unwrap().generation, "gen-5");
+        assert_eq!(generations.last().unwrap().node_count, 0);
+    }
+}
@@ -103,6 +103,17 @@ pub struct ExternalRef {
     pub ref_type: RefType,
 }
 
+/// External reference together with the location of its source symbol
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SymbolReference {
+    /// The reference itself
+    pub reference: ExternalRef,
+    /// File containing the referencing symbol (None if it is not registered)
+    pub file_path: Option<String>,
+    /// Byte range of the referencing symbol in that file
+    pub byte_range: Option<(usize, usize)>,
+}
+
 /// Reference type between symbols
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum RefType {
@@ -577,6 +588,66 @@ impl<'a> GlobalSymbolTable<'a> {
         Ok(refs)
     }
 
+    /// Find every reference to a symbol across all registered projects
+    ///
+    /// Results are ordered by referencing project, then reference type, so
+    /// usages from the same project are grouped together.
+    pub fn find_references(
+        &self,
+        symbol_id: &GlobalSymbolId,
+    ) -> Result<Vec<ExternalRef>, GlobalSymbolError> {
+        Ok(self
+            .find_reference_locations(symbol_id)?
+            .into_iter()
+            .map(|found| found.reference)
+            .collect())
+    }
+
+    /// Like [`find_references`](Self::find_references), with the file and byte
+    /// range of each referencing symbol
+    pub fn find_reference_locations(
+        &self,
+        symbol_id: &GlobalSymbolId,
+    ) -> Result<Vec<SymbolReference>, GlobalSymbolError> {
+        let mut stmt = self
+            .db
+            .conn()
+            .prepare(
+                "SELECT r.ref_id, r.source_project_id, r.source_symbol_id,
+                    r.target_project_id, r.target_symbol_id, r.ref_type,
+                    s.file_path, s.byte_range_start, s.byte_range_end
+             FROM external_refs r
+             LEFT JOIN global_symbols s ON s.symbol_id = r.source_symbol_id
+             WHERE r.target_symbol_id = ?1
+             ORDER BY r.source_project_id, r.ref_type, s.file_path, r.ref_id",
+            )
+            .map_err(GlobalSymbolError::from)?;
+
+        let refs = stmt
+            .query_map(params![symbol_id], |row| {
+                let start: Option<i64> = row.get(7)?;
+                let end: Option<i64> = row.get(8)?;
+                Ok(SymbolReference {
+                    reference: ExternalRef {
+                        ref_id: row.get(0)?,
+                        source_project_id: row.get(1)?,
+                        source_symbol_id: row.get(2)?,
+                        target_project_id: row.get(3)?,
+                        target_symbol_id: row.get(4)?,
+                        ref_type: RefType::from_str_name(row.get::<_, String>(5)?.as_str())
+                            .unwrap_or(RefType::Call),
+                    },
+                    file_path: row.get(6)?,
+                    byte_range: start.zip(end).map(|(s, e)| (s as usize, e as usize)),
+                })
+            })
+            .map_err(GlobalSymbolError::from)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(GlobalSymbolError::from)?;
+
+        Ok(refs)
+    }
+
     /// Add project dependency
     pub fn add_project_dep(&self, dep: &ProjectDep) -> Result<(), GlobalSymbolError> {
         self.db.conn().execute(
@@ -926,6 +997,59 @@ mod tests {
         assert_eq!(incoming[0].source_symbol_id, source.symbol_id);
     }
 
+    #[test]
+    fn test_find_references_across_projects() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let db = Storage::open(temp_file.path()).unwrap();
+        let table = GlobalSymbolTable::new(&db);
+
+        let symbol = |project: &str, name: &str, file: &str, start: usize| GlobalSymbol {
+            symbol_id: GlobalSymbolTable::generate_symbol_id(project, name, None),
+            project_id: project.to_string(),
+            symbol_name: name.to_string(),
+            symbol_type: SymbolType::Class,
+            signature: None,
+            file_path: file.to_string(),
+            byte_range: (start, start + 40),
+            complexity: 1,
+            is_public: true,
+        };
+        let base = symbol("lib", "Base", "src/base.rs", 0);
+        let caller = symbol("app", "run", "src/main.rs", 100);
+        let derived = symbol("plugin", "Derived", "src/derived.rs", 200);
+        for s in [&base, &caller, &derived] {
+            table.upsert_symbol(s).unwrap();
+        }
+
+        let reference = |id: &str, source: &GlobalSymbol, ref_type: RefType| ExternalRef {
+            ref_id: id.to_string(),
+            source_project_id: source.project_id.clone(),
+            source_symbol_id: source.symbol_id.clone(),
+            target_project_id: base.project_id.clone(),
+            target_symbol_id: base.symbol_id.clone(),
+            ref_type,
+        };
+        table
+            .add_external_ref(&reference("ref_call", &caller, RefType::Call))
+            .unwrap();
+        table
+            .add_external_ref(&reference("ref_inherit", &derived, RefType::Inheritance))
+            .unwrap();
+
+        let refs = table.find_references(&base.symbol_id).unwrap();
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].source_project_id, "app");
+        assert_eq!(refs[0].ref_type, RefType::Call);
+        assert_eq!(refs[1].source_project_id, "plugin");
+        assert_eq!(refs[1].ref_type, RefType::Inheritance);
+
+        let located = table.find_reference_locations(&base.symbol_id).unwrap();
+        assert_eq!(located[1].file_path.as_deref(), Some("src/derived.rs"));
+        assert_eq!(located[1].byte_range, Some((200, 240)));
+
+        assert!(table.find_references(&caller.symbol_id).unwrap().is_empty());
+    }
+
     #[test]
     fn test_project_deps() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -0,0 +1,59 @@
+// Indexing run durations
+//
+// Every completed indexing pass appends its wall-clock duration. Processes
+// that share the database but never index themselves (leserve) read the runs
+// back to report indexing latency on `/metrics`.
+
+use crate::storage::schema::Storage;
+use rusqlite::{params, Result as SqliteResult};
+use std::time::Duration;
+
+/// Record a completed indexing run of `project_id` that took `duration`
+pub fn record_index_run(
+    storage: &mut Storage,
+    project_id: &str,
+    duration: Duration,
+) -> SqliteResult<()> {
+    let duration_ms = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+    storage.retry_on_busy(|storage| {
+        storage.conn().execute(
+            "INSERT INTO index_runs (project_id, duration_ms, completed_at)
+             VALUES (?1, ?2, strftime('%s', 'now'))",
+            params![project_id, duration_ms],
+        )?;
+        Ok(())
+    })
+}
+
+/// Durations of every recorded indexing run, oldest first
+pub fn index_run_durations(storage: &Storage) -> SqliteResult<Vec<Duration>> {
+    let mut stmt = storage
+        .conn()
+        .prepare("SELECT duration_ms FROM index_runs ORDER BY id")?;
+    let durations = stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .map(|ms| ms.map(|ms| Duration::from_millis(ms.max(0) as u64)))
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(durations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_index_runs_are_recorded_across_projects() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        assert!(index_run_durations(&storage).unwrap().is_empty());
+
+        record_index_run(&mut storage, "proj", Duration::from_millis(1500)).unwrap();
+        record_index_run(&mut storage, "other", Duration::from_millis(20)).unwrap();
+
+        assert_eq!(
+            index_run_durations(&storage).unwrap(),
+            [Duration::from_millis(1500), Duration::from_millis(20)]
+        );
+    }
+}
@@ -0,0 +1,773 @@
+// Versioned schema migrations
+//
+// The storage schema is built by an embedded, ordered list of migrations.
+// Each applied migration is recorded in `schema_migrations` together with a
+// checksum of its SQL, so an edited migration is detected instead of
+// silently diverging, and every migration carries the SQL to roll it back.
+//
+// The early migrations use `IF NOT EXISTS` throughout: databases created
+// before migrations were tracked already hold those tables, and adopt the
+// migrations without changes the first time they are opened.
+
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use thiserror::Error;
+
+/// One step of schema evolution
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Version reached by applying this migration; versions start at 1 and
+    /// increase by one
+    pub version: u32,
+    /// Short description
+    pub name: &'static str,
+    /// SQL applying the migration
+    pub up: &'static str,
+    /// SQL reverting the migration
+    pub down: &'static str,
+}
+
+impl Migration {
+    /// BLAKE3 checksum of the `up` SQL, recorded when the migration is applied
+    pub fn checksum(&self) -> String {
+        blake3::hash(self.up.as_bytes()).to_hex().to_string()
+    }
+}
+
+/// A migration recorded in `schema_migrations`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    /// Migration version
+    pub version: u32,
+    /// Migration name at the time it was applied
+    pub name: String,
+    /// Checksum of the SQL that was applied
+    pub checksum: String,
+    /// Unix timestamp of when it was applied
+    pub applied_at: i64,
+}
+
+/// Migration errors
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    /// SQLite error while applying or reverting a migration
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// An applied migration no longer matches the embedded one
+    #[error(
+        "Migration {version} ({name}) was modified after it was applied: \
+         recorded checksum {recorded}, embedded checksum {embedded}"
+    )]
+    ChecksumMismatch {
+        /// Migration version
+        version: u32,
+        /// Migration name
+        name: String,
+        /// Checksum recorded in the database
+        recorded: String,
+        /// Checksum of the embedded migration
+        embedded: String,
+    },
+
+    /// The database has a migration this version does not know
+    #[error(
+        "Database has migration {0}, which is newer than this version. Please upgrade LeIndex."
+    )]
+    UnknownMigration(u32),
+}
+
+impl From<MigrationError> for rusqlite::Error {
+    fn from(err: MigrationError) -> Self {
+        match err {
+            MigrationError::Sqlite(e) => e,
+            other => crate::storage::schema::StorageError::SchemaMigration(other).into(),
+        }
+    }
+}
+
+/// Result type for migration operations
+pub type Result<T> = std::result::Result<T, MigrationError>;
+
+/// Applies, verifies and rolls back an ordered list of migrations
+pub struct MigrationManager<'a> {
+    migrations: &'a [Migration],
+}
+
+impl MigrationManager<'static> {
+    /// Manager for the migrations embedded in this build
+    pub fn embedded() -> Self {
+        Self::new(MIGRATIONS)
+    }
+}
+
+impl<'a> MigrationManager<'a> {
+    /// Create a manager for `migrations`, which must be ordered by version
+    pub fn new(migrations: &'a [Migration]) -> Self {
+        debug_assert!(
+            migrations
+                .iter()
+                .enumerate()
+                .all(|(i, m)| m.version as usize == i + 1),
+            "migration versions must be 1, 2, 3, ..."
+        );
+        Self { migrations }
+    }
+
+    /// Highest version the migrations reach
+    pub fn latest_version(&self) -> u32 {
+        self.migrations.last().map_or(0, |m| m.version)
+    }
+
+    fn ensure_table(conn: &Connection) -> SqliteResult<()> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                checksum TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Migrations recorded in the database, ordered by version
+    pub fn applied(&self, conn: &Connection) -> Result<Vec<AppliedMigration>> {
+        let exists: Option<i64> = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if exists.is_none() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare(
+            "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version",
+        )?;
+        let applied = stmt
+            .query_map([], |row| {
+                Ok(AppliedMigration {
+                    version: row.get(0)?,
+                    name: row.get(1)?,
+                    checksum: row.get(2)?,
+                    applied_at: row.get(3)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(applied)
+    }
+
+    /// Check every applied migration against the embedded ones
+    ///
+    /// Fails if a migration's SQL changed since it was applied, or if the
+    /// database has migrations this version does not know.
+    pub fn verify(&self, conn: &Connection) -> Result<()> {
+        for applied in self.applied(conn)? {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version == applied.version)
+                .ok_or(MigrationError::UnknownMigration(applied.version))?;
+            let embedded = migration.checksum();
+            if applied.checksum != embedded {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: applied.version,
+                    name: migration.name.to_string(),
+                    recorded: applied.checksum,
+                    embedded,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify applied migrations, then apply the pending ones in order
+    ///
+    /// Each migration runs in its own transaction together with its
+    /// `schema_migrations` record. Returns the versions applied.
+    pub fn migrate(&self, conn: &mut Connection) -> Result<Vec<u32>> {
+        Self::ensure_table(conn)?;
+        self.verify(conn)?;
+        let current = self.current_version(conn)?;
+
+        let mut applied = Vec::new();
+        for migration in self.migrations.iter().filter(|m| m.version > current) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration.up)?;
+            tx.execute(
+                "INSERT INTO schema_migrations (version, name, checksum, applied_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    migration.version,
+                    migration.name,
+                    migration.checksum(),
+                    chrono::Utc::now().timestamp()
+                ],
+            )?;
+            tx.commit()?;
+            applied.push(migration.version);
+        }
+        Ok(applied)
+    }
+
+    /// Revert applied migrations, newest first, until `version` is the
+    /// latest one applied
+    ///
+    /// Returns the versions reverted. Rolling back drops the tables those
+    /// migrations created, along with their data.
+    pub fn rollback_to(&self, conn: &mut Connection, version: u32) -> Result<Vec<u32>> {
+        self.verify(conn)?;
+        let current = self.current_version(conn)?;
+
+        let mut reverted = Vec::new();
+        for migration in self
+            .migrations
+            .iter()
+            .rev()
+            .filter(|m| m.version > version && m.version <= current)
+        {
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration.down)?;
+            tx.execute(
+                "DELETE FROM schema_migrations WHERE version = ?1",
+                params![migration.version],
+            )?;
+            tx.commit()?;
+            reverted.push(migration.version);
+        }
+        Ok(reverted)
+    }
+
+    fn current_version(&self, conn: &Connection) -> Result<u32> {
+        Ok(self.applied(conn)?.last().map_or(0, |m| m.version))
+    }
+}
+
+/// Schema migrations embedded in this build, oldest first
+///
+/// Never edit a migration that has shipped; add a new one instead.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "core_graph",
+        up: "
+CREATE TABLE IF NOT EXISTS project_metadata (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    unique_project_id TEXT UNIQUE NOT NULL,
+    base_name TEXT NOT NULL,
+    path_hash TEXT NOT NULL,
+    instance INTEGER DEFAULT 0,
+    canonical_path TEXT NOT NULL,
+    display_name TEXT,
+    is_clone BOOLEAN DEFAULT 0,
+    cloned_from TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(canonical_path)
+);
+CREATE INDEX IF NOT EXISTS idx_project_metadata_unique_id ON project_metadata(unique_project_id);
+CREATE INDEX IF NOT EXISTS idx_project_metadata_canonical_path ON project_metadata(canonical_path);
+CREATE INDEX IF NOT EXISTS idx_project_metadata_base_hash ON project_metadata(base_name, path_hash);
+CREATE INDEX IF NOT EXISTS idx_project_metadata_base_name ON project_metadata(base_name);
+
+CREATE TABLE IF NOT EXISTS indexed_files (
+    file_path TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    file_hash TEXT NOT NULL,
+    last_indexed INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS intel_nodes (
+    id INTEGER PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    symbol_name TEXT NOT NULL,
+    qualified_name TEXT NOT NULL,
+    language TEXT NOT NULL DEFAULT 'unknown',
+    node_type TEXT NOT NULL,
+    signature TEXT,
+    complexity INTEGER,
+    content_hash TEXT NOT NULL,
+    embedding BLOB,
+    byte_range_start INTEGER,
+    byte_range_end INTEGER,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    embedding_format INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_nodes_project ON intel_nodes(project_id);
+CREATE INDEX IF NOT EXISTS idx_nodes_file ON intel_nodes(file_path);
+CREATE INDEX IF NOT EXISTS idx_nodes_symbol ON intel_nodes(symbol_name);
+CREATE INDEX IF NOT EXISTS idx_nodes_hash ON intel_nodes(content_hash);
+
+CREATE TABLE IF NOT EXISTS intel_edges (
+    caller_id INTEGER NOT NULL,
+    callee_id INTEGER NOT NULL,
+    edge_type TEXT NOT NULL,
+    metadata TEXT,
+    FOREIGN KEY(caller_id) REFERENCES intel_nodes(id),
+    FOREIGN KEY(callee_id) REFERENCES intel_nodes(id),
+    PRIMARY KEY(caller_id, callee_id, edge_type)
+);
+
+CREATE TABLE IF NOT EXISTS analysis_cache (
+    node_hash TEXT PRIMARY KEY,
+    cfg_data BLOB,
+    complexity_metrics BLOB,
+    timestamp INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cache_telemetry (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    cache_hits INTEGER NOT NULL DEFAULT 0,
+    cache_misses INTEGER NOT NULL DEFAULT 0,
+    cache_writes INTEGER NOT NULL DEFAULT 0,
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+INSERT OR IGNORE INTO cache_telemetry (id, cache_hits, cache_misses, cache_writes, updated_at)
+VALUES (1, 0, 0, 0, strftime('%s', 'now'));
+",
+        down: "
+DROP TABLE IF EXISTS cache_telemetry;
+DROP TABLE IF EXISTS analysis_cache;
+DROP TABLE IF EXISTS intel_edges;
+DROP TABLE IF EXISTS intel_nodes;
+DROP TABLE IF EXISTS indexed_files;
+DROP TABLE IF EXISTS project_metadata;
+",
+    },
+    Migration {
+        version: 2,
+        name: "cross_project",
+        up: "
+CREATE TABLE IF NOT EXISTS global_symbols (
+    symbol_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    symbol_name TEXT NOT NULL,
+    symbol_type TEXT NOT NULL,
+    signature TEXT,
+    file_path TEXT NOT NULL,
+    byte_range_start INTEGER,
+    byte_range_end INTEGER,
+    complexity INTEGER DEFAULT 1,
+    is_public INTEGER DEFAULT 0,
+    created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    UNIQUE(project_id, symbol_name, signature)
+);
+CREATE INDEX IF NOT EXISTS idx_global_symbols_name ON global_symbols(symbol_name);
+CREATE INDEX IF NOT EXISTS idx_global_symbols_type ON global_symbols(symbol_type);
+CREATE INDEX IF NOT EXISTS idx_global_symbols_project ON global_symbols(project_id);
+CREATE INDEX IF NOT EXISTS idx_global_symbols_public ON global_symbols(symbol_id) WHERE is_public = 1;
+
+CREATE TABLE IF NOT EXISTS external_refs (
+    ref_id TEXT PRIMARY KEY,
+    source_project_id TEXT NOT NULL,
+    source_symbol_id TEXT NOT NULL,
+    target_project_id TEXT NOT NULL,
+    target_symbol_id TEXT NOT NULL,
+    ref_type TEXT NOT NULL,
+    FOREIGN KEY (source_symbol_id) REFERENCES global_symbols(symbol_id),
+    FOREIGN KEY (target_symbol_id) REFERENCES global_symbols(symbol_id)
+);
+CREATE INDEX IF NOT EXISTS idx_external_refs_source ON external_refs(source_symbol_id);
+CREATE INDEX IF NOT EXISTS idx_external_refs_target ON external_refs(target_symbol_id);
+CREATE INDEX IF NOT EXISTS idx_external_refs_target_project
+    ON external_refs(target_symbol_id, source_project_id);
+
+CREATE TABLE IF NOT EXISTS project_deps (
+    dep_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    depends_on_project_id TEXT NOT NULL,
+    dependency_type TEXT NOT NULL,
+    UNIQUE(project_id, depends_on_project_id)
+);
+CREATE INDEX IF NOT EXISTS idx_project_deps_project ON project_deps(project_id);
+",
+        down: "
+DROP TABLE IF EXISTS project_deps;
+DROP TABLE IF EXISTS external_refs;
+DROP TABLE IF EXISTS global_symbols;
+",
+    },
+    Migration {
+        version: 3,
+        name: "analytics",
+        up: "
+CREATE TABLE IF NOT EXISTS analytics_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id TEXT NOT NULL,
+    generation TEXT NOT NULL,
+    taken_at INTEGER NOT NULL,
+    node_count INTEGER NOT NULL,
+    edge_count INTEGER NOT NULL,
+    avg_complexity REAL NOT NULL,
+    max_complexity INTEGER NOT NULL,
+    hotspot_count INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_analytics_snapshots_project
+    ON analytics_snapshots(project_id, taken_at);
+
+CREATE TABLE IF NOT EXISTS unresolved_imports (
+    project_id TEXT NOT NULL,
+    import_path TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    PRIMARY KEY(project_id, import_path, file_path)
+);
+",
+        down: "
+DROP TABLE IF EXISTS unresolved_imports;
+DROP TABLE IF EXISTS analytics_snapshots;
+",
+    },
+    Migration {
+        version: 4,
+        name: "parse_errors",
+        // Position columns are NULL when the whole file failed to parse
+        up: "
+CREATE TABLE IF NOT EXISTS parse_errors (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    start_byte INTEGER,
+    end_byte INTEGER,
+    line INTEGER,
+    col INTEGER,
+    message TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+CREATE INDEX IF NOT EXISTS idx_parse_errors_file ON parse_errors(project_id, file_path);
+",
+        down: "
+DROP TABLE IF EXISTS parse_errors;
+",
+    },
+    Migration {
+        version: 5,
+        name: "index_generations",
+        up: "
+CREATE TABLE IF NOT EXISTS index_generations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id TEXT NOT NULL,
+    generation TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    node_count INTEGER NOT NULL,
+    edge_count INTEGER NOT NULL,
+    UNIQUE(project_id, generation)
+);
+CREATE TABLE IF NOT EXISTS generation_nodes (
+    project_id TEXT NOT NULL,
+    generation TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+    symbol_name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    node_type TEXT NOT NULL,
+    fingerprint TEXT NOT NULL,
+    PRIMARY KEY(project_id, generation, node_id)
+);
+CREATE TABLE IF NOT EXISTS generation_edges (
+    project_id TEXT NOT NULL,
+    generation TEXT NOT NULL,
+    caller_id TEXT NOT NULL,
+    callee_id TEXT NOT NULL,
+    edge_type TEXT NOT NULL,
+    PRIMARY KEY(project_id, generation, caller_id, callee_id, edge_type)
+);
+",
+        down: "
+DROP TABLE IF EXISTS generation_edges;
+DROP TABLE IF EXISTS generation_nodes;
+DROP TABLE IF EXISTS index_generations;
+",
+    },
+    Migration {
+        version: 6,
+        name: "trigram_index",
+        // One serialized trigram index blob per project
+        up: "
+CREATE TABLE IF NOT EXISTS trigram_index (
+    project_id TEXT PRIMARY KEY,
+    index_data BLOB NOT NULL,
+    node_count INTEGER NOT NULL DEFAULT 0,
+    trigram_count INTEGER NOT NULL DEFAULT 0,
+    updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+",
+        down: "
+DROP TABLE IF EXISTS trigram_index;
+",
+    },
+    Migration {
+        version: 7,
+        name: "pdg_checksums",
+        // Counts and content hash of each project's stored PDG rows
+        up: "
+CREATE TABLE pdg_checksums (
+    project_id TEXT PRIMARY KEY,
+    node_count INTEGER NOT NULL,
+    edge_count INTEGER NOT NULL,
+    checksum TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+",
+        down: "
+DROP TABLE IF EXISTS pdg_checksums;
+",
+    },
+    Migration {
+        version: 8,
+        name: "project_scoped_ids",
+        // Key file records by project so projects sharing a database and a
+        // relative path keep separate hashes; the column order is unchanged
+        // because ingest copies rows positionally
+        up: "
+CREATE TABLE indexed_files_scoped (
+    file_path TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    file_hash TEXT NOT NULL,
+    last_indexed INTEGER NOT NULL,
+    PRIMARY KEY(project_id, file_path)
+);
+INSERT INTO indexed_files_scoped (file_path, project_id, file_hash, last_indexed)
+    SELECT file_path, project_id, file_hash, last_indexed FROM indexed_files;
+DROP TABLE indexed_files;
+ALTER TABLE indexed_files_scoped RENAME TO indexed_files;
+
+CREATE INDEX IF NOT EXISTS idx_nodes_project_node ON intel_nodes(project_id, node_id);
+",
+        down: "
+DROP INDEX IF EXISTS idx_nodes_project_node;
+
+CREATE TABLE indexed_files_unscoped (
+    file_path TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    file_hash TEXT NOT NULL,
+    last_indexed INTEGER NOT NULL
+);
+INSERT OR REPLACE INTO indexed_files_unscoped (file_path, project_id, file_hash, last_indexed)
+    SELECT file_path, project_id, file_hash, last_indexed FROM indexed_files;
+DROP TABLE indexed_files;
+ALTER TABLE indexed_files_unscoped RENAME TO indexed_files;
+",
+    },
+    Migration {
+        version: 9,
+        name: "edge_callee_index",
+        // Incoming-edge lookups; outgoing ones use the primary key, which
+        // starts with caller_id
+        up: "
+CREATE INDEX IF NOT EXISTS idx_edges_callee_type ON intel_edges(callee_id, edge_type);
+",
+        down: "
+DROP INDEX IF EXISTS idx_edges_callee_type;
+",
+    },
+    Migration {
+        version: 10,
+        name: "symbol_hashes",
+        // Source hash of each symbol at its last parse, for symbol-level
+        // freshness within changed files
+        up: "
+CREATE TABLE symbol_hashes (
+    project_id TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    symbol_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    PRIMARY KEY(project_id, file_path, symbol_id)
+);
+",
+        down: "
+DROP TABLE IF EXISTS symbol_hashes;
+",
+    },
+    Migration {
+        version: 11,
+        name: "query_history",
+        // Recent searches per project (trimmed on insert) and named saved
+        // searches
+        up: "
+CREATE TABLE query_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id TEXT NOT NULL,
+    query TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL
+);
+CREATE INDEX idx_query_history_project ON query_history(project_id, id);
+CREATE TABLE saved_queries (
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    query TEXT NOT NULL,
+    saved_at INTEGER NOT NULL,
+    PRIMARY KEY(project_id, name)
+);
+",
+        down: "
+DROP TABLE IF EXISTS saved_queries;
+DROP TABLE IF EXISTS query_history;
+",
+    },
+    Migration {
+        version: 12,
+        name: "incremental_pdg_checksums",
+        // Checksums became sums of per-row hashes so incremental writes can
+        // update them; recorded ones in the old format are dropped and
+        // rebuilt by the next full save
+        up: "
+DELETE FROM pdg_checksums;
+",
+        down: "
+DELETE FROM pdg_checksums;
+",
+    },
+    Migration {
+        version: 13,
+        name: "node_text_search",
+        // Full-text index over node names, signatures and paths, kept in
+        // step with intel_nodes by triggers; updates of other columns
+        // (embeddings, hashes) leave it alone
+        up: "
+CREATE VIRTUAL TABLE intel_nodes_fts USING fts5(
+    symbol_name, qualified_name, signature, file_path,
+    content='intel_nodes', content_rowid='id'
+);
+CREATE TRIGGER intel_nodes_fts_ai AFTER INSERT ON intel_nodes BEGIN
+    INSERT INTO intel_nodes_fts(rowid, symbol_name, qualified_name, signature, file_path)
+    VALUES (new.id, new.symbol_name, new.qualified_name, new.signature, new.file_path);
+END;
+CREATE TRIGGER intel_nodes_fts_ad AFTER DELETE ON intel_nodes BEGIN
+    INSERT INTO intel_nodes_fts(intel_nodes_fts, rowid, symbol_name, qualified_name, signature, file_path)
+    VALUES ('delete', old.id, old.symbol_name, old.qualified_name, old.signature, old.file_path);
+END;
+CREATE TRIGGER intel_nodes_fts_au
+AFTER UPDATE OF symbol_name, qualified_name, signature, file_path ON intel_nodes BEGIN
+    INSERT INTO intel_nodes_fts(intel_nodes_fts, rowid, symbol_name, qualified_name, signature, file_path)
+    VALUES ('delete', old.id, old.symbol_name, old.qualified_name, old.signature, old.file_path);
+    INSERT INTO intel_nodes_fts(rowid, symbol_name, qualified_name, signature, file_path)
+    VALUES (new.id, new.symbol_name, new.qualified_name, new.signature, new.file_path);
+END;
+INSERT INTO intel_nodes_fts(intel_nodes_fts) VALUES ('rebuild');
+",
+        down: "
+DROP TRIGGER IF EXISTS intel_nodes_fts_au;
+DROP TRIGGER IF EXISTS intel_nodes_fts_ad;
+DROP TRIGGER IF EXISTS intel_nodes_fts_ai;
+DROP TABLE IF EXISTS intel_nodes_fts;
+",
+    },
+    Migration {
+        version: 14,
+        name: "index_runs",
+        // One row per completed indexing pass, read back by leserve for the
+        // index duration histogram on /metrics
+        up: "
+CREATE TABLE index_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    completed_at INTEGER NOT NULL
+);
+",
+        down: "
+DROP TABLE IF EXISTS index_runs;
+",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::schema::Storage;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_fresh_database_records_applied_migrations() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+
+        let manager = MigrationManager::embedded();
+        let applied = manager.applied(storage.conn()).unwrap();
+        let versions: Vec<u32> = applied.iter().map(|m| m.version).collect();
+        assert_eq!(versions, (1..=manager.latest_version()).collect::<Vec<_>>());
+        for (applied, migration) in applied.iter().zip(MIGRATIONS) {
+            assert_eq!(applied.name, migration.name);
+            assert_eq!(applied.checksum, migration.checksum());
+        }
+        drop(storage);
+
+        // Reopening applies nothing new
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        assert!(manager.migrate(storage.conn_mut()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rollback_reverts_and_reopen_reapplies() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let manager = MigrationManager::embedded();
+        let latest = manager.latest_version();
+
+        // Version 6 created trigram_index
+        let reverted = manager.rollback_to(storage.conn_mut(), 5).unwrap();
+        assert_eq!(reverted, (6..=latest).rev().collect::<Vec<_>>());
+        let trigram_table: Option<i64> = storage
+            .conn()
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE name = 'trigram_index'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .unwrap();
+        assert!(trigram_table.is_none());
+        drop(storage);
+
+        let storage = Storage::open(temp_file.path()).unwrap();
+        assert_eq!(
+            manager
+                .applied(storage.conn())
+                .unwrap()
+                .last()
+                .unwrap()
+                .version,
+            latest
+        );
+    }
+
+    #[test]
+    fn test_modified_migration_is_rejected() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        storage
+            .conn()
+            .execute(
+                "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 1",
+                [],
+            )
+            .unwrap();
+        drop(storage);
+
+        let err = Storage::open(temp_file.path())
+            .err()
+            .expect("checksum mismatch should fail the open");
+        assert!(
+            err.to_string().contains("modified after it was applied"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_unknown_migration_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        MigrationManager::new(&MIGRATIONS[..2])
+            .migrate(&mut conn)
+            .unwrap();
+        let err = MigrationManager::new(&MIGRATIONS[..1])
+            .verify(&conn)
+            .unwrap_err();
+        assert!(matches!(err, MigrationError::UnknownMigration(2)));
+    }
+}
@@ -11,16 +11,28 @@ pub mod analytics;
 pub mod cross_project;
 /// Storage and retrieval of graph edges.
 pub mod edges;
+/// Garbage collection of orphaned and dangling rows.
+pub mod gc;
+/// Symbol and edge sets of past indexing generations.
+pub mod generations;
 /// Global symbol table for cross-project indexing.
 pub mod global_symbols;
+/// Durations of completed indexing runs.
+pub mod index_runs;
+/// Versioned schema migrations with checksums and rollback.
+pub mod migrations;
 /// Storage and retrieval of code nodes.
 pub mod nodes;
+/// Parse errors recorded during indexing.
+pub mod parse_errors;
 /// Persistent storage for Program Dependence Graphs.
 pub mod pdg_store;
 /// Unique project identification with BLAKE3 path hashing.
 pub mod project_id;
 /// Project metadata storage and retrieval.
 pub mod project_metadata;
+/// Query history and named saved searches.
+pub mod query_history;
 /// Salsa-inspired incremental computation and caching.
 pub mod salsa;
 /// Database schema and connection management.
@@ -29,26 +41,48 @@ pub mod schema;
 #[cfg(feature = "turso")]
 pub mod turso_config;
 
-pub use analytics::Analytics;
-pub use cross_project::{CrossProjectResolver, MergeError, ResolutionError, ResolvedSymbol};
+pub use analytics::{
+    save_unresolved_imports, Analytics, AnalyticsSnapshot, ComplexityWeights, LanguageCount,
+    ProjectComplexity, ProjectSummary, TrendMetric, TrendPoint, UnresolvedImportRecord,
+};
+pub use cross_project::{
+    resolve_call_edges_with_symbol_table, CrossProjectResolver, MergeError, ResolutionError,
+    ResolvedSymbol,
+};
 pub use edges::{EdgeRecord, EdgeStore};
+pub use gc::{gc, gc_dry_run, GcSummary};
+pub use generations::{
+    diff_generations, has_generation, list_generations, record_generation, GenerationDiff,
+    GenerationEdge, GenerationInfo, GenerationNode, ModifiedNode,
+};
 pub use global_symbols::{
     DepType, ExternalRef, GlobalSymbol, GlobalSymbolError, GlobalSymbolId, GlobalSymbolTable,
-    ProjectDep, RefType, SymbolType,
+    ProjectDep, RefType, SymbolReference, SymbolType,
 };
+pub use index_runs::{index_run_durations, record_index_run};
+pub use migrations::{AppliedMigration, Migration, MigrationError, MigrationManager};
 pub use nodes::{NodeRecord, NodeStore};
+pub use parse_errors::{load_parse_errors, replace_parse_errors, ParseErrorRecord};
 pub use pdg_store::{
-    delete_pdg, load_pdg, pdg_exists, save_pdg, PdgStoreError, Result as PdgStoreResult,
+    delete_pdg, load_file_subgraph, load_nodes_page, load_pdg, load_pdg_async, pdg_exists,
+    save_file_delta, save_incremental, save_pdg, save_pdg_async, stored_checksum,
+    ConsistencyReport, DeltaSummary, PdgChecksum, PdgStoreError, RepairSummary,
+    Result as PdgStoreResult,
 };
-pub use project_id::UniqueProjectId;
+pub use project_id::{IdentityStrategy, UniqueProjectId, PROJECT_ID_MARKER};
 pub use project_metadata::{ProjectMetadata, ProjectMetadataError};
+pub use query_history::{
+    delete_saved_query, load_saved_queries, load_saved_query, recent_queries, record_query,
+    save_query, QueryHistoryEntry, SavedQuery, QUERY_HISTORY_LIMIT,
+};
 pub use salsa::{IncrementalCache, NodeHash};
 pub use schema::{
-    Storage, StorageConfig, StoragePool, StoragePoolError, StorageRole, DEFAULT_READER_POOL_SIZE,
+    Storage, StorageConfig, StorageError, StoragePool, StoragePoolError, StorageRole,
+    DEFAULT_READER_POOL_SIZE,
 };
 
 #[cfg(feature = "turso")]
-pub use turso_config::{HybridStorage, MigrationStats, StorageError, StorageMode, TursoConfig};
+pub use turso_config::{HybridStorage, MigrationStats, StorageMode, TursoConfig};
 
 /// Storage library initialization
 pub fn init() {
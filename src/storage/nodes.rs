@@ -1,6 +1,7 @@
 // Node persistence operations
 
-use crate::storage::schema::Storage;
+use crate::storage::project_id::UniqueProjectId;
+use crate::storage::schema::{Storage, StorageError};
 use rusqlite::{params, OptionalExtension, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
 
@@ -84,19 +85,87 @@ impl NodeType {
     }
 }
 
+const NODE_COLUMNS: &str = "id, project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format";
+
+/// Turn free text into an FTS5 query matching rows that contain every word
+/// as a prefix, so user input never hits FTS5 query syntax
+fn fts_query(query: &str) -> String {
+    query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn node_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<NodeRecord> {
+    Ok(NodeRecord {
+        id: Some(row.get(0)?),
+        project_id: row.get(1)?,
+        file_path: row.get(2)?,
+        node_id: row.get(3)?,
+        symbol_name: row.get(4)?,
+        qualified_name: row.get(5)?,
+        language: row.get(6)?,
+        node_type: NodeType::from_str_name(&row.get::<_, String>(7)?).unwrap_or(NodeType::Function),
+        signature: row.get(8)?,
+        complexity: row.get(9)?,
+        content_hash: row.get(10)?,
+        embedding: row.get(11)?,
+        byte_range_start: row.get(12)?,
+        byte_range_end: row.get(13)?,
+        embedding_format: row.get(14)?,
+    })
+}
+
 /// Node store for CRUD operations
+///
+/// Node IDs (`file_path:qualified_name`) are only unique within a project, so
+/// when several projects share one database a store created with
+/// [`for_project`](Self::for_project) keeps reads and writes inside that
+/// project. A store created with [`new`](Self::new) spans every project.
 pub struct NodeStore<'a> {
     storage: &'a mut Storage,
+    project: Option<String>,
 }
 
 impl<'a> NodeStore<'a> {
     /// Create a new node store
     pub fn new(storage: &'a mut Storage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            project: None,
+        }
+    }
+
+    /// Create a node store scoped to one project
+    pub fn for_project(storage: &'a mut Storage, project_id: &UniqueProjectId) -> Self {
+        Self {
+            storage,
+            project: Some(project_id.as_unique_string()),
+        }
+    }
+
+    /// Project this store is scoped to, if any
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    fn check_project(&self, record: &NodeRecord) -> SqliteResult<()> {
+        match &self.project {
+            Some(project) if *project != record.project_id => Err(StorageError::ProjectMismatch {
+                node_id: record.node_id.clone(),
+                project_id: record.project_id.clone(),
+                expected: project.clone(),
+            }
+            .into()),
+            _ => Ok(()),
+        }
     }
 
     /// Insert a node record
     pub fn insert(&mut self, record: &NodeRecord) -> SqliteResult<i64> {
+        self.check_project(record)?;
         self.storage.conn().execute(
             "INSERT INTO intel_nodes (project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, created_at, updated_at, embedding_format)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
@@ -125,6 +194,9 @@ impl<'a> NodeStore<'a> {
 
     /// Batch insert nodes
     pub fn batch_insert(&mut self, records: &[NodeRecord]) -> SqliteResult<Vec<i64>> {
+        for record in records {
+            self.check_project(record)?;
+        }
         let tx = self.storage.conn_mut().transaction()?;
 
         let mut ids = Vec::new();
@@ -160,94 +232,82 @@ impl<'a> NodeStore<'a> {
 
     /// Get node by ID
     pub fn get(&self, id: i64) -> SqliteResult<Option<NodeRecord>> {
-        let mut stmt = self.storage.conn().prepare(
-            "SELECT id, project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format
-             FROM intel_nodes WHERE id = ?1"
-        )?;
+        let mut stmt = self.storage.conn().prepare(&format!(
+            "SELECT {} FROM intel_nodes WHERE id = ?1 AND (?2 IS NULL OR project_id = ?2)",
+            NODE_COLUMNS
+        ))?;
 
-        let result = stmt.query_row(params![id], |row| {
-            Ok(NodeRecord {
-                id: Some(row.get(0)?),
-                project_id: row.get(1)?,
-                file_path: row.get(2)?,
-                node_id: row.get(3)?,
-                symbol_name: row.get(4)?,
-                qualified_name: row.get(5)?,
-                language: row.get(6)?,
-                node_type: NodeType::from_str_name(&row.get::<_, String>(7)?)
-                    .unwrap_or(NodeType::Function),
-                signature: row.get(8)?,
-                complexity: row.get(9)?,
-                content_hash: row.get(10)?,
-                embedding: row.get(11)?,
-                byte_range_start: row.get(12)?,
-                byte_range_end: row.get(13)?,
-                embedding_format: row.get(14)?,
-            })
-        });
-
-        result.optional()
+        stmt.query_row(params![id, self.project], node_from_row)
+            .optional()
+    }
+
+    /// Get a node by its node ID (`file_path:qualified_name`)
+    ///
+    /// An unscoped store returns the first match of any project.
+    pub fn get_by_node_id(&self, node_id: &str) -> SqliteResult<Option<NodeRecord>> {
+        let mut stmt = self.storage.conn().prepare(&format!(
+            "SELECT {} FROM intel_nodes WHERE node_id = ?1 AND (?2 IS NULL OR project_id = ?2)
+             ORDER BY id LIMIT 1",
+            NODE_COLUMNS
+        ))?;
+
+        stmt.query_row(params![node_id, self.project], node_from_row)
+            .optional()
     }
 
     /// Find node by content hash
     pub fn find_by_hash(&self, hash: &str) -> SqliteResult<Option<NodeRecord>> {
-        let mut stmt = self.storage.conn().prepare(
-            "SELECT id, project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format
-             FROM intel_nodes WHERE content_hash = ?1"
-        )?;
+        let mut stmt = self.storage.conn().prepare(&format!(
+            "SELECT {} FROM intel_nodes WHERE content_hash = ?1 AND (?2 IS NULL OR project_id = ?2)",
+            NODE_COLUMNS
+        ))?;
 
-        let result = stmt.query_row(params![hash], |row| {
-            Ok(NodeRecord {
-                id: Some(row.get(0)?),
-                project_id: row.get(1)?,
-                file_path: row.get(2)?,
-                node_id: row.get(3)?,
-                symbol_name: row.get(4)?,
-                qualified_name: row.get(5)?,
-                language: row.get(6)?,
-                node_type: NodeType::from_str_name(&row.get::<_, String>(7)?)
-                    .unwrap_or(NodeType::Function),
-                signature: row.get(8)?,
-                complexity: row.get(9)?,
-                content_hash: row.get(10)?,
-                embedding: row.get(11)?,
-                byte_range_start: row.get(12)?,
-                byte_range_end: row.get(13)?,
-                embedding_format: row.get(14)?,
-            })
-        });
-
-        result.optional()
+        stmt.query_row(params![hash, self.project], node_from_row)
+            .optional()
+    }
+
+    /// Search node names, signatures and file paths, best matches first
+    ///
+    /// Every word of `query` must prefix-match one of the indexed columns.
+    /// A storage-level fallback for when no in-memory search index is
+    /// loaded, backed by the `intel_nodes_fts` index the `node_text_search`
+    /// migration maintains.
+    pub fn search_text(&self, query: &str, limit: usize) -> SqliteResult<Vec<NodeRecord>> {
+        let query = fts_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let columns = NODE_COLUMNS
+            .split(", ")
+            .map(|column| format!("n.{}", column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut stmt = self.storage.conn().prepare(&format!(
+            "SELECT {} FROM intel_nodes_fts f
+             JOIN intel_nodes n ON n.id = f.rowid
+             WHERE intel_nodes_fts MATCH ?1 AND (?2 IS NULL OR n.project_id = ?2)
+             ORDER BY f.rank
+             LIMIT ?3",
+            columns
+        ))?;
+
+        let nodes = stmt
+            .query_map(params![query, self.project, limit as i64], node_from_row)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(nodes)
     }
 
     /// Get nodes by file path
     pub fn get_by_file(&self, file_path: &str) -> SqliteResult<Vec<NodeRecord>> {
-        let mut stmt = self.storage.conn().prepare(
-            "SELECT id, project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format
-             FROM intel_nodes WHERE file_path = ?1"
-        )?;
+        let mut stmt = self.storage.conn().prepare(&format!(
+            "SELECT {} FROM intel_nodes WHERE file_path = ?1 AND (?2 IS NULL OR project_id = ?2)",
+            NODE_COLUMNS
+        ))?;
 
         let nodes = stmt
-            .query_map(params![file_path], |row| {
-                Ok(NodeRecord {
-                    id: Some(row.get(0)?),
-                    project_id: row.get(1)?,
-                    file_path: row.get(2)?,
-                    node_id: row.get(3)?,
-                    symbol_name: row.get(4)?,
-                    qualified_name: row.get(5)?,
-                    language: row.get(6)?,
-                    node_type: NodeType::from_str_name(&row.get::<_, String>(7)?)
-                        .unwrap_or(NodeType::Function),
-                    signature: row.get(8)?,
-                    complexity: row.get(9)?,
-                    content_hash: row.get(10)?,
-                    embedding: row.get(11)?,
-                    byte_range_start: row.get(12)?,
-                    byte_range_end: row.get(13)?,
-                    embedding_format: row.get(14)?,
-                })
-            })?
+            .query_map(params![file_path, self.project], node_from_row)?
             .collect::<SqliteResult<Vec<_>>>()?;
 
         Ok(nodes)
@@ -320,4 +380,157 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().symbol_name, "test_func");
     }
+
+    fn main_node(project_id: &UniqueProjectId, signature: &str) -> NodeRecord {
+        NodeRecord {
+            id: None,
+            project_id: project_id.as_unique_string(),
+            file_path: "src/main.rs".to_string(),
+            node_id: "src/main.rs:main".to_string(),
+            symbol_name: "main".to_string(),
+            qualified_name: "main".to_string(),
+            language: "rust".to_string(),
+            node_type: NodeType::Function,
+            signature: Some(signature.to_string()),
+            complexity: None,
+            content_hash: blake3::hash(b"src/main.rs:main").to_hex().to_string(),
+            embedding: None,
+            byte_range_start: Some(0),
+            byte_range_end: Some(40),
+            embedding_format: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_node_ids_are_scoped_by_project() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let first = UniqueProjectId::new("app".to_string(), "aaaaaaaa".to_string(), 0);
+        let second = UniqueProjectId::new("app".to_string(), "bbbbbbbb".to_string(), 0);
+
+        let first_id = NodeStore::for_project(&mut storage, &first)
+            .insert(&main_node(&first, "fn main() -> first"))
+            .unwrap();
+        let second_id = NodeStore::for_project(&mut storage, &second)
+            .insert(&main_node(&second, "fn main() -> second"))
+            .unwrap();
+        assert_ne!(first_id, second_id);
+
+        for (project, signature, own_id, other_id) in [
+            (&first, "fn main() -> first", first_id, second_id),
+            (&second, "fn main() -> second", second_id, first_id),
+        ] {
+            let store = NodeStore::for_project(&mut storage, project);
+            let node = store.get_by_node_id("src/main.rs:main").unwrap().unwrap();
+            assert_eq!(node.id, Some(own_id));
+            assert_eq!(node.signature.as_deref(), Some(signature));
+
+            let hash = blake3::hash(b"src/main.rs:main").to_hex().to_string();
+            assert_eq!(store.find_by_hash(&hash).unwrap().unwrap().id, Some(own_id));
+            let in_file = store.get_by_file("src/main.rs").unwrap();
+            assert_eq!(in_file.len(), 1);
+            assert_eq!(in_file[0].project_id, project.as_unique_string());
+
+            assert!(store.get(own_id).unwrap().is_some());
+            assert!(store.get(other_id).unwrap().is_none());
+        }
+
+        // An unscoped store still sees both
+        let store = NodeStore::new(&mut storage);
+        assert_eq!(store.get_by_file("src/main.rs").unwrap().len(), 2);
+
+        // A scoped store refuses records of another project
+        let mut store = NodeStore::for_project(&mut storage, &first);
+        assert!(store.insert(&main_node(&second, "fn main()")).is_err());
+        assert!(store
+            .batch_insert(&[main_node(&second, "fn main()")])
+            .is_err());
+    }
+
+    #[test]
+    fn test_indexed_files_are_scoped_by_project() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        crate::storage::pdg_store::update_indexed_file(&mut storage, "one", "src/main.rs", "h1")
+            .unwrap();
+        crate::storage::pdg_store::update_indexed_file(&mut storage, "two", "src/main.rs", "h2")
+            .unwrap();
+
+        let one = crate::storage::pdg_store::get_indexed_files(&storage, "one").unwrap();
+        let two = crate::storage::pdg_store::get_indexed_files(&storage, "two").unwrap();
+        assert_eq!(one.get("src/main.rs").map(String::as_str), Some("h1"));
+        assert_eq!(two.get("src/main.rs").map(String::as_str), Some("h2"));
+    }
+
+    #[test]
+    fn test_search_text_finds_matching_nodes() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let project = UniqueProjectId::new("app".to_string(), "aaaaaaaa".to_string(), 0);
+        let mut store = NodeStore::for_project(&mut storage, &project);
+
+        let mut parse = main_node(&project, "fn parse_config(path: &Path) -> Config");
+        parse.node_id = "src/config.rs:parse_config".to_string();
+        parse.symbol_name = "parse_config".to_string();
+        parse.qualified_name = "config::parse_config".to_string();
+        parse.file_path = "src/config.rs".to_string();
+        let parse_id = store.insert(&parse).unwrap();
+        let main_id = store.insert(&main_node(&project, "fn main()")).unwrap();
+
+        let found = store.search_text("parse_config", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, Some(parse_id));
+        assert_eq!(found[0].symbol_name, "parse_config");
+
+        // Prefix and signature matches; punctuation is not query syntax
+        let found = store.search_text("Conf (path", 10).unwrap();
+        assert_eq!(
+            found.iter().map(|n| n.id).collect::<Vec<_>>(),
+            [Some(parse_id)]
+        );
+        let found = store.search_text("main", 10).unwrap();
+        assert_eq!(
+            found.iter().map(|n| n.id).collect::<Vec<_>>(),
+            [Some(main_id)]
+        );
+        assert!(store.search_text("nothing_here", 10).unwrap().is_empty());
+        assert!(store.search_text("  ", 10).unwrap().is_empty());
+
+        // Updates of indexed columns are tracked, others leave it alone
+        storage
+            .conn()
+            .execute(
+                "UPDATE intel_nodes SET symbol_name = 'load_config',
+                 qualified_name = 'config::load_config', signature = 'fn load_config()'
+                 WHERE id = ?1",
+                params![parse_id],
+            )
+            .unwrap();
+        storage
+            .conn()
+            .execute(
+                "UPDATE intel_nodes SET complexity = 4 WHERE id = ?1",
+                params![parse_id],
+            )
+            .unwrap();
+        let store = NodeStore::for_project(&mut storage, &project);
+        assert_eq!(
+            store
+                .search_text("load_config", 10)
+                .unwrap()
+                .iter()
+                .map(|n| n.id)
+                .collect::<Vec<_>>(),
+            [Some(parse_id)]
+        );
+        assert!(store.search_text("parse_config", 10).unwrap().is_empty());
+
+        // Deleted nodes leave the index
+        storage
+            .conn()
+            .execute("DELETE FROM intel_nodes WHERE id = ?1", params![parse_id])
+            .unwrap();
+        let store = NodeStore::for_project(&mut storage, &project);
+        assert!(store.search_text("load_config", 10).unwrap().is_empty());
+    }
 }
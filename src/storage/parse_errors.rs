@@ -0,0 +1,134 @@
+// Parse errors recorded during indexing
+//
+// Failed parses and recovered syntax errors are stored per file so they can
+// be reviewed later (`leindex errors`) without re-indexing. Rows for a file
+// are replaced whenever that file is parsed again.
+
+use crate::storage::schema::Storage;
+use rusqlite::{params, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+/// A parse error recorded for one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseErrorRecord {
+    /// File that failed to parse
+    pub file_path: String,
+
+    /// Byte range of the offending region (None when the whole file failed)
+    pub byte_range: Option<(usize, usize)>,
+
+    /// One-based line where the offending region starts
+    pub line: Option<usize>,
+
+    /// One-based column where the offending region starts
+    pub column: Option<usize>,
+
+    /// Error message
+    pub message: String,
+}
+
+/// Replace the stored parse errors of `files` with `errors`
+///
+/// Every file in `files` loses its previous rows, so a file that now parses
+/// cleanly ends up with none. `errors` should only mention files in `files`.
+pub fn replace_parse_errors(
+    storage: &Storage,
+    project_id: &str,
+    files: &[String],
+    errors: &[ParseErrorRecord],
+) -> SqliteResult<()> {
+    let tx = storage.conn().unchecked_transaction()?;
+    {
+        let mut delete =
+            tx.prepare("DELETE FROM parse_errors WHERE project_id = ?1 AND file_path = ?2")?;
+        for file_path in files {
+            delete.execute(params![project_id, file_path])?;
+        }
+        let mut insert = tx.prepare(
+            "INSERT INTO parse_errors
+             (project_id, file_path, start_byte, end_byte, line, col, message)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for error in errors {
+            insert.execute(params![
+                project_id,
+                error.file_path,
+                error.byte_range.map(|(start, _)| start as i64),
+                error.byte_range.map(|(_, end)| end as i64),
+                error.line.map(|line| line as i64),
+                error.column.map(|column| column as i64),
+                error.message,
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+/// Load the stored parse errors of a project, ordered by file and position
+pub fn load_parse_errors(
+    storage: &Storage,
+    project_id: &str,
+) -> SqliteResult<Vec<ParseErrorRecord>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT file_path, start_byte, end_byte, line, col, message FROM parse_errors
+         WHERE project_id = ?1
+         ORDER BY file_path, line, col, id",
+    )?;
+
+    let errors = stmt
+        .query_map(params![project_id], |row| {
+            let start: Option<i64> = row.get(1)?;
+            let end: Option<i64> = row.get(2)?;
+            let line: Option<i64> = row.get(3)?;
+            let column: Option<i64> = row.get(4)?;
+            Ok(ParseErrorRecord {
+                file_path: row.get(0)?,
+                byte_range: start.zip(end).map(|(s, e)| (s as usize, e as usize)),
+                line: line.map(|l| l as usize),
+                column: column.map(|c| c as usize),
+                message: row.get(5)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_replace_parse_errors_per_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        let error = |file: &str, line: Option<usize>| ParseErrorRecord {
+            file_path: file.to_string(),
+            byte_range: line.map(|l| (l * 10, l * 10 + 3)),
+            line,
+            column: line.map(|_| 1),
+            message: "syntax error".to_string(),
+        };
+
+        replace_parse_errors(
+            &storage,
+            "proj",
+            &["a.py".to_string(), "b.py".to_string()],
+            &[error("a.py", Some(3)), error("b.py", None)],
+        )
+        .unwrap();
+        assert_eq!(
+            load_parse_errors(&storage, "proj").unwrap(),
+            vec![error("a.py", Some(3)), error("b.py", None)]
+        );
+
+        // Reparsing a.py cleanly clears its rows and leaves b.py alone
+        replace_parse_errors(&storage, "proj", &["a.py".to_string()], &[]).unwrap();
+        assert_eq!(
+            load_parse_errors(&storage, "proj").unwrap(),
+            vec![error("b.py", None)]
+        );
+        assert!(load_parse_errors(&storage, "other").unwrap().is_empty());
+    }
+}
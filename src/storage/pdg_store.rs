@@ -1,6 +1,12 @@
 // PDG Persistence Bridge
 //
 // *Le Pont* (The Bridge) - Converts between legraphe PDG and lestockage records
+//
+// Public write operations run through `Storage::retry_on_busy`, so a write
+// racing another connection is retried instead of failing with SQLITE_BUSY.
+// Each of them also records a checksum of the project's rows in
+// `pdg_checksums`, which `load_pdg` verifies. Full saves compute it from
+// every row; incremental writes only re-hash the rows they touch.
 
 use crate::graph::pdg::{
     Edge as PDGEdge, EdgeMetadata as PDGEdgeMetadata, EdgeType as PDGEdgeType, Node as PDGNode,
@@ -10,9 +16,9 @@ use crate::graph::trigram::TrigramIndex;
 use crate::storage::edges::{EdgeMetadata as StorageEdgeMetadata, EdgeType as StorageEdgeType};
 use crate::storage::nodes::{NodeRecord, NodeType as StorageNodeType};
 use crate::storage::schema::Storage;
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Type alias for node database rows to reduce type complexity
 type NodeDbRow = (
@@ -31,6 +37,9 @@ type NodeDbRow = (
     Option<i32>,
 );
 
+/// Edge database row: caller ID, callee ID, edge type, metadata JSON
+type EdgeDbRow = (i64, i64, String, Option<String>);
+
 /// Errors that can occur during PDG persistence
 #[derive(Debug, thiserror::Error)]
 pub enum PdgStoreError {
@@ -58,6 +67,24 @@ pub enum PdgStoreError {
     /// Failed to deserialize stored data back into a PDG
     #[error("Deserialization error: {0}")]
     Deserialization(String),
+
+    /// The stored rows do not match the checksum recorded when they were
+    /// saved; the project should be re-indexed
+    #[error(
+        "Stored PDG of project '{project_id}' is corrupted: expected {expected}, found {found}"
+    )]
+    Corrupted {
+        /// Project whose PDG failed verification
+        project_id: String,
+        /// Checksum recorded at save time
+        expected: PdgChecksum,
+        /// Checksum of the rows found on load
+        found: PdgChecksum,
+    },
+
+    /// A blocking storage task panicked, or the storage lock was poisoned
+    #[error("Storage task failed: {0}")]
+    TaskFailed(String),
 }
 
 /// Result type for PDG store operations
@@ -115,6 +142,7 @@ fn convert_edge_metadata(metadata: &PDGEdgeMetadata) -> StorageEdgeMetadata {
         call_count: metadata.call_count,
         variable_name: metadata.variable_name.clone(),
         confidence: metadata.confidence,
+        call_target: metadata.call_target.clone(),
     }
 }
 
@@ -124,6 +152,7 @@ fn convert_storage_edge_metadata(metadata: &StorageEdgeMetadata) -> PDGEdgeMetad
         call_count: metadata.call_count,
         variable_name: metadata.variable_name.clone(),
         confidence: metadata.confidence,
+        call_target: metadata.call_target.clone(),
     }
 }
 
@@ -153,6 +182,14 @@ pub fn save_pdg(
     storage: &mut Storage,
     project_id: &str,
     pdg: &ProgramDependenceGraph,
+) -> Result<()> {
+    storage.retry_on_busy(|storage| save_pdg_once(storage, project_id, pdg))
+}
+
+fn save_pdg_once(
+    storage: &mut Storage,
+    project_id: &str,
+    pdg: &ProgramDependenceGraph,
 ) -> Result<()> {
     let tx = storage.conn_mut().transaction()?;
 
@@ -176,56 +213,7 @@ pub fn save_pdg(
             .get_node(node_idx)
             .ok_or_else(|| PdgStoreError::Serialization("Missing node data".to_string()))?;
 
-        // Note: Embeddings are now externalized to EmbeddingStore, not stored in Node
-        // They are persisted separately if needed
-        let record = NodeRecord {
-            id: None,
-            project_id: project_id.to_string(),
-            file_path: pdg_node.file_path.to_string(),
-            node_id: pdg_node.id.clone(),
-            symbol_name: pdg_node.name.clone(),
-            qualified_name: pdg_node
-                .id
-                .split(':')
-                .next_back()
-                .unwrap_or(&pdg_node.id)
-                .to_string(),
-            language: pdg_node.language.clone(),
-            node_type: convert_node_type(&pdg_node.node_type),
-            signature: None, // Could be populated from node content
-            complexity: Some(pdg_node.complexity as i32),
-            content_hash: blake3::hash(pdg_node.id.as_bytes()).to_hex().to_string(),
-            embedding: None, // Embeddings externalized to EmbeddingStore
-            byte_range_start: Some(pdg_node.byte_range.0 as i64),
-            byte_range_end: Some(pdg_node.byte_range.1 as i64),
-            embedding_format: Some(0),
-        };
-
-        let db_id: i64 = tx.query_row(
-            "INSERT INTO intel_nodes (project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, created_at, updated_at, embedding_format)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
-             RETURNING id",
-            params![
-                record.project_id,
-                record.file_path,
-                record.node_id,
-                record.symbol_name,
-                record.qualified_name,
-                record.language,
-                record.node_type.as_str(),
-                record.signature,
-                record.complexity,
-                record.content_hash,
-                record.embedding.as_deref(),
-                record.byte_range_start,
-                record.byte_range_end,
-                chrono::Utc::now().timestamp(),
-                chrono::Utc::now().timestamp(),
-                record.embedding_format,
-            ],
-            |row| row.get(0),
-        )?;
-
+        let db_id = insert_node_tx(&tx, project_id, pdg_node)?;
         node_id_map.insert(node_idx, db_id);
     }
 
@@ -255,21 +243,7 @@ pub fn save_pdg(
                     callee: target.index() as i64,
                 })?;
 
-        let metadata = convert_edge_metadata(&pdg_edge.metadata);
-        let metadata_json = serde_json::to_string(&metadata)
-            .map_err(|e| PdgStoreError::Serialization(e.to_string()))?;
-
-        tx.execute(
-            "INSERT INTO intel_edges (caller_id, callee_id, edge_type, metadata)
-                 VALUES (?1, ?2, ?3, ?4)
-                 ON CONFLICT DO UPDATE SET metadata = excluded.metadata",
-            params![
-                caller_id,
-                callee_id,
-                convert_edge_type(&pdg_edge.edge_type).as_str(),
-                metadata_json,
-            ],
-        )?;
+        upsert_edge_tx(&tx, caller_id, callee_id, pdg_edge)?;
     }
 
     // Save trigram index alongside the PDG (within the same transaction)
@@ -279,10 +253,339 @@ pub fn save_pdg(
         tracing::warn!("Failed to save trigram index: {e}");
     }
 
+    refresh_checksum(&tx, project_id)?;
     tx.commit()?;
     Ok(())
 }
 
+/// Insert one PDG node row and return its database ID
+fn insert_node_tx(
+    tx: &rusqlite::Transaction<'_>,
+    project_id: &str,
+    pdg_node: &PDGNode,
+) -> Result<i64> {
+    // Note: Embeddings are now externalized to EmbeddingStore, not stored in Node
+    // They are persisted separately if needed
+    let record = NodeRecord {
+        id: None,
+        project_id: project_id.to_string(),
+        file_path: pdg_node.file_path.to_string(),
+        node_id: pdg_node.id.clone(),
+        symbol_name: pdg_node.name.clone(),
+        qualified_name: pdg_node
+            .id
+            .split(':')
+            .next_back()
+            .unwrap_or(&pdg_node.id)
+            .to_string(),
+        language: pdg_node.language.clone(),
+        node_type: convert_node_type(&pdg_node.node_type),
+        signature: None, // Could be populated from node content
+        complexity: Some(pdg_node.complexity as i32),
+        content_hash: blake3::hash(pdg_node.id.as_bytes()).to_hex().to_string(),
+        embedding: None, // Embeddings externalized to EmbeddingStore
+        byte_range_start: Some(pdg_node.byte_range.0 as i64),
+        byte_range_end: Some(pdg_node.byte_range.1 as i64),
+        embedding_format: Some(0),
+    };
+
+    let db_id: i64 = tx.query_row(
+        "INSERT INTO intel_nodes (project_id, file_path, node_id, symbol_name, qualified_name, language, node_type, signature, complexity, content_hash, embedding, byte_range_start, byte_range_end, created_at, updated_at, embedding_format)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+         RETURNING id",
+        params![
+            record.project_id,
+            record.file_path,
+            record.node_id,
+            record.symbol_name,
+            record.qualified_name,
+            record.language,
+            record.node_type.as_str(),
+            record.signature,
+            record.complexity,
+            record.content_hash,
+            record.embedding.as_deref(),
+            record.byte_range_start,
+            record.byte_range_end,
+            chrono::Utc::now().timestamp(),
+            chrono::Utc::now().timestamp(),
+            record.embedding_format,
+        ],
+        |row| row.get(0),
+    )?;
+
+    Ok(db_id)
+}
+
+/// Insert an edge row, replacing the metadata of an existing identical edge
+fn upsert_edge_tx(
+    tx: &rusqlite::Transaction<'_>,
+    caller_id: i64,
+    callee_id: i64,
+    pdg_edge: &PDGEdge,
+) -> Result<()> {
+    let metadata = convert_edge_metadata(&pdg_edge.metadata);
+    let metadata_json = serde_json::to_string(&metadata)
+        .map_err(|e| PdgStoreError::Serialization(e.to_string()))?;
+
+    tx.execute(
+        "INSERT INTO intel_edges (caller_id, callee_id, edge_type, metadata)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT DO UPDATE SET metadata = excluded.metadata",
+        params![
+            caller_id,
+            callee_id,
+            convert_edge_type(&pdg_edge.edge_type).as_str(),
+            metadata_json,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Rows written by an incremental save
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeltaSummary {
+    /// Node rows inserted (new or rewritten)
+    pub nodes_written: usize,
+    /// Node rows deleted
+    pub nodes_removed: usize,
+    /// Edge rows inserted or updated
+    pub edges_written: usize,
+    /// Edge rows deleted
+    pub edges_removed: usize,
+}
+
+/// Persist only the changes to a project's PDG
+///
+/// Unlike [`save_pdg`], which rewrites every row, this applies a delta in a
+/// single transaction:
+/// - `removed_node_ids` are deleted together with every edge touching them.
+/// - `added_nodes` are inserted; a node whose ID is already stored is
+///   rewritten, and its old edges are dropped.
+/// - `removed_edges` and `added_edges` are given as
+///   `(caller node ID, callee node ID, ...)` and applied last, so added edges
+///   may refer to nodes added in the same call.
+///
+/// Edges referring to a node ID that is not stored fail with
+/// [`PdgStoreError::Serialization`]. The trigram index is left untouched.
+pub fn save_incremental(
+    storage: &mut Storage,
+    project_id: &str,
+    added_nodes: &[PDGNode],
+    removed_node_ids: &[String],
+    added_edges: &[(String, String, PDGEdge)],
+    removed_edges: &[(String, String, PDGEdgeType)],
+) -> Result<DeltaSummary> {
+    storage.retry_on_busy(|storage| {
+        save_incremental_once(
+            storage,
+            project_id,
+            added_nodes,
+            removed_node_ids,
+            added_edges,
+            removed_edges,
+        )
+    })
+}
+
+fn save_incremental_once(
+    storage: &mut Storage,
+    project_id: &str,
+    added_nodes: &[PDGNode],
+    removed_node_ids: &[String],
+    added_edges: &[(String, String, PDGEdge)],
+    removed_edges: &[(String, String, PDGEdgeType)],
+) -> Result<DeltaSummary> {
+    let scope = DigestScope {
+        files: &[],
+        node_ids: added_nodes
+            .iter()
+            .map(|node| node.id.as_str())
+            .chain(removed_node_ids.iter().map(String::as_str))
+            .chain(
+                added_edges
+                    .iter()
+                    .flat_map(|(caller, callee, _)| [caller.as_str(), callee.as_str()]),
+            )
+            .chain(
+                removed_edges
+                    .iter()
+                    .flat_map(|(caller, callee, _)| [caller.as_str(), callee.as_str()]),
+            )
+            .collect(),
+    };
+    let tx = storage.conn_mut().transaction()?;
+    let before = scope.digest(&tx, project_id)?;
+    let summary = apply_delta_tx(
+        &tx,
+        project_id,
+        added_nodes,
+        removed_node_ids,
+        added_edges,
+        removed_edges,
+    )?;
+    update_checksum(&tx, project_id, &before, &scope.digest(&tx, project_id)?)?;
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// Rewrite the stored rows of `files` from the in-memory PDG
+///
+/// Every node stored for one of `files` is replaced by the PDG's current
+/// nodes for that file, and every PDG edge touching those nodes is written
+/// back. Rows of other files keep their database IDs. Deleted files may be
+/// passed too; their rows are simply removed. The trigram index is saved in
+/// the same transaction, as in [`save_pdg`].
+pub fn save_file_delta(
+    storage: &mut Storage,
+    project_id: &str,
+    pdg: &ProgramDependenceGraph,
+    files: &[String],
+) -> Result<DeltaSummary> {
+    storage.retry_on_busy(|storage| save_file_delta_once(storage, project_id, pdg, files))
+}
+
+fn save_file_delta_once(
+    storage: &mut Storage,
+    project_id: &str,
+    pdg: &ProgramDependenceGraph,
+    files: &[String],
+) -> Result<DeltaSummary> {
+    let mut removed_node_ids = Vec::new();
+    {
+        let mut stmt = storage
+            .conn()
+            .prepare("SELECT node_id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2")?;
+        for file in files {
+            let ids = stmt
+                .query_map(params![project_id, file], |row| row.get::<_, String>(0))?
+                .collect::<SqliteResult<Vec<_>>>()?;
+            removed_node_ids.extend(ids);
+        }
+    }
+
+    let mut added_nodes = Vec::new();
+    let mut touched = std::collections::HashSet::new();
+    for file in files {
+        for node_idx in pdg.nodes_in_file(file) {
+            if let Some(node) = pdg.get_node(node_idx) {
+                added_nodes.push(node.clone());
+                touched.insert(node_idx);
+            }
+        }
+    }
+
+    let mut added_edges = Vec::new();
+    for edge_idx in pdg.edge_indices() {
+        let Some((source, target)) = pdg.edge_endpoints(edge_idx) else {
+            continue;
+        };
+        if !touched.contains(&source) && !touched.contains(&target) {
+            continue;
+        }
+        if let (Some(caller), Some(callee), Some(edge)) = (
+            pdg.get_node(source),
+            pdg.get_node(target),
+            pdg.get_edge(edge_idx),
+        ) {
+            added_edges.push((caller.id.clone(), callee.id.clone(), edge.clone()));
+        }
+    }
+
+    let scope = DigestScope {
+        files,
+        node_ids: added_nodes.iter().map(|node| node.id.as_str()).collect(),
+    };
+    let tx = storage.conn_mut().transaction()?;
+    let before = scope.digest(&tx, project_id)?;
+    let summary = apply_delta_tx(
+        &tx,
+        project_id,
+        &added_nodes,
+        &removed_node_ids,
+        &added_edges,
+        &[],
+    )?;
+    if let Err(e) = save_trigram_index_tx(&tx, project_id, pdg.trigram_index()) {
+        tracing::warn!("Failed to save trigram index: {e}");
+    }
+    update_checksum(&tx, project_id, &before, &scope.digest(&tx, project_id)?)?;
+    tx.commit()?;
+    Ok(summary)
+}
+
+/// Apply a PDG delta within an existing transaction
+fn apply_delta_tx(
+    tx: &rusqlite::Transaction<'_>,
+    project_id: &str,
+    added_nodes: &[PDGNode],
+    removed_node_ids: &[String],
+    added_edges: &[(String, String, PDGEdge)],
+    removed_edges: &[(String, String, PDGEdgeType)],
+) -> Result<DeltaSummary> {
+    let mut summary = DeltaSummary::default();
+
+    let replaced = added_nodes.iter().map(|node| node.id.as_str());
+    for node_id in removed_node_ids.iter().map(String::as_str).chain(replaced) {
+        summary.edges_removed += tx.execute(
+            "DELETE FROM intel_edges WHERE
+             caller_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND node_id = ?2) OR
+             callee_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND node_id = ?2)",
+            params![project_id, node_id],
+        )?;
+        summary.nodes_removed += tx.execute(
+            "DELETE FROM intel_nodes WHERE project_id = ?1 AND node_id = ?2",
+            params![project_id, node_id],
+        )?;
+    }
+
+    let mut db_ids: HashMap<&str, i64> = HashMap::new();
+    for node in added_nodes {
+        db_ids.insert(node.id.as_str(), insert_node_tx(tx, project_id, node)?);
+        summary.nodes_written += 1;
+    }
+
+    let resolve = |node_id: &str| -> Result<i64> {
+        if let Some(&db_id) = db_ids.get(node_id) {
+            return Ok(db_id);
+        }
+        tx.query_row(
+            "SELECT id FROM intel_nodes WHERE project_id = ?1 AND node_id = ?2 LIMIT 1",
+            params![project_id, node_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                PdgStoreError::Serialization(format!("Edge refers to unknown node: {}", node_id))
+            }
+            other => other.into(),
+        })
+    };
+
+    for (caller, callee, edge_type) in removed_edges {
+        let (Ok(caller_id), Ok(callee_id)) = (resolve(caller.as_str()), resolve(callee.as_str()))
+        else {
+            continue;
+        };
+        summary.edges_removed += tx.execute(
+            "DELETE FROM intel_edges WHERE caller_id = ?1 AND callee_id = ?2 AND edge_type = ?3",
+            params![caller_id, callee_id, convert_edge_type(edge_type).as_str()],
+        )?;
+    }
+
+    for (caller, callee, edge) in added_edges {
+        upsert_edge_tx(
+            tx,
+            resolve(caller.as_str())?,
+            resolve(callee.as_str())?,
+            edge,
+        )?;
+        summary.edges_written += 1;
+    }
+
+    Ok(summary)
+}
+
 /// Save trigram index within an existing transaction.
 fn save_trigram_index_tx(
     tx: &rusqlite::Transaction<'_>,
@@ -338,31 +641,20 @@ pub fn load_pdg(storage: &Storage, project_id: &str) -> Result<ProgramDependence
     let mut pdg = ProgramDependenceGraph::new();
     let mut db_id_to_node_id: HashMap<i64, NodeId> = HashMap::new();
 
-    // Load all nodes for the project
-    let mut nodes_stmt = storage.conn().prepare(
-        "SELECT id, file_path, node_id, symbol_name, qualified_name, language, node_type, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format
-         FROM intel_nodes WHERE project_id = ?1"
-    )?;
-
-    let node_rows: Vec<NodeDbRow> = nodes_stmt
-        .query_map(params![project_id], |row| {
-            Ok((
-                row.get::<_, i64>(0)?,             // id
-                row.get::<_, String>(1)?,          // file_path
-                row.get::<_, String>(2)?,          // node_id
-                row.get::<_, String>(3)?,          // symbol_name
-                row.get::<_, String>(4)?,          // qualified_name
-                row.get::<_, String>(5)?,          // language
-                row.get::<_, String>(6)?,          // node_type
-                row.get::<_, Option<i32>>(7)?,     // complexity
-                row.get::<_, String>(8)?,          // content_hash
-                row.get::<_, Option<Vec<u8>>>(9)?, // embedding
-                row.get::<_, Option<i64>>(10)?,    // byte_range_start
-                row.get::<_, Option<i64>>(11)?,    // byte_range_end
-                row.get::<_, Option<i32>>(12)?,    // embedding_format
-            ))
-        })?
-        .collect::<SqliteResult<Vec<_>>>()?;
+    let node_rows = query_node_rows(storage.conn(), project_id)?;
+    let edge_rows = query_edge_rows(storage.conn(), project_id)?;
+
+    // Projects saved before checksums were recorded have none to verify
+    if let Some(expected) = stored_checksum(storage, project_id)? {
+        let found = checksum_rows(&node_rows, &edge_rows);
+        if found != expected {
+            return Err(PdgStoreError::Corrupted {
+                project_id: project_id.to_string(),
+                expected,
+                found,
+            });
+        }
+    }
 
     for (
         db_id,
@@ -398,26 +690,6 @@ pub fn load_pdg(storage: &Storage, project_id: &str) -> Result<ProgramDependence
         db_id_to_node_id.insert(db_id, node_id);
     }
 
-    // Load all edges for the project using a JOIN query
-    let mut edges_stmt = storage.conn().prepare(
-        "SELECT e.caller_id, e.callee_id, e.edge_type, e.metadata
-         FROM intel_edges e
-         INNER JOIN intel_nodes n1 ON e.caller_id = n1.id
-         INNER JOIN intel_nodes n2 ON e.callee_id = n2.id
-         WHERE n1.project_id = ?1 AND n2.project_id = ?1",
-    )?;
-
-    let edge_rows: Vec<(i64, i64, String, Option<String>)> = edges_stmt
-        .query_map(params![project_id], |row| {
-            Ok((
-                row.get::<_, i64>(0)?,            // caller_id
-                row.get::<_, i64>(1)?,            // callee_id
-                row.get::<_, String>(2)?,         // edge_type
-                row.get::<_, Option<String>>(3)?, // metadata
-            ))
-        })?
-        .collect::<SqliteResult<Vec<_>>>()?;
-
     for (caller_id, callee_id, edge_type_str, metadata_json) in edge_rows {
         let caller_node_id = *db_id_to_node_id
             .get(&caller_id)
@@ -427,26 +699,7 @@ pub fn load_pdg(storage: &Storage, project_id: &str) -> Result<ProgramDependence
             .get(&callee_id)
             .ok_or_else(|| PdgStoreError::NodeNotFound(callee_id))?;
 
-        let edge_type = StorageEdgeType::from_str_name(&edge_type_str).ok_or_else(|| {
-            PdgStoreError::Deserialization(format!("Invalid edge type: {}", edge_type_str))
-        })?;
-
-        let metadata = match metadata_json.as_deref() {
-            Some(json) => serde_json::from_str(json).map_err(|e| {
-                PdgStoreError::Deserialization(format!("Invalid edge metadata: {}", e))
-            })?,
-            None => StorageEdgeMetadata {
-                call_count: None,
-                variable_name: None,
-                confidence: None,
-            },
-        };
-
-        let pdg_edge = PDGEdge {
-            edge_type: convert_storage_edge_type(&edge_type),
-            metadata: convert_storage_edge_metadata(&metadata),
-        };
-
+        let pdg_edge = pdg_edge_from_row(&edge_type_str, metadata_json.as_deref())?;
         pdg.add_edge(caller_node_id, callee_node_id, pdg_edge);
     }
 
@@ -461,85 +714,930 @@ pub fn load_pdg(storage: &Storage, project_id: &str) -> Result<ProgramDependence
     Ok(pdg)
 }
 
-/// Check if a PDG exists for a project
+/// Run [`save_pdg`] on tokio's blocking thread pool
 ///
-/// # Arguments
+/// Lets async callers (e.g. leserve handlers) persist a large PDG without
+/// stalling the runtime. The storage lock is held on the blocking thread for
+/// the duration of the save.
 ///
-/// * `storage` - Reference to the storage backend
-/// * `project_id` - Project identifier to check
+/// # Errors
 ///
-/// # Returns
+/// Same as [`save_pdg`]; a poisoned lock or a panicking save is reported as
+/// [`PdgStoreError::TaskFailed`].
+pub async fn save_pdg_async(
+    storage: Arc<Mutex<Storage>>,
+    project_id: String,
+    pdg: Arc<ProgramDependenceGraph>,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || {
+        let mut storage = storage
+            .lock()
+            .map_err(|e| PdgStoreError::TaskFailed(format!("Storage lock error: {}", e)))?;
+        save_pdg(&mut storage, &project_id, &pdg)
+    })
+    .await
+    .map_err(|e| PdgStoreError::TaskFailed(e.to_string()))?
+}
+
+/// Run [`load_pdg`] on tokio's blocking thread pool
 ///
-/// `true` if the project has at least one node, `false` otherwise
-pub fn pdg_exists(storage: &Storage, project_id: &str) -> SqliteResult<bool> {
-    let count: i64 = storage.conn().query_row(
-        "SELECT COUNT(*) FROM intel_nodes WHERE project_id = ?1",
-        params![project_id],
-        |row| row.get(0),
-    )?;
-    Ok(count > 0)
+/// The async counterpart of [`load_pdg`]; see [`save_pdg_async`].
+pub async fn load_pdg_async(
+    storage: Arc<Mutex<Storage>>,
+    project_id: String,
+) -> Result<ProgramDependenceGraph> {
+    tokio::task::spawn_blocking(move || {
+        let storage = storage
+            .lock()
+            .map_err(|e| PdgStoreError::TaskFailed(format!("Storage lock error: {}", e)))?;
+        load_pdg(&storage, &project_id)
+    })
+    .await
+    .map_err(|e| PdgStoreError::TaskFailed(e.to_string()))?
 }
 
-/// Delete a PDG from storage
-pub fn delete_pdg(storage: &mut Storage, project_id: &str) -> SqliteResult<()> {
-    // Delete edges first
-    storage.conn().execute(
-        "DELETE FROM intel_edges WHERE caller_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1)",
-        params![project_id],
+/// Load all node rows of a project
+fn query_node_rows(conn: &Connection, project_id: &str) -> SqliteResult<Vec<NodeDbRow>> {
+    let mut nodes_stmt = conn.prepare(
+        "SELECT id, file_path, node_id, symbol_name, qualified_name, language, node_type, complexity, content_hash, embedding, byte_range_start, byte_range_end, embedding_format
+         FROM intel_nodes WHERE project_id = ?1"
     )?;
 
-    // Then delete nodes
-    storage.conn().execute(
-        "DELETE FROM intel_nodes WHERE project_id = ?1",
-        params![project_id],
-    )?;
+    let node_rows = nodes_stmt
+        .query_map(params![project_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,             // id
+                row.get::<_, String>(1)?,          // file_path
+                row.get::<_, String>(2)?,          // node_id
+                row.get::<_, String>(3)?,          // symbol_name
+                row.get::<_, String>(4)?,          // qualified_name
+                row.get::<_, String>(5)?,          // language
+                row.get::<_, String>(6)?,          // node_type
+                row.get::<_, Option<i32>>(7)?,     // complexity
+                row.get::<_, String>(8)?,          // content_hash
+                row.get::<_, Option<Vec<u8>>>(9)?, // embedding
+                row.get::<_, Option<i64>>(10)?,    // byte_range_start
+                row.get::<_, Option<i64>>(11)?,    // byte_range_end
+                row.get::<_, Option<i32>>(12)?,    // embedding_format
+            ))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(node_rows)
+}
 
-    // Delete indexed files records
-    storage.conn().execute(
-        "DELETE FROM indexed_files WHERE project_id = ?1",
-        params![project_id],
+/// Load all edge rows between nodes of a project
+fn query_edge_rows(conn: &Connection, project_id: &str) -> SqliteResult<Vec<EdgeDbRow>> {
+    let mut edges_stmt = conn.prepare(
+        "SELECT e.caller_id, e.callee_id, e.edge_type, e.metadata
+         FROM intel_edges e
+         INNER JOIN intel_nodes n1 ON e.caller_id = n1.id
+         INNER JOIN intel_nodes n2 ON e.callee_id = n2.id
+         WHERE n1.project_id = ?1 AND n2.project_id = ?1",
     )?;
 
-    // Delete trigram index
-    if let Err(e) = delete_trigram_index(storage, project_id) {
-        tracing::warn!(
-            "Failed to delete trigram index for project {}: {e}",
-            project_id
-        );
-    }
+    let edge_rows = edges_stmt
+        .query_map(params![project_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,            // caller_id
+                row.get::<_, i64>(1)?,            // callee_id
+                row.get::<_, String>(2)?,         // edge_type
+                row.get::<_, Option<String>>(3)?, // metadata
+            ))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(edge_rows)
+}
 
-    Ok(())
+/// Node and edge counts plus a content hash of a project's stored PDG
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdgChecksum {
+    /// Number of node rows
+    pub node_count: usize,
+    /// Number of edge rows between the project's nodes
+    pub edge_count: usize,
+    /// Sum of per-row BLAKE3 hashes, independent of row order and database IDs
+    pub hash: String,
 }
 
-/// Delete nodes and edges for a specific file in a project
-pub fn delete_file_data(
-    storage: &mut Storage,
-    project_id: &str,
-    file_path: &str,
-) -> SqliteResult<()> {
-    // Delete edges where caller or callee belongs to this file
-    storage.conn().execute(
-        "DELETE FROM intel_edges WHERE 
-         caller_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2) OR
-         callee_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2)",
-        params![project_id, file_path],
-    )?;
+impl std::fmt::Display for PdgChecksum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} edges (hash {})",
+            self.node_count,
+            self.edge_count,
+            &self.hash[..self.hash.len().min(16)]
+        )
+    }
+}
 
-    // Delete nodes for this file
-    storage.conn().execute(
-        "DELETE FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2",
+/// Running checksum of a set of node and edge rows
+///
+/// Row hashes are summed lane by lane (four wrapping `u64`s), so a write can
+/// subtract the rows it replaces and add the rows it writes without reading
+/// the rest of the project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RowDigest {
+    nodes: i64,
+    edges: i64,
+    lanes: [u64; 4],
+}
+
+impl RowDigest {
+    fn add_row(&mut self, hash: &[u8; 32]) {
+        for (lane, chunk) in self.lanes.iter_mut().zip(hash.chunks_exact(8)) {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(chunk);
+            *lane = lane.wrapping_add(u64::from_le_bytes(bytes));
+        }
+    }
+
+    fn add_node(&mut self, hash: &[u8; 32]) {
+        self.nodes += 1;
+        self.add_row(hash);
+    }
+
+    fn add_edge(&mut self, caller: &str, callee: &str, edge_type: &str, metadata: Option<&str>) {
+        self.edges += 1;
+        self.add_row(&row_hash(
+            b"edge",
+            &[
+                caller.as_bytes(),
+                callee.as_bytes(),
+                edge_type.as_bytes(),
+                metadata.unwrap_or_default().as_bytes(),
+            ],
+        ));
+    }
+
+    /// `self - removed + added`
+    fn replace(self, removed: &RowDigest, added: &RowDigest) -> RowDigest {
+        let mut lanes = self.lanes;
+        for ((lane, old), new) in lanes.iter_mut().zip(removed.lanes).zip(added.lanes) {
+            *lane = lane.wrapping_sub(old).wrapping_add(new);
+        }
+        RowDigest {
+            nodes: self.nodes - removed.nodes + added.nodes,
+            edges: self.edges - removed.edges + added.edges,
+            lanes,
+        }
+    }
+
+    fn to_checksum(self) -> PdgChecksum {
+        PdgChecksum {
+            node_count: self.nodes.max(0) as usize,
+            edge_count: self.edges.max(0) as usize,
+            hash: self
+                .lanes
+                .iter()
+                .map(|lane| format!("{:016x}", lane))
+                .collect(),
+        }
+    }
+
+    /// Parse a recorded checksum; `None` for one in another format
+    fn from_checksum(checksum: &PdgChecksum) -> Option<RowDigest> {
+        if checksum.hash.len() != 64 || !checksum.hash.is_ascii() {
+            return None;
+        }
+        let mut lanes = [0u64; 4];
+        for (i, lane) in lanes.iter_mut().enumerate() {
+            *lane = u64::from_str_radix(&checksum.hash[i * 16..(i + 1) * 16], 16).ok()?;
+        }
+        Some(RowDigest {
+            nodes: checksum.node_count as i64,
+            edges: checksum.edge_count as i64,
+            lanes,
+        })
+    }
+}
+
+/// BLAKE3 hash of one row, with each field length-prefixed
+fn row_hash(kind: &[u8], fields: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(kind);
+    for field in fields {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field);
+    }
+    *hasher.finalize().as_bytes()
+}
+
+/// Hash of a node row's checksummed columns
+#[allow(clippy::too_many_arguments)]
+fn node_hash(
+    node_id: &str,
+    file_path: &str,
+    symbol_name: &str,
+    language: &str,
+    node_type: &str,
+    complexity: Option<i32>,
+    start: Option<i64>,
+    end: Option<i64>,
+) -> [u8; 32] {
+    let numbers = format!("{:?} {:?} {:?}", complexity, start, end);
+    row_hash(
+        b"node",
+        &[
+            node_id.as_bytes(),
+            file_path.as_bytes(),
+            symbol_name.as_bytes(),
+            language.as_bytes(),
+            node_type.as_bytes(),
+            numbers.as_bytes(),
+        ],
+    )
+}
+
+/// Compute the checksum of loaded node and edge rows
+///
+/// Edges are hashed by the `node_id` of their endpoints, so the checksum does
+/// not depend on database IDs.
+fn checksum_rows(nodes: &[NodeDbRow], edges: &[EdgeDbRow]) -> PdgChecksum {
+    let node_ids: HashMap<i64, &str> = nodes.iter().map(|row| (row.0, row.2.as_str())).collect();
+    let mut digest = RowDigest::default();
+    for (
+        _,
+        file_path,
+        node_id,
+        symbol_name,
+        _,
+        language,
+        node_type,
+        complexity,
+        _,
+        _,
+        start,
+        end,
+        _,
+    ) in nodes
+    {
+        digest.add_node(&node_hash(
+            node_id,
+            file_path,
+            symbol_name,
+            language,
+            node_type,
+            *complexity,
+            *start,
+            *end,
+        ));
+    }
+    for (caller_id, callee_id, edge_type, metadata) in edges {
+        digest.add_edge(
+            node_ids.get(caller_id).copied().unwrap_or_default(),
+            node_ids.get(callee_id).copied().unwrap_or_default(),
+            edge_type,
+            metadata.as_deref(),
+        );
+    }
+    digest.to_checksum()
+}
+
+/// Node columns read for checksumming; the embedding is left out
+const CHECKSUM_NODE_COLUMNS: &str = "id, node_id, file_path, symbol_name, language, node_type, \
+                                     complexity, byte_range_start, byte_range_end";
+
+/// Edge columns read for checksumming, with the endpoints' `node_id`s
+const CHECKSUM_EDGE_QUERY: &str =
+    "SELECT e.caller_id, e.callee_id, n1.node_id, n2.node_id, e.edge_type, e.metadata
+     FROM intel_edges e
+     INNER JOIN intel_nodes n1 ON e.caller_id = n1.id
+     INNER JOIN intel_nodes n2 ON e.callee_id = n2.id";
+
+/// Database IDs and row hashes of the nodes selected by `stmt`
+fn hash_node_rows(
+    stmt: &mut rusqlite::Statement<'_>,
+    params: impl rusqlite::Params,
+) -> SqliteResult<Vec<(i64, [u8; 32])>> {
+    stmt.query_map(params, |row| {
+        Ok((
+            row.get(0)?,
+            node_hash(
+                &row.get::<_, String>(1)?,
+                &row.get::<_, String>(2)?,
+                &row.get::<_, String>(3)?,
+                &row.get::<_, String>(4)?,
+                &row.get::<_, String>(5)?,
+                row.get(6)?,
+                row.get(7)?,
+                row.get(8)?,
+            ),
+        ))
+    })?
+    .collect()
+}
+
+/// Add the edges selected by `stmt` to `digest`, skipping ones in `seen`
+fn digest_edge_rows(
+    stmt: &mut rusqlite::Statement<'_>,
+    params: impl rusqlite::Params,
+    seen: &mut std::collections::HashSet<(i64, i64, String)>,
+    digest: &mut RowDigest,
+) -> SqliteResult<()> {
+    let mut rows = stmt.query(params)?;
+    while let Some(row) = rows.next()? {
+        let edge_type: String = row.get(4)?;
+        if !seen.insert((row.get(0)?, row.get(1)?, edge_type.clone())) {
+            continue;
+        }
+        digest.add_edge(
+            &row.get::<_, String>(2)?,
+            &row.get::<_, String>(3)?,
+            &edge_type,
+            row.get::<_, Option<String>>(5)?.as_deref(),
+        );
+    }
+    Ok(())
+}
+
+/// Checksum of every stored row of a project
+fn project_digest(conn: &Connection, project_id: &str) -> SqliteResult<RowDigest> {
+    let mut digest = RowDigest::default();
+    let mut nodes = conn.prepare(&format!(
+        "SELECT {} FROM intel_nodes WHERE project_id = ?1",
+        CHECKSUM_NODE_COLUMNS
+    ))?;
+    for (_, hash) in hash_node_rows(&mut nodes, params![project_id])? {
+        digest.add_node(&hash);
+    }
+    let mut edges = conn.prepare(&format!(
+        "{} WHERE n1.project_id = ?1 AND n2.project_id = ?1",
+        CHECKSUM_EDGE_QUERY
+    ))?;
+    digest_edge_rows(
+        &mut edges,
+        params![project_id],
+        &mut std::collections::HashSet::new(),
+        &mut digest,
+    )?;
+    Ok(digest)
+}
+
+/// The rows an incremental write may change: the nodes of `files`, the
+/// nodes named in `node_ids`, and every edge touching one of them
+#[derive(Default)]
+struct DigestScope<'a> {
+    files: &'a [String],
+    node_ids: Vec<&'a str>,
+}
+
+impl DigestScope<'_> {
+    /// Checksum of the rows currently in scope
+    fn digest(&self, conn: &Connection, project_id: &str) -> SqliteResult<RowDigest> {
+        let mut nodes: HashMap<i64, [u8; 32]> = HashMap::new();
+        let mut by_file = conn.prepare_cached(&format!(
+            "SELECT {} FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2",
+            CHECKSUM_NODE_COLUMNS
+        ))?;
+        for file in self.files {
+            nodes.extend(hash_node_rows(&mut by_file, params![project_id, file])?);
+        }
+        let mut by_node_id = conn.prepare_cached(&format!(
+            "SELECT {} FROM intel_nodes WHERE project_id = ?1 AND node_id = ?2",
+            CHECKSUM_NODE_COLUMNS
+        ))?;
+        for node_id in &self.node_ids {
+            nodes.extend(hash_node_rows(
+                &mut by_node_id,
+                params![project_id, node_id],
+            )?);
+        }
+
+        let mut digest = RowDigest::default();
+        for hash in nodes.values() {
+            digest.add_node(hash);
+        }
+        let mut outgoing = conn.prepare_cached(&format!(
+            "{} WHERE e.caller_id = ?1 AND n2.project_id = ?2",
+            CHECKSUM_EDGE_QUERY
+        ))?;
+        let mut incoming = conn.prepare_cached(&format!(
+            "{} WHERE e.callee_id = ?1 AND n1.project_id = ?2",
+            CHECKSUM_EDGE_QUERY
+        ))?;
+        let mut seen = std::collections::HashSet::new();
+        for id in nodes.keys() {
+            digest_edge_rows(
+                &mut outgoing,
+                params![id, project_id],
+                &mut seen,
+                &mut digest,
+            )?;
+            digest_edge_rows(
+                &mut incoming,
+                params![id, project_id],
+                &mut seen,
+                &mut digest,
+            )?;
+        }
+        Ok(digest)
+    }
+}
+
+/// Record `checksum` as the checksum of a project's stored PDG
+fn write_checksum(conn: &Connection, project_id: &str, checksum: &PdgChecksum) -> SqliteResult<()> {
+    conn.execute(
+        "INSERT INTO pdg_checksums (project_id, node_count, edge_count, checksum, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(project_id) DO UPDATE SET
+            node_count = excluded.node_count,
+            edge_count = excluded.edge_count,
+            checksum = excluded.checksum,
+            updated_at = excluded.updated_at",
+        params![
+            project_id,
+            checksum.node_count as i64,
+            checksum.edge_count as i64,
+            checksum.hash,
+            chrono::Utc::now().timestamp(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Recompute and record the checksum of a project's stored PDG
+///
+/// Reads every row of the project, so it is only used by full saves and
+/// garbage collection; incremental writes go through [`update_checksum`].
+pub(crate) fn refresh_checksum(conn: &Connection, project_id: &str) -> SqliteResult<()> {
+    write_checksum(
+        conn,
+        project_id,
+        &project_digest(conn, project_id)?.to_checksum(),
+    )
+}
+
+/// Swap the `removed` rows of a write for the `added` ones in a project's
+/// recorded checksum
+///
+/// A project without a recorded checksum (or with one in an older format)
+/// stays unverified until its next full save.
+fn update_checksum(
+    conn: &Connection,
+    project_id: &str,
+    removed: &RowDigest,
+    added: &RowDigest,
+) -> SqliteResult<()> {
+    let recorded = stored_checksum_conn(conn, project_id)?;
+    match recorded.as_ref().and_then(RowDigest::from_checksum) {
+        Some(digest) => write_checksum(
+            conn,
+            project_id,
+            &digest.replace(removed, added).to_checksum(),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Checksum recorded for a project's stored PDG, if any
+pub fn stored_checksum(storage: &Storage, project_id: &str) -> SqliteResult<Option<PdgChecksum>> {
+    stored_checksum_conn(storage.conn(), project_id)
+}
+
+fn stored_checksum_conn(conn: &Connection, project_id: &str) -> SqliteResult<Option<PdgChecksum>> {
+    conn.query_row(
+        "SELECT node_count, edge_count, checksum FROM pdg_checksums WHERE project_id = ?1",
+        params![project_id],
+        |row| {
+            Ok(PdgChecksum {
+                node_count: row.get::<_, i64>(0)? as usize,
+                edge_count: row.get::<_, i64>(1)? as usize,
+                hash: row.get(2)?,
+            })
+        },
+    )
+    .optional()
+}
+
+/// Convert a stored edge type and metadata JSON into a PDG edge
+fn pdg_edge_from_row(edge_type_str: &str, metadata_json: Option<&str>) -> Result<PDGEdge> {
+    let edge_type = StorageEdgeType::from_str_name(edge_type_str).ok_or_else(|| {
+        PdgStoreError::Deserialization(format!("Invalid edge type: {}", edge_type_str))
+    })?;
+
+    let metadata = match metadata_json {
+        Some(json) => serde_json::from_str(json)
+            .map_err(|e| PdgStoreError::Deserialization(format!("Invalid edge metadata: {}", e)))?,
+        None => StorageEdgeMetadata {
+            call_count: None,
+            variable_name: None,
+            confidence: None,
+            call_target: None,
+        },
+    };
+
+    Ok(PDGEdge {
+        edge_type: convert_storage_edge_type(&edge_type),
+        metadata: convert_storage_edge_metadata(&metadata),
+    })
+}
+
+/// Columns selected by [`query_nodes`], in order
+const NODE_COLUMNS: &str =
+    "id, file_path, node_id, symbol_name, language, node_type, complexity, byte_range_start, byte_range_end";
+
+/// Run a node query selecting [`NODE_COLUMNS`] and convert the rows
+fn query_nodes(
+    storage: &Storage,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<(i64, PDGNode)>> {
+    let mut stmt = storage.conn().prepare(sql)?;
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, Option<i32>>(6)?,
+                row.get::<_, Option<i64>>(7)?,
+                row.get::<_, Option<i64>>(8)?,
+            ))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    rows.into_iter()
+        .map(
+            |(db_id, file_path, id, name, language, node_type_str, complexity, start, end)| {
+                let node_type =
+                    StorageNodeType::from_str_name(&node_type_str).ok_or_else(|| {
+                        PdgStoreError::Deserialization(format!(
+                            "Invalid node type: {}",
+                            node_type_str
+                        ))
+                    })?;
+                Ok((
+                    db_id,
+                    PDGNode {
+                        id,
+                        node_type: convert_storage_node_type(&node_type),
+                        name,
+                        file_path: Arc::from(file_path),
+                        byte_range: (start.unwrap_or(0) as usize, end.unwrap_or(0) as usize),
+                        complexity: complexity.unwrap_or(0) as u32,
+                        language,
+                    },
+                ))
+            },
+        )
+        .collect()
+}
+
+/// Load the nodes of one file plus their immediate neighbors
+///
+/// Returns a PDG holding every node stored for `file_path`, every node
+/// directly connected to one of them (in either direction), and the edges
+/// that touch the file's nodes. Use this instead of [`load_pdg`] when only a
+/// single file needs to be explored.
+pub fn load_file_subgraph(
+    storage: &Storage,
+    project_id: &str,
+    file_path: &str,
+) -> Result<ProgramDependenceGraph> {
+    let mut pdg = ProgramDependenceGraph::new();
+    let mut db_id_to_node_id: HashMap<i64, NodeId> = HashMap::new();
+
+    let nodes = query_nodes(
+        storage,
+        &format!(
+            "SELECT {NODE_COLUMNS} FROM intel_nodes n
+             WHERE n.project_id = ?1 AND (
+                n.file_path = ?2
+                OR n.id IN (
+                    SELECT e.callee_id FROM intel_edges e
+                    JOIN intel_nodes f ON f.id = e.caller_id
+                    WHERE f.project_id = ?1 AND f.file_path = ?2
+                )
+                OR n.id IN (
+                    SELECT e.caller_id FROM intel_edges e
+                    JOIN intel_nodes f ON f.id = e.callee_id
+                    WHERE f.project_id = ?1 AND f.file_path = ?2
+                )
+             )
+             ORDER BY n.id"
+        ),
         params![project_id, file_path],
     )?;
+    for (db_id, node) in nodes {
+        db_id_to_node_id.insert(db_id, pdg.add_node(node));
+    }
 
-    // Delete indexed file record
+    let mut edges_stmt = storage.conn().prepare(
+        "SELECT e.caller_id, e.callee_id, e.edge_type, e.metadata
+         FROM intel_edges e
+         INNER JOIN intel_nodes n1 ON e.caller_id = n1.id
+         INNER JOIN intel_nodes n2 ON e.callee_id = n2.id
+         WHERE n1.project_id = ?1 AND n2.project_id = ?1
+           AND (n1.file_path = ?2 OR n2.file_path = ?2)",
+    )?;
+    let edge_rows: Vec<(i64, i64, String, Option<String>)> = edges_stmt
+        .query_map(params![project_id, file_path], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    for (caller_id, callee_id, edge_type_str, metadata_json) in edge_rows {
+        let (Some(&caller), Some(&callee)) = (
+            db_id_to_node_id.get(&caller_id),
+            db_id_to_node_id.get(&callee_id),
+        ) else {
+            continue;
+        };
+        let pdg_edge = pdg_edge_from_row(&edge_type_str, metadata_json.as_deref())?;
+        pdg.add_edge(caller, callee, pdg_edge);
+    }
+
+    Ok(pdg)
+}
+
+/// Load a single node by its PDG node ID, without edges
+pub fn load_node(storage: &Storage, project_id: &str, node_id: &str) -> Result<Option<PDGNode>> {
+    let nodes = query_nodes(
+        storage,
+        &format!(
+            "SELECT {NODE_COLUMNS} FROM intel_nodes
+             WHERE project_id = ?1 AND node_id = ?2
+             LIMIT 1"
+        ),
+        params![project_id, node_id],
+    )?;
+    Ok(nodes.into_iter().next().map(|(_, node)| node))
+}
+
+/// Load one page of a project's nodes, without edges
+///
+/// Nodes are returned in a stable (insertion) order, so consumers can stream
+/// a large graph by advancing `offset` by `limit` until a short page is
+/// returned.
+pub fn load_nodes_page(
+    storage: &Storage,
+    project_id: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<PDGNode>> {
+    let nodes = query_nodes(
+        storage,
+        &format!(
+            "SELECT {NODE_COLUMNS} FROM intel_nodes
+             WHERE project_id = ?1
+             ORDER BY id
+             LIMIT ?2 OFFSET ?3"
+        ),
+        params![project_id, limit as i64, offset as i64],
+    )?;
+    Ok(nodes.into_iter().map(|(_, node)| node).collect())
+}
+
+/// Check if a PDG exists for a project
+///
+/// # Arguments
+///
+/// * `storage` - Reference to the storage backend
+/// * `project_id` - Project identifier to check
+///
+/// # Returns
+///
+/// `true` if the project has at least one node, `false` otherwise
+pub fn pdg_exists(storage: &Storage, project_id: &str) -> SqliteResult<bool> {
+    let count: i64 = storage.conn().query_row(
+        "SELECT COUNT(*) FROM intel_nodes WHERE project_id = ?1",
+        params![project_id],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Delete a PDG from storage
+pub fn delete_pdg(storage: &mut Storage, project_id: &str) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| delete_pdg_once(storage, project_id))
+}
+
+fn delete_pdg_once(storage: &mut Storage, project_id: &str) -> SqliteResult<()> {
+    // Delete edges first
     storage.conn().execute(
+        "DELETE FROM intel_edges WHERE caller_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1)",
+        params![project_id],
+    )?;
+
+    // Then delete nodes
+    storage.conn().execute(
+        "DELETE FROM intel_nodes WHERE project_id = ?1",
+        params![project_id],
+    )?;
+
+    // Delete indexed files records
+    storage.conn().execute(
+        "DELETE FROM indexed_files WHERE project_id = ?1",
+        params![project_id],
+    )?;
+
+    // Delete recorded parse errors
+    storage.conn().execute(
+        "DELETE FROM parse_errors WHERE project_id = ?1",
+        params![project_id],
+    )?;
+
+    storage.conn().execute(
+        "DELETE FROM pdg_checksums WHERE project_id = ?1",
+        params![project_id],
+    )?;
+
+    storage.conn().execute(
+        "DELETE FROM symbol_hashes WHERE project_id = ?1",
+        params![project_id],
+    )?;
+
+    // Delete trigram index
+    if let Err(e) = delete_trigram_index(storage, project_id) {
+        tracing::warn!(
+            "Failed to delete trigram index for project {}: {e}",
+            project_id
+        );
+    }
+
+    Ok(())
+}
+
+/// Drop an index an older version stored under a project's directory name
+///
+/// Rows used to be keyed by the directory name, `legacy_key`, which another
+/// project sharing the database may use as its own key. The rows are only
+/// removed when they can belong to nobody but the project at
+/// `canonical_path`: no project records `legacy_key` as its ID, every
+/// project recorded under that base name lives at `canonical_path`, and every
+/// file indexed under the key lies inside it. Returns whether rows were
+/// removed.
+pub fn migrate_legacy_project_key(
+    storage: &mut Storage,
+    legacy_key: &str,
+    canonical_path: &std::path::Path,
+) -> SqliteResult<bool> {
+    if !has_indexed_files(storage, legacy_key) {
+        return Ok(false);
+    }
+
+    let canonical = canonical_path.to_string_lossy();
+    let foreign_owners: i64 = storage.conn().query_row(
+        "SELECT COUNT(*) FROM project_metadata
+         WHERE unique_project_id = ?1 OR (base_name = ?1 AND canonical_path != ?2)",
+        params![legacy_key, canonical],
+        |row| row.get(0),
+    )?;
+    if foreign_owners > 0 {
+        return Ok(false);
+    }
+    let files = get_indexed_files(storage, legacy_key)?;
+    if !files
+        .keys()
+        .all(|file| std::path::Path::new(file).starts_with(canonical_path))
+    {
+        return Ok(false);
+    }
+
+    delete_pdg(storage, legacy_key)?;
+    Ok(true)
+}
+
+/// Delete nodes and edges for a specific file in a project
+pub fn delete_file_data(
+    storage: &mut Storage,
+    project_id: &str,
+    file_path: &str,
+) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| delete_file_data_once(storage, project_id, file_path))
+}
+
+fn delete_file_data_once(
+    storage: &mut Storage,
+    project_id: &str,
+    file_path: &str,
+) -> SqliteResult<()> {
+    let files = [file_path.to_string()];
+    let scope = DigestScope {
+        files: &files,
+        ..Default::default()
+    };
+    let tx = storage.conn_mut().transaction()?;
+    let before = scope.digest(&tx, project_id)?;
+
+    // Delete edges where caller or callee belongs to this file
+    tx.execute(
+        "DELETE FROM intel_edges WHERE 
+         caller_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2) OR
+         callee_id IN (SELECT id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2)",
+        params![project_id, file_path],
+    )?;
+
+    // Delete nodes for this file
+    tx.execute(
+        "DELETE FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, file_path],
+    )?;
+
+    // Delete indexed file record
+    tx.execute(
         "DELETE FROM indexed_files WHERE project_id = ?1 AND file_path = ?2",
         params![project_id, file_path],
     )?;
 
+    // Delete recorded parse errors
+    tx.execute(
+        "DELETE FROM parse_errors WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, file_path],
+    )?;
+
+    update_checksum(&tx, project_id, &before, &RowDigest::default())?;
+    tx.commit()?;
     Ok(())
 }
 
+/// Re-key the stored data of a renamed file to its new path
+///
+/// Node rows keep their database IDs, so edges and anything else keyed on
+/// them survive the move; only `file_path` and the path prefix of each
+/// `node_id` change. The indexed-file record, recorded parse errors, symbol
+/// hashes and the project's global symbols move with them. The stored
+/// trigram index covers paths too, so it is dropped and rebuilt on the next
+/// load.
+///
+/// # Returns
+///
+/// The number of node rows moved
+pub fn rename_file_data(
+    storage: &mut Storage,
+    project_id: &str,
+    old_path: &str,
+    new_path: &str,
+) -> SqliteResult<usize> {
+    storage.retry_on_busy(|storage| rename_file_data_once(storage, project_id, old_path, new_path))
+}
+
+fn rename_file_data_once(
+    storage: &mut Storage,
+    project_id: &str,
+    old_path: &str,
+    new_path: &str,
+) -> SqliteResult<usize> {
+    let files = [old_path.to_string(), new_path.to_string()];
+    let scope = DigestScope {
+        files: &files,
+        ..Default::default()
+    };
+    let tx = storage.conn_mut().transaction()?;
+    let before = scope.digest(&tx, project_id)?;
+    let moved = tx.execute(
+        "UPDATE intel_nodes SET
+            file_path = ?3,
+            node_id = CASE WHEN substr(node_id, 1, length(?2) + 1) = ?2 || ':'
+                THEN ?3 || substr(node_id, length(?2) + 1)
+                ELSE node_id END
+         WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, old_path, new_path],
+    )?;
+    tx.execute(
+        "UPDATE OR REPLACE indexed_files SET file_path = ?3, last_indexed = ?4
+         WHERE project_id = ?1 AND file_path = ?2",
+        params![
+            project_id,
+            old_path,
+            new_path,
+            chrono::Utc::now().timestamp()
+        ],
+    )?;
+    tx.execute(
+        "UPDATE OR REPLACE unresolved_imports SET file_path = ?3 WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, old_path, new_path],
+    )?;
+    tx.execute(
+        "UPDATE parse_errors SET file_path = ?3 WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, old_path, new_path],
+    )?;
+    tx.execute(
+        "UPDATE OR REPLACE symbol_hashes SET
+            file_path = ?3,
+            symbol_id = CASE WHEN substr(symbol_id, 1, length(?2) + 1) = ?2 || ':'
+                THEN ?3 || substr(symbol_id, length(?2) + 1)
+                ELSE symbol_id END
+         WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, old_path, new_path],
+    )?;
+    tx.execute(
+        "UPDATE global_symbols SET file_path = ?3 WHERE project_id = ?1 AND file_path = ?2",
+        params![project_id, old_path, new_path],
+    )?;
+    tx.execute(
+        "DELETE FROM trigram_index WHERE project_id = ?1",
+        params![project_id],
+    )?;
+    update_checksum(&tx, project_id, &before, &scope.digest(&tx, project_id)?)?;
+    tx.commit()?;
+    Ok(moved)
+}
+
 /// Get all indexed files for a project with their hashes
 pub fn get_indexed_files(
     storage: &Storage,
@@ -581,16 +1679,230 @@ pub fn update_indexed_file(
     project_id: &str,
     file_path: &str,
     hash: &str,
+) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| update_indexed_file_once(storage, project_id, file_path, hash))
+}
+
+fn update_indexed_file_once(
+    storage: &mut Storage,
+    project_id: &str,
+    file_path: &str,
+    hash: &str,
 ) -> SqliteResult<()> {
     storage.conn().execute(
         "INSERT INTO indexed_files (file_path, project_id, file_hash, last_indexed)
          VALUES (?1, ?2, ?3, ?4)
-         ON CONFLICT(file_path) DO UPDATE SET file_hash = ?3, last_indexed = ?4",
+         ON CONFLICT(project_id, file_path) DO UPDATE SET file_hash = ?3, last_indexed = ?4",
         params![file_path, project_id, hash, chrono::Utc::now().timestamp()],
     )?;
     Ok(())
 }
 
+/// Get the stored per-symbol source hashes of a file, keyed by node ID
+pub fn get_symbol_hashes(
+    storage: &Storage,
+    project_id: &str,
+    file_path: &str,
+) -> SqliteResult<HashMap<String, String>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT symbol_id, content_hash FROM symbol_hashes
+         WHERE project_id = ?1 AND file_path = ?2",
+    )?;
+    let hashes = stmt
+        .query_map(params![project_id, file_path], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<SqliteResult<HashMap<_, _>>>()?;
+    Ok(hashes)
+}
+
+/// Replace the stored per-symbol source hashes of a file
+///
+/// Kept apart from [`delete_file_data`] so the previous hashes are still
+/// available while a changed file's nodes are replaced.
+pub fn replace_symbol_hashes(
+    storage: &mut Storage,
+    project_id: &str,
+    file_path: &str,
+    hashes: &HashMap<String, String>,
+) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| {
+        let tx = storage.conn_mut().transaction()?;
+        tx.execute(
+            "DELETE FROM symbol_hashes WHERE project_id = ?1 AND file_path = ?2",
+            params![project_id, file_path],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO symbol_hashes (project_id, file_path, symbol_id, content_hash)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (symbol_id, hash) in hashes {
+                stmt.execute(params![project_id, file_path, symbol_id, hash])?;
+            }
+        }
+        tx.commit()
+    })
+}
+
+/// Delete the stored per-symbol source hashes of a file
+pub fn delete_symbol_hashes(
+    storage: &mut Storage,
+    project_id: &str,
+    file_path: &str,
+) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| {
+        storage.conn().execute(
+            "DELETE FROM symbol_hashes WHERE project_id = ?1 AND file_path = ?2",
+            params![project_id, file_path],
+        )?;
+        Ok(())
+    })
+}
+
+/// Edge row whose caller or callee node no longer exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingEdge {
+    /// Database ID of the caller node
+    pub caller_id: i64,
+    /// Database ID of the callee node
+    pub callee_id: i64,
+    /// Stored edge type
+    pub edge_type: String,
+}
+
+/// Indexed-file record whose hash no longer matches the file on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHashMismatch {
+    /// Path recorded in `indexed_files`
+    pub file_path: String,
+    /// Hash recorded at indexing time
+    pub recorded_hash: String,
+    /// Hash of the current file contents (None if the file is gone)
+    pub actual_hash: Option<String>,
+}
+
+/// Result of [`verify`]
+#[derive(Debug, Clone, Default)]
+pub struct ConsistencyReport {
+    /// Edges referencing node rows that do not exist
+    pub dangling_edges: Vec<DanglingEdge>,
+    /// Node IDs whose file has no `indexed_files` record
+    pub orphan_nodes: Vec<String>,
+    /// Indexed files whose recorded hash is stale
+    pub hash_mismatches: Vec<FileHashMismatch>,
+}
+
+impl ConsistencyReport {
+    /// True when no problems were found
+    pub fn is_consistent(&self) -> bool {
+        self.dangling_edges.is_empty()
+            && self.orphan_nodes.is_empty()
+            && self.hash_mismatches.is_empty()
+    }
+}
+
+/// Changes made by [`repair`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairSummary {
+    /// Dangling edge rows deleted
+    pub dangling_edges_removed: usize,
+    /// Stale `indexed_files` records dropped so the next incremental index re-parses them
+    pub file_records_reset: usize,
+}
+
+/// Check the stored PDG of a project for inconsistencies
+///
+/// Reports edges pointing at missing nodes, nodes belonging to files that are
+/// not tracked in `indexed_files`, and indexed files whose recorded hash no
+/// longer matches the file on disk. Orphan nodes are only reported when the
+/// project tracks indexed files at all.
+pub fn verify(storage: &Storage, project_id: &str) -> Result<ConsistencyReport> {
+    let mut report = ConsistencyReport::default();
+
+    let mut edges_stmt = storage.conn().prepare(
+        "SELECT e.caller_id, e.callee_id, e.edge_type
+         FROM intel_edges e
+         LEFT JOIN intel_nodes n1 ON e.caller_id = n1.id
+         LEFT JOIN intel_nodes n2 ON e.callee_id = n2.id
+         WHERE (n1.id IS NULL OR n2.id IS NULL)
+           AND (n1.project_id = ?1 OR n2.project_id = ?1
+                OR (n1.id IS NULL AND n2.id IS NULL))
+         ORDER BY e.caller_id, e.callee_id",
+    )?;
+    report.dangling_edges = edges_stmt
+        .query_map(params![project_id], |row| {
+            Ok(DanglingEdge {
+                caller_id: row.get(0)?,
+                callee_id: row.get(1)?,
+                edge_type: row.get(2)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+
+    if has_indexed_files(storage, project_id) {
+        let mut orphan_stmt = storage.conn().prepare(
+            "SELECT n.node_id FROM intel_nodes n
+             LEFT JOIN indexed_files f
+               ON f.file_path = n.file_path AND f.project_id = n.project_id
+             WHERE n.project_id = ?1 AND f.file_path IS NULL
+             ORDER BY n.node_id",
+        )?;
+        report.orphan_nodes = orphan_stmt
+            .query_map(params![project_id], |row| row.get(0))?
+            .collect::<SqliteResult<Vec<String>>>()?;
+    }
+
+    let mut indexed: Vec<(String, String)> = get_indexed_files(storage, project_id)?
+        .into_iter()
+        .collect();
+    indexed.sort();
+    for (file_path, recorded_hash) in indexed {
+        let actual_hash = std::fs::read(&file_path)
+            .ok()
+            .map(|bytes| blake3::hash(&bytes).to_hex().to_string());
+        if actual_hash.as_deref() != Some(recorded_hash.as_str()) {
+            report.hash_mismatches.push(FileHashMismatch {
+                file_path,
+                recorded_hash,
+                actual_hash,
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// Fix the problems [`verify`] finds that can be fixed without re-parsing
+///
+/// Dangling edges are deleted. Stale `indexed_files` records are dropped so
+/// the next incremental index re-parses those files (and replaces any orphan
+/// nodes they own). Returns the report taken before repairing.
+pub fn repair(
+    storage: &mut Storage,
+    project_id: &str,
+) -> Result<(ConsistencyReport, RepairSummary)> {
+    let report = verify(storage, project_id)?;
+    let mut summary = RepairSummary::default();
+
+    let tx = storage.conn_mut().transaction()?;
+    for edge in &report.dangling_edges {
+        summary.dangling_edges_removed += tx.execute(
+            "DELETE FROM intel_edges WHERE caller_id = ?1 AND callee_id = ?2 AND edge_type = ?3",
+            params![edge.caller_id, edge.callee_id, edge.edge_type],
+        )?;
+    }
+    for mismatch in &report.hash_mismatches {
+        summary.file_records_reset += tx.execute(
+            "DELETE FROM indexed_files WHERE project_id = ?1 AND file_path = ?2",
+            params![project_id, mismatch.file_path],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok((report, summary))
+}
+
 /// Save the trigram index for a project to storage.
 ///
 /// The trigram index is serialized to a binary blob and stored in the
@@ -694,6 +2006,7 @@ mod tests {
                     call_count: Some(5),
                     variable_name: None,
                     confidence: None,
+                    call_target: None,
                 },
             },
         );
@@ -767,6 +2080,387 @@ mod tests {
         assert!(!pdg_exists(&storage, "test_project").unwrap());
     }
 
+    fn multi_file_pdg() -> ProgramDependenceGraph {
+        let mut pdg = ProgramDependenceGraph::new();
+        let node = |id: &str, file: &str| PDGNode {
+            id: id.to_string(),
+            node_type: PDGNodeType::Function,
+            name: id.to_string(),
+            file_path: Arc::from(file),
+            byte_range: (0, 10),
+            complexity: 1,
+            language: "rust".to_string(),
+        };
+        let call = || PDGEdge {
+            edge_type: PDGEdgeType::Call,
+            metadata: PDGEdgeMetadata {
+                call_count: Some(1),
+                variable_name: None,
+                confidence: None,
+                call_target: None,
+            },
+        };
+
+        let a1 = pdg.add_node(node("a1", "a.rs"));
+        let a2 = pdg.add_node(node("a2", "a.rs"));
+        let b1 = pdg.add_node(node("b1", "b.rs"));
+        let c1 = pdg.add_node(node("c1", "c.rs"));
+        let d1 = pdg.add_node(node("d1", "d.rs"));
+        pdg.add_edge(a1, a2, call());
+        pdg.add_edge(a1, b1, call());
+        pdg.add_edge(c1, a2, call());
+        // Not connected to a.rs
+        pdg.add_edge(b1, d1, call());
+        pdg
+    }
+
+    #[test]
+    fn test_save_file_delta_only_rewrites_changed_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let mut pdg = multi_file_pdg();
+        save_pdg(&mut storage, "test_project", &pdg).unwrap();
+
+        let row_ids = |storage: &Storage, file: &str| -> Vec<i64> {
+            let mut stmt = storage
+                .conn()
+                .prepare("SELECT id FROM intel_nodes WHERE project_id = ?1 AND file_path = ?2 ORDER BY id")
+                .unwrap();
+            let ids = stmt
+                .query_map(params!["test_project", file], |row| row.get(0))
+                .unwrap()
+                .collect::<SqliteResult<Vec<_>>>()
+                .unwrap();
+            ids
+        };
+        let untouched: Vec<Vec<i64>> = ["b.rs", "c.rs", "d.rs"]
+            .iter()
+            .map(|file| row_ids(&storage, file))
+            .collect();
+        let old_a = row_ids(&storage, "a.rs");
+
+        // Change a.rs: drop a2 (and its edges) and add a3 called by a1
+        let a2 = pdg.find_by_id("a2").unwrap();
+        pdg.remove_node(a2);
+        let a1 = pdg.find_by_id("a1").unwrap();
+        let a3 = pdg.add_node(PDGNode {
+            id: "a3".to_string(),
+            node_type: PDGNodeType::Function,
+            name: "a3".to_string(),
+            file_path: Arc::from("a.rs"),
+            byte_range: (20, 30),
+            complexity: 1,
+            language: "rust".to_string(),
+        });
+        pdg.add_edge(
+            a1,
+            a3,
+            PDGEdge {
+                edge_type: PDGEdgeType::Call,
+                metadata: PDGEdgeMetadata {
+                    call_count: Some(1),
+                    variable_name: None,
+                    confidence: None,
+                    call_target: None,
+                },
+            },
+        );
+
+        let summary =
+            save_file_delta(&mut storage, "test_project", &pdg, &["a.rs".to_string()]).unwrap();
+        assert_eq!(summary.nodes_removed, 2);
+        assert_eq!(summary.nodes_written, 2);
+        // a1->b1 and a1->a3
+        assert_eq!(summary.edges_written, 2);
+
+        // Rows of other files were not rewritten
+        for (file, ids) in ["b.rs", "c.rs", "d.rs"].iter().zip(&untouched) {
+            assert_eq!(&row_ids(&storage, file), ids, "{file} was rewritten");
+        }
+        let new_a = row_ids(&storage, "a.rs");
+        assert!(new_a.iter().all(|id| !old_a.contains(id)));
+
+        let loaded = load_pdg(&storage, "test_project").unwrap();
+        assert_eq!(loaded.node_count(), 5);
+        assert!(loaded.find_by_id("a2").is_none());
+        // a1->b1, a1->a3, b1->d1; c1->a2 went away with a2
+        assert_eq!(loaded.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_save_incremental_edges() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        save_pdg(&mut storage, "test_project", &multi_file_pdg()).unwrap();
+
+        let call = PDGEdge {
+            edge_type: PDGEdgeType::Call,
+            metadata: PDGEdgeMetadata {
+                call_count: Some(1),
+                variable_name: None,
+                confidence: None,
+                call_target: None,
+            },
+        };
+        let summary = save_incremental(
+            &mut storage,
+            "test_project",
+            &[],
+            &[],
+            &[("d1".to_string(), "c1".to_string(), call.clone())],
+            &[("b1".to_string(), "d1".to_string(), PDGEdgeType::Call)],
+        )
+        .unwrap();
+        assert_eq!(summary.edges_written, 1);
+        assert_eq!(summary.edges_removed, 1);
+        assert_eq!(summary.nodes_written, 0);
+
+        let loaded = load_pdg(&storage, "test_project").unwrap();
+        assert_eq!(loaded.edge_count(), 4);
+
+        let err = save_incremental(
+            &mut storage,
+            "test_project",
+            &[],
+            &[],
+            &[("missing".to_string(), "c1".to_string(), call)],
+            &[],
+        );
+        assert!(matches!(err, Err(PdgStoreError::Serialization(_))));
+    }
+
+    #[test]
+    fn test_load_file_subgraph() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        save_pdg(&mut storage, "test_project", &multi_file_pdg()).unwrap();
+
+        let sub = load_file_subgraph(&storage, "test_project", "a.rs").unwrap();
+        let mut ids: Vec<String> = sub
+            .node_indices()
+            .filter_map(|id| sub.get_node(id))
+            .map(|node| node.id.clone())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a1", "a2", "b1", "c1"]);
+        // a1->a2, a1->b1, c1->a2; b1->d1 is outside the neighborhood
+        assert_eq!(sub.edge_count(), 3);
+        assert_eq!(sub.nodes_in_file("a.rs").len(), 2);
+
+        let empty = load_file_subgraph(&storage, "test_project", "missing.rs").unwrap();
+        assert_eq!(empty.node_count(), 0);
+    }
+
+    #[test]
+    fn test_load_nodes_page() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        save_pdg(&mut storage, "test_project", &multi_file_pdg()).unwrap();
+
+        let mut seen = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = load_nodes_page(&storage, "test_project", offset, 2).unwrap();
+            seen.extend(page.iter().map(|node| node.id.clone()));
+            if page.len() < 2 {
+                break;
+            }
+            offset += 2;
+        }
+        assert_eq!(seen, vec!["a1", "a2", "b1", "c1", "d1"]);
+    }
+
+    #[test]
+    fn test_verify_and_repair_dangling_edge() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+
+        save_pdg(&mut storage, "test_project", &create_test_pdg()).unwrap();
+        update_indexed_file(&mut storage, "test_project", "test.rs", "stale-hash").unwrap();
+
+        let report = verify(&storage, "test_project").unwrap();
+        assert!(report.dangling_edges.is_empty());
+        assert!(report.orphan_nodes.is_empty());
+
+        // Point an edge at a node row that does not exist
+        let caller_id: i64 = storage
+            .conn()
+            .query_row(
+                "SELECT id FROM intel_nodes WHERE project_id = 'test_project' AND node_id = 'func1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO intel_edges (caller_id, callee_id, edge_type) VALUES (?1, 9999, 'call')",
+                params![caller_id],
+            )
+            .unwrap();
+
+        let report = verify(&storage, "test_project").unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(
+            report.dangling_edges,
+            vec![DanglingEdge {
+                caller_id,
+                callee_id: 9999,
+                edge_type: "call".to_string(),
+            }]
+        );
+        assert_eq!(report.hash_mismatches.len(), 1);
+        assert_eq!(report.hash_mismatches[0].actual_hash, None);
+
+        let (_, summary) = repair(&mut storage, "test_project").unwrap();
+        assert_eq!(summary.dangling_edges_removed, 1);
+        assert_eq!(summary.file_records_reset, 1);
+
+        let report = verify(&storage, "test_project").unwrap();
+        assert!(report.is_consistent());
+        // The valid edge survives the repair
+        assert_eq!(load_pdg(&storage, "test_project").unwrap().edge_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_async_save_and_load_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Arc::new(Mutex::new(Storage::open(temp_file.path()).unwrap()));
+        let pdg = Arc::new(multi_file_pdg());
+
+        save_pdg_async(
+            Arc::clone(&storage),
+            "test_project".to_string(),
+            Arc::clone(&pdg),
+        )
+        .await
+        .unwrap();
+        let loaded = load_pdg_async(Arc::clone(&storage), "test_project".to_string())
+            .await
+            .unwrap();
+
+        let node_ids = |graph: &ProgramDependenceGraph| {
+            let mut ids: Vec<String> = graph
+                .node_indices()
+                .filter_map(|idx| graph.get_node(idx).map(|node| node.id.clone()))
+                .collect();
+            ids.sort();
+            ids
+        };
+        let edges = |graph: &ProgramDependenceGraph| {
+            let mut edges: Vec<(String, String)> = graph
+                .edge_indices()
+                .filter_map(|idx| {
+                    let (source, target) = graph.edge_endpoints(idx)?;
+                    Some((
+                        graph.get_node(source)?.id.clone(),
+                        graph.get_node(target)?.id.clone(),
+                    ))
+                })
+                .collect();
+            edges.sort();
+            edges
+        };
+        assert_eq!(node_ids(&loaded), node_ids(&pdg));
+        assert_eq!(edges(&loaded), edges(&pdg));
+
+        // The sync API sees the same data
+        let sync_loaded = load_pdg(&storage.lock().unwrap(), "test_project").unwrap();
+        assert_eq!(node_ids(&sync_loaded), node_ids(&pdg));
+    }
+
+    #[test]
+    fn test_load_pdg_detects_corrupted_rows() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+        let mut pdg = multi_file_pdg();
+        save_pdg(&mut storage, "test_project", &pdg).unwrap();
+
+        // Incremental writes keep the checksum current
+        pdg.remove_file("a.rs");
+        save_file_delta(&mut storage, "test_project", &pdg, &["a.rs".to_string()]).unwrap();
+        let checksum = stored_checksum(&storage, "test_project").unwrap().unwrap();
+        assert_eq!((checksum.node_count, checksum.edge_count), (3, 1));
+        assert_eq!(load_pdg(&storage, "test_project").unwrap().node_count(), 3);
+        let full_checksum = |storage: &Storage| {
+            project_digest(storage.conn(), "test_project")
+                .unwrap()
+                .to_checksum()
+        };
+        assert_eq!(full_checksum(&storage), checksum);
+
+        // Renames and deletes only re-hash the rows they touch, and still
+        // agree with a full recomputation. Global symbols move with the file.
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO global_symbols (symbol_id, project_id, symbol_name, symbol_type, file_path)
+                 VALUES ('sym_b1', 'test_project', 'b1', 'function', 'b.rs')",
+                [],
+            )
+            .unwrap();
+        let symbol_path = |storage: &Storage| -> String {
+            storage
+                .conn()
+                .query_row(
+                    "SELECT file_path FROM global_symbols WHERE symbol_id = 'sym_b1'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        rename_file_data(&mut storage, "test_project", "b.rs", "e.rs").unwrap();
+        let renamed = stored_checksum(&storage, "test_project").unwrap().unwrap();
+        assert_eq!(renamed, full_checksum(&storage));
+        assert_ne!(renamed.hash, checksum.hash);
+        assert_eq!(symbol_path(&storage), "e.rs");
+        rename_file_data(&mut storage, "test_project", "e.rs", "b.rs").unwrap();
+        assert_eq!(
+            stored_checksum(&storage, "test_project").unwrap().unwrap(),
+            checksum
+        );
+        assert_eq!(symbol_path(&storage), "b.rs");
+
+        // A row changed behind the store's back no longer matches
+        storage
+            .conn()
+            .execute(
+                "UPDATE intel_nodes SET symbol_name = 'tampered' WHERE node_id = 'b1'",
+                [],
+            )
+            .unwrap();
+        match load_pdg(&storage, "test_project") {
+            Err(PdgStoreError::Corrupted {
+                project_id,
+                expected,
+                found,
+            }) => {
+                assert_eq!(project_id, "test_project");
+                assert_eq!(expected, checksum);
+                assert_eq!(found.node_count, 3);
+                assert_ne!(found.hash, expected.hash);
+            }
+            other => panic!(
+                "expected Corrupted, got {:?}",
+                other.map(|g| g.node_count())
+            ),
+        }
+
+        // So does a truncated graph
+        storage
+            .conn()
+            .execute("DELETE FROM intel_nodes WHERE node_id = 'b1'", [])
+            .unwrap();
+        assert!(matches!(
+            load_pdg(&storage, "test_project"),
+            Err(PdgStoreError::Corrupted { found, .. }) if found.node_count == 2
+        ));
+
+        // Deleting the project clears its checksum
+        delete_pdg(&mut storage, "test_project").unwrap();
+        assert_eq!(stored_checksum(&storage, "test_project").unwrap(), None);
+    }
+
     #[test]
     fn test_convert_node_types() {
         assert_eq!(
@@ -862,6 +2556,7 @@ mod tests {
             call_count: Some(42),
             variable_name: Some("x".to_string()),
             confidence: None,
+            call_target: None,
         };
 
         let storage_meta = convert_edge_metadata(&pdg_meta);
@@ -919,6 +2614,7 @@ mod tests {
                     call_count: None,
                     variable_name: None,
                     confidence: None,
+                    call_target: None,
                 },
             },
         );
@@ -932,6 +2628,7 @@ mod tests {
                     call_count: None,
                     variable_name: Some("child_instance".to_string()),
                     confidence: None,
+                    call_target: None,
                 },
             },
         );
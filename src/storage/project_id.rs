@@ -5,6 +5,39 @@
 use blake3;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::process::Command;
+
+/// Marker file, relative to the project root, holding the identity token
+/// used by [`IdentityStrategy::FileMarker`]
+pub const PROJECT_ID_MARKER: &str = ".leindex/project-id";
+
+/// How the hash part of a [`UniqueProjectId`] is derived
+///
+/// Only [`PathHash`](Self::PathHash) depends on where the project lives; the
+/// other strategies keep the ID when the project is moved, as long as its
+/// directory name stays the same. When the marker or repository cannot be
+/// read, they fall back to the path hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdentityStrategy {
+    /// BLAKE3 hash of the canonical project path (default)
+    #[default]
+    PathHash,
+    /// Hash of the token in [`PROJECT_ID_MARKER`], which is created with a
+    /// fresh random token on first use and should be committed or kept
+    /// with the project
+    FileMarker,
+    /// Hash of the repository's root commit, so every checkout of the same
+    /// repository shares one identity
+    ///
+    /// That includes clones and worktrees with the same directory name: they
+    /// get the same ID, and when their storage falls back outside the
+    /// project (`LEINDEX_HOME`, the XDG data dir or the temp dir) they share
+    /// one index, which each of them re-indexes from its own files. Use
+    /// [`PathHash`](Self::PathHash) or [`FileMarker`](Self::FileMarker) for
+    /// checkouts that must be indexed independently.
+    GitRoot,
+}
 
 /// Unique project identifier with conflict resolution via instance counter
 ///
@@ -38,7 +71,8 @@ pub struct UniqueProjectId {
     /// Base name extracted from project directory name
     pub base_name: String,
 
-    /// First 8 hex characters of BLAKE3 hash of canonical path
+    /// First 8 hex characters of BLAKE3 hash of canonical path, or of the
+    /// identity marker under a content-based [`IdentityStrategy`]
     pub path_hash: String,
 
     /// Instance counter (0 for original, incremented for clones)
@@ -123,6 +157,125 @@ impl UniqueProjectId {
         }
     }
 
+    /// Generate a unique project ID using the given identity strategy
+    ///
+    /// Unlike [`generate`](Self::generate), an existing ID with the same base
+    /// name and hash is this project seen before (possibly at another path
+    /// under a content-based strategy), so its instance is reused instead of
+    /// counting it as a conflict. The ID is therefore stable across runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use leindex::storage::{IdentityStrategy, UniqueProjectId};
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("/home/user/projects/leindex");
+    /// let id = UniqueProjectId::generate_with_strategy(path, &[], IdentityStrategy::PathHash);
+    /// assert_eq!(id, UniqueProjectId::generate(path, &[]));
+    /// let again =
+    ///     UniqueProjectId::generate_with_strategy(path, &[id.clone()], IdentityStrategy::PathHash);
+    /// assert_eq!(again, id);
+    /// ```
+    #[must_use]
+    pub fn generate_with_strategy(
+        project_path: &Path,
+        existing_ids: &[UniqueProjectId],
+        strategy: IdentityStrategy,
+    ) -> Self {
+        Self::identity(project_path, strategy).resolve_instance(existing_ids)
+    }
+
+    /// ID of the project at `project_path` under `strategy`, at instance 0
+    ///
+    /// Names a project before the IDs it could conflict with are known, e.g.
+    /// to pick its storage location; [`resolve_instance`](Self::resolve_instance)
+    /// completes it.
+    #[must_use]
+    pub fn identity(project_path: &Path, strategy: IdentityStrategy) -> Self {
+        let identity_hash = match strategy {
+            IdentityStrategy::PathHash => None,
+            IdentityStrategy::FileMarker => Self::marker_hash(project_path),
+            IdentityStrategy::GitRoot => Self::git_root_hash(project_path),
+        };
+        let generated = Self::generate(project_path, &[]);
+        let Some(path_hash) = identity_hash else {
+            if strategy != IdentityStrategy::PathHash {
+                tracing::warn!(
+                    "Cannot derive a {:?} project identity for {}; using the path hash",
+                    strategy,
+                    project_path.display()
+                );
+            }
+            return generated;
+        };
+        Self {
+            path_hash,
+            ..generated
+        }
+    }
+
+    /// Take the instance of the existing ID with the same base name and
+    /// hash, or the next free instance for the base name if there is none
+    #[must_use]
+    pub fn resolve_instance(self, existing_ids: &[UniqueProjectId]) -> Self {
+        let instance = existing_ids
+            .iter()
+            .find(|id| id.base_name == self.base_name && id.path_hash == self.path_hash)
+            .map(|id| id.instance)
+            .unwrap_or_else(|| Self::find_next_instance(&self.base_name, existing_ids));
+        Self { instance, ..self }
+    }
+
+    /// Hash of the project's marker token, creating the marker if missing
+    fn marker_hash(project_path: &Path) -> Option<String> {
+        let marker = project_path.join(PROJECT_ID_MARKER);
+        let token = match std::fs::read_to_string(&marker) {
+            Ok(token) if !token.trim().is_empty() => token.trim().to_string(),
+            Ok(_) => return None,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let nanos = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_nanos())
+                    .unwrap_or_default();
+                let seed = format!(
+                    "{}:{}:{}",
+                    project_path.display(),
+                    nanos,
+                    std::process::id()
+                );
+                let token = blake3::hash(seed.as_bytes()).to_hex().to_string();
+                std::fs::create_dir_all(marker.parent()?).ok()?;
+                std::fs::write(&marker, format!("{}\n", token)).ok()?;
+                token
+            }
+            Err(_) => return None,
+        };
+        Some(Self::hash_path(&token))
+    }
+
+    /// Hash of the repository's root commit, if `project_path` is in one
+    ///
+    /// Histories with several root commits use the smallest hash.
+    fn git_root_hash(project_path: &Path) -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(project_path)
+            .args(["rev-list", "--max-parents=0", "HEAD"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let root = stdout
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .min()?;
+        Some(Self::hash_path(root))
+    }
+
     /// Compute BLAKE3 hash of path and return first 8 hex characters
     ///
     /// # Arguments
@@ -551,4 +704,121 @@ mod tests {
         assert_eq!(id2.instance, 1);
         assert_eq!(id3.instance, 2);
     }
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_path_hash_strategy_matches_generate() {
+        let path = Path::new("/home/user/projects/leindex");
+        let id = UniqueProjectId::generate_with_strategy(path, &[], IdentityStrategy::PathHash);
+        assert_eq!(id, UniqueProjectId::generate(path, &[]));
+        assert_eq!(IdentityStrategy::default(), IdentityStrategy::PathHash);
+    }
+
+    #[test]
+    fn test_file_marker_identity_survives_move() {
+        let temp = tempfile::tempdir().unwrap();
+        let original = temp.path().join("a").join("myproject");
+        std::fs::create_dir_all(&original).unwrap();
+
+        let id =
+            UniqueProjectId::generate_with_strategy(&original, &[], IdentityStrategy::FileMarker);
+        assert!(original.join(PROJECT_ID_MARKER).is_file());
+        assert_eq!(id.base_name, "myproject");
+        assert_eq!(id.path_hash.len(), 8);
+        assert_eq!(
+            UniqueProjectId::generate_with_strategy(&original, &[], IdentityStrategy::FileMarker),
+            id
+        );
+
+        // Move the project; the marker travels with it
+        let moved = temp.path().join("b").join("myproject");
+        std::fs::create_dir_all(moved.parent().unwrap()).unwrap();
+        std::fs::rename(&original, &moved).unwrap();
+
+        let after_move = UniqueProjectId::generate_with_strategy(
+            &moved,
+            std::slice::from_ref(&id),
+            IdentityStrategy::FileMarker,
+        );
+        assert_eq!(after_move, id);
+        // ...where the path hash would have changed
+        assert_ne!(
+            UniqueProjectId::generate(&moved, &[]).path_hash,
+            UniqueProjectId::generate(&original, &[]).path_hash
+        );
+
+        // A different project with the same name is still a conflict
+        let other = temp.path().join("c").join("myproject");
+        std::fs::create_dir_all(&other).unwrap();
+        let other_id = UniqueProjectId::generate_with_strategy(
+            &other,
+            std::slice::from_ref(&id),
+            IdentityStrategy::FileMarker,
+        );
+        assert_ne!(other_id.path_hash, id.path_hash);
+        assert_eq!(other_id.instance, 1);
+    }
+
+    #[test]
+    fn test_git_root_identity_is_shared_by_checkouts() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("one").join("project");
+        std::fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-q"]);
+        std::fs::write(repo.join("README.md"), "hello").unwrap();
+        git(&repo, &["add", "README.md"]);
+        git(&repo, &["commit", "-q", "-m", "root"]);
+
+        let id = UniqueProjectId::generate_with_strategy(&repo, &[], IdentityStrategy::GitRoot);
+        assert_eq!(id.path_hash.len(), 8);
+
+        // A later commit does not change the identity, and neither does
+        // checking the repository out somewhere else
+        std::fs::write(repo.join("README.md"), "hello again").unwrap();
+        git(&repo, &["commit", "-q", "-am", "second"]);
+        let clone = temp.path().join("two").join("project");
+        std::fs::create_dir_all(clone.parent().unwrap()).unwrap();
+        git(
+            temp.path(),
+            &[
+                "clone",
+                "-q",
+                repo.to_str().unwrap(),
+                clone.to_str().unwrap(),
+            ],
+        );
+        assert_eq!(
+            UniqueProjectId::generate_with_strategy(&repo, &[], IdentityStrategy::GitRoot),
+            id
+        );
+        assert_eq!(
+            UniqueProjectId::generate_with_strategy(
+                &clone,
+                std::slice::from_ref(&id),
+                IdentityStrategy::GitRoot
+            ),
+            id
+        );
+
+        // Outside a repository the path hash is used
+        let plain = temp.path().join("plain");
+        std::fs::create_dir_all(&plain).unwrap();
+        assert_eq!(
+            UniqueProjectId::generate_with_strategy(&plain, &[], IdentityStrategy::GitRoot),
+            UniqueProjectId::generate(&plain, &[])
+        );
+    }
 }
@@ -4,7 +4,7 @@
 //! unique project IDs, display names, and clone relationships.
 
 use crate::storage::UniqueProjectId;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 use std::path::Path;
 use thiserror::Error;
 
@@ -105,6 +105,21 @@ impl ProjectMetadata {
         Ok(ids)
     }
 
+    /// Unique ID stored for the project at `canonical_path`, if any.
+    pub fn find_id_by_path(
+        conn: &rusqlite::Connection,
+        canonical_path: &str,
+    ) -> Result<Option<UniqueProjectId>> {
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT unique_project_id FROM project_metadata WHERE canonical_path = ?1",
+                params![canonical_path],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(id.and_then(|id| UniqueProjectId::parse_id(&id)))
+    }
+
     /// Save project metadata to the database.
     pub fn save(&self, conn: &rusqlite::Connection) -> Result<()> {
         conn.execute(
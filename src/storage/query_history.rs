@@ -0,0 +1,198 @@
+// Query history and saved searches
+//
+// Every search run from the CLI is appended to a short per-project history,
+// trimmed to the newest `QUERY_HISTORY_LIMIT` entries, so recent
+// investigations can be listed again. Queries can also be saved under a name
+// and re-run by that name; saved queries are never trimmed.
+
+use crate::storage::schema::Storage;
+use rusqlite::{params, OptionalExtension, Result as SqliteResult};
+use serde::{Deserialize, Serialize};
+
+/// History entries kept per project; older ones are dropped on insert
+pub const QUERY_HISTORY_LIMIT: usize = 100;
+
+/// A query from the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryHistoryEntry {
+    /// Query text
+    pub query: String,
+    /// Unix time the query was run
+    pub recorded_at: i64,
+}
+
+/// A query saved under a name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedQuery {
+    /// Name the query was saved under
+    pub name: String,
+    /// Query text
+    pub query: String,
+    /// Unix time the query was last saved
+    pub saved_at: i64,
+}
+
+/// Append `query` to the project's history and drop entries past the limit
+pub fn record_query(storage: &mut Storage, project_id: &str, query: &str) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| {
+        let tx = storage.conn_mut().transaction()?;
+        tx.execute(
+            "INSERT INTO query_history (project_id, query, recorded_at)
+             VALUES (?1, ?2, strftime('%s', 'now'))",
+            params![project_id, query],
+        )?;
+        tx.execute(
+            "DELETE FROM query_history WHERE project_id = ?1 AND id NOT IN (
+                 SELECT id FROM query_history WHERE project_id = ?1
+                 ORDER BY id DESC LIMIT ?2
+             )",
+            params![project_id, QUERY_HISTORY_LIMIT as i64],
+        )?;
+        tx.commit()
+    })
+}
+
+/// Load up to `limit` of the project's most recent queries, newest first
+pub fn recent_queries(
+    storage: &Storage,
+    project_id: &str,
+    limit: usize,
+) -> SqliteResult<Vec<QueryHistoryEntry>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT query, recorded_at FROM query_history
+         WHERE project_id = ?1
+         ORDER BY id DESC LIMIT ?2",
+    )?;
+    let entries = stmt
+        .query_map(params![project_id, limit as i64], |row| {
+            Ok(QueryHistoryEntry {
+                query: row.get(0)?,
+                recorded_at: row.get(1)?,
+            })
+        })?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// Save `query` under `name`, replacing any query already saved under it
+pub fn save_query(
+    storage: &mut Storage,
+    project_id: &str,
+    name: &str,
+    query: &str,
+) -> SqliteResult<()> {
+    storage.retry_on_busy(|storage| {
+        storage.conn().execute(
+            "INSERT OR REPLACE INTO saved_queries (project_id, name, query, saved_at)
+             VALUES (?1, ?2, ?3, strftime('%s', 'now'))",
+            params![project_id, name, query],
+        )?;
+        Ok(())
+    })
+}
+
+/// Load the query saved under `name`, if any
+pub fn load_saved_query(
+    storage: &Storage,
+    project_id: &str,
+    name: &str,
+) -> SqliteResult<Option<SavedQuery>> {
+    storage
+        .conn()
+        .query_row(
+            "SELECT name, query, saved_at FROM saved_queries
+             WHERE project_id = ?1 AND name = ?2",
+            params![project_id, name],
+            saved_query_from_row,
+        )
+        .optional()
+}
+
+/// Load all of the project's saved queries, ordered by name
+pub fn load_saved_queries(storage: &Storage, project_id: &str) -> SqliteResult<Vec<SavedQuery>> {
+    let mut stmt = storage.conn().prepare(
+        "SELECT name, query, saved_at FROM saved_queries
+         WHERE project_id = ?1
+         ORDER BY name",
+    )?;
+    let queries = stmt
+        .query_map(params![project_id], saved_query_from_row)?
+        .collect::<SqliteResult<Vec<_>>>()?;
+    Ok(queries)
+}
+
+/// Delete the query saved under `name`, returning whether one existed
+pub fn delete_saved_query(
+    storage: &mut Storage,
+    project_id: &str,
+    name: &str,
+) -> SqliteResult<bool> {
+    storage.retry_on_busy(|storage| {
+        let deleted = storage.conn().execute(
+            "DELETE FROM saved_queries WHERE project_id = ?1 AND name = ?2",
+            params![project_id, name],
+        )?;
+        Ok(deleted > 0)
+    })
+}
+
+fn saved_query_from_row(row: &rusqlite::Row<'_>) -> SqliteResult<SavedQuery> {
+    Ok(SavedQuery {
+        name: row.get(0)?,
+        query: row.get(1)?,
+        saved_at: row.get(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_history_is_per_project_newest_first_and_trimmed() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+
+        for i in 0..QUERY_HISTORY_LIMIT + 5 {
+            record_query(&mut storage, "proj", &format!("query {}", i)).unwrap();
+        }
+        record_query(&mut storage, "other", "elsewhere").unwrap();
+
+        let recent = recent_queries(&storage, "proj", 3).unwrap();
+        let texts: Vec<&str> = recent.iter().map(|e| e.query.as_str()).collect();
+        assert_eq!(texts, ["query 104", "query 103", "query 102"]);
+        let all = recent_queries(&storage, "proj", QUERY_HISTORY_LIMIT * 2).unwrap();
+        assert_eq!(all.len(), QUERY_HISTORY_LIMIT);
+        assert_eq!(all.last().unwrap().query, "query 5");
+        assert_eq!(recent_queries(&storage, "other", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_saved_queries_replace_and_delete_by_name() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut storage = Storage::open(temp_file.path()).unwrap();
+
+        save_query(&mut storage, "proj", "auth", "login handler").unwrap();
+        save_query(&mut storage, "proj", "auth", "session token").unwrap();
+        save_query(&mut storage, "proj", "db", "connection pool").unwrap();
+
+        let saved = load_saved_query(&storage, "proj", "auth").unwrap().unwrap();
+        assert_eq!(saved.query, "session token");
+        let names: Vec<String> = load_saved_queries(&storage, "proj")
+            .unwrap()
+            .into_iter()
+            .map(|q| q.name)
+            .collect();
+        assert_eq!(names, ["auth", "db"]);
+        assert!(load_saved_query(&storage, "other", "auth")
+            .unwrap()
+            .is_none());
+
+        assert!(delete_saved_query(&mut storage, "proj", "auth").unwrap());
+        assert!(!delete_saved_query(&mut storage, "proj", "auth").unwrap());
+        assert!(load_saved_query(&storage, "proj", "auth")
+            .unwrap()
+            .is_none());
+    }
+}
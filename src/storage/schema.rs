@@ -27,8 +27,27 @@ pub const PROJECT_READER_CACHE_SIZE_KIB: i64 = -2000; // 2 MiB
 /// Project store mmap cap (shared by writer and readers at OS level).
 pub const PROJECT_STORE_MMAP_SIZE: i64 = 67_108_864; // 64 MiB
 
+/// Default `busy_timeout`: how long SQLite waits on a lock before SQLITE_BUSY.
+pub const DEFAULT_BUSY_TIMEOUT_MS: u64 = 5000;
+
+/// Attempts made by [`Storage::retry_on_busy`] before giving up.
+pub const WRITE_RETRY_ATTEMPTS: u32 = 5;
+
+/// Backoff before the first retry; doubles on every further attempt.
+pub const WRITE_RETRY_BACKOFF_MS: u64 = 20;
+
+/// Smallest accepted `cache_size`, in KiB, when given as a negative value.
+pub const MIN_CACHE_SIZE_KIB: i64 = 128;
+/// Largest accepted `cache_size`, in KiB, when given as a negative value.
+pub const MAX_CACHE_SIZE_KIB: i64 = 1_048_576; // 1 GiB
+
+/// Smallest accepted `cache_size`, in pages, when given as a positive value.
+pub const MIN_CACHE_SIZE_PAGES: i64 = 32;
+/// Largest accepted `cache_size`, in pages, when given as a positive value.
+pub const MAX_CACHE_SIZE_PAGES: i64 = 262_144; // 1 GiB at 4 KiB pages
+
 /// Storage configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
     /// Database path
     pub db_path: String,
@@ -42,6 +61,37 @@ pub struct StorageConfig {
 
     /// mmap_size cap in bytes. Defaults to PROJECT_STORE_MMAP_SIZE.
     pub mmap_size: Option<i64>,
+
+    /// Milliseconds to wait on a locked database before failing with
+    /// SQLITE_BUSY. Defaults to DEFAULT_BUSY_TIMEOUT_MS.
+    #[serde(default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+
+    /// Key for encrypting the database file at rest with SQLCipher.
+    /// `None` (the default) keeps the database unencrypted. Requires the
+    /// `encryption` feature; the key is never serialized or logged.
+    #[serde(default, skip_serializing)]
+    pub encryption_key: Option<String>,
+}
+
+impl std::fmt::Debug for StorageConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StorageConfig")
+            .field("db_path", &self.db_path)
+            .field("wal_enabled", &self.wal_enabled)
+            .field("cache_size_kib", &self.cache_size_kib)
+            .field("mmap_size", &self.mmap_size)
+            .field("busy_timeout_ms", &self.busy_timeout_ms)
+            .field(
+                "encryption_key",
+                &self.encryption_key.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+fn default_busy_timeout_ms() -> u64 {
+    DEFAULT_BUSY_TIMEOUT_MS
 }
 
 impl Default for StorageConfig {
@@ -51,15 +101,181 @@ impl Default for StorageConfig {
             wal_enabled: true,
             cache_size_kib: Some(PROJECT_WRITER_CACHE_SIZE_KIB),
             mmap_size: Some(PROJECT_STORE_MMAP_SIZE),
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+            encryption_key: None,
         }
     }
 }
 
+impl StorageConfig {
+    /// Check that the configured values are within sane bounds
+    ///
+    /// `cache_size_kib` follows SQLite's `PRAGMA cache_size` convention: a
+    /// negative value is a size in KiB and must lie within
+    /// [`MIN_CACHE_SIZE_KIB`]..=[`MAX_CACHE_SIZE_KIB`]; a positive value is a
+    /// page count within [`MIN_CACHE_SIZE_PAGES`]..=[`MAX_CACHE_SIZE_PAGES`].
+    /// Fails with [`StorageError::InvalidConfig`], or
+    /// [`StorageError::EncryptionUnavailable`] for a key without SQLCipher.
+    pub fn validate(&self) -> Result<(), StorageError> {
+        if let Some(cache_size) = self.cache_size_kib {
+            let in_range = if cache_size < 0 {
+                (MIN_CACHE_SIZE_KIB..=MAX_CACHE_SIZE_KIB).contains(&-cache_size)
+            } else {
+                (MIN_CACHE_SIZE_PAGES..=MAX_CACHE_SIZE_PAGES).contains(&cache_size)
+            };
+            if !in_range {
+                return Err(StorageError::InvalidConfig(format!(
+                    "cache_size {} is out of range: use -{}..=-{} (KiB) or {}..={} (pages)",
+                    cache_size,
+                    MAX_CACHE_SIZE_KIB,
+                    MIN_CACHE_SIZE_KIB,
+                    MIN_CACHE_SIZE_PAGES,
+                    MAX_CACHE_SIZE_PAGES
+                )));
+            }
+        }
+        if let Some(key) = &self.encryption_key {
+            if key.is_empty() {
+                return Err(StorageError::InvalidConfig(
+                    "encryption_key must not be empty".to_string(),
+                ));
+            }
+            if !cfg!(feature = "encryption") {
+                return Err(StorageError::EncryptionUnavailable);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `err`, or an error it wraps, is SQLITE_BUSY or SQLITE_LOCKED
+fn is_busy_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if let Some(rusqlite::Error::SqliteFailure(failure, _)) =
+            err.downcast_ref::<rusqlite::Error>()
+        {
+            return matches!(
+                failure.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            );
+        }
+        current = err.source();
+    }
+    false
+}
+
+/// Storage errors
+///
+/// Errors that can occur when working with HybridStorage or a read-only
+/// [`Storage`].
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    /// Failed to connect to the storage backend
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    /// Failed to migrate data between storage backends
+    #[error("Migration failed: {0}")]
+    MigrationFailed(String),
+
+    /// The vector search extension is not available on the remote backend
+    #[error("Vector extension not available")]
+    VectorExtensionNotAvailable,
+
+    /// An error occurred in the local SQLite storage
+    #[error("Local storage error: {0}")]
+    LocalStorageError(String),
+
+    /// A query executed on the remote backend failed
+    #[error("Remote query failed: {0}")]
+    RemoteQueryFailed(String),
+
+    /// A write was attempted on storage opened with [`Storage::open_read_only`]
+    #[error("Storage at '{0}' is open read-only; writes are not allowed")]
+    ReadOnly(String),
+
+    /// The database could not be read with the configured encryption key
+    #[error(
+        "Cannot decrypt storage at '{0}': the encryption key is wrong, or the \
+         database is encrypted and no key was given"
+    )]
+    WrongKey(String),
+
+    /// A [`StorageConfig`] value is out of range
+    #[error("Invalid storage config: {0}")]
+    InvalidConfig(String),
+
+    /// The applied schema migrations do not match the ones in this build
+    #[error(transparent)]
+    SchemaMigration(#[from] crate::storage::migrations::MigrationError),
+
+    /// A record was written through a store scoped to another project
+    #[error("Node '{node_id}' belongs to project '{project_id}', not '{expected}'")]
+    ProjectMismatch {
+        /// Node being written
+        node_id: String,
+        /// Project the node belongs to
+        project_id: String,
+        /// Project the store is scoped to
+        expected: String,
+    },
+
+    /// An encryption key was configured but SQLCipher support is not built in
+    #[error("Storage encryption requires building with the `encryption` feature")]
+    EncryptionUnavailable,
+
+    /// The database is encrypted, and the code path opening it has no key
+    #[error(
+        "Storage at '{path}' is encrypted (or not a SQLite database); encrypted storage \
+         is not supported by {operation}"
+    )]
+    EncryptionUnsupported {
+        /// Database path
+        path: String,
+        /// What tried to open it (e.g. `phase analysis`)
+        operation: &'static str,
+    },
+}
+
+/// Header every unencrypted SQLite database file starts with
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Whether the database file at `path` is encrypted
+///
+/// An unencrypted SQLite file starts with a fixed header, which SQLCipher
+/// encrypts along with the rest of the file. Missing and empty files are
+/// not encrypted.
+pub fn is_encrypted_database(path: &Path) -> bool {
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    match std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+impl From<StorageError> for rusqlite::Error {
+    fn from(err: StorageError) -> Self {
+        let code = match err {
+            StorageError::ReadOnly(_) => rusqlite::ffi::SQLITE_READONLY,
+            StorageError::WrongKey(_) => rusqlite::ffi::SQLITE_NOTADB,
+            StorageError::InvalidConfig(_) | StorageError::EncryptionUnavailable => {
+                rusqlite::ffi::SQLITE_MISUSE
+            }
+            StorageError::ProjectMismatch { .. } => rusqlite::ffi::SQLITE_CONSTRAINT,
+            _ => rusqlite::ffi::SQLITE_ERROR,
+        };
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), Some(err.to_string()))
+    }
+}
+
 /// Main storage interface
 pub struct Storage {
     conn: Connection,
     #[allow(dead_code)]
     config: StorageConfig,
+    read_only: bool,
 }
 
 impl Storage {
@@ -69,18 +285,37 @@ impl Storage {
     }
 
     /// Open storage with custom config
+    ///
+    /// Fails without touching the database if the config does not pass
+    /// [`StorageConfig::validate`].
     pub fn open_with_config<P: AsRef<Path>>(path: P, config: StorageConfig) -> SqliteResult<Self> {
+        config.validate()?;
         let conn = Connection::open(path)?;
 
+        // The key must be set before anything reads the database. A wrong
+        // key only shows once a page is read, so read the schema to check.
+        if let Some(key) = &config.encryption_key {
+            conn.pragma_update(None, "key", key)?;
+        }
+        if let Err(e) = conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(())) {
+            return Err(match e.sqlite_error_code() {
+                Some(rusqlite::ErrorCode::NotADatabase) => {
+                    StorageError::WrongKey(config.db_path.clone()).into()
+                }
+                _ => e,
+            });
+        }
+
         // Enable WAL mode for better concurrency
         if config.wal_enabled {
             conn.pragma_update(None, "journal_mode", "WAL")?;
         }
 
-        // Allow concurrent access: wait up to 5 seconds for locks instead of
-        // immediately failing.  This is critical when multiple LeIndex instances
-        // (or a ProjectRegistry) access the same project's .leindex/leindex.db.
-        conn.pragma_update(None, "busy_timeout", 5000)?;
+        // Allow concurrent access: wait for locks (5 seconds by default)
+        // instead of immediately failing.  This is critical when multiple
+        // LeIndex instances (or a ProjectRegistry) access the same project's
+        // .leindex/leindex.db.
+        conn.busy_timeout(std::time::Duration::from_millis(config.busy_timeout_ms))?;
 
         // Set cache size if specified (negative = KiB per SQLite convention)
         if let Some(cache_size_kib) = config.cache_size_kib {
@@ -92,93 +327,127 @@ impl Storage {
             conn.pragma_update(None, "mmap_size", mmap_size)?;
         }
 
-        let mut storage = Self { conn, config };
-
-        // Check schema version BEFORE any DDL — reject newer databases early
-        // so an older binary cannot corrupt a schema it doesn't understand.
-        storage.run_migrations()?;
+        let mut storage = Self {
+            conn,
+            config,
+            read_only: false,
+        };
 
-        // Initialize schema (CREATE TABLE IF NOT EXISTS — safe after version check)
+        // Rejects databases from newer versions before any DDL runs
         storage.initialize_schema()?;
 
         Ok(storage)
     }
 
+    /// Open existing storage for reading only
+    ///
+    /// The connection is opened with `SQLITE_OPEN_READ_ONLY` and
+    /// `query_only`, so it never creates, migrates or writes the database and
+    /// takes no write locks; a WAL database is read alongside a concurrent
+    /// writer. Writes made through [`Storage::retry_on_busy`] (all PDG store
+    /// writes) fail with [`StorageError::ReadOnly`]; any other write is
+    /// rejected by SQLite with `SQLITE_READONLY`. Uses the thin reader cache
+    /// budget. An encrypted database cannot be opened this way, as no key is
+    /// set; it fails with [`StorageError::EncryptionUnsupported`].
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> SqliteResult<Self> {
+        let path = path.as_ref();
+        if is_encrypted_database(path) {
+            return Err(StorageError::EncryptionUnsupported {
+                path: path.display().to_string(),
+                operation: "read-only access",
+            }
+            .into());
+        }
+        let conn = Connection::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+                | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX
+                | rusqlite::OpenFlags::SQLITE_OPEN_URI,
+        )?;
+        let config = StorageConfig {
+            db_path: path.display().to_string(),
+            wal_enabled: false,
+            cache_size_kib: Some(PROJECT_READER_CACHE_SIZE_KIB),
+            ..StorageConfig::default()
+        };
+        conn.busy_timeout(std::time::Duration::from_millis(config.busy_timeout_ms))?;
+        conn.pragma_update(None, "query_only", true)?;
+        if let Some(cache_size_kib) = config.cache_size_kib {
+            conn.pragma_update(None, "cache_size", cache_size_kib)?;
+        }
+        if let Some(mmap_size) = config.mmap_size {
+            conn.pragma_update(None, "mmap_size", mmap_size)?;
+        }
+
+        // Same guard as initialize_schema, without creating the migrations table
+        crate::storage::migrations::MigrationManager::embedded().verify(&conn)?;
+
+        Ok(Self {
+            conn,
+            config,
+            read_only: true,
+        })
+    }
+
+    /// Whether this storage was opened with [`Storage::open_read_only`]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Fail with [`StorageError::ReadOnly`] if this storage is read-only
+    pub fn ensure_writable(&self) -> Result<(), StorageError> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly(self.config.db_path.clone()));
+        }
+        Ok(())
+    }
+
     /// Initialize database schema
+    ///
+    /// Applies the pending [`MIGRATIONS`](crate::storage::migrations::MIGRATIONS)
+    /// and records them in `schema_migrations`; fails if an applied
+    /// migration was modified or is unknown to this version, before
+    /// changing anything.
     fn initialize_schema(&mut self) -> SqliteResult<()> {
-        // Initialize project_metadata table first
-        // SQL schema for project_metadata table
-        let project_metadata_schema = r#"
-CREATE TABLE IF NOT EXISTS project_metadata (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    unique_project_id TEXT UNIQUE NOT NULL,
-    base_name TEXT NOT NULL,
-    path_hash TEXT NOT NULL,
-    instance INTEGER DEFAULT 0,
-    canonical_path TEXT NOT NULL,
-    display_name TEXT,
-    is_clone BOOLEAN DEFAULT 0,
-    cloned_from TEXT,
-    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-    UNIQUE(canonical_path)
-)
-"#;
-
-        // SQL indexes for project_metadata table
-        let project_metadata_indexes = [
-            "CREATE INDEX IF NOT EXISTS idx_project_metadata_unique_id ON project_metadata(unique_project_id)",
-            "CREATE INDEX IF NOT EXISTS idx_project_metadata_canonical_path ON project_metadata(canonical_path)",
-            "CREATE INDEX IF NOT EXISTS idx_project_metadata_base_hash ON project_metadata(base_name, path_hash)",
-            "CREATE INDEX IF NOT EXISTS idx_project_metadata_base_name ON project_metadata(base_name)",
-        ];
-
-        self.conn.execute(project_metadata_schema, [])?;
-        for index_sql in project_metadata_indexes {
-            self.conn.execute(index_sql, [])?;
+        let manager = crate::storage::migrations::MigrationManager::embedded();
+        manager.verify(&self.conn)?;
+        self.upgrade_legacy_columns()?;
+        let applied = manager.migrate(&mut self.conn)?;
+        if !applied.is_empty() {
+            tracing::debug!("Applied storage migrations {:?}", applied);
         }
+        Ok(())
+    }
 
-        // Create indexed_files table for incremental indexing
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS indexed_files (
-                file_path TEXT PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                file_hash TEXT NOT NULL,
-                last_indexed INTEGER NOT NULL
-            )",
-            [],
-        )?;
+    /// Names of the columns of `table`, empty if it does not exist
+    fn table_columns(&self, table: &str) -> SqliteResult<Vec<String>> {
+        self.conn
+            .prepare(&format!("PRAGMA table_info({})", table))?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect()
+    }
 
-        // Create intel_nodes table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS intel_nodes (
-                id INTEGER PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                file_path TEXT NOT NULL,
-                node_id TEXT NOT NULL,
-                symbol_name TEXT NOT NULL,
-                qualified_name TEXT NOT NULL,
-                language TEXT NOT NULL DEFAULT 'unknown',
-                node_type TEXT NOT NULL,
-                signature TEXT,
-                complexity INTEGER,
-                content_hash TEXT NOT NULL,
-                embedding BLOB,
-                byte_range_start INTEGER,
-                byte_range_end INTEGER,
-                created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL,
-                embedding_format INTEGER
-            )",
-            [],
-        )?;
+    /// Bring databases created before migrations were tracked up to the
+    /// tables migration 1 adopts
+    ///
+    /// Adds the columns missing from their tables, so the migrations'
+    /// indexes can be built on them, and drops the `schema_version` table
+    /// that used to track their schema.
+    fn upgrade_legacy_columns(&mut self) -> SqliteResult<()> {
+        let metadata_columns = self.table_columns("project_metadata")?;
+        if !metadata_columns.is_empty() && !metadata_columns.iter().any(|c| c == "last_indexed") {
+            self.conn.execute(
+                "ALTER TABLE project_metadata ADD COLUMN last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP",
+                [],
+            )?;
+        }
+        self.conn
+            .execute("DROP TABLE IF EXISTS schema_version", [])?;
 
-        // Migration: Ensure new columns exist for existing databases
-        let columns: Vec<String> = self
-            .conn
-            .prepare("PRAGMA table_info(intel_nodes)")?
-            .query_map([], |row| row.get::<_, String>(1))?
-            .collect::<SqliteResult<Vec<_>>>()?;
+        let columns = self.table_columns("intel_nodes")?;
+        if columns.is_empty() {
+            return Ok(());
+        }
 
         if !columns.iter().any(|c| c == "node_id") {
             self.conn.execute(
@@ -226,162 +495,17 @@ CREATE TABLE IF NOT EXISTS project_metadata (
                 [],
             )?;
         }
-        // Create intel_edges table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS intel_edges (
-                caller_id INTEGER NOT NULL,
-                callee_id INTEGER NOT NULL,
-                edge_type TEXT NOT NULL,
-                metadata TEXT,
-                FOREIGN KEY(caller_id) REFERENCES intel_nodes(id),
-                FOREIGN KEY(callee_id) REFERENCES intel_nodes(id),
-                PRIMARY KEY(caller_id, callee_id, edge_type)
-            )",
-            [],
-        )?;
-
-        // Create analysis_cache table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS analysis_cache (
-                node_hash TEXT PRIMARY KEY,
-                cfg_data BLOB,
-                complexity_metrics BLOB,
-                timestamp INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        // Persistent cache telemetry for cross-session hit-rate tracking.
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS cache_telemetry (
-                id INTEGER PRIMARY KEY CHECK (id = 1),
-                cache_hits INTEGER NOT NULL DEFAULT 0,
-                cache_misses INTEGER NOT NULL DEFAULT 0,
-                cache_writes INTEGER NOT NULL DEFAULT 0,
-                updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
-            )",
-            [],
-        )?;
-        self.conn.execute(
-            "INSERT OR IGNORE INTO cache_telemetry (id, cache_hits, cache_misses, cache_writes, updated_at)
-             VALUES (1, 0, 0, 0, strftime('%s', 'now'))",
-            [],
-        )?;
-
-        // Create global_symbols table (Phase 7: Cross-Project Resolution)
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS global_symbols (
-                symbol_id TEXT PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                symbol_name TEXT NOT NULL,
-                symbol_type TEXT NOT NULL,
-                signature TEXT,
-                file_path TEXT NOT NULL,
-                byte_range_start INTEGER,
-                byte_range_end INTEGER,
-                complexity INTEGER DEFAULT 1,
-                is_public INTEGER DEFAULT 0,
-                created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
-                UNIQUE(project_id, symbol_name, signature)
-            )",
-            [],
-        )?;
-
-        // Create external_refs table (Phase 7: Cross-Project Resolution)
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS external_refs (
-                ref_id TEXT PRIMARY KEY,
-                source_project_id TEXT NOT NULL,
-                source_symbol_id TEXT NOT NULL,
-                target_project_id TEXT NOT NULL,
-                target_symbol_id TEXT NOT NULL,
-                ref_type TEXT NOT NULL,
-                FOREIGN KEY (source_symbol_id) REFERENCES global_symbols(symbol_id),
-                FOREIGN KEY (target_symbol_id) REFERENCES global_symbols(symbol_id)
-            )",
-            [],
-        )?;
-
-        // Create project_deps table (Phase 7: Cross-Project Resolution)
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS project_deps (
-                dep_id TEXT PRIMARY KEY,
-                project_id TEXT NOT NULL,
-                depends_on_project_id TEXT NOT NULL,
-                dependency_type TEXT NOT NULL,
-                UNIQUE(project_id, depends_on_project_id)
-            )",
-            [],
-        )?;
-
-        // Create indexes for query performance
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_nodes_project ON intel_nodes(project_id)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_nodes_file ON intel_nodes(file_path)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_nodes_symbol ON intel_nodes(symbol_name)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_nodes_hash ON intel_nodes(content_hash)",
-            [],
-        )?;
-
-        // Create indexes for global_symbols (Phase 7)
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_global_symbols_name ON global_symbols(symbol_name)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_global_symbols_type ON global_symbols(symbol_type)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_global_symbols_project ON global_symbols(project_id)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_global_symbols_public ON global_symbols(symbol_id) WHERE is_public = 1",
-            [],
-        )?;
-
-        // Create indexes for external_refs (Phase 7)
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_external_refs_source ON external_refs(source_symbol_id)",
-            [],
-        )?;
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_external_refs_target ON external_refs(target_symbol_id)",
-            [],
-        )?;
-
-        // Create indexes for project_deps (Phase 7)
-        self.conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_project_deps_project ON project_deps(project_id)",
-            [],
-        )?;
-
-        // Create trigram_index table for accelerated fuzzy node lookup.
-        // Stores the serialized trigram index as a single blob per project.
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS trigram_index (
-                project_id TEXT PRIMARY KEY,
-                index_data BLOB NOT NULL,
-                node_count INTEGER NOT NULL DEFAULT 0,
-                trigram_count INTEGER NOT NULL DEFAULT 0,
-                updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
-            )",
-            [],
-        )?;
-
         Ok(())
     }
 
+    /// Effective SQLite page cache size as reported by `PRAGMA cache_size`
+    ///
+    /// Negative values are KiB, positive values are pages.
+    pub fn cache_size(&self) -> SqliteResult<i64> {
+        self.conn
+            .query_row("PRAGMA cache_size", [], |row| row.get(0))
+    }
+
     /// Get the underlying connection
     pub fn conn(&self) -> &Connection {
         &self.conn
@@ -392,6 +516,45 @@ CREATE TABLE IF NOT EXISTS project_metadata (
         &mut self.conn
     }
 
+    /// Run a write operation, retrying it while the database is busy or locked
+    ///
+    /// `busy_timeout` covers most contention, but SQLite still returns
+    /// SQLITE_BUSY immediately in some cases (e.g. a deferred transaction
+    /// that cannot upgrade to a write lock). `op` is retried up to
+    /// [`WRITE_RETRY_ATTEMPTS`] times with exponential backoff starting at
+    /// [`WRITE_RETRY_BACKOFF_MS`], so it must be safe to run again after a
+    /// failed attempt, which holds for a single transaction. Other errors
+    /// are returned immediately, and storage opened read-only fails with
+    /// [`StorageError::ReadOnly`] without running `op`.
+    pub fn retry_on_busy<T, E>(
+        &mut self,
+        mut op: impl FnMut(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: std::error::Error + From<rusqlite::Error> + 'static,
+    {
+        self.ensure_writable()
+            .map_err(|err| E::from(rusqlite::Error::from(err)))?;
+        let mut attempt = 1;
+        loop {
+            match op(self) {
+                Err(err) if attempt < WRITE_RETRY_ATTEMPTS && is_busy_error(&err) => {
+                    let delay = WRITE_RETRY_BACKOFF_MS << (attempt - 1);
+                    tracing::debug!(
+                        "Database busy (attempt {}/{}), retrying in {}ms: {}",
+                        attempt,
+                        WRITE_RETRY_ATTEMPTS,
+                        delay,
+                        err
+                    );
+                    std::thread::sleep(std::time::Duration::from_millis(delay));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Close the storage connection and ensure WAL is checkpointed
     ///
     /// This explicitly checkpoints the WAL (Write-Ahead Log) to the main database file
@@ -416,6 +579,16 @@ CREATE TABLE IF NOT EXISTS project_metadata (
             .map_err(|_| rusqlite::Error::InvalidQuery)
     }
 
+    /// Unique ID recorded for the project at `project_path` by
+    /// [`store_project_metadata`](Self::store_project_metadata), if any.
+    pub fn stored_project_id(&self, project_path: &Path) -> SqliteResult<Option<UniqueProjectId>> {
+        let canonical_path = ProjectMetadata::new(project_path).canonical_path;
+        ProjectMetadata::find_id_by_path(&self.conn, &canonical_path).map_err(|e| match e {
+            crate::storage::ProjectMetadataError::Database(e) => e,
+            _ => rusqlite::Error::InvalidQuery,
+        })
+    }
+
     /// Store project metadata.
     ///
     /// This persists the unique project ID and associated metadata.
@@ -434,85 +607,6 @@ CREATE TABLE IF NOT EXISTS project_metadata (
             .save(&self.conn)
             .map_err(|_| rusqlite::Error::InvalidQuery)
     }
-
-    /// Current schema version. Increment when adding migrations.
-    const SCHEMA_VERSION: u32 = 2;
-
-    /// Run database migrations based on the stored schema version.
-    /// Creates the version tracking table if it doesn't exist.
-    fn run_migrations(&mut self) -> SqliteResult<()> {
-        // Create version tracking table
-        self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS schema_version (
-                key TEXT PRIMARY KEY,
-                version INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        // Read current version
-        let current: u32 = self
-            .conn
-            .query_row(
-                "SELECT COALESCE(MAX(version), 0) FROM schema_version WHERE key = 'schema'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
-
-        // Reject databases from newer versions — they may contain data
-        // this version cannot interpret.
-        if current > Self::SCHEMA_VERSION {
-            return Err(rusqlite::Error::InvalidParameterName(format!(
-                "Database schema v{} is newer than this version (v{}). Please upgrade LeIndex.",
-                current,
-                Self::SCHEMA_VERSION
-            )));
-        }
-
-        // Migration v1 to v2: Add last_indexed column to project_metadata
-        if current < 2 {
-            self.migrate_v1_to_v2()?;
-        }
-
-        // Update stored version
-        self.conn.execute(
-            "INSERT OR REPLACE INTO schema_version (key, version) VALUES ('schema', ?1)",
-            [Self::SCHEMA_VERSION],
-        )?;
-
-        Ok(())
-    }
-
-    /// Migration from v1 to v2: Add last_indexed column to project_metadata table
-    fn migrate_v1_to_v2(&mut self) -> SqliteResult<()> {
-        let table_exists: bool = self.conn.query_row(
-            "SELECT EXISTS(
-                SELECT 1 FROM sqlite_master
-                WHERE type = 'table' AND name = 'project_metadata'
-            )",
-            [],
-            |row| row.get(0),
-        )?;
-        if !table_exists {
-            return Ok(());
-        }
-
-        // Check if column already exists
-        let columns: Vec<String> = self
-            .conn
-            .prepare("PRAGMA table_info(project_metadata)")?
-            .query_map([], |row| row.get::<_, String>(1))?
-            .collect::<SqliteResult<Vec<_>>>()?;
-
-        if !columns.iter().any(|c| c == "last_indexed") {
-            self.conn.execute(
-                "ALTER TABLE project_metadata ADD COLUMN last_indexed TIMESTAMP DEFAULT CURRENT_TIMESTAMP",
-                [],
-            )?;
-        }
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -536,7 +630,7 @@ mod tests {
         let table_count: i64 = storage
             .conn
             .query_row(
-                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND (name LIKE 'intel_%' OR name = 'analysis_cache' OR name = 'cache_telemetry' OR name LIKE 'global_%' OR name LIKE 'external_%' OR name LIKE 'project_%')",
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND (name LIKE 'intel_%' OR name = 'analysis_cache' OR name = 'cache_telemetry' OR name LIKE 'global_%' OR name LIKE 'external_%' OR name LIKE 'project_%') AND name NOT LIKE 'intel_nodes_fts%'",
                 [],
                 |row| row.get(0),
             )
@@ -595,6 +689,8 @@ mod tests {
             wal_enabled: true,
             cache_size_kib: Some(PROJECT_READER_CACHE_SIZE_KIB),
             mmap_size: Some(PROJECT_STORE_MMAP_SIZE),
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+            encryption_key: None,
         };
         let storage = Storage::open_with_config(temp_file.path(), reader_config).unwrap();
 
@@ -608,6 +704,264 @@ mod tests {
             PROJECT_READER_CACHE_SIZE_KIB, cache_size
         );
     }
+
+    #[test]
+    fn test_write_retries_while_another_connection_holds_the_lock() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = StorageConfig {
+            db_path: temp_file.path().to_string_lossy().to_string(),
+            busy_timeout_ms: 10,
+            ..StorageConfig::default()
+        };
+        let mut writer = Storage::open_with_config(temp_file.path(), config.clone()).unwrap();
+        let holder = Storage::open_with_config(temp_file.path(), config).unwrap();
+        let busy: i64 = writer
+            .conn
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(busy, 10);
+
+        // Hold the write lock for longer than the busy timeout
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let holder_thread = std::thread::spawn(move || {
+            holder.conn.execute_batch("BEGIN IMMEDIATE").unwrap();
+            locked_tx.send(()).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(150));
+            holder.conn.execute_batch("COMMIT").unwrap();
+        });
+        locked_rx.recv().unwrap();
+
+        let insert = |storage: &mut Storage| {
+            storage.conn.execute(
+                "INSERT INTO indexed_files (file_path, project_id, file_hash, last_indexed)
+                 VALUES ('a.rs', 'proj', 'h', 0)",
+                [],
+            )
+        };
+        let err = insert(&mut writer).unwrap_err();
+        assert!(is_busy_error(&err), "{}", err);
+        writer.retry_on_busy(insert).unwrap();
+        holder_thread.join().unwrap();
+
+        // Non-busy errors are returned without retrying
+        let mut attempts = 0;
+        let result = writer.retry_on_busy(|storage| {
+            attempts += 1;
+            storage
+                .conn
+                .execute("INSERT INTO no_such_table VALUES (1)", [])
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_read_only_storage_rejects_writes_and_serves_reads() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut writer = Storage::open(temp_file.path()).unwrap();
+        crate::storage::pdg_store::update_indexed_file(&mut writer, "proj", "a.rs", "h1").unwrap();
+
+        let mut reader = Storage::open_read_only(temp_file.path()).unwrap();
+        assert!(reader.is_read_only());
+        assert!(!writer.is_read_only());
+        let files = crate::storage::pdg_store::get_indexed_files(&reader, "proj").unwrap();
+        assert_eq!(files.get("a.rs").map(String::as_str), Some("h1"));
+
+        let err = crate::storage::pdg_store::update_indexed_file(&mut reader, "proj", "b.rs", "h2")
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{}", err);
+        assert!(matches!(
+            reader.ensure_writable(),
+            Err(StorageError::ReadOnly(_))
+        ));
+        // Writes bypassing the PDG store are refused by SQLite itself
+        assert!(reader
+            .conn()
+            .execute("DELETE FROM indexed_files", [])
+            .is_err());
+
+        // The writer is unaffected and the reader sees its later commits
+        crate::storage::pdg_store::update_indexed_file(&mut writer, "proj", "c.rs", "h3").unwrap();
+        let files = crate::storage::pdg_store::get_indexed_files(&reader, "proj").unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(Storage::open_read_only(temp_file.path().with_extension("missing")).is_err());
+    }
+
+    #[test]
+    fn test_legacy_database_adopts_migrations() {
+        let temp_file = NamedTempFile::new().unwrap();
+        {
+            let conn = Connection::open(temp_file.path()).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE schema_version (key TEXT PRIMARY KEY, version INTEGER NOT NULL);
+                 INSERT INTO schema_version (key, version) VALUES ('schema', 1);
+                 CREATE TABLE project_metadata (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     unique_project_id TEXT UNIQUE NOT NULL,
+                     base_name TEXT NOT NULL,
+                     path_hash TEXT NOT NULL,
+                     instance INTEGER DEFAULT 0,
+                     canonical_path TEXT NOT NULL,
+                     display_name TEXT,
+                     is_clone BOOLEAN DEFAULT 0,
+                     cloned_from TEXT,
+                     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                     UNIQUE(canonical_path)
+                 );",
+            )
+            .unwrap();
+        }
+
+        let storage = Storage::open(temp_file.path()).unwrap();
+        let columns = storage.table_columns("project_metadata").unwrap();
+        assert!(columns.iter().any(|c| c == "last_indexed"));
+        assert!(storage.table_columns("schema_version").unwrap().is_empty());
+        let applied = crate::storage::migrations::MigrationManager::embedded()
+            .applied(storage.conn())
+            .unwrap();
+        assert_eq!(
+            applied.last().map(|m| m.version),
+            Some(crate::storage::migrations::MigrationManager::embedded().latest_version())
+        );
+    }
+
+    #[test]
+    fn test_newer_database_is_rejected_by_every_open() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = Storage::open(temp_file.path()).unwrap();
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO schema_migrations (version, name, checksum, applied_at)
+                 VALUES (9999, 'from_the_future', '', 0)",
+                [],
+            )
+            .unwrap();
+        drop(storage);
+
+        for result in [
+            Storage::open(temp_file.path()),
+            Storage::open_read_only(temp_file.path()),
+        ] {
+            let err = result.err().expect("a newer database should be rejected");
+            assert!(err.to_string().contains("migration 9999"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_configured_cache_size_is_applied_and_validated() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = |cache_size_kib| StorageConfig {
+            db_path: temp_file.path().to_string_lossy().to_string(),
+            wal_enabled: true,
+            cache_size_kib,
+            mmap_size: None,
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+            encryption_key: None,
+        };
+
+        let storage = Storage::open_with_config(temp_file.path(), config(Some(-65_536))).unwrap();
+        assert_eq!(storage.cache_size().unwrap(), -65_536);
+        drop(storage);
+
+        let storage = Storage::open_with_config(temp_file.path(), config(Some(4_096))).unwrap();
+        assert_eq!(storage.cache_size().unwrap(), 4_096);
+        drop(storage);
+
+        for invalid in [0, -1, -(MAX_CACHE_SIZE_KIB + 1), MAX_CACHE_SIZE_PAGES + 1] {
+            let err = Storage::open_with_config(temp_file.path(), config(Some(invalid)))
+                .err()
+                .expect("out-of-range cache size should be rejected");
+            assert!(err.to_string().contains("out of range"), "{}", err);
+            assert_eq!(
+                err.sqlite_error_code(),
+                Some(rusqlite::ErrorCode::ApiMisuse)
+            );
+            assert!(matches!(
+                config(Some(invalid)).validate(),
+                Err(StorageError::InvalidConfig(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_encrypted_storage_round_trips_and_rejects_wrong_key() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = |key: Option<&str>| StorageConfig {
+            db_path: temp_file.path().to_string_lossy().to_string(),
+            encryption_key: key.map(str::to_string),
+            ..StorageConfig::default()
+        };
+
+        let mut storage =
+            Storage::open_with_config(temp_file.path(), config(Some("s3cret"))).unwrap();
+        storage
+            .conn()
+            .execute(
+                "INSERT INTO indexed_files (file_path, project_id, file_hash, last_indexed)
+                 VALUES (?1, ?2, ?3, 0)",
+                rusqlite::params!["src/lib.rs", "proj", "abc123"],
+            )
+            .unwrap();
+        storage.close().unwrap();
+        drop(storage);
+
+        // The file on disk holds no plaintext
+        let raw = std::fs::read(temp_file.path()).unwrap();
+        assert!(!raw.starts_with(b"SQLite format 3"));
+        assert!(!raw.windows(10).any(|w| w == b"src/lib.rs"));
+
+        let storage = Storage::open_with_config(temp_file.path(), config(Some("s3cret"))).unwrap();
+        let hash: String = storage
+            .conn()
+            .query_row(
+                "SELECT file_hash FROM indexed_files WHERE file_path = 'src/lib.rs'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hash, "abc123");
+        drop(storage);
+
+        for key in [Some("wrong"), None] {
+            let err = Storage::open_with_config(temp_file.path(), config(key))
+                .err()
+                .expect("opening with the wrong key should fail");
+            assert_eq!(
+                err.sqlite_error_code(),
+                Some(rusqlite::ErrorCode::NotADatabase)
+            );
+            assert!(err.to_string().contains("encryption key"), "{}", err);
+        }
+
+        // Paths that cannot take a key refuse it up front
+        assert!(is_encrypted_database(temp_file.path()));
+        let err = Storage::open_read_only(temp_file.path())
+            .err()
+            .expect("read-only open of an encrypted database should fail");
+        assert!(
+            err.to_string()
+                .contains("not supported by read-only access"),
+            "{}",
+            err
+        );
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    #[test]
+    fn test_encryption_key_requires_encryption_feature() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let config = StorageConfig {
+            db_path: temp_file.path().to_string_lossy().to_string(),
+            encryption_key: Some("s3cret".to_string()),
+            ..StorageConfig::default()
+        };
+        let err = Storage::open_with_config(temp_file.path(), config)
+            .err()
+            .expect("a key without SQLCipher support should be rejected");
+        assert!(err.to_string().contains("`encryption` feature"), "{}", err);
+    }
 }
 
 // ============================================================================
@@ -3,8 +3,8 @@
 // This module provides configuration for Turso/libsql hybrid storage,
 // combining local SQLite with remote Turso vector store capabilities.
 
+pub use crate::storage::schema::StorageError;
 use serde::{Deserialize, Serialize};
-use thiserror::Error;
 
 /// Turso configuration
 ///
@@ -138,32 +138,6 @@ pub enum StorageMode {
     Hybrid,
 }
 
-/// Storage errors
-///
-/// Errors that can occur when working with HybridStorage.
-#[derive(Debug, Error)]
-pub enum StorageError {
-    /// Failed to connect to the storage backend
-    #[error("Connection failed: {0}")]
-    ConnectionFailed(String),
-
-    /// Failed to migrate data between storage backends
-    #[error("Migration failed: {0}")]
-    MigrationFailed(String),
-
-    /// The vector search extension is not available on the remote backend
-    #[error("Vector extension not available")]
-    VectorExtensionNotAvailable,
-
-    /// An error occurred in the local SQLite storage
-    #[error("Local storage error: {0}")]
-    LocalStorageError(String),
-
-    /// A query executed on the remote backend failed
-    #[error("Remote query failed: {0}")]
-    RemoteQueryFailed(String),
-}
-
 /// Hybrid storage: local SQLite + remote Turso
 ///
 /// Combines local SQLite storage with optional remote Turso storage.
@@ -0,0 +1,229 @@
+// Token counting for token-budget accounting
+//
+// Budgets (analysis context, formatter output) are expressed in LLM tokens.
+// A `len / 4` estimate badly undercounts code, where operators, brackets and
+// short identifiers each cost a token. The `Tokenizer` trait lets callers
+// swap in a more faithful counter; `SubwordTokenizer` is the default and a
+// BPE-backed implementation can be added behind the same trait.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Counts tokens in text for budget enforcement
+pub trait Tokenizer: Send + Sync + std::fmt::Debug {
+    /// Short identifier of the tokenizer (e.g. `"subword"`)
+    fn name(&self) -> &'static str;
+
+    /// Number of tokens `text` is expected to occupy
+    fn count_tokens(&self, text: &str) -> usize;
+
+    /// Longest prefix of `text` that fits in `max_tokens`, cut on a char boundary
+    fn truncate_to_tokens<'a>(&self, text: &'a str, max_tokens: usize) -> &'a str {
+        if self.count_tokens(text) <= max_tokens {
+            return text;
+        }
+        // Token counts are monotonic in prefix length, so binary search the
+        // number of chars kept; the whole text is known to be over budget
+        let boundaries: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        let (mut lo, mut hi) = (0, boundaries.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if self.count_tokens(&text[..boundaries[mid]]) <= max_tokens {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        &text[..boundaries[lo]]
+    }
+
+    /// Tokens expected for `bytes` of source text when only its length is known
+    fn estimate_tokens_for_bytes(&self, bytes: usize) -> usize {
+        bytes / 4
+    }
+}
+
+/// Which tokenizer to use for budget accounting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// Whitespace/subword splitting tuned for source code
+    #[default]
+    Subword,
+    /// Legacy fixed characters-per-token estimate
+    CharEstimate,
+}
+
+impl TokenizerKind {
+    /// Build a shareable tokenizer of this kind
+    pub fn build(self) -> Arc<dyn Tokenizer> {
+        match self {
+            Self::Subword => Arc::new(SubwordTokenizer),
+            Self::CharEstimate => Arc::new(CharEstimateTokenizer::default()),
+        }
+    }
+}
+
+/// Fixed characters-per-token estimate (the historical `len / 4`)
+#[derive(Debug, Clone, Copy)]
+pub struct CharEstimateTokenizer {
+    /// Bytes assumed per token
+    pub chars_per_token: usize,
+}
+
+impl Default for CharEstimateTokenizer {
+    fn default() -> Self {
+        Self { chars_per_token: 4 }
+    }
+}
+
+impl Tokenizer for CharEstimateTokenizer {
+    fn name(&self) -> &'static str {
+        "char_estimate"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        text.len() / self.chars_per_token.max(1)
+    }
+
+    fn estimate_tokens_for_bytes(&self, bytes: usize) -> usize {
+        bytes / self.chars_per_token.max(1)
+    }
+}
+
+/// Whitespace/subword tokenizer approximating BPE behaviour on code
+///
+/// - identifiers split on `_` and lower→upper case changes; each piece costs
+///   one token per started 6 characters
+/// - digit runs cost one token per started 3 digits
+/// - every punctuation or operator character costs one token
+/// - a single space is merged into the following word; longer whitespace
+///   runs (indentation, blank lines) cost one token
+/// - other non-ASCII characters cost one token each
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubwordTokenizer;
+
+/// Characters a single identifier piece can hold before costing another token
+const SUBWORD_PIECE_CHARS: usize = 6;
+
+/// Digits a single number token can hold
+const DIGITS_PER_TOKEN: usize = 3;
+
+/// Average bytes per subword token on source code (about 3.4 on this
+/// repository's own Rust sources), used when only a byte length is known
+const SUBWORD_BYTES_PER_TOKEN: usize = 3;
+
+impl SubwordTokenizer {
+    fn count_word(word: &[char]) -> usize {
+        let mut tokens = 0;
+        let mut piece_len = 0;
+        let mut prev: Option<char> = None;
+        for &ch in word {
+            let boundary = ch == '_'
+                || prev.is_some_and(|p| p.is_ascii_lowercase() && ch.is_ascii_uppercase());
+            if boundary && piece_len > 0 {
+                tokens += piece_len.div_ceil(SUBWORD_PIECE_CHARS);
+                piece_len = 0;
+            }
+            if ch != '_' {
+                piece_len += 1;
+            }
+            prev = Some(ch);
+        }
+        tokens + piece_len.div_ceil(SUBWORD_PIECE_CHARS)
+    }
+}
+
+impl Tokenizer for SubwordTokenizer {
+    fn name(&self) -> &'static str {
+        "subword"
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        let chars: Vec<char> = text.chars().collect();
+        let mut tokens = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let ch = chars[i];
+            let start = i;
+            if ch.is_whitespace() {
+                while i < chars.len() && chars[i].is_whitespace() {
+                    i += 1;
+                }
+                if i - start > 1 {
+                    tokens += 1;
+                }
+            } else if ch.is_ascii_digit() {
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                tokens += (i - start).div_ceil(DIGITS_PER_TOKEN);
+            } else if ch.is_ascii_alphabetic() || ch == '_' {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens += Self::count_word(&chars[start..i]).max(1);
+            } else {
+                i += 1;
+                tokens += 1;
+            }
+        }
+        tokens
+    }
+
+    fn estimate_tokens_for_bytes(&self, bytes: usize) -> usize {
+        bytes.div_ceil(SUBWORD_BYTES_PER_TOKEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subword_counts_code_above_naive_estimate() {
+        let code = "fn add(a: i32, b: i32) -> i32 { if a > 0 { a + b } else { b - a } }";
+        let naive = CharEstimateTokenizer::default().count_tokens(code);
+        let subword = SubwordTokenizer.count_tokens(code);
+        assert_eq!(naive, code.len() / 4);
+        assert!(
+            subword > naive + naive / 2,
+            "subword={} naive={}",
+            subword,
+            naive
+        );
+    }
+
+    #[test]
+    fn subword_splits_identifiers_and_numbers() {
+        assert_eq!(SubwordTokenizer.count_tokens("getUserName"), 3);
+        assert_eq!(SubwordTokenizer.count_tokens("snake_case_name"), 3);
+        assert_eq!(SubwordTokenizer.count_tokens("1234567"), 3);
+        assert_eq!(SubwordTokenizer.count_tokens("a b"), 2);
+        assert_eq!(SubwordTokenizer.count_tokens(""), 0);
+    }
+
+    #[test]
+    fn truncate_to_tokens_respects_budget() {
+        let text = "let total = first + second * third;";
+        let cut = SubwordTokenizer.truncate_to_tokens(text, 4);
+        assert!(SubwordTokenizer.count_tokens(cut) <= 4);
+        assert!(text.starts_with(cut));
+        assert_eq!(SubwordTokenizer.truncate_to_tokens(text, 1000), text);
+    }
+
+    #[test]
+    fn byte_estimates_follow_tokenizer_density() {
+        assert_eq!(
+            CharEstimateTokenizer::default().estimate_tokens_for_bytes(400),
+            100
+        );
+        assert_eq!(SubwordTokenizer.estimate_tokens_for_bytes(400), 134);
+    }
+
+    #[test]
+    fn tokenizer_kind_builds_default() {
+        assert_eq!(TokenizerKind::default().build().name(), "subword");
+        assert_eq!(TokenizerKind::CharEstimate.build().name(), "char_estimate");
+    }
+}
@@ -369,23 +369,7 @@ impl SemanticDriftAnalyzer {
         change: &ResolvedEditChange,
         sig: &SignatureInfo,
     ) -> Location {
-        let byte_offset = sig.byte_range.0;
-        let mut line = 1;
-        let mut column = 1;
-
-        for (i, byte) in change.new_content.bytes().enumerate() {
-            if i == byte_offset {
-                break;
-            }
-            if byte == b'\n' {
-                line += 1;
-                column = 1;
-            } else {
-                column += 1;
-            }
-        }
-
-        Location { line, column }
+        Location::from_byte_offset(change.new_content.as_bytes(), sig.byte_range.0)
     }
 
     /// Check if a symbol is part of the public API
@@ -563,6 +547,7 @@ mod tests {
             imports: vec![],
             byte_range: (0, 14),
             cyclomatic_complexity: 0,
+            base_types: Vec::new(),
         };
 
         let location = analyzer.find_signature_location(&change, &sig);
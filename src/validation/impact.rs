@@ -13,7 +13,7 @@ use std::sync::Arc;
 pub struct Location {
     /// Line number (1-indexed)
     pub line: usize,
-    /// Column number (1-indexed, in bytes)
+    /// Column number (1-indexed, in characters)
     pub column: usize,
 }
 
@@ -28,6 +28,17 @@ impl Location {
     pub fn new(line: usize, column: usize) -> Self {
         Self { line, column }
     }
+
+    /// Location of a byte offset within `source`
+    pub fn from_byte_offset(source: &[u8], offset: usize) -> Self {
+        crate::parse::position::byte_to_position(source, offset).into()
+    }
+}
+
+impl From<crate::parse::position::Position> for Location {
+    fn from(position: crate::parse::position::Position) -> Self {
+        Self::new(position.line, position.column)
+    }
 }
 
 /// Risk level of an impact
@@ -431,6 +442,7 @@ mod tests {
                 metadata: EdgeMetadata {
                     call_count: None,
                     confidence: None,
+                    call_target: None,
                     variable_name: None,
                 },
             },
@@ -443,6 +455,7 @@ mod tests {
                 metadata: EdgeMetadata {
                     call_count: None,
                     confidence: None,
+                    call_target: None,
                     variable_name: None,
                 },
             },
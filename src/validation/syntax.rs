@@ -67,23 +67,8 @@ impl SyntaxError {
 
     /// Convert byte offset to line and column
     fn line_column_from_node(node: &tree_sitter::Node<'_>, source: &[u8]) -> (usize, usize) {
-        let start = node.start_byte();
-        let mut line = 1;
-        let mut column = 1;
-
-        for (i, &byte) in source.iter().enumerate() {
-            if i == start {
-                break;
-            }
-            if byte == b'\n' {
-                line += 1;
-                column = 1;
-            } else {
-                column += 1;
-            }
-        }
-
-        (line, column)
+        let position = crate::parse::position::byte_to_position(source, node.start_byte());
+        (position.line, position.column)
     }
 }
 
@@ -121,6 +106,7 @@ impl SyntaxValidator {
             "lua" => Some(LanguageId::Lua),
             "scala" => Some(LanguageId::Scala),
             "c" => Some(LanguageId::C),
+            "objective-c" | "objc" => Some(LanguageId::ObjectiveC),
             "bash" => Some(LanguageId::Bash),
             "json" => Some(LanguageId::Json),
             _ => None,
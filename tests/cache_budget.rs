@@ -34,6 +34,7 @@ fn make_node(id: &str, content: &str, embedding: Vec<f32>) -> NodeInfo {
         complexity: (id.len() * 7 % 100) as u32, // deterministic complexity
         signature: None,
         pre_tokenized: None,
+        node_type: None,
     }
 }
 
@@ -49,13 +50,7 @@ fn search_ids(engine: &mut SearchEngine, query: &str) -> Vec<String> {
     let q = SearchQuery {
         query: query.to_string(),
         top_k: 100,
-        token_budget: None,
-        semantic: false,
-        expand_context: false,
-        query_embedding: None,
-        query_neural_embedding: None,
-        threshold: None,
-        query_type: None,
+        ..Default::default()
     };
     engine
         .search(q)
@@ -105,17 +100,9 @@ fn test_complexity_metadata_correct_after_compression() {
     }
 
     // Search results should include correct complexity
-    let mut engine = engine;
     let q = SearchQuery {
         query: "alpha".to_string(),
-        top_k: 10,
-        token_budget: None,
-        semantic: false,
-        expand_context: false,
-        query_embedding: None,
-        query_neural_embedding: None,
-        threshold: None,
-        query_type: None,
+        ..Default::default()
     };
     let results = engine.search(q).unwrap();
     let alpha_result = results.iter().find(|r| r.node_id == "alpha").unwrap();
@@ -391,20 +378,14 @@ fn test_search_cache_entry_limit() {
         })
         .collect();
 
-    let mut engine = make_engine(nodes);
+    let engine = make_engine(nodes);
 
     // Run more queries than the entry limit
     for i in 0..(SEARCH_CACHE_MAX_ENTRIES + 50) {
         let q = SearchQuery {
             query: format!("unique_query_{}", i),
             top_k: 5,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let _ = engine.search(q);
     }
@@ -430,20 +411,13 @@ fn test_search_cache_byte_limit() {
         })
         .collect();
 
-    let mut engine = make_engine(nodes);
+    let engine = make_engine(nodes);
 
     // Run many queries to fill cache
     for i in 0..500 {
         let q = SearchQuery {
             query: format!("query_{}", i),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
         let _ = engine.search(q);
     }
@@ -464,18 +438,11 @@ fn test_search_cache_returns_correct_results() {
         make_node("other", "fn other() { render(); }", vec![0.0, 1.0, 0.0]),
     ];
 
-    let mut engine = make_engine(nodes);
+    let engine = make_engine(nodes);
 
     let q = SearchQuery {
         query: "target".to_string(),
-        top_k: 10,
-        token_budget: None,
-        semantic: false,
-        expand_context: false,
-        query_embedding: None,
-        query_neural_embedding: None,
-        threshold: None,
-        query_type: None,
+        ..Default::default()
     };
 
     // First call (computed)
@@ -640,7 +607,7 @@ fn test_search_engine_exposes_cache_bytes() {
         make_node("b", "fn b() { render(); }", vec![0.0, 1.0, 0.0]),
     ];
 
-    let mut engine = make_engine(nodes);
+    let engine = make_engine(nodes);
 
     // Before any search, cache should be empty
     assert_eq!(engine.search_cache_bytes(), 0);
@@ -649,14 +616,7 @@ fn test_search_engine_exposes_cache_bytes() {
     // Run a search to populate cache
     let q = SearchQuery {
         query: "compute".to_string(),
-        top_k: 10,
-        token_budget: None,
-        semantic: false,
-        expand_context: false,
-        query_embedding: None,
-        query_neural_embedding: None,
-        threshold: None,
-        query_type: None,
+        ..Default::default()
     };
     let _ = engine.search(q);
 
@@ -12,9 +12,9 @@
 use leindex::cli::mcp::handlers::{
     ContextHandler, DeepAnalyzeHandler, DiagnosticsHandler, EditApplyHandler, EditPreviewHandler,
     FileSummaryHandler, GitStatusHandler, GrepSymbolsHandler, ImpactAnalysisHandler, IndexHandler,
-    PhaseAnalysisAliasHandler, PhaseAnalysisHandler, ProjectMapHandler, ReadFileHandler,
-    ReadSymbolHandler, RenameSymbolHandler, SearchHandler, SymbolLookupHandler, TextSearchHandler,
-    ToolHandler, WriteHandler,
+    ListSymbolsHandler, PhaseAnalysisAliasHandler, PhaseAnalysisHandler, ProjectMapHandler,
+    ReadFileHandler, ReadSymbolHandler, ReloadHandler, RenameSymbolHandler, SearchHandler,
+    SymbolLookupHandler, TextSearchHandler, ToolHandler, ValidateEditHandler, WriteHandler,
 };
 use leindex::cli::mcp::protocol::{JsonRpcRequest, JsonRpcResponse};
 use leindex::cli::mcp::server::{handle_tool_call, list_tools_json};
@@ -51,6 +51,9 @@ fn all_handlers() -> Vec<ToolHandler> {
         ToolHandler::TextSearch(TextSearchHandler),
         ToolHandler::ReadFile(ReadFileHandler),
         ToolHandler::GitStatus(GitStatusHandler),
+        ToolHandler::Reload(ReloadHandler),
+        ToolHandler::ListSymbols(ListSymbolsHandler),
+        ToolHandler::ValidateEdit(ValidateEditHandler),
     ]
 }
 
@@ -196,6 +199,9 @@ fn test_tools_list_all_expected_names_present() {
         "leindex.text-search",
         "leindex.read-file",
         "leindex.git-status",
+        "leindex.reload",
+        "leindex.list-symbols",
+        "leindex.validate-edit",
     ];
 
     for expected in &expected_names {
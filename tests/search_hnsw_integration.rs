@@ -35,6 +35,7 @@ mod tests {
                 complexity: (i % 10) as u32 + 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             });
         }
         nodes
@@ -236,6 +237,7 @@ mod tests {
                 complexity: (i % 10) as u32 + 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             });
         }
 
@@ -289,6 +291,7 @@ mod tests {
                 complexity: 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             });
         }
 
@@ -810,6 +813,7 @@ mod tests {
                 complexity: (i % 10) as u32 + 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             });
         }
         nodes
@@ -839,13 +843,9 @@ mod tests {
         let query = SearchQuery {
             query: "authenticate password verify".to_string(),
             top_k,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(query_embedding.clone()),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         // Run standard (non-staged) search
@@ -927,13 +927,7 @@ mod tests {
         let query = SearchQuery {
             query: "authenticate password".to_string(),
             top_k: 5,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let standard_results = engine.search(query.clone()).unwrap();
@@ -977,13 +971,9 @@ mod tests {
         let query = SearchQuery {
             query: "authenticate verify password".to_string(),
             top_k,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(query_embedding),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let staged_config = StagedRetrievalConfig::enabled_with_multiplier(coarse_multiplier);
@@ -1050,13 +1040,7 @@ mod tests {
         let query = SearchQuery {
             query: "authenticate".to_string(),
             top_k: 5,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let (_, metrics) = engine
@@ -1107,14 +1091,9 @@ mod tests {
 
         let query = SearchQuery {
             query: "authenticate".to_string(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(query_embedding),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let staged_config = StagedRetrievalConfig::enabled_with_multiplier(5);
@@ -1145,14 +1124,9 @@ mod tests {
 
         let query = SearchQuery {
             query: "authenticate".to_string(),
-            top_k: 10,
-            token_budget: None,
             semantic: true,
-            expand_context: false,
             query_embedding: Some(query_embedding),
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let staged_config = StagedRetrievalConfig::enabled_with_multiplier(5);
@@ -1170,14 +1144,7 @@ mod tests {
         let mut engine = SearchEngine::with_dimension(32);
         let query = SearchQuery {
             query: "test".to_string(),
-            top_k: 10,
-            token_budget: None,
-            semantic: false,
-            expand_context: false,
-            query_embedding: None,
-            query_neural_embedding: None,
-            threshold: None,
-            query_type: None,
+            ..Default::default()
         };
 
         let staged_config = StagedRetrievalConfig::enabled_with_multiplier(5);
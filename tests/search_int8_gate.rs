@@ -62,6 +62,7 @@ mod tests {
                 complexity: (i % 10) as u32 + 1,
                 signature: None,
                 pre_tokenized: None,
+                node_type: None,
             });
         }
         nodes
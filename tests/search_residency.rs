@@ -24,6 +24,7 @@ fn make_node(id: &str, content: &str, embedding: Vec<f32>) -> NodeInfo {
         complexity: 1,
         signature: None,
         pre_tokenized: None,
+        node_type: None,
     }
 }
 
@@ -39,13 +40,7 @@ fn search_ids(engine: &mut SearchEngine, query: &str) -> Vec<String> {
     let q = SearchQuery {
         query: query.to_string(),
         top_k: 100,
-        token_budget: None,
-        semantic: false,
-        expand_context: false,
-        query_embedding: None,
-        query_neural_embedding: None,
-        threshold: None,
-        query_type: None,
+        ..Default::default()
     };
     engine
         .search(q)
@@ -4,8 +4,8 @@
 // Validates: VAL-BPHASE-026, VAL-BPHASE-027, VAL-BPHASE-028, VAL-BPHASE-029
 
 use leindex::storage::schema::{
-    StorageConfig, StoragePool, DEFAULT_READER_POOL_SIZE, PROJECT_READER_CACHE_SIZE_KIB,
-    PROJECT_STORE_MMAP_SIZE, PROJECT_WRITER_CACHE_SIZE_KIB,
+    StorageConfig, StoragePool, DEFAULT_BUSY_TIMEOUT_MS, DEFAULT_READER_POOL_SIZE,
+    PROJECT_READER_CACHE_SIZE_KIB, PROJECT_STORE_MMAP_SIZE, PROJECT_WRITER_CACHE_SIZE_KIB,
 };
 use tempfile::TempDir;
 
@@ -23,6 +23,8 @@ fn writer_config() -> StorageConfig {
         wal_enabled: true,
         cache_size_kib: Some(PROJECT_WRITER_CACHE_SIZE_KIB),
         mmap_size: Some(PROJECT_STORE_MMAP_SIZE),
+        busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+        encryption_key: None,
     }
 }
 
@@ -32,6 +34,8 @@ fn reader_config() -> StorageConfig {
         wal_enabled: true,
         cache_size_kib: Some(PROJECT_READER_CACHE_SIZE_KIB),
         mmap_size: Some(PROJECT_STORE_MMAP_SIZE),
+        busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+        encryption_key: None,
     }
 }
 